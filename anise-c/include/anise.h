@@ -0,0 +1,74 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+#ifndef ANISE_H
+#define ANISE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Error codes returned by all of the functions, the message of the error of the last call of the calling thread is returned by anise_last_error.
+ * Internal panics are caught and returned as ANISE_OTHER. */
+typedef enum AniseErrorCode {
+    ANISE_OK = 0,
+    ANISE_NULL_POINTER = 1,
+    ANISE_INVALID_INPUT = 2,
+    ANISE_LOADING = 3,
+    ANISE_EPHEMERIS = 4,
+    ANISE_ORIENTATION = 5,
+    ANISE_OTHER = 6,
+} AniseErrorCode;
+
+/* Opaque Almanac, to be freed with anise_almanac_free. */
+typedef struct AniseAlmanac AniseAlmanac;
+
+/* Message of the error of the last call of the calling thread, or NULL if that call returned ANISE_OK.
+ * Owned by ANISE, valid until the next call of this thread. */
+const char *anise_last_error(void);
+
+/* Returns NULL if the Almanac cannot be initialized. */
+AniseAlmanac *anise_almanac_new(void);
+void anise_almanac_free(AniseAlmanac *almanac);
+
+/* Loads an SPK, BPC, or ANISE data set from a path or from bytes (which are copied). */
+AniseErrorCode anise_almanac_load(AniseAlmanac *almanac, const char *path);
+AniseErrorCode anise_almanac_load_bytes(AniseAlmanac *almanac, const uint8_t *data, size_t len);
+
+/* Converts an epoch string (e.g. "2024-01-01T00:00:00 UTC") into TDB seconds past J2000, the epoch of all other functions. */
+AniseErrorCode anise_epoch_from_str(const char *epoch, double *tdb_seconds_out);
+
+/* State [x, y, z, vx, vy, vz] (km, km/s) of the target with respect to the observer, in the observer orientation.
+ * ab_corr is a SPICE aberration flag (e.g. "LT+S") or NULL. */
+AniseErrorCode anise_transform(const AniseAlmanac *almanac,
+                               int32_t target_id,
+                               int32_t target_orientation_id,
+                               int32_t observer_id,
+                               int32_t observer_orientation_id,
+                               double epoch_tdb_seconds,
+                               const char *ab_corr,
+                               double state_out[6]);
+
+/* Row-major DCM from one orientation to another, and its time derivative if dcm_dt_out is not NULL. */
+AniseErrorCode anise_rotate(const AniseAlmanac *almanac,
+                            int32_t from_orientation_id,
+                            int32_t to_orientation_id,
+                            double epoch_tdb_seconds,
+                            double dcm_out[9],
+                            double dcm_dt_out[9]);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ANISE_H */
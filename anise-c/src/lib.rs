@@ -0,0 +1,473 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+//! C API of ANISE, cf. `include/anise.h`.
+//!
+//! Every function returns an [AniseErrorCode], and the message of the error of the last call of the calling thread
+//! is available with [anise_last_error]. Panics are caught and returned as [AniseErrorCode::Other].
+//!
+//! Epochs are given as TDB seconds past the J2000 reference epoch, as in SPICE; use [anise_epoch_from_str] to convert a string.
+
+use core::any::Any;
+use core::ffi::{c_char, CStr};
+use core::str::FromStr;
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::panic::{self, AssertUnwindSafe};
+
+use anise::errors::AlmanacError;
+use anise::prelude::{Aberration, Almanac, Frame};
+use bytes::Bytes;
+use hifitime::Epoch;
+
+/// Error codes returned by all of the functions of the C API.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AniseErrorCode {
+    Ok = 0,
+    /// A required pointer was null
+    NullPointer = 1,
+    /// A string was not valid UTF-8 or could not be parsed
+    InvalidInput = 2,
+    /// A file could not be read or its content could not be loaded
+    Loading = 3,
+    /// The ephemeris data does not support this query
+    Ephemeris = 4,
+    /// The orientation data does not support this query
+    Orientation = 5,
+    /// Any other error, including an internal panic
+    Other = 6,
+}
+
+impl From<&AlmanacError> for AniseErrorCode {
+    fn from(e: &AlmanacError) -> Self {
+        match e {
+            AlmanacError::Loading { .. } => Self::Loading,
+            AlmanacError::Ephemeris { .. } => Self::Ephemeris,
+            AlmanacError::Orientation { .. } => Self::Orientation,
+            _ => Self::Other,
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Stores the message of the error of the calling thread and returns its code.
+fn fail(code: AniseErrorCode, msg: impl ToString) -> AniseErrorCode {
+    let msg = msg.to_string().replace('\0', " ");
+    LAST_ERROR.with(|last| *last.borrow_mut() = CString::new(msg).ok());
+    code
+}
+
+/// Returns the message of a caught panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        format!("panic: {msg}")
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        format!("panic: {msg}")
+    } else {
+        "panic".to_string()
+    }
+}
+
+/// Runs the body of an exported function, since a panic must not unwind across the C boundary (e.g. on malformed bytes).
+/// A panic is returned as `Other` with its message, and the last error is cleared on success.
+fn ffi_guard(body: impl FnOnce() -> AniseErrorCode) -> AniseErrorCode {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(AniseErrorCode::Ok) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = None);
+            AniseErrorCode::Ok
+        }
+        Ok(code) => code,
+        Err(payload) => fail(AniseErrorCode::Other, panic_message(payload)),
+    }
+}
+
+fn almanac_error(e: AlmanacError) -> AniseErrorCode {
+    fail(AniseErrorCode::from(&e), e)
+}
+
+/// Reads a C string as a UTF-8 string slice.
+///
+/// # Safety
+/// The pointer must be null or point to a nul terminated string which outlives the returned slice.
+unsafe fn read_str<'a>(ptr: *const c_char) -> Result<&'a str, AniseErrorCode> {
+    if ptr.is_null() {
+        return Err(fail(AniseErrorCode::NullPointer, "string is null"));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|e| fail(AniseErrorCode::InvalidInput, e))
+}
+
+/// Returns the message of the error of the last call of the calling thread, or null if that call succeeded.
+/// The string is owned by ANISE and valid until the next call of the calling thread into the C API.
+#[no_mangle]
+pub extern "C" fn anise_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(core::ptr::null(), |msg| msg.as_ptr())
+    })
+}
+
+/// Initializes an empty Almanac, to be freed with `anise_almanac_free`. Returns null if it cannot be initialized.
+#[no_mangle]
+pub extern "C" fn anise_almanac_new() -> *mut Almanac {
+    let mut almanac = core::ptr::null_mut();
+    ffi_guard(|| {
+        almanac = Box::into_raw(Box::<Almanac>::default());
+        AniseErrorCode::Ok
+    });
+    almanac
+}
+
+/// Frees an Almanac initialized with `anise_almanac_new`. Null is ignored.
+///
+/// # Safety
+/// The pointer must be null or have been returned by `anise_almanac_new`, and must not be used afterward.
+#[no_mangle]
+pub unsafe extern "C" fn anise_almanac_free(almanac: *mut Almanac) {
+    ffi_guard(|| {
+        if !almanac.is_null() {
+            drop(Box::from_raw(almanac));
+        }
+        AniseErrorCode::Ok
+    });
+}
+
+/// Loads the SPK, BPC, or ANISE data set at the provided path into the Almanac. The kernel is identified by its path.
+///
+/// # Safety
+/// The almanac must have been returned by `anise_almanac_new`, and the path must be a nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn anise_almanac_load(
+    almanac: *mut Almanac,
+    path: *const c_char,
+) -> AniseErrorCode {
+    ffi_guard(|| {
+        let Some(almanac) = almanac.as_mut() else {
+            return fail(AniseErrorCode::NullPointer, "almanac is null");
+        };
+        let path = match read_str(path) {
+            Ok(path) => path,
+            Err(code) => return code,
+        };
+        match almanac.load(path) {
+            Ok(loaded) => {
+                *almanac = loaded;
+                AniseErrorCode::Ok
+            }
+            Err(e) => almanac_error(e),
+        }
+    })
+}
+
+/// Loads the provided bytes of an SPK, BPC, or ANISE data set into the Almanac. The bytes are copied.
+///
+/// # Safety
+/// The almanac must have been returned by `anise_almanac_new`, and the data must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn anise_almanac_load_bytes(
+    almanac: *mut Almanac,
+    data: *const u8,
+    len: usize,
+) -> AniseErrorCode {
+    ffi_guard(|| {
+        let Some(almanac) = almanac.as_mut() else {
+            return fail(AniseErrorCode::NullPointer, "almanac is null");
+        };
+        if data.is_null() {
+            return fail(AniseErrorCode::NullPointer, "data is null");
+        }
+        let bytes = Bytes::copy_from_slice(core::slice::from_raw_parts(data, len));
+        match almanac.load_from_bytes(bytes) {
+            Ok(loaded) => {
+                *almanac = loaded;
+                AniseErrorCode::Ok
+            }
+            Err(e) => almanac_error(e),
+        }
+    })
+}
+
+/// Converts an epoch string (e.g. "2024-01-01T00:00:00 UTC") into TDB seconds past J2000.
+///
+/// # Safety
+/// The epoch must be a nul terminated string, and the output must point to a writable double.
+#[no_mangle]
+pub unsafe extern "C" fn anise_epoch_from_str(
+    epoch: *const c_char,
+    tdb_seconds_out: *mut f64,
+) -> AniseErrorCode {
+    ffi_guard(|| {
+        let epoch = match read_str(epoch) {
+            Ok(epoch) => epoch,
+            Err(code) => return code,
+        };
+        let Some(out) = tdb_seconds_out.as_mut() else {
+            return fail(AniseErrorCode::NullPointer, "output is null");
+        };
+        match Epoch::from_str(epoch) {
+            Ok(epoch) => {
+                *out = epoch.to_tdb_seconds();
+                AniseErrorCode::Ok
+            }
+            Err(e) => fail(AniseErrorCode::InvalidInput, e),
+        }
+    })
+}
+
+/// Returns the state of the target frame with respect to the observer frame, in the orientation of the observer frame,
+/// as `[x, y, z, vx, vy, vz]` in km and km/s.
+///
+/// The aberration correction is one of the SPICE flags (e.g. "LT+S"), or null for none.
+///
+/// # Safety
+/// The almanac must have been returned by `anise_almanac_new`, the aberration correction must be null or a nul terminated string,
+/// and the output must point to six writable doubles.
+#[no_mangle]
+pub unsafe extern "C" fn anise_transform(
+    almanac: *const Almanac,
+    target_id: i32,
+    target_orientation_id: i32,
+    observer_id: i32,
+    observer_orientation_id: i32,
+    epoch_tdb_seconds: f64,
+    ab_corr: *const c_char,
+    state_out: *mut f64,
+) -> AniseErrorCode {
+    ffi_guard(|| {
+        let Some(almanac) = almanac.as_ref() else {
+            return fail(AniseErrorCode::NullPointer, "almanac is null");
+        };
+        if state_out.is_null() {
+            return fail(AniseErrorCode::NullPointer, "output is null");
+        }
+        let ab_corr = if ab_corr.is_null() {
+            None
+        } else {
+            match read_str(ab_corr).map(Aberration::new) {
+                Ok(Ok(ab_corr)) => ab_corr,
+                Ok(Err(e)) => return fail(AniseErrorCode::InvalidInput, e),
+                Err(code) => return code,
+            }
+        };
+
+        match almanac.transform(
+            Frame::new(target_id, target_orientation_id),
+            Frame::new(observer_id, observer_orientation_id),
+            Epoch::from_tdb_seconds(epoch_tdb_seconds),
+            ab_corr,
+        ) {
+            Ok(state) => {
+                let out = core::slice::from_raw_parts_mut(state_out, 6);
+                out.copy_from_slice(state.to_cartesian_pos_vel().as_slice());
+                AniseErrorCode::Ok
+            }
+            Err(e) => almanac_error(e),
+        }
+    })
+}
+
+/// Returns the direction cosine matrix rotating vectors from one orientation to another, in row-major order,
+/// and its time derivative if `dcm_dt_out` is not null (zero if the rotation is time-independent).
+///
+/// # Safety
+/// The almanac must have been returned by `anise_almanac_new`, and the outputs must be null (derivative only)
+/// or point to nine writable doubles.
+#[no_mangle]
+pub unsafe extern "C" fn anise_rotate(
+    almanac: *const Almanac,
+    from_orientation_id: i32,
+    to_orientation_id: i32,
+    epoch_tdb_seconds: f64,
+    dcm_out: *mut f64,
+    dcm_dt_out: *mut f64,
+) -> AniseErrorCode {
+    ffi_guard(|| {
+        let Some(almanac) = almanac.as_ref() else {
+            return fail(AniseErrorCode::NullPointer, "almanac is null");
+        };
+        if dcm_out.is_null() {
+            return fail(AniseErrorCode::NullPointer, "output is null");
+        }
+
+        match almanac.rotate(
+            Frame::new(0, from_orientation_id),
+            Frame::new(0, to_orientation_id),
+            Epoch::from_tdb_seconds(epoch_tdb_seconds),
+        ) {
+            Ok(dcm) => {
+                let out = core::slice::from_raw_parts_mut(dcm_out, 9);
+                out.copy_from_slice(dcm.rot_mat.transpose().as_slice());
+                if !dcm_dt_out.is_null() {
+                    let out = core::slice::from_raw_parts_mut(dcm_dt_out, 9);
+                    match dcm.rot_mat_dt {
+                        Some(rot_mat_dt) => out.copy_from_slice(rot_mat_dt.transpose().as_slice()),
+                        None => out.fill(0.0),
+                    }
+                }
+                AniseErrorCode::Ok
+            }
+            Err(e) => almanac_error(AlmanacError::Orientation {
+                action: "rotating",
+                source: Box::new(e),
+            }),
+        }
+    })
+}
+
+#[cfg(test)]
+mod ut_c_api {
+    use super::*;
+
+    #[test]
+    fn transform_and_rotate() {
+        unsafe {
+            let almanac = anise_almanac_new();
+            assert_eq!(
+                anise_almanac_load(almanac, c"../data/de440s.bsp".as_ptr()),
+                AniseErrorCode::Ok
+            );
+            assert_eq!(
+                anise_almanac_load(almanac, c"../data/pck08.pca".as_ptr()),
+                AniseErrorCode::Ok
+            );
+            assert_eq!(
+                anise_almanac_load(almanac, c"not a file".as_ptr()),
+                AniseErrorCode::Loading
+            );
+            assert!(!anise_last_error().is_null());
+            // The error is cleared by the next successful call
+            let mut epoch = 0.0;
+            assert_eq!(
+                anise_epoch_from_str(c"2024-01-01T00:00:00 UTC".as_ptr(), &mut epoch),
+                AniseErrorCode::Ok
+            );
+            assert!(anise_last_error().is_null());
+
+            // Malformed bytes are an error, not a crash of the host process
+            let garbage = [0xAB_u8; 1536];
+            assert_ne!(
+                anise_almanac_load_bytes(almanac, garbage.as_ptr(), garbage.len()),
+                AniseErrorCode::Ok
+            );
+            assert!(!anise_last_error().is_null());
+
+            // Moon with respect to the Earth, in J2000
+            let mut state = [0.0; 6];
+            assert_eq!(
+                anise_transform(
+                    almanac,
+                    301,
+                    1,
+                    399,
+                    1,
+                    epoch,
+                    core::ptr::null(),
+                    state.as_mut_ptr()
+                ),
+                AniseErrorCode::Ok
+            );
+            let expected = (*almanac)
+                .translate(
+                    Frame::from_ephem_j2000(301),
+                    Frame::from_ephem_j2000(399),
+                    Epoch::from_tdb_seconds(epoch),
+                    None,
+                )
+                .unwrap();
+            assert_eq!(state[0], expected.radius_km.x);
+            assert_eq!(state[5], expected.velocity_km_s.z);
+
+            assert_eq!(
+                anise_transform(
+                    almanac,
+                    301,
+                    1,
+                    399,
+                    1,
+                    epoch,
+                    c"not a flag".as_ptr(),
+                    state.as_mut_ptr()
+                ),
+                AniseErrorCode::InvalidInput
+            );
+
+            // J2000 to the IAU Earth frame
+            let mut dcm = [0.0; 9];
+            let mut dcm_dt = [0.0; 9];
+            assert_eq!(
+                anise_rotate(
+                    almanac,
+                    1,
+                    399,
+                    epoch,
+                    dcm.as_mut_ptr(),
+                    dcm_dt.as_mut_ptr()
+                ),
+                AniseErrorCode::Ok
+            );
+            let expected = (*almanac)
+                .rotate(
+                    Frame::new(0, 1),
+                    Frame::new(0, 399),
+                    Epoch::from_tdb_seconds(epoch),
+                )
+                .unwrap();
+            // Row-major
+            assert_eq!(dcm[1], expected.rot_mat[(0, 1)]);
+            assert_eq!(dcm[3], expected.rot_mat[(1, 0)]);
+            assert!(dcm_dt.iter().any(|x| *x != 0.0));
+
+            assert_eq!(
+                anise_rotate(
+                    almanac,
+                    1,
+                    399,
+                    epoch,
+                    core::ptr::null_mut(),
+                    core::ptr::null_mut()
+                ),
+                AniseErrorCode::NullPointer
+            );
+            // No Earth ITRF93 orientation loaded
+            assert_eq!(
+                anise_rotate(
+                    almanac,
+                    1,
+                    3000,
+                    epoch,
+                    dcm.as_mut_ptr(),
+                    core::ptr::null_mut()
+                ),
+                AniseErrorCode::Orientation
+            );
+
+            anise_almanac_free(almanac);
+        }
+    }
+
+    #[test]
+    fn panic_is_caught() {
+        assert_eq!(
+            ffi_guard(|| panic!("malformed kernel")),
+            AniseErrorCode::Other
+        );
+        let msg = unsafe { CStr::from_ptr(anise_last_error()) };
+        assert_eq!(msg.to_str().unwrap(), "panic: malformed kernel");
+
+        assert_eq!(ffi_guard(|| AniseErrorCode::Ok), AniseErrorCode::Ok);
+        assert!(anise_last_error().is_null());
+    }
+}
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use clap::{Args, Parser, Subcommand};
-use hifitime::Epoch;
+use hifitime::{Duration, Epoch};
 
 #[derive(Parser, Debug)]
 #[clap(name="ANISE", author="Rabotin and ANISE contributors", version, about, long_about = None)]
@@ -10,7 +10,7 @@ pub struct CliArgs {
     pub action: Actions,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Subcommand)]
+#[derive(Debug, PartialEq, PartialOrd, Subcommand)]
 pub enum Actions {
     /// Checks the integrity of the file
     Check {
@@ -40,13 +40,54 @@ pub enum Actions {
         /// Output ANISE binary file
         outfile: PathBuf,
     },
+    /// Convert a CSV of time-tagged quaternions (`epoch,w,x,y,z`), e.g. from an ADCS simulator, into an Euler Parameter ANISE dataset
+    ConvertQuaternionCsv(ConvertQuaternionCsv),
     /// Truncate the segment of the provided ID of the input NAIF DAF file to the provided start and end epochs
     /// Limitation: this may not work correctly if there are several segments with the same ID.
-    /// Only works with Chebyshev Type 2 data types (i.e. planetary ephemerides).
+    /// Only works with Chebyshev Type 2 and Type 3 data types (i.e. planetary ephemerides).
     TruncDAFById(TruncateById),
+    /// Re-fit the Chebyshev segment of the provided ID of the input NAIF DAF file with longer records and the lowest degree within
+    /// the maximum error, to shrink the file while bounding the loss of accuracy.
+    /// Limitation: this may not work correctly if there are several segments with the same ID.
+    /// Only works with Chebyshev Type 2 and Type 3 data types (i.e. planetary ephemerides).
+    ReduceDAFById(ReduceById),
     /// Remove the segment of the provided ID of the input NAIF DAF file.
     /// Limitation: this may not work correctly if there are several segments with the same ID.
     RmDAFById(RmById),
+    /// Merge several DAF files of the same kind (all SPK or all BPC) into a single file.
+    /// Segments of the same ID and center must not overlap in time across the input files.
+    /// Limitation: the merged file has a single summary record, so it holds at most 25 segments and larger merges are rejected.
+    Merge(Merge),
+    /// Numerically compare two DAF files of the same kind by sampling both over the overlapping domain of each segment.
+    /// Reports the max and RMS position and velocity differences for SPKs, and rotation angle and rate differences for BPCs.
+    Diff(Diff),
+    /// Extract the segments of the provided ID into a new DAF file, optionally only those covering a time window.
+    /// Segments are copied as is, without truncation.
+    Extract(Extract),
+    /// Query the state of a target with respect to an observer over a time range, and export it as CSV (or Parquet with the `parquet` feature)
+    Query(Query),
+    /// Query the rotation from one orientation to another, either at a single epoch or over a time range exported as CSV
+    Rotate(Rotate),
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Args)]
+pub(crate) struct ConvertQuaternionCsv {
+    /// Path to the CSV file
+    pub csvfile: PathBuf,
+    /// Output ANISE binary file
+    pub outfile: PathBuf,
+    /// ID of the frame the quaternions rotate from
+    #[clap(long)]
+    pub from: i32,
+    /// ID of the frame the quaternions rotate to
+    #[clap(long)]
+    pub to: i32,
+    /// Resample the quaternions with this step, e.g. `1 min` (defaults to the epochs of the CSV)
+    #[clap(long)]
+    pub step: Option<Duration>,
+    /// Maximum deviation of the norm of each quaternion from one
+    #[clap(long, default_value_t = 1e-6)]
+    pub norm_tolerance: f64,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
@@ -71,4 +112,124 @@ pub(crate) struct TruncateById {
     pub start: Option<Epoch>,
     /// New end epoch of the segment
     pub end: Option<Epoch>,
+    /// Split each record of a Chebyshev Type 3 segment into this many records before truncating it, to trim closer to the requested epochs
+    #[clap(long)]
+    pub split: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Args)]
+pub(crate) struct ReduceById {
+    /// Input DAF file, SPK or BPC
+    pub input: PathBuf,
+    /// Output DAF file path
+    pub output: PathBuf,
+    /// ID of the segment to re-fit
+    pub id: i32,
+    /// Maximum error of the re-fitted segment, in km for SPKs and in radians for BPCs
+    #[clap(long)]
+    pub max_error: f64,
+    /// Number of records of the segment merged into each re-fitted record
+    #[clap(long, default_value_t = 1)]
+    pub interval_multiple: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Merge {
+    /// Input DAF files, all SPK or all BPC
+    #[clap(required = true, num_args = 2..)]
+    pub inputs: Vec<PathBuf>,
+    /// Output DAF file path
+    #[clap(short, long)]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Diff {
+    /// Reference DAF file, SPK or BPC
+    pub reference: PathBuf,
+    /// DAF file to compare with the reference
+    pub other: PathBuf,
+    /// Only compare the segments of this ID (defaults to all of the segments of the reference file)
+    #[clap(long)]
+    pub id: Option<i32>,
+    /// Sampling step, e.g. `1 h`
+    #[clap(long)]
+    pub step: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Extract {
+    /// Input DAF file, SPK or BPC
+    pub input: PathBuf,
+    /// ID of the segments to extract
+    #[clap(long)]
+    pub id: i32,
+    /// Output DAF file path
+    #[clap(short, long)]
+    pub output: PathBuf,
+    /// Only extract the segments with data after this epoch
+    #[clap(long)]
+    pub start: Option<Epoch>,
+    /// Only extract the segments with data before this epoch
+    #[clap(long)]
+    pub end: Option<Epoch>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Query {
+    /// Kernels to load (SPK, BPC, or ANISE datasets)
+    #[clap(required = true)]
+    pub kernels: Vec<PathBuf>,
+    /// Target body, as a NAIF ID or a name (e.g. `Moon` or `301`)
+    #[clap(long)]
+    pub target: String,
+    /// Observer body, as a NAIF ID or a name (e.g. `Earth` or `399`)
+    #[clap(long)]
+    pub observer: String,
+    /// Orientation of the returned states, as a NAIF ID or a name
+    #[clap(long, default_value = "J2000")]
+    pub frame: String,
+    /// Aberration correction (e.g. `LT+S`), defaults to none
+    #[clap(long)]
+    pub aberration: Option<String>,
+    /// Start epoch of the query
+    #[clap(long)]
+    pub start: Epoch,
+    /// End epoch of the query (inclusive)
+    #[clap(long)]
+    pub end: Epoch,
+    /// Step between two states, e.g. `1 h`
+    #[clap(long)]
+    pub step: Duration,
+    /// Output CSV file, or Parquet file if its extension is `.parquet` (requires the `parquet` feature), prints CSV to the standard output if not set
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Args)]
+pub(crate) struct Rotate {
+    /// Kernels to load (BPC, or ANISE datasets)
+    #[clap(required = true)]
+    pub kernels: Vec<PathBuf>,
+    /// Orientation to rotate from, as a NAIF ID or a name (e.g. `ITRF93` or `3000`)
+    #[clap(long)]
+    pub from: String,
+    /// Orientation to rotate into, as a NAIF ID or a name (e.g. `J2000` or `1`)
+    #[clap(long)]
+    pub to: String,
+    /// Single epoch to print the rotation at
+    #[clap(long)]
+    pub epoch: Option<Epoch>,
+    /// Start epoch of the sweep
+    #[clap(long)]
+    pub start: Option<Epoch>,
+    /// End epoch of the sweep (inclusive)
+    #[clap(long)]
+    pub end: Option<Epoch>,
+    /// Step of the sweep, e.g. `1 h`
+    #[clap(long)]
+    pub step: Option<Duration>,
+    /// Output CSV file of the sweep, prints to the standard output if not set
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
 }
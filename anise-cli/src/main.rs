@@ -1,17 +1,23 @@
 extern crate pretty_env_logger;
 use std::collections::HashSet;
 use std::env::{set_var, var};
-use std::io;
-use std::path::PathBuf;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 
+use anise::constants::celestial_objects::celestial_name_from_id;
+use anise::constants::orientations::id_to_orientation_name;
+use anise::ephemerides::EphemerisError;
+use anise::errors::{AlmanacError, PhysicsError};
 use anise::math::interpolation::InterpolationError;
-use anise::naif::daf::datatypes::Type2ChebyshevSet;
-use anise::naif::daf::{DafDataType, NAIFDataSet, DAF};
+use anise::math::rotation::Quaternion;
+use anise::naif::daf::datatypes::{Type2ChebyshevSet, Type3ChebyshevSet};
+use anise::naif::daf::{DAFBuilder, DafDataType, NAIFDataSet, DAF};
 use anise::naif::pck::BPCSummaryRecord;
 use anise::naif::pretty_print::NAIFPrettyPrint;
 use anise::naif::spk::summary::SPKSummaryRecord;
 use bytes::Bytes;
 use clap::Parser;
+use indicatif::{ProgressBar, ProgressStyle};
 use log::info;
 use snafu::prelude::*;
 use zerocopy::FromBytes;
@@ -19,15 +25,20 @@ use zerocopy::FromBytes;
 use anise::file2heap;
 use anise::naif::daf::{file_record::FileRecordError, DAFError, FileRecord, NAIFRecord};
 use anise::naif::kpl::parser::{convert_fk, convert_tpc};
+use anise::orientations::OrientationError;
 use anise::prelude::*;
 use anise::structure::dataset::{DataSetError, DataSetType};
 use anise::structure::metadata::Metadata;
-use anise::structure::{EulerParameterDataSet, PlanetaryDataSet, SpacecraftDataSet};
+use anise::structure::{
+    EulerParameterDataSet, LocationDataSet, PlanetaryDataSet, SpacecraftDataSet,
+};
 
 mod args;
 use args::{Actions, CliArgs};
 
 const LOG_VAR: &str = "ANISE_LOG";
+const PARQUET_FEATURE_REQUIRED: &str =
+    "exporting to Parquet requires building anise-cli with the `parquet` feature";
 
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
@@ -62,6 +73,25 @@ pub enum CliErrors {
     SegmentInterpolation {
         source: InterpolationError,
     },
+    CliEphemeris {
+        source: EphemerisError,
+    },
+    CliOrientation {
+        source: OrientationError,
+    },
+    CliAlmanac {
+        source: AlmanacError,
+    },
+    CliPhysics {
+        source: PhysicsError,
+    },
+    /// Two segments of the same ID and center from different files overlap in time
+    MergeConflict {
+        id: i32,
+        center_id: i32,
+        first: PathBuf,
+        second: PathBuf,
+    },
 }
 
 fn main() -> Result<(), CliErrors> {
@@ -109,6 +139,13 @@ fn main() -> Result<(), CliErrors> {
                         println!("{dataset}");
                         Ok(())
                     }
+                    DataSetType::LocationData => {
+                        // Decode as location data
+                        let dataset =
+                            LocationDataSet::try_from_bytes(bytes).context(CliDataSetSnafu)?;
+                        println!("{dataset}");
+                        Ok(())
+                    }
                 }
             } else {
                 // Load the header only
@@ -159,6 +196,22 @@ fn main() -> Result<(), CliErrors> {
 
             Ok(())
         }
+        Actions::ConvertQuaternionCsv(action) => {
+            let dataset = EulerParameterDataSet::from_quaternion_csv(
+                action.csvfile,
+                action.from,
+                action.to,
+                action.step,
+                action.norm_tolerance,
+            )
+            .context(CliDataSetSnafu)?;
+
+            dataset
+                .save_as(&action.outfile, false)
+                .context(CliDataSetSnafu)?;
+
+            Ok(())
+        }
         Actions::TruncDAFById(action) => {
             ensure!(
                 action.start.is_some() || action.end.is_some(),
@@ -177,6 +230,17 @@ fn main() -> Result<(), CliErrors> {
                 }),
             }
         }
+        Actions::ReduceDAFById(action) => {
+            let (bytes, file_record) = read_and_record(action.input.clone())?;
+
+            match file_record.identification().context(CliFileRecordSnafu)? {
+                "PCK" => reduce_daf_by_id::<BPCSummaryRecord>(action, bytes),
+                "SPK" => reduce_daf_by_id::<SPKSummaryRecord>(action, bytes),
+                fileid => Err(CliErrors::ArgumentError {
+                    arg: format!("{fileid} is not supported yet"),
+                }),
+            }
+        }
         Actions::RmDAFById(action) => {
             let (bytes, file_record) = read_and_record(action.input.clone())?;
 
@@ -188,6 +252,66 @@ fn main() -> Result<(), CliErrors> {
                 }),
             }
         }
+        Actions::Merge(action) => {
+            let mut fileids = Vec::with_capacity(action.inputs.len());
+            for input in &action.inputs {
+                let (_, file_record) = read_and_record(input.clone())?;
+                fileids.push(
+                    file_record
+                        .identification()
+                        .context(CliFileRecordSnafu)?
+                        .to_string(),
+                );
+            }
+
+            ensure!(
+                fileids.iter().all(|fileid| fileid == &fileids[0]),
+                ArgumentSnafu {
+                    arg: format!("cannot merge DAF files of different kinds: {fileids:?}")
+                }
+            );
+
+            match fileids[0].as_str() {
+                "PCK" => merge_daf::<BPCSummaryRecord>(action),
+                "SPK" => merge_daf::<SPKSummaryRecord>(action),
+                fileid => Err(CliErrors::ArgumentError {
+                    arg: format!("{fileid} is not supported yet"),
+                }),
+            }
+        }
+        Actions::Diff(action) => {
+            let (ref_bytes, ref_record) = read_and_record(action.reference.clone())?;
+            let (other_bytes, other_record) = read_and_record(action.other.clone())?;
+
+            let fileid = ref_record.identification().context(CliFileRecordSnafu)?;
+            ensure!(
+                other_record.identification().context(CliFileRecordSnafu)? == fileid,
+                ArgumentSnafu {
+                    arg: "cannot compare DAF files of different kinds"
+                }
+            );
+
+            match fileid {
+                "PCK" => diff_bpc(action, ref_bytes, other_bytes),
+                "SPK" => diff_spk(action, ref_bytes, other_bytes),
+                fileid => Err(CliErrors::ArgumentError {
+                    arg: format!("{fileid} is not supported yet"),
+                }),
+            }
+        }
+        Actions::Extract(action) => {
+            let (bytes, file_record) = read_and_record(action.input.clone())?;
+
+            match file_record.identification().context(CliFileRecordSnafu)? {
+                "PCK" => extract_daf::<BPCSummaryRecord>(action, bytes),
+                "SPK" => extract_daf::<SPKSummaryRecord>(action, bytes),
+                fileid => Err(CliErrors::ArgumentError {
+                    arg: format!("{fileid} is not supported yet"),
+                }),
+            }
+        }
+        Actions::Query(action) => query(action),
+        Actions::Rotate(action) => rotate(action),
     }
 }
 
@@ -251,6 +375,7 @@ fn truncate_daf_by_id<R>(
         id,
         start,
         end,
+        split,
     }: args::TruncateById,
     bytes: Bytes,
 ) -> Result<(), CliErrors>
@@ -273,32 +398,738 @@ where
         error: Box::new(err),
     })?;
     ensure!(
-                        data_type == DafDataType::Type2ChebyshevTriplet,
-                        ArgumentSnafu {
-                            arg: format!("{input:?} is of type {data_type:?}, but operation is only valid for Type2ChebyshevTriplet")
-                        }
-                    );
-
-    let segment = fmt
-        .nth_data::<Type2ChebyshevSet>(idx)
-        .context(CliDAFSnafu)?;
+        split.is_none() || data_type == DafDataType::Type3ChebyshevSextuplet,
+        ArgumentSnafu {
+            arg: format!("{input:?} is of type {data_type:?}, but record splitting is only valid for Type3ChebyshevSextuplet")
+        }
+    );
 
-    let updated_segment = segment
-        .truncate(summary, start, end)
-        .context(SegmentInterpolationSnafu)?;
+    let new_start = start.unwrap_or_else(|| summary.start_epoch());
+    let new_end = end.unwrap_or_else(|| summary.end_epoch());
 
     let mut my_pck_mut = fmt.to_mutable();
-    assert!(my_pck_mut
-        .set_nth_data(
-            idx,
-            updated_segment,
-            start.unwrap_or_else(|| summary.start_epoch()),
-            end.unwrap_or_else(|| summary.end_epoch()),
-        )
-        .is_ok());
+    match data_type {
+        DafDataType::Type2ChebyshevTriplet => {
+            let segment = fmt
+                .nth_data::<Type2ChebyshevSet>(idx)
+                .context(CliDAFSnafu)?;
+
+            let updated_segment = segment
+                .truncate(summary, start, end)
+                .context(SegmentInterpolationSnafu)?;
+
+            my_pck_mut
+                .set_nth_data(idx, updated_segment, new_start, new_end)
+                .context(CliDAFSnafu)?;
+        }
+        DafDataType::Type3ChebyshevSextuplet => {
+            let segment = fmt
+                .nth_data::<Type3ChebyshevSet>(idx)
+                .context(CliDAFSnafu)?;
+
+            // Splitting the records first allows trimming closer to the requested epochs.
+            let split_data = match split {
+                Some(splits) => Some(
+                    segment
+                        .split_records(splits)
+                        .context(SegmentInterpolationSnafu)?,
+                ),
+                None => None,
+            };
+            let segment = match &split_data {
+                Some(data) => Type3ChebyshevSet::from_f64_slice(data).map_err(|source| {
+                    CliErrors::SegmentInterpolation {
+                        source: InterpolationError::InterpDecoding { source },
+                    }
+                })?,
+                None => segment,
+            };
+
+            let updated_segment = segment
+                .truncate(summary, start, end)
+                .context(SegmentInterpolationSnafu)?;
+
+            my_pck_mut
+                .set_nth_data(idx, updated_segment, new_start, new_end)
+                .context(CliDAFSnafu)?;
+        }
+        _ => {
+            return Err(CliErrors::ArgumentError {
+                arg: format!("{input:?} is of type {data_type:?}, but operation is only valid for Type2ChebyshevTriplet and Type3ChebyshevSextuplet"),
+            })
+        }
+    }
 
     info!("Saving file to {output:?}");
     my_pck_mut.persist(output).context(FilePersistSnafu)?;
 
     Ok(())
 }
+
+fn reduce_daf_by_id<R>(
+    args::ReduceById {
+        input,
+        output,
+        id,
+        max_error,
+        interval_multiple,
+    }: args::ReduceById,
+    bytes: Bytes,
+) -> Result<(), CliErrors>
+where
+    R: NAIFSummaryRecord,
+{
+    info!("Loading {input:?}");
+    let fmt = DAF::<R>::parse(bytes).context(CliDAFSnafu)?;
+
+    let (summary, idx) = fmt.summary_from_id(id).context(CliDAFSnafu)?;
+
+    let data_type = summary.data_type().map_err(|err| CliErrors::CliDataType {
+        error: Box::new(err),
+    })?;
+
+    let decoding_err = |source| CliErrors::SegmentInterpolation {
+        source: InterpolationError::InterpDecoding { source },
+    };
+
+    let mut my_fmt_mut = fmt.to_mutable();
+    let refit = match data_type {
+        DafDataType::Type2ChebyshevTriplet => {
+            let segment = fmt
+                .nth_data::<Type2ChebyshevSet>(idx)
+                .context(CliDAFSnafu)?;
+            info!("Original segment: {} records with degree {}", segment.num_records, segment.degree());
+
+            let refit = segment
+                .reduce(interval_multiple, max_error)
+                .context(SegmentInterpolationSnafu)?;
+            let updated_segment =
+                Type2ChebyshevSet::from_f64_slice(&refit.data).map_err(decoding_err)?;
+            my_fmt_mut
+                .set_nth_data(idx, updated_segment, summary.start_epoch(), summary.end_epoch())
+                .context(CliDAFSnafu)?;
+            refit
+        }
+        DafDataType::Type3ChebyshevSextuplet => {
+            let segment = fmt
+                .nth_data::<Type3ChebyshevSet>(idx)
+                .context(CliDAFSnafu)?;
+            info!("Original segment: {} records with degree {}", segment.num_records, segment.degree());
+
+            let refit = segment
+                .reduce(interval_multiple, max_error)
+                .context(SegmentInterpolationSnafu)?;
+            let updated_segment =
+                Type3ChebyshevSet::from_f64_slice(&refit.data).map_err(decoding_err)?;
+            my_fmt_mut
+                .set_nth_data(idx, updated_segment, summary.start_epoch(), summary.end_epoch())
+                .context(CliDAFSnafu)?;
+            refit
+        }
+        _ => {
+            return Err(CliErrors::ArgumentError {
+                arg: format!("{input:?} is of type {data_type:?}, but operation is only valid for Type2ChebyshevTriplet and Type3ChebyshevSextuplet"),
+            })
+        }
+    };
+
+    info!("Re-fitted segment: {refit}");
+
+    info!("Saving file to {output:?}");
+    my_fmt_mut.persist(output).context(FilePersistSnafu)?;
+
+    Ok(())
+}
+
+fn merge_daf<R>(args::Merge { inputs, output }: args::Merge) -> Result<(), CliErrors>
+where
+    R: NAIFSummaryRecord,
+{
+    let mut comments = String::from("Merged by ANISE from:\n");
+    for input in &inputs {
+        comments.push_str(&format!("{}\n", input.display()));
+    }
+
+    let mut files = Vec::with_capacity(inputs.len());
+    for input in &inputs {
+        info!("Loading {input:?}");
+        let (bytes, _) = read_and_record(input.clone())?;
+        files.push(DAF::<R>::parse(bytes).context(CliDAFSnafu)?);
+    }
+
+    // The merged file has a single summary record, so check its capacity before copying any data.
+    let mut num_segments = 0;
+    for fmt in &files {
+        num_segments += fmt
+            .data_summaries()
+            .context(CliDAFSnafu)?
+            .iter()
+            .filter(|summary| !summary.is_empty())
+            .count();
+    }
+    ensure!(
+        num_segments <= DAFBuilder::<R>::max_segments(),
+        ArgumentSnafu {
+            arg: format!(
+                "the inputs have {num_segments} segments in total, but a merged file holds at most {} segments",
+                DAFBuilder::<R>::max_segments()
+            )
+        }
+    );
+
+    let mut builder = DAFBuilder::<R>::new("ANISE MERGE").with_comments(&comments);
+    // Keep track of which file each segment came from to report conflicts.
+    let mut merged: Vec<(R, usize)> = Vec::new();
+
+    for (input_no, (input, fmt)) in inputs.iter().zip(&files).enumerate() {
+        for (idx, summary) in fmt
+            .data_summaries()
+            .context(CliDAFSnafu)?
+            .iter()
+            .enumerate()
+        {
+            if summary.is_empty() {
+                continue;
+            }
+
+            // Segments of a single file may overlap by design: the last one takes precedence, as in SPICE.
+            if let Some((_, first_no)) = merged.iter().find(|(other, other_no)| {
+                *other_no != input_no
+                    && other.id() == summary.id()
+                    && other.center_id() == summary.center_id()
+                    && other.start_epoch() < summary.end_epoch()
+                    && summary.start_epoch() < other.end_epoch()
+            }) {
+                return Err(CliErrors::MergeConflict {
+                    id: summary.id(),
+                    center_id: summary.center_id(),
+                    first: inputs[*first_no].clone(),
+                    second: input.clone(),
+                });
+            }
+
+            let name = fmt.nth_name(idx).context(CliDAFSnafu)?;
+            let data = fmt.nth_data_raw(idx).context(CliDAFSnafu)?;
+            info!(
+                "Adding segment `{name}` (ID {}) from {input:?}",
+                summary.id()
+            );
+            builder
+                .add_segment(*summary, &name, data.to_vec())
+                .context(CliDAFSnafu)?;
+            merged.push((*summary, input_no));
+        }
+    }
+
+    info!("Saving {} segments to {output:?}", builder.len());
+    builder
+        .build()
+        .context(CliDAFSnafu)?
+        .persist(output)
+        .context(FilePersistSnafu)?;
+
+    Ok(())
+}
+
+/// Returns the overlapping domain of two segments, if any.
+fn overlap(ref_domain: (Epoch, Epoch), other_domain: (Epoch, Epoch)) -> Option<(Epoch, Epoch)> {
+    let start = ref_domain.0.max(other_domain.0);
+    let end = ref_domain.1.min(other_domain.1);
+    (start < end).then_some((start, end))
+}
+
+/// Returns the maximum and RMS of the provided errors.
+fn max_rms(errors: &[f64]) -> (f64, f64) {
+    let max = errors.iter().copied().fold(0.0, f64::max);
+    let rms = (errors.iter().map(|e| e.powi(2)).sum::<f64>() / errors.len() as f64).sqrt();
+    (max, rms)
+}
+
+fn diff_spk(
+    args::Diff {
+        reference,
+        other,
+        id,
+        step,
+    }: args::Diff,
+    ref_bytes: Bytes,
+    other_bytes: Bytes,
+) -> Result<(), CliErrors> {
+    let ref_spk = SPK::parse(ref_bytes).context(CliDAFSnafu)?;
+    let ref_almanac = Almanac::from_spk(ref_spk.clone()).context(CliEphemerisSnafu)?;
+    let other_almanac = Almanac::from_spk(SPK::parse(other_bytes).context(CliDAFSnafu)?)
+        .context(CliEphemerisSnafu)?;
+
+    info!("Comparing {other:?} to {reference:?} every {step}");
+
+    for (idx, summary) in ref_spk
+        .data_summaries()
+        .context(CliDAFSnafu)?
+        .iter()
+        .enumerate()
+    {
+        if summary.is_empty() || id.is_some_and(|id| id != summary.target_id) {
+            continue;
+        }
+
+        let name = ref_spk.nth_name(idx).context(CliDAFSnafu)?;
+        let other_domain = match other_almanac.spk_domain(summary.target_id) {
+            Ok(domain) => domain,
+            Err(_) => {
+                println!("{name} (ID {}): not in {other:?}", summary.target_id);
+                continue;
+            }
+        };
+
+        let Some((start, end)) =
+            overlap((summary.start_epoch(), summary.end_epoch()), other_domain)
+        else {
+            println!("{name} (ID {}): no overlapping domain", summary.target_id);
+            continue;
+        };
+
+        let mut pos_errs_km = Vec::new();
+        let mut vel_errs_km_s = Vec::new();
+        for epoch in TimeSeries::inclusive(start, end, step) {
+            let ref_state = ref_almanac
+                .translate_geometric(summary.target_frame(), summary.center_frame(), epoch)
+                .context(CliEphemerisSnafu)?;
+            let other_state = other_almanac
+                .translate_geometric(summary.target_frame(), summary.center_frame(), epoch)
+                .context(CliEphemerisSnafu)?;
+
+            pos_errs_km.push((ref_state.radius_km - other_state.radius_km).norm());
+            vel_errs_km_s.push((ref_state.velocity_km_s - other_state.velocity_km_s).norm());
+        }
+
+        let (max_pos_km, rms_pos_km) = max_rms(&pos_errs_km);
+        let (max_vel_km_s, rms_vel_km_s) = max_rms(&vel_errs_km_s);
+
+        println!(
+            "{name} (ID {}) from {start} to {end} ({} samples)\n\tposition: max = {max_pos_km:e} km\tRMS = {rms_pos_km:e} km\n\tvelocity: max = {max_vel_km_s:e} km/s\tRMS = {rms_vel_km_s:e} km/s",
+            summary.target_id,
+            pos_errs_km.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn diff_bpc(
+    args::Diff {
+        reference,
+        other,
+        id,
+        step,
+    }: args::Diff,
+    ref_bytes: Bytes,
+    other_bytes: Bytes,
+) -> Result<(), CliErrors> {
+    let ref_bpc = BPC::parse(ref_bytes).context(CliDAFSnafu)?;
+    let ref_almanac = Almanac::from_bpc(ref_bpc.clone()).context(CliOrientationSnafu)?;
+    let other_almanac = Almanac::from_bpc(BPC::parse(other_bytes).context(CliDAFSnafu)?)
+        .context(CliOrientationSnafu)?;
+
+    info!("Comparing {other:?} to {reference:?} every {step}");
+
+    for (idx, summary) in ref_bpc
+        .data_summaries()
+        .context(CliDAFSnafu)?
+        .iter()
+        .enumerate()
+    {
+        if summary.is_empty() || id.is_some_and(|id| id != summary.frame_id) {
+            continue;
+        }
+
+        let name = ref_bpc.nth_name(idx).context(CliDAFSnafu)?;
+        let other_domain = match other_almanac.bpc_domain(summary.frame_id) {
+            Ok(domain) => domain,
+            Err(_) => {
+                println!("{name} (ID {}): not in {other:?}", summary.frame_id);
+                continue;
+            }
+        };
+
+        let Some((start, end)) =
+            overlap((summary.start_epoch(), summary.end_epoch()), other_domain)
+        else {
+            println!("{name} (ID {}): no overlapping domain", summary.frame_id);
+            continue;
+        };
+
+        let from_frame = Frame::from_orient_ssb(summary.inertial_frame_id);
+        let to_frame = Frame::from_orient_ssb(summary.frame_id);
+
+        let mut angle_errs_rad = Vec::new();
+        let mut rate_errs = Vec::new();
+        for epoch in TimeSeries::inclusive(start, end, step) {
+            let ref_dcm = ref_almanac
+                .rotate(from_frame, to_frame, epoch)
+                .context(CliOrientationSnafu)?;
+            let other_dcm = other_almanac
+                .rotate(from_frame, to_frame, epoch)
+                .context(CliOrientationSnafu)?;
+
+            // Angle of the rotation between both DCMs
+            let delta = ref_dcm.rot_mat * other_dcm.rot_mat.transpose();
+            angle_errs_rad.push(((delta.trace() - 1.0) / 2.0).clamp(-1.0, 1.0).acos());
+
+            if let (Some(ref_dt), Some(other_dt)) = (ref_dcm.rot_mat_dt, other_dcm.rot_mat_dt) {
+                rate_errs.push((ref_dt - other_dt).norm());
+            }
+        }
+
+        let (max_angle_rad, rms_angle_rad) = max_rms(&angle_errs_rad);
+        let (max_rate, rms_rate) = max_rms(&rate_errs);
+
+        println!(
+            "{name} (ID {}) from {start} to {end} ({} samples)\n\tangle: max = {max_angle_rad:e} rad\tRMS = {rms_angle_rad:e} rad\n\tDCM rate: max = {max_rate:e} 1/s\tRMS = {rms_rate:e} 1/s",
+            summary.frame_id,
+            angle_errs_rad.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn extract_daf<R>(
+    args::Extract {
+        input,
+        id,
+        output,
+        start,
+        end,
+    }: args::Extract,
+    bytes: Bytes,
+) -> Result<(), CliErrors>
+where
+    R: NAIFSummaryRecord,
+{
+    info!("Loading {input:?}");
+    let fmt = DAF::<R>::parse(bytes).context(CliDAFSnafu)?;
+
+    let mut builder = DAFBuilder::<R>::new("ANISE EXTRACT").with_comments(&format!(
+        "Extracted by ANISE from {} (ID {id})",
+        input.display()
+    ));
+
+    for (idx, summary) in fmt
+        .data_summaries()
+        .context(CliDAFSnafu)?
+        .iter()
+        .enumerate()
+    {
+        if summary.is_empty()
+            || summary.id() != id
+            || start.is_some_and(|start| summary.end_epoch() < start)
+            || end.is_some_and(|end| summary.start_epoch() > end)
+        {
+            continue;
+        }
+
+        let name = fmt.nth_name(idx).context(CliDAFSnafu)?;
+        let data = fmt.nth_data_raw(idx).context(CliDAFSnafu)?;
+        info!("Extracting segment `{name}`: {summary:?}");
+        builder
+            .add_segment(*summary, &name, data.to_vec())
+            .context(CliDAFSnafu)?;
+    }
+
+    ensure!(
+        !builder.is_empty(),
+        ArgumentSnafu {
+            arg: format!("no segment of ID {id} found in {input:?} for the requested time window")
+        }
+    );
+
+    info!("Saving {} segments to {output:?}", builder.len());
+    builder
+        .build()
+        .context(CliDAFSnafu)?
+        .persist(output)
+        .context(FilePersistSnafu)?;
+
+    Ok(())
+}
+
+/// Loads all of the provided kernels into a new Almanac.
+fn load_almanac(kernels: &[PathBuf]) -> Result<Almanac, CliErrors> {
+    let mut almanac = Almanac::default();
+    for kernel in kernels {
+        info!("Loading {kernel:?}");
+        almanac = almanac
+            .load(&kernel.to_string_lossy())
+            .context(CliAlmanacSnafu)?;
+    }
+    Ok(almanac)
+}
+
+/// Returns the NAIF ID of a body provided either as an integer or as its name, case insensitive.
+fn body_id(name: &str) -> Result<i32, CliErrors> {
+    if let Ok(id) = name.parse() {
+        return Ok(id);
+    }
+    // All of the named bodies have IDs below 1000 (barycenters, planets, and their moons).
+    (0..1000)
+        .find(|id| {
+            celestial_name_from_id(*id).is_some_and(|known| known.eq_ignore_ascii_case(name))
+        })
+        .ok_or(CliErrors::ArgumentError {
+            arg: format!("unknown body `{name}`, use its NAIF ID instead"),
+        })
+}
+
+/// Returns the NAIF ID of an orientation provided either as an integer or as its name, case insensitive.
+fn orientation_id(name: &str) -> Result<i32, CliErrors> {
+    if let Ok(id) = name.parse() {
+        return Ok(id);
+    }
+    id_to_orientation_name(name)
+        .or_else(|_| id_to_orientation_name(&name.to_uppercase()))
+        .map_err(|_| CliErrors::ArgumentError {
+            arg: format!("unknown orientation `{name}`, use its NAIF ID instead"),
+        })
+}
+
+/// Returns a progress bar over the provided number of epochs when the output is a file, and a hidden one when the output is printed.
+fn progress_bar(to_file: bool, len: usize) -> ProgressBar {
+    if !to_file {
+        return ProgressBar::hidden();
+    }
+    let progress = ProgressBar::new(len as u64);
+    if let Ok(style) =
+        ProgressStyle::with_template("{bar:40} {pos}/{len} epochs [{elapsed_precise} < {eta}]")
+    {
+        progress.set_style(style);
+    }
+    progress
+}
+
+/// Returns whether the provided output path has a `.parquet` extension.
+fn is_parquet(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"))
+}
+
+/// Writes the provided states to a Parquet file, with the same columns as the CSV export.
+#[cfg(feature = "parquet")]
+fn write_states_parquet(path: &Path, states: &[Orbit]) -> Result<(), CliErrors> {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Float64Array, StringArray};
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+
+    let column = |component: fn(&Orbit) -> f64| -> ArrayRef {
+        Arc::new(Float64Array::from_iter_values(states.iter().map(component)))
+    };
+
+    let batch = RecordBatch::try_from_iter(vec![
+        (
+            "epoch",
+            Arc::new(StringArray::from_iter_values(
+                states.iter().map(|state| state.epoch.to_string()),
+            )) as ArrayRef,
+        ),
+        ("x_km", column(|state| state.radius_km.x)),
+        ("y_km", column(|state| state.radius_km.y)),
+        ("z_km", column(|state| state.radius_km.z)),
+        ("vx_km_s", column(|state| state.velocity_km_s.x)),
+        ("vy_km_s", column(|state| state.velocity_km_s.y)),
+        ("vz_km_s", column(|state| state.velocity_km_s.z)),
+    ])
+    .map_err(io::Error::other)
+    .context(FilePersistSnafu)?;
+
+    let file = File::create(path).context(FilePersistSnafu)?;
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None)
+        .map_err(io::Error::other)
+        .context(FilePersistSnafu)?;
+    writer
+        .write(&batch)
+        .map_err(io::Error::other)
+        .context(FilePersistSnafu)?;
+    writer
+        .close()
+        .map_err(io::Error::other)
+        .context(FilePersistSnafu)?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_states_parquet(_path: &Path, _states: &[Orbit]) -> Result<(), CliErrors> {
+    Err(CliErrors::ArgumentError {
+        arg: PARQUET_FEATURE_REQUIRED.to_string(),
+    })
+}
+
+fn query(
+    args::Query {
+        kernels,
+        target,
+        observer,
+        frame,
+        aberration,
+        start,
+        end,
+        step,
+        output,
+    }: args::Query,
+) -> Result<(), CliErrors> {
+    let almanac = load_almanac(&kernels)?;
+
+    let orientation = orientation_id(&frame)?;
+    let target_frame = Frame::new(body_id(&target)?, orientation);
+    let observer_frame = Frame::new(body_id(&observer)?, orientation);
+    let ab_corr = match aberration {
+        Some(flag) => Aberration::new(&flag).context(CliPhysicsSnafu)?,
+        None => None,
+    };
+
+    let epochs = TimeSeries::inclusive(start, end, step).collect::<Vec<Epoch>>();
+    let progress = progress_bar(output.is_some(), epochs.len());
+
+    if let Some(path) = output.as_ref().filter(|path| is_parquet(path)) {
+        ensure!(
+            cfg!(feature = "parquet"),
+            ArgumentSnafu {
+                arg: PARQUET_FEATURE_REQUIRED
+            }
+        );
+        let mut states = Vec::with_capacity(epochs.len());
+        for epoch in epochs {
+            progress.inc(1);
+            states.push(
+                almanac
+                    .transform(target_frame, observer_frame, epoch, ab_corr)
+                    .context(CliAlmanacSnafu)?,
+            );
+        }
+        write_states_parquet(path, &states)?;
+        progress.finish_and_clear();
+        info!("States of {target_frame:e} with respect to {observer_frame:e} saved to {path:?}");
+        return Ok(());
+    }
+
+    let mut writer: Box<dyn Write> = match &output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).context(FilePersistSnafu)?,
+        )),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    writeln!(writer, "epoch,x_km,y_km,z_km,vx_km_s,vy_km_s,vz_km_s").context(FilePersistSnafu)?;
+
+    for epoch in epochs {
+        progress.inc(1);
+        let state = almanac
+            .transform(target_frame, observer_frame, epoch, ab_corr)
+            .context(CliAlmanacSnafu)?;
+        writeln!(
+            writer,
+            "{epoch},{},{},{},{},{},{}",
+            state.radius_km.x,
+            state.radius_km.y,
+            state.radius_km.z,
+            state.velocity_km_s.x,
+            state.velocity_km_s.y,
+            state.velocity_km_s.z
+        )
+        .context(FilePersistSnafu)?;
+    }
+
+    writer.flush().context(FilePersistSnafu)?;
+    progress.finish_and_clear();
+
+    if let Some(path) = output {
+        info!("States of {target_frame:e} with respect to {observer_frame:e} saved to {path:?}");
+    }
+
+    Ok(())
+}
+
+fn rotate(
+    args::Rotate {
+        kernels,
+        from,
+        to,
+        epoch,
+        start,
+        end,
+        step,
+        output,
+    }: args::Rotate,
+) -> Result<(), CliErrors> {
+    let almanac = load_almanac(&kernels)?;
+
+    let from_frame = Frame::from_orient_ssb(orientation_id(&from)?);
+    let to_frame = Frame::from_orient_ssb(orientation_id(&to)?);
+
+    if let Some(epoch) = epoch {
+        let dcm = almanac
+            .rotate(from_frame, to_frame, epoch)
+            .context(CliOrientationSnafu)?;
+        let omega_rad_s = almanac
+            .angular_velocity(from_frame, to_frame, epoch)
+            .context(CliOrientationSnafu)?;
+        println!("{epoch}");
+        println!("{dcm}");
+        println!("{}", Quaternion::from(dcm));
+        println!(
+            "Angular velocity (rad/s): [{:e}, {:e}, {:e}]",
+            omega_rad_s.x, omega_rad_s.y, omega_rad_s.z
+        );
+        return Ok(());
+    }
+
+    let (Some(start), Some(end), Some(step)) = (start, end, step) else {
+        return Err(CliErrors::ArgumentError {
+            arg: "you must provide either EPOCH, or START, END, and STEP".to_string(),
+        });
+    };
+
+    let mut writer: Box<dyn Write> = match &output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).context(FilePersistSnafu)?,
+        )),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    writeln!(
+        writer,
+        "epoch,q_w,q_x,q_y,q_z,omega_x_rad_s,omega_y_rad_s,omega_z_rad_s"
+    )
+    .context(FilePersistSnafu)?;
+
+    let epochs = TimeSeries::inclusive(start, end, step).collect::<Vec<Epoch>>();
+    let progress = progress_bar(output.is_some(), epochs.len());
+
+    for epoch in epochs {
+        progress.inc(1);
+        let q = Quaternion::from(
+            almanac
+                .rotate(from_frame, to_frame, epoch)
+                .context(CliOrientationSnafu)?,
+        );
+        let omega_rad_s = almanac
+            .angular_velocity(from_frame, to_frame, epoch)
+            .context(CliOrientationSnafu)?;
+        writeln!(
+            writer,
+            "{epoch},{},{},{},{},{},{},{}",
+            q.w, q.x, q.y, q.z, omega_rad_s.x, omega_rad_s.y, omega_rad_s.z
+        )
+        .context(FilePersistSnafu)?;
+    }
+
+    writer.flush().context(FilePersistSnafu)?;
+    progress.finish_and_clear();
+
+    if let Some(path) = output {
+        info!("Rotations from {from_frame:o} to {to_frame:o} saved to {path:?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,297 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::str::FromStr;
+
+use anise::prelude::{Aberration, Almanac, Frame, Orbit};
+use hifitime::Epoch;
+
+#[cxx::bridge(namespace = "anise")]
+mod ffi {
+    /// A frame, defined by its ephemeris and orientation NAIF IDs.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct CxxFrame {
+        ephemeris_id: i32,
+        orientation_id: i32,
+    }
+
+    extern "Rust" {
+        type CxxAlmanac;
+        type CxxOrbit;
+
+        fn new_almanac() -> Box<CxxAlmanac>;
+        fn load(self: &mut CxxAlmanac, path: &str) -> Result<()>;
+        fn transform(
+            self: &CxxAlmanac,
+            target: CxxFrame,
+            observer: CxxFrame,
+            epoch_tdb_seconds: f64,
+            ab_corr: &str,
+        ) -> Result<Box<CxxOrbit>>;
+        fn rotate(
+            self: &CxxAlmanac,
+            from: CxxFrame,
+            to: CxxFrame,
+            epoch_tdb_seconds: f64,
+        ) -> Result<Vec<f64>>;
+        fn orbit(
+            self: &CxxAlmanac,
+            state: &[f64],
+            epoch_tdb_seconds: f64,
+            frame: CxxFrame,
+        ) -> Result<Box<CxxOrbit>>;
+
+        fn epoch_from_str(epoch: &str) -> Result<f64>;
+
+        fn frame(self: &CxxOrbit) -> CxxFrame;
+        fn epoch_tdb_seconds(self: &CxxOrbit) -> f64;
+        fn state(self: &CxxOrbit) -> Vec<f64>;
+        fn rmag_km(self: &CxxOrbit) -> f64;
+        fn vmag_km_s(self: &CxxOrbit) -> f64;
+        fn sma_km(self: &CxxOrbit) -> Result<f64>;
+        fn ecc(self: &CxxOrbit) -> Result<f64>;
+        fn inc_deg(self: &CxxOrbit) -> Result<f64>;
+        fn raan_deg(self: &CxxOrbit) -> Result<f64>;
+        fn aop_deg(self: &CxxOrbit) -> Result<f64>;
+        fn ta_deg(self: &CxxOrbit) -> Result<f64>;
+        fn periapsis_km(self: &CxxOrbit) -> Result<f64>;
+        fn apoapsis_km(self: &CxxOrbit) -> Result<f64>;
+    }
+}
+
+pub use ffi::CxxFrame;
+
+impl From<CxxFrame> for Frame {
+    fn from(frame: CxxFrame) -> Self {
+        Frame::new(frame.ephemeris_id, frame.orientation_id)
+    }
+}
+
+impl From<Frame> for CxxFrame {
+    fn from(frame: Frame) -> Self {
+        Self {
+            ephemeris_id: frame.ephemeris_id,
+            orientation_id: frame.orientation_id,
+        }
+    }
+}
+
+/// Converts an epoch string (e.g. "2024-01-01T00:00:00 UTC") into TDB seconds past J2000, the epoch representation of the C++ API.
+pub fn epoch_from_str(epoch: &str) -> Result<f64, String> {
+    Epoch::from_str(epoch)
+        .map(|epoch| epoch.to_tdb_seconds())
+        .map_err(|e| e.to_string())
+}
+
+/// An Almanac for C++, where kernels are loaded in place.
+#[derive(Default)]
+pub struct CxxAlmanac {
+    inner: Almanac,
+}
+
+pub fn new_almanac() -> Box<CxxAlmanac> {
+    Box::default()
+}
+
+impl CxxAlmanac {
+    /// Loads the SPK, BPC, or ANISE data set at the provided path.
+    pub fn load(&mut self, path: &str) -> Result<(), String> {
+        self.inner = self.inner.load(path).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Returns the frame with its planetary data (e.g. its gravitational parameter) if it is loaded.
+    fn frame_info(&self, frame: CxxFrame) -> Frame {
+        let frame = Frame::from(frame);
+        self.inner.frame_from_uid(frame).unwrap_or(frame)
+    }
+
+    /// Returns the state of the target with respect to the observer, in the observer frame, optionally corrected
+    /// for aberration (e.g. "LT+S", or an empty string for none).
+    pub fn transform(
+        &self,
+        target: CxxFrame,
+        observer: CxxFrame,
+        epoch_tdb_seconds: f64,
+        ab_corr: &str,
+    ) -> Result<Box<CxxOrbit>, String> {
+        let ab_corr = if ab_corr.is_empty() {
+            None
+        } else {
+            Aberration::new(ab_corr).map_err(|e| e.to_string())?
+        };
+
+        let mut state = self
+            .inner
+            .transform(
+                target.into(),
+                observer.into(),
+                Epoch::from_tdb_seconds(epoch_tdb_seconds),
+                ab_corr,
+            )
+            .map_err(|e| e.to_string())?;
+        state.frame = self.frame_info(observer);
+
+        Ok(Box::new(CxxOrbit { inner: state }))
+    }
+
+    /// Returns the direction cosine matrix rotating vectors from one frame to another, as 9 numbers in row-major order.
+    pub fn rotate(
+        &self,
+        from: CxxFrame,
+        to: CxxFrame,
+        epoch_tdb_seconds: f64,
+    ) -> Result<Vec<f64>, String> {
+        let dcm = self
+            .inner
+            .rotate(
+                from.into(),
+                to.into(),
+                Epoch::from_tdb_seconds(epoch_tdb_seconds),
+            )
+            .map_err(|e| e.to_string())?;
+
+        Ok(dcm.rot_mat.transpose().iter().copied().collect())
+    }
+
+    /// Builds an orbit from its state `[x, y, z, vx, vy, vz]` (km, km/s), with the planetary data of the frame if it is loaded.
+    pub fn orbit(
+        &self,
+        state: &[f64],
+        epoch_tdb_seconds: f64,
+        frame: CxxFrame,
+    ) -> Result<Box<CxxOrbit>, String> {
+        let [x, y, z, vx, vy, vz] = <[f64; 6]>::try_from(state)
+            .map_err(|_| format!("expected six state components, got {}", state.len()))?;
+
+        Ok(Box::new(CxxOrbit {
+            inner: Orbit::new(
+                x,
+                y,
+                z,
+                vx,
+                vy,
+                vz,
+                Epoch::from_tdb_seconds(epoch_tdb_seconds),
+                self.frame_info(frame),
+            ),
+        }))
+    }
+}
+
+/// An orbit for C++. The orbital elements require the gravitational parameter of its frame.
+pub struct CxxOrbit {
+    inner: Orbit,
+}
+
+impl CxxOrbit {
+    pub fn frame(&self) -> CxxFrame {
+        self.inner.frame.into()
+    }
+
+    pub fn epoch_tdb_seconds(&self) -> f64 {
+        self.inner.epoch.to_tdb_seconds()
+    }
+
+    /// Returns `[x, y, z, vx, vy, vz]` in km and km/s.
+    pub fn state(&self) -> Vec<f64> {
+        self.inner.to_cartesian_pos_vel().iter().copied().collect()
+    }
+
+    pub fn rmag_km(&self) -> f64 {
+        self.inner.rmag_km()
+    }
+
+    pub fn vmag_km_s(&self) -> f64 {
+        self.inner.vmag_km_s()
+    }
+
+    pub fn sma_km(&self) -> Result<f64, String> {
+        self.inner.sma_km().map_err(|e| e.to_string())
+    }
+
+    pub fn ecc(&self) -> Result<f64, String> {
+        self.inner.ecc().map_err(|e| e.to_string())
+    }
+
+    pub fn inc_deg(&self) -> Result<f64, String> {
+        self.inner.inc_deg().map_err(|e| e.to_string())
+    }
+
+    pub fn raan_deg(&self) -> Result<f64, String> {
+        self.inner.raan_deg().map_err(|e| e.to_string())
+    }
+
+    pub fn aop_deg(&self) -> Result<f64, String> {
+        self.inner.aop_deg().map_err(|e| e.to_string())
+    }
+
+    pub fn ta_deg(&self) -> Result<f64, String> {
+        self.inner.ta_deg().map_err(|e| e.to_string())
+    }
+
+    pub fn periapsis_km(&self) -> Result<f64, String> {
+        self.inner.periapsis_km().map_err(|e| e.to_string())
+    }
+
+    pub fn apoapsis_km(&self) -> Result<f64, String> {
+        self.inner.apoapsis_km().map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod ut_cpp {
+    use super::*;
+
+    #[test]
+    fn almanac_and_orbit() {
+        let mut almanac = new_almanac();
+        almanac.load("../data/de440s.bsp").unwrap();
+        almanac.load("../data/pck08.pca").unwrap();
+        assert!(almanac.load("not a file").is_err());
+
+        let epoch = epoch_from_str("2024-01-01T00:00:00 UTC").unwrap();
+        let moon = CxxFrame {
+            ephemeris_id: 301,
+            orientation_id: 1,
+        };
+        let earth = CxxFrame {
+            ephemeris_id: 399,
+            orientation_id: 1,
+        };
+
+        let orbit = almanac.transform(moon, earth, epoch, "").unwrap();
+        assert_eq!(orbit.frame(), earth);
+        assert_eq!(orbit.epoch_tdb_seconds(), epoch);
+        assert!((orbit.rmag_km() - 384_000.0).abs() < 25_000.0);
+        // The Earth GM is loaded from the planetary constants
+        let ecc = orbit.ecc().unwrap();
+        assert!(ecc > 0.0 && ecc < 0.1, "{ecc}");
+
+        let copy = almanac.orbit(&orbit.state(), epoch, earth).unwrap();
+        assert_eq!(copy.sma_km().unwrap(), orbit.sma_km().unwrap());
+        assert!(almanac.orbit(&[1.0, 2.0], epoch, earth).is_err());
+        assert!(almanac.transform(moon, earth, epoch, "bad").is_err());
+
+        let dcm = almanac
+            .rotate(
+                earth,
+                CxxFrame {
+                    ephemeris_id: 399,
+                    orientation_id: 399,
+                },
+                epoch,
+            )
+            .unwrap();
+        assert_eq!(dcm.len(), 9);
+        // Rotation about the Earth pole, close to Z
+        assert!((dcm[8] - 1.0).abs() < 1e-3);
+    }
+}
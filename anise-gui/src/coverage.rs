@@ -0,0 +1,272 @@
+use anise::naif::daf::NAIFSummaryRecord;
+use anise::prelude::Almanac;
+use anise::NaifId;
+use egui::{Align2, Color32, FontId, Pos2, Rect, Sense, Stroke, Vec2};
+use hifitime::{Epoch, TimeScale};
+
+const COLORS: [Color32; 6] = [
+    Color32::from_rgb(31, 119, 180),
+    Color32::from_rgb(255, 127, 14),
+    Color32::from_rgb(44, 160, 44),
+    Color32::from_rgb(148, 103, 189),
+    Color32::from_rgb(140, 86, 75),
+    Color32::from_rgb(23, 190, 207),
+];
+const GAP_COLOR: Color32 = Color32::from_rgb(214, 39, 40);
+const OVERLAP_COLOR: Color32 = Color32::from_rgb(255, 221, 87);
+
+const LABEL_WIDTH: f32 = 180.0;
+const ROW_HEIGHT: f32 = 22.0;
+
+/// A segment of a loaded kernel.
+struct Segment {
+    kernel_no: usize,
+    start: Epoch,
+    end: Epoch,
+}
+
+/// All of the segments of a given NAIF ID in the loaded kernels of one kind.
+struct Row {
+    kind: &'static str,
+    id: NaifId,
+    segments: Vec<Segment>,
+}
+
+impl Row {
+    /// Intervals covered by segments of at least two different kernels.
+    fn overlaps(&self) -> Vec<(Epoch, Epoch)> {
+        let mut overlaps = Vec::new();
+        for (i, first) in self.segments.iter().enumerate() {
+            for second in self.segments.iter().skip(i + 1) {
+                if first.kernel_no == second.kernel_no {
+                    continue;
+                }
+                let start = first.start.max(second.start);
+                let end = first.end.min(second.end);
+                if start < end {
+                    overlaps.push((start, end));
+                }
+            }
+        }
+        overlaps
+    }
+
+    /// Intervals between the first and last epochs of this ID which no segment covers.
+    fn gaps(&self) -> Vec<(Epoch, Epoch)> {
+        let mut sorted = self
+            .segments
+            .iter()
+            .map(|seg| (seg.start, seg.end))
+            .collect::<Vec<_>>();
+        sorted.sort();
+
+        let mut gaps = Vec::new();
+        let mut covered_until = match sorted.first() {
+            Some((_, end)) => *end,
+            None => return gaps,
+        };
+        for (start, end) in sorted.into_iter().skip(1) {
+            if start > covered_until {
+                gaps.push((covered_until, start));
+            }
+            covered_until = covered_until.max(end);
+        }
+        gaps
+    }
+}
+
+fn rows_of<R: NAIFSummaryRecord>(kind: &'static str, kernels: Vec<&[R]>, rows: &mut Vec<Row>) {
+    for (kernel_no, summaries) in kernels.into_iter().enumerate() {
+        for summary in summaries.iter().filter(|summary| !summary.is_empty()) {
+            let segment = Segment {
+                kernel_no,
+                start: summary.start_epoch(),
+                end: summary.end_epoch(),
+            };
+            match rows
+                .iter_mut()
+                .find(|row| row.kind == kind && row.id == summary.id())
+            {
+                Some(row) => row.segments.push(segment),
+                None => rows.push(Row {
+                    kind,
+                    id: summary.id(),
+                    segments: vec![segment],
+                }),
+            }
+        }
+    }
+}
+
+fn kernel_name(aliases: &[Option<String>], kernel_no: usize) -> String {
+    match aliases.get(kernel_no).and_then(|alias| alias.as_deref()) {
+        Some(alias) => alias.to_string(),
+        None => format!("#{kernel_no}"),
+    }
+}
+
+/// Shows a Gantt-style timeline of the coverage of each NAIF ID in all of the loaded SPK and BPC kernels,
+/// highlighting the gaps in red and the overlaps between kernels in yellow.
+pub fn coverage_ui(ui: &mut egui::Ui, almanac: &Almanac, selected_time_scale: TimeScale) {
+    let mut rows = Vec::new();
+    rows_of(
+        "SPK",
+        almanac
+            .spk_data
+            .iter()
+            .take(almanac.num_loaded_spk())
+            .map(|spk| spk.as_ref().unwrap().data_summaries().unwrap_or_default())
+            .collect(),
+        &mut rows,
+    );
+    rows_of(
+        "BPC",
+        almanac
+            .bpc_data
+            .iter()
+            .take(almanac.num_loaded_bpc())
+            .map(|bpc| bpc.as_ref().unwrap().data_summaries().unwrap_or_default())
+            .collect(),
+        &mut rows,
+    );
+    rows.sort_by_key(|row| (row.kind, row.id));
+
+    let Some(start) = rows
+        .iter()
+        .flat_map(|row| row.segments.iter().map(|seg| seg.start))
+        .min()
+    else {
+        ui.label("No SPK or BPC loaded");
+        return;
+    };
+    let end = rows
+        .iter()
+        .flat_map(|row| row.segments.iter().map(|seg| seg.end))
+        .max()
+        .unwrap();
+    let span_s = (end - start).to_seconds().max(1.0);
+
+    // Legend
+    ui.horizontal_wrapped(|ui| {
+        for (kind, aliases, count) in [
+            ("SPK", &almanac.spk_aliases[..], almanac.num_loaded_spk()),
+            ("BPC", &almanac.bpc_aliases[..], almanac.num_loaded_bpc()),
+        ] {
+            for kernel_no in 0..count {
+                ui.colored_label(
+                    COLORS[kernel_no % COLORS.len()],
+                    format!("■ {kind} {}", kernel_name(aliases, kernel_no)),
+                );
+            }
+        }
+        ui.colored_label(GAP_COLOR, "■ gap");
+        ui.colored_label(OVERLAP_COLOR, "■ overlap");
+    });
+
+    let width = ui.available_width().max(LABEL_WIDTH + 200.0);
+    let height = ROW_HEIGHT * (rows.len() as f32 + 1.0);
+    let (response, painter) = ui.allocate_painter(Vec2::new(width, height), Sense::hover());
+    let rect = response.rect;
+    let bar_left = rect.left() + LABEL_WIDTH;
+    let bar_width = rect.right() - bar_left - 10.0;
+    let x_of = |epoch: Epoch| -> f32 {
+        bar_left + bar_width * ((epoch - start).to_seconds() / span_s) as f32
+    };
+
+    let text_color = ui.visuals().text_color();
+    let hover_pos = response.hover_pos();
+    let mut tooltip = None;
+
+    for (row_no, row) in rows.iter().enumerate() {
+        let top = rect.top() + ROW_HEIGHT * row_no as f32;
+        let row_rect = Rect::from_min_max(
+            Pos2::new(rect.left(), top),
+            Pos2::new(rect.right(), top + ROW_HEIGHT),
+        );
+        if row_no % 2 == 0 {
+            painter.rect_filled(row_rect, 0.0, ui.visuals().faint_bg_color);
+        }
+        painter.text(
+            Pos2::new(rect.left() + 4.0, top + ROW_HEIGHT / 2.0),
+            Align2::LEFT_CENTER,
+            format!("{} {}", row.kind, row.id),
+            FontId::monospace(12.0),
+            text_color,
+        );
+
+        let bar = |from: Epoch, to: Epoch, shrink: f32| {
+            Rect::from_min_max(
+                Pos2::new(x_of(from), top + shrink),
+                Pos2::new(x_of(to).max(x_of(from) + 1.0), top + ROW_HEIGHT - shrink),
+            )
+        };
+
+        let aliases = if row.kind == "SPK" {
+            &almanac.spk_aliases[..]
+        } else {
+            &almanac.bpc_aliases[..]
+        };
+
+        for seg in &row.segments {
+            painter.rect_filled(
+                bar(seg.start, seg.end, 4.0),
+                2.0,
+                COLORS[seg.kernel_no % COLORS.len()],
+            );
+        }
+        for (from, to) in row.gaps() {
+            painter.rect_filled(bar(from, to, 8.0), 0.0, GAP_COLOR);
+        }
+        for (from, to) in row.overlaps() {
+            painter.rect_stroke(bar(from, to, 2.0), 0.0, Stroke::new(1.5, OVERLAP_COLOR));
+        }
+
+        if hover_pos.is_some_and(|pos| row_rect.contains(pos)) {
+            let mut text = format!("{} {}", row.kind, row.id);
+            for seg in &row.segments {
+                text.push_str(&format!(
+                    "\n{}: {} to {}",
+                    kernel_name(aliases, seg.kernel_no),
+                    seg.start.to_gregorian_str(selected_time_scale),
+                    seg.end.to_gregorian_str(selected_time_scale)
+                ));
+            }
+            for (from, to) in row.gaps() {
+                text.push_str(&format!(
+                    "\ngap: {} to {}",
+                    from.to_gregorian_str(selected_time_scale),
+                    to.to_gregorian_str(selected_time_scale)
+                ));
+            }
+            tooltip = Some(text);
+        }
+    }
+
+    // Time axis
+    let axis_y = rect.top() + ROW_HEIGHT * rows.len() as f32;
+    painter.line_segment(
+        [
+            Pos2::new(bar_left, axis_y),
+            Pos2::new(bar_left + bar_width, axis_y),
+        ],
+        Stroke::new(1.0, text_color),
+    );
+    painter.text(
+        Pos2::new(bar_left, axis_y + 2.0),
+        Align2::LEFT_TOP,
+        start.to_gregorian_str(selected_time_scale),
+        FontId::monospace(11.0),
+        text_color,
+    );
+    painter.text(
+        Pos2::new(bar_left + bar_width, axis_y + 2.0),
+        Align2::RIGHT_TOP,
+        end.to_gregorian_str(selected_time_scale),
+        FontId::monospace(11.0),
+        text_color,
+    );
+
+    if let Some(text) = tooltip {
+        response.on_hover_text_at_pointer(text);
+    }
+}
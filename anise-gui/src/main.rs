@@ -6,9 +6,11 @@ mod ui;
 use ui::UiApp;
 
 mod bpc;
+mod coverage;
 mod epa;
 mod pca;
 mod spk;
+mod trajectory;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
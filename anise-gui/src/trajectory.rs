@@ -0,0 +1,294 @@
+use anise::constants::celestial_objects::celestial_name_from_id;
+use anise::math::Vector3;
+use anise::prelude::{Almanac, Frame};
+use anise::NaifId;
+use std::f32::consts::PI;
+
+use egui::{Align2, Color32, FontId, Pos2, Sense, Shape, Stroke, Vec2};
+use hifitime::{Epoch, TimeScale};
+
+const COLORS: [Color32; 6] = [
+    Color32::from_rgb(31, 119, 180),
+    Color32::from_rgb(255, 127, 14),
+    Color32::from_rgb(44, 160, 44),
+    Color32::from_rgb(214, 39, 40),
+    Color32::from_rgb(148, 103, 189),
+    Color32::from_rgb(23, 190, 207),
+];
+
+/// Sampled trajectories, recomputed only when the selection changes.
+struct Tracks {
+    key: (NaifId, Vec<NaifId>, usize),
+    start: Epoch,
+    end: Epoch,
+    positions: Vec<(NaifId, Vec<Option<Vector3>>)>,
+}
+
+/// State of the 3D view of the trajectories of the selected SPK IDs around a central body.
+pub struct TrajectoryView {
+    pub center_id: NaifId,
+    pub selected: Vec<NaifId>,
+    pub num_samples: usize,
+    /// Fraction of the time span of the displayed epoch
+    pub scrub: f64,
+    pub yaw_rad: f32,
+    pub pitch_rad: f32,
+    pub zoom: f32,
+    tracks: Option<Tracks>,
+}
+
+impl Default for TrajectoryView {
+    fn default() -> Self {
+        Self {
+            center_id: 0,
+            selected: Vec::new(),
+            num_samples: 500,
+            scrub: 0.0,
+            yaw_rad: 0.0,
+            pitch_rad: 0.0,
+            zoom: 1.0,
+            tracks: None,
+        }
+    }
+}
+
+fn name_of(id: NaifId) -> String {
+    celestial_name_from_id(id)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{id}"))
+}
+
+fn position_km(almanac: &Almanac, id: NaifId, center_id: NaifId, epoch: Epoch) -> Option<Vector3> {
+    almanac
+        .translate(
+            Frame::from_ephem_j2000(id),
+            Frame::from_ephem_j2000(center_id),
+            epoch,
+            None,
+        )
+        .ok()
+        .map(|state| state.radius_km)
+}
+
+impl TrajectoryView {
+    /// Samples the selected trajectories over the common domain of the selected IDs, if needed.
+    fn update_tracks(&mut self, almanac: &Almanac) {
+        let key = (self.center_id, self.selected.clone(), self.num_samples);
+        if self.tracks.as_ref().map(|tracks| &tracks.key) == Some(&key) {
+            return;
+        }
+        self.tracks = None;
+
+        if self.selected.is_empty() {
+            return;
+        }
+
+        let mut domain: Option<(Epoch, Epoch)> = None;
+        for id in self.selected.iter().chain([self.center_id].iter()) {
+            if let Ok((start, end)) = almanac.spk_domain(*id) {
+                domain = Some(match domain {
+                    None => (start, end),
+                    Some((cur_start, cur_end)) => (cur_start.max(start), cur_end.min(end)),
+                });
+            }
+        }
+
+        let Some((start, end)) = domain else {
+            return;
+        };
+        if end <= start {
+            return;
+        }
+
+        let step = (end - start) / ((self.num_samples.max(2) - 1) as f64);
+        let positions = self
+            .selected
+            .iter()
+            .map(|id| {
+                let samples = (0..self.num_samples.max(2))
+                    .map(|i| position_km(almanac, *id, self.center_id, start + step * (i as f64)))
+                    .collect();
+                (*id, samples)
+            })
+            .collect();
+
+        self.tracks = Some(Tracks {
+            key,
+            start,
+            end,
+            positions,
+        });
+    }
+
+    /// Orthographic projection after rotating about the Z axis (yaw) then the X axis (pitch), looking down +Z by default.
+    fn project(&self, r: &Vector3) -> Vec2 {
+        let (sy, cy) = self.yaw_rad.sin_cos();
+        let (sp, cp) = self.pitch_rad.sin_cos();
+        let (x, y, z) = (r.x as f32, r.y as f32, r.z as f32);
+        let x1 = x * cy - y * sy;
+        let y1 = x * sy + y * cy;
+        let y2 = y1 * cp - z * sp;
+        Vec2::new(x1, -y2)
+    }
+}
+
+pub fn trajectory_ui(
+    ui: &mut egui::Ui,
+    almanac: &Almanac,
+    view: &mut TrajectoryView,
+    selected_time_scale: TimeScale,
+) {
+    let Ok(domains) = almanac.spk_domains() else {
+        ui.label("No ephemeris loaded");
+        return;
+    };
+
+    let mut ids = domains.keys().copied().collect::<Vec<_>>();
+    ids.sort();
+
+    let mut centers = ids.clone();
+    if !centers.contains(&0) {
+        centers.insert(0, 0);
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Central body");
+        egui::ComboBox::new("trajectory_center", "")
+            .selected_text(name_of(view.center_id))
+            .show_ui(ui, |ui| {
+                for id in &centers {
+                    ui.selectable_value(&mut view.center_id, *id, name_of(*id));
+                }
+            });
+
+        ui.label("Samples");
+        ui.add(egui::DragValue::new(&mut view.num_samples).range(2..=10_000));
+
+        if ui.button("Reset view").clicked() {
+            view.yaw_rad = 0.0;
+            view.pitch_rad = 0.0;
+            view.zoom = 1.0;
+        }
+    });
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Trajectories");
+        for id in &ids {
+            if *id == view.center_id {
+                continue;
+            }
+            let mut checked = view.selected.contains(id);
+            if ui.checkbox(&mut checked, name_of(*id)).changed() {
+                if checked {
+                    view.selected.push(*id);
+                } else {
+                    view.selected.retain(|sel| sel != id);
+                }
+            }
+        }
+    });
+    view.selected.retain(|id| *id != view.center_id);
+
+    view.update_tracks(almanac);
+
+    let Some((start, end)) = view
+        .tracks
+        .as_ref()
+        .map(|tracks| (tracks.start, tracks.end))
+    else {
+        ui.label("Select trajectories with a common time domain to display them");
+        return;
+    };
+
+    let epoch = start + (end - start) * view.scrub;
+    ui.horizontal(|ui| {
+        ui.label("Epoch");
+        ui.add(egui::Slider::new(&mut view.scrub, 0.0..=1.0).show_value(false));
+        ui.label(epoch.to_gregorian_str(selected_time_scale));
+    });
+
+    let size = Vec2::new(ui.available_width().max(300.0), 500.0);
+    let (response, painter) = ui.allocate_painter(size, Sense::drag());
+    let rect = response.rect;
+
+    // Drag to rotate, scroll to zoom
+    let drag = response.drag_delta();
+    view.yaw_rad += drag.x * 0.01;
+    view.pitch_rad = (view.pitch_rad + drag.y * 0.01).clamp(-PI, PI);
+    if response.hovered() {
+        let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+        view.zoom = (view.zoom * (1.0 + scroll * 0.001)).clamp(0.01, 100.0);
+    }
+
+    painter.rect_filled(rect, 0.0, Color32::from_gray(16));
+
+    let tracks = view.tracks.as_ref().unwrap();
+    let max_norm_km = tracks
+        .positions
+        .iter()
+        .flat_map(|(_, samples)| samples.iter().flatten())
+        .map(|r| r.norm())
+        .fold(0.0_f64, f64::max);
+    if max_norm_km <= 0.0 {
+        return;
+    }
+    let scale = 0.45 * rect.width().min(rect.height()) / (max_norm_km as f32) * view.zoom;
+    let center = rect.center();
+    let to_screen = |r: &Vector3| -> Pos2 { center + view.project(r) * scale };
+
+    let text_color = ui.visuals().text_color();
+    painter.circle_filled(center, 4.0, text_color);
+    painter.text(
+        center + Vec2::new(6.0, -6.0),
+        Align2::LEFT_BOTTOM,
+        name_of(view.center_id),
+        FontId::monospace(12.0),
+        text_color,
+    );
+
+    for (no, (id, samples)) in tracks.positions.iter().enumerate() {
+        let color = COLORS[no % COLORS.len()];
+
+        // Split the polyline where there is no data
+        let mut line = Vec::new();
+        for sample in samples {
+            match sample {
+                Some(r) => line.push(to_screen(r)),
+                None => {
+                    if line.len() > 1 {
+                        painter.add(Shape::line(
+                            std::mem::take(&mut line),
+                            Stroke::new(1.5, color),
+                        ));
+                    }
+                }
+            }
+        }
+        if line.len() > 1 {
+            painter.add(Shape::line(line, Stroke::new(1.5, color)));
+        }
+
+        if let Some(r) = position_km(almanac, *id, view.center_id, epoch) {
+            let pos = to_screen(&r);
+            painter.circle_filled(pos, 5.0, color);
+            painter.text(
+                pos + Vec2::new(6.0, -6.0),
+                Align2::LEFT_BOTTOM,
+                format!("{} ({:.0} km)", name_of(*id), r.norm()),
+                FontId::monospace(12.0),
+                color,
+            );
+        }
+    }
+
+    painter.text(
+        rect.left_bottom() + Vec2::new(6.0, -6.0),
+        Align2::LEFT_BOTTOM,
+        format!(
+            "J2000 centered on {}, drag to rotate, scroll to zoom",
+            name_of(view.center_id)
+        ),
+        FontId::proportional(12.0),
+        text_color,
+    );
+}
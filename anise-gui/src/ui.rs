@@ -7,7 +7,14 @@ use log::error;
 #[cfg(target_arch = "wasm32")]
 use poll_promise::Promise;
 
-use crate::{bpc::bpc_ui, epa::epa_ui, pca::pca_ui, spk::spk_ui};
+use crate::{
+    bpc::bpc_ui,
+    coverage::coverage_ui,
+    epa::epa_ui,
+    pca::pca_ui,
+    spk::spk_ui,
+    trajectory::{trajectory_ui, TrajectoryView},
+};
 
 #[cfg(target_arch = "wasm32")]
 type AlmanacFile = Option<(String, Vec<u8>)>;
@@ -17,6 +24,7 @@ pub struct UiApp {
     pub show_unix: bool,
     pub almanac: Almanac,
     pub path: Option<String>,
+    pub trajectory: TrajectoryView,
     #[cfg(target_arch = "wasm32")]
     promise: Option<Promise<AlmanacFile>>,
 }
@@ -28,6 +36,7 @@ impl Default for UiApp {
             show_unix: false,
             almanac: Default::default(),
             path: None,
+            trajectory: Default::default(),
             #[cfg(target_arch = "wasm32")]
             promise: Default::default(),
         }
@@ -86,12 +95,45 @@ impl UiApp {
             FileLoadResult::NoFileSelectedYet
         }
     }
+
+    /// Loads all of the files dropped onto the window, in order, into the current almanac.
+    fn load_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
+
+        for file in dropped_files {
+            let result = match (&file.path, &file.bytes) {
+                #[cfg(not(target_arch = "wasm32"))]
+                (Some(path_buf), _) => {
+                    let path = path_buf.to_str().unwrap().to_string();
+                    self.almanac.load(&path).map(|almanac| (path, almanac))
+                }
+                (_, Some(data)) => self
+                    .almanac
+                    .load_from_bytes(bytes::Bytes::copy_from_slice(data))
+                    .map(|almanac| (file.name.clone(), almanac)),
+                _ => continue,
+            };
+
+            match result {
+                Ok((name, almanac)) => {
+                    self.almanac = almanac;
+                    self.path = Some(match self.path.take() {
+                        None => name,
+                        Some(path) => format!("{path}, {name}"),
+                    });
+                }
+                Err(e) => error!("{e}"),
+            }
+        }
+    }
 }
 
 impl eframe::App for UiApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         ctx.set_pixels_per_point(1.25);
 
+        self.load_dropped_files(ctx);
+
         egui::TopBottomPanel::top("header").show(ctx, |ui| {
             ui.horizontal_centered(|ui| {
                 ui.vertical_centered(|ui| {
@@ -129,6 +171,7 @@ impl eframe::App for UiApp {
                                 let mut trigger_file_load = false;
                                 trigger_file_load |=
                                     ui.button("Select file to inspect...").clicked();
+                                ui.label("or drop one or more files here");
 
                                 // If we are in the browser, we need to also check if the file
                                 // is ready to be loaded instead of just checking if the button
@@ -155,7 +198,12 @@ impl eframe::App for UiApp {
                             }
                             Some(path) => {
                                 // Grab generic data
-                                let (label, crc) = if self.almanac.num_loaded_spk() == 1 {
+                                let (label, crc) = if self.almanac.num_loaded_spk()
+                                    + self.almanac.num_loaded_bpc()
+                                    > 1
+                                {
+                                    ("DAF/MULTIPLE", 0)
+                                } else if self.almanac.num_loaded_spk() == 1 {
                                     (
                                         "DAF/SPK",
                                         self.almanac.spk_data[0].as_ref().unwrap().crc32(),
@@ -187,8 +235,10 @@ impl eframe::App for UiApp {
                                         ui.label("File type");
                                         ui.label(label);
 
-                                        ui.label("CRC32");
-                                        ui.text_edit_singleline(&mut format!("{crc}"));
+                                        if label != "DAF/MULTIPLE" {
+                                            ui.label("CRC32");
+                                            ui.text_edit_singleline(&mut format!("{crc}"));
+                                        }
 
                                         if label.ends_with("SPK") {
                                             let num_summaries = self.almanac.spk_data[0]
@@ -254,6 +304,17 @@ impl eframe::App for UiApp {
                                             self.show_unix,
                                             self.selected_time_scale,
                                         );
+
+                                        ui.collapsing("3D trajectories", |ui| {
+                                            trajectory_ui(
+                                                ui,
+                                                &self.almanac,
+                                                &mut self.trajectory,
+                                                self.selected_time_scale,
+                                            );
+                                        });
+                                    } else if label == "DAF/MULTIPLE" {
+                                        coverage_ui(ui, &self.almanac, self.selected_time_scale);
                                     } else if label == "ANISE/PCA" {
                                         pca_ui(ui, &self.almanac);
                                     } else if label == "ANISE/EPA" {
@@ -264,6 +325,7 @@ impl eframe::App for UiApp {
                                 if unload_file {
                                     self.almanac = Almanac::default();
                                     self.path = None;
+                                    self.trajectory = TrajectoryView::default();
                                 }
                             }
                         };
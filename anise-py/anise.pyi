@@ -1,7 +1,7 @@
 import typing
 import numpy
 
-_all__: list = ["time", "astro", "utils", "Aberration", "Almanac", "MetaAlmanac", "MetaFile"]
+_all__: list = ["time", "astro", "utils", "instrument", "Aberration", "Almanac", "AlmanacDescription", "BPCDescription", "BPCSegmentDescription", "DataSetEntryDescription", "HermiteWindow", "MetaAlmanac", "MetaFile", "QueryOptions", "QueryResult", "SPKDescription", "SPKSegmentDescription"]
 
 @typing.final
 class Aberration:
@@ -76,6 +76,18 @@ class Almanac:
     def __init__(self, path: str) -> Almanac:
         """An Almanac contains all of the loaded SPICE and ANISE data. It is the context for all computations."""
 
+    def angular_separation(self, target1: Frame, target2: Frame, observer: Frame, epoch: Epoch, ab_corr: Aberration=None) -> float:
+        """Returns the angular separation (between 0 and 180 degrees) between two targets as seen from the observer, accounting for the aberration correction if provided.
+
+This is a generalization of the `sun_angle_deg` function, useful for star tracker blinding or antenna interference analyses."""
+
+    def angular_velocity(self, from_frame: Frame, to_frame: Frame, epoch: Epoch) -> numpy.array:
+        """Returns the angular velocity vector in rad/s of the `to_frame` with respect to the `from_frame`, expressed in the `to_frame`.
+
+If the rotation between these frames does not provide its time derivative, it is computed by central differencing
+of the rotation matrices 0.1 seconds before and after the requested epoch.
+Warning: you MUST manually install numpy to call this function."""
+
     def azimuth_elevation_range_sez(self, rx: Orbit, tx: Orbit, obstructing_body: Frame=None, ab_corr: Aberration=None) -> AzElRange:
         """Computes the azimuth (in degrees), elevation (in degrees), and range (in kilometers) of the
 receiver state (`rx`) seen from the transmitter state (`tx`), once converted into the SEZ frame of the transmitter.
@@ -92,6 +104,14 @@ The obstructing body _should_ be a tri-axial ellipsoid body, e.g. IAU_MOON_FRAME
 6. Compute the elevation, and ensure it is between +/- 180 degrees.
 7. Compute the azimuth with a quadrant check, and ensure it is between 0 and 360 degrees."""
 
+    def azimuth_elevation_range_sez_from_location_id(self, rx: Orbit, location_id: int, obstructing_body: Frame=None, ab_corr: Aberration=None) -> AzElRange:
+        """Computes the azimuth (in degrees), elevation (in degrees), and range (in kilometers) of the
+receiver state (`rx`) seen from the location ID, once converted into the SEZ frame of the location."""
+
+    def azimuth_elevation_range_sez_from_location_name(self, rx: Orbit, location_name: str, obstructing_body: Frame=None, ab_corr: Aberration=None) -> AzElRange:
+        """Computes the azimuth (in degrees), elevation (in degrees), and range (in kilometers) of the
+receiver state (`rx`) seen from the location name, once converted into the SEZ frame of the location."""
+
     def bpc_domain(self, id: int) -> typing.Tuple:
         """Returns the applicable domain of the request id, i.e. start and end epoch that the provided id has loaded data."""
 
@@ -101,19 +121,123 @@ The obstructing body _should_ be a tri-axial ellipsoid body, e.g. IAU_MOON_FRAME
 # Warning
 This function performs a memory allocation."""
 
-    def bpc_summaries(self, id: int) -> typing.List:
-        """Returns a vector of the summaries whose ID matches the desired `id`, in the order in which they will be used, i.e. in reverse loading order.
+    def bpc_summaries(self, id: int=None) -> typing.List:
+        """Returns the summaries of the segments of all of the loaded BPCs, in the order in which they will be used, i.e. in reverse loading order.
+If an `id` is provided, only the segments whose frame ID matches are returned, and an error is raised if there are none."""
 
-# Warning
-This function performs a memory allocation."""
+    def clear_frame_override(self, ephemeris_id: int) -> Almanac:
+        """Removes the override of the frames centered on this body, if any, from a clone of this Almanac."""
+
+    def compute_footprint(self, instrument: Instrument, sc_state: Orbit, sc_attitude: DCM, target_frame: Frame, num_points: int) -> numpy.array:
+        """Computes the footprint of the instrument on the target body, given the spacecraft state and attitude (the rotation from the frame
+of the spacecraft state to the body frame), by intersecting rays regularly spaced on the edge of the field of view with the shape of the target.
+
+Returns an array of shape (N, 2) of the geodetic latitude and longitude (in degrees) of each intersection, which are NaN if that ray does not intersect the target.
+The target frame must be body fixed, and its shape is fetched from the loaded planetary data if it is not set."""
+
+    def context_digest(self) -> int:
+        """Returns a digest of all of the data loaded in this Almanac, to be stamped into reports and exported files such that results can be traced
+to an exact data configuration.
+
+The digest is the CRC32 of the checksums, aliases, and load order of the SPK and BPC kernels, of the checksums of the ANISE data sets, and of
+the kernel pool and body names. It is stable across runs, platforms, and context files (cf. `save_context`), and changes if any kernel is added,
+removed, reordered, or renamed."""
+
+    def deprioritize(self, alias: str) -> Almanac:
+        """Gives all of the other kernels of the same kind precedence over the kernels with the provided alias,
+as if they had been loaded first."""
 
     def describe(self, spk: bool=None, bpc: bool=None, planetary: bool=None, time_scale: TimeScale=None, round_time: bool=None) -> None:
         """Pretty prints the description of this Almanac, showing everything by default. Default time scale is TDB.
 If any parameter is set to true, then nothing other than that will be printed."""
 
+    def describe_data(self) -> AlmanacDescription:
+        """Returns a structured description of all of the data loaded in this Almanac, including the summaries of
+every segment of the loaded SPKs and BPCs, so that it may be rendered without parsing the output of `describe`."""
+
+    def describe_path_coverage(self, frame: Frame, epoch: Epoch) -> str:
+        """Returns a human readable diagnostic of the ephemeris and orientation paths of the provided frame at the provided epoch,
+listing every kernel segment considered for each hop and its coverage interval, and the first hop without data, if any."""
+
+    def frame_from_name(self, name: str) -> Frame:
+        """Returns the frame with the provided name, e.g. "Mars IAU", "EARTH J2000", "Earth-Moon Barycenter ECLIPJ2000", or any name registered with `register_frame`.
+
+The name is case insensitive. Unless it is a registered name, it is the name (or NAIF ID) of the center, followed by the name (or ID) of the orientation,
+where the orientation "IAU" is the IAU body fixed frame of that center. The center may be any built-in celestial object, or the name of an entry of the
+loaded planetary data. The gravitational parameter and the shape of the frame are then set as in `frame_from_uid`, if available."""
+
     def frame_info(self, uid: Frame) -> Frame:
         """Returns the frame information (gravitational param, shape) as defined in this Almanac from an empty frame"""
 
+    def frame_info_sources(self, uid: Frame) -> FrameInfoSources:
+        """Returns the frame information as `frame_info`, along with the source of its gravitational parameter and of its shape."""
+
+    def gcpool(self, name: str) -> typing.List:
+        """Returns the values of the character variable of the kernel pool, if defined.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of gcpool."""
+
+    def gdpool(self, name: str) -> typing.List:
+        """Returns the values of the numeric variable of the kernel pool, if defined.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of gdpool."""
+
+    def gipool(self, name: str) -> typing.List:
+        """Returns the values of the numeric variable of the kernel pool rounded to the nearest integer, if defined.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of gipool."""
+
+    def illumination_angles(self, surface_point: Orbit, observer: Orbit, ab_corr: Aberration=None) -> IlluminationAngles:
+        """Computes the illumination angles (phase, incidence, and emission) of a point on the surface of a body, as seen from the observer.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of ilumin, where the surface point is provided as an Orbit in the body fixed frame of
+the target (e.g. built with `Orbit::try_latlongalt`), and the observer may be in any frame. The surface normal is computed
+from the tri-axial ellipsoid shape of the body fixed frame, which is fetched from the Almanac if not set.
+
+# Algorithm
+1. Compute the position of the observer and of the Sun with respect to the target in the body fixed frame of the surface point.
+2. Compute the outward surface normal of the ellipsoid at the surface point.
+3. The phase angle is the angle between the surface-to-Sun and surface-to-observer vectors.
+4. The incidence (resp. emission) angle is the angle between the surface normal and the surface-to-Sun (resp. surface-to-observer) vector."""
+
+    def is_visible_from_location_id(self, rx: Orbit, location_id: int, obstructing_body: Frame=None, ab_corr: Aberration=None) -> bool:
+        """Returns whether the receiver state (`rx`) is visible from the location ID, accounting for its terrain mask,
+its minimum antenna elevation, and the optional obstructing body."""
+
+    def kernel_aliases(self) -> typing.List:
+        """Returns the aliases of the loaded SPK and BPC kernels, from the lowest to the highest precedence within each kind."""
+
+    def lagrange_point_state(self, primary: Frame, secondary: Frame, point: LagrangePoint, epoch: Epoch, observer_frame: Frame) -> Orbit:
+        """Returns the instantaneous state of the requested libration point of the system formed by the primary and the secondary bodies,
+as seen from the observer frame. The returned state can be used as a target or an observer in all of the other computations.
+
+# Algorithm
+1. Compute the mass ratio of the system from the gravitational parameters of the primary and secondary frames, fetched from the
+   loaded planetary data if they are not set;
+2. Compute the state of the secondary with respect to the primary from the loaded ephemerides;
+3. Locate the libration point in the circular restricted three body problem (cf. `LagrangePoint::synodic_position`), scaled by the
+   instantaneous distance between both bodies, in the rotating frame whose X axis points from the primary to the secondary and whose
+   Z axis is along their relative angular momentum;
+4. Compute its velocity from the instantaneous rotation rate and the pulsation of that rotating frame.
+
+# Note
+The libration points are only equilibrium points of the circular restricted three body problem: these states are not
+invariant in the true dynamics, but are the usual targets and references of mission design."""
+
+    def limb_as_seen_from(self, observer: Frame, target_frame: Frame, epoch: Epoch, resolution: int) -> typing.List:
+        """Computes the limb of the target body as seen from the observer, i.e. the points of the surface of the target
+ellipsoid where the line of sight from the observer is tangent to that surface, as a polygon of `resolution` points.
+
+The points are ordered counterclockwise as seen from the observer, and expressed in the body fixed frame of the
+target with a zero velocity. The shape of the target frame is fetched from the Almanac if not set.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of edlimb, sampled on the limb ellipse."""
+
     def line_of_sight_obstructed(self, observer: Orbit, observed: Orbit, obstructing_body: Frame, ab_corr: Aberration=None) -> bool:
         """Computes whether the line of sight between an observer and an observed Cartesian state is obstructed by the obstructing body.
 Returns true if the obstructing body is in the way, false otherwise.
@@ -147,10 +271,54 @@ Algorithm (source: Algorithm 35 of Vallado, 4th edition, page 308.):
     def load(self, path: str) -> Almanac:
         """Generic function that tries to load the provided path guessing to the file type."""
 
+    def load_body_names(self, path: str) -> Almanac:
+        """Loads the body names and NAIF IDs defined by the `NAIF_BODY_NAME` and `NAIF_BODY_CODE` variables of the provided text kernel
+(e.g. a mission frame kernel) into a clone of this Almanac."""
+
+    def load_context(self, path: str) -> Almanac:
+        """Loads all of the data of the context file saved with `save_context` into a clone of this Almanac, as if each kernel and data set
+had been loaded in the same order as in the original Almanac. Load it into a default Almanac to reproduce the original one."""
+
+    def load_lazy(self, path: str) -> Almanac:
+        """Loads the provided path like `load`, but SPK and BPC files are memory mapped instead of copied onto the heap
+and parsed lazily: only the records needed by each query are read from disk, which bounds the memory footprint
+of large kernels (e.g. DE440 or high precision Earth orientation files).
+
+WARNING: the file must not be modified nor truncated while loaded, otherwise queries may crash the interpreter (SIGBUS)
+or return corrupted data. ANISE data sets (PCA, EPA, etc.) are small and are loaded as with `load`."""
+
     def load_from_metafile(self, metafile: Metafile, autodelete: bool) -> Almanac:
         """Load from the provided MetaFile, downloading it if necessary.
 Set autodelete to true to automatically delete lock files. Lock files are important in multi-threaded loads."""
 
+    def load_text_kernel(self, path: str) -> Almanac:
+        """Loads all of the variables of the provided text kernel (e.g. a TPC, FK, IK, or SCLK) into the kernel pool of a clone of this Almanac,
+along with the body names and NAIF IDs, and the two-vector dynamic frames it defines, if any.
+
+# SPICE Compatibility
+This function is the equivalent of furnsh for text kernels: variables assigned with `=` replace the previously loaded values,
+and those assigned with `+=` are appended to them."""
+
+    def local_solar_time_hours(self, body_fixed_frame: Frame, longitude_deg: float, epoch: Epoch, ab_corr: Aberration=None) -> float:
+        """Returns the local solar time, in hours between 0 and 24, at the provided longitude on the body fixed frame.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of et2lst with the "PLANETOCENTRIC" longitude type: the local solar time is noon
+at the planetocentric longitude of the Sun in the body fixed frame, and each hour corresponds to 15 degrees of longitude."""
+
+    def naif_id_from_name(self, name: str) -> int:
+        """Returns the NAIF ID of the provided body name, ignoring case and repeated whitespaces, e.g. "Mars Reconnaissance Orbiter" returns -74.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of bods2c: the body names loaded in this Almanac take precedence over the built-in names,
+the latest definition of a name takes precedence over earlier ones, and a NAIF ID provided as a string is returned as is."""
+
+    def naif_name_from_id(self, id: int) -> str:
+        """Returns the name of the provided NAIF ID, e.g. -74 returns "MARS RECONNAISSANCE ORBITER".
+
+# SPICE Compatibility
+This function is the SPICE equivalent of bodc2n: the latest name loaded in this Almanac for this ID takes precedence over the built-in names."""
+
     def occultation(self, back_frame: Frame, front_frame: Frame, observer: Orbit, ab_corr: Aberration=None) -> Occultation:
         """Computes the occultation percentage of the `back_frame` object by the `front_frame` object as seen from the observer, when according for the provided aberration correction.
 
@@ -159,12 +327,79 @@ A 100%  percent occultation means that the back object is fully hidden from the
 A value in between means that the back object is partially hidden from the observser (i.e. _penumbra_ if the back object is the Sun).
 Refer to the [MathSpec](https://nyxspace.com/nyxspace/MathSpec/celestial/eclipse/) for modeling details."""
 
+    def occultation_triaxial(self, back_frame: Frame, front_frame: Frame, observer: Orbit, ab_corr: Aberration=None) -> Occultation:
+        """Computes the occultation percentage of the `back_frame` object by the `front_frame` object as seen from the observer,
+modeling the front object as its tri-axial ellipsoid instead of a sphere of its mean equatorial radius.
+
+The apparent radius of the front object is that of its limb in the direction of the back object, which improves the
+occultation predictions in grazing geometries, e.g. when the Sun sets over the pole of an oblate planet.
+
+# Frame warning
+The ellipsoid is oriented in the orientation of the front frame: to account for the orientation of the body, the front frame
+should be body fixed (e.g. IAU_EARTH_FRAME or IAU_MOON_FRAME)."""
+
+    def override_frame(self, ephemeris_id: int, mu_km3_s2: float=None, shape: Ellipsoid=None) -> Almanac:
+        """Overrides the gravitational parameter and/or the shape of all of the frames centered on this body into a clone of this Almanac,
+e.g. to use the mu estimated by a small body mission instead of that of the loaded planetary data.
+
+Overrides take precedence over the loaded planetary data and the registered frames in `frame_from_uid` and `frame_from_name`.
+Only the provided data is overridden, and replaces any previous override of this body."""
+
+    def porkchop(self, departure: Frame, arrival: Frame, central: Frame, departure_epochs: typing.List, arrival_epochs: typing.List) -> PorkchopPlot:
+        """Computes the porkchop plot grids of a transfer between the `departure` and `arrival` bodies, as seen from the `central` body.
+Rows correspond to the departure epochs and columns to the arrival epochs."""
+
+    def prioritize(self, alias: str) -> Almanac:
+        """Gives the kernels with the provided alias precedence over all of the other kernels of the same kind,
+as if they had been loaded last."""
+
+    def register_frame(self, name: str, ephemeris_id: int, orientation_id: int, mu_km3_s2: float=None, shape: Ellipsoid=None) -> Almanac:
+        """Registers a frame under the provided name into a clone of this Almanac, e.g. a frame centered on a spacecraft or on a body
+that is not in the built-in constants. The name is case insensitive.
+
+Registered frames are returned by `frame_from_name`, and their gravitational parameter and shape are used by `frame_from_uid`
+if the loaded planetary data does not define that frame. Overrides set with `override_frame` still take precedence."""
+
+    def save_context(self, path: str, overwrite: bool=False) -> None:
+        """Saves all of the data loaded in this Almanac into a single context file, which can be loaded with `load_context` to reproduce
+exactly the same computations elsewhere.
+
+The context includes the SPK and BPC kernels with their aliases and in their order of precedence, the planetary, spacecraft,
+Euler parameter, and location data sets, the variables of the kernel pool, and the body names. Frames and dynamic frames
+which were only registered at runtime (i.e. not defined in a text kernel) and frame overrides are not included.
+
+If overwrite is set to false, and the path already exists, this function will return an error."""
+
+    def smooth_solar_shadow_function(self, eclipsing_frame: Frame, observer: Orbit, sharpness: float, ab_corr: Aberration=None) -> float:
+        """Computes a smooth shadow function of the observer due to the eclipsing_frame, from 0 in umbra to 1 in full sunlight.
+
+Unlike the conical model of `solar_shadow_function`, this function is infinitely differentiable across the penumbra,
+which suits the force models of propagators with adaptive step sizes and the partials of trajectory optimizers.
+
+# Algorithm
+1. Compute the apparent radii of the Sun and of the eclipsing body as seen from the observer, and the apparent separation of their centers.
+2. The shadow function is `(1 + tanh(sharpness * (separation - eclipsing radius) / Sun radius)) / 2`: the penumbra of the
+   conical model, where the separation is within one apparent Sun radius of the apparent eclipsing radius, maps onto
+   `[-sharpness, sharpness]`. A sharpness of about 5 closely follows the conical model.
+
+The observer is in umbra if it is inside the eclipsing body."""
+
     def solar_eclipsing(self, eclipsing_frame: Frame, observer: Orbit, ab_corr: Aberration=None) -> Occultation:
         """Computes the solar eclipsing of the observer due to the eclipsing_frame.
 
 This function calls `occultation` where the back object is the Sun in the J2000 frame, and the front object
 is the provided eclipsing frame."""
 
+    def solar_eclipsing_triaxial(self, eclipsing_frame: Frame, observer: Orbit, ab_corr: Aberration=None) -> Occultation:
+        """Computes the solar eclipsing of the observer due to the eclipsing_frame, modeled as its tri-axial ellipsoid.
+
+This function calls `occultation_triaxial` where the back object is the Sun in the J2000 frame, and the front object
+is the provided eclipsing frame, which should be body fixed."""
+
+    def solar_shadow_function(self, eclipsing_frame: Frame, observer: Orbit, ab_corr: Aberration=None) -> float:
+        """Computes the shadow function of the observer due to the eclipsing_frame, i.e. the fraction of the solar flux it receives,
+from 0 in umbra to 1 in full sunlight, using the conical shadow model of `solar_eclipsing`."""
+
     def spk_domain(self, id: int) -> typing.Tuple:
         """Returns the applicable domain of the request id, i.e. start and end epoch that the provided id has loaded data."""
 
@@ -177,11 +412,9 @@ This function performs a memory allocation."""
     def spk_ezr(self, target: int, epoch: Epoch, frame: int, observer: int, ab_corr: Aberration=None) -> Orbit:
         """Alias fo SPICE's `spkezr` where the inputs must be the NAIF IDs of the objects and frames with the caveat that the aberration is moved to the last positional argument."""
 
-    def spk_summaries(self, id: int) -> typing.List:
-        """Returns a vector of the summaries whose ID matches the desired `id`, in the order in which they will be used, i.e. in reverse loading order.
-
-# Warning
-This function performs a memory allocation."""
+    def spk_summaries(self, id: int=None) -> typing.List:
+        """Returns the summaries of the segments of all of the loaded SPKs, in the order in which they will be used, i.e. in reverse loading order.
+If an `id` is provided, only the segments whose target ID matches are returned, and an error is raised if there are none."""
 
     def state_of(self, object: int, observer: Frame, epoch: Epoch, ab_corr: Aberration=None) -> Orbit:
         """Returns the Cartesian state of the object as seen from the provided observer frame (essentially `spkezr`).
@@ -189,6 +422,22 @@ This function performs a memory allocation."""
 # Note
 The units will be those of the underlying ephemeris data (typically km and km/s)"""
 
+    def sub_observer_point(self, target_frame: Frame, observer: Orbit, ab_corr: Aberration=None) -> Orbit:
+        """Computes the sub-observer point on the target body, i.e. the point on the surface of the target ellipsoid closest to the observer.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of subpnt with the "NEAR POINT/ELLIPSOID" method. The returned surface point is
+expressed in the body fixed frame of the target, with a zero velocity, and its geodetic latitude and longitude are available
+with `latitude_deg` and `longitude_deg`. The shape of the target frame is fetched from the Almanac if not set."""
+
+    def sub_solar_point(self, target_frame: Frame, epoch: Epoch, ab_corr: Aberration=None) -> Orbit:
+        """Computes the sub-solar point on the target body, i.e. the point on the surface of the target ellipsoid closest to the Sun.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of subslr with the "NEAR POINT/ELLIPSOID" method. The returned surface point is
+expressed in the body fixed frame of the target, with a zero velocity, and its geodetic latitude and longitude are available
+with `latitude_deg` and `longitude_deg`. The shape of the target frame is fetched from the Almanac if not set."""
+
     def sun_angle_deg(self, target_id: int, observer_id: int, epoch: Epoch) -> float:
         """Returns the angle (between 0 and 180 degrees) between the observer and the Sun, and the observer and the target body ID.
 This computes the Sun Probe Earth angle (SPE) if the probe is in a loaded SPK, its ID is the "observer_id", and the target is set to its central body.
@@ -230,6 +479,26 @@ Obs. -- Target
     def sun_angle_deg_from_frame(self, target: Frame, observer: Frame, epoch: Epoch) -> float:
         """Convenience function that calls `sun_angle_deg` with the provided frames instead of the ephemeris ID."""
 
+    def surface_intercept(self, ray_origin: Orbit, direction: numpy.array, target_frame: Frame, ab_corr: Aberration=None) -> Orbit:
+        """Computes the intercept of a ray with the surface of the target body, e.g. the intercept of the boresight of an instrument.
+
+The ray starts at the position of the `ray_origin` state and points along the `direction`, expressed in the frame of that state.
+Returns the first intersection point of that ray with the ellipsoid of the target, as an Orbit in the body fixed frame of the
+target with a zero velocity, or None if the ray misses the target.
+
+# SPICE Compatibility
+This function is the SPICE equivalent of sincpt with the "ELLIPSOID" method. The aberration correction, if any, is applied
+to the position of the target as seen from the ray origin."""
+
+    def terminator(self, target_frame: Frame, epoch: Epoch, resolution: int) -> typing.List:
+        """Computes the terminator of the target body, i.e. the boundary between its day and night sides, as a polygon of
+`resolution` points on the surface of the target ellipsoid.
+
+The Sun is modeled as a point source, so each point of the terminator has an incidence angle of 90 degrees: the
+terminator is the limb of the target as seen from the Sun (cf. `limb_as_seen_from`). The points are expressed in
+the body fixed frame of the target, with a zero velocity, and the shape of the target frame is fetched from the
+Almanac if not set."""
+
     def transform(self, target_frame: Orbit, observer_frame: Frame, epoch: Epoch, ab_corr: Aberration=None) -> Orbit:
         """Returns the Cartesian state needed to transform the `from_frame` to the `to_frame`.
 
@@ -249,6 +518,14 @@ The units will be those of the underlying ephemeris data (typically km and km/s)
 
 **WARNING:** This function only performs the translation and no rotation _whatsoever_. Use the `transform_state_to` function instead to include rotations."""
 
+    def transform_with_options(self, target_frame: Frame, observer_frame: Frame, epoch: Epoch, ab_corr: Aberration=None, options: QueryOptions=None) -> QueryResult:
+        """Returns the Cartesian state needed to transform the `from_frame` to the `to_frame`, like `transform`, but allows queries
+slightly outside of the domain of the loaded data to be clamped to its edge or linearly extrapolated, per the provided options.
+
+The edge of the domain is searched from the first hop without data in the ephemeris and orientation paths of both frames,
+and the clamped epoch is returned in the time scale of the requested epoch. Queries which fail for other reasons, or
+which are further from the domain than allowed, return the original error."""
+
     def translate(self, target_frame: Orbit, observer_frame: Frame, epoch: Epoch, ab_corr: Aberration=None) -> Orbit:
         """Returns the Cartesian state of the target frame as seen from the observer frame at the provided epoch, and optionally given the aberration correction.
 
@@ -277,12 +554,147 @@ This function performs a recursion of no more than twice the [MAX_TREE_DEPTH].""
     def translate_to_parent(self, source: Frame, epoch: Epoch) -> Orbit:
         """Performs the GEOMETRIC translation to the parent. Use translate_from_to for aberration."""
 
+    def translate_with_diagnostics(self, target_frame: Orbit, observer_frame: Frame, epoch: Epoch, ab_corr: Aberration=None) -> typing.Tuple:
+        """Returns the Cartesian state of the target frame as seen from the observer frame at the provided epoch, and optionally given the aberration correction,
+along with the diagnostics of that aberration correction (light time, number of iterations, remaining light time change, and stellar correction angle).
+
+The returned state is exactly that of `translate`."""
+
+    def two_way_range_doppler(self, location_id: int, target: Frame, epoch: Epoch) -> TwoWayRangeDoppler:
+        """Computes the two-way range and range rate of the target seen from the location ID, with the signal received
+back at the location at the provided epoch, iterating the light time of both the uplink and the downlink."""
+
+    def unload(self, alias: str) -> Almanac:
+        """Unloads all of the SPK and BPC kernels with the provided alias. The ANISE data sets are left unchanged."""
+
+    def unload_all(self) -> Almanac:
+        """Unloads all of the SPK and BPC kernels. The ANISE data sets are left unchanged."""
+
+    def with_body_name(self, name: str, id: int) -> Almanac:
+        """Adds the provided body name and NAIF ID to a clone of this Almanac, taking precedence over any previous definition of that name."""
+
+    def with_dcm_cache(self, capacity: int, quantum: Duration) -> Almanac:
+        """Returns a copy of this Almanac which caches up to `capacity` rotations, computed at the center of each `quantum` of time,
+e.g. to compute the ITRF93 to J2000 rotation once per epoch in AER and access reports of many ground stations.
+
+The cached rotations lag or lead by up to half of the quantum: with 1 ms, that is about 0.23 m on the surface of the Earth."""
+
+    def with_hermite_window(self, window: HermiteWindow) -> Almanac:
+        """Returns a copy of this Almanac which interpolates the Hermite Type 13 SPK segments with the provided window selection policy."""
+
+    def without_dcm_cache(self) -> Almanac:
+        """Returns a copy of this Almanac without a DCM cache."""
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+
+    def __str__(self) -> str:
+        """Return str(self)."""
+
+@typing.final
+class AlmanacDescription:
+    """Structured description of all of the data loaded in an Almanac, i.e. the information printed by `describe`.
+
+The SPKs and BPCs are listed in the order in which they are searched, i.e. in reverse loading order."""
+    bpc: typing.List
+    context_digest: int
+    euler_param_data: typing.List
+    location_data: typing.List
+    planetary_data: typing.List
+    spacecraft_data: typing.List
+    spk: typing.List
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+
+    def __str__(self) -> str:
+        """Return str(self)."""
+
+@typing.final
+class BPCDescription:
+    """Description of a BPC loaded in the Almanac, with the summaries of all of its non-empty segments."""
+    alias: str
+    crc32: int
+    segments: typing.List
+
+@typing.final
+class BPCSegmentDescription:
+    """Description of a single segment of a BPC."""
+    data_type: str
+    end_epoch: Epoch
+    frame_id: int
+    inertial_frame_id: int
+    name: str
+    start_epoch: Epoch
+
+    def duration(self) -> Duration:
+        """Returns the duration covered by this segment."""
+
     def __repr__(self) -> str:
         """Return repr(self)."""
 
     def __str__(self) -> str:
         """Return str(self)."""
 
+@typing.final
+class DataSetEntryDescription:
+    """Description of an entry of a data set, which may be accessible by its ID, its name, or both."""
+    id: int
+    name: str
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+
+@typing.final
+class FrameDataSource:
+    """Source of a datum of the frame information, in decreasing order of precedence."""
+
+    def __eq__(self, value: typing.Any) -> bool:
+        """Return self==value."""
+
+    def __int__(self) -> None:
+        """int(self)"""
+
+    def __ne__(self, value: typing.Any) -> bool:
+        """Return self!=value."""
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+    Override: FrameDataSource = ...
+    PlanetaryData: FrameDataSource = ...
+    Registry: FrameDataSource = ...
+
+@typing.final
+class FrameInfoSources:
+    """Frame information and the source of its gravitational parameter and shape, as returned by `frame_sources_from_uid`."""
+    frame: Frame
+    mu_km3_s2: FrameDataSource
+    shape: FrameDataSource
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+
+@typing.final
+class HermiteWindow:
+    """Selection of the window of samples used to interpolate a Hermite Type 13 segment between two of its records.
+
+The window is always shifted to remain within the segment, so the policy only matters away from its boundaries."""
+
+    def __eq__(self, value: typing.Any) -> bool:
+        """Return self==value."""
+
+    def __int__(self) -> None:
+        """int(self)"""
+
+    def __ne__(self, value: typing.Any) -> bool:
+        """Return self!=value."""
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+    Backward: HermiteWindow = ...
+    Centered: HermiteWindow = ...
+    Forward: HermiteWindow = ...
+
 @typing.final
 class MetaAlmanac:
     """A structure to set up an Almanac, with automatic downloading, local storage, checksum checking, and more.
@@ -291,7 +703,8 @@ class MetaAlmanac:
 If the URI is a local path, relative or absolute, nothing will be fetched from a remote. Relative paths are relative to the execution folder (i.e. the current working directory).
 If the URI is a remote path, the MetaAlmanac will first check if the file exists locally. If it exists, it will check that the CRC32 checksum of this file matches that of the specs.
 If it does not match, the file will be downloaded again. If no CRC32 is provided but the file exists, then the MetaAlmanac will fetch the remote file and overwrite the existing file.
-The downloaded path will be stored in the "AppData" folder."""
+The downloaded path will be stored in the "AppData" folder, or in the folder set by the `ANISE_CACHE_DIR` environment variable.
+The cache may be inspected and pruned with `cache_size`, `purge_cache`, and `verify_cache`."""
     files: typing.List
 
     def __init__(self, maybe_path: str=None) -> MetaAlmanac:
@@ -301,7 +714,16 @@ The downloaded path will be stored in the "AppData" folder."""
 If the URI is a local path, relative or absolute, nothing will be fetched from a remote. Relative paths are relative to the execution folder (i.e. the current working directory).
 If the URI is a remote path, the MetaAlmanac will first check if the file exists locally. If it exists, it will check that the CRC32 checksum of this file matches that of the specs.
 If it does not match, the file will be downloaded again. If no CRC32 is provided but the file exists, then the MetaAlmanac will fetch the remote file and overwrite the existing file.
-The downloaded path will be stored in the "AppData" folder."""
+The downloaded path will be stored in the "AppData" folder, or in the folder set by the `ANISE_CACHE_DIR` environment variable.
+The cache may be inspected and pruned with `cache_size`, `purge_cache`, and `verify_cache`."""
+
+    @staticmethod
+    def cache_dir() -> str:
+        """Returns the folder where the remote files are cached, which may be overridden with the `ANISE_CACHE_DIR` environment variable."""
+
+    @staticmethod
+    def cache_size() -> int:
+        """Returns the total size in bytes of the files in the cache folder."""
 
     def dumps(self) -> str:
         """Dumps the configured Meta Almanac into a Dhall string."""
@@ -335,6 +757,16 @@ where multiple processes download the data at the same time. Set `autodelete` to
 this lock file if a dead lock is detected after 10 seconds. Set this flag to false if you have
 more than ten processes which may attempt to download files in parallel."""
 
+    @staticmethod
+    def purge_cache(older_than: Duration) -> typing.List:
+        """Deletes the kernels and data sets of the cache folder which were last modified longer ago than `older_than`, and returns their paths.
+Only the files with the extension of a kernel or data set (bsp, bpc, xsp, xpc, xfr, pca, epa, lka, sca) are deleted, never the lock files of the downloads."""
+
+    def verify_cache(self, delete: bool=None) -> typing.List:
+        """Recomputes the CRC32 of the cached copy of each remote file with a CRC32 and returns the paths of those which do not match,
+e.g. because the file was truncated or modified after its download. Set `delete` to remove these files from the cache,
+so that they are downloaded again on the next call to `process`. Files which are not cached are ignored."""
+
     def __eq__(self, value: typing.Any) -> bool:
         """Return self==value."""
 
@@ -365,16 +797,35 @@ class MetaFile:
 
 The data is stored in the user's local temp directory (i.e. `~/.local/share/nyx-space/anise/` on Linux and `AppData/Local/nyx-space/anise/` on Windows).
 Prior to loading a remote resource, if the local resource exists, its CRC32 will be computed: if it matches the CRC32 of this instance of MetaFile,
-then the file will not be downloaded a second time."""
+then the file will not be downloaded a second time.
+
+A remote URI may request the latest version of a file with a pattern ending in `@latest`, where each `#` matches a digit, e.g.
+`https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/planets/de###.bsp@latest`: the directory listing is fetched and the file
+with the greatest number is used. When the CRC32 is set, downloads which do not match it are rejected.
+
+If the file cannot be fetched, each of the mirrors is tried in order, and each URI is retried `retries` times. Set `offline_ok`
+to fall back to the cached copy, if any, when none of the URIs can be fetched (e.g. in CI or on an air-gapped network).
+A cached copy which does not match the CRC32, if set, is never used."""
     crc32: int
+    mirrors: typing.List
+    offline_ok: bool
+    retries: int
     uri: str
 
-    def __init__(self, uri: str, crc32: int=None) -> MetaFile:
+    def __init__(self, uri: str, crc32: int=None, mirrors: typing.List=None, retries: int=None, offline_ok: bool=None) -> MetaFile:
         """MetaFile allows downloading a remote file from a URL (http, https only), and interpolation of paths in environment variable using the Dhall syntax `env:MY_ENV_VAR`.
 
 The data is stored in the user's local temp directory (i.e. `~/.local/share/nyx-space/anise/` on Linux and `AppData/Local/nyx-space/anise/` on Windows).
 Prior to loading a remote resource, if the local resource exists, its CRC32 will be computed: if it matches the CRC32 of this instance of MetaFile,
-then the file will not be downloaded a second time."""
+then the file will not be downloaded a second time.
+
+A remote URI may request the latest version of a file with a pattern ending in `@latest`, where each `#` matches a digit, e.g.
+`https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/planets/de###.bsp@latest`: the directory listing is fetched and the file
+with the greatest number is used. When the CRC32 is set, downloads which do not match it are rejected.
+
+If the file cannot be fetched, each of the mirrors is tried in order, and each URI is retried `retries` times. Set `offline_ok`
+to fall back to the cached copy, if any, when none of the URIs can be fetched (e.g. in CI or on an air-gapped network).
+A cached copy which does not match the CRC32, if set, is never used."""
 
     def process(self, autodelete: bool=None) -> None:
         """Processes this MetaFile by downloading it if it's a URL.
@@ -405,9 +856,91 @@ This function modified `self` and changes the URI to be the path to the download
     def __str__(self) -> str:
         """Return str(self)."""
 
+@typing.final
+class QueryOptions:
+    """Options of a transform query which may fall slightly outside of the domain of the loaded data, e.g. a few seconds
+past the end of a kernel.
+
+- If `max_offset` is set, queries up to that duration outside of the domain are allowed: the state is clamped to the
+  edge of the domain if `clamp_to_domain` is true, and linearly extrapolated from that edge otherwise.
+- Queries further than `max_offset` from the domain, or outside of the domain if `max_offset` is not set, fail as with
+  `transform`, whether or not they would be clamped."""
+    clamp_to_domain: bool
+    max_offset: Duration
+
+    def __init__(self, clamp_to_domain: bool=None, max_offset: Duration=None) -> QueryOptions:
+        """Options of a transform query which may fall slightly outside of the domain of the loaded data, e.g. a few seconds
+past the end of a kernel.
+
+- If `max_offset` is set, queries up to that duration outside of the domain are allowed: the state is clamped to the
+  edge of the domain if `clamp_to_domain` is true, and linearly extrapolated from that edge otherwise.
+- Queries further than `max_offset` from the domain, or outside of the domain if `max_offset` is not set, fail as with
+  `transform`, whether or not they would be clamped."""
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+
+@typing.final
+class QueryResult:
+    """Result of a transform query with options, flagging whether the state was clamped or extrapolated."""
+    clamped: bool
+    extrapolated: bool
+    offset: Duration
+    state: Orbit
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+
+    def __str__(self) -> str:
+        """Return str(self)."""
+
+@typing.final
+class SPKDescription:
+    """Description of an SPK loaded in the Almanac, with the summaries of all of its non-empty segments."""
+    alias: str
+    crc32: int
+    segments: typing.List
+
+@typing.final
+class SPKSegmentDescription:
+    """Description of a single segment of an SPK."""
+    center_id: int
+    data_type: str
+    end_epoch: Epoch
+    frame_id: int
+    name: str
+    start_epoch: Epoch
+    target_id: int
+
+    def duration(self) -> Duration:
+        """Returns the duration covered by this segment."""
+
+    def __repr__(self) -> str:
+        """Return repr(self)."""
+
+    def __str__(self) -> str:
+        """Return str(self)."""
+
 @typing.final
 class astro:
-    _all__: list = ["constants", "AzElRange", "Ellipsoid", "Occultation", "Orbit"]
+    _all__: list = ["constants", "AberrationDiagnostics", "AzElRange", "Ellipsoid", "IlluminationAngles", "LagrangePoint", "Occultation", "OccultationKind", "Orbit", "TwoWayRangeDoppler"]
+
+    @typing.final
+    class AberrationDiagnostics:
+        """Diagnostics of the aberration corrections applied in a single translation query.
+
+    This is useful to check how far the light time iteration converged and how large the stellar aberration correction was."""
+        ab_corr: Aberration
+        iterations: int
+        light_time: Duration
+        light_time_residual: Duration
+        stellar_correction_deg: float
+
+        def __repr__(self) -> str:
+            """Return repr(self)."""
+
+        def __str__(self) -> str:
+            """Return str(self)."""
 
     @typing.final
     class AzElRange:
@@ -456,6 +989,26 @@ class astro:
         def __str__(self) -> str:
             """Return str(self)."""
 
+    @typing.final
+    class BPlane:
+        """Stores the B-plane parameters of a hyperbolic orbit.
+
+    The B-plane is the plane perpendicular to the incoming asymptote (S) and containing the center of the central body.
+    The T axis is along S x Z (where Z is the Z axis of the frame of the orbit), and R completes the right-handed set."""
+        b_r_km: float
+        b_t_km: float
+        frame: Frame
+        ltof: Duration
+
+        def b_mag_km(self) -> float:
+            """Returns the magnitude of the B vector in km"""
+
+        def b_theta_deg(self) -> float:
+            """Returns the B-plane angle in degrees, measured from the T axis towards the R axis"""
+
+        def __str__(self) -> str:
+            """Return str(self)."""
+
     @typing.final
     class Ellipsoid:
         """Only the tri-axial Ellipsoid shape model is currently supported by ANISE.
@@ -570,6 +1123,14 @@ class astro:
             """Removes the graviational parameter and the shape information from this frame.
     Use this to prevent astrodynamical computations."""
 
+        @staticmethod
+        def synodic(primary_id: int, secondary_id: int) -> Frame:
+            """Returns the synodic (rotating) frame of the primary and secondary bodies, whose X axis points from the primary to the secondary
+    and whose Z axis is along the angular momentum of the secondary with respect to the primary, or None if either ID is not between 0 and 999."""
+
+        def synodic_bodies(self) -> typing.Tuple:
+            """Returns the IDs of the primary and secondary bodies if this is a synodic frame (cf. `Frame.synodic`)"""
+
         def with_ephem(self, new_ephem_id: int) -> Frame:
             """Returns a copy of this Frame whose ephemeris ID is set to the provided ID"""
 
@@ -606,6 +1167,67 @@ class astro:
         def __str__(self) -> str:
             """Return str(self)."""
 
+    @typing.final
+    class IlluminationAngles:
+        """Stores the illumination angles of a point on the surface of a body, as computed by SPICE's `ilumin`.
+
+    - The phase angle is the angle between the directions from the surface point to the Sun and to the observer.
+    - The incidence angle is the angle between the surface normal and the direction from the surface point to the Sun.
+    - The emission angle is the angle between the surface normal and the direction from the surface point to the observer."""
+        emission_deg: float
+        epoch: Epoch
+        incidence_deg: float
+        phase_deg: float
+
+        def __init__(self, epoch: Epoch, phase_deg: float, incidence_deg: float, emission_deg: float) -> IlluminationAngles:
+            """Stores the illumination angles of a point on the surface of a body, as computed by SPICE's `ilumin`.
+
+    - The phase angle is the angle between the directions from the surface point to the Sun and to the observer.
+    - The incidence angle is the angle between the surface normal and the direction from the surface point to the Sun.
+    - The emission angle is the angle between the surface normal and the direction from the surface point to the observer."""
+
+        def is_lit(self) -> bool:
+            """Returns true if the Sun is above the local horizon of the surface point, i.e. the incidence angle is less than 90 degrees."""
+
+        def is_visible(self) -> bool:
+            """Returns true if the observer is above the local horizon of the surface point, i.e. the emission angle is less than 90 degrees."""
+
+        def __repr__(self) -> str:
+            """Return repr(self)."""
+
+        def __str__(self) -> str:
+            """Return str(self)."""
+
+    @typing.final
+    class LagrangePoint:
+        """Libration (Lagrange) points of a system of two bodies.
+
+    L1 is between the primary and the secondary, L2 is beyond the secondary, L3 is beyond the primary,
+    and L4 (resp. L5) leads (resp. trails) the secondary by 60 degrees on its orbit."""
+
+        def synodic_position(self, mass_ratio: float) -> typing.Tuple:
+            """Returns the position of this libration point in the synodic frame of the circular restricted three body problem, normalized by the distance
+    between the primary and the secondary, and centered on the barycenter: the primary is at (-mass_ratio, 0) and the secondary at (1 - mass_ratio, 0).
+
+    The mass ratio is the gravitational parameter of the secondary divided by the sum of the gravitational parameters of both bodies."""
+
+        def __eq__(self, value: typing.Any) -> bool:
+            """Return self==value."""
+
+        def __int__(self) -> None:
+            """int(self)"""
+
+        def __ne__(self, value: typing.Any) -> bool:
+            """Return self!=value."""
+
+        def __repr__(self) -> str:
+            """Return repr(self)."""
+        L1: LagrangePoint = ...
+        L2: LagrangePoint = ...
+        L3: LagrangePoint = ...
+        L4: LagrangePoint = ...
+        L5: LagrangePoint = ...
+
     @typing.final
     class Occultation:
         """Stores the result of an occultation computation with the occulation percentage
@@ -613,11 +1235,15 @@ class astro:
         back_frame: Frame
         epoch: Epoch
         front_frame: Frame
+        kind: OccultationKind
         percentage: float
 
         def factor(self) -> float:
             """Returns the percentage as a factor between 0 and 1"""
 
+        def is_annular(self) -> bool:
+            """Returns true if the front object lies entirely within the apparent disk of the back object (e.g. annular eclipse or transit)"""
+
         def is_eclipse_computation(self) -> bool:
             """Returns true if the back object is the Sun, false otherwise"""
 
@@ -636,6 +1262,28 @@ class astro:
         def __str__(self) -> str:
             """Return str(self)."""
 
+    @typing.final
+    class OccultationKind:
+        """Classification of an occultation of a back object by a front object, as seen from an observer.
+
+    The integer values match the (positive) codes returned by SPICE's `occult` routine."""
+
+        def __eq__(self, value: typing.Any) -> bool:
+            """Return self==value."""
+
+        def __int__(self) -> None:
+            """int(self)"""
+
+        def __ne__(self, value: typing.Any) -> bool:
+            """Return self!=value."""
+
+        def __repr__(self) -> str:
+            """Return repr(self)."""
+        Annular: OccultationKind = ...
+        Partial: OccultationKind = ...
+        Total: OccultationKind = ...
+        Visible: OccultationKind = ...
+
     @typing.final
     class Orbit:
         """Defines a Cartesian state in a given frame at a given epoch in a given time scale. Radius data is expressed in kilometers. Velocity data is expressed in kilometers per second.
@@ -713,6 +1361,19 @@ class astro:
     This is not a true propagation of the orbit. This is akin to a two body propagation ONLY without any other force models applied.
     Use Nyx for high fidelity propagation."""
 
+        def b_plane(self) -> BPlane:
+            """Computes the B-plane parameters of this hyperbolic orbit, including the partials of B.R, B.T and LTOF
+    with respect to this Cartesian state (computed with central finite differencing).
+
+    The T axis is defined with respect to the Z axis of the frame of this orbit, so the B-plane is consistent with
+    the frame in which the state is expressed (e.g. transform the state to the body's equatorial frame first).
+
+    # Errors
+    + NotHyperbolic if the orbit is not hyperbolic
+    + MissingFrameData if the frame does not have a gravitational parameter
+
+    Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., section 12.5."""
+
         def c3_km2_s2(self) -> float:
             """Returns the $C_3$ of this orbit in km^2/s^2"""
 
@@ -739,12 +1400,22 @@ class astro:
         def fpa_deg(self) -> float:
             """Returns the flight path angle in degrees"""
 
+        @staticmethod
+        def from_brouwer_mean(sma_km: float, ecc: float, inc_deg: float, raan_deg: float, aop_deg: float, ma_deg: float, epoch: Epoch, frame: Frame, j2: float) -> Orbit:
+            """Creates a new osculating Orbit from the Brouwer-Lyddane mean Keplerian elements, accounting for the first order short and
+    long period effects of the provided J2 zonal harmonic of the central body of the frame."""
+
         @staticmethod
         def from_cartesian(x_km: float, y_km: float, z_km: float, vx_km_s: float, vy_km_s: float, vz_km_s: float, epoch: Epoch, frame: Frame) -> Orbit:
             """Creates a new Cartesian state in the provided frame at the provided Epoch.
 
     **Units:** km, km, km, km/s, km/s, km/s"""
 
+        @staticmethod
+        def from_equinoctial(sma_km: float, h: float, k: float, p: float, q: float, mean_long_deg: float, epoch: Epoch, frame: Frame) -> Orbit:
+            """Creates a new Orbit from the equinoctial elements (a, h, k, p, q, λ), where a is the semi-major axis in km
+    and λ is the mean longitude in degrees, as defined in GMAT."""
+
         @staticmethod
         def from_keplerian(sma_km: float, ecc: float, inc_deg: float, raan_deg: float, aop_deg: float, ta_deg: float, epoch: Epoch, frame: Frame) -> Orbit:
             """Creates a new Orbit around the provided Celestial or Geoid frame from the Keplerian orbital elements.
@@ -776,6 +1447,16 @@ class astro:
     using the keplerian(..) method.
     The conversion is from GMAT's MeanToTrueAnomaly function, transliterated originally by Claude and GPT4 with human adjustments."""
 
+        @staticmethod
+        def from_modified_equinoctial(p_km: float, f: float, g: float, h: float, k: float, true_long_deg: float, epoch: Epoch, frame: Frame) -> Orbit:
+            """Creates a new Orbit from the modified equinoctial elements (p, f, g, h, k, L), where p is the semi-parameter in km
+    and L is the true longitude in degrees, as defined in GMAT and Nyx."""
+
+        @staticmethod
+        def from_keplerian_mean_motion(mean_motion_rev_day: float, ecc: float, inc_deg: float, raan_deg: float, aop_deg: float, ma_deg: float, epoch: Epoch, frame: Frame) -> Orbit:
+            """Creates a new Orbit from the Keplerian orbital elements where the semi-major axis is computed from
+    the mean motion, in revolutions per day, as published in general perturbations (GP) catalogs."""
+
         @staticmethod
         def from_latlongalt(latitude_deg: float, longitude_deg: float, height_km: float, angular_velocity: float, epoch: Epoch, frame: Frame) -> Orbit:
             """Creates a new Orbit from the latitude (φ), longitude (λ) and height (in km) with respect to the frame's ellipsoid given the angular velocity.
@@ -849,6 +1530,12 @@ class astro:
 
     This is a conversion from GMAT's StateConversionUtil::TrueToMeanAnomaly"""
 
+        def mean_long_deg(self) -> float:
+            """Returns the mean longitude in degrees, i.e. Ω + ω + M"""
+
+        def mean_motion_rev_day(self) -> float:
+            """Returns the Keplerian mean motion in revolutions per day"""
+
         def periapsis_altitude_km(self) -> float:
             """Returns the altitude of periapsis (or perigee around Earth), in kilometers."""
 
@@ -861,6 +1548,12 @@ class astro:
         def raan_deg(self) -> float:
             """Returns the right ascension of the ascending node in degrees"""
 
+        def radec(self) -> typing.Tuple:
+            """Returns the right ascension (between 0 and 360 degrees), declination (between -90 and 90 degrees), and range (in km) of the position of this state in its frame.
+
+    # SPICE Compatibility
+    This is the SPICE equivalent of recrad, with the range returned last."""
+
         def rel_difference(self, other: Orbit) -> typing.Tuple:
             """Returns the relative difference between this orbit and another for the position and velocity, respectively the first and second return values.
     Both return values are UNITLESS because the relative difference is computed as the absolute difference divided by the rmag and vmag of this object.
@@ -951,6 +1644,21 @@ class astro:
         def tlong_deg(self) -> float:
             """Returns the true longitude in degrees"""
 
+        def to_brouwer_mean(self, j2: float) -> typing.Tuple:
+            """Returns the Brouwer-Lyddane mean Keplerian elements (sma km, ecc, inc deg, raan deg, aop deg, ma deg) of this
+    osculating orbit, accounting for the first order short and long period effects of the provided J2 zonal harmonic
+    of the central body of the frame."""
+
+        def to_equinoctial(self) -> typing.Tuple:
+            """Returns the equinoctial elements (a, h, k, p, q, λ), where a is the semi-major axis in km and λ is the mean longitude in degrees.
+
+    These elements are only defined for elliptical orbits."""
+
+        def to_modified_equinoctial(self) -> typing.Tuple:
+            """Returns the modified equinoctial elements (p, f, g, h, k, L), where p is the semi-parameter in km and L is the true longitude in degrees.
+
+    The conversion is computed from the Cartesian state directly, and is not singular for circular or equatorial orbits."""
+
         def velocity_declination_deg(self) -> float:
             """Returns the velocity declination of this orbit in degrees"""
 
@@ -1022,7 +1730,43 @@ class astro:
 
         def __str__(self) -> str:
             """Return str(self)."""
-    
+
+    @typing.final
+    class TwoWayRangeDoppler:
+        """Stores a two-way range and range rate observable, computed with the light time iteration of both the uplink and the downlink.
+
+    The epoch is the reception epoch of the signal at the location. The signal was transmitted by the location at
+    `epoch - uplink_light_time - downlink_light_time`, and it was turned around by the target at `epoch - downlink_light_time`."""
+        downlink_light_time: Duration
+        epoch: Epoch
+        range_km: float
+        range_rate_km_s: float
+        uplink_light_time: Duration
+
+        def __init__(self, epoch: Epoch, range_km: float, range_rate_km_s: float, uplink_light_time: Duration, downlink_light_time: Duration) -> TwoWayRangeDoppler:
+            """Stores a two-way range and range rate observable, computed with the light time iteration of both the uplink and the downlink.
+
+    The epoch is the reception epoch of the signal at the location. The signal was transmitted by the location at
+    `epoch - uplink_light_time - downlink_light_time`, and it was turned around by the target at `epoch - downlink_light_time`."""
+
+        def doppler_hz(self, uplink_frequency_hz: float, turnaround_ratio: float) -> float:
+            """Returns the two-way Doppler shift in Hertz of a signal transmitted at the uplink frequency (in Hertz) and
+    coherently turned around by the target with the provided turnaround ratio (e.g. 880/749 for X-band).
+
+    The shift is negative when the range increases."""
+
+        def transmit_epoch(self) -> Epoch:
+            """Returns the epoch at which the signal was transmitted by the location."""
+
+        def turnaround_epoch(self) -> Epoch:
+            """Returns the epoch at which the signal was turned around by the target."""
+
+        def __repr__(self) -> str:
+            """Return repr(self)."""
+
+        def __str__(self) -> str:
+            """Return str(self)."""
+
     class constants:
         @typing.final
         class CelestialObjects:
@@ -2218,6 +2962,11 @@ class rotation:
             """Defines a direction cosine matrix from one frame ID to another frame ID, optionally with its time derivative.
     It provides a number of run-time checks that prevent invalid rotations."""
 
+        def angular_velocity_rad_s(self) -> numpy.array:
+            """Returns the angular velocity vector in rad/s of the `to` frame with respect to the `from` frame, expressed in the `to` frame.
+    Raises an exception if the time derivative of this DCM is not set.
+    Warning: you MUST manually install numpy to call this function."""
+
         @staticmethod
         def from_identity(from_id: int, to_id: int) -> DCM:
             """Builds an identity rotation."""
@@ -2279,4 +3028,71 @@ class rotation:
             """Return repr(self)."""
 
         def __str__(self) -> str:
-            """Return str(self)."""
\ No newline at end of file
+            """Return str(self)."""
+
+@typing.final
+class instrument:
+    _all__: list = ["FovShape", "Instrument"]
+
+    class FovShape:
+        """Shape of the field of view of an instrument, defined by its half angles about the boresight, which is the +Z axis of the instrument frame."""
+
+        @typing.final
+        class Circular(FovShape):
+            """Cone of the provided half angle about the boresight"""
+            half_angle_deg: float
+
+            def __init__(self, half_angle_deg: float) -> FovShape.Circular:
+                """Cone of the provided half angle about the boresight"""
+
+        @typing.final
+        class Rectangular(FovShape):
+            """Rectangle of the provided half angles about the X and Y axes of the instrument frame"""
+            x_half_angle_deg: float
+            y_half_angle_deg: float
+
+            def __init__(self, x_half_angle_deg: float, y_half_angle_deg: float) -> FovShape.Rectangular:
+                """Rectangle of the provided half angles about the X and Y axes of the instrument frame"""
+
+    @typing.final
+    class Instrument:
+        """An instrument mounted on a spacecraft, defined by the rotation from the spacecraft body frame to the instrument frame,
+    whose +Z axis is the boresight, and the shape of its field of view."""
+        fov: FovShape
+        mounting: DCM
+
+        def __init__(self, mounting: DCM, fov: FovShape) -> Instrument:
+            """An instrument mounted on a spacecraft, defined by the rotation from the spacecraft body frame to the instrument frame,
+    whose +Z axis is the boresight, and the shape of its field of view."""
+
+        def boresight(self) -> numpy.array:
+            """Returns the boresight of this instrument in the spacecraft body frame, as a vector of size 3."""
+
+        def edge_directions(self, num_points: int) -> numpy.array:
+            """Returns unit vectors regularly spaced on the edge of the field of view of this instrument, in the spacecraft body frame,
+as an array of shape (N, 3)."""
+
+        def is_in_fov(self, sc_state: Orbit, sc_attitude: DCM, target_state: Orbit) -> bool:
+            """Returns whether the target is within the field of view of this instrument, given the spacecraft state and attitude
+(the rotation from the frame of the spacecraft state to the body frame). Both states must be in the same frame and at the same epoch."""
+
+        def __repr__(self) -> str:
+            """Return repr(self)."""
+
+@typing.final
+class analysis:
+
+    @typing.final
+    class PorkchopPlot:
+        """Grids of a porkchop plot. Rows correspond to the departure epochs and columns to the arrival epochs.
+
+    Cells where the arrival is before the departure, or where the Lambert solver failed, are set to NaN."""
+        arrival_epochs: typing.List
+        c3_km2_s2: numpy.array
+        departure_epochs: typing.List
+        tof_days: numpy.array
+        vinf_arrival_km_s: numpy.array
+        vinf_departure_km_s: numpy.array
+
+        def __repr__(self) -> str:
+            """Return repr(self)."""
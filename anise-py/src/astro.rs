@@ -8,8 +8,14 @@
  * Documentation: https://nyxspace.com/
  */
 
+use anise::astro::AberrationDiagnostics;
 use anise::astro::AzElRange;
+use anise::astro::BPlane;
+use anise::astro::IlluminationAngles;
+use anise::astro::LagrangePoint;
 use anise::astro::Occultation;
+use anise::astro::OccultationKind;
+use anise::astro::TwoWayRangeDoppler;
 use anise::structure::planetocentric::ellipsoid::Ellipsoid;
 use pyo3::prelude::*;
 use pyo3::py_run;
@@ -25,7 +31,13 @@ pub(crate) fn register_astro(parent_module: &Bound<'_, PyModule>) -> PyResult<()
     sm.add_class::<Frame>()?;
     sm.add_class::<Orbit>()?;
     sm.add_class::<AzElRange>()?;
+    sm.add_class::<AberrationDiagnostics>()?;
     sm.add_class::<Occultation>()?;
+    sm.add_class::<OccultationKind>()?;
+    sm.add_class::<IlluminationAngles>()?;
+    sm.add_class::<LagrangePoint>()?;
+    sm.add_class::<BPlane>()?;
+    sm.add_class::<TwoWayRangeDoppler>()?;
 
     register_constants(&sm)?;
 
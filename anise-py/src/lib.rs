@@ -8,9 +8,16 @@
  * Documentation: https://nyxspace.com/
  */
 
+use ::anise::almanac::description::{
+    AlmanacDescription, BPCDescription, BPCSegmentDescription, DataSetEntryDescription,
+    SPKDescription, SPKSegmentDescription,
+};
 use ::anise::almanac::metaload::{MetaAlmanac, MetaFile};
+use ::anise::almanac::planetary::{FrameDataSource, FrameInfoSources};
+use ::anise::almanac::query::{QueryOptions, QueryResult};
 use ::anise::almanac::Almanac;
 use ::anise::astro::Aberration;
+use ::anise::naif::daf::datatypes::HermiteWindow;
 use hifitime::leap_seconds::{LatestLeapSeconds, LeapSecondsFile};
 use hifitime::prelude::*;
 use hifitime::ut1::Ut1Provider;
@@ -18,8 +25,10 @@ use hifitime::ut1::Ut1Provider;
 use pyo3::prelude::*;
 use pyo3::py_run;
 
+mod analysis;
 mod astro;
 mod constants;
+mod instrument;
 mod rotation;
 mod utils;
 
@@ -29,12 +38,25 @@ fn anise(m: &Bound<'_, PyModule>) -> PyResult<()> {
     pyo3_log::init();
     register_time_module(m)?;
     astro::register_astro(m)?;
+    analysis::register_analysis(m)?;
+    instrument::register_instrument(m)?;
     utils::register_utils(m)?;
     rotation::register_rotation(m)?;
     m.add_class::<Almanac>()?;
     m.add_class::<Aberration>()?;
     m.add_class::<MetaAlmanac>()?;
     m.add_class::<MetaFile>()?;
+    m.add_class::<AlmanacDescription>()?;
+    m.add_class::<SPKDescription>()?;
+    m.add_class::<BPCDescription>()?;
+    m.add_class::<SPKSegmentDescription>()?;
+    m.add_class::<BPCSegmentDescription>()?;
+    m.add_class::<DataSetEntryDescription>()?;
+    m.add_class::<QueryOptions>()?;
+    m.add_class::<QueryResult>()?;
+    m.add_class::<HermiteWindow>()?;
+    m.add_class::<FrameDataSource>()?;
+    m.add_class::<FrameInfoSources>()?;
     Ok(())
 }
 
@@ -4,9 +4,12 @@ import pickle
 from anise import Almanac, MetaAlmanac
 from anise.astro import *
 from anise.astro.constants import Frames
+from anise.instrument import FovShape, Instrument
 from anise.rotation import DCM
 from anise.time import Epoch
 
+from math import pi
+from numpy import isnan
 from os import environ
 
 
@@ -144,6 +147,63 @@ def test_meta_load():
         assert abs(eme2k.shape.flattening() - 0.0033536422844278) < 2e-16
 
 
+def test_instrument_footprint():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    ctx = Almanac(str(data_path.joinpath("de440s.bsp"))).load(
+        str(data_path.joinpath("pck08.pca"))
+    )
+
+    epoch = Epoch("2024-03-20 00:00:00 UTC")
+    eme2k = ctx.frame_info(Frames.EME2000)
+    sc = Orbit.from_cartesian(7000.0, 0.0, 0.0, 0.0, 7.5, 0.0, epoch, eme2k)
+    # Body aligned with J2000, boresight along the body -X axis, i.e. nadir
+    attitude = DCM.from_identity(1, -100)
+    instrument = Instrument(
+        DCM.from_r2(-pi / 2, -100, -100_001), FovShape.Circular(half_angle_deg=5.0)
+    )
+
+    assert abs(instrument.boresight()[0] + 1.0) < 1e-12
+    assert instrument.edge_directions(12).shape == (12, 3)
+    assert instrument.is_in_fov(
+        sc, attitude, Orbit.from_cartesian(0.0, 0.0, 0.0, 0.0, 0.0, 0.0, epoch, eme2k)
+    )
+
+    footprint = ctx.compute_footprint(
+        instrument, sc, attitude, Frames.IAU_EARTH_FRAME, 36
+    )
+    assert footprint.shape == (36, 2)
+    assert not isnan(footprint).any()
+
+    # Looking away from the Earth
+    instrument.mounting = DCM.from_r2(pi / 2, -100, -100_001)
+    footprint = ctx.compute_footprint(
+        instrument, sc, attitude, Frames.IAU_EARTH_FRAME, 8
+    )
+    assert isnan(footprint).all()
+
+
+def test_summaries():
+    data_path = Path(__file__).parent.joinpath("..", "..", "data")
+    ctx = Almanac(str(data_path.joinpath("de440s.bsp"))).load(
+        str(data_path.joinpath("gmat-hermite.bsp"))
+    )
+
+    summaries = ctx.spk_summaries()
+    assert len(summaries) == 15
+    # Most recently loaded first
+    assert summaries[0].target_id == -10000001
+
+    moon = ctx.spk_summaries(301)
+    assert len(moon) == 1
+    assert moon[0].center_id == 3
+    assert moon[0].frame_id == 1
+    assert moon[0].data_type == "Chebyshev Triplet"
+    assert moon[0].start_epoch < moon[0].end_epoch
+    print(moon[0])
+
+    assert ctx.bpc_summaries() == []
+
+
 def test_exports():
     for cls in [Frame, Ellipsoid, Orbit]:
         print(f"{cls} OK")
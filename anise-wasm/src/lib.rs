@@ -0,0 +1,173 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::str::FromStr;
+
+use anise::math::rotation::Quaternion;
+use anise::prelude::{Aberration, Frame};
+use bytes::Bytes;
+use hifitime::Epoch;
+use wasm_bindgen::prelude::*;
+
+fn to_js_error<E: core::fmt::Display>(e: E) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+fn parse_epoch(epoch: &str) -> Result<Epoch, JsError> {
+    Epoch::from_str(epoch).map_err(to_js_error)
+}
+
+fn parse_aberration(ab_corr: Option<String>) -> Result<Option<Aberration>, JsError> {
+    match ab_corr {
+        Some(flag) => Aberration::new(&flag).map_err(to_js_error),
+        None => Ok(None),
+    }
+}
+
+/// An Almanac for JavaScript: kernels are loaded from their bytes (e.g. the ArrayBuffer of a `fetch` response wrapped in a Uint8Array)
+/// since the browser has no file system.
+///
+/// Frames are specified by their ephemeris and orientation NAIF IDs, and epochs as strings (e.g. "2024-01-01T00:00:00 UTC").
+/// Like the Rust Almanac, loading returns a new Almanac and leaves this one unchanged.
+#[wasm_bindgen]
+#[derive(Clone, Default)]
+pub struct Almanac {
+    inner: anise::prelude::Almanac,
+}
+
+#[wasm_bindgen]
+impl Almanac {
+    /// Initializes an empty Almanac.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the provided bytes of an SPK, BPC, or ANISE data set, identified by the provided name (e.g. its file name).
+    pub fn load(&self, data: &[u8], name: &str) -> Result<Almanac, JsError> {
+        Ok(Self {
+            inner: self
+                .inner
+                .load_from_bytes_as(Bytes::copy_from_slice(data), name)
+                .map_err(to_js_error)?,
+        })
+    }
+
+    /// Unloads the kernels loaded with the provided name.
+    pub fn unload(&self, name: &str) -> Result<Almanac, JsError> {
+        Ok(Self {
+            inner: self.inner.unload(name).map_err(to_js_error)?,
+        })
+    }
+
+    /// Returns the names of the loaded SPK and BPC kernels.
+    #[wasm_bindgen(js_name = kernelNames)]
+    pub fn kernel_names(&self) -> Vec<String> {
+        self.inner.kernel_aliases()
+    }
+
+    /// Returns the number of loaded SPK kernels.
+    #[wasm_bindgen(js_name = numLoadedSpk)]
+    pub fn num_loaded_spk(&self) -> usize {
+        self.inner.num_loaded_spk()
+    }
+
+    /// Returns the number of loaded BPC kernels.
+    #[wasm_bindgen(js_name = numLoadedBpc)]
+    pub fn num_loaded_bpc(&self) -> usize {
+        self.inner.num_loaded_bpc()
+    }
+
+    /// Returns the position (km) and velocity (km/s) of the target frame with respect to the observer frame
+    /// as `[x, y, z, vx, vy, vz]`, in the orientation of the observer frame, optionally corrected for aberration (e.g. "LT+S").
+    pub fn transform(
+        &self,
+        target_id: i32,
+        target_orientation_id: i32,
+        observer_id: i32,
+        observer_orientation_id: i32,
+        epoch: &str,
+        ab_corr: Option<String>,
+    ) -> Result<Vec<f64>, JsError> {
+        let state = self
+            .inner
+            .transform(
+                Frame::new(target_id, target_orientation_id),
+                Frame::new(observer_id, observer_orientation_id),
+                parse_epoch(epoch)?,
+                parse_aberration(ab_corr)?,
+            )
+            .map_err(to_js_error)?;
+
+        Ok(state.to_cartesian_pos_vel().iter().copied().collect())
+    }
+
+    /// Returns the position (km) and velocity (km/s) of the target with respect to the observer as `[x, y, z, vx, vy, vz]`
+    /// without any rotation, i.e. in the J2000 orientation, optionally corrected for aberration (e.g. "LT+S").
+    pub fn translate(
+        &self,
+        target_id: i32,
+        observer_id: i32,
+        epoch: &str,
+        ab_corr: Option<String>,
+    ) -> Result<Vec<f64>, JsError> {
+        let state = self
+            .inner
+            .translate(
+                Frame::from_ephem_j2000(target_id),
+                Frame::from_ephem_j2000(observer_id),
+                parse_epoch(epoch)?,
+                parse_aberration(ab_corr)?,
+            )
+            .map_err(to_js_error)?;
+
+        Ok(state.to_cartesian_pos_vel().iter().copied().collect())
+    }
+
+    /// Returns the direction cosine matrix rotating vectors from one orientation to another, as 9 numbers in row-major order.
+    pub fn rotate(
+        &self,
+        from_orientation_id: i32,
+        to_orientation_id: i32,
+        epoch: &str,
+    ) -> Result<Vec<f64>, JsError> {
+        let dcm = self
+            .inner
+            .rotate(
+                Frame::new(0, from_orientation_id),
+                Frame::new(0, to_orientation_id),
+                parse_epoch(epoch)?,
+            )
+            .map_err(to_js_error)?;
+
+        Ok(dcm.rot_mat.transpose().iter().copied().collect())
+    }
+
+    /// Returns the rotation from one orientation to another as a quaternion `[w, x, y, z]`.
+    #[wasm_bindgen(js_name = rotateQuaternion)]
+    pub fn rotate_quaternion(
+        &self,
+        from_orientation_id: i32,
+        to_orientation_id: i32,
+        epoch: &str,
+    ) -> Result<Vec<f64>, JsError> {
+        let dcm = self
+            .inner
+            .rotate(
+                Frame::new(0, from_orientation_id),
+                Frame::new(0, to_orientation_id),
+                parse_epoch(epoch)?,
+            )
+            .map_err(to_js_error)?;
+
+        let q = Quaternion::from(dcm);
+        Ok(vec![q.w, q.x, q.y, q.z])
+    }
+}
@@ -0,0 +1,54 @@
+#!/usr/bin/env bash
+# Compares the Criterion benchmarks of the working tree against those of a git reference (default: master).
+#
+# Usage (from the `anise` folder): ./benches/compare_baseline.sh [git ref] [bench name...]
+#
+# The benchmarks of the reference are run in a temporary git worktree and saved as a Criterion baseline named after the reference,
+# then the benchmarks of the working tree are compared against it (benchmarks missing from the baseline are simply run). Criterion reports each change with its confidence interval,
+# and flags it as a regression or an improvement when it exceeds the noise threshold.
+#
+# Both runs share the same target folder so that Criterion finds the baseline. Run this on an otherwise idle machine.
+set -euo pipefail
+
+BASE_REF="${1:-master}"
+shift || true
+BENCHES=("$@")
+if [ ${#BENCHES[@]} -eq 0 ]; then
+    BENCHES=(crit_jpl_ephemerides crit_spacecraft_ephemeris crit_bpc_rotation crit_planetary_data crit_transform_chains crit_event_search crit_daf_parse)
+fi
+
+CRATE_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+REPO_DIR="$(git -C "$CRATE_DIR" rev-parse --show-toplevel)"
+export CARGO_TARGET_DIR="${CARGO_TARGET_DIR:-$REPO_DIR/target}"
+BASELINE="$(echo "$BASE_REF" | tr '/' '_')"
+
+WORKTREE="$(mktemp -d)"
+cleanup() {
+    git -C "$REPO_DIR" worktree remove --force "$WORKTREE" || true
+}
+trap cleanup EXIT
+
+git -C "$REPO_DIR" worktree add --detach "$WORKTREE" "$BASE_REF"
+# The kernels are large and may not be checked out in the worktree, so reuse those of this repository.
+rm -rf "$WORKTREE/data"
+ln -s "$REPO_DIR/data" "$WORKTREE/data"
+
+bench_args() {
+    for bench in "${BENCHES[@]}"; do
+        printf -- "--bench %s " "$bench"
+    done
+}
+
+echo "Running benchmarks of $BASE_REF as baseline \`$BASELINE\`"
+# Benchmarks which do not exist in the reference are skipped.
+for bench in "${BENCHES[@]}"; do
+    if [ -f "$WORKTREE/anise/benches/$bench.rs" ]; then
+        (cd "$WORKTREE/anise" && cargo bench --bench "$bench" -- --save-baseline "$BASELINE")
+    else
+        echo "Skipping $bench: not in $BASE_REF"
+    fi
+done
+
+echo "Comparing the working tree against \`$BASELINE\`"
+# shellcheck disable=SC2046
+(cd "$CRATE_DIR" && cargo bench $(bench_args) -- --baseline-lenient "$BASELINE")
@@ -1,7 +1,12 @@
-use anise::{constants::orientations::ITRF93, prelude::*};
+use anise::{
+    constants::frames::{EARTH_ITRF93, EARTH_J2000},
+    constants::orientations::ITRF93,
+    prelude::*,
+};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 const NUM_QUERIES_PER_PAIR: f64 = 100.0;
+const NUM_GROUND_STATIONS: usize = 10;
 
 fn benchmark_spice_single_hop_type2_cheby(time_it: TimeSeries) {
     for epoch in time_it {
@@ -22,6 +27,14 @@ fn benchmark_anise_single_hop_type2_cheby(ctx: &Almanac, time_it: TimeSeries) {
     }
 }
 
+fn benchmark_anise_ground_stations_to_j2000(ctx: &Almanac, time_it: TimeSeries) {
+    for epoch in time_it {
+        for _ in 0..NUM_GROUND_STATIONS {
+            black_box(ctx.rotate(EARTH_ITRF93, EARTH_J2000, epoch).unwrap());
+        }
+    }
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     let start_epoch = Epoch::from_gregorian_at_noon(2012, 1, 1, TimeScale::ET);
     let end_epoch = Epoch::from_gregorian_at_noon(2021, 1, 1, TimeScale::ET);
@@ -40,6 +53,16 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("SPICE DAF/BPC single hop to parent", |b| {
         b.iter(|| benchmark_spice_single_hop_type2_cheby(time_it.clone()))
     });
+
+    c.bench_function("ANISE ITRF93 to J2000 for each ground station", |b| {
+        b.iter(|| benchmark_anise_ground_stations_to_j2000(&almanac, time_it.clone()))
+    });
+
+    let cached = almanac.with_dcm_cache(16, Unit::Millisecond * 1);
+    c.bench_function(
+        "ANISE ITRF93 to J2000 for each ground station with DCM cache",
+        |b| b.iter(|| benchmark_anise_ground_stations_to_j2000(&cached, time_it.clone())),
+    );
 }
 
 criterion_group!(bpc, criterion_benchmark);
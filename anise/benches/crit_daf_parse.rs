@@ -0,0 +1,35 @@
+use anise::{file2heap, prelude::*};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let de440s = file2heap!("../data/de440s.bsp").unwrap();
+    let earth_bpc = file2heap!("../data/earth_latest_high_prec.bpc").unwrap();
+
+    c.bench_function("ANISE DAF/SPK parse de440s", |b| {
+        b.iter(|| black_box(SPK::parse(de440s.clone()).unwrap()))
+    });
+
+    c.bench_function("ANISE DAF/SPK lazy parse de440s", |b| {
+        b.iter(|| black_box(SPK::parse_lazy(de440s.clone()).unwrap()))
+    });
+
+    c.bench_function("ANISE DAF/BPC parse Earth high precision", |b| {
+        b.iter(|| black_box(BPC::parse(earth_bpc.clone()).unwrap()))
+    });
+
+    c.bench_function("ANISE DAF/SPK load de440s from disk", |b| {
+        b.iter(|| black_box(SPK::load("../data/de440s.bsp").unwrap()))
+    });
+
+    c.bench_function("ANISE DAF/SPK lazy load de440s from disk", |b| {
+        b.iter(|| black_box(unsafe { SPK::load_lazy("../data/de440s.bsp") }.unwrap()))
+    });
+
+    let spk = SPK::parse(de440s).unwrap();
+    c.bench_function("ANISE DAF/SPK summaries de440s", |b| {
+        b.iter(|| black_box(spk.data_summaries().unwrap()))
+    });
+}
+
+criterion_group!(daf, criterion_benchmark);
+criterion_main!(daf);
@@ -0,0 +1,45 @@
+use anise::{
+    constants::frames::{EARTH_J2000, MOON_J2000},
+    prelude::*,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Sweeps the eclipse state of a low Earth orbit over one day: this is the evaluation kernel of any eclipse event search.
+fn benchmark_eclipse_sweep(almanac: &Almanac, orbit: Orbit, time_it: TimeSeries) {
+    for epoch in time_it {
+        let state = orbit.at_epoch(epoch).unwrap();
+        black_box(almanac.solar_eclipsing(EARTH_J2000, state, None).unwrap());
+    }
+}
+
+/// Sweeps the occultation of the Sun by the Moon as seen from a low Earth orbit, which requires an additional translation per step.
+fn benchmark_occultation_sweep(almanac: &Almanac, orbit: Orbit, time_it: TimeSeries) {
+    for epoch in time_it {
+        let state = orbit.at_epoch(epoch).unwrap();
+        black_box(almanac.solar_eclipsing(MOON_J2000, state, None).unwrap());
+    }
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck11.pca")
+        .unwrap();
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 4, 8);
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let orbit = Orbit::keplerian(6_800.0, 1e-4, 51.6, 30.0, 60.0, 90.0, epoch, eme2k);
+
+    let time_it = TimeSeries::exclusive(epoch, epoch + 1.days(), 1.minutes());
+
+    c.bench_function("ANISE eclipse sweep over one day", |b| {
+        b.iter(|| benchmark_eclipse_sweep(&almanac, orbit, time_it.clone()))
+    });
+
+    c.bench_function("ANISE lunar occultation sweep over one day", |b| {
+        b.iter(|| benchmark_occultation_sweep(&almanac, orbit, time_it.clone()))
+    });
+}
+
+criterion_group!(events, criterion_benchmark);
+criterion_main!(events);
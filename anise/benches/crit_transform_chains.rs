@@ -0,0 +1,99 @@
+use anise::{
+    constants::frames::{
+        EARTH_ITRF93, EARTH_J2000, IAU_MARS_FRAME, MARS_BARYCENTER_J2000, MOON_J2000,
+        MOON_PA_DE440_FRAME,
+    },
+    prelude::*,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const NUM_QUERIES: f64 = 100.0;
+
+fn benchmark_translation_chain(almanac: &Almanac, time_it: TimeSeries) {
+    for epoch in time_it {
+        black_box(
+            almanac
+                .translate_geometric(MOON_J2000, MARS_BARYCENTER_J2000, epoch)
+                .unwrap(),
+        );
+    }
+}
+
+fn benchmark_transform_chain(almanac: &Almanac, time_it: TimeSeries) {
+    for epoch in time_it {
+        let orbit = Orbit::keplerian(7_000.0, 1e-3, 51.6, 30.0, 60.0, 90.0, epoch, EARTH_ITRF93);
+        black_box(
+            almanac
+                .transform_to(orbit, MOON_PA_DE440_FRAME, None)
+                .unwrap(),
+        );
+    }
+}
+
+fn benchmark_rotation_chain(almanac: &Almanac, time_it: TimeSeries) {
+    for epoch in time_it {
+        black_box(
+            almanac
+                .rotate(EARTH_ITRF93, MOON_PA_DE440_FRAME, epoch)
+                .unwrap(),
+        );
+        black_box(almanac.rotate(EARTH_J2000, IAU_MARS_FRAME, epoch).unwrap());
+    }
+}
+
+fn benchmark_sequential_type13(spk_almanac: &Almanac, frame: Frame, time_it: TimeSeries) {
+    for epoch in time_it {
+        black_box(
+            spk_almanac
+                .translate_geometric(frame, EARTH_J2000, epoch)
+                .unwrap(),
+        );
+    }
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let start_epoch = Epoch::from_gregorian_at_noon(2020, 1, 1, TimeScale::ET);
+    let end_epoch = Epoch::from_gregorian_at_noon(2025, 1, 1, TimeScale::ET);
+    let time_step = ((end_epoch - start_epoch).to_seconds() / NUM_QUERIES).seconds();
+    let time_it = TimeSeries::exclusive(start_epoch, end_epoch - time_step, time_step);
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap()
+        .load("../data/moon_pa_de440_200625.bpc")
+        .unwrap()
+        .load("../data/pck11.pca")
+        .unwrap();
+
+    c.bench_function("ANISE translation chain Moon to Mars barycenter", |b| {
+        b.iter(|| benchmark_translation_chain(&almanac, time_it.clone()))
+    });
+
+    c.bench_function("ANISE transform chain ITRF93 to Moon PA", |b| {
+        b.iter(|| benchmark_transform_chain(&almanac, time_it.clone()))
+    });
+
+    c.bench_function(
+        "ANISE rotation chains ITRF93 to Moon PA and J2000 to IAU Mars",
+        |b| b.iter(|| benchmark_rotation_chain(&almanac, time_it.clone())),
+    );
+
+    // Sequential queries of a Type 13 spacecraft ephemeris, which benefit from the record index cache.
+    let hermite = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/gmat-hermite.bsp")
+        .unwrap();
+    let (summary, _, _) = hermite.spk_summary_from_name("SPK_SEGMENT").unwrap();
+    let sc_frame = Frame::from_ephem_j2000(summary.target_id);
+    let (start, end) = (summary.start_epoch(), summary.end_epoch());
+    let sc_step = ((end - start).to_seconds() / 1000.0).seconds();
+    let sc_time_it = TimeSeries::inclusive(start, end - sc_step, sc_step);
+
+    c.bench_function("ANISE sequential Type 13 queries", |b| {
+        b.iter(|| benchmark_sequential_type13(&hermite, sc_frame, sc_time_it.clone()))
+    });
+}
+
+criterion_group!(chains, criterion_benchmark);
+criterion_main!(chains);
@@ -0,0 +1,27 @@
+#![no_main]
+use anise::naif::daf::datatypes::Type2ChebyshevSet;
+use anise::naif::daf::{DafDataType, NAIFDataSet, NAIFSummaryRecord};
+use anise::naif::BPC;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(bpc) = BPC::parse(data) else {
+        return;
+    };
+
+    let _ = bpc.comments();
+    let _ = bpc.daf_summary();
+
+    for (idx, summary) in bpc.data_summaries().unwrap_or_default().iter().enumerate() {
+        let _ = bpc.nth_name(idx);
+        // Only evaluate at valid epochs, we're fuzzing the decoding of the data, not hifitime.
+        if !summary.start_epoch_et_s().is_finite() || !summary.end_epoch_et_s().is_finite() {
+            continue;
+        }
+        if let Ok(DafDataType::Type2ChebyshevTriplet) = summary.data_type() {
+            if let Ok(dataset) = bpc.nth_data::<Type2ChebyshevSet>(idx) {
+                let _ = dataset.evaluate(summary.start_epoch(), summary);
+            }
+        }
+    }
+});
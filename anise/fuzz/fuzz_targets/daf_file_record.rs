@@ -0,0 +1,17 @@
+#![no_main]
+use anise::naif::daf::FileRecord;
+use libfuzzer_sys::fuzz_target;
+use zerocopy::FromBytes;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((file_record, _)) = FileRecord::read_from_prefix(data) {
+        let _ = file_record.ni();
+        let _ = file_record.nd();
+        let _ = file_record.fwrd_idx();
+        let _ = file_record.summary_size();
+        let _ = file_record.identification();
+        let _ = file_record.endianness();
+        let _ = file_record.internal_filename();
+        let _ = file_record.is_empty();
+    }
+});
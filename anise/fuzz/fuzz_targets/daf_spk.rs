@@ -0,0 +1,41 @@
+#![no_main]
+use anise::naif::daf::datatypes::{HermiteSetType13, LagrangeSetType9, Type2ChebyshevSet};
+use anise::naif::daf::{DafDataType, NAIFDataSet, NAIFSummaryRecord};
+use anise::naif::SPK;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(spk) = SPK::parse(data) else {
+        return;
+    };
+
+    let _ = spk.comments();
+    let _ = spk.daf_summary();
+
+    for (idx, summary) in spk.data_summaries().unwrap_or_default().iter().enumerate() {
+        let _ = spk.nth_name(idx);
+        // Only evaluate at valid epochs, we're fuzzing the decoding of the data, not hifitime.
+        if !summary.start_epoch_et_s().is_finite() || !summary.end_epoch_et_s().is_finite() {
+            continue;
+        }
+        let epoch = summary.start_epoch();
+        match summary.data_type() {
+            Ok(DafDataType::Type2ChebyshevTriplet) => {
+                if let Ok(dataset) = spk.nth_data::<Type2ChebyshevSet>(idx) {
+                    let _ = dataset.evaluate(epoch, summary);
+                }
+            }
+            Ok(DafDataType::Type9LagrangeUnequalStep) => {
+                if let Ok(dataset) = spk.nth_data::<LagrangeSetType9>(idx) {
+                    let _ = dataset.evaluate(epoch, summary);
+                }
+            }
+            Ok(DafDataType::Type13HermiteUnequalStep) => {
+                if let Ok(dataset) = spk.nth_data::<HermiteSetType13>(idx) {
+                    let _ = dataset.evaluate(epoch, summary);
+                }
+            }
+            _ => {}
+        }
+    }
+});
@@ -0,0 +1,33 @@
+#![no_main]
+use anise::naif::daf::datatypes::HermiteSetType13;
+use anise::naif::daf::NAIFDataSet;
+use anise::naif::spk::summary::SPKSummaryRecord;
+use hifitime::Epoch;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let doubles = data
+        .chunks_exact(8)
+        .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect::<Vec<f64>>();
+
+    let Ok(dataset) = HermiteSetType13::from_f64_slice(&doubles) else {
+        return;
+    };
+    if dataset.check_integrity().is_err() {
+        return;
+    }
+
+    let summary = SPKSummaryRecord::default();
+    for n in 0..dataset.num_records.min(8) {
+        let _ = dataset.nth_record(n);
+    }
+    // Evaluate at and between the first epochs
+    for window in dataset.epoch_data.windows(2).take(4) {
+        let _ = dataset.evaluate(Epoch::from_et_seconds(window[0]), &summary);
+        let _ = dataset.evaluate(
+            Epoch::from_et_seconds(0.5 * (window[0] + window[1])),
+            &summary,
+        );
+    }
+});
@@ -0,0 +1,36 @@
+#![no_main]
+use anise::naif::daf::datatypes::Type2ChebyshevSet;
+use anise::naif::daf::NAIFDataSet;
+use anise::naif::spk::summary::SPKSummaryRecord;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let doubles = data
+        .chunks_exact(8)
+        .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect::<Vec<f64>>();
+
+    let Ok(dataset) = Type2ChebyshevSet::from_f64_slice(&doubles) else {
+        return;
+    };
+    if dataset.check_integrity().is_err() {
+        return;
+    }
+
+    let start_epoch_et_s = dataset.init_epoch.to_et_seconds();
+    let end_epoch_et_s =
+        start_epoch_et_s + dataset.interval_length.to_seconds() * dataset.num_records as f64;
+    if !end_epoch_et_s.is_finite() {
+        return;
+    }
+    let summary = SPKSummaryRecord {
+        start_epoch_et_s,
+        end_epoch_et_s,
+        ..Default::default()
+    };
+
+    for n in 0..dataset.num_records.min(8) {
+        let _ = dataset.nth_record(n);
+    }
+    let _ = dataset.evaluate(dataset.init_epoch, &summary);
+});
@@ -0,0 +1,55 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use tokio::task::{spawn_blocking, JoinError};
+
+use crate::errors::{AlmanacError, AlmanacResult};
+
+use super::Almanac;
+
+impl Almanac {
+    /// Loads the provided path as one of the data types supported in ANISE, without blocking the async runtime.
+    ///
+    /// Reading and parsing a kernel is blocking work, so it is run on the blocking thread pool of the Tokio runtime.
+    /// This function must be called from within a Tokio runtime.
+    pub async fn load_async(&self, path: &str) -> AlmanacResult<Self> {
+        let almanac = self.clone();
+        let path = path.to_string();
+        spawn_blocking(move || almanac.load(&path))
+            .await
+            .map_err(join_error)?
+    }
+}
+
+/// Converts a failed blocking task (panic or cancellation) into an Almanac error.
+pub(crate) fn join_error(e: JoinError) -> AlmanacError {
+    AlmanacError::GenericError {
+        err: format!("blocking task failed: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod ut_async {
+    use crate::prelude::Almanac;
+
+    #[tokio::test]
+    async fn load_async() {
+        let almanac = Almanac::default()
+            .load_async("../data/de440s.bsp")
+            .await
+            .unwrap();
+        assert_eq!(almanac.num_loaded_spk(), 1);
+
+        assert!(Almanac::default()
+            .load_async("../data/does-not-exist.bsp")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,147 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::Almanac;
+use crate::{
+    ephemerides::EphemerisError,
+    errors::{AlmanacError, AlmanacResult},
+    naif::bodies::{
+        body_id_from_name, body_name_from_id, body_names_from_kernel, normalize_body_name,
+    },
+    structure::dataset::DataSetError,
+    NaifId,
+};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns the NAIF ID of the provided body name, ignoring case and repeated whitespaces, e.g. "Mars Reconnaissance Orbiter" returns -74.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of bods2c: the body names loaded in this Almanac take precedence over the built-in names,
+    /// the latest definition of a name takes precedence over earlier ones, and a NAIF ID provided as a string is returned as is.
+    ///
+    /// :type name: str
+    /// :rtype: int
+    pub fn naif_id_from_name(&self, name: &str) -> Result<NaifId, EphemerisError> {
+        let normalized = normalize_body_name(name);
+
+        self.body_names
+            .iter()
+            .rev()
+            .find(|(body_name, _)| *body_name == normalized)
+            .map(|(_, id)| *id)
+            .or_else(|| body_id_from_name(&normalized))
+            .or_else(|| normalized.parse::<NaifId>().ok())
+            .ok_or(EphemerisError::NameToId {
+                name: name.to_string(),
+            })
+    }
+
+    /// Returns the name of the provided NAIF ID, e.g. -74 returns "MARS RECONNAISSANCE ORBITER".
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of bodc2n: the latest name loaded in this Almanac for this ID takes precedence over the built-in names.
+    ///
+    /// :type id: int
+    /// :rtype: str
+    pub fn naif_name_from_id(&self, id: NaifId) -> Result<String, EphemerisError> {
+        self.body_names
+            .iter()
+            .rev()
+            .find(|(_, body_id)| *body_id == id)
+            .map(|(name, _)| name.clone())
+            .or_else(|| body_name_from_id(id).map(|name| name.to_string()))
+            .ok_or(EphemerisError::IdToName { id })
+    }
+
+    /// Adds the provided body name and NAIF ID to a clone of this Almanac, taking precedence over any previous definition of that name.
+    ///
+    /// :type name: str
+    /// :type id: int
+    /// :rtype: Almanac
+    pub fn with_body_name(&self, name: &str, id: NaifId) -> Self {
+        let mut me = self.clone();
+        me.body_names.push((normalize_body_name(name), id));
+        me
+    }
+
+    /// Loads the body names and NAIF IDs defined by the `NAIF_BODY_NAME` and `NAIF_BODY_CODE` variables of the provided text kernel
+    /// (e.g. a mission frame kernel) into a clone of this Almanac.
+    ///
+    /// :type path: str
+    /// :rtype: Almanac
+    pub fn load_body_names(&self, path: &str) -> AlmanacResult<Self> {
+        let kernel = std::fs::read_to_string(path).map_err(|source| AlmanacError::TLDataSet {
+            action: "loading body names",
+            source: DataSetError::IO {
+                action: "reading text kernel",
+                source,
+            },
+        })?;
+
+        let names = body_names_from_kernel(&kernel).map_err(|source| AlmanacError::TLDataSet {
+            action: "loading body names",
+            source,
+        })?;
+
+        let mut me = self.clone();
+        me.body_names.extend(names);
+        Ok(me)
+    }
+}
+
+#[cfg(test)]
+mod ut_bodies {
+    use crate::prelude::Almanac;
+
+    #[test]
+    fn naif_names() {
+        let almanac = Almanac::default();
+
+        assert_eq!(
+            almanac
+                .naif_id_from_name("MARS RECONNAISSANCE ORBITER")
+                .unwrap(),
+            -74
+        );
+        assert_eq!(almanac.naif_id_from_name("-10000001").unwrap(), -10000001);
+        assert!(almanac.naif_id_from_name("My Spacecraft").is_err());
+        assert_eq!(almanac.naif_name_from_id(399014).unwrap(), "DSS-14");
+        assert!(almanac.naif_name_from_id(-10000001).is_err());
+
+        // Runtime definitions take precedence over the built-in names.
+        let almanac = almanac
+            .with_body_name("My   Spacecraft", -10000001)
+            .with_body_name("MRO", -1234);
+
+        assert_eq!(
+            almanac.naif_id_from_name("my spacecraft").unwrap(),
+            -10000001
+        );
+        assert_eq!(
+            almanac.naif_name_from_id(-10000001).unwrap(),
+            "MY SPACECRAFT"
+        );
+        assert_eq!(almanac.naif_id_from_name("MRO").unwrap(), -1234);
+        assert_eq!(
+            almanac
+                .naif_id_from_name("Mars Reconnaissance Orbiter")
+                .unwrap(),
+            -74
+        );
+
+        assert!(almanac
+            .load_body_names("../data/does-not-exist.tf")
+            .is_err());
+    }
+}
@@ -177,17 +177,11 @@ impl Almanac {
             source: DAFError::SummaryIdError { kind: "BPC", id },
         })
     }
-}
 
-#[cfg_attr(feature = "python", pymethods)]
-impl Almanac {
     /// Returns a vector of the summaries whose ID matches the desired `id`, in the order in which they will be used, i.e. in reverse loading order.
     ///
     /// # Warning
     /// This function performs a memory allocation.
-    ///
-    /// :type id: int
-    /// :rtype: typing.List
     pub fn bpc_summaries(&self, id: NaifId) -> Result<Vec<BPCSummaryRecord>, OrientationError> {
         let mut summaries = vec![];
 
@@ -212,7 +206,10 @@ impl Almanac {
             Ok(summaries)
         }
     }
+}
 
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
     /// Returns the applicable domain of the request id, i.e. start and end epoch that the provided id has loaded data.
     ///
     /// :type id: int
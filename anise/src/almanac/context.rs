@@ -0,0 +1,582 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::fs::File;
+use std::io::{Error as IOError, ErrorKind as IOErrorKind, Write};
+use std::path::Path;
+
+use bytes::Bytes;
+use der::Encode;
+use log::info;
+use snafu::ResultExt;
+
+use super::planetary::FrameOverride;
+use super::Almanac;
+use crate::errors::{AlmanacError, AlmanacResult, DecodingError, LoadingSnafu};
+use crate::file2heap;
+use crate::structure::dataset::DataSetError;
+use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+use crate::NaifId;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Magic bytes at the start of an Almanac context file
+const CONTEXT_MAGIC: &[u8; 8] = b"ANISECTX";
+/// Version of the layout of the Almanac context files
+const CONTEXT_VERSION: u32 = 1;
+
+/// Kind of each entry of an Almanac context file
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum ContextEntry {
+    Spk = 1,
+    Bpc = 2,
+    PlanetaryData = 3,
+    SpacecraftData = 4,
+    EulerParameterData = 5,
+    LocationData = 6,
+    KernelPool = 7,
+    BodyNames = 8,
+    FrameOverride = 9,
+}
+
+impl TryFrom<u8> for ContextEntry {
+    type Error = AlmanacError;
+
+    fn try_from(kind: u8) -> Result<Self, Self::Error> {
+        match kind {
+            1 => Ok(Self::Spk),
+            2 => Ok(Self::Bpc),
+            3 => Ok(Self::PlanetaryData),
+            4 => Ok(Self::SpacecraftData),
+            5 => Ok(Self::EulerParameterData),
+            6 => Ok(Self::LocationData),
+            7 => Ok(Self::KernelPool),
+            8 => Ok(Self::BodyNames),
+            9 => Ok(Self::FrameOverride),
+            _ => Err(AlmanacError::GenericError {
+                err: format!("unknown entry kind {kind} in Almanac context"),
+            }),
+        }
+    }
+}
+
+/// Appends an entry to the context buffer: its kind, its alias (empty if none), and its data, with little endian lengths.
+fn push_entry(buf: &mut Vec<u8>, kind: ContextEntry, alias: Option<&str>, data: &[u8]) {
+    let alias = alias.unwrap_or_default().as_bytes();
+    buf.push(kind as u8);
+    buf.extend_from_slice(&(alias.len() as u32).to_le_bytes());
+    buf.extend_from_slice(alias);
+    buf.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    buf.extend_from_slice(data);
+}
+
+/// Reads the little endian integer of N bytes at the cursor, advancing it.
+fn read_le<const N: usize>(bytes: &[u8], cursor: &mut usize) -> AlmanacResult<[u8; N]> {
+    let value = bytes
+        .get(*cursor..*cursor + N)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or(AlmanacError::GenericError {
+            err: format!("Almanac context truncated at byte {cursor}"),
+        })?;
+    *cursor += N;
+    Ok(value)
+}
+
+/// Encodes the override of the frames centered on the provided body: its ID, flags of the set data (bit 0 for the gravitational parameter,
+/// bit 1 for the shape), and the set data, all little endian.
+fn encode_frame_override(ephemeris_id: NaifId, frame_override: &FrameOverride) -> Vec<u8> {
+    let mut data = ephemeris_id.to_le_bytes().to_vec();
+    let flags = u8::from(frame_override.mu_km3_s2.is_some())
+        | u8::from(frame_override.shape.is_some()) << 1;
+    data.push(flags);
+    if let Some(mu_km3_s2) = frame_override.mu_km3_s2 {
+        data.extend_from_slice(&mu_km3_s2.to_le_bytes());
+    }
+    if let Some(shape) = frame_override.shape {
+        for radius_km in [
+            shape.semi_major_equatorial_radius_km,
+            shape.semi_minor_equatorial_radius_km,
+            shape.polar_radius_km,
+        ] {
+            data.extend_from_slice(&radius_km.to_le_bytes());
+        }
+    }
+    data
+}
+
+/// Decodes the override of the frames centered on a body, cf. `encode_frame_override`.
+fn decode_frame_override(data: &[u8]) -> AlmanacResult<(NaifId, FrameOverride)> {
+    let mut cursor = 0;
+    let ephemeris_id = NaifId::from_le_bytes(read_le(data, &mut cursor)?);
+    let flags = read_le::<1>(data, &mut cursor)?[0];
+    let mut read_f64 = || read_le(data, &mut cursor).map(f64::from_le_bytes);
+
+    let mu_km3_s2 = if flags & 1 != 0 {
+        Some(read_f64()?)
+    } else {
+        None
+    };
+    let shape = if flags & (1 << 1) != 0 {
+        Some(Ellipsoid {
+            semi_major_equatorial_radius_km: read_f64()?,
+            semi_minor_equatorial_radius_km: read_f64()?,
+            polar_radius_km: read_f64()?,
+        })
+    } else {
+        None
+    };
+
+    Ok((ephemeris_id, FrameOverride { mu_km3_s2, shape }))
+}
+
+fn encoding_error(err: der::Error) -> AlmanacError {
+    AlmanacError::TLDataSet {
+        action: "saving Almanac context",
+        source: DataSetError::DataDecoding {
+            action: "encoding data set",
+            source: DecodingError::DecodingDer { err },
+        },
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Saves all of the data loaded in this Almanac into a single context file, which can be loaded with `load_context` to reproduce
+    /// exactly the same computations elsewhere.
+    ///
+    /// The context includes the SPK and BPC kernels with their aliases and in their order of precedence, the planetary, spacecraft,
+    /// Euler parameter, and location data sets, the variables of the kernel pool, the body names, and the frame overrides. Frames and
+    /// dynamic frames which were only registered at runtime (i.e. not defined in a text kernel) are not included.
+    ///
+    /// If overwrite is set to false, and the path already exists, this function will return an error.
+    ///
+    /// :type path: str
+    /// :type overwrite: bool, optional
+    /// :rtype: None
+    #[cfg_attr(feature = "python", pyo3(signature=(path, overwrite=false)))]
+    pub fn save_context(&self, path: &str, overwrite: bool) -> AlmanacResult<()> {
+        if Path::new(path).exists() && !overwrite {
+            return Err(AlmanacError::TLDataSet {
+                action: "saving Almanac context",
+                source: DataSetError::IO {
+                    source: IOError::new(
+                        IOErrorKind::AlreadyExists,
+                        "file exists and overwrite flag set to false",
+                    ),
+                    action: "creating context file",
+                },
+            });
+        }
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(CONTEXT_MAGIC);
+        buf.extend_from_slice(&CONTEXT_VERSION.to_le_bytes());
+
+        // Kernels are stored in load order, which is their order of precedence.
+        for (spk, alias) in self.spk_data.iter().zip(self.spk_aliases.iter()) {
+            if let Some(spk) = spk {
+                push_entry(&mut buf, ContextEntry::Spk, alias.as_deref(), &spk.bytes);
+            }
+        }
+        for (bpc, alias) in self.bpc_data.iter().zip(self.bpc_aliases.iter()) {
+            if let Some(bpc) = bpc {
+                push_entry(&mut buf, ContextEntry::Bpc, alias.as_deref(), &bpc.bytes);
+            }
+        }
+
+        let mut encoded = Vec::new();
+        if !self.planetary_data.is_empty() {
+            self.planetary_data
+                .encode_to_vec(&mut encoded)
+                .map_err(encoding_error)?;
+            push_entry(&mut buf, ContextEntry::PlanetaryData, None, &encoded);
+            encoded.clear();
+        }
+        if !self.spacecraft_data.is_empty() {
+            self.spacecraft_data
+                .encode_to_vec(&mut encoded)
+                .map_err(encoding_error)?;
+            push_entry(&mut buf, ContextEntry::SpacecraftData, None, &encoded);
+            encoded.clear();
+        }
+        if !self.euler_param_data.is_empty() {
+            self.euler_param_data
+                .encode_to_vec(&mut encoded)
+                .map_err(encoding_error)?;
+            push_entry(&mut buf, ContextEntry::EulerParameterData, None, &encoded);
+            encoded.clear();
+        }
+        if !self.location_data.is_empty() {
+            self.location_data
+                .encode_to_vec(&mut encoded)
+                .map_err(encoding_error)?;
+            push_entry(&mut buf, ContextEntry::LocationData, None, &encoded);
+        }
+
+        if !self.kernel_pool.is_empty() {
+            push_entry(
+                &mut buf,
+                ContextEntry::KernelPool,
+                None,
+                self.kernel_pool.to_kernel().as_bytes(),
+            );
+        }
+
+        if !self.body_names.is_empty() {
+            let names = self
+                .body_names
+                .iter()
+                .map(|(name, id)| format!("{id}\t{name}\n"))
+                .collect::<String>();
+            push_entry(&mut buf, ContextEntry::BodyNames, None, names.as_bytes());
+        }
+
+        for (ephemeris_id, frame_override) in &self.frame_overrides {
+            push_entry(
+                &mut buf,
+                ContextEntry::FrameOverride,
+                None,
+                &encode_frame_override(*ephemeris_id, frame_override),
+            );
+        }
+
+        File::create(path)
+            .and_then(|mut file| file.write_all(&buf))
+            .map_err(|source| AlmanacError::TLDataSet {
+                action: "saving Almanac context",
+                source: DataSetError::IO {
+                    source,
+                    action: "writing context file",
+                },
+            })?;
+
+        info!("[OK] Almanac context saved to {path}");
+        Ok(())
+    }
+
+    /// Loads all of the data of the context file saved with `save_context` into a clone of this Almanac, as if each kernel and data set
+    /// had been loaded in the same order as in the original Almanac. Load it into a default Almanac to reproduce the original one.
+    ///
+    /// :type path: str
+    /// :rtype: Almanac
+    pub fn load_context(&self, path: &str) -> AlmanacResult<Self> {
+        let bytes = file2heap!(path).context(LoadingSnafu {
+            path: path.to_string(),
+        })?;
+
+        self.load_context_from_bytes(bytes).map_err(|e| match e {
+            AlmanacError::GenericError { err } => AlmanacError::GenericError {
+                err: format!("with {path}: {err}"),
+            },
+            _ => e,
+        })
+    }
+
+    /// Returns a digest of all of the data loaded in this Almanac, to be stamped into reports and exported files such that results can be traced
+    /// to an exact data configuration.
+    ///
+    /// The digest is the CRC32 of the checksums, aliases, and load order of the SPK and BPC kernels, of the checksums of the ANISE data sets, and of
+    /// the kernel pool, body names, and frame overrides. It is stable across runs, platforms, and context files (cf. `save_context`), and changes if any kernel is added,
+    /// removed, reordered, or renamed.
+    ///
+    /// :rtype: int
+    pub fn context_digest(&self) -> u32 {
+        let mut hasher = crc32fast::Hasher::new();
+
+        let mut update = |kind: ContextEntry, alias: Option<&str>, crc32: u32| {
+            let alias = alias.unwrap_or_default().as_bytes();
+            hasher.update(&[kind as u8]);
+            hasher.update(&(alias.len() as u32).to_le_bytes());
+            hasher.update(alias);
+            hasher.update(&crc32.to_le_bytes());
+        };
+
+        // Lazily loaded kernels do not have their checksum computed at load time.
+        for (spk, alias) in self.spk_data.iter().zip(self.spk_aliases.iter()) {
+            if let Some(spk) = spk {
+                let crc32 = match spk.crc32_checksum {
+                    0 => spk.crc32(),
+                    crc32 => crc32,
+                };
+                update(ContextEntry::Spk, alias.as_deref(), crc32);
+            }
+        }
+        for (bpc, alias) in self.bpc_data.iter().zip(self.bpc_aliases.iter()) {
+            if let Some(bpc) = bpc {
+                let crc32 = match bpc.crc32_checksum {
+                    0 => bpc.crc32(),
+                    crc32 => crc32,
+                };
+                update(ContextEntry::Bpc, alias.as_deref(), crc32);
+            }
+        }
+
+        if !self.planetary_data.is_empty() {
+            update(
+                ContextEntry::PlanetaryData,
+                None,
+                self.planetary_data.crc32(),
+            );
+        }
+        if !self.spacecraft_data.is_empty() {
+            update(
+                ContextEntry::SpacecraftData,
+                None,
+                self.spacecraft_data.crc32(),
+            );
+        }
+        if !self.euler_param_data.is_empty() {
+            update(
+                ContextEntry::EulerParameterData,
+                None,
+                self.euler_param_data.crc32(),
+            );
+        }
+        if !self.location_data.is_empty() {
+            update(ContextEntry::LocationData, None, self.location_data.crc32());
+        }
+
+        if !self.kernel_pool.is_empty() {
+            update(
+                ContextEntry::KernelPool,
+                None,
+                crc32fast::hash(self.kernel_pool.to_kernel().as_bytes()),
+            );
+        }
+        for (name, id) in &self.body_names {
+            update(ContextEntry::BodyNames, Some(name), *id as u32);
+        }
+        for (ephemeris_id, frame_override) in &self.frame_overrides {
+            update(
+                ContextEntry::FrameOverride,
+                None,
+                crc32fast::hash(&encode_frame_override(*ephemeris_id, frame_override)),
+            );
+        }
+
+        hasher.finalize()
+    }
+}
+
+impl Almanac {
+    /// Loads the bytes of an Almanac context, cf. `load_context`.
+    pub fn load_context_from_bytes(&self, bytes: Bytes) -> AlmanacResult<Self> {
+        if bytes.get(..CONTEXT_MAGIC.len()) != Some(CONTEXT_MAGIC.as_slice()) {
+            return Err(AlmanacError::GenericError {
+                err: "not an Almanac context (invalid magic bytes)".to_string(),
+            });
+        }
+
+        let mut cursor = CONTEXT_MAGIC.len();
+        let version = u32::from_le_bytes(read_le(&bytes, &mut cursor)?);
+        if version != CONTEXT_VERSION {
+            return Err(AlmanacError::GenericError {
+                err: format!(
+                    "Almanac context version {version} is not supported (expected {CONTEXT_VERSION})"
+                ),
+            });
+        }
+
+        let mut me = self.clone();
+
+        while cursor < bytes.len() {
+            let kind = ContextEntry::try_from(read_le::<1>(&bytes, &mut cursor)?[0])?;
+
+            let alias_len = u32::from_le_bytes(read_le(&bytes, &mut cursor)?) as usize;
+            let alias = bytes
+                .get(cursor..cursor + alias_len)
+                .map(|alias| String::from_utf8_lossy(alias).to_string())
+                .ok_or(AlmanacError::GenericError {
+                    err: format!("Almanac context truncated at byte {cursor}"),
+                })?;
+            cursor += alias_len;
+
+            let data_len = u64::from_le_bytes(read_le(&bytes, &mut cursor)?) as usize;
+            if cursor + data_len > bytes.len() {
+                return Err(AlmanacError::GenericError {
+                    err: format!("Almanac context truncated at byte {cursor}"),
+                });
+            }
+            let data = bytes.slice(cursor..cursor + data_len);
+            cursor += data_len;
+
+            match kind {
+                ContextEntry::Spk
+                | ContextEntry::Bpc
+                | ContextEntry::PlanetaryData
+                | ContextEntry::SpacecraftData
+                | ContextEntry::EulerParameterData
+                | ContextEntry::LocationData => {
+                    let alias = if alias.is_empty() {
+                        None
+                    } else {
+                        Some(alias.as_str())
+                    };
+                    me = me._load_from_bytes(data, alias, false)?;
+                }
+                ContextEntry::KernelPool => {
+                    let kernel = String::from_utf8_lossy(&data);
+                    // The body names are restored exactly from their own entry.
+                    let num_names = me.body_names.len();
+                    me = me._load_text_kernel(&kernel)?;
+                    me.body_names.truncate(num_names);
+                }
+                ContextEntry::BodyNames => {
+                    for line in String::from_utf8_lossy(&data).lines() {
+                        let parsed = line
+                            .split_once('\t')
+                            .and_then(|(id, name)| Some((name, id.parse::<NaifId>().ok()?)));
+                        match parsed {
+                            Some((name, id)) => me.body_names.push((name.to_string(), id)),
+                            None => {
+                                return Err(AlmanacError::GenericError {
+                                    err: format!("invalid body name `{line}` in Almanac context"),
+                                })
+                            }
+                        }
+                    }
+                }
+                ContextEntry::FrameOverride => {
+                    let (ephemeris_id, frame_override) = decode_frame_override(&data)?;
+                    me.frame_overrides.insert(ephemeris_id, frame_override);
+                }
+            }
+        }
+
+        Ok(me)
+    }
+}
+
+#[cfg(test)]
+mod ut_context {
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000};
+    use crate::prelude::{Almanac, Epoch};
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn context_round_trip() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/gmat-hermite.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap()
+            .load_text_kernel("../data/moon_080317.txt")
+            .unwrap()
+            .with_body_name("MY SPACECRAFT", -10000001)
+            .prioritize("../data/gmat-hermite.bsp")
+            .unwrap()
+            .override_frame(301, Some(4902.8), Some(Ellipsoid::from_sphere(1737.4)))
+            .override_frame(399, Some(398600.4418), None);
+
+        let path = std::env::temp_dir().join("anise-context-round-trip.ctx");
+        let path = path.to_str().unwrap();
+        almanac.save_context(path, true).unwrap();
+        // Do not overwrite by default
+        assert!(almanac.save_context(path, false).is_err());
+
+        let loaded = Almanac::default().load_context(path).unwrap();
+
+        assert_eq!(loaded.kernel_aliases(), almanac.kernel_aliases());
+        assert_eq!(loaded.planetary_data, almanac.planetary_data);
+        assert_eq!(loaded.kernel_pool, almanac.kernel_pool);
+        assert_eq!(loaded.body_names, almanac.body_names);
+        assert_eq!(loaded.frame_overrides, almanac.frame_overrides);
+        assert_eq!(
+            loaded.naif_id_from_name("MY SPACECRAFT").unwrap(),
+            -10000001
+        );
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+        assert_eq!(
+            loaded
+                .transform(MOON_J2000, EARTH_J2000, epoch, None)
+                .unwrap(),
+            almanac
+                .transform(MOON_J2000, EARTH_J2000, epoch, None)
+                .unwrap()
+        );
+
+        // Not a context
+        assert!(Almanac::default()
+            .load_context("../data/pck08.pca")
+            .is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn context_digest() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/gmat-hermite.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+
+        let digest = almanac.context_digest();
+        assert_eq!(digest, almanac.clone().context_digest());
+        assert_ne!(digest, Almanac::default().context_digest());
+
+        // Lazily loaded kernels have the same digest
+        let lazy = unsafe {
+            Almanac::default()
+                .load_lazy("../data/de440s.bsp")
+                .unwrap()
+                .load_lazy("../data/gmat-hermite.bsp")
+        }
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+        assert_eq!(lazy.context_digest(), digest);
+
+        // The order of the kernels, their aliases, and the body names are part of the digest
+        let reordered = almanac.prioritize("../data/de440s.bsp").unwrap();
+        assert_ne!(reordered.context_digest(), digest);
+        assert_ne!(
+            almanac
+                .with_body_name("MY SPACECRAFT", -10000001)
+                .context_digest(),
+            digest
+        );
+        let other_pca = almanac.load("../data/pck11.pca").unwrap();
+        assert_ne!(other_pca.context_digest(), digest);
+
+        // Frame overrides are part of the digest
+        let overridden = almanac.override_frame(399, Some(398600.4418), None);
+        assert_ne!(overridden.context_digest(), digest);
+        assert_ne!(
+            almanac
+                .override_frame(399, Some(398600.0), None)
+                .context_digest(),
+            overridden.context_digest()
+        );
+        assert_ne!(
+            almanac
+                .override_frame(399, None, Some(Ellipsoid::from_sphere(6378.1366)))
+                .context_digest(),
+            digest
+        );
+        assert_eq!(
+            overridden.clear_frame_override(399).context_digest(),
+            digest
+        );
+
+        // The digest survives a round trip through a context file
+        let path = std::env::temp_dir().join("anise-context-digest.ctx");
+        let path = path.to_str().unwrap();
+        almanac.save_context(path, true).unwrap();
+        let loaded = Almanac::default().load_context(path).unwrap();
+        assert_eq!(loaded.context_digest(), digest);
+        std::fs::remove_file(path).unwrap();
+    }
+}
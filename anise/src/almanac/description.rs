@@ -0,0 +1,666 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch};
+use snafu::ResultExt;
+
+use super::Almanac;
+use crate::astro::orbit_describe::OrbitDescription;
+use crate::ephemerides::{EphemerisError, SPKSnafu};
+use crate::errors::{AlmanacResult, EphemerisSnafu, OrientationSnafu};
+use crate::naif::daf::NAIFSummaryRecord;
+use crate::naif::pck::BPCSummaryRecord;
+use crate::naif::spk::summary::SPKSummaryRecord;
+use crate::naif::{BPC, SPK};
+use crate::orientations::{BPCSnafu, OrientationError};
+use crate::prelude::{Frame, Orbit};
+use crate::structure::dataset::{DataSet, DataSetT};
+use crate::NaifId;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Description of a single segment of an SPK.
+///
+/// :rtype: SPKSegmentDescription
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct SPKSegmentDescription {
+    pub name: String,
+    pub target_id: NaifId,
+    pub center_id: NaifId,
+    pub frame_id: NaifId,
+    /// Name of the interpolation kind of this segment, e.g. "Chebyshev Triplet"
+    pub data_type: String,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
+
+/// Description of a single segment of a BPC.
+///
+/// :rtype: BPCSegmentDescription
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct BPCSegmentDescription {
+    pub name: String,
+    pub frame_id: NaifId,
+    pub inertial_frame_id: NaifId,
+    /// Name of the interpolation kind of this segment, e.g. "Chebyshev Triplet"
+    pub data_type: String,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
+
+/// Description of an SPK loaded in the Almanac, with the summaries of all of its non-empty segments.
+///
+/// :rtype: SPKDescription
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct SPKDescription {
+    pub alias: Option<String>,
+    pub crc32: u32,
+    pub segments: Vec<SPKSegmentDescription>,
+}
+
+/// Description of a BPC loaded in the Almanac, with the summaries of all of its non-empty segments.
+///
+/// :rtype: BPCDescription
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct BPCDescription {
+    pub alias: Option<String>,
+    pub crc32: u32,
+    pub segments: Vec<BPCSegmentDescription>,
+}
+
+/// Description of an entry of a data set, which may be accessible by its ID, its name, or both.
+///
+/// :rtype: DataSetEntryDescription
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct DataSetEntryDescription {
+    pub id: Option<NaifId>,
+    pub name: Option<String>,
+}
+
+/// Structured description of all of the data loaded in an Almanac, i.e. the information printed by `describe`.
+///
+/// The SPKs and BPCs are listed in the order in which they are searched, i.e. in reverse loading order.
+///
+/// :rtype: AlmanacDescription
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct AlmanacDescription {
+    pub spk: Vec<SPKDescription>,
+    pub bpc: Vec<BPCDescription>,
+    pub planetary_data: Vec<DataSetEntryDescription>,
+    pub spacecraft_data: Vec<DataSetEntryDescription>,
+    pub euler_param_data: Vec<DataSetEntryDescription>,
+    pub location_data: Vec<DataSetEntryDescription>,
+    /// Digest of the data loaded in the Almanac (cf. `Almanac::context_digest`)
+    pub context_digest: u32,
+}
+
+impl SPKDescription {
+    fn from_daf(spk: &SPK, alias: Option<&str>) -> Result<Self, EphemerisError> {
+        let mut segments = Vec::new();
+        let summaries = spk.data_summaries().context(SPKSnafu {
+            action: "describing SPK",
+        })?;
+        for (sno, summary) in summaries.iter().enumerate() {
+            if summary.is_empty() {
+                continue;
+            }
+            let name = spk.nth_name(sno).context(SPKSnafu {
+                action: "describing SPK",
+            })?;
+            segments.push(SPKSegmentDescription::from_summary(name, summary)?);
+        }
+
+        Ok(Self {
+            alias: alias.map(|alias| alias.to_string()),
+            crc32: match spk.crc32_checksum {
+                0 => spk.crc32(),
+                crc32 => crc32,
+            },
+            segments,
+        })
+    }
+}
+
+impl BPCDescription {
+    fn from_daf(bpc: &BPC, alias: Option<&str>) -> Result<Self, OrientationError> {
+        let mut segments = Vec::new();
+        let summaries = bpc.data_summaries().context(BPCSnafu {
+            action: "describing BPC",
+        })?;
+        for (sno, summary) in summaries.iter().enumerate() {
+            if summary.is_empty() {
+                continue;
+            }
+            let name = bpc.nth_name(sno).context(BPCSnafu {
+                action: "describing BPC",
+            })?;
+            segments.push(BPCSegmentDescription::from_summary(name, summary)?);
+        }
+
+        Ok(Self {
+            alias: alias.map(|alias| alias.to_string()),
+            crc32: match bpc.crc32_checksum {
+                0 => bpc.crc32(),
+                crc32 => crc32,
+            },
+            segments,
+        })
+    }
+}
+
+/// Returns the description of all of the entries of this data set, sorted by ID and then by name.
+fn describe_dataset<T: DataSetT, const ENTRIES: usize>(
+    dataset: &DataSet<T, ENTRIES>,
+) -> Vec<DataSetEntryDescription> {
+    let mut entries = dataset
+        .lut
+        .entries()
+        .values()
+        .map(|(id, name)| DataSetEntryDescription {
+            id: *id,
+            name: name.as_ref().map(|name| name.to_string()),
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| (a.id, &a.name).cmp(&(b.id, &b.name)));
+    entries
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns a structured description of all of the data loaded in this Almanac, including the summaries of
+    /// every segment of the loaded SPKs and BPCs, so that it may be rendered without parsing the output of `describe`.
+    ///
+    /// :rtype: AlmanacDescription
+    pub fn describe_data(&self) -> AlmanacResult<AlmanacDescription> {
+        let mut spk = Vec::with_capacity(self.num_loaded_spk());
+        for (maybe_spk, alias) in self
+            .spk_data
+            .iter()
+            .zip(self.spk_aliases.iter())
+            .take(self.num_loaded_spk())
+            .rev()
+        {
+            spk.push(
+                SPKDescription::from_daf(maybe_spk.as_ref().unwrap(), alias.as_deref()).context(
+                    EphemerisSnafu {
+                        action: "describing Almanac",
+                    },
+                )?,
+            );
+        }
+
+        let mut bpc = Vec::with_capacity(self.num_loaded_bpc());
+        for (maybe_bpc, alias) in self
+            .bpc_data
+            .iter()
+            .zip(self.bpc_aliases.iter())
+            .take(self.num_loaded_bpc())
+            .rev()
+        {
+            bpc.push(
+                BPCDescription::from_daf(maybe_bpc.as_ref().unwrap(), alias.as_deref()).context(
+                    OrientationSnafu {
+                        action: "describing Almanac",
+                    },
+                )?,
+            );
+        }
+
+        Ok(AlmanacDescription {
+            spk,
+            bpc,
+            planetary_data: describe_dataset(&self.planetary_data),
+            spacecraft_data: describe_dataset(&self.spacecraft_data),
+            euler_param_data: describe_dataset(&self.euler_param_data),
+            location_data: describe_dataset(&self.location_data),
+            context_digest: self.context_digest(),
+        })
+    }
+}
+
+impl Almanac {
+    /// Returns the summary of the state in all of the representations which apply to it (cf. `Orbit::describe_all`), where
+    /// the gravitational parameter and shape of its frame are fetched from the loaded planetary data if available.
+    ///
+    /// The geodetic coordinates are computed in the IAU body fixed frame of the center of the state, if its orientation and
+    /// shape are loaded, so they are valid whatever the frame of the state. The reference state, if provided, is first
+    /// transformed into the frame of the state.
+    pub fn describe_all(
+        &self,
+        state: Orbit,
+        reference: Option<Orbit>,
+    ) -> AlmanacResult<OrbitDescription> {
+        let frame = self.frame_from_uid(state.frame).unwrap_or(state.frame);
+        let state = Orbit { frame, ..state };
+
+        let reference = reference
+            .map(|reference| self.transform_to(reference, frame, None))
+            .transpose()?;
+
+        let mut description = state.describe_all(reference.as_ref());
+
+        let body_fixed = Frame::new(frame.ephemeris_id, frame.ephemeris_id);
+        description.geodetic = self
+            .frame_from_uid(body_fixed)
+            .ok()
+            .filter(|body_fixed| body_fixed.shape.is_some())
+            .and_then(|body_fixed| self.transform_to(state, body_fixed, None).ok())
+            .and_then(|state| state.describe_all(None).geodetic);
+
+        Ok(description)
+    }
+}
+
+impl SPKSegmentDescription {
+    /// Builds the description of the segment with the provided name from its summary record.
+    pub fn from_summary(name: String, summary: &SPKSummaryRecord) -> Result<Self, EphemerisError> {
+        Ok(Self {
+            name,
+            target_id: summary.target_id,
+            center_id: summary.center_id,
+            frame_id: summary.frame_id,
+            data_type: summary.data_type()?.to_string(),
+            start_epoch: summary.start_epoch(),
+            end_epoch: summary.end_epoch(),
+        })
+    }
+
+    /// Returns the duration covered by this segment.
+    pub fn duration(&self) -> Duration {
+        self.end_epoch - self.start_epoch
+    }
+}
+
+impl BPCSegmentDescription {
+    /// Builds the description of the segment with the provided name from its summary record.
+    pub fn from_summary(
+        name: String,
+        summary: &BPCSummaryRecord,
+    ) -> Result<Self, OrientationError> {
+        Ok(Self {
+            name,
+            frame_id: summary.frame_id,
+            inertial_frame_id: summary.inertial_frame_id,
+            data_type: summary.data_type()?.to_string(),
+            start_epoch: summary.start_epoch(),
+            end_epoch: summary.end_epoch(),
+        })
+    }
+
+    /// Returns the duration covered by this segment.
+    pub fn duration(&self) -> Duration {
+        self.end_epoch - self.start_epoch
+    }
+}
+
+impl fmt::Display for SPKSegmentDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} wrt {} in {} ({}) from {} to {}",
+            self.name,
+            self.target_id,
+            self.center_id,
+            self.frame_id,
+            self.data_type,
+            self.start_epoch,
+            self.end_epoch
+        )
+    }
+}
+
+impl fmt::Display for BPCSegmentDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} wrt {} ({}) from {} to {}",
+            self.name,
+            self.frame_id,
+            self.inertial_frame_id,
+            self.data_type,
+            self.start_epoch,
+            self.end_epoch
+        )
+    }
+}
+
+impl fmt::Display for AlmanacDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Almanac 0x{:08x}: {} SPK ({} segments), {} BPC ({} segments), {} planetary data, {} spacecraft data, {} Euler parameters, {} locations",
+            self.context_digest,
+            self.spk.len(),
+            self.spk.iter().map(|spk| spk.segments.len()).sum::<usize>(),
+            self.bpc.len(),
+            self.bpc.iter().map(|bpc| bpc.segments.len()).sum::<usize>(),
+            self.planetary_data.len(),
+            self.spacecraft_data.len(),
+            self.euler_param_data.len(),
+            self.location_data.len()
+        )
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl SPKSegmentDescription {
+    /// :rtype: str
+    #[getter]
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_target_id(&self) -> NaifId {
+        self.target_id
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_center_id(&self) -> NaifId {
+        self.center_id
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_frame_id(&self) -> NaifId {
+        self.frame_id
+    }
+    /// :rtype: str
+    #[getter]
+    fn get_data_type(&self) -> String {
+        self.data_type.clone()
+    }
+    /// :rtype: Epoch
+    #[getter]
+    fn get_start_epoch(&self) -> Epoch {
+        self.start_epoch
+    }
+    /// :rtype: Epoch
+    #[getter]
+    fn get_end_epoch(&self) -> Epoch {
+        self.end_epoch
+    }
+    /// Returns the duration covered by this segment.
+    ///
+    /// :rtype: Duration
+    #[pyo3(name = "duration")]
+    fn py_duration(&self) -> Duration {
+        self.duration()
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl BPCSegmentDescription {
+    /// :rtype: str
+    #[getter]
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_frame_id(&self) -> NaifId {
+        self.frame_id
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_inertial_frame_id(&self) -> NaifId {
+        self.inertial_frame_id
+    }
+    /// :rtype: str
+    #[getter]
+    fn get_data_type(&self) -> String {
+        self.data_type.clone()
+    }
+    /// :rtype: Epoch
+    #[getter]
+    fn get_start_epoch(&self) -> Epoch {
+        self.start_epoch
+    }
+    /// :rtype: Epoch
+    #[getter]
+    fn get_end_epoch(&self) -> Epoch {
+        self.end_epoch
+    }
+    /// Returns the duration covered by this segment.
+    ///
+    /// :rtype: Duration
+    #[pyo3(name = "duration")]
+    fn py_duration(&self) -> Duration {
+        self.duration()
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl SPKDescription {
+    /// :rtype: str
+    #[getter]
+    fn get_alias(&self) -> Option<String> {
+        self.alias.clone()
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_crc32(&self) -> u32 {
+        self.crc32
+    }
+    /// :rtype: typing.List
+    #[getter]
+    fn get_segments(&self) -> Vec<SPKSegmentDescription> {
+        self.segments.clone()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl BPCDescription {
+    /// :rtype: str
+    #[getter]
+    fn get_alias(&self) -> Option<String> {
+        self.alias.clone()
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_crc32(&self) -> u32 {
+        self.crc32
+    }
+    /// :rtype: typing.List
+    #[getter]
+    fn get_segments(&self) -> Vec<BPCSegmentDescription> {
+        self.segments.clone()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl DataSetEntryDescription {
+    /// :rtype: int
+    #[getter]
+    fn get_id(&self) -> Option<NaifId> {
+        self.id
+    }
+    /// :rtype: str
+    #[getter]
+    fn get_name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?} (@{self:p})")
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl AlmanacDescription {
+    /// :rtype: typing.List
+    #[getter]
+    fn get_spk(&self) -> Vec<SPKDescription> {
+        self.spk.clone()
+    }
+    /// :rtype: typing.List
+    #[getter]
+    fn get_bpc(&self) -> Vec<BPCDescription> {
+        self.bpc.clone()
+    }
+    /// :rtype: typing.List
+    #[getter]
+    fn get_planetary_data(&self) -> Vec<DataSetEntryDescription> {
+        self.planetary_data.clone()
+    }
+    /// :rtype: typing.List
+    #[getter]
+    fn get_spacecraft_data(&self) -> Vec<DataSetEntryDescription> {
+        self.spacecraft_data.clone()
+    }
+    /// :rtype: typing.List
+    #[getter]
+    fn get_euler_param_data(&self) -> Vec<DataSetEntryDescription> {
+        self.euler_param_data.clone()
+    }
+    /// :rtype: typing.List
+    #[getter]
+    fn get_location_data(&self) -> Vec<DataSetEntryDescription> {
+        self.location_data.clone()
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_context_digest(&self) -> u32 {
+        self.context_digest
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+#[cfg(test)]
+mod ut_description {
+    use crate::constants::frames::{EARTH_J2000, IAU_EARTH_FRAME};
+    use crate::prelude::{Almanac, Orbit};
+    use hifitime::Epoch;
+
+    #[test]
+    fn describe_data() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/gmat-hermite.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+
+        let desc = almanac.describe_data().unwrap();
+        println!("{desc}");
+
+        assert_eq!(desc.spk.len(), 2);
+        assert!(desc.bpc.is_empty());
+        assert_eq!(desc.context_digest, almanac.context_digest());
+
+        // Most recently loaded first, i.e. in the order in which they are searched
+        assert_eq!(
+            desc.spk[0].alias.as_deref(),
+            Some("../data/gmat-hermite.bsp")
+        );
+        assert_eq!(desc.spk[0].segments.len(), 1);
+        assert_eq!(desc.spk[0].segments[0].target_id, -10000001);
+        assert_eq!(
+            desc.spk[0].segments[0].start_epoch,
+            almanac.spk_domain(-10000001).unwrap().0
+        );
+
+        let de440s = &desc.spk[1];
+        assert_eq!(de440s.alias.as_deref(), Some("../data/de440s.bsp"));
+        assert_eq!(de440s.crc32, almanac.spk_data[0].as_ref().unwrap().crc32());
+        assert_eq!(de440s.segments.len(), 14);
+        let moon = de440s
+            .segments
+            .iter()
+            .find(|segment| segment.target_id == 301)
+            .unwrap();
+        assert_eq!(moon.center_id, 3);
+        assert_eq!(moon.frame_id, 1);
+        assert_eq!(moon.data_type, "Chebyshev Triplet");
+
+        assert_eq!(desc.planetary_data.len(), almanac.planetary_data.len());
+        assert!(desc
+            .planetary_data
+            .iter()
+            .any(|entry| entry.id == Some(399)));
+        assert!(desc.spacecraft_data.is_empty());
+    }
+
+    #[test]
+    fn describe_all() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+        // The frame of the state has neither a gravitational parameter nor a shape
+        let mut state = Orbit::new(6_578.0, 100.0, 4_000.0, -4.0, 6.0, 1.0, epoch, EARTH_J2000);
+        assert!(state.describe_all(None).keplerian.is_none());
+
+        let description = almanac.describe_all(state, Some(state)).unwrap();
+        println!("{description}");
+        assert!(description.keplerian.is_some());
+        assert!(description.ric.unwrap().radius_km.norm() < 1e-9);
+
+        // Geodetic coordinates are those in the body fixed frame
+        let geodetic = description.geodetic.unwrap();
+        let body_fixed = almanac.transform_to(state, IAU_EARTH_FRAME, None).unwrap();
+        let (lat_deg, long_deg, height_km) = body_fixed.latlongalt().unwrap();
+        assert_eq!(geodetic.latitude_deg, lat_deg);
+        assert_eq!(geodetic.longitude_deg, long_deg);
+        assert_eq!(geodetic.height_km, height_km);
+
+        // The reference is transformed into the frame of the state
+        state.radius_km.x += 1.0;
+        let reference = almanac.transform_to(state, IAU_EARTH_FRAME, None).unwrap();
+        state.radius_km.x -= 1.0;
+        let description = almanac.describe_all(state, Some(reference)).unwrap();
+        assert!((description.ric.unwrap().radius_km.norm() - 1.0).abs() < 1e-6);
+    }
+}
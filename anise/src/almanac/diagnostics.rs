@@ -0,0 +1,364 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Epoch, Unit};
+
+use super::Almanac;
+use crate::constants::orientations::{ECLIPJ2000, J2000};
+use crate::ephemerides::paths::MAX_TREE_DEPTH;
+use crate::frames::Frame;
+use crate::naif::daf::{NAIFSummaryRecord, SUMMARY_EPOCH_TOLERANCE_NS};
+use crate::NaifId;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// A segment of a loaded kernel which has data for the ID of a hop, and its coverage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentCoverage {
+    /// Index of the kernel in the load order
+    pub kernel_no: usize,
+    /// Alias of the kernel, if it was loaded from a path
+    pub kernel: Option<String>,
+    /// Index of the segment in the kernel
+    pub segment_no: usize,
+    /// Center (SPK) or inertial frame (BPC) of this segment
+    pub parent_id: NaifId,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    /// Whether this segment is the one used at the requested epoch
+    pub selected: bool,
+}
+
+impl SegmentCoverage {
+    /// Returns whether this segment covers the provided epoch, with the same tolerance on its bounds as the queries.
+    pub fn covers(&self, epoch: Epoch) -> bool {
+        let tolerance = Unit::Nanosecond * SUMMARY_EPOCH_TOLERANCE_NS;
+        epoch >= self.start_epoch - tolerance && epoch <= self.end_epoch + tolerance
+    }
+}
+
+impl fmt::Display for SegmentCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] kernel #{} ({}) segment #{}: {} to {} (parent {})",
+            if self.selected { "x" } else { " " },
+            self.kernel_no,
+            self.kernel.as_deref().unwrap_or("no alias"),
+            self.segment_no,
+            self.start_epoch,
+            self.end_epoch,
+            self.parent_id
+        )
+    }
+}
+
+/// One hop of a frame path, with all of the segments which were considered to go from this ID to its parent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HopDiagnostic {
+    pub id: NaifId,
+    /// Parent ID of this hop, None if no data is available for this hop at the requested epoch
+    pub parent_id: Option<NaifId>,
+    /// All of the segments for this ID, in order of precedence
+    pub segments: Vec<SegmentCoverage>,
+    /// Time-independent data set used for this hop if no segment covers the epoch (orientations only)
+    pub dataset: Option<&'static str>,
+}
+
+/// Diagnostic of the path of an ephemeris or orientation ID to the root of the loaded data, at a given epoch.
+///
+/// The path stops at the first hop without data at the requested epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathDiagnostic {
+    /// Either "SPK" or "BPC"
+    pub kind: &'static str,
+    pub epoch: Epoch,
+    pub hops: Vec<HopDiagnostic>,
+}
+
+impl PathDiagnostic {
+    /// Returns whether every hop of this path has data at the requested epoch.
+    pub fn is_complete(&self) -> bool {
+        self.hops.iter().all(|hop| hop.parent_id.is_some())
+    }
+}
+
+impl fmt::Display for PathDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} path at {}", self.kind, self.epoch)?;
+        if self.hops.is_empty() {
+            write!(f, ": source is the root of the loaded data")?;
+        }
+        for hop in &self.hops {
+            match hop.parent_id {
+                Some(parent_id) => write!(f, "\n  {} -> {parent_id}", hop.id)?,
+                None => write!(f, "\n  {} -> NO DATA AT EPOCH", hop.id)?,
+            }
+            if let Some(dataset) = hop.dataset {
+                write!(f, " from {dataset}")?;
+            }
+            if hop.segments.is_empty() {
+                write!(f, "\n    no {} segment for {}", self.kind, hop.id)?;
+            }
+            for segment in &hop.segments {
+                write!(f, "\n    {segment}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lists the segments for the provided ID in all of the kernels, in order of precedence, marking the one used by the queries, if any,
+/// given as its kernel and segment numbers.
+fn segments_for<R: NAIFSummaryRecord>(
+    summaries: &[(Option<&String>, &[R])],
+    id: NaifId,
+    selected: Option<(usize, usize)>,
+) -> Vec<SegmentCoverage> {
+    let mut segments = Vec::new();
+
+    for (kernel_no, (alias, summaries)) in summaries.iter().enumerate().rev() {
+        for (segment_no, summary) in summaries.iter().enumerate() {
+            if summary.is_empty() || summary.id() != id {
+                continue;
+            }
+            segments.push(SegmentCoverage {
+                kernel_no,
+                kernel: alias.cloned(),
+                segment_no,
+                parent_id: summary.center_id(),
+                start_epoch: summary.start_epoch(),
+                end_epoch: summary.end_epoch(),
+                selected: selected == Some((kernel_no, segment_no)),
+            });
+        }
+    }
+
+    segments
+}
+
+/// Returns the IDs of each hop of a path and of its parent, if any, from the nodes reached by a path walk.
+/// If the walk failed, the last hop has no parent.
+fn hops_of(
+    source: NaifId,
+    of_path_len: usize,
+    of_path: [Option<NaifId>; MAX_TREE_DEPTH],
+    complete: bool,
+) -> Vec<(NaifId, Option<NaifId>)> {
+    let mut hops = Vec::with_capacity(of_path_len + 1);
+    let mut id = source;
+    for parent_id in of_path.iter().take(of_path_len).flatten() {
+        hops.push((id, Some(*parent_id)));
+        id = *parent_id;
+    }
+    if !complete {
+        hops.push((id, None));
+    }
+    hops
+}
+
+impl Almanac {
+    /// Returns the kernel and segment numbers of the SPK segment used to go from this ID to its parent at the provided epoch.
+    pub(crate) fn ephemeris_hop_segment(&self, id: NaifId, epoch: Epoch) -> Option<(usize, usize)> {
+        self.spk_summary_at_epoch(id, epoch)
+            .ok()
+            .map(|(_, spk_no, idx)| (spk_no, idx))
+    }
+
+    /// Returns the kernel and segment numbers of the BPC segment used to go from this ID to its parent at the provided epoch,
+    /// or the name of the data set used instead, in the same order of precedence as the orientation queries.
+    pub(crate) fn orientation_hop_source(
+        &self,
+        id: NaifId,
+        parent_id: NaifId,
+        epoch: Epoch,
+    ) -> (Option<(usize, usize)>, Option<&'static str>) {
+        if self.dynamic_frame(id).is_some() {
+            (None, Some("dynamic frames"))
+        } else if let Ok((_, bpc_no, idx)) = self.bpc_summary_at_epoch(id, epoch) {
+            (Some((bpc_no, idx)), None)
+        } else if self.planetary_data.get_by_id(id).is_ok() {
+            (None, Some("planetary constants"))
+        } else if self.euler_param_data.get_by_id(id).is_ok() {
+            (None, Some("Euler parameters"))
+        } else if id == ECLIPJ2000 && parent_id == J2000 {
+            (None, Some("built-in constants"))
+        } else {
+            (None, None)
+        }
+    }
+
+    /// Returns the diagnostic of the ephemeris path from the source frame to the root of the loaded SPKs at the provided epoch,
+    /// listing the coverage of every segment considered for each hop. This is useful to understand why a query fails.
+    ///
+    /// The hops are those of [Self::ephemeris_path_to_root], up to the first one which fails.
+    pub fn ephemeris_path_diagnostic(&self, source: Frame, epoch: Epoch) -> PathDiagnostic {
+        let summaries = self
+            .spk_data
+            .iter()
+            .zip(self.spk_aliases.iter())
+            .take(self.num_loaded_spk())
+            .map(|(spk, alias)| {
+                (
+                    alias.as_ref(),
+                    spk.as_ref().unwrap().data_summaries().unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let (of_path_len, of_path, result) = self.ephemeris_path_walk(source, epoch);
+
+        let hops = hops_of(source.ephemeris_id, of_path_len, of_path, result.is_ok())
+            .into_iter()
+            .map(|(id, parent_id)| {
+                let selected = parent_id.and_then(|_| self.ephemeris_hop_segment(id, epoch));
+                HopDiagnostic {
+                    id,
+                    parent_id,
+                    segments: segments_for(&summaries, id, selected),
+                    dataset: None,
+                }
+            })
+            .collect();
+
+        PathDiagnostic {
+            kind: "SPK",
+            epoch,
+            hops,
+        }
+    }
+
+    /// Returns the diagnostic of the orientation path from the source frame to the root of the loaded orientations at the provided epoch,
+    /// listing the coverage of every BPC segment considered for each hop, and the other data set used if none cover the epoch.
+    ///
+    /// The hops are those of [Self::orientation_path_to_root], up to the first one which fails.
+    pub fn orientation_path_diagnostic(&self, source: Frame, epoch: Epoch) -> PathDiagnostic {
+        let summaries = self
+            .bpc_data
+            .iter()
+            .zip(self.bpc_aliases.iter())
+            .take(self.num_loaded_bpc())
+            .map(|(bpc, alias)| {
+                (
+                    alias.as_ref(),
+                    bpc.as_ref().unwrap().data_summaries().unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let (of_path_len, of_path, result) = self.orientation_path_walk(source, epoch);
+
+        let hops = hops_of(source.orientation_id, of_path_len, of_path, result.is_ok())
+            .into_iter()
+            .map(|(id, parent_id)| {
+                let (selected, dataset) = match parent_id {
+                    Some(parent_id) => self.orientation_hop_source(id, parent_id, epoch),
+                    None => (None, None),
+                };
+                HopDiagnostic {
+                    id,
+                    parent_id,
+                    segments: segments_for(&summaries, id, selected),
+                    dataset,
+                }
+            })
+            .collect();
+
+        PathDiagnostic {
+            kind: "BPC",
+            epoch,
+            hops,
+        }
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns a human readable diagnostic of the ephemeris and orientation paths of the provided frame at the provided epoch,
+    /// listing every kernel segment considered for each hop and its coverage interval, and the first hop without data, if any.
+    ///
+    /// :type frame: Frame
+    /// :type epoch: Epoch
+    /// :rtype: str
+    pub fn describe_path_coverage(&self, frame: Frame, epoch: Epoch) -> String {
+        format!(
+            "{frame}\n{}\n{}",
+            self.ephemeris_path_diagnostic(frame, epoch),
+            self.orientation_path_diagnostic(frame, epoch)
+        )
+    }
+}
+
+#[cfg(test)]
+mod ut_diagnostics {
+    use crate::constants::frames::{EARTH_ITRF93, IAU_MOON_FRAME, MOON_J2000};
+    use crate::prelude::Almanac;
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn path_diagnostic() {
+        let almanac = Almanac::new("../data/de421.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2002, 2, 7);
+        let diag = almanac.ephemeris_path_diagnostic(MOON_J2000, epoch);
+        assert!(diag.is_complete(), "{diag}");
+        assert_eq!(diag.hops.len(), 2);
+        assert_eq!(diag.hops[0].parent_id, Some(3));
+        assert_eq!(
+            diag.hops[0].segments[0].kernel.as_deref(),
+            Some("../data/de421.bsp")
+        );
+        assert!(diag.hops[0].segments[0].selected);
+
+        // DE421 ends in 2053
+        let late = Epoch::from_gregorian_utc_at_midnight(2100, 1, 1);
+        let diag = almanac.ephemeris_path_diagnostic(MOON_J2000, late);
+        assert!(!diag.is_complete());
+        assert_eq!(diag.hops.len(), 1);
+        assert_eq!(diag.hops[0].segments.len(), 1);
+        assert!(!diag.hops[0].segments[0].selected);
+        assert!(!diag.hops[0].segments[0].covers(late));
+
+        // Same tolerance on the bounds of the segments as the queries
+        let segment = &diag.hops[0].segments[0];
+        assert!(segment.covers(segment.end_epoch + Unit::Nanosecond * 50));
+        assert!(!segment.covers(segment.end_epoch + Unit::Microsecond * 1));
+        assert!(almanac.translate_to_parent(MOON_J2000, late).is_err());
+
+        // The hops are those of the orientation queries
+        let diag = almanac.orientation_path_diagnostic(IAU_MOON_FRAME, epoch);
+        assert!(diag.is_complete(), "{diag}");
+        let (len, path) = almanac
+            .orientation_path_to_root(IAU_MOON_FRAME, epoch)
+            .unwrap();
+        assert_eq!(
+            diag.hops
+                .iter()
+                .map(|hop| hop.parent_id)
+                .collect::<Vec<_>>(),
+            path[..len].to_vec()
+        );
+        assert_eq!(diag.hops[0].dataset, Some("planetary constants"));
+
+        // Without the high precision Earth orientation, ITRF93 is not available
+        let diag = almanac.orientation_path_diagnostic(EARTH_ITRF93, epoch);
+        assert!(!diag.is_complete());
+        assert!(diag.hops[0].segments.is_empty());
+
+        let description = almanac.describe_path_coverage(EARTH_ITRF93, epoch);
+        assert!(description.contains("NO DATA AT EPOCH"), "{description}");
+    }
+}
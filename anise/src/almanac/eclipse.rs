@@ -11,12 +11,14 @@
 use log::error;
 
 use crate::{
-    astro::{Aberration, Occultation},
+    astro::{Aberration, Occultation, OccultationKind},
     constants::{frames::SUN_J2000, orientations::J2000},
     ephemerides::EphemerisPhysicsSnafu,
-    errors::{AlmanacError, EphemerisSnafu, OrientationSnafu},
+    errors::{AlmanacError, EphemerisSnafu, OrientationSnafu, PhysicsError},
     frames::Frame,
+    math::Vector3,
     prelude::Orbit,
+    structure::planetocentric::ellipsoid::Ellipsoid,
 };
 
 use super::Almanac;
@@ -118,6 +120,8 @@ impl Almanac {
     /// A zero percent occultation means that the back object is fully visible from the observer.
     /// A 100%  percent occultation means that the back object is fully hidden from the observer because of the front frame (i.e. _umbra_ if the back object is the Sun).
     /// A value in between means that the back object is partially hidden from the observser (i.e. _penumbra_ if the back object is the Sun).
+    /// The `kind` of the returned occultation further distinguishes partial overlaps from annular ones (i.e. transits), where the
+    /// front object lies entirely within the apparent disk of the back object.
     /// Refer to the [MathSpec](https://nyxspace.com/nyxspace/MathSpec/celestial/eclipse/) for modeling details.
     ///
     /// :type back_frame: Frame
@@ -126,11 +130,187 @@ impl Almanac {
     /// :type ab_corr: Aberration, optional
     /// :rtype: Occultation
     pub fn occultation(
+        &self,
+        back_frame: Frame,
+        front_frame: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Occultation> {
+        self.occultation_model(back_frame, front_frame, observer, ab_corr, false)
+    }
+
+    /// Computes the occultation percentage of the `back_frame` object by the `front_frame` object as seen from the observer,
+    /// modeling the front object as its tri-axial ellipsoid instead of a sphere of its mean equatorial radius.
+    ///
+    /// The apparent radius of the front object is that of its limb in the direction of the back object, which improves the
+    /// occultation predictions in grazing geometries, e.g. when the Sun sets over the pole of an oblate planet.
+    ///
+    /// # Frame warning
+    /// The ellipsoid is oriented in the orientation of the front frame: to account for the orientation of the body, the front frame
+    /// should be body fixed (e.g. IAU_EARTH_FRAME or IAU_MOON_FRAME).
+    ///
+    /// :type back_frame: Frame
+    /// :type front_frame: Frame
+    /// :type observer: Orbit
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: Occultation
+    pub fn occultation_triaxial(
+        &self,
+        back_frame: Frame,
+        front_frame: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Occultation> {
+        self.occultation_model(back_frame, front_frame, observer, ab_corr, true)
+    }
+
+    /// Computes the solar eclipsing of the observer due to the eclipsing_frame.
+    ///
+    /// This function calls `occultation` where the back object is the Sun in the J2000 frame, and the front object
+    /// is the provided eclipsing frame.
+    ///
+    /// :type eclipsing_frame: Frame
+    /// :type observer: Orbit
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: Occultation
+    pub fn solar_eclipsing(
+        &self,
+        eclipsing_frame: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Occultation> {
+        self.occultation(SUN_J2000, eclipsing_frame, observer, ab_corr)
+    }
+
+    /// Computes the solar eclipsing of the observer due to the eclipsing_frame, modeled as its tri-axial ellipsoid.
+    ///
+    /// This function calls `occultation_triaxial` where the back object is the Sun in the J2000 frame, and the front object
+    /// is the provided eclipsing frame, which should be body fixed.
+    ///
+    /// :type eclipsing_frame: Frame
+    /// :type observer: Orbit
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: Occultation
+    pub fn solar_eclipsing_triaxial(
+        &self,
+        eclipsing_frame: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Occultation> {
+        self.occultation_triaxial(SUN_J2000, eclipsing_frame, observer, ab_corr)
+    }
+
+    /// Computes the shadow function of the observer due to the eclipsing_frame, i.e. the fraction of the solar flux it receives,
+    /// from 0 in umbra to 1 in full sunlight, using the conical shadow model of `solar_eclipsing`.
+    ///
+    /// :type eclipsing_frame: Frame
+    /// :type observer: Orbit
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: float
+    pub fn solar_shadow_function(
+        &self,
+        eclipsing_frame: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<f64> {
+        Ok(1.0
+            - self
+                .solar_eclipsing(eclipsing_frame, observer, ab_corr)?
+                .factor())
+    }
+
+    /// Computes a smooth shadow function of the observer due to the eclipsing_frame, from 0 in umbra to 1 in full sunlight.
+    ///
+    /// Unlike the conical model of `solar_shadow_function`, this function is infinitely differentiable across the penumbra,
+    /// which suits the force models of propagators with adaptive step sizes and the partials of trajectory optimizers.
+    ///
+    /// # Algorithm
+    /// 1. Compute the apparent radii of the Sun and of the eclipsing body as seen from the observer, and the apparent separation of their centers.
+    /// 2. The shadow function is `(1 + tanh(sharpness * (separation - eclipsing radius) / Sun radius)) / 2`: the penumbra of the
+    ///    conical model, where the separation is within one apparent Sun radius of the apparent eclipsing radius, maps onto
+    ///    `[-sharpness, sharpness]`. A sharpness of about 5 closely follows the conical model.
+    ///
+    /// The observer is in umbra if it is inside the eclipsing body.
+    ///
+    /// :type eclipsing_frame: Frame
+    /// :type observer: Orbit
+    /// :type sharpness: float
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: float
+    pub fn smooth_solar_shadow_function(
+        &self,
+        eclipsing_frame: Frame,
+        observer: Orbit,
+        sharpness: f64,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<f64> {
+        let mut sun_frame = SUN_J2000;
+        if sun_frame.mean_equatorial_radius_km().is_err() {
+            sun_frame = self
+                .frame_from_uid(sun_frame)
+                .map_err(|e| AlmanacError::GenericError {
+                    err: format!("{e} when fetching {sun_frame:e} frame data"),
+                })?;
+        }
+        let mut eclipsing_frame = eclipsing_frame;
+        if eclipsing_frame.mean_equatorial_radius_km().is_err() {
+            eclipsing_frame =
+                self.frame_from_uid(eclipsing_frame)
+                    .map_err(|e| AlmanacError::GenericError {
+                        err: format!("{e} when fetching {eclipsing_frame:e} frame data"),
+                    })?;
+        }
+
+        let radius_km = |frame: Frame| {
+            frame
+                .mean_equatorial_radius_km()
+                .context(EphemerisPhysicsSnafu {
+                    action: "fetching mean equatorial radius",
+                })
+                .context(EphemerisSnafu {
+                    action: "computing smooth shadow function",
+                })
+        };
+        let sun_radius_km = radius_km(sun_frame)?;
+        let eclipsing_radius_km = radius_km(eclipsing_frame)?;
+
+        // Positions of the Sun and of the eclipsing body with respect to the observer
+        let observer = self
+            .rotate_to(observer, observer.frame.with_orient(J2000))
+            .context(OrientationSnafu {
+                action: "computing smooth shadow function",
+            })?;
+        let to_sun = -self
+            .transform_to(observer, sun_frame.with_orient(J2000), ab_corr)?
+            .radius_km;
+        let to_eclipsing = -self
+            .transform_to(observer, eclipsing_frame.with_orient(J2000), ab_corr)?
+            .radius_km;
+
+        if to_eclipsing.norm() <= eclipsing_radius_km {
+            return Ok(0.0);
+        }
+
+        let sun_apparent_rad = (sun_radius_km / to_sun.norm()).min(1.0).asin();
+        let eclipsing_apparent_rad = (eclipsing_radius_km / to_eclipsing.norm()).asin();
+        let separation_rad = to_sun.angle(&to_eclipsing);
+
+        Ok(0.5
+            * (1.0
+                + (sharpness * (separation_rad - eclipsing_apparent_rad) / sun_apparent_rad)
+                    .tanh()))
+    }
+}
+
+impl Almanac {
+    /// Computes the occultation, modeling the front object as its tri-axial ellipsoid if `triaxial_front` is set, or as a sphere otherwise.
+    fn occultation_model(
         &self,
         mut back_frame: Frame,
         mut front_frame: Frame,
         mut observer: Orbit,
         ab_corr: Option<Aberration>,
+        triaxial_front: bool,
     ) -> AlmanacResult<Occultation> {
         if back_frame.mean_equatorial_radius_km().is_err() {
             back_frame =
@@ -162,17 +342,18 @@ impl Almanac {
         // If the back object's radius is zero, just call the line of sight algorithm
         if bobj_mean_eq_radius_km < f64::EPSILON {
             let observed = -self.transform_to(observer, back_frame, ab_corr)?;
-            let percentage =
+            let (percentage, kind) =
                 if self.line_of_sight_obstructed(observer, observed, front_frame, ab_corr)? {
-                    100.0
+                    (100.0, OccultationKind::Total)
                 } else {
-                    0.0
+                    (0.0, OccultationKind::Visible)
                 };
             return Ok(Occultation {
                 epoch,
                 percentage,
                 back_frame,
                 front_frame,
+                kind,
             });
         }
 
@@ -211,12 +392,42 @@ impl Almanac {
                 action: "computing eclipse state",
             })?;
 
-        let r_fobj_prime = if fobj_mean_eq_radius_km >= r_eb.norm() {
+        let mut r_fobj_prime = if fobj_mean_eq_radius_km >= r_eb.norm() {
             fobj_mean_eq_radius_km
         } else {
             (fobj_mean_eq_radius_km / r_eb.norm()).asin()
         };
 
+        if triaxial_front {
+            // Rotate the geometry into the orientation of the front object, where its ellipsoid is defined.
+            let shape = front_frame
+                .shape
+                .ok_or(PhysicsError::MissingFrameData {
+                    action: "computing triaxial occultation",
+                    data: "shape",
+                    frame: front_frame.into(),
+                })
+                .context(EphemerisPhysicsSnafu {
+                    action: "fetching shape of front object",
+                })
+                .context(EphemerisSnafu {
+                    action: "computing eclipse state",
+                })?;
+
+            let dcm =
+                self.rotate(observer.frame, front_frame, epoch)
+                    .context(OrientationSnafu {
+                        action: "computing triaxial eclipse state",
+                    })?;
+
+            // If the observer is inside the ellipsoid, keep the spherical apparent radius.
+            if let Some(limb_angle_rad) =
+                apparent_limb_angle_rad(&shape, &(dcm.rot_mat * r_eb), &(dcm.rot_mat * r_ls))
+            {
+                r_fobj_prime = limb_angle_rad;
+            }
+        }
+
         // Compute the apparent separation of both circles
         let d_prime = (-(r_ls.dot(&r_eb)) / (r_eb.norm() * r_ls.norm())).acos();
 
@@ -229,6 +440,7 @@ impl Almanac {
                 percentage: 0.0,
                 back_frame,
                 front_frame,
+                kind: OccultationKind::Visible,
             })
         } else if r_fobj_prime > d_prime + r_ls_prime {
             // The back object is fully hidden by the front object, hence we're in total eclipse.
@@ -237,6 +449,7 @@ impl Almanac {
                 percentage: 100.0,
                 back_frame,
                 front_frame,
+                kind: OccultationKind::Total,
             })
         } else if (r_ls_prime - r_fobj_prime).abs() < d_prime && d_prime < r_ls_prime + r_fobj_prime
         {
@@ -263,6 +476,7 @@ impl Almanac {
                     percentage: 100.0,
                     back_frame,
                     front_frame,
+                    kind: OccultationKind::Total,
                 });
             }
             // Compute the nominal area of the back object
@@ -274,6 +488,7 @@ impl Almanac {
                 percentage,
                 back_frame,
                 front_frame,
+                kind: OccultationKind::Partial,
             })
         } else {
             // Annular eclipse.
@@ -284,27 +499,61 @@ impl Almanac {
                 percentage,
                 back_frame,
                 front_frame,
+                kind: OccultationKind::Annular,
             })
         }
     }
+}
 
-    /// Computes the solar eclipsing of the observer due to the eclipsing_frame.
-    ///
-    /// This function calls `occultation` where the back object is the Sun in the J2000 frame, and the front object
-    /// is the provided eclipsing frame.
-    ///
-    /// :type eclipsing_frame: Frame
-    /// :type observer: Orbit
-    /// :type ab_corr: Aberration, optional
-    /// :rtype: Occultation
-    pub fn solar_eclipsing(
-        &self,
-        eclipsing_frame: Frame,
-        observer: Orbit,
-        ab_corr: Option<Aberration>,
-    ) -> AlmanacResult<Occultation> {
-        self.occultation(SUN_J2000, eclipsing_frame, observer, ab_corr)
+/// Returns the apparent angular radius (in radians) of the limb of the ellipsoid as seen from the observer, in the plane containing
+/// the center of the ellipsoid and the provided direction. The observer position is relative to the center of the ellipsoid, and all
+/// vectors are expressed in the frame of the ellipsoid.
+///
+/// Returns None if the observer is inside the ellipsoid, or if the limb is not within 90 degrees of the center of the ellipsoid.
+fn apparent_limb_angle_rad(
+    shape: &Ellipsoid,
+    observer_km: &Vector3,
+    toward: &Vector3,
+) -> Option<f64> {
+    let radii_sq = Vector3::new(
+        shape.semi_major_equatorial_radius_km.powi(2),
+        shape.semi_minor_equatorial_radius_km.powi(2),
+        shape.polar_radius_km.powi(2),
+    );
+    // Quadratic form of the ellipsoid, such that the surface is x^T A x = 1.
+    let quad = |v: &Vector3| v.component_div(&radii_sq);
+
+    let c0 = observer_km.dot(&quad(observer_km)) - 1.0;
+    if c0 <= 0.0 {
+        return None;
+    }
+
+    // Build an orthonormal basis of the plane, where u points to the center of the ellipsoid.
+    let u = -observer_km.normalize();
+    let mut w = toward - u * toward.dot(&u);
+    if w.norm() < f64::EPSILON {
+        // The direction is aligned with the center: pick any perpendicular direction.
+        w = u.cross(&Vector3::z());
+        if w.norm() < f64::EPSILON {
+            w = u.cross(&Vector3::x());
+        }
+    }
+    let w = w.normalize();
+
+    // A ray along cos(θ) u + sin(θ) w is tangent to the ellipsoid when the discriminant of its intersection
+    // vanishes, which is a quadratic in tan(θ): r tan²(θ) + 2 q tan(θ) + p = 0.
+    let o_a = quad(observer_km);
+    let (oau, oaw) = (o_a.dot(&u), o_a.dot(&w));
+    let p = oau.powi(2) - c0 * u.dot(&quad(&u));
+    let q = oau * oaw - c0 * u.dot(&quad(&w));
+    let r = oaw.powi(2) - c0 * w.dot(&quad(&w));
+
+    if p <= 0.0 || r >= 0.0 {
+        return None;
     }
+
+    // Since p > 0 and r < 0, this is the only positive root.
+    Some(((-q - (q.powi(2) - p * r).sqrt()) / r).atan())
 }
 
 /// Compute the area of the circular segment of radius r and chord length d
@@ -470,4 +719,235 @@ mod ut_los {
             Ok(false)
         );
     }
+
+    #[rstest]
+    fn occultation_kinds(almanac: Almanac) {
+        use crate::constants::frames::SUN_J2000;
+        use crate::math::Vector3;
+
+        let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+
+        let r_moon = almanac
+            .translate(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap()
+            .radius_km;
+        let r_sun = almanac
+            .translate(SUN_J2000, EARTH_J2000, epoch, None)
+            .unwrap()
+            .radius_km;
+
+        // Anti-Sun direction from the Moon, and a direction orthogonal to it.
+        let u = (r_moon - r_sun).normalize();
+        let w = u.cross(&Vector3::z()).normalize();
+
+        let observer_at = |r: Vector3| {
+            let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+            Orbit::from_position(r.x, r.y, r.z, epoch, eme2k)
+        };
+
+        // Right behind the Moon, its disk is much larger than the Sun's: total.
+        let occult = almanac
+            .solar_eclipsing(MOON_J2000, observer_at(r_moon + u * 10_000.0), None)
+            .unwrap();
+        assert_eq!(occult.kind, OccultationKind::Total, "{occult}");
+        assert!(occult.is_obstructed());
+
+        // Far behind the Moon, its disk fits within the Sun's: annular.
+        let occult = almanac
+            .solar_eclipsing(MOON_J2000, observer_at(r_moon + u * 2e6), None)
+            .unwrap();
+        assert_eq!(occult.kind, OccultationKind::Annular, "{occult}");
+        assert!(occult.is_annular());
+        assert!(occult.is_partial());
+
+        // Offset such that the center of the Moon is on the limb of the Sun: partial.
+        let occult = almanac
+            .solar_eclipsing(
+                MOON_J2000,
+                observer_at(r_moon + u * 2e6 + w * 9_300.0),
+                None,
+            )
+            .unwrap();
+        assert_eq!(occult.kind, OccultationKind::Partial, "{occult}");
+        assert!(occult.is_partial());
+        assert!(!occult.is_annular());
+
+        // Far off the shadow axis: visible.
+        let occult = almanac
+            .solar_eclipsing(
+                MOON_J2000,
+                observer_at(r_moon + u * 2e6 + w * 100_000.0),
+                None,
+            )
+            .unwrap();
+        assert_eq!(occult.kind, OccultationKind::Visible, "{occult}");
+        assert!(occult.is_visible());
+    }
+
+    #[test]
+    fn apparent_limb_angles() {
+        let distance_km = 10_000.0;
+        let observer = Vector3::new(distance_km, 0.0, 0.0);
+
+        // A sphere matches the spherical apparent radius in all directions.
+        let sphere = Ellipsoid::from_sphere(6378.0);
+        for toward in [-Vector3::x(), Vector3::y(), Vector3::z(), -Vector3::z()] {
+            let angle = apparent_limb_angle_rad(&sphere, &observer, &toward).unwrap();
+            assert!((angle - (6378.0_f64 / distance_km).asin()).abs() < 1e-12);
+        }
+
+        // For a tri-axial ellipsoid seen from the X axis, the limb in the X-Y plane depends on the second radius,
+        // and the limb in the X-Z plane on the polar radius.
+        let shape = Ellipsoid {
+            semi_major_equatorial_radius_km: 6378.0,
+            semi_minor_equatorial_radius_km: 6370.0,
+            polar_radius_km: 6357.0,
+        };
+        let eq_angle = apparent_limb_angle_rad(&shape, &observer, &Vector3::y()).unwrap();
+        let expected = (6370.0 / (distance_km.powi(2) - 6378.0_f64.powi(2)).sqrt()).atan();
+        assert!((eq_angle - expected).abs() < 1e-12);
+
+        let polar_angle = apparent_limb_angle_rad(&shape, &observer, &Vector3::z()).unwrap();
+        let expected = (6357.0 / (distance_km.powi(2) - 6378.0_f64.powi(2)).sqrt()).atan();
+        assert!((polar_angle - expected).abs() < 1e-12);
+        assert!(polar_angle < eq_angle);
+
+        // Inside the ellipsoid
+        assert!(apparent_limb_angle_rad(&shape, &Vector3::zeros(), &Vector3::z()).is_none());
+    }
+
+    #[rstest]
+    fn triaxial_eclipse(almanac: Almanac) {
+        use crate::constants::frames::{IAU_EARTH_FRAME, SUN_J2000};
+
+        let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+        let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+
+        let r_sun = almanac
+            .translate(SUN_J2000, EARTH_J2000, epoch, None)
+            .unwrap()
+            .radius_km
+            .normalize();
+
+        // Deep in the shadow of the Earth, and fully sunlit on the day side.
+        for (factor, expected) in [(-7000.0, 100.0), (7000.0, 0.0)] {
+            let r = r_sun * factor;
+            let observer = Orbit::from_position(r.x, r.y, r.z, epoch, eme2k);
+
+            let spherical = almanac
+                .solar_eclipsing(IAU_EARTH_FRAME, observer, None)
+                .unwrap();
+            let triaxial = almanac
+                .solar_eclipsing_triaxial(IAU_EARTH_FRAME, observer, None)
+                .unwrap();
+
+            assert_eq!(spherical.percentage, expected, "{spherical}");
+            assert_eq!(triaxial.percentage, expected, "{triaxial}");
+        }
+
+        // Over the pole, the limb is set by the polar radius which is smaller than the mean equatorial radius,
+        // so the triaxial model is never more occulted than the spherical one.
+        let pole_dir = Vector3::z();
+        let side = r_sun.cross(&pole_dir).normalize();
+        let mut num_strictly_less = 0;
+        for offset_km in [6300.0, 6340.0, 6360.0, 6380.0, 6420.0] {
+            // Observer behind the Earth, with the Sun grazing the polar limb.
+            let r = -r_sun * 20_000.0 + r_sun.cross(&side).normalize() * offset_km;
+            let observer = Orbit::from_position(r.x, r.y, r.z, epoch, eme2k);
+
+            let spherical = almanac
+                .solar_eclipsing(IAU_EARTH_FRAME, observer, None)
+                .unwrap();
+            let triaxial = almanac
+                .solar_eclipsing_triaxial(IAU_EARTH_FRAME, observer, None)
+                .unwrap();
+
+            assert!(
+                triaxial.percentage <= spherical.percentage + 1e-9,
+                "{triaxial} vs {spherical}"
+            );
+            if triaxial.percentage < spherical.percentage - 1e-3 {
+                num_strictly_less += 1;
+            }
+        }
+        // The grazing geometries are sensitive to the flattening.
+        assert!(num_strictly_less > 0);
+    }
+
+    #[rstest]
+    fn smooth_shadow_function(almanac: Almanac) {
+        let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+        let epoch = Epoch::from_gregorian_tai_at_midnight(2020, 1, 1);
+
+        let r_sun = almanac
+            .translate(SUN_J2000, EARTH_J2000, epoch, None)
+            .unwrap()
+            .radius_km
+            .normalize();
+        let side = r_sun.cross(&Vector3::z()).normalize();
+
+        // Moving out of the shadow of the Earth across the penumbra
+        let mut prev_smooth = 0.0;
+        let mut num_penumbra = 0;
+        for step in 0..=300 {
+            let offset_km = 5_000.0 + 10.0 * step as f64;
+            let r = -r_sun * 20_000.0 + side * offset_km;
+            let observer = Orbit::from_position(r.x, r.y, r.z, epoch, eme2k);
+
+            let conical = almanac
+                .solar_shadow_function(EARTH_J2000, observer, None)
+                .unwrap();
+            let smooth = almanac
+                .smooth_solar_shadow_function(EARTH_J2000, observer, 5.0, None)
+                .unwrap();
+
+            assert!((0.0..=1.0).contains(&conical) && (0.0..=1.0).contains(&smooth));
+            assert!(smooth >= prev_smooth, "{offset_km} km");
+            assert!(
+                (smooth - conical).abs() < 0.15,
+                "{offset_km} km: {smooth} vs {conical}"
+            );
+            if conical > 0.0 && conical < 1.0 {
+                num_penumbra += 1;
+            }
+            prev_smooth = smooth;
+        }
+        assert!(num_penumbra > 5);
+        assert!(prev_smooth > 0.999);
+
+        // Umbra, full sunlight, and inside the eclipsing body
+        let umbra = Orbit::from_position(
+            -r_sun.x * 7000.0,
+            -r_sun.y * 7000.0,
+            -r_sun.z * 7000.0,
+            epoch,
+            eme2k,
+        );
+        assert!(
+            almanac
+                .smooth_solar_shadow_function(EARTH_J2000, umbra, 5.0, None)
+                .unwrap()
+                < 1e-3
+        );
+        let sunlit = Orbit::from_position(
+            r_sun.x * 7000.0,
+            r_sun.y * 7000.0,
+            r_sun.z * 7000.0,
+            epoch,
+            eme2k,
+        );
+        assert!(
+            almanac
+                .smooth_solar_shadow_function(EARTH_J2000, sunlit, 5.0, None)
+                .unwrap()
+                > 0.999
+        );
+        let inside = Orbit::from_position(100.0, 0.0, 0.0, epoch, eme2k);
+        assert_eq!(
+            almanac
+                .smooth_solar_shadow_function(EARTH_J2000, inside, 5.0, None)
+                .unwrap(),
+            0.0
+        );
+    }
 }
@@ -0,0 +1,128 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::path::Path;
+
+use hifitime::{Epoch, TimeSeries};
+use snafu::ResultExt;
+
+use crate::ephemerides::EphemerisError;
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu};
+use crate::naif::daf::DafDataType;
+use crate::naif::SPK;
+use crate::prelude::Frame;
+
+use super::Almanac;
+
+impl Almanac {
+    /// Exports the states of the target with respect to the observer into a new SPK file at the provided path,
+    /// which can then be loaded by ANISE or CSPICE.
+    ///
+    /// The states are geometric (no aberration correction) and expressed in the orientation of the observer frame, i.e.
+    /// they are the states returned by `transform(target, observer, epoch, None)`.
+    ///
+    /// # Data types
+    /// + `Type2ChebyshevTriplet`: the whole time series is stored in a single segment, where each Chebyshev record spans
+    ///   the step of the time series, and the positions are fitted on `degree + 1` Chebyshev nodes per record;
+    /// + `Type13HermiteUnequalStep`: the states at each epoch of the time series are stored, and interpolated with Hermite
+    ///   polynomials of the provided (odd) degree.
+    ///
+    /// # Limitations
+    /// The target and observer must be in the loaded ephemeris data throughout the whole time series.
+    #[allow(clippy::too_many_arguments)]
+    pub fn export_spk<P: AsRef<Path>>(
+        &self,
+        target: Frame,
+        observer: Frame,
+        time_series: TimeSeries,
+        datatype: DafDataType,
+        degree: usize,
+        path: P,
+    ) -> AlmanacResult<SPK> {
+        let epochs: Vec<Epoch> = time_series.collect();
+        if epochs.len() < 2 {
+            return Err(AlmanacError::GenericError {
+                err: "exporting an SPK requires a time series of at least two epochs".to_string(),
+            });
+        }
+
+        let name = format!("{target:e} wrt {observer:e}");
+        let mut builder = SPK::builder(&name).with_comments(&format!(
+            "{name}\nExported by ANISE v{}\nAlmanac context digest: 0x{:08x}",
+            env!("CARGO_PKG_VERSION"),
+            self.context_digest()
+        ));
+
+        match datatype {
+            DafDataType::Type2ChebyshevTriplet => {
+                // The builder requires an ephemeris error, so keep track of the first error from the transform.
+                let mut query_err = None;
+                let rslt = builder.add_type2_segment(
+                    &name,
+                    target.ephemeris_id,
+                    observer.ephemeris_id,
+                    observer.orientation_id,
+                    epochs[0],
+                    epochs[epochs.len() - 1],
+                    epochs[1] - epochs[0],
+                    degree,
+                    |epoch| match self.transform(target, observer, epoch, None) {
+                        Ok(state) => Ok(state),
+                        Err(e) => {
+                            query_err.get_or_insert(e);
+                            Err(EphemerisError::Unreachable)
+                        }
+                    },
+                );
+                if let Some(e) = query_err {
+                    return Err(e);
+                }
+                rslt.context(EphemerisSnafu {
+                    action: "exporting Type 2 SPK segment",
+                })?;
+            }
+            DafDataType::Type13HermiteUnequalStep => {
+                let states = epochs
+                    .iter()
+                    .map(|epoch| self.transform(target, observer, *epoch, None))
+                    .collect::<AlmanacResult<Vec<_>>>()?;
+
+                builder
+                    .add_type13_segment(
+                        &name,
+                        target.ephemeris_id,
+                        observer.ephemeris_id,
+                        observer.orientation_id,
+                        &states,
+                        degree,
+                    )
+                    .context(EphemerisSnafu {
+                        action: "exporting Type 13 SPK segment",
+                    })?;
+            }
+            _ => {
+                return Err(AlmanacError::GenericError {
+                    err: format!("exporting SPK data of type {datatype:?} is not supported, use Type 2 or Type 13"),
+                })
+            }
+        }
+
+        let spk = builder.build().context(EphemerisSnafu {
+            action: "building exported SPK",
+        })?;
+
+        spk.persist(path.as_ref())
+            .map_err(|e| AlmanacError::GenericError {
+                err: format!("could not write SPK to {}: {e}", path.as_ref().display()),
+            })?;
+
+        Ok(spk)
+    }
+}
@@ -0,0 +1,224 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::Epoch;
+
+use super::Almanac;
+use crate::ephemerides::paths::MAX_TREE_DEPTH;
+use crate::errors::{AlmanacError, AlmanacResult};
+use crate::frames::Frame;
+use crate::NaifId;
+
+/// A hop of a frame path between two NAIF IDs, and the kernel or data set which provides the data for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathHop {
+    pub from_id: NaifId,
+    pub to_id: NaifId,
+    /// Alias of the kernel (or name of the data set) providing the data of this hop, None if the kernel has no alias
+    pub kernel: Option<String>,
+}
+
+impl fmt::Display for PathHop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {} ({})",
+            self.from_id,
+            self.to_id,
+            self.kernel.as_deref().unwrap_or("no alias")
+        )
+    }
+}
+
+/// Returns the nodes of the path of a frame to the root, as returned by the path to root functions, starting with the source itself.
+fn nodes_of(source: NaifId, len: usize, path: [Option<NaifId>; MAX_TREE_DEPTH]) -> Vec<NaifId> {
+    core::iter::once(source)
+        .chain(path.iter().take(len).flatten().copied())
+        .collect()
+}
+
+/// Joins the nodes of the paths of two frames to the root into the hops from the first to the second, through their common node.
+///
+/// The kernel of each hop is provided by `kernel_of`, called with the child and parent IDs of that hop.
+fn join_paths(
+    from_nodes: &[NaifId],
+    to_nodes: &[NaifId],
+    kernel_of: impl Fn(NaifId, NaifId) -> Option<String>,
+) -> AlmanacResult<Vec<PathHop>> {
+    let (from_len, to_len) = from_nodes
+        .iter()
+        .enumerate()
+        .find_map(|(from_len, node)| {
+            to_nodes
+                .iter()
+                .position(|to_node| to_node == node)
+                .map(|to_len| (from_len, to_len))
+        })
+        .ok_or_else(|| AlmanacError::GenericError {
+            err: format!(
+                "paths {from_nodes:?} and {to_nodes:?} do not share a common node, the loaded data is disjoint"
+            ),
+        })?;
+
+    // Walk up from the source to the common node, then down to the destination.
+    let mut path = from_nodes
+        .windows(2)
+        .take(from_len)
+        .map(|hop| PathHop {
+            from_id: hop[0],
+            to_id: hop[1],
+            kernel: kernel_of(hop[0], hop[1]),
+        })
+        .collect::<Vec<_>>();
+    for hop in to_nodes.windows(2).take(to_len).rev() {
+        path.push(PathHop {
+            from_id: hop[1],
+            to_id: hop[0],
+            kernel: kernel_of(hop[0], hop[1]),
+        });
+    }
+
+    Ok(path)
+}
+
+impl Almanac {
+    /// Returns the hops of the ephemeris path from one frame to another at the provided epoch, through their common node,
+    /// and the alias of the SPK providing the data of each hop. Hops past the common node are in the reverse direction of the SPK data.
+    ///
+    /// The nodes are those of [Self::ephemeris_path_to_root] for both frames. If a hop has no data at this epoch, the error
+    /// includes the coverage of every segment considered, cf. [Self::ephemeris_path_diagnostic].
+    pub fn ephemeris_path(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+    ) -> AlmanacResult<Vec<PathHop>> {
+        if from_frame.ephemeris_id == to_frame.ephemeris_id {
+            return Ok(Vec::new());
+        }
+
+        let mut nodes = Vec::with_capacity(2);
+        for frame in [from_frame, to_frame] {
+            let (len, path) = self.ephemeris_path_to_root(frame, epoch).map_err(|_| {
+                AlmanacError::GenericError {
+                    err: format!(
+                        "no path at epoch: {}",
+                        self.ephemeris_path_diagnostic(frame, epoch)
+                    ),
+                }
+            })?;
+            nodes.push(nodes_of(frame.ephemeris_id, len, path));
+        }
+
+        join_paths(&nodes[0], &nodes[1], |id, _| {
+            self.ephemeris_hop_segment(id, epoch)
+                .and_then(|(spk_no, _)| self.spk_aliases[spk_no].clone())
+        })
+    }
+
+    /// Returns the hops of the orientation path from one frame to another at the provided epoch, through their common node,
+    /// and the alias of the BPC (or name of the data set) providing the data of each hop. Hops past the common node are in the reverse direction of the data.
+    ///
+    /// The nodes are those of [Self::orientation_path_to_root] for both frames. If a hop has no data at this epoch, the error
+    /// includes the coverage of every segment considered, cf. [Self::orientation_path_diagnostic].
+    pub fn orientation_path(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+    ) -> AlmanacResult<Vec<PathHop>> {
+        if from_frame.orientation_id == to_frame.orientation_id {
+            return Ok(Vec::new());
+        }
+
+        let mut nodes = Vec::with_capacity(2);
+        for frame in [from_frame, to_frame] {
+            let (len, path) = self.orientation_path_to_root(frame, epoch).map_err(|_| {
+                AlmanacError::GenericError {
+                    err: format!(
+                        "no path at epoch: {}",
+                        self.orientation_path_diagnostic(frame, epoch)
+                    ),
+                }
+            })?;
+            nodes.push(nodes_of(frame.orientation_id, len, path));
+        }
+
+        join_paths(&nodes[0], &nodes[1], |id, parent_id| {
+            match self.orientation_hop_source(id, parent_id, epoch) {
+                (_, Some(dataset)) => Some(dataset.to_string()),
+                (Some((bpc_no, _)), None) => self.bpc_aliases[bpc_no].clone(),
+                (None, None) => None,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod ut_frame_path {
+    use crate::constants::frames::{
+        EARTH_ITRF93, EARTH_J2000, IAU_EARTH_FRAME, IAU_MOON_FRAME, MOON_J2000, SSB_J2000,
+    };
+    use crate::prelude::Almanac;
+    use hifitime::Epoch;
+
+    #[test]
+    fn frame_paths() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+        let path = almanac
+            .ephemeris_path(MOON_J2000, EARTH_J2000, epoch)
+            .unwrap();
+        let ids = path
+            .iter()
+            .map(|hop| (hop.from_id, hop.to_id))
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![(301, 3), (3, 399)]);
+        assert!(path
+            .iter()
+            .all(|hop| hop.kernel.as_deref() == Some("../data/de440s.bsp")));
+
+        // Up to the root
+        let path = almanac
+            .ephemeris_path(MOON_J2000, SSB_J2000, epoch)
+            .unwrap();
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[1].to_id, 0);
+        let path = almanac
+            .ephemeris_path(SSB_J2000, MOON_J2000, epoch)
+            .unwrap();
+        assert_eq!(path[0].from_id, 0);
+        assert_eq!(path[1].to_id, 301);
+
+        assert!(almanac
+            .ephemeris_path(EARTH_J2000, EARTH_J2000, epoch)
+            .unwrap()
+            .is_empty());
+
+        let path = almanac
+            .orientation_path(IAU_MOON_FRAME, IAU_EARTH_FRAME, epoch)
+            .unwrap();
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[0].from_id, IAU_MOON_FRAME.orientation_id);
+        assert_eq!(path[1].to_id, IAU_EARTH_FRAME.orientation_id);
+        assert_eq!(path[0].kernel.as_deref(), Some("planetary constants"));
+
+        let err = almanac
+            .orientation_path(EARTH_ITRF93, EARTH_J2000, epoch)
+            .unwrap_err();
+        assert!(format!("{err}").contains("NO DATA AT EPOCH"));
+    }
+}
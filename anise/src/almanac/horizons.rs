@@ -0,0 +1,364 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::fs::create_dir_all;
+use std::path::PathBuf;
+
+use hifitime::{Duration, Epoch, Unit};
+use log::{info, warn};
+use serde_json::Value;
+use snafu::prelude::*;
+use url::Url;
+
+use super::metaload::{cache_dir, MetaAlmanacError};
+use super::Almanac;
+use crate::constants::orientations::J2000;
+use crate::ephemerides::EphemerisError;
+use crate::errors::{AlmanacError, InputOutputError};
+use crate::math::cartesian::CartesianState;
+use crate::math::Vector3;
+use crate::naif::SPK;
+use crate::prelude::Frame;
+use crate::NaifId;
+
+/// Endpoint of the JPL Horizons API.
+pub const HORIZONS_API_URL: &str = "https://ssd.jpl.nasa.gov/api/horizons.api";
+
+/// NAIF IDs from which the targets are small bodies, queried by their SPK ID in Horizons.
+const SMALL_BODY_MIN_ID: NaifId = 1_000_000;
+
+/// Degree of the Hermite polynomials of the segments built from the Horizons vector tables.
+const HORIZONS_HERMITE_DEGREE: usize = 7;
+
+#[derive(Debug, Snafu, PartialEq)]
+#[snafu(visibility(pub(crate)))]
+pub enum HorizonsError {
+    #[snafu(display("invalid Horizons query: {reason}"))]
+    InvalidQuery { reason: String },
+    #[snafu(display("fetching {uri} returned {error}"))]
+    HorizonsCnx { uri: String, error: String },
+    #[snafu(display("Horizons API returned an error: {message}"))]
+    HorizonsApi { message: String },
+    #[snafu(display("Horizons response does not include a vector table between $$SOE and $$EOE"))]
+    NoVectorTable,
+    #[snafu(display("could not parse Horizons vector table row `{row}`"))]
+    InvalidRow { row: String },
+    #[snafu(display("when {action}, {source}"))]
+    HorizonsCache {
+        action: &'static str,
+        source: MetaAlmanacError,
+    },
+    #[snafu(display("when {action}, {source}"))]
+    HorizonsEphemeris {
+        action: &'static str,
+        #[snafu(source(from(EphemerisError, Box::new)))]
+        source: Box<EphemerisError>,
+    },
+    #[snafu(display("when {action}, {source}"))]
+    HorizonsAlmanac {
+        action: &'static str,
+        #[snafu(source(from(AlmanacError, Box::new)))]
+        source: Box<AlmanacError>,
+    },
+}
+
+/// A query of the geometric states of a target with respect to a center in the J2000 (ICRF) orientation from the JPL Horizons API.
+///
+/// Small bodies (NAIF IDs above one million, e.g. 2000433 for Eros) are queried by their SPK ID.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HorizonsQuery {
+    pub target_id: NaifId,
+    pub center_id: NaifId,
+    pub start: Epoch,
+    pub end: Epoch,
+    /// Step between the states, rounded to the minute (the resolution of Horizons)
+    pub step: Duration,
+}
+
+impl HorizonsQuery {
+    pub fn new(
+        target_id: NaifId,
+        center_id: NaifId,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+    ) -> Self {
+        Self {
+            target_id,
+            center_id,
+            start,
+            end,
+            step,
+        }
+    }
+
+    /// Returns the URL of this query of the Horizons API, requesting a CSV vector table of positions and velocities in km and km/s.
+    pub fn url(&self) -> Result<Url, HorizonsError> {
+        ensure!(
+            self.end > self.start,
+            InvalidQuerySnafu {
+                reason: format!("end {} is not after start {}", self.end, self.start)
+            }
+        );
+        let step_min = self.step.to_unit(Unit::Minute).round();
+        ensure!(
+            step_min >= 1.0,
+            InvalidQuerySnafu {
+                reason: format!("step {} is shorter than a minute", self.step)
+            }
+        );
+
+        let command = if self.target_id >= SMALL_BODY_MIN_ID {
+            format!("'DES={};'", self.target_id)
+        } else {
+            format!("'{}'", self.target_id)
+        };
+        let jd_tdb = |epoch: Epoch| format!("'JD {:.9}'", epoch.to_jde_tdb_days());
+
+        Url::parse_with_params(
+            HORIZONS_API_URL,
+            &[
+                ("format", "json".to_string()),
+                ("COMMAND", command),
+                ("OBJ_DATA", "'NO'".to_string()),
+                ("MAKE_EPHEM", "'YES'".to_string()),
+                ("EPHEM_TYPE", "'VECTORS'".to_string()),
+                ("CENTER", format!("'500@{}'", self.center_id)),
+                ("REF_PLANE", "'FRAME'".to_string()),
+                ("REF_SYSTEM", "'ICRF'".to_string()),
+                ("START_TIME", jd_tdb(self.start)),
+                ("STOP_TIME", jd_tdb(self.end)),
+                ("STEP_SIZE", format!("'{step_min} min'")),
+                ("TIME_TYPE", "'TDB'".to_string()),
+                ("VEC_TABLE", "'2'".to_string()),
+                ("VEC_CORR", "'NONE'".to_string()),
+                ("OUT_UNITS", "'KM-S'".to_string()),
+                ("CSV_FORMAT", "'YES'".to_string()),
+                ("VEC_LABELS", "'NO'".to_string()),
+            ],
+        )
+        .map_err(|e| HorizonsError::InvalidQuery {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Fetches the states of this query from the Horizons API.
+    pub fn fetch(&self) -> Result<Vec<CartesianState>, HorizonsError> {
+        let url = self.url()?;
+        info!("querying Horizons for {url}");
+
+        let client = reqwest::blocking::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(30))
+            .timeout(std::time::Duration::from_secs(120))
+            .build()
+            .unwrap();
+
+        let body = client
+            .get(url.clone())
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .and_then(|resp| resp.text())
+            .map_err(|e| HorizonsError::HorizonsCnx {
+                uri: url.to_string(),
+                error: e.to_string(),
+            })?;
+
+        self.parse_response(&body)
+    }
+
+    /// Parses the JSON response of the Horizons API to this query.
+    pub fn parse_response(&self, json: &str) -> Result<Vec<CartesianState>, HorizonsError> {
+        let root: Value = serde_json::from_str(json).map_err(|e| HorizonsError::HorizonsApi {
+            message: format!("invalid JSON: {e}"),
+        })?;
+
+        if let Some(message) = root.get("error").and_then(Value::as_str) {
+            return Err(HorizonsError::HorizonsApi {
+                message: message.to_string(),
+            });
+        }
+
+        let result = root
+            .get("result")
+            .and_then(Value::as_str)
+            .context(NoVectorTableSnafu)?;
+
+        parse_vector_table(result, Frame::new(self.center_id, J2000))
+    }
+
+    /// Builds an SPK with a single Type 13 segment of the states of this query.
+    pub fn to_spk(&self, states: &[CartesianState]) -> Result<SPK, HorizonsError> {
+        let mut builder =
+            SPK::builder(&format!("HORIZONS {}", self.target_id)).with_comments(&format!(
+            "Ephemeris of {} with respect to {} fetched from JPL Horizons by ANISE {}\nQuery: {}",
+            self.target_id,
+            self.center_id,
+            env!("CARGO_PKG_VERSION"),
+            self.url()?
+        ));
+
+        builder
+            .add_type13_segment(
+                &format!("HORIZONS {} WRT {}", self.target_id, self.center_id),
+                self.target_id,
+                self.center_id,
+                J2000,
+                states,
+                HORIZONS_HERMITE_DEGREE,
+            )
+            .and_then(|_| builder.build())
+            .context(HorizonsEphemerisSnafu {
+                action: "building Horizons SPK",
+            })
+    }
+
+    /// Returns the path of the SPK of this query in the cache folder of the MetaAlmanac, named after the CRC32 of the query URL.
+    pub fn cached_path(&self) -> Result<PathBuf, HorizonsError> {
+        let dir = cache_dir()
+            .ok_or(MetaAlmanacError::AppDirError)
+            .context(HorizonsCacheSnafu {
+                action: "locating the cache folder",
+            })?;
+        let crc32 = crc32fast::hash(self.url()?.as_str().as_bytes());
+        Ok(dir.join(format!("horizons-{}-{crc32:08x}.bsp", self.target_id)))
+    }
+}
+
+impl Almanac {
+    /// Loads the ephemeris of the Horizons query into a clone of this Almanac, fetching it from the JPL Horizons API if it is not
+    /// already in the cache folder of the MetaAlmanac (cf. `MetaAlmanac::cache_dir`).
+    ///
+    /// The fetched states are stored as a Type 13 SPK in the cache, so the same query works offline afterwards and the cached
+    /// files are managed with `MetaAlmanac::purge_cache`. Failures to write the cache are logged and otherwise ignored.
+    pub fn load_horizons(&self, query: &HorizonsQuery) -> Result<Self, HorizonsError> {
+        let path = query.cached_path()?;
+        let path_s = path.to_string_lossy().to_string();
+
+        if path.exists() {
+            info!("using cached Horizons ephemeris {path_s}");
+            return self.load(&path_s).context(HorizonsAlmanacSnafu {
+                action: "loading cached Horizons SPK",
+            });
+        }
+
+        let spk = query.to_spk(&query.fetch()?)?;
+
+        match path
+            .parent()
+            .map_or(Ok(()), create_dir_all)
+            .and_then(|_| spk.persist(&path))
+        {
+            Ok(()) => info!("cached Horizons ephemeris as {path_s}"),
+            Err(e) => warn!(
+                "{} -- Horizons ephemeris not cached",
+                MetaAlmanacError::MetaIO {
+                    path: path_s,
+                    what: "caching Horizons SPK",
+                    source: InputOutputError::IOError { kind: e.kind() },
+                }
+            ),
+        }
+
+        self.with_spk(spk).context(HorizonsEphemerisSnafu {
+            action: "loading Horizons SPK",
+        })
+    }
+}
+
+/// Parses the rows of the CSV vector table between the `$$SOE` and `$$EOE` markers, i.e. the Julian date in TDB, the calendar date,
+/// and the position and velocity components.
+pub fn parse_vector_table(
+    result: &str,
+    frame: Frame,
+) -> Result<Vec<CartesianState>, HorizonsError> {
+    let start = result.find("$$SOE").context(NoVectorTableSnafu)? + "$$SOE".len();
+    let end = result[start..].find("$$EOE").context(NoVectorTableSnafu)? + start;
+
+    result[start..end]
+        .lines()
+        .map(str::trim)
+        .filter(|row| !row.is_empty())
+        .map(|row| {
+            let fields = row
+                .split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .collect::<Vec<&str>>();
+            let values = fields
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| *idx != 1)
+                .map(|(_, field)| field.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>();
+
+            match values {
+                Ok(values) if values.len() == 7 => Ok(CartesianState {
+                    radius_km: Vector3::new(values[1], values[2], values[3]),
+                    velocity_km_s: Vector3::new(values[4], values[5], values[6]),
+                    epoch: Epoch::from_jde_tdb(values[0]),
+                    frame,
+                }),
+                _ => Err(HorizonsError::InvalidRow {
+                    row: row.to_string(),
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod ut_horizons {
+    use super::{parse_vector_table, HorizonsError, HorizonsQuery};
+    use crate::constants::frames::SUN_J2000;
+    use hifitime::{Epoch, Unit};
+
+    const RESPONSE: &str = r#"{"signature":{"source":"NASA/JPL Horizons API","version":"1.2"},
+"result":"*******************************************************************************\nEphemeris / API_USER\nCenter body name: Sun (10)\n*******************************************************************************\n$$SOE\n2460310.500000000, A.D. 2024-Jan-01 00:00:00.0000, -2.521092855899356E+07,  1.449279195838006E+08, -6.164888475374435E+02, -2.983983333677879E+01, -5.207633902410673E+00,  6.168441184239981E-05,\n2460310.541666667, A.D. 2024-Jan-01 01:00:00.0000, -2.531834137127546E+07,  1.449091647393087E+08, -6.162668159380998E+02, -2.983304838016212E+01, -5.226262082035007E+00,  6.165478101906524E-05,\n$$EOE\n*******************************************************************************\n"}"#;
+
+    #[test]
+    fn parse_horizons_vectors() {
+        let start = Epoch::from_gregorian_tai_at_midnight(2024, 1, 1);
+        let query = HorizonsQuery::new(399, 10, start, start + Unit::Hour * 1, Unit::Hour * 1);
+
+        let url = query.url().unwrap();
+        assert!(url.as_str().contains("COMMAND=%27399%27"));
+        assert!(url.as_str().contains("STEP_SIZE=%2760+min%27"));
+
+        let states = query.parse_response(RESPONSE).unwrap();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].frame, SUN_J2000);
+        assert_eq!(states[0].radius_km.y, 1.449279195838006E+08);
+        assert_eq!(states[1].velocity_km_s.z, 6.165478101906524E-05);
+        assert!(
+            ((states[1].epoch - states[0].epoch) - Unit::Hour * 1).abs() < Unit::Millisecond * 1
+        );
+
+        assert_eq!(
+            parse_vector_table("no table", SUN_J2000),
+            Err(HorizonsError::NoVectorTable)
+        );
+        assert!(matches!(
+            query.parse_response(r#"{"error": "no such object"}"#),
+            Err(HorizonsError::HorizonsApi { .. })
+        ));
+
+        // Small bodies are queried by their SPK ID, and the step must be at least a minute
+        let eros = HorizonsQuery::new(2000433, 10, start, start + Unit::Day * 1, Unit::Second * 1);
+        assert!(matches!(
+            eros.url(),
+            Err(HorizonsError::InvalidQuery { .. })
+        ));
+        let eros = HorizonsQuery {
+            step: Unit::Hour * 6,
+            ..eros
+        };
+        assert!(eros.url().unwrap().as_str().contains("DES%3D2000433%3B"));
+    }
+}
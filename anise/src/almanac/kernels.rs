@@ -0,0 +1,248 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::Almanac;
+use crate::errors::{AlmanacError, AlmanacResult};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Takes all of the loaded kernels and their aliases out of their slots, in load order.
+fn take_kernels<T>(
+    data: &mut [Option<T>],
+    aliases: &mut [Option<String>],
+) -> Vec<(T, Option<String>)> {
+    data.iter_mut()
+        .zip(aliases.iter_mut())
+        .map_while(|(kernel, alias)| kernel.take().map(|kernel| (kernel, alias.take())))
+        .collect()
+}
+
+/// Puts the kernels back into their slots in the provided order, clearing the remaining slots.
+fn put_kernels<T>(
+    kernels: Vec<(T, Option<String>)>,
+    data: &mut [Option<T>],
+    aliases: &mut [Option<String>],
+) {
+    for slot in data.iter_mut() {
+        *slot = None;
+    }
+    for slot in aliases.iter_mut() {
+        *slot = None;
+    }
+    for (idx, (kernel, alias)) in kernels.into_iter().enumerate() {
+        data[idx] = Some(kernel);
+        aliases[idx] = alias;
+    }
+}
+
+/// Reorders the kernels such that those with the provided alias are either first or last, preserving their relative order.
+/// Returns whether any kernel matched the alias.
+fn move_kernel_slots<T>(
+    data: &mut [Option<T>],
+    aliases: &mut [Option<String>],
+    alias: &str,
+    last: bool,
+) -> bool {
+    let (mut matching, others): (Vec<_>, Vec<_>) = take_kernels(data, aliases)
+        .into_iter()
+        .partition(|(_, this_alias)| this_alias.as_deref() == Some(alias));
+
+    let found = !matching.is_empty();
+
+    let kernels = if last {
+        let mut kernels = others;
+        kernels.append(&mut matching);
+        kernels
+    } else {
+        matching.extend(others);
+        matching
+    };
+
+    put_kernels(kernels, data, aliases);
+    found
+}
+
+/// # Kernel precedence
+///
+/// When several loaded kernels of the same kind (SPK or BPC) have data for the same ID at the same epoch, the kernel loaded
+/// _last_ takes precedence, as in SPICE. Kernels loaded from a path (e.g. with `load`) are identified by that path, their alias,
+/// and their precedence may be changed with `prioritize` and `deprioritize`.
+///
+/// Like loading, all of these functions return a new Almanac and leave the original one unchanged.
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns the aliases of the loaded SPK and BPC kernels, from the lowest to the highest precedence within each kind.
+    ///
+    /// :rtype: typing.List
+    pub fn kernel_aliases(&self) -> Vec<String> {
+        self.spk_aliases
+            .iter()
+            .chain(self.bpc_aliases.iter())
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Unloads all of the SPK and BPC kernels with the provided alias. The ANISE data sets are left unchanged.
+    ///
+    /// :type alias: str
+    /// :rtype: Almanac
+    pub fn unload(&self, alias: &str) -> AlmanacResult<Self> {
+        let mut me = self.clone();
+
+        let spks = take_kernels(&mut me.spk_data, &mut me.spk_aliases);
+        let num_spks = spks.len();
+        let spks = spks
+            .into_iter()
+            .filter(|(_, this_alias)| this_alias.as_deref() != Some(alias))
+            .collect::<Vec<_>>();
+        let mut found = spks.len() != num_spks;
+        put_kernels(spks, &mut me.spk_data, &mut me.spk_aliases);
+
+        let bpcs = take_kernels(&mut me.bpc_data, &mut me.bpc_aliases);
+        let num_bpcs = bpcs.len();
+        let bpcs = bpcs
+            .into_iter()
+            .filter(|(_, this_alias)| this_alias.as_deref() != Some(alias))
+            .collect::<Vec<_>>();
+        found |= bpcs.len() != num_bpcs;
+        put_kernels(bpcs, &mut me.bpc_data, &mut me.bpc_aliases);
+
+        if found {
+            Ok(me)
+        } else {
+            Err(AlmanacError::GenericError {
+                err: format!("no kernel loaded as {alias}"),
+            })
+        }
+    }
+
+    /// Unloads all of the SPK and BPC kernels. The ANISE data sets are left unchanged.
+    ///
+    /// :rtype: Almanac
+    pub fn unload_all(&self) -> Self {
+        let mut me = self.clone();
+        put_kernels(Vec::new(), &mut me.spk_data, &mut me.spk_aliases);
+        put_kernels(Vec::new(), &mut me.bpc_data, &mut me.bpc_aliases);
+        me
+    }
+
+    /// Gives the kernels with the provided alias precedence over all of the other kernels of the same kind,
+    /// as if they had been loaded last.
+    ///
+    /// :type alias: str
+    /// :rtype: Almanac
+    pub fn prioritize(&self, alias: &str) -> AlmanacResult<Self> {
+        self.move_kernels(alias, true)
+    }
+
+    /// Gives all of the other kernels of the same kind precedence over the kernels with the provided alias,
+    /// as if they had been loaded first.
+    ///
+    /// :type alias: str
+    /// :rtype: Almanac
+    pub fn deprioritize(&self, alias: &str) -> AlmanacResult<Self> {
+        self.move_kernels(alias, false)
+    }
+}
+
+impl Almanac {
+    fn move_kernels(&self, alias: &str, last: bool) -> AlmanacResult<Self> {
+        let mut me = self.clone();
+        let found_spk = move_kernel_slots(&mut me.spk_data, &mut me.spk_aliases, alias, last);
+        let found_bpc = move_kernel_slots(&mut me.bpc_data, &mut me.bpc_aliases, alias, last);
+
+        if found_spk || found_bpc {
+            Ok(me)
+        } else {
+            Err(AlmanacError::GenericError {
+                err: format!("no kernel loaded as {alias}"),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_kernels {
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000};
+    use crate::prelude::Almanac;
+    use hifitime::Epoch;
+
+    #[test]
+    fn unload_and_priority() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/de421.bsp")
+            .unwrap()
+            .load("../data/earth_latest_high_prec.bpc")
+            .unwrap();
+
+        assert_eq!(almanac.num_loaded_spk(), 2);
+        assert_eq!(
+            almanac.kernel_aliases(),
+            vec![
+                "../data/de440s.bsp",
+                "../data/de421.bsp",
+                "../data/earth_latest_high_prec.bpc"
+            ]
+        );
+
+        // DE421 was loaded last, so it is used.
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2002, 2, 7);
+        let (_, spk_no, _) = almanac.spk_summary_at_epoch(301, epoch).unwrap();
+        assert_eq!(
+            almanac.spk_aliases[spk_no].as_deref(),
+            Some("../data/de421.bsp")
+        );
+
+        let prioritized = almanac.prioritize("../data/de440s.bsp").unwrap();
+        let (_, spk_no, _) = prioritized.spk_summary_at_epoch(301, epoch).unwrap();
+        assert_eq!(
+            prioritized.spk_aliases[spk_no].as_deref(),
+            Some("../data/de440s.bsp")
+        );
+        assert_eq!(
+            prioritized
+                .deprioritize("../data/de440s.bsp")
+                .unwrap()
+                .kernel_aliases(),
+            almanac.kernel_aliases()
+        );
+
+        // Both ephemerides are consistent
+        let state_421 = almanac
+            .translate(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap();
+        let state_440 = prioritized
+            .translate(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap();
+        assert!((state_421.radius_km - state_440.radius_km).norm() < 1.0);
+
+        let unloaded = almanac.unload("../data/de421.bsp").unwrap();
+        assert_eq!(unloaded.num_loaded_spk(), 1);
+        assert_eq!(unloaded.num_loaded_bpc(), 1);
+        assert_eq!(
+            unloaded.spk_aliases[0].as_deref(),
+            Some("../data/de440s.bsp")
+        );
+        assert!(unloaded.spk_aliases[1].is_none());
+        // The original almanac is unchanged
+        assert_eq!(almanac.num_loaded_spk(), 2);
+
+        assert!(almanac.unload("not loaded").is_err());
+        assert!(almanac.prioritize("not loaded").is_err());
+
+        let empty = almanac.unload_all();
+        assert_eq!(empty.num_loaded_spk(), 0);
+        assert_eq!(empty.num_loaded_bpc(), 0);
+        assert!(empty.kernel_aliases().is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use crate::{
+    astro::LagrangePoint,
+    constants::orientations::J2000,
+    ephemerides::EphemerisPhysicsSnafu,
+    errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu},
+    math::cartesian::CartesianState,
+    prelude::{Frame, Orbit},
+};
+
+use super::Almanac;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns the instantaneous state of the requested libration point of the system formed by the primary and the secondary bodies,
+    /// as seen from the observer frame. The returned state can be used as a target or an observer in all of the other computations.
+    ///
+    /// # Algorithm
+    /// 1. Compute the mass ratio of the system from the gravitational parameters of the primary and secondary frames, fetched from the
+    ///    loaded planetary data if they are not set;
+    /// 2. Compute the state of the secondary with respect to the primary from the loaded ephemerides;
+    /// 3. Locate the libration point in the circular restricted three body problem (cf. `LagrangePoint::synodic_position`), scaled by the
+    ///    instantaneous distance between both bodies, in the rotating frame whose X axis points from the primary to the secondary and whose
+    ///    Z axis is along their relative angular momentum;
+    /// 4. Compute its velocity from the instantaneous rotation rate and the pulsation of that rotating frame.
+    ///
+    /// # Note
+    /// The libration points are only equilibrium points of the circular restricted three body problem: these states are not
+    /// invariant in the true dynamics, but are the usual targets and references of mission design.
+    ///
+    /// :type primary: Frame
+    /// :type secondary: Frame
+    /// :type point: LagrangePoint
+    /// :type epoch: Epoch
+    /// :type observer_frame: Frame
+    /// :rtype: Orbit
+    pub fn lagrange_point_state(
+        &self,
+        primary: Frame,
+        secondary: Frame,
+        point: LagrangePoint,
+        epoch: Epoch,
+        observer_frame: Frame,
+    ) -> AlmanacResult<Orbit> {
+        let mut mu_km3_s2 = [0.0; 2];
+        for (body, mu) in [primary, secondary].iter().zip(mu_km3_s2.iter_mut()) {
+            let frame = if body.mu_km3_s2().is_ok() {
+                *body
+            } else {
+                self.frame_from_uid(*body)
+                    .map_err(|e| AlmanacError::GenericError {
+                        err: format!("{e} when fetching frame data for {body}"),
+                    })?
+            };
+
+            *mu = frame
+                .mu_km3_s2()
+                .context(EphemerisPhysicsSnafu {
+                    action: "fetching gravitational parameter of libration point system",
+                })
+                .context(EphemerisSnafu {
+                    action: "computing libration point state",
+                })?;
+        }
+
+        let mass_ratio = mu_km3_s2[1] / (mu_km3_s2[0] + mu_km3_s2[1]);
+
+        // State of the secondary with respect to the primary, in the inertial frame
+        let rel = self
+            .translate(
+                secondary.with_orient(J2000),
+                primary.with_orient(J2000),
+                epoch,
+                None,
+            )
+            .context(EphemerisSnafu {
+                action: "computing libration point state",
+            })?;
+
+        let dist_km = rel.rmag_km();
+        let x_hat = rel.radius_km / dist_km;
+        let ang_mom = rel.radius_km.cross(&rel.velocity_km_s);
+        let z_hat = ang_mom.normalize();
+        let y_hat = z_hat.cross(&x_hat);
+
+        // Libration point with respect to the primary, which is at (-mass_ratio, 0) in normalized units
+        let (x, y) = point.synodic_position(mass_ratio);
+        let radius_km = dist_km * ((x + mass_ratio) * x_hat + y * y_hat);
+
+        // The rotating frame pulsates with the distance between both bodies and rotates at their instantaneous orbital rate
+        let dist_rate = x_hat.dot(&rel.velocity_km_s) / dist_km;
+        let omega_rad_s = ang_mom / dist_km.powi(2);
+        let velocity_km_s = dist_rate * radius_km + omega_rad_s.cross(&radius_km);
+
+        // Offset by the state of the primary as seen from the observer
+        let primary_state = self
+            .translate(
+                primary.with_orient(J2000),
+                observer_frame.with_orient(J2000),
+                epoch,
+                None,
+            )
+            .context(EphemerisSnafu {
+                action: "computing libration point state",
+            })?;
+
+        let state = CartesianState {
+            radius_km: primary_state.radius_km + radius_km,
+            velocity_km_s: primary_state.velocity_km_s + velocity_km_s,
+            epoch,
+            frame: observer_frame.with_orient(J2000),
+        };
+
+        self.rotate_to(state, observer_frame)
+            .context(OrientationSnafu {
+                action: "computing libration point state",
+            })
+    }
+}
+
+#[cfg(test)]
+mod ut_lagrange {
+    use crate::{
+        astro::LagrangePoint,
+        constants::frames::{EARTH_J2000, MOON_J2000},
+        prelude::{Almanac, Epoch, Frame},
+    };
+
+    #[test]
+    fn earth_moon_libration_points() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+        let earth_moon = Frame::synodic(399, 301).unwrap();
+
+        let moon = almanac
+            .transform(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap();
+
+        let points = [
+            LagrangePoint::L1,
+            LagrangePoint::L2,
+            LagrangePoint::L3,
+            LagrangePoint::L4,
+            LagrangePoint::L5,
+        ];
+
+        for point in points {
+            let state = almanac
+                .lagrange_point_state(EARTH_J2000, MOON_J2000, point, epoch, EARTH_J2000)
+                .unwrap();
+
+            let from_moon_km = (state.radius_km - moon.radius_km).norm();
+
+            match point {
+                LagrangePoint::L1 => {
+                    assert!(state.rmag_km() < moon.rmag_km(), "{state}");
+                    assert!(
+                        (from_moon_km / moon.rmag_km() - 0.1509).abs() < 1e-3,
+                        "{state}"
+                    );
+                }
+                LagrangePoint::L2 => {
+                    assert!(state.rmag_km() > moon.rmag_km(), "{state}");
+                    assert!(
+                        (from_moon_km / moon.rmag_km() - 0.1678).abs() < 1e-3,
+                        "{state}"
+                    );
+                }
+                LagrangePoint::L3 => {
+                    assert!(state.radius_km.dot(&moon.radius_km) < 0.0, "{state}");
+                }
+                LagrangePoint::L4 | LagrangePoint::L5 => {
+                    // Equilateral triangle with both bodies
+                    assert!((state.rmag_km() - moon.rmag_km()).abs() < 1e-6, "{state}");
+                    assert!((from_moon_km - moon.rmag_km()).abs() < 1e-6, "{state}");
+                }
+            }
+
+            // In the Earth-Moon rotating frame, the libration points lie in the XY plane and only move with the pulsation of the frame.
+            let rotating = almanac.transform_to(state, earth_moon, None).unwrap();
+            assert!(rotating.radius_km.z.abs() < 1e-2, "{point}: {rotating}");
+            if matches!(point, LagrangePoint::L4) {
+                assert!(rotating.radius_km.y > 3e5, "{point}: {rotating}");
+            } else if matches!(point, LagrangePoint::L5) {
+                assert!(rotating.radius_km.y < -3e5, "{point}: {rotating}");
+            } else {
+                assert!(rotating.radius_km.y.abs() < 1e-2, "{point}: {rotating}");
+            }
+            assert!(rotating.vmag_km_s() < 0.1, "{point}: {rotating}");
+        }
+    }
+}
@@ -0,0 +1,539 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Epoch, TimeUnits};
+use snafu::ResultExt;
+
+use super::Almanac;
+use crate::{
+    astro::{
+        signal_delay::{Klobuchar, Saastamoinen},
+        Aberration, AzElRange, TwoWayRangeDoppler,
+    },
+    constants::{frames::SSB_J2000, SPEED_OF_LIGHT_KM_S},
+    errors::{AlmanacError, AlmanacResult, TLDataSetSnafu},
+    frames::Frame,
+    prelude::Orbit,
+    structure::{location::Location, LocationDataSet},
+    NaifId,
+};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Maximum number of iterations of each light time computation of the two-way observables.
+const MAX_LIGHT_TIME_ITERATIONS: usize = 10;
+/// Convergence tolerance on the light time, in seconds (about 0.3 millimeter).
+const LIGHT_TIME_TOLERANCE_S: f64 = 1e-12;
+
+impl Almanac {
+    /// Loads the provided location data into a clone of this original Almanac.
+    pub fn with_location_data(&self, location_data: LocationDataSet) -> Self {
+        let mut me = self.clone();
+        me.location_data = location_data;
+        me
+    }
+
+    /// Returns a copy of the location with the provided ID.
+    pub fn location_from_id(&self, id: NaifId) -> AlmanacResult<Location> {
+        self.location_data.get_by_id(id).context(TLDataSetSnafu {
+            action: "fetching location by its ID",
+        })
+    }
+
+    /// Returns a copy of the location with the provided name.
+    pub fn location_from_name(&self, name: &str) -> AlmanacResult<Location> {
+        self.location_data
+            .get_by_name(name)
+            .context(TLDataSetSnafu {
+                action: "fetching location by its name",
+            })
+    }
+
+    /// Computes the azimuth (in degrees), elevation (in degrees), and range (in kilometers) of the
+    /// receiver state (`rx`) seen from the provided location, once converted into the SEZ frame of the location.
+    ///
+    /// The body fixed frame of the location must be loaded in this Almanac, since its shape is needed to place the location.
+    /// If the location specifies a refraction model, the returned elevation is the apparent (refracted) elevation.
+    pub fn azimuth_elevation_range_sez_from_location(
+        &self,
+        rx: Orbit,
+        location: &Location,
+        obstructing_body: Option<Frame>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<AzElRange> {
+        let tx = self.location_state(location, rx.epoch)?;
+
+        let mut aer = self.azimuth_elevation_range_sez(rx, tx, obstructing_body, ab_corr)?;
+
+        if let Some(refraction) = location.refraction {
+            aer.elevation_deg += refraction.correction_deg(aer.elevation_deg, location.height_km);
+        }
+
+        Ok(aer)
+    }
+
+    /// Computes the one-way range (in kilometers) of the receiver state (`rx`) seen from the provided location, including the
+    /// tropospheric and ionospheric delays of the signal if their models are provided.
+    pub fn range_from_location_km(
+        &self,
+        rx: Orbit,
+        location: &Location,
+        troposphere: Option<&Saastamoinen>,
+        ionosphere: Option<&Klobuchar>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<f64> {
+        let aer = self.azimuth_elevation_range_sez_from_location(rx, location, None, ab_corr)?;
+        Ok(aer.range_km + location.signal_delay_km(&aer, troposphere, ionosphere))
+    }
+
+    /// Computes the two-way range and range rate of the target seen from the provided location, with the signal received
+    /// back at the location at the provided epoch.
+    ///
+    /// Unlike the instantaneous range and range rate of the AER computation, the light time of the downlink (from the target
+    /// to the location) is iterated first to find the turnaround epoch of the target, and then the light time of the uplink
+    /// (from the location to the target) is iterated to find the transmission epoch of the location. All positions are
+    /// computed with respect to the solar system barycenter. The range rate is the exact time derivative of the two-way
+    /// range with respect to the reception epoch, accounting for the variation of both light times.
+    pub fn two_way_range_doppler_from_location(
+        &self,
+        location: &Location,
+        target: Frame,
+        epoch: Epoch,
+    ) -> AlmanacResult<TwoWayRangeDoppler> {
+        let station_ssb = |epoch: Epoch| -> AlmanacResult<Orbit> {
+            self.transform_to(self.location_state(location, epoch)?, SSB_J2000, None)
+        };
+
+        // Downlink: the signal left the target at t2 and is received by the location at t3 = epoch.
+        let rx = station_ssb(epoch)?;
+        let mut turnaround = self.transform(target, SSB_J2000, epoch, None)?;
+        let mut downlink_lt_s = (rx.radius_km - turnaround.radius_km).norm() / SPEED_OF_LIGHT_KM_S;
+        for _ in 0..MAX_LIGHT_TIME_ITERATIONS {
+            turnaround =
+                self.transform(target, SSB_J2000, epoch - downlink_lt_s.seconds(), None)?;
+            let lt_s = (rx.radius_km - turnaround.radius_km).norm() / SPEED_OF_LIGHT_KM_S;
+            let converged = (lt_s - downlink_lt_s).abs() < LIGHT_TIME_TOLERANCE_S;
+            downlink_lt_s = lt_s;
+            if converged {
+                break;
+            }
+        }
+
+        // Uplink: the signal left the location at t1 and reached the target at t2.
+        let turnaround_epoch = epoch - downlink_lt_s.seconds();
+        let mut tx = station_ssb(turnaround_epoch)?;
+        let mut uplink_lt_s = (turnaround.radius_km - tx.radius_km).norm() / SPEED_OF_LIGHT_KM_S;
+        for _ in 0..MAX_LIGHT_TIME_ITERATIONS {
+            tx = station_ssb(turnaround_epoch - uplink_lt_s.seconds())?;
+            let lt_s = (turnaround.radius_km - tx.radius_km).norm() / SPEED_OF_LIGHT_KM_S;
+            let converged = (lt_s - uplink_lt_s).abs() < LIGHT_TIME_TOLERANCE_S;
+            uplink_lt_s = lt_s;
+            if converged {
+                break;
+            }
+        }
+
+        // Derivative of the downlink range with respect to t3, where dt2/dt3 = 1 - (d rho_down / dt3) / c.
+        let down_km = rx.radius_km - turnaround.radius_km;
+        let down_hat = down_km / down_km.norm();
+        let down_rate_km_s = down_hat.dot(&(rx.velocity_km_s - turnaround.velocity_km_s))
+            / (1.0 - down_hat.dot(&turnaround.velocity_km_s) / SPEED_OF_LIGHT_KM_S);
+
+        // Derivative of the uplink range with respect to t2, where dt1/dt2 = 1 - (d rho_up / dt2) / c.
+        let up_km = turnaround.radius_km - tx.radius_km;
+        let up_hat = up_km / up_km.norm();
+        let up_rate_km_s = up_hat.dot(&(turnaround.velocity_km_s - tx.velocity_km_s))
+            / (1.0 - up_hat.dot(&tx.velocity_km_s) / SPEED_OF_LIGHT_KM_S);
+
+        Ok(TwoWayRangeDoppler {
+            epoch,
+            range_km: 0.5 * (downlink_lt_s + uplink_lt_s) * SPEED_OF_LIGHT_KM_S,
+            range_rate_km_s: 0.5
+                * (down_rate_km_s + up_rate_km_s * (1.0 - down_rate_km_s / SPEED_OF_LIGHT_KM_S)),
+            uplink_light_time: uplink_lt_s.seconds(),
+            downlink_light_time: downlink_lt_s.seconds(),
+        })
+    }
+
+    /// Returns whether the receiver state (`rx`) is visible from the provided location, accounting for its terrain mask,
+    /// its minimum antenna elevation, and the optional obstructing body.
+    pub fn is_visible_from_location(
+        &self,
+        rx: Orbit,
+        location: &Location,
+        obstructing_body: Option<Frame>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<bool> {
+        let aer = self.azimuth_elevation_range_sez_from_location(
+            rx,
+            location,
+            obstructing_body,
+            ab_corr,
+        )?;
+        Ok(location.is_visible(&aer))
+    }
+
+    /// Returns the state of the location in its body fixed frame at the provided epoch.
+    ///
+    /// The body fixed frame of the location must be loaded in this Almanac, since its shape is needed to place the location.
+    pub(crate) fn location_state(&self, location: &Location, epoch: Epoch) -> AlmanacResult<Orbit> {
+        let from_frame =
+            self.frame_from_uid(location.frame)
+                .map_err(|e| AlmanacError::GenericError {
+                    err: format!("{e} when fetching {} frame data", location.frame),
+                })?;
+        // The location is fixed in its body fixed frame, so its angular velocity in that frame is zero.
+        Orbit::try_latlongalt(
+            location.latitude_deg,
+            location.longitude_deg,
+            location.height_km,
+            0.0,
+            epoch,
+            from_frame,
+        )
+        .map_err(|e| AlmanacError::GenericError {
+            err: format!("{e} when building location state"),
+        })
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Computes the azimuth (in degrees), elevation (in degrees), and range (in kilometers) of the
+    /// receiver state (`rx`) seen from the location ID, once converted into the SEZ frame of the location.
+    ///
+    /// :type rx: Orbit
+    /// :type location_id: int
+    /// :type obstructing_body: Frame, optional
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: AzElRange
+    pub fn azimuth_elevation_range_sez_from_location_id(
+        &self,
+        rx: Orbit,
+        location_id: NaifId,
+        obstructing_body: Option<Frame>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<AzElRange> {
+        let location = self.location_from_id(location_id)?;
+        self.azimuth_elevation_range_sez_from_location(rx, &location, obstructing_body, ab_corr)
+    }
+
+    /// Computes the azimuth (in degrees), elevation (in degrees), and range (in kilometers) of the
+    /// receiver state (`rx`) seen from the location name, once converted into the SEZ frame of the location.
+    ///
+    /// :type rx: Orbit
+    /// :type location_name: str
+    /// :type obstructing_body: Frame, optional
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: AzElRange
+    pub fn azimuth_elevation_range_sez_from_location_name(
+        &self,
+        rx: Orbit,
+        location_name: &str,
+        obstructing_body: Option<Frame>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<AzElRange> {
+        let location = self.location_from_name(location_name)?;
+        self.azimuth_elevation_range_sez_from_location(rx, &location, obstructing_body, ab_corr)
+    }
+
+    /// Computes the two-way range and range rate of the target seen from the location ID, with the signal received
+    /// back at the location at the provided epoch, iterating the light time of both the uplink and the downlink.
+    ///
+    /// :type location_id: int
+    /// :type target: Frame
+    /// :type epoch: Epoch
+    /// :rtype: TwoWayRangeDoppler
+    pub fn two_way_range_doppler(
+        &self,
+        location_id: NaifId,
+        target: Frame,
+        epoch: Epoch,
+    ) -> AlmanacResult<TwoWayRangeDoppler> {
+        let location = self.location_from_id(location_id)?;
+        self.two_way_range_doppler_from_location(&location, target, epoch)
+    }
+
+    /// Returns whether the receiver state (`rx`) is visible from the location ID, accounting for its terrain mask,
+    /// its minimum antenna elevation, and the optional obstructing body.
+    ///
+    /// :type rx: Orbit
+    /// :type location_id: int
+    /// :type obstructing_body: Frame, optional
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: bool
+    pub fn is_visible_from_location_id(
+        &self,
+        rx: Orbit,
+        location_id: NaifId,
+        obstructing_body: Option<Frame>,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<bool> {
+        let location = self.location_from_id(location_id)?;
+        self.is_visible_from_location(rx, &location, obstructing_body, ab_corr)
+    }
+}
+
+#[cfg(test)]
+mod ut_location {
+    use crate::constants::frames::{EARTH_ITRF93, EARTH_J2000, IAU_EARTH_FRAME};
+    use crate::constants::SPEED_OF_LIGHT_KM_S;
+    use crate::prelude::{Almanac, Frame, Orbit};
+    use crate::structure::location::{Location, RefractionModel, TerrainMask};
+    use crate::structure::LocationDataSet;
+    use hifitime::Epoch;
+
+    #[test]
+    fn location_visibility() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+
+        let mut dataset = LocationDataSet::default();
+        let location = Location {
+            latitude_deg: 0.0,
+            longitude_deg: 0.0,
+            height_km: 0.0,
+            frame: IAU_EARTH_FRAME.into(),
+            terrain_mask: TerrainMask::from_flat_terrain(5.0),
+            min_elevation_deg: Some(10.0),
+            ..Default::default()
+        };
+        dataset
+            .push(location.clone(), Some(1), Some("Null Island"))
+            .unwrap();
+        let almanac = almanac.with_location_data(dataset);
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        // Place a GEO spacecraft right above the location, and compute it in the inertial frame.
+        let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+        let above = Orbit::try_latlongalt(0.0, 0.0, 35_786.0, 0.0, epoch, iau_earth).unwrap();
+        let rx = almanac.transform_to(above, EARTH_J2000, None).unwrap();
+
+        let aer = almanac
+            .azimuth_elevation_range_sez_from_location_id(rx, 1, None, None)
+            .unwrap();
+        assert!((aer.elevation_deg - 90.0).abs() < 1e-6);
+        assert!((aer.range_km - 35_786.0).abs() < 1e-6);
+        assert!(almanac
+            .is_visible_from_location_id(rx, 1, None, None)
+            .unwrap());
+
+        // Low on the horizon: above the terrain but below the antenna minimum elevation
+        let low = Orbit::try_latlongalt(0.0, 74.0, 35_786.0, 0.0, epoch, iau_earth).unwrap();
+        let rx = almanac.transform_to(low, EARTH_J2000, None).unwrap();
+        let aer = almanac
+            .azimuth_elevation_range_sez_from_location_name(rx, "Null Island", None, None)
+            .unwrap();
+        assert!(
+            aer.elevation_deg > 5.0 && aer.elevation_deg < 10.0,
+            "{aer:?}"
+        );
+        assert!(!location.is_visible(&aer));
+
+        // The high precision Earth orientation is not loaded in this almanac, so ITRF93 cannot be used
+        let mut itrf = location;
+        itrf.frame = EARTH_ITRF93.into();
+        assert!(almanac
+            .azimuth_elevation_range_sez_from_location(rx, &itrf, None, None)
+            .is_err());
+    }
+
+    #[test]
+    fn location_refraction() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+
+        // Standard values of refraction on the horizon and at zenith.
+        let bennett = RefractionModel::Bennett;
+        assert!((bennett.correction_deg(0.0, 0.0) * 60.0 - 28.97).abs() < 0.05);
+        assert!(bennett.correction_deg(90.0, 0.0).abs() < 1e-6);
+        assert_eq!(bennett.correction_deg(-2.0, 0.0), 0.0);
+
+        let itu = RefractionModel::ItuRP834;
+        assert!((itu.correction_deg(0.0, 0.0) - 1.0 / 1.314).abs() < 1e-12);
+        // Less atmosphere above higher locations
+        assert!(itu.correction_deg(0.0, 2.0) < itu.correction_deg(0.0, 0.0));
+        // Refraction decreases with elevation
+        assert!(itu.correction_deg(10.0, 0.0) < itu.correction_deg(1.0, 0.0));
+
+        let geometric = Location {
+            latitude_deg: 0.0,
+            longitude_deg: 0.0,
+            height_km: 0.0,
+            frame: IAU_EARTH_FRAME.into(),
+            ..Default::default()
+        };
+        let mut refracted = geometric.clone();
+        refracted.refraction = Some(bennett);
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+        // Low GEO spacecraft
+        let low = Orbit::try_latlongalt(0.0, 80.0, 35_786.0, 0.0, epoch, iau_earth).unwrap();
+        let rx = almanac.transform_to(low, EARTH_J2000, None).unwrap();
+
+        let aer_geo = almanac
+            .azimuth_elevation_range_sez_from_location(rx, &geometric, None, None)
+            .unwrap();
+        let aer_refr = almanac
+            .azimuth_elevation_range_sez_from_location(rx, &refracted, None, None)
+            .unwrap();
+
+        assert!(aer_geo.elevation_deg > 0.0 && aer_geo.elevation_deg < 5.0);
+        assert!(
+            (aer_refr.elevation_deg
+                - aer_geo.elevation_deg
+                - bennett.correction_deg(aer_geo.elevation_deg, 0.0))
+            .abs()
+                < 1e-12
+        );
+        assert!(aer_refr.elevation_deg > aer_geo.elevation_deg);
+        assert_eq!(aer_refr.azimuth_deg, aer_geo.azimuth_deg);
+        assert_eq!(aer_refr.range_km, aer_geo.range_km);
+    }
+
+    #[test]
+    fn location_signal_delays() {
+        use crate::astro::signal_delay::{Klobuchar, Saastamoinen, GPS_L1_FREQUENCY_HZ};
+
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+
+        let location = Location {
+            latitude_deg: 40.427_222,
+            longitude_deg: 4.250_556,
+            height_km: 0.834_939,
+            frame: IAU_EARTH_FRAME.into(),
+            ..Default::default()
+        };
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let iau_earth = almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+        let above = Orbit::try_latlongalt(40.0, 4.0, 20_000.0, 0.0, epoch, iau_earth).unwrap();
+        let rx = almanac.transform_to(above, EARTH_J2000, None).unwrap();
+
+        let tropo = Saastamoinen::standard_at_height(location.height_km);
+        let iono = Klobuchar {
+            alpha: [0.1118e-07, -0.7451e-08, -0.5961e-07, 0.1192e-06],
+            beta: [0.1167e+06, -0.2294e+06, -0.1311e+06, 0.1049e+07],
+            frequency_hz: GPS_L1_FREQUENCY_HZ,
+        };
+
+        let geometric_km = almanac
+            .range_from_location_km(rx, &location, None, None, None)
+            .unwrap();
+        let aer = almanac
+            .azimuth_elevation_range_sez_from_location(rx, &location, None, None)
+            .unwrap();
+        assert_eq!(geometric_km, aer.range_km);
+
+        let tropo_km = almanac
+            .range_from_location_km(rx, &location, Some(&tropo), None, None)
+            .unwrap();
+        let both_km = almanac
+            .range_from_location_km(rx, &location, Some(&tropo), Some(&iono), None)
+            .unwrap();
+
+        // Both delays lengthen the range by a few meters.
+        assert!(tropo_km - geometric_km > 1e-3 && tropo_km - geometric_km < 1e-2);
+        assert!(both_km > tropo_km);
+        assert!(
+            (both_km - geometric_km - location.signal_delay_km(&aer, Some(&tropo), Some(&iono)))
+                .abs()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn two_way_range_doppler() {
+        use hifitime::TimeUnits;
+
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .and_then(|almanac| almanac.load("../data/gmat-hermite.bsp"))
+            .unwrap();
+
+        let location = Location {
+            latitude_deg: 40.427_222,
+            longitude_deg: 4.250_556,
+            height_km: 0.834_939,
+            frame: IAU_EARTH_FRAME.into(),
+            ..Default::default()
+        };
+        let mut dataset = LocationDataSet::default();
+        dataset
+            .push(location.clone(), Some(1), Some("Madrid"))
+            .unwrap();
+        let almanac = almanac.with_location_data(dataset);
+
+        let my_sc_j2k = Frame::from_ephem_j2000(-10000001);
+        let epoch = Epoch::from_gregorian_utc_hms(2000, 1, 1, 13, 0, 0);
+
+        let obs = almanac.two_way_range_doppler(1, my_sc_j2k, epoch).unwrap();
+        assert_eq!(
+            obs,
+            almanac
+                .two_way_range_doppler_from_location(&location, my_sc_j2k, epoch)
+                .unwrap()
+        );
+        assert!(obs.turnaround_epoch() < epoch);
+        assert!(obs.transmit_epoch() < obs.turnaround_epoch());
+        println!("{obs}");
+
+        // The two-way observables are close to the instantaneous geometry.
+        let rx = almanac
+            .transform(my_sc_j2k, EARTH_J2000, epoch, None)
+            .unwrap();
+        let aer = almanac
+            .azimuth_elevation_range_sez_from_location(rx, &location, None, None)
+            .unwrap();
+        assert!((obs.range_km - aer.range_km).abs() < 1.0, "{aer:?}");
+        assert!(
+            (obs.range_rate_km_s - aer.range_rate_km_s).abs() < 1e-2,
+            "{aer:?}"
+        );
+        assert!(
+            ((obs.uplink_light_time + obs.downlink_light_time).to_seconds() * 0.5
+                - aer.light_time.to_seconds())
+            .abs()
+                < 1e-5
+        );
+
+        // The range rate is the time derivative of the two-way range.
+        let before = almanac
+            .two_way_range_doppler(1, my_sc_j2k, epoch - 1.seconds())
+            .unwrap();
+        let after = almanac
+            .two_way_range_doppler(1, my_sc_j2k, epoch + 1.seconds())
+            .unwrap();
+        let fd_range_rate_km_s = (after.range_km - before.range_km) / 2.0;
+        assert!(
+            (fd_range_rate_km_s - obs.range_rate_km_s).abs() < 1e-5,
+            "{fd_range_rate_km_s} != {}",
+            obs.range_rate_km_s
+        );
+
+        // The Doppler shift has the opposite sign of the range rate.
+        let doppler_hz = obs.doppler_hz(7.2e9, 880.0 / 749.0);
+        assert_eq!(doppler_hz.signum(), -obs.range_rate_km_s.signum());
+        assert!(
+            (doppler_hz + 2.0 * 8.4e9 * obs.range_rate_km_s / SPEED_OF_LIGHT_KM_S).abs()
+                < 1e-3 * doppler_hz.abs()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use snafu::ResultExt;
+
+use crate::{
+    errors::{AlmanacError, AlmanacResult, OrientationSnafu, TLDataSetSnafu},
+    math::Vector3,
+    prelude::{Frame, Orbit},
+};
+
+use super::Almanac;
+
+impl Almanac {
+    /// Returns the magnetic field at the position of the state, in nT, using the centered tilted dipole of the body whose
+    /// body fixed frame is provided (e.g. IAU_EARTH), as set in the loaded planetary data.
+    ///
+    /// The field is expressed in the orientation of the frame of the state, e.g. to model the measurements of a magnetometer
+    /// from an inertial trajectory, or to study the radiation environment along an orbit.
+    ///
+    /// # Errors
+    /// + If the planetary data of the orientation of the provided frame is not loaded, or has no geomagnetic dipole.
+    /// + If the state cannot be transformed into the provided frame.
+    pub fn dipole_field(&self, frame: Frame, state: Orbit) -> AlmanacResult<Vector3> {
+        let data = self
+            .planetary_data
+            .get_by_id(frame.orientation_id)
+            .context(TLDataSetSnafu {
+                action: "fetching planetary data for the dipole field",
+            })?;
+
+        let dipole = data
+            .geomagnetic_dipole
+            .ok_or_else(|| AlmanacError::GenericError {
+                err: format!("no geomagnetic dipole in the planetary data of {frame}"),
+            })?;
+
+        let body_fixed = self.transform_to(state, frame, None)?;
+        let field_nt = dipole.field_nt(&body_fixed.radius_km);
+
+        let dcm = self
+            .rotate(frame, state.frame, state.epoch)
+            .context(OrientationSnafu {
+                action: "rotating the dipole field",
+            })?;
+
+        Ok(dcm * field_nt)
+    }
+}
+
+#[cfg(test)]
+mod ut_magnetic {
+    use crate::constants::frames::{EARTH_J2000, IAU_EARTH_FRAME, IAU_MOON_FRAME};
+    use crate::naif::kpl::parser::convert_tpc;
+    use crate::prelude::{Almanac, Orbit};
+    use hifitime::Epoch;
+
+    #[test]
+    fn earth_dipole_field() {
+        let planetary_data = convert_tpc("../data/pck00008.tpc", "../data/gm_de431.tpc").unwrap();
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .with_planetary_data(planetary_data);
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+        let dipole = almanac
+            .planetary_data
+            .get_by_id(399)
+            .unwrap()
+            .geomagnetic_dipole
+            .unwrap();
+        assert_eq!(
+            (dipole.north_pole_lat_deg, dipole.north_pole_lon_deg),
+            (79.54, 288.43)
+        );
+
+        // Above the north geomagnetic pole, the field points down
+        let pole = dipole.north_pole_unit() * 7000.0;
+        let above_pole = Orbit::from_position(pole.x, pole.y, pole.z, epoch, IAU_EARTH_FRAME);
+        let b_nt = almanac.dipole_field(IAU_EARTH_FRAME, above_pole).unwrap();
+        assert!((b_nt.normalize() + pole.normalize()).norm() < 1e-12);
+
+        // The field is rotated into the frame of the state, preserving its magnitude
+        let inertial = almanac.transform_to(above_pole, EARTH_J2000, None).unwrap();
+        let b_inertial_nt = almanac.dipole_field(IAU_EARTH_FRAME, inertial).unwrap();
+        assert!((b_inertial_nt.norm() - b_nt.norm()).abs() < 1e-6);
+        assert!((b_inertial_nt.normalize() + inertial.radius_km.normalize()).norm() < 1e-9);
+
+        // The Moon has no dipole
+        assert!(almanac.dipole_field(IAU_MOON_FRAME, above_pole).is_err());
+    }
+}
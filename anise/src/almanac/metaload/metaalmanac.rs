@@ -8,12 +8,20 @@
  * Documentation: https://nyxspace.com/
  */
 
+use hifitime::Duration;
+use log::{info, warn};
 use serde_derive::{Deserialize, Serialize};
 use serde_dhall::SimpleType;
 use snafu::prelude::*;
+use std::fs::{read, read_dir, remove_file};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::SystemTime;
 use url::Url;
 
+#[cfg(feature = "async")]
+use tokio::task::spawn_blocking;
+
 #[cfg(feature = "python")]
 use pyo3::exceptions::PyTypeError;
 #[cfg(feature = "python")]
@@ -23,9 +31,13 @@ use pyo3::pyclass::CompareOp;
 #[cfg(feature = "python")]
 use pyo3::types::PyType;
 
+#[cfg(feature = "async")]
+use crate::almanac::asynchronous::join_error;
 use crate::errors::{AlmanacResult, MetaSnafu};
+use crate::prelude::InputOutputError;
+use crate::progress::ProgressSink;
 
-use super::{Almanac, MetaAlmanacError, MetaFile};
+use super::{cache_dir, Almanac, MetaAlmanacError, MetaFile};
 
 /// A structure to set up an Almanac, with automatic downloading, local storage, checksum checking, and more.
 ///
@@ -33,7 +45,8 @@ use super::{Almanac, MetaAlmanacError, MetaFile};
 /// If the URI is a local path, relative or absolute, nothing will be fetched from a remote. Relative paths are relative to the execution folder (i.e. the current working directory).
 /// If the URI is a remote path, the MetaAlmanac will first check if the file exists locally. If it exists, it will check that the CRC32 checksum of this file matches that of the specs.
 /// If it does not match, the file will be downloaded again. If no CRC32 is provided but the file exists, then the MetaAlmanac will fetch the remote file and overwrite the existing file.
-/// The downloaded path will be stored in the "AppData" folder.
+/// The downloaded path will be stored in the "AppData" folder, or in the folder set by the `ANISE_CACHE_DIR` environment variable.
+/// The cache may be inspected and pruned with `cache_size`, `purge_cache`, and `verify_cache`.
 ///
 /// :type maybe_path: str, optional
 /// :rtype: MetaAlmanac
@@ -62,11 +75,21 @@ impl MetaAlmanac {
     /// this lock file if a dead lock is detected after 10 seconds. Set this flag to false if you have
     /// more than ten processes which may attempt to download files in parallel.
     pub fn process(&mut self, autodelete: bool) -> AlmanacResult<Almanac> {
+        self.process_with_progress(autodelete, None)
+    }
+
+    /// Fetch all of the URIs exactly like `process`, reporting the progress of each download to the provided sink, if any.
+    pub fn process_with_progress(
+        &mut self,
+        autodelete: bool,
+        progress: Option<&dyn ProgressSink>,
+    ) -> AlmanacResult<Almanac> {
         for (fno, file) in self.files.iter_mut().enumerate() {
-            file.process(autodelete).context(MetaSnafu {
-                fno,
-                file: file.clone(),
-            })?;
+            file.process_with_progress(autodelete, progress)
+                .context(MetaSnafu {
+                    fno,
+                    file: file.clone(),
+                })?;
         }
         // At this stage, all of the files are local files, so we can load them as is.
         let mut ctx = Almanac::default();
@@ -92,6 +115,151 @@ impl MetaAlmanac {
     pub fn latest() -> AlmanacResult<Almanac> {
         Self::default().process(true)
     }
+
+    /// Returns the folder where the remote files are cached, which may be overridden with the `ANISE_CACHE_DIR` environment variable.
+    pub fn cache_dir() -> Result<PathBuf, MetaAlmanacError> {
+        cache_dir().ok_or(MetaAlmanacError::AppDirError)
+    }
+
+    /// Returns the total size in bytes of the files in the cache folder.
+    pub fn cache_size() -> Result<u64, MetaAlmanacError> {
+        dir_size(&Self::cache_dir()?)
+    }
+
+    /// Deletes the kernels and data sets of the cache folder which were last modified longer ago than `older_than`, and returns their paths.
+    /// Only the files with the extension of a kernel or data set (bsp, bpc, xsp, xpc, xfr, pca, epa, lka, sca) are deleted, never the lock files of the downloads.
+    pub fn purge_cache(older_than: Duration) -> Result<Vec<String>, MetaAlmanacError> {
+        purge_dir(&Self::cache_dir()?, older_than)
+    }
+
+    /// Recomputes the CRC32 of the cached copy of each remote file with a CRC32 and returns the paths of those which do not match,
+    /// e.g. because the file was truncated or modified after its download. Set `delete` to remove these files from the cache,
+    /// so that they are downloaded again on the next call to `process`. Files which are not cached are ignored.
+    pub fn verify_cache(&self, delete: bool) -> Result<Vec<String>, MetaAlmanacError> {
+        let mut corrupted = Vec::new();
+        for file in &self.files {
+            let (Some(crc32), Some(path)) = (file.crc32, file.cached_path()) else {
+                continue;
+            };
+            if !path.exists() {
+                continue;
+            }
+            let path_s = path.to_string_lossy().to_string();
+            let bytes = read(&path).map_err(|e| MetaAlmanacError::MetaIO {
+                path: path_s.clone(),
+                what: "verifying cached file",
+                source: InputOutputError::IOError { kind: e.kind() },
+            })?;
+            let computed = crc32fast::hash(&bytes);
+            if computed != crc32 {
+                warn!("cached {path_s} has CRC32 0x{computed:x} but expected 0x{crc32:x}");
+                if delete {
+                    remove_file(&path).map_err(|e| MetaAlmanacError::MetaIO {
+                        path: path_s.clone(),
+                        what: "deleting corrupted cached file",
+                        source: InputOutputError::IOError { kind: e.kind() },
+                    })?;
+                }
+                corrupted.push(path_s);
+            }
+        }
+        Ok(corrupted)
+    }
+}
+
+/// Returns the total size in bytes of the files in the provided folder (not recursive).
+fn dir_size(dir: &Path) -> Result<u64, MetaAlmanacError> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let mut size = 0;
+    for entry in read_dir(dir).map_err(|e| io_error(dir, "listing cache", e))? {
+        let entry = entry.map_err(|e| io_error(dir, "listing cache", e))?;
+        let metadata = entry
+            .metadata()
+            .map_err(|e| io_error(&entry.path(), "reading metadata", e))?;
+        if metadata.is_file() {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
+
+/// Extensions of the files which ANISE downloads into its cache: SPICE kernels, their transfer files, and ANISE data sets.
+const CACHED_EXTENSIONS: [&str; 9] = [
+    "bsp", "bpc", "xsp", "xpc", "xfr", "pca", "epa", "lka", "sca",
+];
+
+/// Returns whether the provided path has the extension of a file ANISE downloads, which excludes the `.lock` files of the downloads.
+fn is_cached_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            CACHED_EXTENSIONS
+                .iter()
+                .any(|cached| ext.eq_ignore_ascii_case(cached))
+        })
+}
+
+/// Deletes the files downloaded by ANISE in the provided folder (not recursive) last modified longer ago than `older_than`.
+fn purge_dir(dir: &Path, older_than: Duration) -> Result<Vec<String>, MetaAlmanacError> {
+    let mut purged = Vec::new();
+    if !dir.exists() {
+        return Ok(purged);
+    }
+    let now = SystemTime::now();
+    for entry in read_dir(dir).map_err(|e| io_error(dir, "listing cache", e))? {
+        let entry = entry.map_err(|e| io_error(dir, "listing cache", e))?;
+        let path = entry.path();
+        let metadata = entry
+            .metadata()
+            .map_err(|e| io_error(&path, "reading metadata", e))?;
+        if !metadata.is_file() || !is_cached_file(&path) {
+            continue;
+        }
+        let modified = metadata
+            .modified()
+            .map_err(|e| io_error(&path, "reading modification time", e))?;
+        // Files modified in the future (clock skew) have an age of zero.
+        let age_s = now
+            .duration_since(modified)
+            .map(|age| age.as_secs_f64())
+            .unwrap_or(0.0);
+        if age_s > older_than.to_seconds() {
+            remove_file(&path).map_err(|e| io_error(&path, "purging cached file", e))?;
+            info!("purged {}", path.display());
+            purged.push(path.to_string_lossy().to_string());
+        }
+    }
+    Ok(purged)
+}
+
+fn io_error(path: &Path, what: &'static str, e: std::io::Error) -> MetaAlmanacError {
+    MetaAlmanacError::MetaIO {
+        path: path.to_string_lossy().to_string(),
+        what,
+        source: InputOutputError::IOError { kind: e.kind() },
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl MetaAlmanac {
+    /// Fetch all of the URIs and return a loaded Almanac, without blocking the async runtime.
+    ///
+    /// The downloads and the loading are run on the blocking thread pool of the Tokio runtime, and `self` is updated
+    /// with the local paths of the files once done, as with `process`. This function must be called from within a Tokio runtime.
+    pub async fn process_async(&mut self, autodelete: bool) -> AlmanacResult<Almanac> {
+        let mut meta = self.clone();
+        let (meta, rslt) = spawn_blocking(move || {
+            let rslt = meta.process(autodelete);
+            (meta, rslt)
+        })
+        .await
+        .map_err(join_error)?;
+        *self = meta;
+        rslt
+    }
 }
 
 impl FromStr for MetaAlmanac {
@@ -118,7 +286,7 @@ impl MetaAlmanac {
     pub fn dumps(&self) -> Result<String, MetaAlmanacError> {
         // Define the Dhall type
         let dhall_type: SimpleType =
-            serde_dhall::from_str("{ files : List { uri : Text, crc32 : Optional Natural } }")
+            serde_dhall::from_str("{ files : List { uri : Text, crc32 : Optional Natural, mirrors : List Text, retries : Natural, offline_ok : Bool } }")
                 .parse()
                 .unwrap();
 
@@ -201,6 +369,50 @@ impl MetaAlmanac {
         py.allow_threads(|| self.process(autodelete.unwrap_or(true)))
     }
 
+    /// Returns the folder where the remote files are cached, which may be overridden with the `ANISE_CACHE_DIR` environment variable.
+    ///
+    /// :rtype: str
+    #[classmethod]
+    #[pyo3(name = "cache_dir")]
+    fn py_cache_dir(_cls: &Bound<'_, PyType>) -> Result<String, MetaAlmanacError> {
+        Ok(Self::cache_dir()?.to_string_lossy().to_string())
+    }
+
+    /// Returns the total size in bytes of the files in the cache folder.
+    ///
+    /// :rtype: int
+    #[classmethod]
+    #[pyo3(name = "cache_size")]
+    fn py_cache_size(_cls: &Bound<'_, PyType>) -> Result<u64, MetaAlmanacError> {
+        Self::cache_size()
+    }
+
+    /// Deletes the kernels and data sets of the cache folder which were last modified longer ago than `older_than`, and returns their paths.
+    /// Only the files with the extension of a kernel or data set (bsp, bpc, xsp, xpc, xfr, pca, epa, lka, sca) are deleted, never the lock files of the downloads.
+    ///
+    /// :type older_than: Duration
+    /// :rtype: typing.List
+    #[classmethod]
+    #[pyo3(name = "purge_cache")]
+    fn py_purge_cache(
+        _cls: &Bound<'_, PyType>,
+        older_than: Duration,
+    ) -> Result<Vec<String>, MetaAlmanacError> {
+        Self::purge_cache(older_than)
+    }
+
+    /// Recomputes the CRC32 of the cached copy of each remote file with a CRC32 and returns the paths of those which do not match,
+    /// e.g. because the file was truncated or modified after its download. Set `delete` to remove these files from the cache,
+    /// so that they are downloaded again on the next call to `process`. Files which are not cached are ignored.
+    ///
+    /// :type delete: bool, optional
+    /// :rtype: typing.List
+    #[pyo3(name = "verify_cache")]
+    #[pyo3(signature=(delete=None))]
+    fn py_verify_cache(&self, delete: Option<bool>) -> Result<Vec<String>, MetaAlmanacError> {
+        self.verify_cache(delete.unwrap_or(false))
+    }
+
     fn __str__(&self) -> String {
         format!("{self:?}")
     }
@@ -257,10 +469,12 @@ impl Default for MetaAlmanac {
                 MetaFile {
                     uri: nyx_cloud_stor.join("de440s.bsp").unwrap().to_string(),
                     crc32: Some(0x7286750a),
+                    ..Default::default()
                 },
                 MetaFile {
                     uri: nyx_cloud_stor.join("v0.5/pck11.pca").unwrap().to_string(),
                     crc32: Some(0x8213b6e9),
+                    ..Default::default()
                 },
                 MetaFile {
                     uri: nyx_cloud_stor
@@ -268,6 +482,7 @@ impl Default for MetaAlmanac {
                         .unwrap()
                         .to_string(),
                     crc32: Some(0x21633903),
+                    ..Default::default()
                 },
                 MetaFile {
                     uri: nyx_cloud_stor
@@ -275,6 +490,7 @@ impl Default for MetaAlmanac {
                         .unwrap()
                         .to_string(),
                     crc32: Some(0xcde5ca7d),
+                    ..Default::default()
                 },
                 MetaFile {
                     uri: jpl_cloud_stor
@@ -282,8 +498,82 @@ impl Default for MetaAlmanac {
                         .unwrap()
                         .to_string(),
                     crc32: None,
+                    ..Default::default()
                 },
             ],
         }
     }
 }
+
+#[cfg(test)]
+mod ut_cache {
+    use super::{dir_size, purge_dir, MetaAlmanac, MetaFile};
+    use hifitime::{Duration, TimeUnits};
+    use std::env;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::thread::sleep;
+
+    #[test]
+    fn size_and_purge() {
+        let dir = env::temp_dir().join("anise-cache-purge-test");
+        let _ = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+        write(dir.join("a.bsp"), [0_u8; 100]).unwrap();
+        write(dir.join("b.BPC"), [0_u8; 28]).unwrap();
+        write(dir.join("a.bsp.lock"), [0_u8; 2]).unwrap();
+        write(dir.join("notes.txt"), [0_u8; 3]).unwrap();
+
+        assert_eq!(dir_size(&dir).unwrap(), 133);
+
+        // Nothing is a day old
+        assert!(purge_dir(&dir, 1.days()).unwrap().is_empty());
+        assert_eq!(dir_size(&dir).unwrap(), 133);
+
+        // Only the downloaded files are purged, never the lock files
+        sleep(std::time::Duration::from_millis(10));
+        assert_eq!(purge_dir(&dir, Duration::ZERO).unwrap().len(), 2);
+        assert_eq!(dir_size(&dir).unwrap(), 5);
+        assert!(dir.join("a.bsp.lock").exists());
+        assert!(dir.join("notes.txt").exists());
+        remove_dir_all(&dir).unwrap();
+
+        // Missing folders are empty caches
+        assert_eq!(dir_size(&dir.join("missing")).unwrap(), 0);
+    }
+
+    #[test]
+    fn verify_cache() {
+        let file = MetaFile {
+            uri: "http://127.0.0.1:9/anise-verify-test.bsp".to_string(),
+            crc32: Some(crc32fast::hash(b"valid")),
+            ..Default::default()
+        };
+        let path = file.cached_path().unwrap();
+        create_dir_all(path.parent().unwrap()).unwrap();
+
+        let meta = MetaAlmanac { files: vec![file] };
+
+        write(&path, b"valid").unwrap();
+        assert!(meta.verify_cache(true).unwrap().is_empty());
+
+        write(&path, b"corrupted").unwrap();
+        assert_eq!(meta.verify_cache(false).unwrap().len(), 1);
+        assert!(path.exists());
+        assert_eq!(meta.verify_cache(true).unwrap().len(), 1);
+        assert!(!path.exists());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn process_async() {
+        let mut meta = MetaAlmanac {
+            files: vec![MetaFile {
+                uri: "../data/de440s.bsp".to_string(),
+                ..Default::default()
+            }],
+        };
+        let almanac = meta.process_async(true).await.unwrap();
+        assert_eq!(almanac.num_loaded_spk(), 1);
+        assert_eq!(meta.files[0].uri, "../data/de440s.bsp");
+    }
+}
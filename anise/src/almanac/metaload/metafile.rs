@@ -8,15 +8,14 @@
  * Documentation: https://nyxspace.com/
  */
 
-use log::{debug, info, warn};
-use platform_dirs::AppDirs;
+use log::{debug, error, info, warn};
 use regex::Regex;
 use serde_derive::{Deserialize, Serialize};
 use serde_dhall::StaticType;
 use std::env;
-use std::fs::{create_dir_all, remove_file, File};
-use std::io::Write;
-use std::path::Path;
+use std::fs::{create_dir_all, read_dir, remove_file, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::Duration;
 use url::Url;
@@ -30,17 +29,36 @@ use pyo3::pyclass::CompareOp;
 
 use crate::file2heap;
 use crate::prelude::InputOutputError;
+use crate::progress::ProgressSink;
 
-use super::MetaAlmanacError;
+use super::{cache_dir, MetaAlmanacError};
+
+/// Suffix of a URI requesting the latest version of a file, e.g. `de###.bsp@latest`
+const LATEST_SUFFIX: &str = "@latest";
+
+/// Size of the chunks in which the downloads are read, i.e. the granularity of the progress reports.
+const DOWNLOAD_CHUNK_SIZE: usize = 1 << 16;
 
 /// MetaFile allows downloading a remote file from a URL (http, https only), and interpolation of paths in environment variable using the Dhall syntax `env:MY_ENV_VAR`.
 ///
-/// The data is stored in the user's local temp directory (i.e. `~/.local/share/nyx-space/anise/` on Linux and `AppData/Local/nyx-space/anise/` on Windows).
+/// The data is stored in the user's local temp directory (i.e. `~/.local/share/nyx-space/anise/` on Linux and `AppData/Local/nyx-space/anise/` on Windows),
+/// unless the `ANISE_CACHE_DIR` environment variable is set.
 /// Prior to loading a remote resource, if the local resource exists, its CRC32 will be computed: if it matches the CRC32 of this instance of MetaFile,
 /// then the file will not be downloaded a second time.
 ///
+/// A remote URI may request the latest version of a file with a pattern ending in `@latest`, where each `#` matches a digit, e.g.
+/// `https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/planets/de###.bsp@latest`: the directory listing is fetched and the file
+/// with the greatest number is used. When the CRC32 is set, downloads which do not match it are rejected.
+///
+/// If the file cannot be fetched, each of the mirrors is tried in order, and each URI is retried `retries` times. Set `offline_ok`
+/// to fall back to the cached copy, if any, when none of the URIs can be fetched (e.g. in CI or on an air-gapped network).
+/// A cached copy which does not match the CRC32, if set, is never used.
+///
 /// :type uri: str
 /// :type crc32: int, optional
+/// :type mirrors: typing.List, optional
+/// :type retries: int, optional
+/// :type offline_ok: bool, optional
 /// :rtype: MetaFile
 #[cfg_attr(feature = "python", pyclass)]
 #[cfg_attr(feature = "python", pyo3(module = "anise"))]
@@ -50,6 +68,15 @@ pub struct MetaFile {
     pub uri: String,
     /// Optionally specify the CRC32 of this file, which will be checked prior to loading.
     pub crc32: Option<u32>,
+    /// Alternative URIs of this file, tried in order if the main URI cannot be fetched.
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+    /// Number of additional attempts for each URI if the download fails, with an exponential back off starting at one second.
+    #[serde(default)]
+    pub retries: u32,
+    /// If the file cannot be downloaded from any URI, use the cached copy if there is one and it matches the CRC32, if set.
+    #[serde(default)]
+    pub offline_ok: bool,
 }
 
 impl MetaFile {
@@ -57,8 +84,34 @@ impl MetaFile {
     ///
     /// This function modified `self` and changes the URI to be the path to the downloaded file.
     pub fn process(&mut self, autodelete: bool) -> Result<(), MetaAlmanacError> {
+        self.process_with_progress(autodelete, None)
+    }
+
+    /// Processes this MetaFile exactly like `process`, reporting the progress of the download to the provided sink, if any.
+    ///
+    /// The task is named after the URL being downloaded and its total is the size of the file in bytes, if provided by the server.
+    /// Nothing is reported if the file is local or if the cached copy is used.
+    pub fn process_with_progress(
+        &mut self,
+        autodelete: bool,
+        progress: Option<&dyn ProgressSink>,
+    ) -> Result<(), MetaAlmanacError> {
+        self.process_in_dir(cache_dir(), autodelete, progress)
+    }
+
+    /// Processes this MetaFile like `process_with_progress`, caching remote files in the provided folder.
+    fn process_in_dir(
+        &mut self,
+        data_dir: Option<PathBuf>,
+        autodelete: bool,
+        progress: Option<&dyn ProgressSink>,
+    ) -> Result<(), MetaAlmanacError> {
         // First, parse environment variables if any.
         self.uri = replace_env_vars(&self.uri);
+        // Then, resolve the latest version of this file if requested.
+        if self.uri.ends_with(LATEST_SUFFIX) {
+            self.resolve_latest(data_dir.as_deref())?;
+        }
         match Url::parse(&self.uri) {
             Err(e) => {
                 debug!("parsing {} caused {e} -- assuming local path", self.uri);
@@ -78,15 +131,15 @@ impl MetaFile {
                     Some(remote_file_path) => {
                         match Path::new(remote_file_path).file_name() {
                             Some(file_name) => {
-                                match AppDirs::new(Some("nyx-space/anise"), true) {
-                                    Some(app_dir) => {
+                                match data_dir {
+                                    Some(data_dir) => {
                                         // Check whether the path currently exists.
 
-                                        if !app_dir.data_dir.exists() {
+                                        if !data_dir.exists() {
                                             // Create the folders
-                                            create_dir_all(&app_dir.data_dir).map_err(|e| {
+                                            create_dir_all(&data_dir).map_err(|e| {
                                                 MetaAlmanacError::MetaIO {
-                                                    path: app_dir.data_dir.to_str().unwrap().into(),
+                                                    path: data_dir.to_str().unwrap().into(),
                                                     what: "creating directories for storage",
                                                     source: InputOutputError::IOError {
                                                         kind: e.kind(),
@@ -95,7 +148,7 @@ impl MetaFile {
                                             })?;
                                         }
 
-                                        let dest_path = app_dir.data_dir.join(file_name);
+                                        let dest_path = data_dir.join(file_name);
                                         let lock_path = dest_path.with_file_name(
                                             file_name.to_str().unwrap().to_string() + ".lock",
                                         );
@@ -170,74 +223,37 @@ impl MetaFile {
                                             });
                                         }
 
-                                        let del_lock_file = || {
-                                            // Ignore if the deletion of the lock file fails
-                                            let _ = remove_file(lock_path);
-                                        };
-
-                                        let client = reqwest::blocking::Client::builder()
-                                            .connect_timeout(Duration::from_secs(30))
-                                            .timeout(Duration::from_secs(30))
-                                            .build()
-                                            .unwrap();
-
-                                        match client.get(url.clone()).send() {
-                                            Ok(resp) => {
-                                                if resp.status().is_success() {
-                                                    // Downloaded the file, let's store it locally.
-                                                    match File::create(&dest_path) {
-                                                        Err(e) => {
-                                                            del_lock_file();
-                                                            Err(MetaAlmanacError::MetaIO {
-                                                                path: dest_path
-                                                                    .to_str()
-                                                                    .unwrap()
-                                                                    .into(),
-                                                                what: "creating file for storage",
-                                                                source: InputOutputError::IOError {
-                                                                    kind: e.kind(),
-                                                                },
-                                                            })
-                                                        }
-                                                        Ok(mut file) => {
-                                                            // Created the file, let's write the bytes.
-                                                            let bytes = resp.bytes().unwrap();
-                                                            let crc32 = crc32fast::hash(&bytes);
-                                                            file.write_all(&bytes).unwrap();
-
-                                                            info!(
-                                                                 "Saved {url} to {} (CRC32 = 0x{crc32:x})",
-                                                                 dest_path.to_str().unwrap()
-                                                             );
-
-                                                            // Set the URI for loading
-                                                            self.uri = dest_path
-                                                                .to_str()
-                                                                .unwrap()
-                                                                .to_string();
-
-                                                            // Set the CRC32
-                                                            self.crc32 = Some(crc32);
+                                        let rslt = self.download(&url, &dest_path, progress);
 
-                                                            del_lock_file();
+                                        // Ignore if the deletion of the lock file fails
+                                        let _ = remove_file(lock_path);
 
-                                                            Ok(())
-                                                        }
-                                                    }
-                                                } else {
-                                                    del_lock_file();
-                                                    Err(MetaAlmanacError::FetchError {
-                                                        status: resp.status(),
-                                                        uri: self.uri.clone(),
-                                                    })
-                                                }
+                                        let dest_path_s = dest_path.to_str().unwrap().to_string();
+                                        match rslt {
+                                            Ok(crc32) => {
+                                                // Set the URI for loading and the CRC32
+                                                self.uri = dest_path_s;
+                                                self.crc32 = Some(crc32);
+                                                Ok(())
                                             }
                                             Err(e) => {
-                                                del_lock_file();
-                                                Err(MetaAlmanacError::CnxError {
-                                                    uri: self.uri.clone(),
-                                                    error: format!("{e}"),
-                                                })
+                                                if !self.offline_ok || !dest_path.exists() {
+                                                    return Err(e);
+                                                }
+                                                // The cached copy is only used if it matches the expected CRC32, if any.
+                                                if let Some(crc32) = self.crc32 {
+                                                    let dest_path_c = dest_path.clone(); // macro token issue
+                                                    let computed_crc32 = file2heap!(dest_path_c)
+                                                        .map(|bytes| crc32fast::hash(&bytes))
+                                                        .ok();
+                                                    if computed_crc32 != Some(crc32) {
+                                                        error!("{e} -- NOT using cached {dest_path_s} although offline_ok is set: its CRC32 is {computed_crc32:x?} but expected 0x{crc32:x}");
+                                                        return Err(e);
+                                                    }
+                                                }
+                                                warn!("{e} -- using cached {dest_path_s} (offline_ok is set)");
+                                                self.uri = dest_path_s;
+                                                Ok(())
                                             }
                                         }
                                     }
@@ -256,6 +272,172 @@ impl MetaFile {
             }
         }
     }
+
+    /// Returns the path where this remote file is (or would be) cached, or None if the URI is a local path or an unresolved `@latest` pattern.
+    pub fn cached_path(&self) -> Option<PathBuf> {
+        let uri = replace_env_vars(&self.uri);
+        if uri.ends_with(LATEST_SUFFIX) {
+            return None;
+        }
+        let url = Url::parse(&uri).ok()?;
+        if !url.scheme().starts_with("http") {
+            return None;
+        }
+        let remote_file_path = url.path_segments()?.last()?;
+        let file_name = Path::new(remote_file_path).file_name()?;
+        Some(cache_dir()?.join(file_name))
+    }
+
+    /// Resolves a URI of the form `https://host/path/de###.bsp@latest` into the URI of the latest file of the remote directory
+    /// listing that matches the pattern, where each `#` matches a single digit and the latest file is that with the greatest number.
+    ///
+    /// If the listing cannot be fetched and `offline_ok` is set, the latest matching file of the cache is used instead.
+    fn resolve_latest(&mut self, data_dir: Option<&Path>) -> Result<(), MetaAlmanacError> {
+        let uri = self.uri.trim_end_matches(LATEST_SUFFIX).to_string();
+        let (dir_uri, pattern) = match uri.rfind('/') {
+            Some(idx) => uri.split_at(idx + 1),
+            None => {
+                return Err(MetaAlmanacError::MissingFilePath {
+                    path: self.uri.clone(),
+                })
+            }
+        };
+
+        let file_names = match fetch_listing(dir_uri) {
+            Ok(listing) => listing,
+            Err(e) => {
+                if !self.offline_ok {
+                    return Err(e);
+                }
+                warn!("{e} -- looking for {pattern} in the cache (offline_ok is set)");
+                let data_dir = data_dir.ok_or(MetaAlmanacError::AppDirError)?;
+                read_dir(data_dir)
+                    .map(|entries| {
+                        entries
+                            .filter_map(|entry| entry.ok())
+                            .map(|entry| entry.file_name().to_string_lossy().to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+        };
+
+        match latest_match(&file_names, pattern) {
+            Some(file_name) => {
+                info!("Resolved {} to {file_name}", self.uri);
+                self.uri = format!("{dir_uri}{file_name}");
+                Ok(())
+            }
+            None => Err(MetaAlmanacError::NoLatestMatch {
+                pattern: pattern.to_string(),
+                uri: dir_uri.to_string(),
+            }),
+        }
+    }
+
+    /// Downloads this file to the destination path, trying the main URI and then each mirror, with retries on each of them.
+    /// Returns the CRC32 of the downloaded file.
+    fn download(
+        &self,
+        url: &Url,
+        dest_path: &Path,
+        progress: Option<&dyn ProgressSink>,
+    ) -> Result<u32, MetaAlmanacError> {
+        let client = reqwest::blocking::Client::builder()
+            .connect_timeout(Duration::from_secs(30))
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        let mut urls = vec![url.clone()];
+        for mirror in &self.mirrors {
+            match Url::parse(&replace_env_vars(mirror)) {
+                Ok(mirror_url) => urls.push(mirror_url),
+                Err(e) => warn!("ignoring mirror {mirror}: {e}"),
+            }
+        }
+
+        let mut last_err = None;
+
+        for url in urls {
+            for attempt in 0..=self.retries {
+                if attempt > 0 {
+                    // Exponential back off, capped at 32 seconds.
+                    let wait = Duration::from_secs(1 << (attempt - 1).min(5));
+                    info!(
+                        "retrying {url} in {wait:?} (attempt {attempt} of {})",
+                        self.retries
+                    );
+                    thread::sleep(wait);
+                }
+
+                match client.get(url.clone()).send() {
+                    Ok(resp) => {
+                        let status = resp.status();
+                        if !status.is_success() {
+                            last_err = Some(MetaAlmanacError::FetchError {
+                                status,
+                                uri: url.to_string(),
+                            });
+                            if status.is_client_error() {
+                                // Retrying will not change the outcome, move onto the next mirror.
+                                break;
+                            }
+                            continue;
+                        }
+
+                        let bytes = match read_body(resp, url.as_str(), progress) {
+                            Ok(bytes) => bytes,
+                            Err(e) => {
+                                last_err = Some(MetaAlmanacError::CnxError {
+                                    uri: url.to_string(),
+                                    error: format!("{e}"),
+                                });
+                                continue;
+                            }
+                        };
+
+                        let crc32 = crc32fast::hash(&bytes);
+                        if let Some(expected) = self.crc32 {
+                            if crc32 != expected {
+                                last_err = Some(MetaAlmanacError::ChecksumMismatch {
+                                    uri: url.to_string(),
+                                    expected,
+                                    computed: crc32,
+                                });
+                                // This mirror serves another version of this file.
+                                break;
+                            }
+                        }
+
+                        // Downloaded the file, let's store it locally.
+                        File::create(dest_path)
+                            .and_then(|mut file| file.write_all(&bytes))
+                            .map_err(|e| MetaAlmanacError::MetaIO {
+                                path: dest_path.to_str().unwrap().into(),
+                                what: "creating file for storage",
+                                source: InputOutputError::IOError { kind: e.kind() },
+                            })?;
+
+                        info!(
+                            "Saved {url} to {} (CRC32 = 0x{crc32:x})",
+                            dest_path.to_str().unwrap()
+                        );
+
+                        return Ok(crc32);
+                    }
+                    Err(e) => {
+                        last_err = Some(MetaAlmanacError::CnxError {
+                            uri: url.to_string(),
+                            error: format!("{e}"),
+                        });
+                    }
+                }
+            }
+        }
+
+        Err(last_err.unwrap())
+    }
 }
 
 #[cfg(feature = "python")]
@@ -263,9 +445,21 @@ impl MetaFile {
 impl MetaFile {
     /// Builds a new MetaFile from the provided URI and optionally its CRC32 checksum.
     #[new]
-    #[pyo3(signature=(uri, crc32=None))]
-    pub fn py_new(uri: String, crc32: Option<u32>) -> Self {
-        Self { uri, crc32 }
+    #[pyo3(signature=(uri, crc32=None, mirrors=None, retries=None, offline_ok=None))]
+    pub fn py_new(
+        uri: String,
+        crc32: Option<u32>,
+        mirrors: Option<Vec<String>>,
+        retries: Option<u32>,
+        offline_ok: Option<bool>,
+    ) -> Self {
+        Self {
+            uri,
+            crc32,
+            mirrors: mirrors.unwrap_or_default(),
+            retries: retries.unwrap_or_default(),
+            offline_ok: offline_ok.unwrap_or_default(),
+        }
     }
 
     fn __str__(&self) -> String {
@@ -323,6 +517,134 @@ impl MetaFile {
         self.crc32 = crc32;
         Ok(())
     }
+    /// :rtype: typing.List
+    #[getter]
+    fn get_mirrors(&self) -> PyResult<Vec<String>> {
+        Ok(self.mirrors.clone())
+    }
+    /// :type mirrors: typing.List
+    #[setter]
+    fn set_mirrors(&mut self, mirrors: Vec<String>) -> PyResult<()> {
+        self.mirrors = mirrors;
+        Ok(())
+    }
+    /// :rtype: int
+    #[getter]
+    fn get_retries(&self) -> PyResult<u32> {
+        Ok(self.retries)
+    }
+    /// :type retries: int
+    #[setter]
+    fn set_retries(&mut self, retries: u32) -> PyResult<()> {
+        self.retries = retries;
+        Ok(())
+    }
+    /// :rtype: bool
+    #[getter]
+    fn get_offline_ok(&self) -> PyResult<bool> {
+        Ok(self.offline_ok)
+    }
+    /// :type offline_ok: bool
+    #[setter]
+    fn set_offline_ok(&mut self, offline_ok: bool) -> PyResult<()> {
+        self.offline_ok = offline_ok;
+        Ok(())
+    }
+}
+
+/// Fetches the HTML directory listing at the provided URI and returns the file names it links to.
+/// Reads the body of the response in chunks, reporting the number of bytes read to the progress sink, if any.
+fn read_body(
+    mut resp: reqwest::blocking::Response,
+    task: &str,
+    progress: Option<&dyn ProgressSink>,
+) -> std::io::Result<Vec<u8>> {
+    let total = resp.content_length();
+    if let Some(progress) = progress {
+        progress.start(task, total);
+    }
+
+    let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+    let mut chunk = vec![0_u8; DOWNLOAD_CHUNK_SIZE];
+    loop {
+        let count = resp.read(&mut chunk)?;
+        if count == 0 {
+            break;
+        }
+        bytes.extend_from_slice(&chunk[..count]);
+        if let Some(progress) = progress {
+            progress.update(bytes.len() as u64);
+        }
+    }
+
+    if let Some(progress) = progress {
+        progress.finish();
+    }
+
+    Ok(bytes)
+}
+
+fn fetch_listing(dir_uri: &str) -> Result<Vec<String>, MetaAlmanacError> {
+    let client = reqwest::blocking::Client::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .timeout(Duration::from_secs(30))
+        .build()
+        .unwrap();
+
+    let resp = client
+        .get(dir_uri)
+        .send()
+        .map_err(|e| MetaAlmanacError::CnxError {
+            uri: dir_uri.to_string(),
+            error: format!("{e}"),
+        })?;
+
+    if !resp.status().is_success() {
+        return Err(MetaAlmanacError::FetchError {
+            status: resp.status(),
+            uri: dir_uri.to_string(),
+        });
+    }
+
+    let listing = resp.text().map_err(|e| MetaAlmanacError::CnxError {
+        uri: dir_uri.to_string(),
+        error: format!("{e}"),
+    })?;
+
+    let re = Regex::new(r#"href="([^"]+)""#).unwrap();
+    Ok(re
+        .captures_iter(&listing)
+        .filter_map(|caps| caps[1].rsplit('/').next().map(|name| name.to_string()))
+        .collect())
+}
+
+/// Returns the file name matching the pattern (where `#` is any digit) with the greatest number.
+fn latest_match(file_names: &[String], pattern: &str) -> Option<String> {
+    let mut re_str = String::from("^");
+    for c in pattern.chars() {
+        if c == '#' {
+            // Only ASCII digits: `\d` also matches other Unicode digits.
+            re_str.push_str("[0-9]");
+        } else {
+            re_str.push_str(&regex::escape(&c.to_string()));
+        }
+    }
+    re_str.push('$');
+    let re = Regex::new(&re_str).ok()?;
+
+    file_names
+        .iter()
+        .filter(|name| re.is_match(name))
+        .max_by_key(|name| {
+            name.chars()
+                .zip(pattern.chars())
+                .filter(|(_, p)| *p == '#')
+                .filter_map(|(c, _)| c.to_digit(10))
+                .fold(0_u64, |acc, digit| {
+                    acc.saturating_mul(10).saturating_add(digit as u64)
+                })
+        })
+        .cloned()
 }
 
 fn replace_env_vars(input: &str) -> String {
@@ -343,6 +665,7 @@ mod ut_metafile {
         let mut window_path = MetaFile {
             uri: "C:\\Users\\me\\meta.dhall".to_string(),
             crc32: None,
+            ..Default::default()
         };
         assert!(window_path.process(true).is_ok());
         assert_eq!(window_path.uri, "C:\\Users\\me\\meta.dhall".to_string());
@@ -350,6 +673,7 @@ mod ut_metafile {
         let mut file_prefix_path = MetaFile {
             uri: "fIlE:///Users/me/meta.dhall".to_string(),
             crc32: None,
+            ..Default::default()
         };
         assert!(file_prefix_path.process(true).is_ok());
         assert_eq!(file_prefix_path.uri, "/Users/me/meta.dhall".to_string());
@@ -357,6 +681,7 @@ mod ut_metafile {
         let mut unix_abs_path = MetaFile {
             uri: "/Users/me/meta.dhall".to_string(),
             crc32: None,
+            ..Default::default()
         };
         assert!(unix_abs_path.process(true).is_ok());
         assert_eq!(unix_abs_path.uri, "/Users/me/meta.dhall".to_string());
@@ -364,6 +689,7 @@ mod ut_metafile {
         let mut unix_rel_path = MetaFile {
             uri: "../Users/me/meta.dhall".to_string(),
             crc32: None,
+            ..Default::default()
         };
         assert!(unix_rel_path.process(true).is_ok());
         assert_eq!(unix_rel_path.uri, "../Users/me/meta.dhall".to_string());
@@ -375,6 +701,7 @@ mod ut_metafile {
         let mut user_path = MetaFile {
             uri: "env:USER/.cargo/env".to_string(),
             crc32: None,
+            ..Default::default()
         };
         user_path.process(false).unwrap();
         assert_eq!(user_path.uri, env::var("USER").unwrap() + "/.cargo/env");
@@ -382,6 +709,7 @@ mod ut_metafile {
         let mut unknown_path = MetaFile {
             uri: "env:BLAH_BLAH_NO_EXIST/.cargo/env".to_string(),
             crc32: None,
+            ..Default::default()
         };
         unknown_path.process(false).unwrap();
         assert_eq!(
@@ -389,4 +717,98 @@ mod ut_metafile {
             "env:BLAH_BLAH_NO_EXIST/.cargo/env".to_string()
         );
     }
+
+    #[test]
+    fn offline_fallback() {
+        use std::env;
+        use std::fs::{create_dir_all, remove_dir_all, write};
+
+        let _ = pretty_env_logger::try_init();
+
+        let data_dir = env::temp_dir().join("anise-offline-test");
+        let _ = remove_dir_all(&data_dir);
+        create_dir_all(&data_dir).unwrap();
+        let cached_path = data_dir.join("anise-offline-test.bsp");
+
+        // The `invalid` top level domain never resolves, so these fail with or without a network.
+        let uri = "http://anise-offline-test.invalid/anise-offline-test.bsp".to_string();
+        let mirrors = vec!["http://mirror.invalid/anise-offline-test.bsp".to_string()];
+
+        let mut no_cache = MetaFile {
+            uri: uri.clone(),
+            mirrors: mirrors.clone(),
+            offline_ok: true,
+            ..Default::default()
+        };
+        assert!(no_cache
+            .process_in_dir(Some(data_dir.clone()), true, None)
+            .is_err());
+
+        write(&cached_path, b"cached").unwrap();
+
+        let mut online_only = MetaFile {
+            uri: uri.clone(),
+            mirrors: mirrors.clone(),
+            ..Default::default()
+        };
+        assert!(online_only
+            .process_in_dir(Some(data_dir.clone()), true, None)
+            .is_err());
+
+        let mut offline = MetaFile {
+            uri: uri.clone(),
+            mirrors: mirrors.clone(),
+            offline_ok: true,
+            ..Default::default()
+        };
+        offline
+            .process_in_dir(Some(data_dir.clone()), true, None)
+            .unwrap();
+        assert_eq!(offline.uri, cached_path.to_str().unwrap());
+
+        // A cached copy which does not match the CRC32 is not used, even offline
+        let mut mismatched = MetaFile {
+            uri,
+            crc32: Some(crc32fast::hash(b"expected")),
+            mirrors,
+            offline_ok: true,
+            ..Default::default()
+        };
+        assert!(mismatched
+            .process_in_dir(Some(data_dir.clone()), true, None)
+            .is_err());
+        assert!(mismatched.uri.starts_with("http"));
+
+        remove_dir_all(&data_dir).unwrap();
+    }
+
+    #[test]
+    fn latest_pattern() {
+        use super::latest_match;
+
+        let listing = [
+            "de430.bsp",
+            "de440s.bsp",
+            "de440.bsp",
+            "de441_part-1.bsp",
+            "de432s.bsp",
+            "aareadme.txt",
+            // Arabic-Indic digits are not version numbers
+            "de٤٥٠.bsp",
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            latest_match(&listing, "de###.bsp"),
+            Some("de440.bsp".to_string())
+        );
+        assert_eq!(
+            latest_match(&listing, "de###s.bsp"),
+            Some("de440s.bsp".to_string())
+        );
+        assert_eq!(latest_match(&listing, "pck#####.tpc"), None);
+        assert_eq!(latest_match(&listing[6..], "de###.bsp"), None);
+    }
 }
@@ -20,8 +20,11 @@ use crate::{
     errors::{AlmanacResult, MetaSnafu},
     prelude::InputOutputError,
 };
+use platform_dirs::AppDirs;
 use reqwest::StatusCode;
 use snafu::prelude::*;
+use std::env;
+use std::path::PathBuf;
 
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
@@ -51,6 +54,26 @@ pub enum MetaAlmanacError {
         "download to {desired} blocked while lock file `{desired}.lock` exists, please delete lock file"
     ))]
     PersistentLock { desired: String },
+    #[snafu(display("downloaded {uri} has CRC32 0x{computed:x} but expected 0x{expected:x}"))]
+    ChecksumMismatch {
+        uri: String,
+        expected: u32,
+        computed: u32,
+    },
+    #[snafu(display("no file matching `{pattern}` found in {uri}"))]
+    NoLatestMatch { pattern: String, uri: String },
+}
+
+/// Environment variable overriding the folder where the remote files are cached.
+pub const CACHE_DIR_ENV_VAR: &str = "ANISE_CACHE_DIR";
+
+/// Returns the folder where the remote files are cached: the `ANISE_CACHE_DIR` environment variable if set,
+/// or the user's local data directory otherwise.
+pub(crate) fn cache_dir() -> Option<PathBuf> {
+    match env::var_os(CACHE_DIR_ENV_VAR) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => AppDirs::new(Some("nyx-space/anise"), true).map(|app_dir| app_dir.data_dir),
+    }
 }
 
 impl Almanac {
@@ -115,7 +138,8 @@ mod meta_test {
             .load_from_metafile(
                 MetaFile {
                     uri: "http://example.com/non/existing.pca".to_string(),
-                    crc32: None
+                    crc32: None,
+                    ..Default::default()
                 },
                 true
             )
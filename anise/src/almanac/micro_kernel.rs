@@ -0,0 +1,218 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use core::fmt::Write;
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use crate::ephemerides::EphemerisError;
+use crate::errors::{AlmanacError, AlmanacResult, EphemerisSnafu};
+use crate::naif::daf::datatypes::ChebyshevRefit;
+use crate::naif::SPK;
+use crate::prelude::Frame;
+
+use super::Almanac;
+
+/// Compact SPK of a handful of bodies over a limited time span, fitted within a maximum position error, for embedded targets
+/// where even a truncated planetary ephemeris is too large.
+///
+/// The SPK may be stored as a binary blob, or as Rust source to be compiled into the firmware and loaded with
+/// `Almanac::load_from_bytes(Bytes::from_static(&NAME))`, cf. `to_rust_source`.
+#[derive(Clone, Debug)]
+pub struct MicroKernel {
+    pub spk: SPK,
+    /// Target and observer of each segment, and the fit of that segment
+    pub segments: Vec<(Frame, Frame, ChebyshevRefit)>,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
+
+impl MicroKernel {
+    /// Returns the bytes of this micro kernel, which is a valid SPK file.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.spk.bytes
+    }
+
+    /// Returns the Rust source code declaring this micro kernel as a public static byte array of the provided name.
+    pub fn to_rust_source(&self, name: &str) -> String {
+        let bytes = self.as_bytes();
+        let mut src = format!(
+            "// ANISE micro kernel generated by ANISE v{} from {} to {}\n",
+            env!("CARGO_PKG_VERSION"),
+            self.start_epoch,
+            self.end_epoch
+        );
+        for (target, observer, refit) in &self.segments {
+            // Writing to a String cannot fail
+            let _ = writeln!(src, "// {target:e} wrt {observer:e}: {refit}");
+        }
+        let _ = writeln!(src, "pub static {name}: [u8; {}] = [", bytes.len());
+        for chunk in bytes.chunks(16) {
+            src.push_str("   ");
+            for byte in chunk {
+                let _ = write!(src, " 0x{byte:02x},");
+            }
+            src.push('\n');
+        }
+        src.push_str("];\n");
+        src
+    }
+}
+
+impl fmt::Display for MicroKernel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Micro kernel from {} to {} ({} bytes)",
+            self.start_epoch,
+            self.end_epoch,
+            self.as_bytes().len()
+        )?;
+        for (target, observer, refit) in &self.segments {
+            writeln!(f, "  {target:e} wrt {observer:e}: {refit}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Almanac {
+    /// Builds a micro kernel of the geometric states of each target with respect to its observer, expressed in the orientation
+    /// of the observer frame, from `start` to `end`. Each segment is the smallest Type 2 Chebyshev segment whose position error
+    /// with respect to the loaded ephemerides is within `max_error_km`, cf. `SPKBuilder::add_type2_segment_within`.
+    ///
+    /// # Limitations
+    /// The targets and observers must be in the loaded ephemeris data throughout the whole time span.
+    pub fn build_micro_kernel(
+        &self,
+        bodies: &[(Frame, Frame)],
+        start: Epoch,
+        end: Epoch,
+        max_error_km: f64,
+    ) -> AlmanacResult<MicroKernel> {
+        if bodies.is_empty() {
+            return Err(AlmanacError::GenericError {
+                err: "a micro kernel requires at least one body".to_string(),
+            });
+        }
+
+        let mut builder = SPK::builder("ANISE MICRO KERNEL").with_comments(&format!(
+            "ANISE micro kernel within {max_error_km} km\nExported by ANISE v{}\nAlmanac context digest: 0x{:08x}",
+            env!("CARGO_PKG_VERSION"),
+            self.context_digest()
+        ));
+
+        let mut segments = Vec::with_capacity(bodies.len());
+        for (target, observer) in bodies {
+            // The builder requires an ephemeris error, so keep track of the first error from the transform.
+            let mut query_err = None;
+            let rslt = builder.add_type2_segment_within(
+                &format!("{target:e} wrt {observer:e}"),
+                target.ephemeris_id,
+                observer.ephemeris_id,
+                observer.orientation_id,
+                start,
+                end,
+                max_error_km,
+                |epoch| match self.transform(*target, *observer, epoch, None) {
+                    Ok(state) => Ok(state),
+                    Err(e) => {
+                        query_err.get_or_insert(e);
+                        Err(EphemerisError::Unreachable)
+                    }
+                },
+            );
+            if let Some(e) = query_err {
+                return Err(e);
+            }
+            let refit = rslt.context(EphemerisSnafu {
+                action: "building micro kernel segment",
+            })?;
+            segments.push((*target, *observer, refit));
+        }
+
+        Ok(MicroKernel {
+            spk: builder.build().context(EphemerisSnafu {
+                action: "building micro kernel",
+            })?,
+            segments,
+            start_epoch: start,
+            end_epoch: end,
+        })
+    }
+}
+
+#[cfg(test)]
+mod ut_micro_kernel {
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000, SUN_J2000};
+    use crate::prelude::Almanac;
+    use bytes::Bytes;
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn micro_kernel() {
+        let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let end = start + Unit::Day * 30;
+
+        let micro = almanac
+            .build_micro_kernel(
+                &[(MOON_J2000, EARTH_J2000), (SUN_J2000, EARTH_J2000)],
+                start,
+                end,
+                1e-3,
+            )
+            .unwrap();
+        println!("{micro}");
+        assert_eq!(micro.segments.len(), 2);
+        assert!(micro
+            .segments
+            .iter()
+            .all(|(_, _, refit)| refit.max_error <= 1e-3));
+        // Only a few kilobytes, mostly the file, summary, and name records of the DAF
+        assert!(micro.as_bytes().len() < 16 * 1024, "{micro}");
+
+        // The micro kernel is an SPK which reproduces the original ephemerides
+        let embedded = Almanac::default()
+            .load_from_bytes(Bytes::copy_from_slice(micro.as_bytes()))
+            .unwrap();
+        for hours in [0_i64, 7, 311, 719] {
+            let epoch = start + Unit::Hour * hours;
+            for target in [MOON_J2000, SUN_J2000] {
+                let expected = almanac.translate(target, EARTH_J2000, epoch, None).unwrap();
+                let state = embedded
+                    .translate(target, EARTH_J2000, epoch, None)
+                    .unwrap();
+                // The maximum error is sampled, so allow for some margin between its samples
+                assert!((state.radius_km - expected.radius_km).norm() < 2e-3);
+            }
+        }
+
+        let src = micro.to_rust_source("MICRO_KERNEL");
+        assert!(src.starts_with("// ANISE micro kernel"));
+        assert!(src.contains(&format!(
+            "pub static MICRO_KERNEL: [u8; {}] = [",
+            micro.as_bytes().len()
+        )));
+        assert!(src.ends_with("];\n"));
+
+        // Bodies outside of the loaded ephemerides
+        assert!(almanac
+            .build_micro_kernel(
+                &[(MOON_J2000, EARTH_J2000)],
+                start,
+                start + Unit::Century * 3,
+                1e-3
+            )
+            .is_err());
+        assert!(almanac.build_micro_kernel(&[], start, end, 1e-3).is_err());
+    }
+}
@@ -18,28 +18,51 @@ use crate::ephemerides::SPKSnafu;
 use crate::errors::{
     AlmanacError, AlmanacResult, EphemerisSnafu, LoadingSnafu, OrientationSnafu, TLDataSetSnafu,
 };
-use crate::file2heap;
-use crate::naif::daf::{FileRecord, NAIFRecord};
+use crate::frames::Frame;
+use crate::naif::daf::datatypes::HermiteWindow;
+use crate::naif::daf::{transfer_file_kind, FileRecord, NAIFRecord};
+use crate::naif::kpl::pool::KernelPool;
 use crate::naif::pretty_print::NAIFPrettyPrint;
 use crate::naif::{BPC, SPK};
-use crate::orientations::BPCSnafu;
+use crate::orientations::two_vector::TwoVectorFrame;
+use crate::orientations::{BPCSnafu, DcmCache};
 use crate::structure::dataset::DataSetType;
 use crate::structure::metadata::Metadata;
-use crate::structure::{EulerParameterDataSet, PlanetaryDataSet, SpacecraftDataSet};
+use crate::structure::{
+    EulerParameterDataSet, LocationDataSet, PlanetaryDataSet, SpacecraftDataSet,
+};
+use crate::{file2heap, file2mmap, NaifId};
 use core::fmt;
+use planetary::FrameOverride;
+use std::collections::BTreeMap;
 
 // TODO: Switch these to build constants so that it's configurable when building the library.
 pub const MAX_LOADED_SPKS: usize = 32;
 pub const MAX_LOADED_BPCS: usize = 8;
 pub const MAX_SPACECRAFT_DATA: usize = 16;
 pub const MAX_PLANETARY_DATA: usize = 64;
+pub const MAX_LOCATION_DATA: usize = 128;
 
 pub mod aer;
+pub mod bodies;
 pub mod bpc;
+pub mod context;
+pub mod description;
+pub mod diagnostics;
 pub mod eclipse;
+pub mod export;
+pub mod frame_path;
+pub mod kernels;
+pub mod lagrange;
+pub mod location;
+pub mod magnetic;
+pub mod micro_kernel;
 pub mod planetary;
+pub mod pool;
+pub mod query;
 pub mod solar;
 pub mod spk;
+pub mod surface;
 pub mod transform;
 
 #[cfg(feature = "metaload")]
@@ -52,6 +75,18 @@ mod python;
 #[cfg_attr(docsrs, doc(cfg(feature = "embed_ephem")))]
 mod embed;
 
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+mod asynchronous;
+
+#[cfg(feature = "horizons")]
+#[cfg_attr(docsrs, doc(cfg(feature = "horizons")))]
+pub mod horizons;
+
+#[cfg(feature = "sbdb")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sbdb")))]
+pub mod small_body;
+
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
@@ -67,12 +102,32 @@ pub struct Almanac {
     pub spk_data: [Option<SPK>; MAX_LOADED_SPKS],
     /// NAIF BPC is kept unchanged
     pub bpc_data: [Option<BPC>; MAX_LOADED_BPCS],
+    /// Alias of each loaded SPK, i.e. the path it was loaded from, if any
+    pub spk_aliases: [Option<String>; MAX_LOADED_SPKS],
+    /// Alias of each loaded BPC, i.e. the path it was loaded from, if any
+    pub bpc_aliases: [Option<String>; MAX_LOADED_BPCS],
     /// Dataset of planetary data
     pub planetary_data: PlanetaryDataSet,
     /// Dataset of spacecraft data
     pub spacecraft_data: SpacecraftDataSet,
     /// Dataset of euler parameters
     pub euler_param_data: EulerParameterDataSet,
+    /// Dataset of locations
+    pub location_data: LocationDataSet,
+    /// Frames registered at runtime, by their upper case name
+    pub frame_registry: BTreeMap<String, Frame>,
+    /// Gravitational parameter and shape overrides set at runtime, by the ephemeris ID of the frame center
+    pub frame_overrides: BTreeMap<NaifId, FrameOverride>,
+    /// Body names (upper case) and NAIF IDs loaded from text kernels or added at runtime, in the order of their definition
+    pub body_names: Vec<(String, NaifId)>,
+    /// Variables of the text kernels loaded in this Almanac, including those that are not modeled by ANISE
+    pub kernel_pool: KernelPool,
+    /// Dynamic two-vector frames, by their orientation ID
+    pub dynamic_frames: BTreeMap<NaifId, TwoVectorFrame>,
+    /// Selection of the interpolation window of the Hermite Type 13 SPK segments
+    pub hermite_window: HermiteWindow,
+    /// Cache of the rotations between orientations, if enabled with `with_dcm_cache`
+    pub dcm_cache: Option<DcmCache>,
 }
 
 impl fmt::Display for Almanac {
@@ -92,6 +147,9 @@ impl fmt::Display for Almanac {
         if !self.euler_param_data.lut.by_id.is_empty() {
             write!(f, "\t{}", self.euler_param_data)?;
         }
+        if !self.location_data.data.is_empty() {
+            write!(f, "\t{}", self.location_data)?;
+        }
         Ok(())
     }
 }
@@ -118,11 +176,97 @@ impl Almanac {
 
     /// Loads the provides bytes as one of the data types supported in ANISE.
     pub fn load_from_bytes(&self, bytes: Bytes) -> AlmanacResult<Self> {
-        self._load_from_bytes(bytes, None)
+        self._load_from_bytes(bytes, None, false)
+    }
+
+    /// Loads the provides bytes as one of the data types supported in ANISE, identifying the kernel by the provided alias
+    /// as if it had been loaded from that path (e.g. the name of a file fetched in the browser).
+    pub fn load_from_bytes_as(&self, bytes: Bytes, alias: &str) -> AlmanacResult<Self> {
+        self._load_from_bytes(bytes, Some(alias), false)
+    }
+
+    /// Loads the provided path like `load`, but SPK and BPC files are memory mapped instead of copied onto the heap
+    /// and parsed lazily: only the records needed by each query are read from disk, which bounds the memory footprint
+    /// of large kernels (e.g. DE440 or high precision Earth orientation files).
+    ///
+    /// ANISE data sets (PCA, EPA, etc.) are small and are loaded as with `load`.
+    ///
+    /// # Safety
+    /// The file must not be modified nor truncated while this Almanac or any of its clones is in use, as its data is read from
+    /// disk on each query: a truncated file raises SIGBUS on access, and a rewritten file changes the data under this Almanac.
+    pub unsafe fn load_lazy(&self, path: &str) -> AlmanacResult<Self> {
+        self._load_path(path, true)
     }
 
-    fn _load_from_bytes(&self, bytes: Bytes, path: Option<&str>) -> AlmanacResult<Self> {
-        // Try to load as a SPICE DAF first (likely the most typical use case)
+    /// Loads the provided path, reading the file with a memory map or onto the heap, and maps path-less errors to include the path.
+    fn _load_path(&self, path: &str, lazy: bool) -> AlmanacResult<Self> {
+        let bytes = if lazy {
+            file2mmap!(path)
+        } else {
+            file2heap!(path)
+        }
+        .context(LoadingSnafu {
+            path: path.to_string(),
+        })?;
+
+        self._load_from_bytes(bytes, Some(path), lazy)
+            .map_err(|e| match e {
+                AlmanacError::GenericError { err } => {
+                    // Add the path to the error
+                    AlmanacError::GenericError {
+                        err: format!("with {path}: {err}"),
+                    }
+                }
+                _ => e,
+            })
+    }
+
+    fn _load_from_bytes(
+        &self,
+        bytes: Bytes,
+        path: Option<&str>,
+        lazy: bool,
+    ) -> AlmanacResult<Self> {
+        // SPICE transfer files are converted to binary DAF in memory first
+        if let Some(kind) = transfer_file_kind(&bytes) {
+            info!(
+                "Converting {} from DAF/{kind} transfer format",
+                path.unwrap_or("bytes")
+            );
+            let text = core::str::from_utf8(&bytes).map_err(|e| AlmanacError::GenericError {
+                err: format!("transfer file is not valid UTF-8: {e}"),
+            })?;
+            let binary = match kind {
+                "PCK" => {
+                    BPC::from_transfer(text)
+                        .context(BPCSnafu {
+                            action: "converting transfer file",
+                        })
+                        .context(OrientationSnafu {
+                            action: "from generic loading",
+                        })?
+                        .bytes
+                }
+                "SPK" => {
+                    SPK::from_transfer(text)
+                        .context(SPKSnafu {
+                            action: "converting transfer file",
+                        })
+                        .context(EphemerisSnafu {
+                            action: "from generic loading",
+                        })?
+                        .bytes
+                }
+                kind => {
+                    return Err(AlmanacError::GenericError {
+                        err: format!("DAF/{kind} is not yet supported"),
+                    })
+                }
+            };
+            return self._load_from_bytes(binary, path, false);
+        }
+
+        // Then try to load as a binary SPICE DAF (likely the most typical use case)
 
         // Load the header only
         if let Some(file_record_bytes) = bytes.get(..FileRecord::SIZE) {
@@ -131,29 +275,41 @@ impl Almanac {
                 return match fileid {
                     "PCK" => {
                         info!("Loading {} as DAF/PCK", path.unwrap_or("bytes"));
-                        let bpc = BPC::parse(bytes)
-                            .context(BPCSnafu {
-                                action: "parsing bytes",
-                            })
-                            .context(OrientationSnafu {
-                                action: "from generic loading",
-                            })?;
-                        self.with_bpc(bpc).context(OrientationSnafu {
-                            action: "adding BPC file to context",
+                        let bpc = if lazy {
+                            BPC::parse_lazy(bytes)
+                        } else {
+                            BPC::parse(bytes)
+                        }
+                        .context(BPCSnafu {
+                            action: "parsing bytes",
                         })
+                        .context(OrientationSnafu {
+                            action: "from generic loading",
+                        })?;
+                        let mut me = self.with_bpc(bpc).context(OrientationSnafu {
+                            action: "adding BPC file to context",
+                        })?;
+                        me.bpc_aliases[me.num_loaded_bpc() - 1] = path.map(str::to_string);
+                        Ok(me)
                     }
                     "SPK" => {
                         info!("Loading {} as DAF/SPK", path.unwrap_or("bytes"));
-                        let spk = SPK::parse(bytes)
-                            .context(SPKSnafu {
-                                action: "parsing bytes",
-                            })
-                            .context(EphemerisSnafu {
-                                action: "from generic loading",
-                            })?;
-                        self.with_spk(spk).context(EphemerisSnafu {
-                            action: "adding SPK file to context",
+                        let spk = if lazy {
+                            SPK::parse_lazy(bytes)
+                        } else {
+                            SPK::parse(bytes)
+                        }
+                        .context(SPKSnafu {
+                            action: "parsing bytes",
                         })
+                        .context(EphemerisSnafu {
+                            action: "from generic loading",
+                        })?;
+                        let mut me = self.with_spk(spk).context(EphemerisSnafu {
+                            action: "adding SPK file to context",
+                        })?;
+                        me.spk_aliases[me.num_loaded_spk() - 1] = path.map(str::to_string);
+                        Ok(me)
                     }
                     fileid => Err(AlmanacError::GenericError {
                         err: format!("DAF/{fileid} is not yet supported"),
@@ -200,6 +356,16 @@ impl Almanac {
                     info!("Loading {} as ANISE/EPA", path.unwrap_or("bytes"));
                     Ok(self.with_euler_parameters(dataset))
                 }
+                DataSetType::LocationData => {
+                    // Decode as location data
+                    let dataset = LocationDataSet::try_from_bytes(bytes).context({
+                        TLDataSetSnafu {
+                            action: "loading as location data",
+                        }
+                    })?;
+                    info!("Loading {} as ANISE location data", path.unwrap_or("bytes"));
+                    Ok(self.with_location_data(dataset))
+                }
             }
         } else {
             Err(AlmanacError::GenericError {
@@ -217,20 +383,23 @@ impl Almanac {
     /// :rtype: Almanac
     pub fn load(&self, path: &str) -> AlmanacResult<Self> {
         // Load the data onto the heap
-        let bytes = file2heap!(path).context(LoadingSnafu {
-            path: path.to_string(),
-        })?;
+        self._load_path(path, false)
+    }
 
-        self._load_from_bytes(bytes, Some(path))
-            .map_err(|e| match e {
-                AlmanacError::GenericError { err } => {
-                    // Add the path to the error
-                    AlmanacError::GenericError {
-                        err: format!("with {path}: {err}"),
-                    }
-                }
-                _ => e,
-            })
+    /// Loads the provided path like `load`, but SPK and BPC files are memory mapped instead of copied onto the heap
+    /// and parsed lazily: only the records needed by each query are read from disk, which bounds the memory footprint
+    /// of large kernels (e.g. DE440 or high precision Earth orientation files).
+    ///
+    /// WARNING: the file must not be modified nor truncated while loaded, otherwise queries may crash the interpreter (SIGBUS)
+    /// or return corrupted data. ANISE data sets (PCA, EPA, etc.) are small and are loaded as with `load`.
+    ///
+    /// :type path: str
+    /// :rtype: Almanac
+    #[cfg(feature = "python")]
+    #[pyo3(name = "load_lazy")]
+    fn py_load_lazy(&self, path: &str) -> AlmanacResult<Self> {
+        // Python cannot acknowledge the safety requirements, so they are spelled out in the docstring instead.
+        unsafe { self.load_lazy(path) }
     }
 
     /// Initializes a new Almanac from the provided file path, guessing at the file type
@@ -12,10 +12,15 @@ use snafu::prelude::*;
 use tabled::{settings::Style, Table, Tabled};
 
 use crate::{
+    constants::{celestial_objects::SUN, orientations::id_to_orientation_name},
     prelude::{Frame, FrameUid},
-    structure::{dataset::DataSetError, PlanetaryDataSet},
+    structure::{dataset::DataSetError, planetocentric::ellipsoid::Ellipsoid, PlanetaryDataSet},
+    NaifId,
 };
 
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
 #[derive(Debug, Snafu, PartialEq)]
 #[snafu(visibility(pub(crate)))]
 pub enum PlanetaryDataError {
@@ -24,19 +29,271 @@ pub enum PlanetaryDataError {
         action: &'static str,
         source: DataSetError,
     },
+    #[snafu(display("no frame named `{name}` in the frame registry, the loaded planetary data, or the built-in constants"))]
+    FrameName { name: String },
+}
+
+/// Source of a datum of the frame information, in decreasing order of precedence.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "python", pyclass(eq, eq_int))]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub enum FrameDataSource {
+    /// Overridden at runtime with `override_frame`
+    Override,
+    /// Loaded planetary data
+    PlanetaryData,
+    /// Frame registered at runtime with `register_frame`
+    Registry,
+}
+
+/// Frame information and the source of its gravitational parameter and shape, as returned by `frame_sources_from_uid`.
+///
+/// :rtype: FrameInfoSources
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct FrameInfoSources {
+    pub frame: Frame,
+    /// Source of the gravitational parameter, None if it is not set
+    pub mu_km3_s2: Option<FrameDataSource>,
+    /// Source of the shape, None if it is not set
+    pub shape: Option<FrameDataSource>,
+}
+
+/// Gravitational parameter and shape of the frames centered on a body which take precedence over all other sources, see `override_frame`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FrameOverride {
+    pub mu_km3_s2: Option<f64>,
+    pub shape: Option<Ellipsoid>,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl FrameInfoSources {
+    /// :rtype: Frame
+    #[getter]
+    fn get_frame(&self) -> PyResult<Frame> {
+        Ok(self.frame)
+    }
+
+    /// :rtype: FrameDataSource
+    #[getter]
+    fn get_mu_km3_s2(&self) -> PyResult<Option<FrameDataSource>> {
+        Ok(self.mu_km3_s2)
+    }
+
+    /// :rtype: FrameDataSource
+    #[getter]
+    fn get_shape(&self) -> PyResult<Option<FrameDataSource>> {
+        Ok(self.shape)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?} (@{self:p})")
+    }
 }
 
 impl Almanac {
     /// Given the frame UID (or something that can be transformed into it), attempt to retrieve the full frame information, if that frame is loaded
+    ///
+    /// Each datum is taken from the first source which defines it, in this order of precedence:
+    /// 1. the overrides set at runtime with `override_frame`;
+    /// 2. the loaded planetary data;
+    /// 3. the frames registered at runtime with `register_frame`.
+    ///
+    /// Use `frame_sources_from_uid` to know which source supplied each datum.
     pub fn frame_from_uid<U: Into<FrameUid>>(&self, uid: U) -> Result<Frame, PlanetaryDataError> {
+        self.frame_sources_from_uid(uid).map(|info| info.frame)
+    }
+
+    /// Returns the frame information as `frame_from_uid`, along with the source of its gravitational parameter and of its shape.
+    pub fn frame_sources_from_uid<U: Into<FrameUid>>(
+        &self,
+        uid: U,
+    ) -> Result<FrameInfoSources, PlanetaryDataError> {
         let uid = uid.into();
-        Ok(self
-            .planetary_data
-            .get_by_id(uid.ephemeris_id)
-            .context(PlanetaryDataSetSnafu {
-                action: "fetching frame by its UID via ephemeris_id",
-            })?
-            .to_frame(uid))
+        let (frame, source) =
+            match self
+                .planetary_data
+                .get_by_id(uid.ephemeris_id)
+                .context(PlanetaryDataSetSnafu {
+                    action: "fetching frame by its UID via ephemeris_id",
+                }) {
+                Ok(data) => (data.to_frame(uid), FrameDataSource::PlanetaryData),
+                Err(e) => match self
+                    .frame_registry
+                    .values()
+                    .find(|frame| FrameUid::from(*frame) == uid)
+                {
+                    Some(frame) => (*frame, FrameDataSource::Registry),
+                    None if self.frame_overrides.contains_key(&uid.ephemeris_id) => {
+                        (Frame::from(uid), FrameDataSource::Override)
+                    }
+                    None => return Err(e),
+                },
+            };
+
+        let overrides = self
+            .frame_overrides
+            .get(&uid.ephemeris_id)
+            .copied()
+            .unwrap_or_default();
+
+        let pick = |over: bool, base: bool| {
+            if over {
+                Some(FrameDataSource::Override)
+            } else if base {
+                Some(source)
+            } else {
+                None
+            }
+        };
+
+        Ok(FrameInfoSources {
+            mu_km3_s2: pick(overrides.mu_km3_s2.is_some(), frame.mu_km3_s2.is_some()),
+            shape: pick(overrides.shape.is_some(), frame.shape.is_some()),
+            frame: self.with_frame_overrides(frame),
+        })
+    }
+
+    /// Returns a copy of this frame with the data overridden for its center, if any.
+    fn with_frame_overrides(&self, mut frame: Frame) -> Frame {
+        if let Some(overrides) = self.frame_overrides.get(&frame.ephemeris_id) {
+            if let Some(mu_km3_s2) = overrides.mu_km3_s2 {
+                frame.mu_km3_s2 = Some(mu_km3_s2);
+            }
+            if let Some(shape) = overrides.shape {
+                frame.shape = Some(shape);
+            }
+        }
+        frame
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Registers a frame under the provided name into a clone of this Almanac, e.g. a frame centered on a spacecraft or on a body
+    /// that is not in the built-in constants. The name is case insensitive.
+    ///
+    /// Registered frames are returned by `frame_from_name`, and their gravitational parameter and shape are used by `frame_from_uid`
+    /// if the loaded planetary data does not define that frame. Overrides set with `override_frame` still take precedence.
+    ///
+    /// :type name: str
+    /// :type ephemeris_id: int
+    /// :type orientation_id: int
+    /// :type mu_km3_s2: float, optional
+    /// :type shape: Ellipsoid, optional
+    /// :rtype: Almanac
+    #[cfg_attr(feature = "python", pyo3(signature=(name, ephemeris_id, orientation_id, mu_km3_s2=None, shape=None)))]
+    pub fn register_frame(
+        &self,
+        name: &str,
+        ephemeris_id: NaifId,
+        orientation_id: NaifId,
+        mu_km3_s2: Option<f64>,
+        shape: Option<Ellipsoid>,
+    ) -> Self {
+        let mut me = self.clone();
+        me.frame_registry.insert(
+            normalize_frame_name(name),
+            Frame {
+                ephemeris_id,
+                orientation_id,
+                mu_km3_s2,
+                shape,
+            },
+        );
+        me
+    }
+
+    /// Overrides the gravitational parameter and/or the shape of all of the frames centered on this body into a clone of this Almanac,
+    /// e.g. to use the mu estimated by a small body mission instead of that of the loaded planetary data.
+    ///
+    /// Overrides take precedence over the loaded planetary data and the registered frames in `frame_from_uid` and `frame_from_name`.
+    /// Only the provided data is overridden, and replaces any previous override of this body.
+    ///
+    /// :type ephemeris_id: int
+    /// :type mu_km3_s2: float, optional
+    /// :type shape: Ellipsoid, optional
+    /// :rtype: Almanac
+    #[cfg_attr(feature = "python", pyo3(signature=(ephemeris_id, mu_km3_s2=None, shape=None)))]
+    pub fn override_frame(
+        &self,
+        ephemeris_id: NaifId,
+        mu_km3_s2: Option<f64>,
+        shape: Option<Ellipsoid>,
+    ) -> Self {
+        let mut me = self.clone();
+        me.frame_overrides
+            .insert(ephemeris_id, FrameOverride { mu_km3_s2, shape });
+        me
+    }
+
+    /// Removes the override of the frames centered on this body, if any, from a clone of this Almanac.
+    ///
+    /// :type ephemeris_id: int
+    /// :rtype: Almanac
+    pub fn clear_frame_override(&self, ephemeris_id: NaifId) -> Self {
+        let mut me = self.clone();
+        me.frame_overrides.remove(&ephemeris_id);
+        me
+    }
+
+    /// Returns the frame with the provided name, e.g. "Mars IAU", "EARTH J2000", "Earth-Moon Barycenter ECLIPJ2000", or any name registered with `register_frame`.
+    ///
+    /// The name is case insensitive. Unless it is a registered name, it is the name (or NAIF ID) of the center, followed by the name (or ID) of the orientation,
+    /// where the orientation "IAU" is the IAU body fixed frame of that center. The center may be any body name known to `naif_id_from_name`, or the name of an entry of the
+    /// loaded planetary data. The gravitational parameter and the shape of the frame are then set as in `frame_from_uid`, if available.
+    ///
+    /// :type name: str
+    /// :rtype: Frame
+    pub fn frame_from_name(&self, name: &str) -> Result<Frame, PlanetaryDataError> {
+        let normalized = normalize_frame_name(name);
+
+        if let Some(frame) = self.frame_registry.get(&normalized) {
+            return Ok(self.with_frame_overrides(*frame));
+        }
+
+        let tokens = normalized.split(' ').collect::<Vec<_>>();
+
+        // Try the longest center name first, e.g. "Earth-Moon Barycenter" before "Earth-Moon".
+        for split in (1..tokens.len()).rev() {
+            let center_id = self.center_id_from_name(&tokens[..split].join(" "));
+            let orientation_id =
+                center_id.and_then(|id| orientation_id_from_name(&tokens[split..].join(" "), id));
+
+            if let (Some(ephemeris_id), Some(orientation_id)) = (center_id, orientation_id) {
+                let frame = Frame::new(ephemeris_id, orientation_id);
+                return Ok(self.frame_from_uid(frame).unwrap_or(frame));
+            }
+        }
+
+        Err(PlanetaryDataError::FrameName {
+            name: name.to_string(),
+        })
+    }
+}
+
+impl Almanac {
+    /// Returns the ID of the provided (normalized) center name, which may be any body name known to `naif_id_from_name`, or an entry of the planetary data.
+    fn center_id_from_name(&self, center: &str) -> Option<NaifId> {
+        if let Ok(id) = self.naif_id_from_name(center) {
+            return Some(id);
+        }
+
+        self.planetary_data
+            .lut
+            .by_name
+            .iter()
+            .find(|(entry, _)| normalize_frame_name(entry) == center)
+            .and_then(|(_, index)| {
+                self.planetary_data
+                    .lut
+                    .by_id
+                    .iter()
+                    .find(|(_, i)| i == &index)
+            })
+            .map(|(id, _)| *id)
     }
 
     /// Loads the provided planetary data into a clone of this original Almanac.
@@ -47,6 +304,49 @@ impl Almanac {
     }
 }
 
+/// Returns the orientation ID of the provided (normalized) orientation name, where "IAU" is the IAU body fixed frame of the center.
+fn orientation_id_from_name(orientation: &str, center_id: NaifId) -> Option<NaifId> {
+    if orientation == "IAU" {
+        return match center_id {
+            // The IAU frame of a planetary barycenter is that of the planet
+            1..=9 => Some(center_id * 100 + 99),
+            SUN => Some(SUN),
+            101.. => Some(center_id),
+            _ => None,
+        };
+    }
+
+    orientation
+        .parse::<NaifId>()
+        .ok()
+        .or_else(|| id_to_orientation_name(orientation).ok())
+        .or_else(|| id_to_orientation_name(&title_case(orientation)).ok())
+}
+
+/// Upper cases the name and collapses its whitespaces, so that names are compared in a case insensitive manner.
+fn normalize_frame_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+/// Converts a normalized name into the title case used by the built-in names, e.g. "EARTH-MOON BARYCENTER" into "Earth-Moon Barycenter".
+fn title_case(name: &str) -> String {
+    let mut prev_alpha = false;
+    name.chars()
+        .map(|c| {
+            let rslt = if prev_alpha {
+                c.to_ascii_lowercase()
+            } else {
+                c.to_ascii_uppercase()
+            };
+            prev_alpha = c.is_ascii_alphanumeric();
+            rslt
+        })
+        .collect()
+}
+
 #[derive(Tabled, Default)]
 struct PlanetaryRow {
     #[tabled(rename = "Name")]
@@ -134,3 +434,114 @@ impl PlanetaryDataSet {
         format!("{tbl}")
     }
 }
+
+#[cfg(test)]
+mod ut_planetary {
+    use crate::constants::frames::{
+        EARTH_J2000, EARTH_MOON_BARYCENTER_J2000, IAU_EARTH_FRAME, IAU_MARS_FRAME,
+    };
+    use crate::constants::orientations::{ECLIPJ2000, J2000};
+    use crate::prelude::{Almanac, Frame};
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+
+    use super::FrameDataSource;
+
+    #[test]
+    fn frame_registry_and_names() {
+        let almanac = Almanac::new("../data/pck08.pca").unwrap();
+
+        // Built-in names, with the frame data from the PCA
+        let iau_mars = almanac.frame_from_name("MARS IAU").unwrap();
+        assert_eq!(iau_mars, almanac.frame_from_uid(IAU_MARS_FRAME).unwrap());
+        assert!(iau_mars.shape.is_some());
+
+        let earth_j2k = almanac.frame_from_name("  earth   J2000 ").unwrap();
+        assert_eq!(earth_j2k, almanac.frame_from_uid(EARTH_J2000).unwrap());
+
+        assert_eq!(
+            almanac.frame_from_name("Earth IAU").unwrap(),
+            almanac.frame_from_uid(IAU_EARTH_FRAME).unwrap()
+        );
+
+        let emb = almanac
+            .frame_from_name("Earth-Moon Barycenter ECLIPJ2000")
+            .unwrap();
+        assert_eq!(emb.ephemeris_id, EARTH_MOON_BARYCENTER_J2000.ephemeris_id);
+        assert_eq!(emb.orientation_id, ECLIPJ2000);
+
+        // NAIF IDs are also accepted.
+        assert_eq!(almanac.frame_from_name("399 1").unwrap(), earth_j2k);
+
+        assert!(almanac.frame_from_name("Vulcan J2000").is_err());
+        assert!(almanac.frame_from_name("Earth").is_err());
+
+        // Register an asteroid frame at runtime
+        let shape = Ellipsoid::from_spheroid(0.25, 0.2);
+        let almanac =
+            almanac.register_frame("Didymos J2000", 20065803, J2000, Some(3.5e-8), Some(shape));
+
+        let didymos = almanac.frame_from_name("DIDYMOS j2000").unwrap();
+        assert_eq!(didymos.ephemeris_id, 20065803);
+        assert_eq!(didymos.mu_km3_s2, Some(3.5e-8));
+        assert_eq!(didymos.shape, Some(shape));
+
+        // The registered frame data is available from its UID since it isn't in the PCA.
+        assert_eq!(
+            almanac
+                .frame_from_uid(Frame::from_ephem_j2000(20065803))
+                .unwrap(),
+            didymos
+        );
+
+        // The loaded planetary data is searched first.
+        let almanac = almanac.register_frame("Other Earth", 399, J2000, Some(1.0), None);
+        assert_eq!(almanac.frame_from_uid(EARTH_J2000).unwrap(), earth_j2k);
+        assert_eq!(
+            almanac.frame_from_name("other earth").unwrap().mu_km3_s2,
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn frame_overrides_precedence() {
+        let almanac = Almanac::new("../data/pck08.pca").unwrap();
+        let orig = almanac.frame_sources_from_uid(IAU_MARS_FRAME).unwrap();
+        assert_eq!(orig.mu_km3_s2, Some(FrameDataSource::PlanetaryData));
+        assert_eq!(orig.shape, Some(FrameDataSource::PlanetaryData));
+
+        // Only override the gravitational parameter of Mars: it applies to all of its frames.
+        let almanac = almanac.override_frame(499, Some(42_828.0), None);
+        let info = almanac.frame_sources_from_uid(IAU_MARS_FRAME).unwrap();
+        assert_eq!(info.frame.mu_km3_s2, Some(42_828.0));
+        assert_eq!(info.frame.shape, orig.frame.shape);
+        assert_eq!(info.mu_km3_s2, Some(FrameDataSource::Override));
+        assert_eq!(info.shape, Some(FrameDataSource::PlanetaryData));
+        assert_eq!(
+            almanac.frame_from_name("Mars J2000").unwrap().mu_km3_s2,
+            Some(42_828.0)
+        );
+
+        // Overrides take precedence over registered frames.
+        let almanac = almanac
+            .register_frame("Didymos J2000", 20065803, J2000, Some(3.5e-8), None)
+            .override_frame(20065803, Some(3.0e-8), Some(Ellipsoid::from_sphere(0.4)));
+        let didymos = almanac.frame_from_name("Didymos J2000").unwrap();
+        assert_eq!(didymos.mu_km3_s2, Some(3.0e-8));
+        assert_eq!(didymos.shape, Some(Ellipsoid::from_sphere(0.4)));
+
+        // Frames only defined by an override are available.
+        assert!(almanac
+            .frame_from_uid(Frame::from_ephem_j2000(-170))
+            .is_err());
+        let almanac = almanac.override_frame(-170, None, Some(Ellipsoid::from_sphere(0.01)));
+        let info = almanac
+            .frame_sources_from_uid(Frame::from_ephem_j2000(-170))
+            .unwrap();
+        assert_eq!(info.mu_km3_s2, None);
+        assert_eq!(info.shape, Some(FrameDataSource::Override));
+
+        // Clearing the override restores the loaded planetary data.
+        let almanac = almanac.clear_frame_override(499);
+        assert_eq!(almanac.frame_from_uid(IAU_MARS_FRAME).unwrap(), orig.frame);
+    }
+}
@@ -0,0 +1,155 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::Almanac;
+use crate::{
+    errors::{AlmanacError, AlmanacResult},
+    naif::bodies::body_names_from_kernel,
+    orientations::two_vector::two_vector_frames_from_pool,
+    structure::dataset::DataSetError,
+};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Loads all of the variables of the provided text kernel (e.g. a TPC, FK, IK, or SCLK) into the kernel pool of a clone of this Almanac,
+    /// along with the body names and NAIF IDs, and the two-vector dynamic frames it defines, if any.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the equivalent of furnsh for text kernels: variables assigned with `=` replace the previously loaded values,
+    /// and those assigned with `+=` are appended to them.
+    ///
+    /// :type path: str
+    /// :rtype: Almanac
+    pub fn load_text_kernel(&self, path: &str) -> AlmanacResult<Self> {
+        let kernel = std::fs::read_to_string(path).map_err(|source| AlmanacError::TLDataSet {
+            action: "loading text kernel",
+            source: DataSetError::IO {
+                action: "reading text kernel",
+                source,
+            },
+        })?;
+
+        self._load_text_kernel(&kernel)
+    }
+
+    /// Returns the values of the numeric variable of the kernel pool, if defined.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of gdpool.
+    ///
+    /// :type name: str
+    /// :rtype: typing.List
+    pub fn gdpool(&self, name: &str) -> Option<Vec<f64>> {
+        self.kernel_pool
+            .get_f64s(name)
+            .map(|values| values.to_vec())
+    }
+
+    /// Returns the values of the numeric variable of the kernel pool rounded to the nearest integer, if defined.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of gipool.
+    ///
+    /// :type name: str
+    /// :rtype: typing.List
+    pub fn gipool(&self, name: &str) -> Option<Vec<i32>> {
+        self.kernel_pool.get_i32s(name)
+    }
+
+    /// Returns the values of the character variable of the kernel pool, if defined.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of gcpool.
+    ///
+    /// :type name: str
+    /// :rtype: typing.List
+    pub fn gcpool(&self, name: &str) -> Option<Vec<String>> {
+        self.kernel_pool
+            .get_strings(name)
+            .map(|values| values.to_vec())
+    }
+}
+
+impl Almanac {
+    /// Loads the provided content of a text kernel into a clone of this Almanac, cf. `load_text_kernel`.
+    pub(crate) fn _load_text_kernel(&self, kernel: &str) -> AlmanacResult<Self> {
+        // Body names are only extracted from the variables of this kernel.
+        let names = body_names_from_kernel(kernel).map_err(|source| AlmanacError::TLDataSet {
+            action: "loading text kernel",
+            source,
+        })?;
+
+        let mut me = self.clone();
+        me.kernel_pool
+            .load_kernel(kernel)
+            .map_err(|source| AlmanacError::TLDataSet {
+                action: "loading text kernel",
+                source,
+            })?;
+        me.body_names.extend(names);
+
+        // Dynamic frames may refer to frames defined in previously loaded kernels.
+        let frames = two_vector_frames_from_pool(&me.kernel_pool).map_err(|source| {
+            AlmanacError::TLDataSet {
+                action: "loading two-vector frames from text kernel",
+                source,
+            }
+        })?;
+        for (name, center, frame) in frames {
+            me = me.with_two_vector_frame(&name, center, frame);
+        }
+
+        Ok(me)
+    }
+}
+
+#[cfg(test)]
+mod ut_pool {
+    use crate::prelude::Almanac;
+
+    #[test]
+    fn text_kernel_pool() {
+        let almanac = Almanac::default()
+            .load_text_kernel("../data/pck00008.tpc")
+            .and_then(|almanac| almanac.load_text_kernel("../data/moon_080317.txt"))
+            .unwrap();
+
+        assert_eq!(
+            almanac.gdpool("BODY399_RADII"),
+            Some(vec![6378.14, 6378.14, 6356.75])
+        );
+
+        // Mission specific variables that are not modeled by ANISE are also available.
+        assert_eq!(almanac.gipool("FRAME_MOON_PA"), Some(vec![31000]));
+        assert_eq!(
+            almanac.gcpool("FRAME_31000_NAME"),
+            Some(vec!["MOON_PA".to_string()])
+        );
+        assert_eq!(
+            almanac.gcpool("TKFRAME_31000_RELATIVE"),
+            Some(vec!["MOON_PA_DE421".to_string()])
+        );
+        assert_eq!(
+            almanac.gdpool("TKFRAME_31000_MATRIX"),
+            Some(vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0])
+        );
+
+        assert_eq!(almanac.gdpool("FRAME_31000_NAME"), None);
+        assert_eq!(almanac.gcpool("BODY399_RADII"), None);
+        assert_eq!(almanac.gdpool("FRAME_-85_NAME"), None);
+
+        assert!(Almanac::default()
+            .load_text_kernel("../data/does-not-exist.tf")
+            .is_err());
+    }
+}
@@ -8,13 +8,17 @@
  * Documentation: https://nyxspace.com/
  */
 
-use super::{
-    planetary::{PlanetaryDataError, PlanetaryDataSetSnafu},
-    Almanac,
-};
+use snafu::ResultExt;
+
+use super::description::{BPCSegmentDescription, SPKSegmentDescription};
+use super::planetary::{FrameInfoSources, PlanetaryDataError};
+use super::Almanac;
+use crate::ephemerides::{EphemerisError, SPKSnafu};
+use crate::naif::daf::{DAFError, NAIFSummaryRecord};
+use crate::orientations::{BPCSnafu, OrientationError};
 use crate::prelude::Frame;
+use crate::NaifId;
 use pyo3::prelude::*;
-use snafu::prelude::*;
 
 #[pymethods]
 impl Almanac {
@@ -22,12 +26,86 @@ impl Almanac {
     /// :type uid: Frame
     /// :rtype: Frame
     pub fn frame_info(&self, uid: Frame) -> Result<Frame, PlanetaryDataError> {
-        Ok(self
-            .planetary_data
-            .get_by_id(uid.ephemeris_id)
-            .context(PlanetaryDataSetSnafu {
-                action: "fetching frame by its UID via ephemeris_id",
-            })?
-            .to_frame(uid.into()))
+        self.frame_from_uid(uid)
+    }
+
+    /// Returns the frame information as `frame_info`, along with the source of its gravitational parameter and of its shape.
+    ///
+    /// :type uid: Frame
+    /// :rtype: FrameInfoSources
+    pub fn frame_info_sources(&self, uid: Frame) -> Result<FrameInfoSources, PlanetaryDataError> {
+        self.frame_sources_from_uid(uid)
+    }
+
+    /// Returns the summaries of the segments of all of the loaded SPKs, in the order in which they will be used, i.e. in reverse loading order.
+    /// If an `id` is provided, only the segments whose target ID matches are returned, and an error is raised if there are none.
+    ///
+    /// :type id: int, optional
+    /// :rtype: typing.List
+    #[pyo3(name = "spk_summaries", signature=(id=None))]
+    fn py_spk_summaries(
+        &self,
+        id: Option<NaifId>,
+    ) -> Result<Vec<SPKSegmentDescription>, EphemerisError> {
+        let mut segments = vec![];
+        for maybe_spk in self.spk_data.iter().take(self.num_loaded_spk()).rev() {
+            let spk = maybe_spk.as_ref().unwrap();
+            let summaries = spk.data_summaries().context(SPKSnafu {
+                action: "fetching SPK summaries",
+            })?;
+            for (sno, summary) in summaries.iter().enumerate() {
+                if summary.is_empty() || id.is_some_and(|id| summary.id() != id) {
+                    continue;
+                }
+                let name = spk.nth_name(sno).context(SPKSnafu {
+                    action: "fetching SPK summaries",
+                })?;
+                segments.push(SPKSegmentDescription::from_summary(name, summary)?);
+            }
+        }
+
+        match id {
+            Some(id) if segments.is_empty() => Err(EphemerisError::SPK {
+                action: "searching for SPK summary",
+                source: DAFError::SummaryIdError { kind: "SPK", id },
+            }),
+            _ => Ok(segments),
+        }
+    }
+
+    /// Returns the summaries of the segments of all of the loaded BPCs, in the order in which they will be used, i.e. in reverse loading order.
+    /// If an `id` is provided, only the segments whose frame ID matches are returned, and an error is raised if there are none.
+    ///
+    /// :type id: int, optional
+    /// :rtype: typing.List
+    #[pyo3(name = "bpc_summaries", signature=(id=None))]
+    fn py_bpc_summaries(
+        &self,
+        id: Option<NaifId>,
+    ) -> Result<Vec<BPCSegmentDescription>, OrientationError> {
+        let mut segments = vec![];
+        for maybe_bpc in self.bpc_data.iter().take(self.num_loaded_bpc()).rev() {
+            let bpc = maybe_bpc.as_ref().unwrap();
+            let summaries = bpc.data_summaries().context(BPCSnafu {
+                action: "fetching BPC summaries",
+            })?;
+            for (sno, summary) in summaries.iter().enumerate() {
+                if summary.is_empty() || id.is_some_and(|id| summary.id() != id) {
+                    continue;
+                }
+                let name = bpc.nth_name(sno).context(BPCSnafu {
+                    action: "fetching BPC summaries",
+                })?;
+                segments.push(BPCSegmentDescription::from_summary(name, summary)?);
+            }
+        }
+
+        match id {
+            Some(id) if segments.is_empty() => Err(OrientationError::BPC {
+                action: "searching for BPC summary",
+                source: DAFError::SummaryIdError { kind: "BPC", id },
+            }),
+            _ => Ok(segments),
+        }
     }
 }
@@ -0,0 +1,351 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch, Unit};
+
+use super::diagnostics::PathDiagnostic;
+use super::Almanac;
+use crate::errors::AlmanacResult;
+use crate::math::cartesian::CartesianState;
+use crate::prelude::{Aberration, Frame};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Options of a transform query which may fall slightly outside of the domain of the loaded data, e.g. a few seconds
+/// past the end of a kernel.
+///
+/// - If `max_offset` is set, queries up to that duration outside of the domain are allowed: the state is clamped to the
+///   edge of the domain if `clamp_to_domain` is true, and linearly extrapolated from that edge otherwise.
+/// - Queries further than `max_offset` from the domain, or outside of the domain if `max_offset` is not set, fail as with
+///   `transform`, whether or not they would be clamped.
+///
+/// :type clamp_to_domain: bool, optional
+/// :type max_offset: Duration, optional
+/// :rtype: QueryOptions
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct QueryOptions {
+    pub clamp_to_domain: bool,
+    /// Maximum duration outside of the domain for which the query is clamped or extrapolated, no query outside of the domain is allowed if not set
+    pub max_offset: Option<Duration>,
+}
+
+impl QueryOptions {
+    /// Returns whether a query `offset` away from the domain of the data is allowed by these options.
+    pub fn allows(&self, offset: Duration) -> bool {
+        self.max_offset
+            .is_some_and(|max_offset| offset.abs() <= max_offset)
+    }
+}
+
+/// Result of a transform query with options, flagging whether the state was clamped or extrapolated.
+///
+/// :rtype: QueryResult
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub struct QueryResult {
+    pub state: CartesianState,
+    /// Requested epoch minus the epoch at which the data was evaluated, zero if the query is within the domain
+    pub offset: Duration,
+    /// Whether the state is that of the edge of the domain, and therefore not at the requested epoch
+    pub clamped: bool,
+    /// Whether the state was linearly extrapolated from the edge of the domain to the requested epoch
+    pub extrapolated: bool,
+}
+
+/// Returns the epoch closest to the requested one at which the first hop without data of this path is covered, if any.
+fn nearest_covered_epoch(diag: &PathDiagnostic) -> Option<Epoch> {
+    let hop = diag.hops.iter().find(|hop| hop.parent_id.is_none())?;
+
+    hop.segments
+        .iter()
+        .map(|segment| {
+            if diag.epoch < segment.start_epoch {
+                segment.start_epoch
+            } else {
+                segment.end_epoch
+            }
+        })
+        .min_by_key(|edge| (*edge - diag.epoch).abs())
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns the Cartesian state needed to transform the `from_frame` to the `to_frame`, like `transform`, but allows queries
+    /// slightly outside of the domain of the loaded data to be clamped to its edge or linearly extrapolated, per the provided options.
+    ///
+    /// The edge of the domain is searched from the first hop without data in the ephemeris and orientation paths of both frames,
+    /// and the clamped epoch is returned in the time scale of the requested epoch. Queries which fail for other reasons, or
+    /// which are further from the domain than allowed, return the original error.
+    ///
+    /// :type target_frame: Frame
+    /// :type observer_frame: Frame
+    /// :type epoch: Epoch
+    /// :type ab_corr: Aberration, optional
+    /// :type options: QueryOptions, optional
+    /// :rtype: QueryResult
+    pub fn transform_with_options(
+        &self,
+        target_frame: Frame,
+        observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+        options: Option<QueryOptions>,
+    ) -> AlmanacResult<QueryResult> {
+        let err = match self.transform(target_frame, observer_frame, epoch, ab_corr) {
+            Ok(state) => {
+                return Ok(QueryResult {
+                    state,
+                    offset: Duration::ZERO,
+                    clamped: false,
+                    extrapolated: false,
+                })
+            }
+            Err(e) => e,
+        };
+
+        let options = options.unwrap_or_default();
+
+        // All of the paths must be covered, so the edge is the furthest of the nearest covered epochs.
+        let edge = [target_frame, observer_frame]
+            .into_iter()
+            .flat_map(|frame| {
+                [
+                    self.ephemeris_path_diagnostic(frame, epoch),
+                    self.orientation_path_diagnostic(frame, epoch),
+                ]
+            })
+            .filter_map(|diag| nearest_covered_epoch(&diag))
+            .max_by_key(|edge| (*edge - epoch).abs());
+
+        let edge = match edge {
+            Some(edge) if options.allows(epoch - edge) => edge,
+            _ => return Err(err),
+        };
+
+        let state = self
+            .transform(target_frame, observer_frame, edge, ab_corr)
+            .map_err(|_| err)?;
+        let offset = epoch - edge;
+
+        if options.clamp_to_domain {
+            Ok(QueryResult {
+                state: CartesianState {
+                    epoch: edge.to_time_scale(epoch.time_scale),
+                    ..state
+                },
+                offset,
+                clamped: true,
+                extrapolated: false,
+            })
+        } else {
+            Ok(QueryResult {
+                state: CartesianState {
+                    radius_km: state.radius_km + state.velocity_km_s * offset.to_unit(Unit::Second),
+                    epoch,
+                    ..state
+                },
+                offset,
+                clamped: false,
+                extrapolated: true,
+            })
+        }
+    }
+}
+
+impl fmt::Display for QueryResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.clamped {
+            write!(
+                f,
+                "{} (clamped, {} from the domain)",
+                self.state, self.offset
+            )
+        } else if self.extrapolated {
+            write!(
+                f,
+                "{} (extrapolated, {} from the domain)",
+                self.state, self.offset
+            )
+        } else {
+            write!(f, "{}", self.state)
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl QueryOptions {
+    #[new]
+    #[pyo3(signature=(clamp_to_domain=false, max_offset=None))]
+    fn py_new(clamp_to_domain: bool, max_offset: Option<Duration>) -> Self {
+        Self {
+            clamp_to_domain,
+            max_offset,
+        }
+    }
+
+    /// :rtype: bool
+    #[getter]
+    fn get_clamp_to_domain(&self) -> PyResult<bool> {
+        Ok(self.clamp_to_domain)
+    }
+    /// :type clamp_to_domain: bool
+    #[setter]
+    fn set_clamp_to_domain(&mut self, clamp_to_domain: bool) -> PyResult<()> {
+        self.clamp_to_domain = clamp_to_domain;
+        Ok(())
+    }
+
+    /// :rtype: Duration
+    #[getter]
+    fn get_max_offset(&self) -> PyResult<Option<Duration>> {
+        Ok(self.max_offset)
+    }
+    /// :type max_offset: Duration
+    #[setter]
+    fn set_max_offset(&mut self, max_offset: Option<Duration>) -> PyResult<()> {
+        self.max_offset = max_offset;
+        Ok(())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?} (@{self:p})")
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl QueryResult {
+    /// :rtype: Orbit
+    #[getter]
+    fn get_state(&self) -> PyResult<CartesianState> {
+        Ok(self.state)
+    }
+
+    /// :rtype: Duration
+    #[getter]
+    fn get_offset(&self) -> PyResult<Duration> {
+        Ok(self.offset)
+    }
+
+    /// :rtype: bool
+    #[getter]
+    fn get_clamped(&self) -> PyResult<bool> {
+        Ok(self.clamped)
+    }
+
+    /// :rtype: bool
+    #[getter]
+    fn get_extrapolated(&self) -> PyResult<bool> {
+        Ok(self.extrapolated)
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+#[cfg(test)]
+mod ut_query {
+    use super::QueryOptions;
+    use crate::constants::frames::EARTH_J2000;
+    use crate::prelude::{Almanac, Frame};
+    use hifitime::{TimeScale, Unit};
+
+    #[test]
+    fn clamp_and_extrapolate() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/gmat-hermite.bsp")
+            .unwrap();
+
+        let sc = Frame::from_ephem_j2000(-10000001);
+        let (start, end) = almanac.spk_domain(-10000001).unwrap();
+
+        // Within the domain, the options change nothing
+        let epoch = start + (end - start) * 0.5;
+        let rslt = almanac
+            .transform_with_options(sc, EARTH_J2000, epoch, None, None)
+            .unwrap();
+        assert!(!rslt.clamped && !rslt.extrapolated);
+        assert_eq!(
+            rslt.state,
+            almanac.transform(sc, EARTH_J2000, epoch, None).unwrap()
+        );
+
+        // Two seconds past the end of the data, requested in another time scale
+        let epoch = (end + Unit::Second * 2).to_time_scale(TimeScale::UTC);
+        assert!(almanac.transform(sc, EARTH_J2000, epoch, None).is_err());
+        assert!(almanac
+            .transform_with_options(sc, EARTH_J2000, epoch, None, None)
+            .is_err());
+
+        let at_end = almanac.transform(sc, EARTH_J2000, end, None).unwrap();
+
+        // Clamping requires a maximum offset
+        let unbounded = QueryOptions {
+            clamp_to_domain: true,
+            max_offset: None,
+        };
+        assert!(almanac
+            .transform_with_options(sc, EARTH_J2000, epoch, None, Some(unbounded))
+            .is_err());
+
+        let clamp = QueryOptions {
+            clamp_to_domain: true,
+            max_offset: Some(Unit::Second * 5),
+        };
+        let rslt = almanac
+            .transform_with_options(sc, EARTH_J2000, epoch, None, Some(clamp))
+            .unwrap();
+        println!("{rslt}");
+        assert!(rslt.clamped && !rslt.extrapolated);
+        assert!((rslt.offset - Unit::Second * 2).abs() < Unit::Microsecond * 1);
+        assert!((rslt.state.epoch - end).abs() < Unit::Microsecond * 1);
+        assert_eq!(rslt.state.epoch.time_scale, TimeScale::UTC);
+        assert_eq!(rslt.state.radius_km, at_end.radius_km);
+
+        let extrapolate = QueryOptions {
+            clamp_to_domain: false,
+            max_offset: Some(Unit::Second * 5),
+        };
+        let rslt = almanac
+            .transform_with_options(sc, EARTH_J2000, epoch, None, Some(extrapolate))
+            .unwrap();
+        println!("{rslt}");
+        assert!(!rslt.clamped && rslt.extrapolated);
+        assert_eq!(rslt.state.epoch, epoch);
+        assert!(
+            (rslt.state.radius_km
+                - (at_end.radius_km + at_end.velocity_km_s * rslt.offset.to_seconds()))
+            .norm()
+                < 1e-9
+        );
+
+        // Too far from the domain
+        let short = QueryOptions {
+            clamp_to_domain: true,
+            max_offset: Some(Unit::Second * 1),
+        };
+        assert!(almanac
+            .transform_with_options(sc, EARTH_J2000, epoch, None, Some(short))
+            .is_err());
+    }
+}
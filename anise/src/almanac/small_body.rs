@@ -0,0 +1,287 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Duration, Epoch, TimeSeries};
+use log::info;
+use serde_json::Value;
+use snafu::prelude::*;
+
+use super::Almanac;
+use crate::constants::celestial_objects::SUN;
+use crate::constants::orientations::ECLIPJ2000;
+use crate::ephemerides::EphemerisError;
+use crate::errors::PhysicsError;
+use crate::naif::SPK;
+use crate::prelude::{Frame, Orbit};
+use crate::NaifId;
+
+pub use crate::constants::AU_KM;
+
+/// Gaussian gravitational constant, in AU^(3/2)/day, used by the SBDB to relate the elements of heliocentric orbits.
+const GAUSSIAN_GRAVITATIONAL_CONSTANT: f64 = 0.01720209895;
+
+/// Degree of the Hermite polynomials of the segments built from osculating elements.
+const SMALL_BODY_HERMITE_DEGREE: usize = 7;
+
+#[derive(Debug, Snafu, PartialEq)]
+#[snafu(visibility(pub(crate)))]
+pub enum SmallBodyError {
+    #[snafu(display("SBDB JSON is invalid: {err}"))]
+    SbdbJson { err: String },
+    #[snafu(display("SBDB JSON does not include the `{field}` field"))]
+    MissingField { field: &'static str },
+    #[snafu(display("SBDB field `{field}` has invalid value `{value}`"))]
+    InvalidField { field: &'static str, value: String },
+    #[snafu(display("when {action}, {source}"))]
+    SmallBodyPhysics {
+        action: &'static str,
+        source: PhysicsError,
+    },
+    #[snafu(display("when {action}, {source}"))]
+    SmallBodyEphemeris {
+        action: &'static str,
+        #[snafu(source(from(EphemerisError, Box::new)))]
+        source: Box<EphemerisError>,
+    },
+}
+
+/// Osculating heliocentric orbital elements of an asteroid or a comet, as provided by the JPL Small-Body Database (SBDB) API
+/// or by JPL Horizons, with respect to the ecliptic and mean equinox of J2000.
+///
+/// The elements are stored with the time of perihelion passage instead of the mean anomaly so that hyperbolic orbits are supported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmallBodyElements {
+    /// NAIF ID of the small body, i.e. the SPK ID of the SBDB, e.g. 2000433 for Eros
+    pub naif_id: NaifId,
+    /// Name of the small body, e.g. "433 Eros"
+    pub name: String,
+    /// Epoch of the osculating elements
+    pub epoch: Epoch,
+    /// Eccentricity
+    pub ecc: f64,
+    /// Perihelion distance, in km
+    pub periapsis_km: f64,
+    /// Inclination with respect to the ecliptic, in degrees
+    pub inc_deg: f64,
+    /// Longitude of the ascending node, in degrees
+    pub raan_deg: f64,
+    /// Argument of perihelion, in degrees
+    pub aop_deg: f64,
+    /// Time of perihelion passage
+    pub periapsis_epoch: Epoch,
+}
+
+impl SmallBodyElements {
+    /// Parses the osculating elements from the JSON response of the SBDB API (`https://ssd-api.jpl.nasa.gov/sbdb.api?sstr=...`).
+    ///
+    /// The name is the short name of the object if available (e.g. "433 Eros" or "1P/Halley"), and its full name otherwise.
+    pub fn from_sbdb_json(json: &str) -> Result<Self, SmallBodyError> {
+        let root: Value = serde_json::from_str(json)
+            .map_err(|e| SmallBodyError::SbdbJson { err: e.to_string() })?;
+
+        let object = root
+            .get("object")
+            .context(MissingFieldSnafu { field: "object" })?;
+        let orbit = root
+            .get("orbit")
+            .context(MissingFieldSnafu { field: "orbit" })?;
+
+        let naif_id = parse_field::<NaifId>(object.get("spkid"), "spkid")?;
+        let name = object
+            .get("shortname")
+            .or_else(|| object.get("fullname"))
+            .and_then(Value::as_str)
+            .context(MissingFieldSnafu { field: "fullname" })?
+            .trim()
+            .to_string();
+
+        let epoch = jde_tdb(parse_field(orbit.get("epoch"), "epoch")?);
+
+        let elements = orbit
+            .get("elements")
+            .and_then(Value::as_array)
+            .context(MissingFieldSnafu { field: "elements" })?;
+        let element = |field: &'static str| -> Result<f64, SmallBodyError> {
+            parse_field(
+                elements
+                    .iter()
+                    .find(|element| element.get("name").and_then(Value::as_str) == Some(field))
+                    .and_then(|element| element.get("value")),
+                field,
+            )
+        };
+
+        Ok(Self {
+            naif_id,
+            name,
+            epoch,
+            ecc: element("e")?,
+            periapsis_km: element("q")? * AU_KM,
+            inc_deg: element("i")?,
+            raan_deg: element("om")?,
+            aop_deg: element("w")?,
+            periapsis_epoch: jde_tdb(element("tp")?),
+        })
+    }
+
+    /// Returns the heliocentric frame of these elements: the Sun in the ecliptic J2000 orientation, with the gravitational parameter of the Gaussian constant.
+    pub fn frame(&self) -> Frame {
+        let day_s = Duration::from_days(1.0).to_seconds();
+        Frame::new(SUN, ECLIPJ2000)
+            .with_mu_km3_s2(GAUSSIAN_GRAVITATIONAL_CONSTANT.powi(2) * AU_KM.powi(3) / day_s.powi(2))
+    }
+
+    /// Returns the two-body heliocentric state of the small body at the provided epoch.
+    pub fn to_orbit(&self, epoch: Epoch) -> Result<Orbit, SmallBodyError> {
+        let sma_km = self.periapsis_km / (1.0 - self.ecc);
+        Orbit::try_keplerian(
+            sma_km,
+            self.ecc,
+            self.inc_deg,
+            self.raan_deg,
+            self.aop_deg,
+            0.0,
+            self.periapsis_epoch,
+            self.frame(),
+        )
+        .and_then(|periapsis| periapsis.at_epoch(epoch))
+        .context(SmallBodyPhysicsSnafu {
+            action: "propagating small body elements",
+        })
+    }
+
+    /// Builds an SPK with a single Type 13 segment of the two-body heliocentric states of this small body, sampled every `step` from `start` to `end`.
+    pub fn to_spk(&self, start: Epoch, end: Epoch, step: Duration) -> Result<SPK, SmallBodyError> {
+        let states = TimeSeries::inclusive(start, end, step)
+            .map(|epoch| self.to_orbit(epoch))
+            .collect::<Result<Vec<Orbit>, SmallBodyError>>()?;
+
+        let mut builder = SPK::builder(&format!("{} SBDB", self.name)).with_comments(&format!(
+            "Two-body ephemeris of {} ({}) built by ANISE {} from osculating elements at {}",
+            self.name,
+            self.naif_id,
+            env!("CARGO_PKG_VERSION"),
+            self.epoch
+        ));
+
+        builder
+            .add_type13_segment(
+                &self.name,
+                self.naif_id,
+                SUN,
+                ECLIPJ2000,
+                &states,
+                SMALL_BODY_HERMITE_DEGREE,
+            )
+            .and_then(|_| builder.build())
+            .context(SmallBodyEphemerisSnafu {
+                action: "building small body SPK",
+            })
+    }
+}
+
+impl Almanac {
+    /// Loads the two-body ephemeris of this small body from `start` to `end` into a clone of this Almanac, e.g. for flyby studies
+    /// of a target without a dedicated BSP. The name of the small body is also registered, so it may be used in `naif_id_from_name`.
+    ///
+    /// The ephemeris is a Type 13 segment of heliocentric states in the ecliptic J2000 orientation sampled every `step`:
+    /// it only accounts for the gravity of the Sun, and its accuracy degrades away from the epoch of the elements.
+    pub fn with_small_body(
+        &self,
+        elements: &SmallBodyElements,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+    ) -> Result<Self, SmallBodyError> {
+        let spk = elements.to_spk(start, end, step)?;
+        info!(
+            "loading two-body ephemeris of {} ({}) from {start} to {end}",
+            elements.name, elements.naif_id
+        );
+
+        let me = self
+            .with_spk(spk)
+            .context(SmallBodyEphemerisSnafu {
+                action: "loading small body SPK",
+            })?
+            .with_body_name(&elements.name, elements.naif_id);
+
+        Ok(me)
+    }
+}
+
+/// Returns the epoch of this Julian date in the TDB time scale.
+fn jde_tdb(days: f64) -> Epoch {
+    Epoch::from_jde_tdb(days)
+}
+
+/// Parses a field which the SBDB provides either as a string or as a number.
+fn parse_field<T: std::str::FromStr>(
+    value: Option<&Value>,
+    field: &'static str,
+) -> Result<T, SmallBodyError> {
+    let value = value.context(MissingFieldSnafu { field })?;
+    let repr = match value {
+        Value::String(repr) => repr.clone(),
+        other => other.to_string(),
+    };
+    repr.trim()
+        .parse::<T>()
+        .map_err(|_| SmallBodyError::InvalidField { field, value: repr })
+}
+
+#[cfg(test)]
+mod ut_small_body {
+    use super::{SmallBodyElements, SmallBodyError, AU_KM};
+    use crate::constants::celestial_objects::SUN;
+    use crate::constants::orientations::ECLIPJ2000;
+    use crate::prelude::{Almanac, Frame};
+    use hifitime::Unit;
+
+    #[test]
+    fn sbdb_eros() {
+        let json = std::fs::read_to_string("../data/sbdb-eros.json").unwrap();
+        let eros = SmallBodyElements::from_sbdb_json(&json).unwrap();
+        assert_eq!(eros.naif_id, 2000433);
+        assert_eq!(eros.name, "433 Eros");
+
+        // The state at the epoch of the elements matches the other elements of the SBDB
+        let orbit = eros.to_orbit(eros.epoch).unwrap();
+        assert!((orbit.sma_km().unwrap() / AU_KM - 1.457981430614).abs() < 1e-9);
+        assert!((orbit.ecc().unwrap() - 0.2228359407).abs() < 1e-9);
+        assert!((orbit.ma_deg().unwrap() - 53.28218065).abs() < 1e-6);
+
+        let start = eros.epoch - Unit::Day * 30;
+        let end = eros.epoch + Unit::Day * 30;
+        let almanac = Almanac::default()
+            .with_small_body(&eros, start, end, Unit::Day * 1)
+            .unwrap();
+        assert_eq!(almanac.naif_id_from_name("433 EROS").unwrap(), 2000433);
+
+        // Between the samples, the Hermite interpolation matches the two-body propagation
+        let epoch = eros.epoch + Unit::Hour * 7;
+        let state = almanac
+            .translate(
+                Frame::from_ephem_j2000(2000433),
+                Frame::new(SUN, ECLIPJ2000),
+                epoch,
+                None,
+            )
+            .unwrap();
+        let expected = eros.to_orbit(epoch).unwrap();
+        assert!((state.radius_km - expected.radius_km).norm() < 1e-3);
+
+        assert!(matches!(
+            SmallBodyElements::from_sbdb_json("{\"object\": {}}"),
+            Err(SmallBodyError::MissingField { field: "orbit" })
+        ));
+        assert!(SmallBodyElements::from_sbdb_json("not json").is_err());
+    }
+}
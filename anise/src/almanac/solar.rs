@@ -8,11 +8,20 @@
  * Documentation: https://nyxspace.com/
  */
 
-use crate::{constants::frames::SUN_J2000, ephemerides::EphemerisError, prelude::Frame, NaifId};
+use crate::{
+    astro::{Aberration, IlluminationAngles},
+    constants::frames::SUN_J2000,
+    ephemerides::{EphemerisError, EphemerisPhysicsSnafu},
+    errors::{AlmanacError, AlmanacResult, EphemerisSnafu, PhysicsError},
+    math::Vector3,
+    prelude::{Frame, Orbit},
+    NaifId,
+};
 
 use super::Almanac;
 
 use hifitime::Epoch;
+use snafu::ResultExt;
 
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
@@ -95,6 +104,138 @@ impl Almanac {
     ) -> Result<f64, EphemerisError> {
         self.sun_angle_deg(target.ephemeris_id, observer.ephemeris_id, epoch)
     }
+
+    /// Returns the angular separation (between 0 and 180 degrees) between two targets as seen from the observer, accounting for the aberration correction if provided.
+    ///
+    /// This is a generalization of the `sun_angle_deg` function, useful for star tracker blinding or antenna interference analyses.
+    ///
+    /// :type target1: Frame
+    /// :type target2: Frame
+    /// :type observer: Frame
+    /// :type epoch: Epoch
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: float
+    pub fn angular_separation(
+        &self,
+        target1: Frame,
+        target2: Frame,
+        observer: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> Result<f64, EphemerisError> {
+        let obs_to_target1 = self.translate(target1, observer, epoch, ab_corr)?;
+        let obs_to_target2 = self.translate(target2, observer, epoch, ab_corr)?;
+
+        Ok(angle_between_deg(
+            &obs_to_target1.radius_km,
+            &obs_to_target2.radius_km,
+        ))
+    }
+
+    /// Returns the local solar time, in hours between 0 and 24, at the provided longitude on the body fixed frame.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of et2lst with the "PLANETOCENTRIC" longitude type: the local solar time is noon
+    /// at the planetocentric longitude of the Sun in the body fixed frame, and each hour corresponds to 15 degrees of longitude.
+    ///
+    /// :type body_fixed_frame: Frame
+    /// :type longitude_deg: float
+    /// :type epoch: Epoch
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: float
+    pub fn local_solar_time_hours(
+        &self,
+        body_fixed_frame: Frame,
+        longitude_deg: f64,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<f64> {
+        let sun_bf = self.transform(SUN_J2000, body_fixed_frame, epoch, ab_corr)?;
+
+        Ok(((longitude_deg - sun_bf.longitude_deg()) / 15.0 + 12.0).rem_euclid(24.0))
+    }
+
+    /// Computes the illumination angles (phase, incidence, and emission) of a point on the surface of a body, as seen from the observer.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of ilumin, where the surface point is provided as an Orbit in the body fixed frame of
+    /// the target (e.g. built with `Orbit::try_latlongalt`), and the observer may be in any frame. The surface normal is computed
+    /// from the tri-axial ellipsoid shape of the body fixed frame, which is fetched from the Almanac if not set.
+    ///
+    /// # Algorithm
+    /// 1. Compute the position of the observer and of the Sun with respect to the target in the body fixed frame of the surface point.
+    /// 2. Compute the outward surface normal of the ellipsoid at the surface point.
+    /// 3. The phase angle is the angle between the surface-to-Sun and surface-to-observer vectors.
+    /// 4. The incidence (resp. emission) angle is the angle between the surface normal and the surface-to-Sun (resp. surface-to-observer) vector.
+    ///
+    /// :type surface_point: Orbit
+    /// :type observer: Orbit
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: IlluminationAngles
+    pub fn illumination_angles(
+        &self,
+        mut surface_point: Orbit,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<IlluminationAngles> {
+        if observer.epoch != surface_point.epoch {
+            return Err(AlmanacError::Ephemeris {
+                action: "computing illumination angles",
+                source: Box::new(EphemerisError::EphemerisPhysics {
+                    action: "computing illumination angles",
+                    source: PhysicsError::EpochMismatch {
+                        action: "computing illumination angles",
+                        epoch1: surface_point.epoch,
+                        epoch2: observer.epoch,
+                    },
+                }),
+            });
+        }
+
+        surface_point.frame = self.surface_frame(surface_point.frame)?;
+
+        let shape = surface_point
+            .frame
+            .shape
+            .ok_or(PhysicsError::MissingFrameData {
+                action: "computing illumination angles",
+                data: "shape",
+                frame: surface_point.frame.into(),
+            })
+            .context(EphemerisPhysicsSnafu {
+                action: "computing illumination angles",
+            })
+            .context(EphemerisSnafu {
+                action: "computing illumination angles",
+            })?;
+
+        let body_fixed = surface_point.frame;
+        let epoch = surface_point.epoch;
+
+        let r_obs = self.transform_to(observer, body_fixed, ab_corr)?.radius_km;
+        let r_sun = self
+            .transform(SUN_J2000, body_fixed, epoch, ab_corr)?
+            .radius_km;
+
+        let normal = shape.surface_normal(&surface_point.radius_km);
+        let to_obs = r_obs - surface_point.radius_km;
+        let to_sun = r_sun - surface_point.radius_km;
+
+        Ok(IlluminationAngles {
+            epoch,
+            phase_deg: angle_between_deg(&to_sun, &to_obs),
+            incidence_deg: angle_between_deg(&normal, &to_sun),
+            emission_deg: angle_between_deg(&normal, &to_obs),
+        })
+    }
+}
+
+/// Returns the angle between both vectors in degrees, between 0 and 180 degrees.
+fn angle_between_deg(a: &Vector3, b: &Vector3) -> f64 {
+    (a.dot(b) / (a.norm() * b.norm()))
+        .clamp(-1.0, 1.0)
+        .acos()
+        .to_degrees()
 }
 
 #[cfg(test)]
@@ -180,4 +321,131 @@ mod ut_solar {
             assert!((sun_elevation_deg + 90.0 - spe_deg).abs() < 5e-2)
         }
     }
+
+    #[test]
+    fn angular_separation_matches_sun_angle() {
+        let ctx = Almanac::default()
+            .load("../data/de440s.bsp")
+            .and_then(|ctx| ctx.load("../data/gmat-hermite.bsp"))
+            .unwrap();
+
+        let epoch = Epoch::from_gregorian_hms(2000, 1, 1, 12, 0, 0, TimeScale::UTC);
+
+        let sc_id = -10000001;
+        let my_sc_j2k = Frame::from_ephem_j2000(sc_id);
+
+        let spe_deg = ctx.sun_angle_deg(EARTH, sc_id, epoch).unwrap();
+        let sep_deg = ctx
+            .angular_separation(SUN_J2000, EARTH_J2000, my_sc_j2k, epoch, None)
+            .unwrap();
+        assert!((spe_deg - sep_deg).abs() < 1e-10);
+
+        // Symmetric in the targets
+        let sep_rev_deg = ctx
+            .angular_separation(EARTH_J2000, SUN_J2000, my_sc_j2k, epoch, None)
+            .unwrap();
+        assert!((sep_deg - sep_rev_deg).abs() < 1e-10);
+
+        // Light time corrections only slightly change the separation
+        let sep_lt_deg = ctx
+            .angular_separation(SUN_J2000, EARTH_J2000, my_sc_j2k, epoch, Aberration::LT)
+            .unwrap();
+        assert!((sep_deg - sep_lt_deg).abs() < 1e-2);
+    }
+
+    #[test]
+    fn local_solar_time() {
+        let ctx = Almanac::default()
+            .load("../data/de440s.bsp")
+            .and_then(|ctx| ctx.load("../data/pck11.pca"))
+            .unwrap();
+
+        let epoch = Epoch::from_gregorian_hms(2024, 6, 21, 12, 0, 0, TimeScale::UTC);
+
+        let sub_solar = ctx.sub_solar_point(IAU_EARTH_FRAME, epoch, None).unwrap();
+        let noon_long_deg = sub_solar.longitude_deg();
+
+        for (delta_long_deg, expected_hours) in [
+            (0.0, 12.0),
+            (90.0, 18.0),
+            (-90.0, 6.0),
+            (180.0, 0.0),
+            (-45.0, 9.0),
+        ] {
+            let lst = ctx
+                .local_solar_time_hours(
+                    IAU_EARTH_FRAME,
+                    noon_long_deg + delta_long_deg,
+                    epoch,
+                    None,
+                )
+                .unwrap();
+            // Midnight may wrap to 24 hours
+            let err = (lst - expected_hours).abs() % 24.0;
+            assert!(err.min(24.0 - err) < 1e-9, "{lst} != {expected_hours}");
+            assert!((0.0..24.0).contains(&lst));
+        }
+
+        // Near Greenwich at noon UTC, the local solar time is close to noon (within the equation of time).
+        let lst = ctx
+            .local_solar_time_hours(IAU_EARTH_FRAME, 0.0, epoch, None)
+            .unwrap();
+        assert!((lst - 12.0).abs() < 0.5, "{lst}");
+    }
+
+    /// At the geodetic nadir point of a spacecraft, the observer lies along the surface normal, so the emission angle is zero
+    /// and the phase angle equals the incidence angle.
+    #[test]
+    fn illumination_at_nadir() {
+        let ctx = Almanac::default()
+            .load("../data/de440s.bsp")
+            .and_then(|ctx| ctx.load("../data/gmat-hermite.bsp"))
+            .and_then(|ctx| ctx.load("../data/pck11.pca"))
+            .unwrap();
+
+        let epoch = Epoch::from_gregorian_hms(2000, 1, 1, 12, 0, 0, TimeScale::UTC);
+
+        let my_sc_j2k = Frame::from_ephem_j2000(-10000001);
+
+        let state = ctx.transform(my_sc_j2k, EARTH_J2000, epoch, None).unwrap();
+
+        let iau_earth = ctx.frame_from_uid(IAU_EARTH_FRAME).unwrap();
+
+        for epoch in TimeSeries::inclusive(
+            epoch,
+            epoch + state.period().unwrap(),
+            0.1 * state.period().unwrap(),
+        ) {
+            let state_bf = ctx
+                .transform(my_sc_j2k, IAU_EARTH_FRAME, epoch, None)
+                .unwrap();
+
+            let nadir_surface_point = Orbit::try_latlongalt(
+                state_bf.latitude_deg().unwrap(),
+                state_bf.longitude_deg(),
+                0.0,
+                MEAN_EARTH_ANGULAR_VELOCITY_DEG_S,
+                epoch,
+                iau_earth,
+            )
+            .unwrap();
+
+            // The observer is provided in the inertial frame.
+            let observer = ctx.transform(my_sc_j2k, EARTH_J2000, epoch, None).unwrap();
+
+            let angles = ctx
+                .illumination_angles(nadir_surface_point, observer, None)
+                .unwrap();
+
+            println!("{angles}");
+
+            assert!(angles.emission_deg < 1e-5, "{angles}");
+            assert!(angles.is_visible());
+            assert!(
+                (angles.phase_deg - angles.incidence_deg).abs() < 1e-5,
+                "{angles}"
+            );
+            assert_eq!(angles.is_lit(), angles.incidence_deg < 90.0);
+        }
+    }
 }
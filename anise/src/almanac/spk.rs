@@ -17,6 +17,7 @@ use pyo3::prelude::*;
 use snafu::ensure;
 
 use crate::ephemerides::NoEphemerisLoadedSnafu;
+use crate::naif::daf::datatypes::HermiteWindow;
 use crate::naif::daf::DAFError;
 use crate::naif::daf::NAIFSummaryRecord;
 use crate::naif::spk::summary::SPKSummaryRecord;
@@ -188,17 +189,11 @@ impl Almanac {
             source: DAFError::SummaryIdError { kind: "SPK", id },
         })
     }
-}
 
-#[cfg_attr(feature = "python", pymethods)]
-impl Almanac {
     /// Returns a vector of the summaries whose ID matches the desired `id`, in the order in which they will be used, i.e. in reverse loading order.
     ///
     /// # Warning
     /// This function performs a memory allocation.
-    ///
-    /// :type id: int
-    /// :rtype: typing.List
     pub fn spk_summaries(&self, id: NaifId) -> Result<Vec<SPKSummaryRecord>, EphemerisError> {
         let mut summaries = vec![];
         for maybe_spk in self.spk_data.iter().take(self.num_loaded_spk()).rev() {
@@ -223,6 +218,19 @@ impl Almanac {
             Ok(summaries)
         }
     }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Returns a copy of this Almanac which interpolates the Hermite Type 13 SPK segments with the provided window selection policy.
+    ///
+    /// :type window: HermiteWindow
+    /// :rtype: Almanac
+    pub fn with_hermite_window(&self, window: HermiteWindow) -> Self {
+        let mut me = self.clone();
+        me.hermite_window = window;
+        me
+    }
 
     /// Returns the applicable domain of the request id, i.e. start and end epoch that the provided id has loaded data.
     ///
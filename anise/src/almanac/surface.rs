@@ -0,0 +1,425 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::f64::consts::TAU;
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use super::Almanac;
+use crate::{
+    astro::Aberration,
+    constants::frames::SUN_J2000,
+    ephemerides::EphemerisPhysicsSnafu,
+    errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu, PhysicsError},
+    frames::Frame,
+    math::Vector3,
+    prelude::Orbit,
+};
+
+#[cfg(feature = "python")]
+use numpy::{PyReadonlyArray1, PyUntypedArrayMethods};
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyTypeError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Almanac {
+    /// Computes the sub-observer point on the target body, i.e. the point on the surface of the target ellipsoid closest to the observer.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of subpnt with the "NEAR POINT/ELLIPSOID" method. The returned surface point is
+    /// expressed in the body fixed frame of the target, with a zero velocity, and its geodetic latitude and longitude are available
+    /// with `latitude_deg` and `longitude_deg`. The shape of the target frame is fetched from the Almanac if not set.
+    ///
+    /// :type target_frame: Frame
+    /// :type observer: Orbit
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: Orbit
+    pub fn sub_observer_point(
+        &self,
+        target_frame: Frame,
+        observer: Orbit,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Orbit> {
+        let target_frame = self.surface_frame(target_frame)?;
+
+        let observer_bf = self.transform_to(observer, target_frame, ab_corr)?;
+
+        Self::nadir_surface_point(observer_bf, "computing sub-observer point")
+    }
+
+    /// Computes the sub-solar point on the target body, i.e. the point on the surface of the target ellipsoid closest to the Sun.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of subslr with the "NEAR POINT/ELLIPSOID" method. The returned surface point is
+    /// expressed in the body fixed frame of the target, with a zero velocity, and its geodetic latitude and longitude are available
+    /// with `latitude_deg` and `longitude_deg`. The shape of the target frame is fetched from the Almanac if not set.
+    ///
+    /// :type target_frame: Frame
+    /// :type epoch: Epoch
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: Orbit
+    pub fn sub_solar_point(
+        &self,
+        target_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Orbit> {
+        let target_frame = self.surface_frame(target_frame)?;
+
+        let sun_bf = self.transform(SUN_J2000, target_frame, epoch, ab_corr)?;
+
+        Self::nadir_surface_point(sun_bf, "computing sub-solar point")
+    }
+
+    /// Computes the terminator of the target body, i.e. the boundary between its day and night sides, as a polygon of
+    /// `resolution` points on the surface of the target ellipsoid.
+    ///
+    /// The Sun is modeled as a point source, so each point of the terminator has an incidence angle of 90 degrees: the
+    /// terminator is the limb of the target as seen from the Sun (cf. `limb_as_seen_from`). The points are expressed in
+    /// the body fixed frame of the target, with a zero velocity, and the shape of the target frame is fetched from the
+    /// Almanac if not set.
+    ///
+    /// :type target_frame: Frame
+    /// :type epoch: Epoch
+    /// :type resolution: int
+    /// :rtype: typing.List
+    pub fn terminator(
+        &self,
+        target_frame: Frame,
+        epoch: Epoch,
+        resolution: usize,
+    ) -> AlmanacResult<Vec<Orbit>> {
+        self.limb_as_seen_from(SUN_J2000, target_frame, epoch, resolution)
+    }
+
+    /// Computes the limb of the target body as seen from the observer, i.e. the points of the surface of the target
+    /// ellipsoid where the line of sight from the observer is tangent to that surface, as a polygon of `resolution` points.
+    ///
+    /// The points are ordered counterclockwise as seen from the observer, and expressed in the body fixed frame of the
+    /// target with a zero velocity. The shape of the target frame is fetched from the Almanac if not set.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of edlimb, sampled on the limb ellipse.
+    ///
+    /// :type observer: Frame
+    /// :type target_frame: Frame
+    /// :type epoch: Epoch
+    /// :type resolution: int
+    /// :rtype: typing.List
+    pub fn limb_as_seen_from(
+        &self,
+        observer: Frame,
+        target_frame: Frame,
+        epoch: Epoch,
+        resolution: usize,
+    ) -> AlmanacResult<Vec<Orbit>> {
+        let action = "computing limb";
+        let target_frame = self.surface_frame(target_frame)?;
+
+        let shape = target_frame
+            .shape
+            .ok_or(PhysicsError::MissingFrameData {
+                action,
+                data: "shape",
+                frame: target_frame.into(),
+            })
+            .context(EphemerisPhysicsSnafu {
+                action: "fetching shape of target",
+            })
+            .context(EphemerisSnafu { action })?;
+
+        let observer_km = self
+            .transform(observer, target_frame, epoch, None)?
+            .radius_km;
+
+        // Scale the problem such that the ellipsoid is the unit sphere, where the limb is a small circle
+        let radii = Vector3::new(
+            shape.semi_major_equatorial_radius_km,
+            shape.semi_minor_equatorial_radius_km,
+            shape.polar_radius_km,
+        );
+        let v = observer_km.component_div(&radii);
+        if v.norm_squared() <= 1.0 {
+            return Err(AlmanacError::GenericError {
+                err: format!("observer {observer:e} is inside the shape of {target_frame:e}"),
+            });
+        }
+
+        let v_hat = v.normalize();
+        let center = v / v.norm_squared();
+        let radius = (1.0 - 1.0 / v.norm_squared()).sqrt();
+        let e1 = if v_hat.cross(&Vector3::z()).norm() > 1e-6 {
+            v_hat.cross(&Vector3::z()).normalize()
+        } else {
+            v_hat.cross(&Vector3::x()).normalize()
+        };
+        let e2 = v_hat.cross(&e1);
+
+        Ok((0..resolution)
+            .map(|i| {
+                let (sin_theta, cos_theta) = (TAU * i as f64 / resolution as f64).sin_cos();
+                let point_km =
+                    (center + radius * (cos_theta * e1 + sin_theta * e2)).component_mul(&radii);
+                Orbit::from_position(point_km.x, point_km.y, point_km.z, epoch, target_frame)
+            })
+            .collect())
+    }
+}
+
+impl Almanac {
+    /// Computes the intercept of a ray with the surface of the target body, e.g. the intercept of the boresight of an instrument.
+    ///
+    /// The ray starts at the position of the `ray_origin` state and points along the `direction`, expressed in the frame of that state.
+    /// Returns the first intersection point of that ray with the ellipsoid of the target, as an Orbit in the body fixed frame of the
+    /// target with a zero velocity, or None if the ray misses the target.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of sincpt with the "ELLIPSOID" method. The aberration correction, if any, is applied
+    /// to the position of the target as seen from the ray origin.
+    pub fn surface_intercept(
+        &self,
+        ray_origin: Orbit,
+        direction: Vector3,
+        target_frame: Frame,
+        ab_corr: Option<Aberration>,
+    ) -> AlmanacResult<Option<Orbit>> {
+        let target_frame = self.surface_frame(target_frame)?;
+
+        let shape = target_frame
+            .shape
+            .ok_or(PhysicsError::MissingFrameData {
+                action: "computing surface intercept",
+                data: "shape",
+                frame: target_frame.into(),
+            })
+            .context(EphemerisPhysicsSnafu {
+                action: "fetching shape of target",
+            })
+            .context(EphemerisSnafu {
+                action: "computing surface intercept",
+            })?;
+
+        let origin_km = self
+            .transform_to(ray_origin, target_frame, ab_corr)?
+            .radius_km;
+
+        let to_target = self
+            .rotate(ray_origin.frame, target_frame, ray_origin.epoch)
+            .context(OrientationSnafu {
+                action: "computing surface intercept",
+            })?;
+
+        Ok(shape
+            .ray_intersection(&origin_km, &(to_target.rot_mat * direction))
+            .map(|point_km| {
+                Orbit::from_position(
+                    point_km.x,
+                    point_km.y,
+                    point_km.z,
+                    ray_origin.epoch,
+                    target_frame,
+                )
+            }))
+    }
+
+    /// Returns the provided frame with its shape, fetching the frame data from the Almanac if needed.
+    pub(crate) fn surface_frame(&self, frame: Frame) -> AlmanacResult<Frame> {
+        if frame.shape.is_some() {
+            Ok(frame)
+        } else {
+            self.frame_from_uid(frame)
+                .map_err(|e| AlmanacError::GenericError {
+                    err: format!("{e} when fetching {frame:e} frame data"),
+                })
+        }
+    }
+
+    /// Returns the point on the surface of the ellipsoid directly below the provided body fixed state.
+    fn nadir_surface_point(state_bf: Orbit, action: &'static str) -> AlmanacResult<Orbit> {
+        let (lat_deg, long_deg, _) = state_bf
+            .latlongalt()
+            .context(EphemerisPhysicsSnafu { action })
+            .context(EphemerisSnafu { action })?;
+
+        Orbit::try_latlongalt(lat_deg, long_deg, 0.0, 0.0, state_bf.epoch, state_bf.frame)
+            .context(EphemerisPhysicsSnafu { action })
+            .context(EphemerisSnafu { action })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Almanac {
+    /// Computes the intercept of a ray with the surface of the target body, e.g. the intercept of the boresight of an instrument.
+    ///
+    /// The ray starts at the position of the `ray_origin` state and points along the `direction`, expressed in the frame of that state.
+    /// Returns the first intersection point of that ray with the ellipsoid of the target, as an Orbit in the body fixed frame of the
+    /// target with a zero velocity, or None if the ray misses the target.
+    ///
+    /// # SPICE Compatibility
+    /// This function is the SPICE equivalent of sincpt with the "ELLIPSOID" method. The aberration correction, if any, is applied
+    /// to the position of the target as seen from the ray origin.
+    ///
+    /// :type ray_origin: Orbit
+    /// :type direction: numpy.array
+    /// :type target_frame: Frame
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: Orbit
+    #[pyo3(name = "surface_intercept", signature=(ray_origin, direction, target_frame, ab_corr=None))]
+    fn py_surface_intercept<'py>(
+        &self,
+        ray_origin: Orbit,
+        direction: PyReadonlyArray1<'py, f64>,
+        target_frame: Frame,
+        ab_corr: Option<Aberration>,
+    ) -> PyResult<Option<Orbit>> {
+        if direction.shape() != [3] {
+            return Err(PyErr::new::<PyTypeError, _>(
+                "direction must be a vector of size 3",
+            ));
+        }
+
+        let direction = Vector3::from_iterator(direction.as_array().iter().copied());
+
+        Ok(self.surface_intercept(ray_origin, direction, target_frame, ab_corr)?)
+    }
+}
+
+#[cfg(test)]
+mod ut_surface {
+    use crate::constants::frames::{EARTH_J2000, IAU_EARTH_FRAME, MOON_J2000};
+    use crate::prelude::*;
+
+    #[test]
+    fn sub_observer_and_solar_points() {
+        let ctx = Almanac::default()
+            .load("../data/de440s.bsp")
+            .and_then(|ctx| ctx.load("../data/gmat-hermite.bsp"))
+            .and_then(|ctx| ctx.load("../data/pck11.pca"))
+            .unwrap();
+
+        let epoch = Epoch::from_gregorian_hms(2000, 1, 1, 12, 0, 0, TimeScale::UTC);
+
+        let my_sc_j2k = Frame::from_ephem_j2000(-10000001);
+
+        let state = ctx.transform(my_sc_j2k, EARTH_J2000, epoch, None).unwrap();
+
+        for epoch in TimeSeries::inclusive(
+            epoch,
+            epoch + state.period().unwrap(),
+            0.1 * state.period().unwrap(),
+        ) {
+            let observer = ctx.transform(my_sc_j2k, EARTH_J2000, epoch, None).unwrap();
+            let observer_bf = ctx
+                .transform(my_sc_j2k, IAU_EARTH_FRAME, epoch, None)
+                .unwrap();
+
+            // The frame data is fetched from the almanac.
+            let sub_obs = ctx
+                .sub_observer_point(IAU_EARTH_FRAME, observer, None)
+                .unwrap();
+
+            assert!(sub_obs.height_km().unwrap().abs() < 1e-6);
+            assert!(
+                (sub_obs.latitude_deg().unwrap() - observer_bf.latitude_deg().unwrap()).abs()
+                    < 1e-9
+            );
+            assert!((sub_obs.longitude_deg() - observer_bf.longitude_deg()).abs() < 1e-9);
+
+            // The observer is along the surface normal of the sub-observer point.
+            let angles = ctx.illumination_angles(sub_obs, observer, None).unwrap();
+            assert!(angles.emission_deg < 1e-5, "{angles}");
+
+            // The Sun is along the surface normal of the sub-solar point.
+            let sub_solar = ctx.sub_solar_point(IAU_EARTH_FRAME, epoch, None).unwrap();
+            assert!(sub_solar.height_km().unwrap().abs() < 1e-6);
+            let angles = ctx.illumination_angles(sub_solar, observer, None).unwrap();
+            assert!(angles.incidence_deg < 1e-5, "{angles}");
+        }
+    }
+
+    #[test]
+    fn boresight_intercept() {
+        let ctx = Almanac::default()
+            .load("../data/de440s.bsp")
+            .and_then(|ctx| ctx.load("../data/pck08.pca"))
+            .unwrap();
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+
+        let sc = Orbit::from_position(7000.0, 1000.0, 2000.0, epoch, EARTH_J2000);
+
+        // Pointing towards the center of the Earth intercepts the surface below the spacecraft.
+        let intercept = ctx
+            .surface_intercept(sc, -sc.radius_km, IAU_EARTH_FRAME, None)
+            .unwrap()
+            .unwrap();
+
+        let sc_bf = ctx.transform_to(sc, IAU_EARTH_FRAME, None).unwrap();
+        assert!((intercept.radius_km.normalize() - sc_bf.radius_km.normalize()).norm() < 1e-12);
+        assert!(intercept.height_km().unwrap().abs() < 1e-6);
+        assert!((intercept.radius_km - sc_bf.radius_km).norm() > sc_bf.height_km().unwrap());
+
+        // Pointing away from the Earth misses it.
+        assert!(ctx
+            .surface_intercept(sc, sc.radius_km, IAU_EARTH_FRAME, None)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn terminator_and_limb() {
+        let ctx = Almanac::default()
+            .load("../data/de440s.bsp")
+            .and_then(|ctx| ctx.load("../data/pck08.pca"))
+            .unwrap();
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+        let moon = ctx
+            .transform(MOON_J2000, IAU_EARTH_FRAME, epoch, None)
+            .unwrap();
+
+        // The Sun grazes each point of the terminator
+        let terminator = ctx.terminator(IAU_EARTH_FRAME, epoch, 36).unwrap();
+        assert_eq!(terminator.len(), 36);
+        let sub_solar = ctx.sub_solar_point(IAU_EARTH_FRAME, epoch, None).unwrap();
+        for point in &terminator {
+            assert!(point.height_km().unwrap().abs() < 1e-6);
+            let angles = ctx.illumination_angles(*point, moon, None).unwrap();
+            assert!((angles.incidence_deg - 90.0).abs() < 1e-6, "{angles}");
+            let separation_deg = point.r_hat().angle(&sub_solar.r_hat()).to_degrees();
+            assert!((separation_deg - 90.0).abs() < 0.5, "{separation_deg}");
+        }
+
+        // The line of sight from the Moon is tangent to the surface at each point of the limb
+        let limb = ctx
+            .limb_as_seen_from(MOON_J2000, IAU_EARTH_FRAME, epoch, 12)
+            .unwrap();
+        assert_eq!(limb.len(), 12);
+        for point in &limb {
+            assert!(point.height_km().unwrap().abs() < 1e-6);
+            let angles = ctx.illumination_angles(*point, moon, None).unwrap();
+            assert!((angles.emission_deg - 90.0).abs() < 1e-6, "{angles}");
+        }
+
+        // Counterclockwise as seen from the Moon
+        let normal =
+            (limb[1].radius_km - limb[0].radius_km).cross(&(limb[2].radius_km - limb[1].radius_km));
+        assert!(normal.dot(&moon.radius_km) > 0.0);
+
+        // The observer must be outside of the target
+        assert!(ctx
+            .limb_as_seen_from(EARTH_J2000, IAU_EARTH_FRAME, epoch, 12)
+            .is_err());
+    }
+}
@@ -0,0 +1,326 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch, Unit};
+use snafu::prelude::*;
+
+use super::{
+    AlmanacAnalysisSnafu, AnalysisResult, InvalidSetupSnafu, PhysicsAnalysisSnafu,
+    PlanetaryDataAnalysisSnafu,
+};
+use crate::almanac::Almanac;
+use crate::constants::frames::SUN_J2000;
+use crate::errors::PhysicsResult;
+use crate::math::Vector3;
+use crate::prelude::{Frame, Orbit};
+use crate::NaifId;
+
+/// Orbit whose beta angle is analyzed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BetaAngleOrbit {
+    /// Trajectory of the spacecraft in the loaded ephemerides, around the provided central body
+    Ephemeris {
+        spacecraft_id: NaifId,
+        center_id: NaifId,
+    },
+    /// Mean orbit in an inertial frame, whose ascending node precesses under the provided J2 of its central body
+    /// (e.g. a sun-synchronous orbit), useful to analyze seasons over months or years before any trajectory exists
+    MeanJ2 { orbit: Orbit, j2: f64 },
+}
+
+/// Beta angle history of an orbit, and its eclipse-free seasons.
+///
+/// An orbit has no eclipse when the absolute value of its beta angle is greater than the critical beta angle, i.e. the
+/// arcsine of the ratio of the mean equatorial radius of the central body to the semi major axis of the orbit. This
+/// cylindrical shadow model assumes a near circular orbit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetaAngleReport {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    /// Epoch, beta angle, and critical beta angle at each sample, in degrees
+    pub history: Vec<(Epoch, f64, f64)>,
+    /// Start and end of each eclipse-free season, where the boundaries between samples are refined to one second
+    pub eclipse_free_seasons: Vec<(Epoch, Epoch)>,
+    /// Digest of the data loaded in the Almanac used for this analysis (cf. `Almanac::context_digest`)
+    pub context_digest: u32,
+}
+
+impl BetaAngleReport {
+    /// Returns the smallest and largest beta angles over all samples, in degrees.
+    pub fn min_max_beta_deg(&self) -> (f64, f64) {
+        self.history
+            .iter()
+            .fold((f64::NAN, f64::NAN), |(min, max), (_, beta_deg, _)| {
+                (min.min(*beta_deg), max.max(*beta_deg))
+            })
+    }
+
+    /// Returns the start and end of each eclipse season, i.e. the complement of the eclipse-free seasons over the analysis span.
+    pub fn eclipse_seasons(&self) -> Vec<(Epoch, Epoch)> {
+        let mut seasons = Vec::new();
+        let mut start = self.start_epoch;
+        for (free_start, free_end) in &self.eclipse_free_seasons {
+            if *free_start > start {
+                seasons.push((start, *free_start));
+            }
+            start = *free_end;
+        }
+        if start < self.end_epoch {
+            seasons.push((start, self.end_epoch));
+        }
+        seasons
+    }
+
+    /// Returns the total duration of the eclipse-free seasons.
+    pub fn total_eclipse_free(&self) -> Duration {
+        self.eclipse_free_seasons
+            .iter()
+            .fold(Duration::ZERO, |acc, (start, end)| acc + (*end - *start))
+    }
+}
+
+impl fmt::Display for BetaAngleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min_beta_deg, max_beta_deg) = self.min_max_beta_deg();
+        writeln!(
+            f,
+            "Beta angle from {} to {} ({} samples): {min_beta_deg:.3} deg to {max_beta_deg:.3} deg",
+            self.start_epoch,
+            self.end_epoch,
+            self.history.len()
+        )?;
+        writeln!(
+            f,
+            "  {} eclipse-free seasons, total {}",
+            self.eclipse_free_seasons.len(),
+            self.total_eclipse_free()
+        )?;
+        for (start, end) in &self.eclipse_free_seasons {
+            writeln!(
+                f,
+                "  eclipse-free from {start} to {end} ({})",
+                *end - *start
+            )?;
+        }
+        write!(f, "  Almanac context digest: 0x{:08x}", self.context_digest)
+    }
+}
+
+impl Almanac {
+    /// Returns the beta angle of the orbit at the provided epoch, in degrees, i.e. the angle between the orbital plane and
+    /// the direction of the Sun from the central body, positive when the Sun is on the side of the orbit normal.
+    pub fn beta_angle_deg(&self, orbit: &BetaAngleOrbit, epoch: Epoch) -> AnalysisResult<f64> {
+        self.beta_angle_sample(orbit, epoch)
+            .map(|(beta_deg, _)| beta_deg)
+    }
+
+    /// Computes the beta angle of the orbit at each of the provided epochs (e.g. daily over several months) and returns its
+    /// eclipse-free seasons as arcs, cf. `BetaAngleReport` for the shadow model.
+    pub fn beta_angle_seasons(
+        &self,
+        orbit: &BetaAngleOrbit,
+        epochs: &[Epoch],
+    ) -> AnalysisResult<BetaAngleReport> {
+        ensure!(
+            epochs.len() > 1,
+            InvalidSetupSnafu {
+                err: "beta angle seasons require at least two epochs".to_string()
+            }
+        );
+
+        let history = epochs
+            .iter()
+            .map(|epoch| {
+                self.beta_angle_sample(orbit, *epoch)
+                    .map(|(beta_deg, critical_deg)| (*epoch, beta_deg, critical_deg))
+            })
+            .collect::<AnalysisResult<Vec<(Epoch, f64, f64)>>>()?;
+
+        let eclipse_free = history
+            .iter()
+            .map(|(_, beta_deg, critical_deg)| beta_deg.abs() > *critical_deg)
+            .collect::<Vec<bool>>();
+
+        let mut eclipse_free_seasons = Vec::new();
+        let mut season_start = None;
+        for (idx, free) in eclipse_free.into_iter().enumerate() {
+            match (free, season_start) {
+                (true, None) => {
+                    season_start = Some(if idx == 0 {
+                        epochs[0]
+                    } else {
+                        self.beta_angle_boundary(orbit, epochs[idx - 1], epochs[idx])?
+                    });
+                }
+                (false, Some(start)) => {
+                    let end = self.beta_angle_boundary(orbit, epochs[idx - 1], epochs[idx])?;
+                    eclipse_free_seasons.push((start, end));
+                    season_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = season_start {
+            eclipse_free_seasons.push((start, epochs[epochs.len() - 1]));
+        }
+
+        Ok(BetaAngleReport {
+            start_epoch: epochs[0],
+            end_epoch: epochs[epochs.len() - 1],
+            history,
+            eclipse_free_seasons,
+            context_digest: self.context_digest(),
+        })
+    }
+
+    /// Returns the beta angle and the critical beta angle of the orbit at the provided epoch, in degrees.
+    fn beta_angle_sample(
+        &self,
+        orbit: &BetaAngleOrbit,
+        epoch: Epoch,
+    ) -> AnalysisResult<(f64, f64)> {
+        let action = "computing beta angle";
+
+        let (center, orbit_normal, sma_km) = match orbit {
+            BetaAngleOrbit::Ephemeris {
+                spacecraft_id,
+                center_id,
+            } => {
+                let center = self
+                    .frame_from_uid(Frame::from_ephem_j2000(*center_id))
+                    .context(PlanetaryDataAnalysisSnafu { action })?;
+                let state = self
+                    .transform(Frame::from_ephem_j2000(*spacecraft_id), center, epoch, None)
+                    .context(AlmanacAnalysisSnafu { action })?;
+                (
+                    center,
+                    state.hvec().context(PhysicsAnalysisSnafu { action })?,
+                    state.sma_km().context(PhysicsAnalysisSnafu { action })?,
+                )
+            }
+            BetaAngleOrbit::MeanJ2 { orbit, j2 } => {
+                let center = self
+                    .frame_from_uid(orbit.frame)
+                    .context(PlanetaryDataAnalysisSnafu { action })?;
+                let orbit = Orbit {
+                    frame: center,
+                    ..*orbit
+                };
+
+                let sma_km = orbit.sma_km().context(PhysicsAnalysisSnafu { action })?;
+                let inc_rad = orbit
+                    .inc_deg()
+                    .context(PhysicsAnalysisSnafu { action })?
+                    .to_radians();
+                let raan_rate_rad_s =
+                    j2_raan_rate_rad_s(&orbit, *j2).context(PhysicsAnalysisSnafu { action })?;
+                let raan_rad = orbit
+                    .raan_deg()
+                    .context(PhysicsAnalysisSnafu { action })?
+                    .to_radians()
+                    + raan_rate_rad_s * (epoch - orbit.epoch).to_seconds();
+
+                let (sin_inc, cos_inc) = inc_rad.sin_cos();
+                let (sin_raan, cos_raan) = raan_rad.sin_cos();
+                (
+                    center,
+                    Vector3::new(sin_inc * sin_raan, -sin_inc * cos_raan, cos_inc),
+                    sma_km,
+                )
+            }
+        };
+
+        let sun = self
+            .translate(SUN_J2000, center, epoch, None)
+            .context(AlmanacAnalysisSnafu { action })?;
+
+        let beta_deg = 90.0 - orbit_normal.angle(&sun.radius_km).to_degrees();
+
+        let eq_radius_km = center
+            .mean_equatorial_radius_km()
+            .context(PhysicsAnalysisSnafu { action })?;
+        let critical_deg = (eq_radius_km / sma_km).min(1.0).asin().to_degrees();
+
+        Ok((beta_deg, critical_deg))
+    }
+
+    /// Returns the epoch between both epochs where the orbit enters or exits an eclipse-free season, to within one second.
+    fn beta_angle_boundary(
+        &self,
+        orbit: &BetaAngleOrbit,
+        mut before: Epoch,
+        mut after: Epoch,
+    ) -> AnalysisResult<Epoch> {
+        let is_free = |epoch| {
+            self.beta_angle_sample(orbit, epoch)
+                .map(|(beta_deg, critical_deg)| beta_deg.abs() > critical_deg)
+        };
+
+        let free_before = is_free(before)?;
+        while after - before > Unit::Second * 1 {
+            let mid = before + (after - before) / 2;
+            if is_free(mid)? == free_before {
+                before = mid;
+            } else {
+                after = mid;
+            }
+        }
+        Ok(after)
+    }
+}
+
+/// Returns the secular rate of the right ascension of the ascending node of the orbit due to the J2 of its central body.
+fn j2_raan_rate_rad_s(orbit: &Orbit, j2: f64) -> PhysicsResult<f64> {
+    let sma_km = orbit.sma_km()?;
+    let semi_parameter_km = orbit.semi_parameter_km()?;
+    let mean_motion_rad_s = (orbit.frame.mu_km3_s2()? / sma_km.powi(3)).sqrt();
+    let eq_radius_km = orbit.frame.mean_equatorial_radius_km()?;
+    Ok(-1.5
+        * mean_motion_rad_s
+        * j2
+        * (eq_radius_km / semi_parameter_km).powi(2)
+        * orbit.inc_deg()?.to_radians().cos())
+}
+
+#[cfg(test)]
+mod ut_beta_angle {
+    use super::BetaAngleReport;
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn eclipse_seasons() {
+        let start = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let day = |days: i64| start + Unit::Day * days;
+
+        let mut report = BetaAngleReport {
+            start_epoch: start,
+            end_epoch: day(100),
+            history: vec![
+                (start, 10.0, 60.0),
+                (day(50), 70.0, 60.0),
+                (day(100), -20.0, 60.0),
+            ],
+            eclipse_free_seasons: vec![(day(10), day(20)), (day(60), day(100))],
+            context_digest: 0,
+        };
+        println!("{report}");
+        assert_eq!(report.min_max_beta_deg(), (-20.0, 70.0));
+        assert_eq!(report.total_eclipse_free(), Unit::Day * 50);
+        assert_eq!(
+            report.eclipse_seasons(),
+            vec![(start, day(10)), (day(20), day(60))]
+        );
+
+        report.eclipse_free_seasons.clear();
+        assert_eq!(report.eclipse_seasons(), vec![(start, day(100))]);
+    }
+}
@@ -0,0 +1,465 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use std::collections::HashMap;
+
+use hifitime::{Duration, Epoch};
+use snafu::prelude::*;
+
+use super::coverage::{CoverageAsset, CoverageAttitude, CoverageReport};
+use super::dop::DopTransmitters;
+use super::{AlmanacAnalysisSnafu, AnalysisError, AnalysisResult, InvalidSetupSnafu};
+use crate::almanac::Almanac;
+use crate::astro::gnss::GnssEphemerides;
+use crate::errors::{AlmanacError, EphemerisSnafu, OrientationSnafu};
+use crate::instrument::Instrument;
+use crate::math::rotation::DCM;
+use crate::orientations::OrientationPhysicsSnafu;
+use crate::prelude::{Frame, Orbit};
+use crate::structure::location::Location;
+use crate::NaifId;
+
+/// Members of a constellation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstellationMembers {
+    /// Ephemeris IDs of the members, whose trajectories are in the loaded ephemerides
+    Ephemerides(Vec<NaifId>),
+    /// GNSS satellites from broadcast ephemerides or almanacs, identified by their PRN, where only the healthy satellites are used
+    Gnss(GnssEphemerides),
+}
+
+/// A group of spacecraft analyzed together, e.g. all of the planes of a LEO constellation or a GNSS constellation.
+///
+/// The bulk computations of the Almanac on a constellation share the frame data common to all members at each epoch: the
+/// state of the center of their ephemerides and the rotation into the analysis frame are only computed once per epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constellation {
+    pub name: String,
+    pub members: ConstellationMembers,
+}
+
+impl Constellation {
+    /// Initializes a new constellation of the provided ephemeris IDs.
+    pub fn from_ephemerides(name: &str, ids: &[NaifId]) -> Self {
+        Self {
+            name: name.to_string(),
+            members: ConstellationMembers::Ephemerides(ids.to_vec()),
+        }
+    }
+
+    /// Initializes a new constellation of the satellites of these GNSS ephemerides.
+    pub fn from_gnss(name: &str, gnss: GnssEphemerides) -> Self {
+        Self {
+            name: name.to_string(),
+            members: ConstellationMembers::Gnss(gnss),
+        }
+    }
+
+    /// Returns the identifiers of the members: their ephemeris IDs, or their PRNs for GNSS constellations.
+    pub fn ids(&self) -> Vec<NaifId> {
+        match &self.members {
+            ConstellationMembers::Ephemerides(ids) => ids.clone(),
+            ConstellationMembers::Gnss(gnss) => gnss.prns().into_iter().map(NaifId::from).collect(),
+        }
+    }
+
+    /// Returns the number of members of this constellation.
+    pub fn len(&self) -> usize {
+        self.ids().len()
+    }
+
+    /// Returns whether this constellation has no member.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a> From<&'a Constellation> for DopTransmitters<'a> {
+    fn from(constellation: &'a Constellation) -> Self {
+        match &constellation.members {
+            ConstellationMembers::Ephemerides(ids) => Self::Ephemerides(ids),
+            ConstellationMembers::Gnss(gnss) => Self::Gnss(gnss),
+        }
+    }
+}
+
+/// Access intervals of one member of a constellation seen from a location.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemberAccess {
+    /// Ephemeris ID, or PRN for GNSS constellations
+    pub id: NaifId,
+    /// Start and end of each interval of consecutive samples where this member is visible
+    pub accesses: Vec<(Epoch, Epoch)>,
+    /// Highest elevation of this member when visible, in degrees, or NaN if never visible
+    pub max_elevation_deg: f64,
+}
+
+impl MemberAccess {
+    /// Returns the sum of the durations of the access intervals.
+    pub fn total_access(&self) -> Duration {
+        self.accesses
+            .iter()
+            .fold(Duration::ZERO, |acc, (start, end)| acc + (*end - *start))
+    }
+}
+
+/// Visibility report of the members of a constellation seen from a location.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstellationReport {
+    pub name: String,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub num_samples: usize,
+    /// Number of visible members at each sample
+    pub num_in_view: Vec<usize>,
+    /// Intervals of consecutive samples where at least one member is visible
+    pub any_in_view: Vec<(Epoch, Epoch)>,
+    /// Access intervals of each member, in the order of the members of the constellation
+    pub members: Vec<MemberAccess>,
+    /// Digest of the data loaded in the Almanac used for this analysis (cf. `Almanac::context_digest`)
+    pub context_digest: u32,
+}
+
+impl ConstellationReport {
+    /// Returns the percentage of samples where at least one member is visible.
+    pub fn percent_any_in_view(&self) -> f64 {
+        if self.num_samples == 0 {
+            return 0.0;
+        }
+        let in_view = self.num_in_view.iter().filter(|count| **count > 0).count();
+        100.0 * in_view as f64 / self.num_samples as f64
+    }
+
+    /// Returns the smallest and largest number of visible members over all samples.
+    pub fn min_max_in_view(&self) -> (usize, usize) {
+        (
+            self.num_in_view.iter().min().copied().unwrap_or_default(),
+            self.num_in_view.iter().max().copied().unwrap_or_default(),
+        )
+    }
+
+    /// Returns the longest duration without any visible member between two intervals where at least one member is visible.
+    pub fn max_gap(&self) -> Option<Duration> {
+        self.any_in_view
+            .windows(2)
+            .map(|pair| pair[1].0 - pair[0].1)
+            .max()
+    }
+}
+
+impl fmt::Display for ConstellationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min_in_view, max_in_view) = self.min_max_in_view();
+        writeln!(
+            f,
+            "Visibility of {} ({} members) from {} to {} ({} samples)",
+            self.name,
+            self.members.len(),
+            self.start_epoch,
+            self.end_epoch,
+            self.num_samples
+        )?;
+        writeln!(
+            f,
+            "  any in view: {:.2} % of samples, {min_in_view} to {max_in_view} members in view",
+            self.percent_any_in_view()
+        )?;
+        match self.max_gap() {
+            Some(gap) => writeln!(f, "  max gap: {gap}")?,
+            None => writeln!(f, "  max gap: N/A")?,
+        }
+        for member in &self.members {
+            writeln!(
+                f,
+                "  #{}: {} accesses, total {}, max elevation {:.3} deg",
+                member.id,
+                member.accesses.len(),
+                member.total_access(),
+                member.max_elevation_deg
+            )?;
+        }
+        write!(f, "  Almanac context digest: 0x{:08x}", self.context_digest)
+    }
+}
+
+/// Frame data shared by the members of a constellation at one epoch: the state of the center of each of their ephemerides,
+/// and the rotation from the J2000 orientation of their ephemerides, both into the analysis frame.
+struct SharedFramePaths {
+    epoch: Epoch,
+    frame: Frame,
+    rotation: Option<DCM>,
+    centers: HashMap<NaifId, Orbit>,
+}
+
+impl SharedFramePaths {
+    fn new(frame: Frame, epoch: Epoch) -> Self {
+        Self {
+            epoch,
+            frame,
+            rotation: None,
+            centers: HashMap::new(),
+        }
+    }
+
+    /// Returns the state of the member in the analysis frame, only computing its translation to the center of its ephemeris.
+    fn state(&mut self, almanac: &Almanac, id: NaifId) -> Result<Orbit, AlmanacError> {
+        let (radius_km, velocity_km_s, center) = almanac
+            .translation_parts_to_parent(Frame::from_ephem_j2000(id), self.epoch)
+            .context(EphemerisSnafu {
+                action: "computing constellation member state",
+            })?;
+
+        let center_state = match self.centers.get(&center.ephemeris_id) {
+            Some(state) => *state,
+            None => {
+                let state = almanac.transform(center, self.frame, self.epoch, None)?;
+                self.centers.insert(center.ephemeris_id, state);
+                state
+            }
+        };
+
+        let rotation = match self.rotation {
+            Some(dcm) => dcm,
+            None => {
+                let dcm =
+                    almanac
+                        .rotate(center, self.frame, self.epoch)
+                        .context(OrientationSnafu {
+                            action: "rotating constellation member state",
+                        })?;
+                self.rotation = Some(dcm);
+                dcm
+            }
+        };
+
+        let relative = (rotation
+            * Orbit {
+                radius_km,
+                velocity_km_s,
+                epoch: self.epoch,
+                frame: center,
+            })
+        .context(OrientationPhysicsSnafu {})
+        .context(OrientationSnafu {
+            action: "rotating constellation member state",
+        })?;
+
+        Ok(Orbit {
+            radius_km: center_state.radius_km + relative.radius_km,
+            velocity_km_s: center_state.velocity_km_s + relative.velocity_km_s,
+            epoch: self.epoch,
+            frame: self.frame,
+        })
+    }
+}
+
+/// Returns the intervals of consecutive epochs where the flag is set.
+pub(super) fn intervals(
+    epochs: &[Epoch],
+    flags: impl Iterator<Item = bool>,
+) -> Vec<(Epoch, Epoch)> {
+    let mut intervals: Vec<(Epoch, Epoch)> = Vec::new();
+    let mut in_interval = false;
+    for (epoch, flag) in epochs.iter().zip(flags) {
+        match (flag, in_interval) {
+            (true, false) => intervals.push((*epoch, *epoch)),
+            (true, true) => intervals.last_mut().unwrap().1 = *epoch,
+            _ => {}
+        }
+        in_interval = flag;
+    }
+    intervals
+}
+
+impl Almanac {
+    /// Returns the geometric state of each member of the constellation in the provided frame at the provided epoch, along
+    /// with the ID of that member (its PRN for GNSS constellations, of which only the healthy satellites are returned).
+    ///
+    /// The state of the centers of the ephemerides of the members and the rotation into the provided frame are computed once
+    /// and shared by all of the members, which all must be in the J2000 orientation.
+    pub fn constellation_states(
+        &self,
+        constellation: &Constellation,
+        frame: Frame,
+        epoch: Epoch,
+    ) -> AnalysisResult<Vec<(NaifId, Orbit)>> {
+        match &constellation.members {
+            ConstellationMembers::Ephemerides(ids) => {
+                let mut shared = SharedFramePaths::new(frame, epoch);
+                ids.iter()
+                    .map(|id| {
+                        shared.state(self, *id).map(|state| (*id, state)).context(
+                            AlmanacAnalysisSnafu {
+                                action: "computing constellation states",
+                            },
+                        )
+                    })
+                    .collect()
+            }
+            ConstellationMembers::Gnss(gnss) => gnss
+                .states(epoch)
+                .into_iter()
+                .map(|(prn, state)| {
+                    let state = if state.frame == frame {
+                        state
+                    } else {
+                        self.transform_to(state, frame, None)
+                            .context(AlmanacAnalysisSnafu {
+                                action: "computing constellation states",
+                            })?
+                    };
+                    Ok((NaifId::from(prn), state))
+                })
+                .collect(),
+        }
+    }
+
+    /// Computes the visibility of the members of the constellation from the provided location at each of the provided epochs,
+    /// accounting for the terrain mask and the minimum antenna elevation of the location.
+    ///
+    /// The states of the members are computed directly in the body fixed frame of the location (cf. `constellation_states`),
+    /// which must be loaded in this Almanac.
+    pub fn constellation_visibility(
+        &self,
+        constellation: &Constellation,
+        location: &Location,
+        epochs: &[Epoch],
+    ) -> AnalysisResult<ConstellationReport> {
+        ensure!(
+            !constellation.is_empty() && !epochs.is_empty(),
+            InvalidSetupSnafu {
+                err: "constellation visibility requires at least one member and one epoch"
+                    .to_string()
+            }
+        );
+
+        let ids = constellation.ids();
+        let frame = Frame::from(location.frame);
+
+        // Elevation of each member at each epoch, NaN if not visible
+        let mut elevations = Vec::with_capacity(epochs.len());
+        for epoch in epochs {
+            let mut at_epoch = vec![f64::NAN; ids.len()];
+            for (id, state) in self.constellation_states(constellation, frame, *epoch)? {
+                let aer = self
+                    .azimuth_elevation_range_sez_from_location(state, location, None, None)
+                    .context(AlmanacAnalysisSnafu {
+                        action: "computing constellation visibility",
+                    })?;
+                if location.is_visible(&aer) {
+                    if let Some(idx) = ids.iter().position(|member| *member == id) {
+                        at_epoch[idx] = aer.elevation_deg;
+                    }
+                }
+            }
+            elevations.push(at_epoch);
+        }
+
+        let num_in_view = elevations
+            .iter()
+            .map(|at_epoch| at_epoch.iter().filter(|el| el.is_finite()).count())
+            .collect::<Vec<usize>>();
+
+        let members = ids
+            .iter()
+            .enumerate()
+            .map(|(idx, id)| MemberAccess {
+                id: *id,
+                accesses: intervals(
+                    epochs,
+                    elevations.iter().map(|at_epoch| at_epoch[idx].is_finite()),
+                ),
+                max_elevation_deg: elevations
+                    .iter()
+                    .map(|at_epoch| at_epoch[idx])
+                    .filter(|el| el.is_finite())
+                    .fold(f64::NAN, f64::max),
+            })
+            .collect();
+
+        Ok(ConstellationReport {
+            name: constellation.name.clone(),
+            start_epoch: epochs[0],
+            end_epoch: epochs[epochs.len() - 1],
+            num_samples: epochs.len(),
+            any_in_view: intervals(epochs, num_in_view.iter().map(|count| *count > 0)),
+            num_in_view,
+            members,
+            context_digest: self.context_digest(),
+        })
+    }
+
+    /// Computes the coverage of the surface of the target by the same instrument on every member of the constellation,
+    /// cf. `coverage` for the algorithm. Only constellations of ephemerides are supported.
+    #[allow(clippy::too_many_arguments)]
+    pub fn constellation_coverage(
+        &self,
+        constellation: &Constellation,
+        instrument: Instrument,
+        attitude: CoverageAttitude,
+        target_frame: Frame,
+        epochs: &[Epoch],
+        lat_step_deg: f64,
+        long_step_deg: f64,
+        min_elevation_deg: f64,
+    ) -> AnalysisResult<CoverageReport> {
+        let ConstellationMembers::Ephemerides(ids) = &constellation.members else {
+            return Err(AnalysisError::InvalidSetup {
+                err: format!(
+                    "coverage of {} requires the ephemerides of its members",
+                    constellation.name
+                ),
+            });
+        };
+
+        let assets = ids
+            .iter()
+            .map(|id| CoverageAsset {
+                spacecraft_id: *id,
+                instrument,
+                attitude,
+            })
+            .collect::<Vec<_>>();
+
+        self.coverage(
+            target_frame,
+            &assets,
+            epochs,
+            lat_step_deg,
+            long_step_deg,
+            min_elevation_deg,
+        )
+    }
+}
+
+#[cfg(test)]
+mod ut_constellation {
+    use super::intervals;
+    use hifitime::{Epoch, TimeSeries, Unit};
+
+    #[test]
+    fn access_intervals() {
+        let start = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let epochs = TimeSeries::inclusive(start, start + Unit::Minute * 5, Unit::Minute * 1)
+            .collect::<Vec<Epoch>>();
+
+        let flags = [true, true, false, true, false, true];
+        assert_eq!(
+            intervals(&epochs, flags.into_iter()),
+            vec![
+                (epochs[0], epochs[1]),
+                (epochs[3], epochs[3]),
+                (epochs[5], epochs[5])
+            ]
+        );
+        assert!(intervals(&epochs, [false; 6].into_iter()).is_empty());
+    }
+}
@@ -0,0 +1,439 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use hifitime::{Duration, Epoch};
+use snafu::prelude::*;
+
+use super::{
+    AlmanacAnalysisSnafu, AnalysisResult, InvalidSetupSnafu, PhysicsAnalysisSnafu,
+    PlanetaryDataAnalysisSnafu,
+};
+use crate::almanac::Almanac;
+use crate::constants::orientations::J2000;
+use crate::errors::{OrientationSnafu, PhysicsError};
+use crate::instrument::Instrument;
+use crate::math::rotation::DCM;
+use crate::math::{Matrix3, Vector3};
+use crate::prelude::{Frame, Orbit};
+use crate::progress::ProgressSink;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Attitude of the spacecraft carrying an instrument during a coverage analysis.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CoverageAttitude {
+    /// Fixed rotation from the J2000 orientation to the body frame
+    Inertial(DCM),
+    /// Body +Z axis toward the center of the target, body +Y axis along the negative orbit normal, and body +X axis completing the triad (close to the velocity)
+    Nadir,
+}
+
+/// An instrument on a spacecraft whose trajectory is in the loaded ephemerides, used as an observer of a coverage analysis.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CoverageAsset {
+    /// Ephemeris ID of the spacecraft
+    pub spacecraft_id: i32,
+    pub instrument: Instrument,
+    pub attitude: CoverageAttitude,
+}
+
+/// Access and revisit statistics of one cell of the grid of a coverage analysis, at the center of that cell.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CoverageCell {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    /// Number of access intervals, i.e. of consecutive samples where at least one asset sees this cell
+    pub num_accesses: usize,
+    /// Sum of the durations of the access intervals
+    pub total_access: Duration,
+    /// Start of the first access interval
+    pub first_access: Option<Epoch>,
+    /// End of the last access interval
+    pub last_access: Option<Epoch>,
+    /// Longest duration between the end of an access and the start of the next one
+    pub max_revisit: Option<Duration>,
+    /// Mean duration between the end of an access and the start of the next one
+    pub mean_revisit: Option<Duration>,
+}
+
+impl CoverageCell {
+    /// Returns whether this cell was seen at least once.
+    pub fn is_covered(&self) -> bool {
+        self.num_accesses > 0
+    }
+}
+
+/// Summary report of a coverage analysis of a target body over a grid of latitude and longitude cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoverageReport {
+    pub target_frame: Frame,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub num_samples: usize,
+    pub lat_step_deg: f64,
+    pub long_step_deg: f64,
+    pub cells: Vec<CoverageCell>,
+    /// Digest of the data loaded in the Almanac used for this analysis (cf. `Almanac::context_digest`)
+    pub context_digest: u32,
+}
+
+impl CoverageReport {
+    /// Returns the percentage of cells which were seen at least once.
+    pub fn percent_covered(&self) -> f64 {
+        if self.cells.is_empty() {
+            return 0.0;
+        }
+        let covered = self.cells.iter().filter(|cell| cell.is_covered()).count();
+        100.0 * covered as f64 / self.cells.len() as f64
+    }
+
+    /// Returns the percentage of the surface of the target which was seen at least once, weighting each cell by its area on a sphere.
+    pub fn percent_area_covered(&self) -> f64 {
+        let weight = |cell: &CoverageCell| {
+            let half_step = (self.lat_step_deg / 2.0).to_radians();
+            let lat = cell.latitude_deg.to_radians();
+            (lat + half_step).min(core::f64::consts::FRAC_PI_2).sin()
+                - (lat - half_step).max(-core::f64::consts::FRAC_PI_2).sin()
+        };
+        let total: f64 = self.cells.iter().map(weight).sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let covered: f64 = self
+            .cells
+            .iter()
+            .filter(|cell| cell.is_covered())
+            .map(weight)
+            .sum();
+        100.0 * covered / total
+    }
+
+    /// Returns the longest revisit time of all of the cells.
+    pub fn max_revisit(&self) -> Option<Duration> {
+        self.cells.iter().filter_map(|cell| cell.max_revisit).max()
+    }
+
+    /// Returns the mean of the mean revisit times of the cells which were revisited.
+    pub fn mean_revisit(&self) -> Option<Duration> {
+        let revisits = self
+            .cells
+            .iter()
+            .filter_map(|cell| cell.mean_revisit)
+            .collect::<Vec<_>>();
+        if revisits.is_empty() {
+            None
+        } else {
+            Some(
+                revisits
+                    .iter()
+                    .fold(Duration::ZERO, |acc, revisit| acc + *revisit)
+                    / revisits.len() as f64,
+            )
+        }
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Coverage of {} from {} to {} ({} samples, {} cells)",
+            self.target_frame,
+            self.start_epoch,
+            self.end_epoch,
+            self.num_samples,
+            self.cells.len()
+        )?;
+        writeln!(
+            f,
+            "  covered: {:.2} % of cells, {:.2} % of area",
+            self.percent_covered(),
+            self.percent_area_covered()
+        )?;
+        match self.max_revisit() {
+            Some(revisit) => writeln!(f, "  max revisit: {revisit}")?,
+            None => writeln!(f, "  max revisit: N/A")?,
+        }
+        match self.mean_revisit() {
+            Some(revisit) => writeln!(f, "  mean revisit: {revisit}")?,
+            None => writeln!(f, "  mean revisit: N/A")?,
+        }
+        write!(f, "  Almanac context digest: 0x{:08x}", self.context_digest)
+    }
+}
+
+/// Geometry of an asset at one sample: position of the spacecraft and rotation into the instrument frame, both in the target frame.
+struct AssetSample {
+    position_km: Vector3,
+    instrument_from_target: Matrix3,
+}
+
+/// Returns the rotation from the frame of the state to the nadir pointing body frame.
+fn nadir_dcm(state: &Orbit, body_id: i32) -> DCM {
+    let z = -state.radius_km.normalize();
+    let y = -state.radius_km.cross(&state.velocity_km_s).normalize();
+    let x = y.cross(&z);
+    DCM {
+        rot_mat: Matrix3::from_rows(&[x.transpose(), y.transpose(), z.transpose()]),
+        rot_mat_dt: None,
+        from: state.frame.orientation_id,
+        to: body_id,
+    }
+}
+
+impl Almanac {
+    /// Computes the coverage of the surface of the target by the instruments of the assets, over a grid of cells of the provided
+    /// latitude and longitude steps, sampled at the provided epochs.
+    ///
+    /// # Algorithm
+    /// 1. Compute the center of each cell on the surface of the target, using the shape of the target frame.
+    /// 2. At each epoch, compute the position of each spacecraft and the rotation into its instrument frame, in the target frame.
+    /// 3. A cell is seen at an epoch if it is within the field of view of at least one instrument and above the minimum elevation as seen from that cell.
+    /// 4. Consecutive samples where a cell is seen form one access interval, and the revisit times are the gaps between these intervals.
+    ///
+    /// The target frame must be body fixed, and its shape is fetched from the loaded planetary data if it is not set.
+    /// If the `rayon` feature is enabled, the cells are processed in parallel.
+    pub fn coverage(
+        &self,
+        target_frame: Frame,
+        assets: &[CoverageAsset],
+        epochs: &[Epoch],
+        lat_step_deg: f64,
+        long_step_deg: f64,
+        min_elevation_deg: f64,
+    ) -> AnalysisResult<CoverageReport> {
+        self.coverage_with_progress(
+            target_frame,
+            assets,
+            epochs,
+            lat_step_deg,
+            long_step_deg,
+            min_elevation_deg,
+            None,
+        )
+    }
+
+    /// Computes the coverage exactly like `coverage`, reporting its progress to the provided sink, if any.
+    ///
+    /// The total amount of work is the number of epochs (computation of the geometry of the assets) plus the number of cells.
+    #[allow(clippy::too_many_arguments)]
+    pub fn coverage_with_progress(
+        &self,
+        mut target_frame: Frame,
+        assets: &[CoverageAsset],
+        epochs: &[Epoch],
+        lat_step_deg: f64,
+        long_step_deg: f64,
+        min_elevation_deg: f64,
+        progress: Option<&dyn ProgressSink>,
+    ) -> AnalysisResult<CoverageReport> {
+        ensure!(
+            !assets.is_empty() && !epochs.is_empty(),
+            InvalidSetupSnafu {
+                err: "coverage requires at least one asset and one epoch".to_string()
+            }
+        );
+        ensure!(
+            lat_step_deg > 0.0 && long_step_deg > 0.0,
+            InvalidSetupSnafu {
+                err: format!(
+                    "coverage grid steps must be positive, got {lat_step_deg} and {long_step_deg} deg"
+                )
+            }
+        );
+
+        if target_frame.shape.is_none() {
+            target_frame =
+                self.frame_from_uid(target_frame)
+                    .context(PlanetaryDataAnalysisSnafu {
+                        action: "fetching target body for coverage",
+                    })?;
+        }
+        let shape = target_frame
+            .shape
+            .ok_or(PhysicsError::MissingFrameData {
+                action: "computing coverage",
+                data: "shape",
+                frame: target_frame.into(),
+            })
+            .context(PhysicsAnalysisSnafu {
+                action: "fetching shape of target for coverage",
+            })?;
+
+        // Centers of the cells and their outward normals
+        let num_lat = (180.0 / lat_step_deg).ceil() as usize;
+        let num_long = (360.0 / long_step_deg).ceil() as usize;
+        let mut centers = Vec::with_capacity(num_lat * num_long);
+        for i in 0..num_lat {
+            let latitude_deg = (-90.0 + lat_step_deg * (i as f64 + 0.5)).min(90.0);
+            for j in 0..num_long {
+                let longitude_deg = (-180.0 + long_step_deg * (j as f64 + 0.5)).min(180.0);
+                let point = Orbit::try_latlongalt(
+                    latitude_deg,
+                    longitude_deg,
+                    0.0,
+                    0.0,
+                    epochs[0],
+                    target_frame,
+                )
+                .context(PhysicsAnalysisSnafu {
+                    action: "computing center of coverage cell",
+                })?;
+                let normal = Vector3::new(
+                    point.radius_km.x / shape.semi_major_equatorial_radius_km.powi(2),
+                    point.radius_km.y / shape.semi_minor_equatorial_radius_km.powi(2),
+                    point.radius_km.z / shape.polar_radius_km.powi(2),
+                )
+                .normalize();
+                centers.push((latitude_deg, longitude_deg, point.radius_km, normal));
+            }
+        }
+
+        if let Some(progress) = progress {
+            progress.start("coverage", Some((epochs.len() + centers.len()) as u64));
+        }
+        let done = AtomicU64::new(0);
+        let advance = || {
+            let count = done.fetch_add(1, Ordering::Relaxed) + 1;
+            if let Some(progress) = progress {
+                progress.update(count);
+            }
+        };
+
+        // Geometry of each asset at each epoch
+        let mut samples: Vec<Vec<AssetSample>> = Vec::with_capacity(epochs.len());
+        for epoch in epochs {
+            let mut at_epoch = Vec::with_capacity(assets.len());
+            for asset in assets {
+                let inertial = self
+                    .transform(
+                        Frame::from_ephem_j2000(asset.spacecraft_id),
+                        target_frame.with_orient(J2000),
+                        *epoch,
+                        None,
+                    )
+                    .context(AlmanacAnalysisSnafu {
+                        action: "computing spacecraft state for coverage",
+                    })?;
+
+                let attitude = match asset.attitude {
+                    CoverageAttitude::Inertial(dcm) => dcm,
+                    CoverageAttitude::Nadir => nadir_dcm(&inertial, asset.instrument.mounting.from),
+                };
+                let to_instrument = asset
+                    .instrument
+                    .dcm_to_instrument(&inertial, &attitude)
+                    .context(PhysicsAnalysisSnafu {
+                        action: "rotating into instrument frame for coverage",
+                    })?;
+
+                let to_target = self
+                    .rotate(inertial.frame, target_frame, *epoch)
+                    .context(OrientationSnafu {
+                        action: "rotating into target frame",
+                    })
+                    .context(AlmanacAnalysisSnafu {
+                        action: "computing coverage",
+                    })?;
+
+                at_epoch.push(AssetSample {
+                    position_km: to_target.rot_mat * inertial.radius_km,
+                    instrument_from_target: to_instrument.rot_mat * to_target.rot_mat.transpose(),
+                });
+            }
+            samples.push(at_epoch);
+            advance();
+        }
+
+        let sin_min_elevation = min_elevation_deg.to_radians().sin();
+
+        let cell = |(latitude_deg, longitude_deg, position_km, normal): &(
+            f64,
+            f64,
+            Vector3,
+            Vector3,
+        )|
+         -> CoverageCell {
+            let mut intervals: Vec<(Epoch, Epoch)> = Vec::new();
+            let mut in_access = false;
+            for (epoch, at_epoch) in epochs.iter().zip(samples.iter()) {
+                let seen = at_epoch.iter().zip(assets.iter()).any(|(sample, asset)| {
+                    let line_of_sight = sample.position_km - position_km;
+                    let range_km = line_of_sight.norm();
+                    range_km > 0.0
+                        && line_of_sight.dot(normal) / range_km >= sin_min_elevation
+                        && asset
+                            .instrument
+                            .fov
+                            .contains(&(sample.instrument_from_target * -line_of_sight))
+                });
+                match (seen, in_access) {
+                    (true, false) => intervals.push((*epoch, *epoch)),
+                    (true, true) => intervals.last_mut().unwrap().1 = *epoch,
+                    _ => {}
+                }
+                in_access = seen;
+            }
+
+            let revisits = intervals
+                .windows(2)
+                .map(|pair| pair[1].0 - pair[0].1)
+                .collect::<Vec<_>>();
+
+            advance();
+
+            CoverageCell {
+                latitude_deg: *latitude_deg,
+                longitude_deg: *longitude_deg,
+                num_accesses: intervals.len(),
+                total_access: intervals
+                    .iter()
+                    .fold(Duration::ZERO, |acc, (start, end)| acc + (*end - *start)),
+                first_access: intervals.first().map(|(start, _)| *start),
+                last_access: intervals.last().map(|(_, end)| *end),
+                max_revisit: revisits.iter().max().copied(),
+                mean_revisit: if revisits.is_empty() {
+                    None
+                } else {
+                    Some(
+                        revisits
+                            .iter()
+                            .fold(Duration::ZERO, |acc, revisit| acc + *revisit)
+                            / revisits.len() as f64,
+                    )
+                },
+            }
+        };
+
+        #[cfg(feature = "rayon")]
+        let cells = centers.par_iter().map(cell).collect::<Vec<_>>();
+        #[cfg(not(feature = "rayon"))]
+        let cells = centers.iter().map(cell).collect::<Vec<_>>();
+
+        if let Some(progress) = progress {
+            progress.finish();
+        }
+
+        Ok(CoverageReport {
+            target_frame,
+            start_epoch: epochs[0],
+            end_epoch: epochs[epochs.len() - 1],
+            num_samples: epochs.len(),
+            lat_step_deg,
+            long_step_deg,
+            cells,
+            context_digest: self.context_digest(),
+        })
+    }
+}
@@ -0,0 +1,202 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::Epoch;
+use nalgebra::{Matrix4, RowVector4};
+use snafu::prelude::*;
+
+use super::{AlmanacAnalysisSnafu, AnalysisResult, InvalidSetupSnafu};
+use crate::almanac::Almanac;
+use crate::astro::gnss::GnssEphemerides;
+use crate::prelude::{Frame, Orbit};
+use crate::structure::location::Location;
+use crate::NaifId;
+
+/// Transmitters whose geometry is used to compute the dilution of precision.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DopTransmitters<'a> {
+    /// Ephemeris IDs of the transmitters, whose trajectories are in the loaded ephemerides
+    Ephemerides(&'a [NaifId]),
+    /// GNSS constellation from broadcast ephemerides or almanacs, where only the healthy satellites are used
+    Gnss(&'a GnssEphemerides),
+}
+
+/// Dilution of precision of the transmitters visible from a location at one epoch.
+///
+/// The dilutions are NaN if fewer than four transmitters are visible, or if their geometry is degenerate.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Dop {
+    pub epoch: Epoch,
+    /// Number of transmitters above the elevation mask of the location
+    pub num_visible: usize,
+    /// Geometric dilution of precision (position and time)
+    pub gdop: f64,
+    /// Position dilution of precision
+    pub pdop: f64,
+    /// Horizontal dilution of precision
+    pub hdop: f64,
+    /// Vertical dilution of precision
+    pub vdop: f64,
+    /// Time dilution of precision
+    pub tdop: f64,
+}
+
+impl Dop {
+    /// Computes the dilution of precision from the azimuth and elevation of each visible transmitter, in degrees.
+    pub fn from_azimuth_elevations(epoch: Epoch, az_el_deg: &[(f64, f64)]) -> Self {
+        let mut dop = Self {
+            epoch,
+            num_visible: az_el_deg.len(),
+            gdop: f64::NAN,
+            pdop: f64::NAN,
+            hdop: f64::NAN,
+            vdop: f64::NAN,
+            tdop: f64::NAN,
+        };
+
+        if az_el_deg.len() < 4 {
+            return dop;
+        }
+
+        // Normal matrix of the geometry, where each row is the line of sight in the local east, north, up frame and the clock term
+        let normal = az_el_deg.iter().fold(
+            Matrix4::<f64>::zeros(),
+            |acc, (azimuth_deg, elevation_deg)| {
+                let (sin_az, cos_az) = azimuth_deg.to_radians().sin_cos();
+                let (sin_el, cos_el) = elevation_deg.to_radians().sin_cos();
+                let row = RowVector4::new(cos_el * sin_az, cos_el * cos_az, sin_el, 1.0);
+                acc + row.transpose() * row
+            },
+        );
+
+        if let Some(cov) = normal.try_inverse() {
+            let diag = cov.diagonal();
+            dop.gdop = diag.sum().sqrt();
+            dop.pdop = (diag[0] + diag[1] + diag[2]).sqrt();
+            dop.hdop = (diag[0] + diag[1]).sqrt();
+            dop.vdop = diag[2].sqrt();
+            dop.tdop = diag[3].sqrt();
+        }
+
+        dop
+    }
+
+    /// Returns whether enough transmitters were visible to compute the dilution of precision.
+    pub fn is_valid(&self) -> bool {
+        self.gdop.is_finite()
+    }
+}
+
+impl fmt::Display for Dop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} visible, GDOP = {:.3}, PDOP = {:.3}, HDOP = {:.3}, VDOP = {:.3}, TDOP = {:.3}",
+            self.epoch, self.num_visible, self.gdop, self.pdop, self.hdop, self.vdop, self.tdop
+        )
+    }
+}
+
+impl Almanac {
+    /// Computes the dilution of precision of the transmitters seen from the provided location at each of the provided epochs.
+    ///
+    /// # Algorithm
+    /// 1. Compute the azimuth, elevation, and range of each transmitter from the location, accounting for its refraction model.
+    /// 2. Keep the transmitters visible from the location, i.e. above its terrain mask and minimum antenna elevation.
+    /// 3. The dilutions are the square roots of the diagonal terms of the inverse of the normal matrix of the lines of sight
+    ///    in the local east, north, and up frame, augmented with the receiver clock.
+    ///
+    /// The body fixed frame of the location must be loaded in this Almanac. The states of GNSS transmitters are in the ITRF93
+    /// frame, so the Earth orientation parameters must also be loaded if the location is on another Earth fixed frame.
+    pub fn dop(
+        &self,
+        transmitters: DopTransmitters,
+        location: &Location,
+        epochs: &[Epoch],
+    ) -> AnalysisResult<Vec<Dop>> {
+        ensure!(
+            !epochs.is_empty(),
+            InvalidSetupSnafu {
+                err: "dilution of precision requires at least one epoch".to_string()
+            }
+        );
+
+        epochs
+            .iter()
+            .map(|epoch| {
+                let states = match transmitters {
+                    DopTransmitters::Ephemerides(ids) => ids
+                        .iter()
+                        .map(|id| {
+                            self.transform(
+                                Frame::from_ephem_j2000(*id),
+                                Frame::from(location.frame),
+                                *epoch,
+                                None,
+                            )
+                            .context(AlmanacAnalysisSnafu {
+                                action: "computing transmitter state for dilution of precision",
+                            })
+                        })
+                        .collect::<AnalysisResult<Vec<Orbit>>>()?,
+                    DopTransmitters::Gnss(gnss) => gnss
+                        .states(*epoch)
+                        .into_iter()
+                        .map(|(_, state)| state)
+                        .collect(),
+                };
+
+                let mut az_el_deg = Vec::with_capacity(states.len());
+                for state in states {
+                    let aer = self
+                        .azimuth_elevation_range_sez_from_location(state, location, None, None)
+                        .context(AlmanacAnalysisSnafu {
+                            action: "computing transmitter elevation for dilution of precision",
+                        })?;
+                    if location.is_visible(&aer) {
+                        az_el_deg.push((aer.azimuth_deg, aer.elevation_deg));
+                    }
+                }
+
+                Ok(Dop::from_azimuth_elevations(*epoch, &az_el_deg))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod ut_dop {
+    use super::Dop;
+    use hifitime::Epoch;
+
+    #[test]
+    fn zenith_and_horizon() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        // One transmitter at zenith and three evenly spaced on the horizon
+        let dop = Dop::from_azimuth_elevations(
+            epoch,
+            &[(0.0, 90.0), (0.0, 0.0), (120.0, 0.0), (240.0, 0.0)],
+        );
+        println!("{dop}");
+        assert!(dop.is_valid());
+        assert!((dop.gdop - 3f64.sqrt()).abs() < 1e-12);
+        assert!((dop.pdop - (8.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert!((dop.hdop - (4.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert!((dop.vdop - (4.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert!((dop.tdop - (1.0f64 / 3.0).sqrt()).abs() < 1e-12);
+
+        // Not enough transmitters
+        let dop = Dop::from_azimuth_elevations(epoch, &[(0.0, 90.0), (0.0, 10.0), (120.0, 10.0)]);
+        assert_eq!(dop.num_visible, 3);
+        assert!(!dop.is_valid());
+    }
+}
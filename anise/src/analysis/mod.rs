@@ -0,0 +1,48 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use snafu::prelude::*;
+
+use crate::almanac::planetary::PlanetaryDataError;
+use crate::errors::{AlmanacError, PhysicsError};
+
+pub mod beta_angle;
+pub mod constellation;
+pub mod coverage;
+pub mod dop;
+pub mod opportunities;
+pub mod porkchop;
+pub mod sensitivity;
+pub mod slew;
+
+#[derive(Debug, PartialEq, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum AnalysisError {
+    #[snafu(display("{action} encountered an error with the Almanac: {source}"))]
+    AlmanacAnalysis {
+        action: &'static str,
+        #[snafu(source(from(AlmanacError, Box::new)))]
+        source: Box<AlmanacError>,
+    },
+    #[snafu(display("{action} encountered a physics error: {source}"))]
+    PhysicsAnalysis {
+        action: &'static str,
+        source: PhysicsError,
+    },
+    #[snafu(display("{action} encountered a planetary data error: {source}"))]
+    PlanetaryDataAnalysis {
+        action: &'static str,
+        source: PlanetaryDataError,
+    },
+    #[snafu(display("invalid analysis setup: {err}"))]
+    InvalidSetup { err: String },
+}
+
+pub type AnalysisResult<T> = Result<T, AnalysisError>;
@@ -0,0 +1,449 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch};
+use serde_derive::{Deserialize, Serialize};
+use snafu::prelude::*;
+
+use super::constellation::intervals;
+use super::{AlmanacAnalysisSnafu, AnalysisResult, InvalidSetupSnafu, PhysicsAnalysisSnafu};
+use crate::almanac::Almanac;
+use crate::constants::frames::SUN_J2000;
+use crate::constants::orientations::J2000;
+use crate::errors::OrientationSnafu;
+use crate::instrument::Instrument;
+use crate::orientations::profiles::AttitudeProfile;
+use crate::prelude::Frame;
+use crate::structure::location::Location;
+use crate::NaifId;
+
+/// Kind of an observation opportunity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpportunityKind {
+    Imaging,
+    Communication,
+}
+
+impl fmt::Display for OpportunityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Imaging => write!(f, "imaging"),
+            Self::Communication => write!(f, "communication"),
+        }
+    }
+}
+
+/// Constraints of an imaging request, in addition to the visibility of the spacecraft from the target.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImagingConstraints {
+    /// Minimum elevation of the Sun seen from the target, in degrees (e.g. for optical imagery)
+    pub min_sun_elevation_deg: Option<f64>,
+    /// Maximum angle between the nadir of the spacecraft and the direction of the target, in degrees
+    pub max_off_nadir_deg: Option<f64>,
+    /// Whether the spacecraft must not be in the umbra of the body of the target
+    pub sunlit_spacecraft: bool,
+}
+
+/// A request whose opportunities are searched for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OpportunityRequest {
+    /// Image the target, which must be visible from the spacecraft (cf. its terrain mask and minimum elevation)
+    Imaging {
+        name: String,
+        target: Location,
+        constraints: ImagingConstraints,
+    },
+    /// Communicate with the ground station, which must see the spacecraft above its terrain mask and minimum elevation
+    Communication { name: String, station: Location },
+}
+
+impl OpportunityRequest {
+    /// Returns the name of this request.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Imaging { name, .. } | Self::Communication { name, .. } => name,
+        }
+    }
+
+    /// Returns the kind of the opportunities of this request.
+    pub fn kind(&self) -> OpportunityKind {
+        match self {
+            Self::Imaging { .. } => OpportunityKind::Imaging,
+            Self::Communication { .. } => OpportunityKind::Communication,
+        }
+    }
+
+    /// Returns the location of the target or of the ground station of this request.
+    pub fn location(&self) -> &Location {
+        match self {
+            Self::Imaging { target, .. } => target,
+            Self::Communication { station, .. } => station,
+        }
+    }
+}
+
+/// Imaging instrument of the spacecraft and its attitude profile: if provided, the targets of imaging requests must also
+/// be in the field of view of the instrument. Otherwise, the spacecraft is assumed to point to each target.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ImagingSensor {
+    pub instrument: Instrument,
+    pub attitude: AttitudeProfile,
+}
+
+/// Order of the opportunities of an opportunity list.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OpportunityRanking {
+    /// Earliest opportunities first
+    #[default]
+    Chronological,
+    /// Longest opportunities first
+    Duration,
+    /// Opportunities with the highest elevation of the spacecraft seen from the location first
+    MaxElevation,
+    /// Opportunities with the smallest off-nadir angle first
+    MinOffNadir,
+}
+
+/// An interval of consecutive samples where all of the constraints of a request are met.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Opportunity {
+    /// Rank of this opportunity in its list, starting at 1
+    pub rank: usize,
+    pub kind: OpportunityKind,
+    /// Name of the request of this opportunity
+    pub request: String,
+    pub start: Epoch,
+    pub end: Epoch,
+    /// Highest elevation of the spacecraft seen from the location during this opportunity, in degrees
+    pub max_elevation_deg: f64,
+    /// Smallest angle between the nadir of the spacecraft and the direction of the location during this opportunity, in degrees
+    pub min_off_nadir_deg: f64,
+    /// Smallest range between the spacecraft and the location during this opportunity, in kilometers
+    pub min_range_km: f64,
+}
+
+impl Opportunity {
+    /// Returns the duration of this opportunity, zero if it is a single sample.
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+impl fmt::Display for Opportunity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} {}: {} to {} ({}), max elevation {:.3} deg, min off-nadir {:.3} deg, min range {:.3} km",
+            self.rank,
+            self.kind,
+            self.request,
+            self.start,
+            self.end,
+            self.duration(),
+            self.max_elevation_deg,
+            self.min_off_nadir_deg,
+            self.min_range_km
+        )
+    }
+}
+
+/// Ranked list of the imaging and communication opportunities of a spacecraft.
+///
+/// This list is serializable, e.g. to export it to a mission planning tool.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OpportunityList {
+    /// Ephemeris ID of the spacecraft
+    pub spacecraft_id: NaifId,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub num_samples: usize,
+    pub opportunities: Vec<Opportunity>,
+    /// Digest of the data loaded in the Almanac used for this analysis (cf. `Almanac::context_digest`)
+    pub context_digest: u32,
+}
+
+impl OpportunityList {
+    /// Returns the opportunities of the provided kind, in their ranking order.
+    pub fn of_kind(&self, kind: OpportunityKind) -> Vec<&Opportunity> {
+        self.opportunities
+            .iter()
+            .filter(|opportunity| opportunity.kind == kind)
+            .collect()
+    }
+
+    /// Returns the opportunities of the provided request, in their ranking order.
+    pub fn of_request(&self, name: &str) -> Vec<&Opportunity> {
+        self.opportunities
+            .iter()
+            .filter(|opportunity| opportunity.request == name)
+            .collect()
+    }
+}
+
+impl fmt::Display for OpportunityList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} opportunities of #{} from {} to {} ({} samples)",
+            self.opportunities.len(),
+            self.spacecraft_id,
+            self.start_epoch,
+            self.end_epoch,
+            self.num_samples
+        )?;
+        for opportunity in &self.opportunities {
+            writeln!(f, "  {opportunity}")?;
+        }
+        write!(f, "  Almanac context digest: 0x{:08x}", self.context_digest)
+    }
+}
+
+/// Geometry of the spacecraft and the location of a request at one sample where all of its constraints are met.
+#[derive(Copy, Clone, Debug)]
+struct OpportunitySample {
+    elevation_deg: f64,
+    off_nadir_deg: f64,
+    range_km: f64,
+}
+
+impl Almanac {
+    /// Generates the imaging and communication opportunities of the spacecraft for each request, sampled at the provided
+    /// epochs, and ranks them.
+    ///
+    /// # Algorithm
+    /// At each epoch and for each request:
+    /// 1. Compute the state of the spacecraft in the body fixed frame of the location of the request, which must be loaded in this Almanac.
+    /// 2. The location must see the spacecraft above its terrain mask and minimum elevation.
+    /// 3. For imaging requests, check the elevation of the Sun at the target, the off-nadir angle of the target, whether the
+    ///    spacecraft is eclipsed by the body of the target, and whether the target is in the field of view of the sensor if provided.
+    ///
+    /// Each interval of consecutive samples meeting these constraints is an opportunity: its duration is only as accurate as the sampling.
+    pub fn opportunities(
+        &self,
+        spacecraft_id: NaifId,
+        sensor: Option<&ImagingSensor>,
+        requests: &[OpportunityRequest],
+        epochs: &[Epoch],
+        ranking: OpportunityRanking,
+    ) -> AnalysisResult<OpportunityList> {
+        ensure!(
+            !requests.is_empty() && !epochs.is_empty(),
+            InvalidSetupSnafu {
+                err: "opportunities require at least one request and one epoch".to_string()
+            }
+        );
+        if let Some(sensor) = sensor {
+            ensure!(
+                sensor.attitude.spacecraft_id == spacecraft_id,
+                InvalidSetupSnafu {
+                    err: format!(
+                        "attitude profile of #{} used for the sensor of #{spacecraft_id}",
+                        sensor.attitude.spacecraft_id
+                    )
+                }
+            );
+        }
+
+        let mut opportunities = Vec::new();
+        for request in requests {
+            let samples = epochs
+                .iter()
+                .map(|epoch| self.opportunity_sample(spacecraft_id, sensor, request, *epoch))
+                .collect::<AnalysisResult<Vec<Option<OpportunitySample>>>>()?;
+
+            for (start, end) in intervals(epochs, samples.iter().map(|sample| sample.is_some())) {
+                let within = epochs
+                    .iter()
+                    .zip(&samples)
+                    .filter(|(epoch, _)| **epoch >= start && **epoch <= end)
+                    .filter_map(|(_, sample)| *sample)
+                    .collect::<Vec<OpportunitySample>>();
+
+                opportunities.push(Opportunity {
+                    rank: 0,
+                    kind: request.kind(),
+                    request: request.name().to_string(),
+                    start,
+                    end,
+                    max_elevation_deg: within
+                        .iter()
+                        .map(|sample| sample.elevation_deg)
+                        .fold(f64::NAN, f64::max),
+                    min_off_nadir_deg: within
+                        .iter()
+                        .map(|sample| sample.off_nadir_deg)
+                        .fold(f64::NAN, f64::min),
+                    min_range_km: within
+                        .iter()
+                        .map(|sample| sample.range_km)
+                        .fold(f64::NAN, f64::min),
+                });
+            }
+        }
+
+        match ranking {
+            OpportunityRanking::Chronological => opportunities.sort_by_key(|opp| opp.start),
+            OpportunityRanking::Duration => {
+                opportunities.sort_by_key(|opp| core::cmp::Reverse(opp.duration()))
+            }
+            OpportunityRanking::MaxElevation => {
+                opportunities.sort_by(|a, b| b.max_elevation_deg.total_cmp(&a.max_elevation_deg))
+            }
+            OpportunityRanking::MinOffNadir => {
+                opportunities.sort_by(|a, b| a.min_off_nadir_deg.total_cmp(&b.min_off_nadir_deg))
+            }
+        }
+        for (idx, opportunity) in opportunities.iter_mut().enumerate() {
+            opportunity.rank = idx + 1;
+        }
+
+        Ok(OpportunityList {
+            spacecraft_id,
+            start_epoch: epochs[0],
+            end_epoch: epochs[epochs.len() - 1],
+            num_samples: epochs.len(),
+            opportunities,
+            context_digest: self.context_digest(),
+        })
+    }
+
+    /// Returns the geometry of the request at this epoch if all of its constraints are met.
+    fn opportunity_sample(
+        &self,
+        spacecraft_id: NaifId,
+        sensor: Option<&ImagingSensor>,
+        request: &OpportunityRequest,
+        epoch: Epoch,
+    ) -> AnalysisResult<Option<OpportunitySample>> {
+        let action = "generating opportunities";
+        let location = request.location();
+        let frame = Frame::from(location.frame);
+
+        let sc_state = self
+            .transform(Frame::from_ephem_j2000(spacecraft_id), frame, epoch, None)
+            .context(AlmanacAnalysisSnafu { action })?;
+        let aer = self
+            .azimuth_elevation_range_sez_from_location(sc_state, location, None, None)
+            .context(AlmanacAnalysisSnafu { action })?;
+        if !location.is_visible(&aer) {
+            return Ok(None);
+        }
+
+        let location_state = self
+            .location_state(location, epoch)
+            .context(AlmanacAnalysisSnafu { action })?;
+        let to_location = location_state.radius_km - sc_state.radius_km;
+        let off_nadir_deg = (-sc_state.radius_km).angle(&to_location).to_degrees();
+
+        let sample = OpportunitySample {
+            elevation_deg: aer.elevation_deg,
+            off_nadir_deg,
+            range_km: aer.range_km,
+        };
+
+        let OpportunityRequest::Imaging { constraints, .. } = request else {
+            return Ok(Some(sample));
+        };
+
+        if let Some(max_off_nadir_deg) = constraints.max_off_nadir_deg {
+            if off_nadir_deg > max_off_nadir_deg {
+                return Ok(None);
+            }
+        }
+
+        if let Some(min_sun_elevation_deg) = constraints.min_sun_elevation_deg {
+            let sun = self
+                .transform(SUN_J2000, frame, epoch, None)
+                .context(AlmanacAnalysisSnafu { action })?;
+            let sun_aer = self
+                .azimuth_elevation_range_sez_from_location(sun, location, None, None)
+                .context(AlmanacAnalysisSnafu { action })?;
+            if sun_aer.elevation_deg < min_sun_elevation_deg {
+                return Ok(None);
+            }
+        }
+
+        if constraints.sunlit_spacecraft
+            && self
+                .solar_eclipsing(frame, sc_state, None)
+                .context(AlmanacAnalysisSnafu { action })?
+                .is_obstructed()
+        {
+            return Ok(None);
+        }
+
+        if let Some(sensor) = sensor {
+            // The attitude is from J2000, so the field of view is checked in the inertial frame centered on the target body
+            let inertial = Frame::new(frame.ephemeris_id, J2000);
+            let sc_inertial = self
+                .transform_to(sc_state, inertial, None)
+                .context(AlmanacAnalysisSnafu { action })?;
+            let target_inertial = self
+                .transform_to(location_state, inertial, None)
+                .context(AlmanacAnalysisSnafu { action })?;
+            let attitude = self
+                .attitude_dcm(&sensor.attitude, epoch)
+                .context(OrientationSnafu {
+                    action: "computing attitude for opportunities",
+                })
+                .context(AlmanacAnalysisSnafu { action })?;
+            if !sensor
+                .instrument
+                .is_in_fov(sc_inertial, attitude, target_inertial)
+                .context(PhysicsAnalysisSnafu { action })?
+            {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(sample))
+    }
+}
+
+#[cfg(test)]
+mod ut_opportunities {
+    use super::{Opportunity, OpportunityKind, OpportunityList};
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn opportunity_list() {
+        let start = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let opportunity = |rank, kind, request: &str, minutes| Opportunity {
+            rank,
+            kind,
+            request: request.to_string(),
+            start,
+            end: start + Unit::Minute * minutes,
+            max_elevation_deg: 45.0,
+            min_off_nadir_deg: 30.0,
+            min_range_km: 1_000.0,
+        };
+
+        let list = OpportunityList {
+            spacecraft_id: -100,
+            start_epoch: start,
+            end_epoch: start + Unit::Day * 1,
+            num_samples: 1441,
+            opportunities: vec![
+                opportunity(1, OpportunityKind::Imaging, "Paris", 2),
+                opportunity(2, OpportunityKind::Communication, "Madrid", 8),
+                opportunity(3, OpportunityKind::Imaging, "Paris", 0),
+            ],
+            context_digest: 0,
+        };
+        println!("{list}");
+
+        assert_eq!(list.of_kind(OpportunityKind::Imaging).len(), 2);
+        assert_eq!(list.of_request("Madrid")[0].duration(), Unit::Minute * 8);
+        assert!(list.of_request("Tokyo").is_empty());
+    }
+}
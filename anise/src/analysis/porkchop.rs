@@ -0,0 +1,252 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Epoch, Unit};
+use nalgebra::DMatrix;
+use snafu::prelude::*;
+
+use super::{
+    AlmanacAnalysisSnafu, AnalysisError, AnalysisResult, InvalidSetupSnafu, PhysicsAnalysisSnafu,
+    PlanetaryDataAnalysisSnafu,
+};
+use crate::almanac::Almanac;
+use crate::astro::lambert::{lambert, TransferKind};
+use crate::math::cartesian::CartesianState;
+use crate::prelude::Frame;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(feature = "python")]
+use ndarray::Array2;
+#[cfg(feature = "python")]
+use numpy::PyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Grids of a porkchop plot. Rows correspond to the departure epochs and columns to the arrival epochs.
+///
+/// Cells where the arrival is before the departure, or where the Lambert solver failed, are set to NaN.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise.analysis"))]
+pub struct PorkchopPlot {
+    pub departure_epochs: Vec<Epoch>,
+    pub arrival_epochs: Vec<Epoch>,
+    /// Characteristic energy at departure, in km^2/s^2
+    pub c3_km2_s2: DMatrix<f64>,
+    /// Hyperbolic excess velocity at departure, in km/s
+    pub vinf_departure_km_s: DMatrix<f64>,
+    /// Hyperbolic excess velocity at arrival, in km/s
+    pub vinf_arrival_km_s: DMatrix<f64>,
+    /// Time of flight, in days
+    pub tof_days: DMatrix<f64>,
+}
+
+impl Almanac {
+    /// Computes the porkchop plot grids of a transfer between the `departure` and `arrival` bodies, as seen from the `central` body.
+    ///
+    /// # Algorithm
+    /// 1. Compute the geometric states of the departure and arrival bodies with respect to the central frame at each epoch
+    /// 2. For each departure/arrival pair with a positive time of flight, solve the zero-revolution Lambert problem
+    /// 3. The C3 and v-infinity are computed from the difference between the Lambert velocities and the velocity of each body.
+    ///
+    /// The gravitational parameter of the central frame is fetched from the loaded planetary data if not set on the frame.
+    /// If the `rayon` feature is enabled, the departure epochs are processed in parallel.
+    pub fn porkchop(
+        &self,
+        departure: Frame,
+        arrival: Frame,
+        central: Frame,
+        departure_epochs: &[Epoch],
+        arrival_epochs: &[Epoch],
+        kind: TransferKind,
+    ) -> AnalysisResult<PorkchopPlot> {
+        ensure!(
+            !departure_epochs.is_empty() && !arrival_epochs.is_empty(),
+            InvalidSetupSnafu {
+                err: "porkchop requires at least one departure and one arrival epoch".to_string()
+            }
+        );
+
+        let mu_km3_s2 = match central.mu_km3_s2 {
+            Some(mu_km3_s2) => mu_km3_s2,
+            None => self
+                .frame_from_uid(central)
+                .context(PlanetaryDataAnalysisSnafu {
+                    action: "fetching central body for porkchop",
+                })?
+                .mu_km3_s2()
+                .context(PhysicsAnalysisSnafu {
+                    action: "fetching central body for porkchop",
+                })?,
+        };
+
+        let departure_states = departure_epochs
+            .iter()
+            .map(|epoch| {
+                self.transform(departure, central, *epoch, None)
+                    .context(AlmanacAnalysisSnafu {
+                        action: "computing departure states for porkchop",
+                    })
+            })
+            .collect::<Result<Vec<CartesianState>, AnalysisError>>()?;
+
+        let arrival_states = arrival_epochs
+            .iter()
+            .map(|epoch| {
+                self.transform(arrival, central, *epoch, None)
+                    .context(AlmanacAnalysisSnafu {
+                        action: "computing arrival states for porkchop",
+                    })
+            })
+            .collect::<Result<Vec<CartesianState>, AnalysisError>>()?;
+
+        let row = |dep: &CartesianState| -> Vec<[f64; 4]> {
+            arrival_states
+                .iter()
+                .map(|arr| {
+                    let tof = arr.epoch - dep.epoch;
+                    if tof.to_seconds() <= 0.0 {
+                        return [f64::NAN; 4];
+                    }
+                    match lambert(dep.radius_km, arr.radius_km, tof, mu_km3_s2, kind) {
+                        Ok(sol) => {
+                            let vinf_dep = (sol.v_init_km_s - dep.velocity_km_s).norm();
+                            let vinf_arr = (sol.v_final_km_s - arr.velocity_km_s).norm();
+                            [vinf_dep.powi(2), vinf_dep, vinf_arr, tof.to_unit(Unit::Day)]
+                        }
+                        Err(_) => [f64::NAN, f64::NAN, f64::NAN, tof.to_unit(Unit::Day)],
+                    }
+                })
+                .collect()
+        };
+
+        #[cfg(feature = "rayon")]
+        let cells = departure_states.par_iter().map(row).collect::<Vec<_>>();
+        #[cfg(not(feature = "rayon"))]
+        let cells = departure_states.iter().map(row).collect::<Vec<_>>();
+
+        let grid =
+            |k: usize| DMatrix::from_fn(cells.len(), arrival_states.len(), |i, j| cells[i][j][k]);
+
+        Ok(PorkchopPlot {
+            departure_epochs: departure_epochs.to_vec(),
+            arrival_epochs: arrival_epochs.to_vec(),
+            c3_km2_s2: grid(0),
+            vinf_departure_km_s: grid(1),
+            vinf_arrival_km_s: grid(2),
+            tof_days: grid(3),
+        })
+    }
+}
+
+impl PorkchopPlot {
+    /// Returns the departure epoch, arrival epoch, and C3 of the minimum C3 transfer, if any transfer succeeded.
+    pub fn min_c3(&self) -> Option<(Epoch, Epoch, f64)> {
+        let mut best: Option<(Epoch, Epoch, f64)> = None;
+        for i in 0..self.c3_km2_s2.nrows() {
+            for j in 0..self.c3_km2_s2.ncols() {
+                let c3 = self.c3_km2_s2[(i, j)];
+                if c3.is_finite() && best.map_or(true, |(_, _, best_c3)| c3 < best_c3) {
+                    best = Some((self.departure_epochs[i], self.arrival_epochs[j], c3));
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(feature = "python")]
+fn to_numpy<'py>(py: Python<'py>, grid: &DMatrix<f64>) -> Bound<'py, PyArray2<f64>> {
+    // Create an ndarray Array2 (row-major order)
+    let array = Array2::from_shape_fn((grid.nrows(), grid.ncols()), |(i, j)| grid[(i, j)]);
+    PyArray2::<f64>::from_owned_array(py, array)
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Almanac {
+    /// Computes the porkchop plot grids of a transfer between the `departure` and `arrival` bodies, as seen from the `central` body.
+    /// Rows correspond to the departure epochs and columns to the arrival epochs.
+    ///
+    /// :type departure: Frame
+    /// :type arrival: Frame
+    /// :type central: Frame
+    /// :type departure_epochs: typing.List
+    /// :type arrival_epochs: typing.List
+    /// :rtype: PorkchopPlot
+    #[pyo3(name = "porkchop")]
+    fn py_porkchop(
+        &self,
+        departure: Frame,
+        arrival: Frame,
+        central: Frame,
+        departure_epochs: Vec<Epoch>,
+        arrival_epochs: Vec<Epoch>,
+    ) -> AnalysisResult<PorkchopPlot> {
+        self.porkchop(
+            departure,
+            arrival,
+            central,
+            &departure_epochs,
+            &arrival_epochs,
+            TransferKind::Auto,
+        )
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PorkchopPlot {
+    /// :rtype: typing.List
+    #[getter]
+    fn get_departure_epochs(&self) -> Vec<Epoch> {
+        self.departure_epochs.clone()
+    }
+
+    /// :rtype: typing.List
+    #[getter]
+    fn get_arrival_epochs(&self) -> Vec<Epoch> {
+        self.arrival_epochs.clone()
+    }
+
+    /// :rtype: numpy.array
+    #[getter]
+    fn get_c3_km2_s2<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        to_numpy(py, &self.c3_km2_s2)
+    }
+
+    /// :rtype: numpy.array
+    #[getter]
+    fn get_vinf_departure_km_s<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        to_numpy(py, &self.vinf_departure_km_s)
+    }
+
+    /// :rtype: numpy.array
+    #[getter]
+    fn get_vinf_arrival_km_s<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        to_numpy(py, &self.vinf_arrival_km_s)
+    }
+
+    /// :rtype: numpy.array
+    #[getter]
+    fn get_tof_days<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        to_numpy(py, &self.tof_days)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PorkchopPlot: {} departures x {} arrivals (@{self:p})",
+            self.departure_epochs.len(),
+            self.arrival_epochs.len()
+        )
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Duration, Epoch, Unit};
+use snafu::prelude::*;
+
+use super::{AlmanacAnalysisSnafu, AnalysisResult, PhysicsAnalysisSnafu};
+use crate::almanac::Almanac;
+use crate::astro::Aberration;
+use crate::errors::AlmanacResult;
+use crate::math::cartesian::CartesianState;
+use crate::math::covariance::{Covariance, ElementSet};
+use crate::math::{Matrix6, Vector6};
+use crate::prelude::Frame;
+
+/// Relative step used in the central finite differencing of the state sensitivities.
+const SENSITIVITY_FD_REL_STEP: f64 = 1e-7;
+
+/// Epoch step in seconds used by default in the central finite differencing of the epoch sensitivities.
+const SENSITIVITY_FD_EPOCH_STEP_S: f64 = 0.1;
+
+/// Computes the Jacobian of the output state of the provided chain of Almanac queries with respect to its input state, i.e. d(output)/d(input),
+/// using central finite differencing on each Cartesian component of the input state.
+///
+/// The chain may be any function of the input state, e.g. a transform into another frame, a translation to another observer with light time corrections,
+/// or a sequence thereof.
+pub fn state_sensitivity<F>(state: &CartesianState, chain: F) -> AnalysisResult<Matrix6>
+where
+    F: Fn(CartesianState) -> AlmanacResult<CartesianState>,
+{
+    let components = state.to_cartesian_pos_vel();
+    let mut jacobian = Matrix6::zeros();
+
+    for j in 0..6 {
+        let step = SENSITIVITY_FD_REL_STEP * components[j].abs().max(1.0);
+
+        let mut plus = *state;
+        let mut minus = *state;
+        if j < 3 {
+            plus.radius_km[j] += step;
+            minus.radius_km[j] -= step;
+        } else {
+            plus.velocity_km_s[j - 3] += step;
+            minus.velocity_km_s[j - 3] -= step;
+        }
+
+        let delta = chain(plus)
+            .context(AlmanacAnalysisSnafu {
+                action: "computing state sensitivity",
+            })?
+            .to_cartesian_pos_vel()
+            - chain(minus)
+                .context(AlmanacAnalysisSnafu {
+                    action: "computing state sensitivity",
+                })?
+                .to_cartesian_pos_vel();
+
+        jacobian.set_column(j, &(delta / (2.0 * step)));
+    }
+
+    Ok(jacobian)
+}
+
+/// Computes the time derivative of the output state of the provided chain of Almanac queries with respect to the epoch at which it is evaluated,
+/// using central finite differencing with the provided step (defaults to 0.1 seconds). The returned vector is in km/s and km/s^2.
+///
+/// This maps a time tag error into a state error, e.g. the sensitivity of a ground station position in the inertial frame to a clock offset.
+pub fn epoch_sensitivity<F>(
+    epoch: Epoch,
+    step: Option<Duration>,
+    chain: F,
+) -> AnalysisResult<Vector6>
+where
+    F: Fn(Epoch) -> AlmanacResult<CartesianState>,
+{
+    let step = step.unwrap_or(Unit::Second * SENSITIVITY_FD_EPOCH_STEP_S);
+
+    let plus = chain(epoch + step).context(AlmanacAnalysisSnafu {
+        action: "computing epoch sensitivity",
+    })?;
+    let minus = chain(epoch - step).context(AlmanacAnalysisSnafu {
+        action: "computing epoch sensitivity",
+    })?;
+
+    Ok((plus.to_cartesian_pos_vel() - minus.to_cartesian_pos_vel())
+        / (2.0 * step.to_unit(Unit::Second)))
+}
+
+impl Almanac {
+    /// Returns the Jacobian of `transform_to` with respect to the input state, i.e. how an offset in the input state maps into the observer frame.
+    ///
+    /// Without aberration corrections, this is the 6x6 state transformation matrix between both frames (cf. `DCM::state_dcm`).
+    pub fn transform_state_sensitivity(
+        &self,
+        state: CartesianState,
+        observer_frame: Frame,
+        ab_corr: Option<Aberration>,
+    ) -> AnalysisResult<Matrix6> {
+        state_sensitivity(&state, |state| {
+            self.transform_to(state, observer_frame, ab_corr)
+        })
+    }
+
+    /// Returns the time derivative of `transform_to` with respect to the epoch of the input state, where the input state is held fixed in its own frame.
+    pub fn transform_epoch_sensitivity(
+        &self,
+        state: CartesianState,
+        observer_frame: Frame,
+        ab_corr: Option<Aberration>,
+    ) -> AnalysisResult<Vector6> {
+        epoch_sensitivity(state.epoch, None, |epoch| {
+            self.transform_to(CartesianState { epoch, ..state }, observer_frame, ab_corr)
+        })
+    }
+
+    /// Maps the covariance of the provided state into the observer frame, linearized with the Jacobian of `transform_to`: P' = J P J^T.
+    ///
+    /// The covariance may be provided in any element set: it is converted to Cartesian about the input state first (which requires the gravitational
+    /// parameter of its frame if not Cartesian). The returned covariance is Cartesian and expressed in the observer frame, along with the transformed state.
+    pub fn transform_covariance(
+        &self,
+        state: CartesianState,
+        covariance: &Covariance,
+        observer_frame: Frame,
+        ab_corr: Option<Aberration>,
+    ) -> AnalysisResult<(CartesianState, Covariance)> {
+        let cartesian =
+            covariance
+                .convert(ElementSet::Cartesian, &state)
+                .context(PhysicsAnalysisSnafu {
+                    action: "converting covariance to Cartesian",
+                })?;
+
+        let jacobian = self.transform_state_sensitivity(state, observer_frame, ab_corr)?;
+
+        let transformed =
+            self.transform_to(state, observer_frame, ab_corr)
+                .context(AlmanacAnalysisSnafu {
+                    action: "transforming state of covariance",
+                })?;
+
+        Ok((
+            transformed,
+            Covariance::new(
+                jacobian * cartesian.matrix * jacobian.transpose(),
+                ElementSet::Cartesian,
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod ut_sensitivity {
+    use super::*;
+    use crate::constants::frames::{EARTH_J2000, IAU_EARTH_FRAME, MOON_J2000};
+    use crate::math::Matrix3;
+
+    fn almanac() -> Almanac {
+        Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap()
+    }
+
+    #[test]
+    fn transform_sensitivities() {
+        let almanac = almanac();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+
+        let state = CartesianState::new(
+            -2436.45,
+            -2436.45,
+            6891.037,
+            5.088_611,
+            -5.088_611,
+            0.0,
+            epoch,
+            EARTH_J2000,
+        );
+
+        // The sensitivity of a rotation is its state transformation matrix.
+        let jacobian = almanac
+            .transform_state_sensitivity(state, IAU_EARTH_FRAME, None)
+            .unwrap();
+        let dcm = almanac.rotate(EARTH_J2000, IAU_EARTH_FRAME, epoch).unwrap();
+        assert!(
+            (jacobian - dcm.state_dcm()).norm() < 1e-6,
+            "{jacobian} != {}",
+            dcm.state_dcm()
+        );
+
+        // Moving the epoch of a state fixed wrt the Earth changes its position wrt the Moon by the velocity of the Moon.
+        let sensitivity = almanac
+            .transform_epoch_sensitivity(state, MOON_J2000, None)
+            .unwrap();
+        let moon = almanac
+            .transform(MOON_J2000, EARTH_J2000, epoch, None)
+            .unwrap();
+        let dr = sensitivity.fixed_rows::<3>(0);
+        assert!((dr + moon.velocity_km_s).norm() < 1e-6, "{dr}");
+        // ... and its velocity by the acceleration of the Moon, about 2.7e-6 km/s^2
+        let dv = sensitivity.fixed_rows::<3>(3).norm();
+        assert!(dv > 2e-6 && dv < 4e-6, "{dv}");
+
+        // An isotropic position covariance remains isotropic through a rotation.
+        let covariance = Covariance::new(
+            Matrix6::from_diagonal(&Vector6::new(1.0, 1.0, 1.0, 1e-6, 1e-6, 1e-6)),
+            ElementSet::Cartesian,
+        );
+        let (bf_state, bf_cov) = almanac
+            .transform_covariance(state, &covariance, IAU_EARTH_FRAME, None)
+            .unwrap();
+        assert_eq!(
+            bf_state.frame.orientation_id,
+            IAU_EARTH_FRAME.orientation_id
+        );
+        let pos_cov = bf_cov.matrix.fixed_view::<3, 3>(0, 0);
+        assert!((pos_cov - Matrix3::identity()).norm() < 1e-6, "{pos_cov}");
+        assert!((bf_cov.matrix - bf_cov.matrix.transpose()).norm() < 1e-12);
+    }
+}
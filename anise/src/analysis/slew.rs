@@ -0,0 +1,288 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch, Unit};
+use snafu::prelude::*;
+
+use super::{
+    AlmanacAnalysisSnafu, AnalysisError, AnalysisResult, InvalidSetupSnafu, PhysicsAnalysisSnafu,
+};
+use crate::almanac::Almanac;
+use crate::errors::OrientationSnafu;
+use crate::math::rotation::{Quaternion, DCM};
+use crate::math::Vector3;
+use crate::orientations::profiles::AttitudeProfile;
+use crate::structure::spacecraft::SpacecraftData;
+
+/// Number of iterations on the slew duration when slewing toward a moving attitude.
+const SLEW_ITERATIONS: usize = 5;
+
+/// Actuator limits of a spacecraft, used to estimate the duration of its slews.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SlewLimits {
+    /// Maximum angular rate about the slew axis, in degrees per second
+    pub max_rate_deg_s: f64,
+    /// Maximum torque about the slew axis, in Newton meters
+    pub max_torque_nm: f64,
+}
+
+/// Estimate of a rest-to-rest eigenaxis slew between two attitudes.
+///
+/// The slew accelerates at the maximum torque, coasts at the maximum rate if it is reached, and decelerates at the maximum torque.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SlewEstimate {
+    /// Eigenaxis of the slew, in the body frame
+    pub axis: Vector3,
+    /// Rotation angle about the eigenaxis, in degrees
+    pub angle_deg: f64,
+    /// Moment of inertia about the eigenaxis, in kg m^2
+    pub inertia_kgm2: f64,
+    /// Minimum duration of the slew
+    pub duration: Duration,
+    /// Duration of the coast at the maximum rate, zero if that rate is never reached
+    pub coast: Duration,
+}
+
+impl SlewEstimate {
+    /// Estimates the minimum duration of the rest-to-rest eigenaxis slew between both attitudes (rotations from the same
+    /// frame to the body frame), given the inertia tensor of the spacecraft data and the actuator limits.
+    ///
+    /// # Errors
+    /// + If the spacecraft data has no inertia, or if its inertia is not expressed in the body frame of the attitudes.
+    /// + If the attitudes are not from the same frame to the same body frame.
+    /// + If the limits are not positive.
+    pub fn from_attitudes(
+        from: &DCM,
+        to: &DCM,
+        spacecraft: &SpacecraftData,
+        limits: &SlewLimits,
+    ) -> AnalysisResult<Self> {
+        ensure!(
+            limits.max_rate_deg_s > 0.0 && limits.max_torque_nm > 0.0,
+            InvalidSetupSnafu {
+                err: format!("slew limits must be positive, got {limits:?}")
+            }
+        );
+        let inertia = spacecraft.inertia.context(InvalidSetupSnafu {
+            err: "slew estimation requires the inertia of the spacecraft".to_string(),
+        })?;
+        ensure!(
+            from.to == to.to && inertia.orientation_id == from.to,
+            InvalidSetupSnafu {
+                err: format!(
+                    "slew from {} to {} with inertia in {}: all must be in the same body frame",
+                    from.to, to.to, inertia.orientation_id
+                )
+            }
+        );
+
+        // Rotation from the initial body attitude to the final body attitude
+        let delta = (*to * from.transpose()).context(PhysicsAnalysisSnafu {
+            action: "computing slew rotation",
+        })?;
+        let (axis, angle_rad) = Quaternion::from(delta).short().uvec_angle();
+
+        if angle_rad.abs() < f64::EPSILON {
+            return Ok(Self {
+                axis,
+                angle_deg: 0.0,
+                inertia_kgm2: 0.0,
+                duration: Duration::ZERO,
+                coast: Duration::ZERO,
+            });
+        }
+
+        let inertia_kgm2 = axis.dot(&(inertia.tensor_kgm2() * axis));
+        ensure!(
+            inertia_kgm2 > 0.0,
+            InvalidSetupSnafu {
+                err: format!("inertia about the slew axis must be positive, got {inertia_kgm2}")
+            }
+        );
+
+        let max_accel_rad_s2 = limits.max_torque_nm / inertia_kgm2;
+        let max_rate_rad_s = limits.max_rate_deg_s.to_radians();
+
+        // The maximum rate is reached if accelerating then decelerating covers less than the slew angle
+        let (duration_s, coast_s) = if angle_rad <= max_rate_rad_s.powi(2) / max_accel_rad_s2 {
+            (2.0 * (angle_rad / max_accel_rad_s2).sqrt(), 0.0)
+        } else {
+            let coast_s = angle_rad / max_rate_rad_s - max_rate_rad_s / max_accel_rad_s2;
+            (coast_s + 2.0 * max_rate_rad_s / max_accel_rad_s2, coast_s)
+        };
+
+        Ok(Self {
+            axis,
+            angle_deg: angle_rad.to_degrees(),
+            inertia_kgm2,
+            duration: duration_s * Unit::Second,
+            coast: coast_s * Unit::Second,
+        })
+    }
+
+    /// Returns whether this slew completes within the provided duration, e.g. the gap between two observations.
+    pub fn fits_in(&self, available: Duration) -> bool {
+        self.duration <= available
+    }
+
+    /// Returns the event of the completion of this slew if it starts at the provided epoch.
+    pub fn complete(&self, start: Epoch) -> SlewComplete {
+        SlewComplete {
+            start,
+            epoch: start + self.duration,
+            angle_deg: self.angle_deg,
+        }
+    }
+}
+
+/// Event of the completion of a slew, from which the spacecraft is in its new attitude.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SlewComplete {
+    /// Start of the slew
+    pub start: Epoch,
+    /// End of the slew
+    pub epoch: Epoch,
+    /// Rotation angle of the slew, in degrees
+    pub angle_deg: f64,
+}
+
+impl fmt::Display for SlewComplete {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "slew of {:.3} deg from {} complete at {} ({})",
+            self.angle_deg,
+            self.start,
+            self.epoch,
+            self.epoch - self.start
+        )
+    }
+}
+
+impl Almanac {
+    /// Returns the completion of the slew of the spacecraft starting at the provided epoch from the attitude of the `from`
+    /// profile to the attitude of the `to` profile.
+    ///
+    /// The target attitude is that of the `to` profile at the end of the slew, which is iterated upon since it may move during the slew.
+    pub fn slew_complete(
+        &self,
+        from: &AttitudeProfile,
+        to: &AttitudeProfile,
+        start: Epoch,
+        spacecraft: &SpacecraftData,
+        limits: &SlewLimits,
+    ) -> AnalysisResult<SlewComplete> {
+        let attitude = |profile: &AttitudeProfile, epoch: Epoch| {
+            self.attitude_dcm(profile, epoch)
+                .context(OrientationSnafu {
+                    action: "computing attitude for slew",
+                })
+                .context(AlmanacAnalysisSnafu {
+                    action: "estimating slew",
+                })
+        };
+
+        let initial = attitude(from, start)?;
+        let mut estimate =
+            SlewEstimate::from_attitudes(&initial, &attitude(to, start)?, spacecraft, limits)?;
+        for _ in 0..SLEW_ITERATIONS {
+            let next = SlewEstimate::from_attitudes(
+                &initial,
+                &attitude(to, start + estimate.duration)?,
+                spacecraft,
+                limits,
+            )?;
+            let converged = (next.duration - estimate.duration).abs() < Unit::Millisecond * 1;
+            estimate = next;
+            if converged {
+                return Ok(estimate.complete(start));
+            }
+        }
+
+        Err(AnalysisError::InvalidSetup {
+            err: format!(
+                "slew duration did not converge after {SLEW_ITERATIONS} iterations: the target attitude moves too fast"
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod ut_slew {
+    use super::{SlewEstimate, SlewLimits};
+    use crate::math::rotation::DCM;
+    use crate::structure::spacecraft::{Inertia, SpacecraftData};
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn eigenaxis_slews() {
+        let spacecraft = SpacecraftData {
+            inertia: Some(Inertia {
+                orientation_id: -100,
+                i_xx_kgm2: 100.0,
+                i_yy_kgm2: 200.0,
+                i_zz_kgm2: 300.0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let limits = SlewLimits {
+            max_rate_deg_s: 1.0,
+            max_torque_nm: 0.1,
+        };
+
+        let from = DCM::identity(1, -100);
+
+        // Short slew about Z: the maximum rate is never reached
+        let to = DCM::r3(2f64.to_radians(), 1, -100);
+        let slew = SlewEstimate::from_attitudes(&from, &to, &spacecraft, &limits).unwrap();
+        assert!((slew.angle_deg - 2.0).abs() < 1e-9);
+        assert!((slew.inertia_kgm2 - 300.0).abs() < 1e-9);
+        assert_eq!(slew.coast, Unit::Second * 0);
+        let expected_s = 2.0 * (2f64.to_radians() / (0.1 / 300.0)).sqrt();
+        assert!((slew.duration.to_seconds() - expected_s).abs() < 1e-6);
+
+        // Long slew about X: accelerate, coast, then decelerate
+        let to = DCM::r1(90f64.to_radians(), 1, -100);
+        let slew = SlewEstimate::from_attitudes(&from, &to, &spacecraft, &limits).unwrap();
+        assert!((slew.inertia_kgm2 - 100.0).abs() < 1e-9);
+        let accel_s = 1f64.to_radians() / (0.1 / 100.0);
+        assert!((slew.coast.to_seconds() - (90.0 - accel_s)).abs() < 1e-6);
+        assert!((slew.duration.to_seconds() - (90.0 + accel_s)).abs() < 1e-6);
+        assert!(slew.fits_in(Unit::Minute * 2));
+        assert!(!slew.fits_in(Unit::Minute * 1));
+
+        let start = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let complete = slew.complete(start);
+        assert_eq!(complete.epoch, start + slew.duration);
+        println!("{complete}");
+
+        // Invalid setups
+        assert!(
+            SlewEstimate::from_attitudes(&from, &to, &SpacecraftData::default(), &limits).is_err()
+        );
+        assert!(
+            SlewEstimate::from_attitudes(&from, &DCM::identity(1, -200), &spacecraft, &limits)
+                .is_err()
+        );
+        assert!(SlewEstimate::from_attitudes(
+            &from,
+            &to,
+            &spacecraft,
+            &SlewLimits {
+                max_rate_deg_s: 0.0,
+                max_torque_nm: 0.1
+            }
+        )
+        .is_err());
+    }
+}
@@ -16,6 +16,8 @@ use crate::{
 
 use core::fmt;
 
+use hifitime::Duration;
+
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 use snafu::ensure;
@@ -41,6 +43,13 @@ use crate::errors::PhysicsError;
 /// The validation test `validate_jplde_de440s_aberration_lt` checks 101,000 pairs of ephemeris computations and shows that the unconverged Light Time computation matches the SPICE computations almost all the time.
 /// More specifically, the 99th percentile of error is less than 5 meters, the 75th percentile is less than one meter, and the median error is less than 2 millimeters.
 ///
+/// All eight corrections (LT, LT+S, CN, CN+S, XLT, XLT+S, XCN, XCN+S) match SPICE's `spkezr` to machine precision in position for the Moon
+/// as seen from the Earth Moon barycenter (test `de440s_translation_verif_aberrations`). Converged corrections iterate the light time three times,
+/// like SPICE. The validation tests `validate_jplde_de440s_aberration_cn_s` and `validate_jplde_de440s_aberration_xcn_s` hold the converged
+/// corrections with stellar aberration to the same accuracy targets as the unconverged light time correction.
+///
+/// Use `translate_with_diagnostics` to retrieve the light time, the number of iterations, and the stellar correction angle of a given query.
+///
 /// :type name: str
 /// :rtype: Aberration
 #[derive(Copy, Clone, Default, PartialEq, Eq)]
@@ -246,6 +255,87 @@ impl fmt::Display for Aberration {
     }
 }
 
+/// Diagnostics of the aberration corrections applied in a single translation query.
+///
+/// This is useful to check how far the light time iteration converged and how large the stellar aberration correction was.
+///
+/// :type ab_corr: Aberration, optional
+/// :type light_time: Duration
+/// :type iterations: int
+/// :type light_time_residual: Duration
+/// :type stellar_correction_deg: float
+/// :rtype: AberrationDiagnostics
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise.astro"))]
+pub struct AberrationDiagnostics {
+    /// Aberration correction that was applied, if any
+    pub ab_corr: Option<Aberration>,
+    /// One-way light time between the observer and the target, geometric if no correction was applied
+    pub light_time: Duration,
+    /// Number of light time iterations performed: zero without correction, one for unconverged and three for converged corrections
+    pub iterations: u8,
+    /// Change in the light time during the last iteration, i.e. an upper bound of the remaining light time error
+    pub light_time_residual: Duration,
+    /// Angle between the light time corrected position and the apparent position, zero without stellar aberration
+    pub stellar_correction_deg: f64,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl AberrationDiagnostics {
+    /// :rtype: Aberration
+    #[getter]
+    fn get_ab_corr(&self) -> PyResult<Option<Aberration>> {
+        Ok(self.ab_corr)
+    }
+
+    /// :rtype: Duration
+    #[getter]
+    fn get_light_time(&self) -> PyResult<Duration> {
+        Ok(self.light_time)
+    }
+
+    /// :rtype: int
+    #[getter]
+    fn get_iterations(&self) -> PyResult<u8> {
+        Ok(self.iterations)
+    }
+
+    /// :rtype: Duration
+    #[getter]
+    fn get_light_time_residual(&self) -> PyResult<Duration> {
+        Ok(self.light_time_residual)
+    }
+
+    /// :rtype: float
+    #[getter]
+    fn get_stellar_correction_deg(&self) -> PyResult<f64> {
+        Ok(self.stellar_correction_deg)
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+impl fmt::Display for AberrationDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ab_corr {
+            None => write!(f, "geometric: light time = {}", self.light_time),
+            Some(ab_corr) => write!(
+                f,
+                "{ab_corr:?}: light time = {} after {} iteration(s) (residual = {}), stellar correction = {:.6e} deg",
+                self.light_time, self.iterations, self.light_time_residual, self.stellar_correction_deg
+            ),
+        }
+    }
+}
+
 /// Returns the provided target [Orbit] with respect to any observer corrected for steller aberration.
 ///
 /// # Arguments
@@ -0,0 +1,284 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, TimeUnits};
+use nalgebra::Matrix3x6;
+use snafu::ensure;
+
+use super::orbit::Orbit;
+use super::PhysicsResult;
+use crate::errors::{PhysicsError, RadiusSnafu};
+use crate::frames::Frame;
+use crate::math::rotation::DCM;
+use crate::math::{Matrix3, Vector3};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Relative step used in the central finite differencing of the B-plane partials.
+const BPLANE_FD_REL_STEP: f64 = 1e-7;
+
+/// Stores the B-plane parameters of a hyperbolic orbit.
+///
+/// The B-plane is the plane perpendicular to the incoming asymptote (S) and containing the center of the central body.
+/// The T axis is along S x Z (where Z is the Z axis of the frame of the orbit), and R completes the right-handed set.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise.astro"))]
+pub struct BPlane {
+    /// B-plane vector component along the R axis, in km
+    pub b_r_km: f64,
+    /// B-plane vector component along the T axis, in km
+    pub b_t_km: f64,
+    /// Linearized time of flight, i.e. the time until periapsis passage on the hyperbola
+    pub ltof: Duration,
+    /// Partials of [B.R (km), B.T (km), LTOF (s)] with respect to the Cartesian state of the orbit
+    pub jacobian: Matrix3x6<f64>,
+    /// Rotation from the inertial frame to the STR frame
+    pub str_dcm: DCM,
+    /// Frame in which this B-plane was computed
+    pub frame: Frame,
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl BPlane {
+    /// Returns the magnitude of the B vector in km
+    ///
+    /// :rtype: float
+    pub fn b_mag_km(&self) -> f64 {
+        (self.b_r_km.powi(2) + self.b_t_km.powi(2)).sqrt()
+    }
+
+    /// Returns the B-plane angle in degrees, measured from the T axis towards the R axis
+    ///
+    /// :rtype: float
+    pub fn b_theta_deg(&self) -> f64 {
+        self.b_r_km.atan2(self.b_t_km).to_degrees()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl BPlane {
+    /// :rtype: float
+    #[getter]
+    fn get_b_r_km(&self) -> f64 {
+        self.b_r_km
+    }
+
+    /// :rtype: float
+    #[getter]
+    fn get_b_t_km(&self) -> f64 {
+        self.b_t_km
+    }
+
+    /// :rtype: Duration
+    #[getter]
+    fn get_ltof(&self) -> Duration {
+        self.ltof
+    }
+
+    /// :rtype: Frame
+    #[getter]
+    fn get_frame(&self) -> Frame {
+        self.frame
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+}
+
+impl fmt::Display for BPlane {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:x}] B-Plane: B∙R = {:.6} km\tB∙T = {:.6} km\t|B| = {:.6} km\tLTOF = {}",
+            self.frame,
+            self.b_r_km,
+            self.b_t_km,
+            self.b_mag_km(),
+            self.ltof
+        )
+    }
+}
+
+/// Returns the B.R (km), B.T (km), LTOF (s), and the STR DCM rotation matrix of the provided Cartesian position and velocity.
+fn b_plane_parts(
+    radius_km: Vector3,
+    velocity_km_s: Vector3,
+    mu_km3_s2: f64,
+) -> PhysicsResult<(f64, f64, f64, Matrix3)> {
+    let rmag_km = radius_km.norm();
+    ensure!(
+        rmag_km > f64::EPSILON,
+        RadiusSnafu {
+            action: "cannot compute B-plane with zero radius"
+        }
+    );
+
+    let hvec = radius_km.cross(&velocity_km_s);
+    let evec = ((velocity_km_s.norm_squared() - mu_km3_s2 / rmag_km) * radius_km
+        - radius_km.dot(&velocity_km_s) * velocity_km_s)
+        / mu_km3_s2;
+    let ecc = evec.norm();
+
+    if ecc <= 1.0 {
+        return Err(PhysicsError::NotHyperbolic { ecc });
+    }
+
+    let e_hat = evec / ecc;
+    let h_hat = hvec / hvec.norm();
+    let n_hat = h_hat.cross(&e_hat);
+
+    let sqrt_term = (1.0 - 1.0 / ecc.powi(2)).sqrt();
+
+    // Incoming asymptote
+    let s_hat = e_hat / ecc + sqrt_term * n_hat;
+    // The B vector magnitude is the semi-minor axis of the hyperbola
+    let b_mag_km = hvec.norm_squared() / (mu_km3_s2 * (ecc.powi(2) - 1.0).sqrt());
+    let b_vec = b_mag_km * (sqrt_term * e_hat - n_hat / ecc);
+
+    let t_hat = s_hat.cross(&Vector3::z()).normalize();
+    let r_hat = s_hat.cross(&t_hat);
+
+    // Time to periapsis from the hyperbolic anomaly
+    let cos_ta = (e_hat.dot(&radius_km) / rmag_km).clamp(-1.0, 1.0);
+    let sin_ta = (1.0 - cos_ta.powi(2))
+        .sqrt()
+        .copysign(radius_km.dot(&velocity_km_s));
+    let hyp_anomaly = (sin_ta * (ecc.powi(2) - 1.0).sqrt() / (1.0 + ecc * cos_ta)).asinh();
+    let sma_km = 1.0 / (2.0 / rmag_km - velocity_km_s.norm_squared() / mu_km3_s2);
+    let mean_motion_rad_s = (mu_km3_s2 / (-sma_km).powi(3)).sqrt();
+    let ltof_s = -(ecc * hyp_anomaly.sinh() - hyp_anomaly) / mean_motion_rad_s;
+
+    let str_mat = Matrix3::from_rows(&[s_hat.transpose(), t_hat.transpose(), r_hat.transpose()]);
+
+    Ok((b_vec.dot(&r_hat), b_vec.dot(&t_hat), ltof_s, str_mat))
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Orbit {
+    /// Computes the B-plane parameters of this hyperbolic orbit, including the partials of B.R, B.T and LTOF
+    /// with respect to this Cartesian state (computed with central finite differencing).
+    ///
+    /// The T axis is defined with respect to the Z axis of the frame of this orbit, so the B-plane is consistent with
+    /// the frame in which the state is expressed (e.g. transform the state to the body's equatorial frame first).
+    ///
+    /// # Errors
+    /// + NotHyperbolic if the orbit is not hyperbolic
+    /// + MissingFrameData if the frame does not have a gravitational parameter
+    ///
+    /// Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., section 12.5.
+    ///
+    /// :rtype: BPlane
+    pub fn b_plane(&self) -> PhysicsResult<BPlane> {
+        let mu_km3_s2 = self.frame.mu_km3_s2()?;
+        let (b_r_km, b_t_km, ltof_s, str_mat) =
+            b_plane_parts(self.radius_km, self.velocity_km_s, mu_km3_s2)?;
+
+        let mut jacobian = Matrix3x6::zeros();
+        let state = self.to_cartesian_pos_vel();
+        for j in 0..6 {
+            let step = BPLANE_FD_REL_STEP * state[j].abs().max(1.0);
+            let mut plus = state;
+            plus[j] += step;
+            let mut minus = state;
+            minus[j] -= step;
+
+            let (br_p, bt_p, ltof_p, _) = b_plane_parts(
+                plus.fixed_rows::<3>(0).into_owned(),
+                plus.fixed_rows::<3>(3).into_owned(),
+                mu_km3_s2,
+            )?;
+            let (br_m, bt_m, ltof_m, _) = b_plane_parts(
+                minus.fixed_rows::<3>(0).into_owned(),
+                minus.fixed_rows::<3>(3).into_owned(),
+                mu_km3_s2,
+            )?;
+
+            jacobian[(0, j)] = (br_p - br_m) / (2.0 * step);
+            jacobian[(1, j)] = (bt_p - bt_m) / (2.0 * step);
+            jacobian[(2, j)] = (ltof_p - ltof_m) / (2.0 * step);
+        }
+
+        Ok(BPlane {
+            b_r_km,
+            b_t_km,
+            ltof: ltof_s.seconds(),
+            jacobian,
+            str_dcm: DCM {
+                rot_mat: str_mat,
+                rot_mat_dt: None,
+                from: self.frame.orientation_id,
+                to: self.frame.orientation_id,
+            },
+            frame: self.frame,
+        })
+    }
+}
+
+#[cfg(test)]
+mod ut_bplane {
+    use super::*;
+    use crate::constants::frames::EARTH_J2000;
+    use crate::prelude::Epoch;
+
+    #[test]
+    fn b_plane_hyperbola() {
+        let eme2k = EARTH_J2000.with_mu_km3_s2(398_600.441_5);
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2000, 1, 1);
+
+        let orbit = Orbit::new(
+            546_507.344_255_845,
+            -527_978.380_486_028,
+            531_109.066_836_708,
+            -4.922_058_926_873_3,
+            5.363_165_230_979_15,
+            -5.221_663_084_251_81,
+            epoch,
+            eme2k,
+        );
+
+        let bplane = orbit.b_plane().unwrap();
+        println!("{bplane}");
+
+        assert!((bplane.b_t_km - 45_892.323_795).abs() < 1e-5);
+        assert!((bplane.b_r_km - 10_606.210_429).abs() < 1e-5);
+        assert!((bplane.b_mag_km() - 47_101.985_977).abs() < 1e-5);
+        // The spacecraft is inbound, so periapsis is in the future.
+        assert!((bplane.ltof.to_seconds() - 102_405.381_787).abs() < 1e-3);
+        // The B-plane vector magnitude is the semi-minor axis
+        assert!((bplane.b_mag_km() - orbit.semi_minor_axis_km().unwrap()).abs() < 1e-6);
+
+        // Check the partials along the velocity with a small perturbation
+        let mut perturbed = orbit;
+        perturbed.velocity_km_s.x += 1e-6;
+        let perturbed_bplane = perturbed.b_plane().unwrap();
+        let expected_db_r = bplane.jacobian[(0, 3)] * 1e-6;
+        assert!(((perturbed_bplane.b_r_km - bplane.b_r_km) - expected_db_r).abs() < 1e-3);
+
+        // The STR DCM is orthonormal
+        assert!(bplane.str_dcm.is_valid(1e-12, 1e-12));
+    }
+
+    #[test]
+    fn b_plane_not_hyperbolic() {
+        let eme2k = EARTH_J2000.with_mu_km3_s2(398_600.441_5);
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2000, 1, 1);
+        let orbit = Orbit::keplerian(7000.0, 0.01, 28.5, 10.0, 20.0, 30.0, epoch, eme2k);
+        assert!(matches!(
+            orbit.b_plane(),
+            Err(PhysicsError::NotHyperbolic { .. })
+        ));
+    }
+}
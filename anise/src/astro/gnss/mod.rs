@@ -0,0 +1,288 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::collections::BTreeSet;
+
+use hifitime::{Duration, Epoch, TimeScale, Unit};
+use snafu::prelude::*;
+
+use crate::constants::frames::EARTH_ITRF93;
+use crate::math::Vector3;
+use crate::prelude::Orbit;
+
+mod rinex;
+mod yuma;
+
+/// Earth gravitational parameter of the GPS broadcast model (IS-GPS-200), in m^3/s^2.
+pub const GPS_MU_M3_S2: f64 = 3.986005e14;
+
+/// Earth rotation rate of the GPS broadcast model (IS-GPS-200), in rad/s.
+pub const GPS_EARTH_ROTATION_RATE_RAD_S: f64 = 7.2921151467e-5;
+
+/// Number of weeks after which the 10-bit week number of the GPS almanacs rolls over.
+const GPS_WEEK_ROLLOVER: u32 = 1024;
+
+/// First week of the current GPS rollover era (since 2019-04-07), used to resolve the 10-bit week numbers of the almanacs.
+const GPS_CURRENT_ERA_WEEK: u32 = 2 * GPS_WEEK_ROLLOVER;
+
+#[derive(Debug, Snafu, PartialEq)]
+#[snafu(visibility(pub(crate)))]
+pub enum GnssError {
+    #[snafu(display("{format} line {lno}: {what}"))]
+    GnssParsing {
+        format: &'static str,
+        lno: usize,
+        what: String,
+    },
+    #[snafu(display("no healthy ephemeris of PRN {prn} at {epoch}"))]
+    NoEphemeris { prn: u8, epoch: Epoch },
+}
+
+/// Orbital parameters of a GPS satellite as broadcast in the navigation message (IS-GPS-200, table 20-III), or as provided by
+/// a Yuma or SEM almanac, in which case the rates and harmonic corrections absent from the almanac are zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BroadcastEphemeris {
+    /// Pseudo-random noise code of the satellite
+    pub prn: u8,
+    /// Reference epoch of the ephemeris (toe), or time of applicability of the almanac, in GPS time
+    pub toe: Epoch,
+    /// Square root of the semi-major axis, in m^(1/2)
+    pub sqrt_a_m: f64,
+    pub ecc: f64,
+    /// Inclination at the reference epoch, in radians
+    pub inc_rad: f64,
+    /// Rate of inclination, in rad/s
+    pub inc_rate_rad_s: f64,
+    /// Longitude of the ascending node at the start of the GPS week, in radians
+    pub raan_rad: f64,
+    /// Rate of right ascension, in rad/s
+    pub raan_rate_rad_s: f64,
+    /// Argument of perigee, in radians
+    pub aop_rad: f64,
+    /// Mean anomaly at the reference epoch, in radians
+    pub ma_rad: f64,
+    /// Mean motion correction, in rad/s
+    pub delta_n_rad_s: f64,
+    /// Amplitudes of the cosine and sine harmonic corrections to the argument of latitude, in radians
+    pub cuc_rad: f64,
+    pub cus_rad: f64,
+    /// Amplitudes of the cosine and sine harmonic corrections to the orbit radius, in meters
+    pub crc_m: f64,
+    pub crs_m: f64,
+    /// Amplitudes of the cosine and sine harmonic corrections to the inclination, in radians
+    pub cic_rad: f64,
+    pub cis_rad: f64,
+    /// Clock bias, in seconds
+    pub af0_s: f64,
+    /// Clock drift, in s/s
+    pub af1_s_s: f64,
+    pub healthy: bool,
+}
+
+impl BroadcastEphemeris {
+    /// Returns the state of the satellite at the provided epoch in the Earth fixed frame (ITRF93, realized by WGS84 in the
+    /// broadcast model), following the user algorithm of IS-GPS-200 (table 20-IV) and its analytical time derivative.
+    pub fn state(&self, epoch: Epoch) -> Orbit {
+        let sma_m = self.sqrt_a_m.powi(2);
+        let tk_s = (epoch - self.toe).to_seconds();
+
+        let mean_motion = (GPS_MU_M3_S2 / sma_m.powi(3)).sqrt() + self.delta_n_rad_s;
+        let ma_rad = self.ma_rad + mean_motion * tk_s;
+
+        // Kepler's equation
+        let mut ea_rad = ma_rad;
+        for _ in 0..30 {
+            let delta =
+                (ma_rad - ea_rad + self.ecc * ea_rad.sin()) / (1.0 - self.ecc * ea_rad.cos());
+            ea_rad += delta;
+            if delta.abs() < 1e-14 {
+                break;
+            }
+        }
+        let (sin_ea, cos_ea) = ea_rad.sin_cos();
+        let ea_rate = mean_motion / (1.0 - self.ecc * cos_ea);
+
+        let sqrt_1me2 = (1.0 - self.ecc.powi(2)).sqrt();
+        let ta_rad = (sqrt_1me2 * sin_ea).atan2(cos_ea - self.ecc);
+        let ta_rate = ea_rate * sqrt_1me2 / (1.0 - self.ecc * cos_ea);
+
+        // Second harmonic corrections
+        let phi = ta_rad + self.aop_rad;
+        let (sin_2phi, cos_2phi) = (2.0 * phi).sin_cos();
+        let u_rad = phi + self.cus_rad * sin_2phi + self.cuc_rad * cos_2phi;
+        let r_m = sma_m * (1.0 - self.ecc * cos_ea) + self.crs_m * sin_2phi + self.crc_m * cos_2phi;
+        let i_rad = self.inc_rad
+            + self.cis_rad * sin_2phi
+            + self.cic_rad * cos_2phi
+            + self.inc_rate_rad_s * tk_s;
+
+        let u_rate = ta_rate * (1.0 + 2.0 * (self.cus_rad * cos_2phi - self.cuc_rad * sin_2phi));
+        let r_rate = sma_m * self.ecc * sin_ea * ea_rate
+            + 2.0 * ta_rate * (self.crs_m * cos_2phi - self.crc_m * sin_2phi);
+        let i_rate = self.inc_rate_rad_s
+            + 2.0 * ta_rate * (self.cis_rad * cos_2phi - self.cic_rad * sin_2phi);
+
+        // Position and velocity in the orbital plane
+        let (sin_u, cos_u) = u_rad.sin_cos();
+        let (xp, yp) = (r_m * cos_u, r_m * sin_u);
+        let xp_rate = r_rate * cos_u - r_m * u_rate * sin_u;
+        let yp_rate = r_rate * sin_u + r_m * u_rate * cos_u;
+
+        // Corrected longitude of the ascending node, accounting for the rotation of the Earth since the start of the week
+        let (_, toe_nanos) = self.toe.to_time_of_week();
+        let toe_sow = toe_nanos as f64 * 1e-9;
+        let raan_rate = self.raan_rate_rad_s - GPS_EARTH_ROTATION_RATE_RAD_S;
+        let raan = self.raan_rad + raan_rate * tk_s - GPS_EARTH_ROTATION_RATE_RAD_S * toe_sow;
+
+        let (sin_raan, cos_raan) = raan.sin_cos();
+        let (sin_i, cos_i) = i_rad.sin_cos();
+
+        let x = xp * cos_raan - yp * cos_i * sin_raan;
+        let y = xp * sin_raan + yp * cos_i * cos_raan;
+        let z = yp * sin_i;
+
+        let vx = xp_rate * cos_raan - yp_rate * cos_i * sin_raan + yp * sin_i * sin_raan * i_rate
+            - raan_rate * y;
+        let vy = xp_rate * sin_raan + yp_rate * cos_i * cos_raan - yp * sin_i * cos_raan * i_rate
+            + raan_rate * x;
+        let vz = yp_rate * sin_i + yp * cos_i * i_rate;
+
+        Orbit {
+            radius_km: Vector3::new(x, y, z) * 1e-3,
+            velocity_km_s: Vector3::new(vx, vy, vz) * 1e-3,
+            epoch,
+            frame: EARTH_ITRF93,
+        }
+    }
+
+    /// Returns the offset of the clock of the satellite with respect to GPS time at the provided epoch, neglecting the
+    /// relativistic correction.
+    pub fn clock_offset(&self, epoch: Epoch) -> Duration {
+        let tk_s = (epoch - self.toe).to_seconds();
+        (self.af0_s + self.af1_s_s * tk_s) * Unit::Second
+    }
+}
+
+/// Broadcast ephemerides or almanac of a GNSS constellation, queryable per PRN, as loaded from RINEX navigation files or
+/// from Yuma or SEM almanacs.
+///
+/// The states are in the Earth fixed ITRF93 frame, so they may directly be used as the receiver state of the azimuth,
+/// elevation, and range computations from ground locations.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GnssEphemerides {
+    pub ephemerides: Vec<BroadcastEphemeris>,
+}
+
+impl GnssEphemerides {
+    /// Returns the sorted PRNs of this constellation.
+    pub fn prns(&self) -> Vec<u8> {
+        self.ephemerides
+            .iter()
+            .map(|eph| eph.prn)
+            .collect::<BTreeSet<u8>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Returns the healthy ephemeris of this PRN whose reference epoch is the closest to the provided epoch, if any.
+    pub fn ephemeris(&self, prn: u8, epoch: Epoch) -> Option<&BroadcastEphemeris> {
+        self.ephemerides
+            .iter()
+            .filter(|eph| eph.prn == prn && eph.healthy)
+            .min_by_key(|eph| (eph.toe - epoch).abs())
+    }
+
+    /// Returns the state of this PRN at the provided epoch in the Earth fixed frame, using the closest healthy ephemeris.
+    pub fn state(&self, prn: u8, epoch: Epoch) -> Result<Orbit, GnssError> {
+        self.ephemeris(prn, epoch)
+            .map(|eph| eph.state(epoch))
+            .context(NoEphemerisSnafu { prn, epoch })
+    }
+
+    /// Returns the states of all of the PRNs with a healthy ephemeris at the provided epoch, sorted by PRN.
+    pub fn states(&self, epoch: Epoch) -> Vec<(u8, Orbit)> {
+        self.prns()
+            .into_iter()
+            .filter_map(|prn| self.state(prn, epoch).ok().map(|state| (prn, state)))
+            .collect()
+    }
+}
+
+/// Returns the GPS time epoch of the provided week and seconds of week.
+fn gps_epoch(week: u32, seconds_of_week: f64) -> Epoch {
+    Epoch::from_time_of_week(week, 0, TimeScale::GPST) + seconds_of_week * Unit::Second
+}
+
+/// Resolves the 10-bit week number of the almanacs into the current GPS rollover era, leaving full week numbers unchanged.
+fn resolve_week(week: u32) -> u32 {
+    if week < GPS_WEEK_ROLLOVER {
+        week + GPS_CURRENT_ERA_WEEK
+    } else {
+        week
+    }
+}
+
+#[cfg(test)]
+mod ut_gnss {
+    use super::{gps_epoch, BroadcastEphemeris, GPS_MU_M3_S2};
+    use hifitime::{Epoch, Unit};
+
+    /// Checks that the analytical velocity matches the central difference of the positions.
+    pub(crate) fn check_velocity(eph: &BroadcastEphemeris, epoch: Epoch) {
+        let state = eph.state(epoch);
+        let before = eph.state(epoch - Unit::Second * 1);
+        let after = eph.state(epoch + Unit::Second * 1);
+        let fd_km_s = (after.radius_km - before.radius_km) / 2.0;
+        assert!(
+            (state.velocity_km_s - fd_km_s).norm() < 1e-6,
+            "{} vs {fd_km_s}",
+            state.velocity_km_s
+        );
+    }
+
+    #[test]
+    fn circular_broadcast_orbit() {
+        let sma_m: f64 = 26_560e3;
+        let eph = BroadcastEphemeris {
+            prn: 1,
+            toe: gps_epoch(2300, 7200.0),
+            sqrt_a_m: sma_m.sqrt(),
+            ecc: 0.0,
+            inc_rad: 55f64.to_radians(),
+            inc_rate_rad_s: 0.0,
+            raan_rad: 1.0,
+            raan_rate_rad_s: -8e-9,
+            aop_rad: 0.0,
+            ma_rad: 0.5,
+            delta_n_rad_s: 0.0,
+            cuc_rad: 0.0,
+            cus_rad: 0.0,
+            crc_m: 0.0,
+            crs_m: 0.0,
+            cic_rad: 0.0,
+            cis_rad: 0.0,
+            af0_s: 1e-4,
+            af1_s_s: 0.0,
+            healthy: true,
+        };
+
+        for hours in [0, 3, 11] {
+            let epoch = eph.toe + Unit::Hour * hours;
+            let state = eph.state(epoch);
+            assert!((state.rmag_km() - sma_m * 1e-3).abs() < 1e-6);
+            check_velocity(&eph, epoch);
+        }
+
+        // GPS satellites complete two orbits per sidereal day.
+        let period_s = 2.0 * core::f64::consts::PI * (sma_m.powi(3) / GPS_MU_M3_S2).sqrt();
+        assert!((period_s / 3600.0 - 11.967).abs() < 1e-2);
+        assert!((eph.clock_offset(eph.toe) - Unit::Microsecond * 100).abs() < Unit::Nanosecond * 1);
+    }
+}
@@ -0,0 +1,225 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::{gps_epoch, BroadcastEphemeris, GnssEphemerides, GnssError};
+
+/// Width of the fixed-width values of the RINEX navigation files.
+const RINEX_FIELD_WIDTH: usize = 19;
+
+/// Number of broadcast orbit lines following the first line of each GPS record.
+const RINEX_GPS_ORBIT_LINES: usize = 7;
+
+impl GnssEphemerides {
+    /// Parses the GPS records of a RINEX navigation file, version 2 (GPS navigation) or version 3 (GPS or mixed navigation).
+    ///
+    /// The records of other constellations in mixed files are skipped.
+    pub fn from_rinex_nav(rinex: &str) -> Result<Self, GnssError> {
+        let lines = rinex.lines().collect::<Vec<&str>>();
+
+        let version = lines
+            .first()
+            .and_then(|line| line.get(..9))
+            .and_then(|version| version.trim().parse::<f64>().ok())
+            .ok_or_else(|| GnssError::GnssParsing {
+                format: "RINEX",
+                lno: 1,
+                what: "expected the RINEX version".to_string(),
+            })?;
+
+        let file_type = lines[0].get(20..21).unwrap_or_default();
+        let system = lines[0].get(40..41).unwrap_or_default();
+        if file_type != "N" || (version >= 3.0 && !matches!(system, "G" | "M" | " " | "")) {
+            return Err(GnssError::GnssParsing {
+                format: "RINEX",
+                lno: 1,
+                what: format!("expected a GPS or mixed navigation file, got type `{file_type}` system `{system}`"),
+            });
+        }
+
+        let body = lines
+            .iter()
+            .position(|line| line.get(60..).unwrap_or_default().trim() == "END OF HEADER")
+            .ok_or_else(|| GnssError::GnssParsing {
+                format: "RINEX",
+                lno: lines.len(),
+                what: "missing END OF HEADER".to_string(),
+            })?
+            + 1;
+
+        // Version 3 records start with the system and PRN (e.g. `G01`) and their values are shifted by one column
+        let (first_col, orbit_col) = if version >= 3.0 { (23, 4) } else { (22, 3) };
+
+        let mut ephemerides = Vec::new();
+        let mut lno = body;
+        while lno < lines.len() {
+            let line = lines[lno];
+            if line.trim().is_empty() {
+                lno += 1;
+                continue;
+            }
+
+            let (system, prn) = if version >= 3.0 {
+                (line.get(..1).unwrap_or_default(), line.get(1..3))
+            } else {
+                ("G", line.get(..2))
+            };
+
+            if system != "G" {
+                // GLONASS and SBAS records have three orbit lines, the other constellations have seven
+                lno += 1 + if matches!(system, "R" | "S") { 3 } else { 7 };
+                continue;
+            }
+
+            let prn = prn
+                .and_then(|prn| prn.trim().parse::<u8>().ok())
+                .ok_or_else(|| GnssError::GnssParsing {
+                    format: "RINEX",
+                    lno: lno + 1,
+                    what: format!("invalid PRN in `{line}`"),
+                })?;
+
+            let mut values = Vec::with_capacity(3 + 4 * RINEX_GPS_ORBIT_LINES);
+            for k in 0..3 {
+                values.push(field(line, first_col + k * RINEX_FIELD_WIDTH, lno)?);
+            }
+            for orbit_lno in lno + 1..=lno + RINEX_GPS_ORBIT_LINES {
+                let orbit = lines.get(orbit_lno).ok_or_else(|| GnssError::GnssParsing {
+                    format: "RINEX",
+                    lno: orbit_lno + 1,
+                    what: format!("truncated record of PRN {prn}"),
+                })?;
+                for k in 0..4 {
+                    values.push(field(orbit, orbit_col + k * RINEX_FIELD_WIDTH, orbit_lno)?);
+                }
+            }
+            lno += 1 + RINEX_GPS_ORBIT_LINES;
+
+            // Clock (af0, af1, af2), then the broadcast orbits 1 to 7 (IODE, Crs, Delta n, M0), (Cuc, e, Cus, sqrt(A)),
+            // (Toe, Cic, OMEGA0, Cis), (i0, Crc, omega, OMEGA DOT), (IDOT, codes on L2, GPS week, L2 P flag),
+            // (accuracy, health, TGD, IODC), (transmission time, fit interval).
+            let v = &values[3..];
+            ephemerides.push(BroadcastEphemeris {
+                prn,
+                toe: gps_epoch(v[18] as u32, v[8]),
+                sqrt_a_m: v[7],
+                ecc: v[5],
+                inc_rad: v[12],
+                inc_rate_rad_s: v[16],
+                raan_rad: v[10],
+                raan_rate_rad_s: v[15],
+                aop_rad: v[14],
+                ma_rad: v[3],
+                delta_n_rad_s: v[2],
+                cuc_rad: v[4],
+                cus_rad: v[6],
+                crc_m: v[13],
+                crs_m: v[1],
+                cic_rad: v[9],
+                cis_rad: v[11],
+                af0_s: values[0],
+                af1_s_s: values[1],
+                healthy: v[21] == 0.0,
+            });
+        }
+
+        Ok(Self { ephemerides })
+    }
+}
+
+/// Parses the Fortran formatted value starting at the provided column, where blank or missing values are zero.
+fn field(line: &str, col: usize, lno: usize) -> Result<f64, GnssError> {
+    let repr = line
+        .get(col..(col + RINEX_FIELD_WIDTH).min(line.len()))
+        .unwrap_or_default()
+        .trim();
+    if repr.is_empty() {
+        return Ok(0.0);
+    }
+    repr.replace(['D', 'd'], "E")
+        .parse::<f64>()
+        .map_err(|_| GnssError::GnssParsing {
+            format: "RINEX",
+            lno: lno + 1,
+            what: format!("invalid value `{repr}` at column {}", col + 1),
+        })
+}
+
+#[cfg(test)]
+mod ut_rinex {
+    use super::super::ut_gnss::check_velocity;
+    use super::super::GnssEphemerides;
+    use hifitime::Unit;
+
+    // Example of the RINEX 2.10 specification (table A4)
+    const RINEX2: &str =
+        "     2.10           N: GPS NAV DATA                         RINEX VERSION / TYPE
+XXRINEXN V2.10      AIUB                3-SEP-99 15:22      PGM / RUN BY / DATE
+                                                            END OF HEADER
+ 6 99  9  2 17 51 44.0 -.839701388031D-03 -.165982783074D-10  .000000000000D+00
+     .910000000000D+02  .934062500000D+02  .116040547840D-08  .162092304801D+00
+     .484101474285D-05  .626740418375D-02  .652112066746D-05  .515365489006D+04
+     .409904000000D+06 -.242143869400D-07  .329237003460D+00 -.596046447754D-07
+     .111541663136D+01  .326593750000D+03  .206958726335D+01 -.638312302555D-08
+     .307155651409D-09  .000000000000D+00  .102500000000D+04  .000000000000D+00
+     .000000000000D+00  .000000000000D+00  .000000000000D+00  .910000000000D+02
+     .406800000000D+06  .000000000000D+00
+";
+
+    // The same record in a mixed RINEX 3 file, after a GLONASS record
+    const RINEX3: &str =
+        "     3.04           N: GNSS NAV DATA    M: Mixed            RINEX VERSION / TYPE
+                                                            END OF HEADER
+R01 1999 09 02 17 45 00 1.000000000000D-05 0.000000000000D+00 3.000000000000D+05
+     1.200000000000D+04 1.000000000000D+00 0.000000000000D+00 0.000000000000D+00
+    -1.000000000000D+04 2.000000000000D+00 0.000000000000D+00 1.000000000000D+00
+     2.000000000000D+04 1.500000000000D+00 0.000000000000D+00 0.000000000000D+00
+G06 1999 09 02 17 51 44-8.397013880310D-04-1.659827830740D-11 0.000000000000D+00
+     9.100000000000D+01 9.340625000000D+01 1.160405478400D-09 1.620923048010D-01
+     4.841014742850D-06 6.267404183750D-03 6.521120667460D-06 5.153654890060D+03
+     4.099040000000D+05-2.421438694000D-08 3.292370034600D-01-5.960464477540D-08
+     1.115416631360D+00 3.265937500000D+02 2.069587263350D+00-6.383123025550D-09
+     3.071556514090D-10 0.000000000000D+00 1.025000000000D+03 0.000000000000D+00
+     0.000000000000D+00 0.000000000000D+00 0.000000000000D+00 9.100000000000D+01
+     4.068000000000D+05 0.000000000000D+00
+";
+
+    #[test]
+    fn rinex_nav() {
+        let v2 = GnssEphemerides::from_rinex_nav(RINEX2).unwrap();
+        assert_eq!(v2.prns(), vec![6]);
+        let eph = v2.ephemerides[0];
+        assert_eq!(eph.toe.to_time_of_week(), (1025, 409_904_000_000_000));
+        assert!((eph.ecc - 0.626740418375e-2).abs() < 1e-15);
+        assert!((eph.crs_m - 93.40625).abs() < 1e-12);
+        assert!((eph.af0_s + 0.839701388031e-3).abs() < 1e-15);
+        assert!(eph.healthy);
+
+        let state = v2.state(6, eph.toe + Unit::Minute * 30).unwrap();
+        assert!((state.rmag_km() - 26_560.0).abs() < 200.0);
+        check_velocity(&eph, eph.toe + Unit::Minute * 30);
+
+        let v3 = GnssEphemerides::from_rinex_nav(RINEX3).unwrap();
+        assert_eq!(v3.prns(), vec![6]);
+        let eph3 = v3.ephemerides[0];
+        assert_eq!(eph3.toe, eph.toe);
+        assert!(
+            (eph3.state(eph.toe).radius_km - eph.state(eph.toe).radius_km).norm() < 1e-9,
+            "RINEX 2 and 3 records differ"
+        );
+
+        // Truncated records and other file types are rejected
+        let truncated = RINEX2.lines().take(8).collect::<Vec<_>>().join("\n");
+        assert!(GnssEphemerides::from_rinex_nav(&truncated).is_err());
+        assert!(GnssEphemerides::from_rinex_nav(
+            "     2.10           O                                       RINEX VERSION / TYPE"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,287 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::f64::consts::PI;
+
+use log::warn;
+
+use super::{gps_epoch, resolve_week, BroadcastEphemeris, GnssEphemerides, GnssError};
+
+/// Reference inclination of the SEM almanacs, in semicircles, to which the inclination offset is added.
+const SEM_REFERENCE_INC_SEMICIRCLES: f64 = 0.3;
+
+/// Number of values of each satellite of a SEM almanac.
+const SEM_VALUES_PER_SATELLITE: usize = 14;
+
+impl GnssEphemerides {
+    /// Parses a GPS almanac in the Yuma format, as published by the US Coast Guard Navigation Center.
+    ///
+    /// Week numbers below 1024 are the 10-bit week numbers of the almanac, and are assumed to be in the current rollover era.
+    pub fn from_yuma(yuma: &str) -> Result<Self, GnssError> {
+        let mut ephemerides = Vec::new();
+        let mut values: [Option<f64>; 13] = [None; 13];
+
+        for (lno, line) in yuma.lines().enumerate() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let idx = match key.as_str() {
+                "id" => 0,
+                "health" => 1,
+                "eccentricity" => 2,
+                k if k.starts_with("time of applicability") => 3,
+                k if k.starts_with("orbital inclination") => 4,
+                k if k.starts_with("rate of right ascen") => 5,
+                k if k.starts_with("sqrt(a)") => 6,
+                k if k.starts_with("right ascen at week") => 7,
+                k if k.starts_with("argument of perigee") => 8,
+                k if k.starts_with("mean anom") => 9,
+                k if k.starts_with("af0") => 10,
+                k if k.starts_with("af1") => 11,
+                "week" => 12,
+                _ => {
+                    warn!("[yuma] line {}: ignoring `{key}`", lno + 1);
+                    continue;
+                }
+            };
+
+            values[idx] =
+                Some(
+                    value
+                        .trim()
+                        .parse::<f64>()
+                        .map_err(|_| GnssError::GnssParsing {
+                            format: "Yuma",
+                            lno: lno + 1,
+                            what: format!("invalid value `{}` for `{key}`", value.trim()),
+                        })?,
+                );
+
+            if idx == 12 {
+                // The week is the last value of each satellite
+                let mut all = [0.0; 13];
+                for (i, value) in values.iter().enumerate() {
+                    all[i] = value.ok_or_else(|| GnssError::GnssParsing {
+                        format: "Yuma",
+                        lno: lno + 1,
+                        what: format!("missing value #{i} of the satellite"),
+                    })?;
+                }
+                values = [None; 13];
+
+                let [prn, health, ecc, toa, inc, raan_rate, sqrt_a, raan, aop, ma, af0, af1, week] =
+                    all;
+                ephemerides.push(BroadcastEphemeris {
+                    prn: prn as u8,
+                    toe: gps_epoch(resolve_week(week as u32), toa),
+                    sqrt_a_m: sqrt_a,
+                    ecc,
+                    inc_rad: inc,
+                    inc_rate_rad_s: 0.0,
+                    raan_rad: raan,
+                    raan_rate_rad_s: raan_rate,
+                    aop_rad: aop,
+                    ma_rad: ma,
+                    delta_n_rad_s: 0.0,
+                    cuc_rad: 0.0,
+                    cus_rad: 0.0,
+                    crc_m: 0.0,
+                    crs_m: 0.0,
+                    cic_rad: 0.0,
+                    cis_rad: 0.0,
+                    af0_s: af0,
+                    af1_s_s: af1,
+                    healthy: health == 0.0,
+                });
+            }
+        }
+
+        Ok(Self { ephemerides })
+    }
+
+    /// Parses a GPS almanac in the SEM format, as published by the US Coast Guard Navigation Center.
+    ///
+    /// The angles of SEM almanacs are in semicircles, and the inclination is an offset from 0.3 semicircles.
+    /// Week numbers below 1024 are the 10-bit week numbers of the almanac, and are assumed to be in the current rollover era.
+    pub fn from_sem(sem: &str) -> Result<Self, GnssError> {
+        // The first line is the number of satellites and the name of the almanac, the second is the week and time of applicability.
+        let mut lines = sem.lines();
+        let num_sats = lines
+            .next()
+            .and_then(|line| line.split_whitespace().next())
+            .and_then(|count| count.parse::<usize>().ok())
+            .ok_or_else(|| GnssError::GnssParsing {
+                format: "SEM",
+                lno: 1,
+                what: "expected the number of satellites".to_string(),
+            })?;
+
+        let header = lines
+            .next()
+            .map(|line| {
+                line.split_whitespace()
+                    .filter_map(|token| token.parse::<f64>().ok())
+                    .collect::<Vec<f64>>()
+            })
+            .unwrap_or_default();
+        let [week, toa] = header[..] else {
+            return Err(GnssError::GnssParsing {
+                format: "SEM",
+                lno: 2,
+                what: "expected the week and the time of applicability".to_string(),
+            });
+        };
+        let toe = gps_epoch(resolve_week(week as u32), toa);
+
+        let mut tokens = Vec::new();
+        for (lno, line) in lines.enumerate() {
+            for token in line.split_whitespace() {
+                tokens.push(token.parse::<f64>().map_err(|_| GnssError::GnssParsing {
+                    format: "SEM",
+                    // The first two lines are the header
+                    lno: lno + 3,
+                    what: format!("invalid value `{token}`"),
+                })?);
+            }
+        }
+
+        if tokens.len() < num_sats * SEM_VALUES_PER_SATELLITE {
+            return Err(GnssError::GnssParsing {
+                format: "SEM",
+                lno: sem.lines().count(),
+                what: format!(
+                    "expected {} values for {num_sats} satellites but found {}",
+                    num_sats * SEM_VALUES_PER_SATELLITE,
+                    tokens.len()
+                ),
+            });
+        }
+
+        let ephemerides = tokens
+            .chunks_exact(SEM_VALUES_PER_SATELLITE)
+            .take(num_sats)
+            .map(|sat| {
+                // PRN, SVN, URA, e, inc offset, raan rate, sqrt(a), raan, aop, ma, af0, af1, health, configuration
+                BroadcastEphemeris {
+                    prn: sat[0] as u8,
+                    toe,
+                    sqrt_a_m: sat[6],
+                    ecc: sat[3],
+                    inc_rad: (SEM_REFERENCE_INC_SEMICIRCLES + sat[4]) * PI,
+                    inc_rate_rad_s: 0.0,
+                    raan_rad: sat[7] * PI,
+                    raan_rate_rad_s: sat[5] * PI,
+                    aop_rad: sat[8] * PI,
+                    ma_rad: sat[9] * PI,
+                    delta_n_rad_s: 0.0,
+                    cuc_rad: 0.0,
+                    cus_rad: 0.0,
+                    crc_m: 0.0,
+                    crs_m: 0.0,
+                    cic_rad: 0.0,
+                    cis_rad: 0.0,
+                    af0_s: sat[10],
+                    af1_s_s: sat[11],
+                    healthy: sat[12] == 0.0,
+                }
+            })
+            .collect();
+
+        Ok(Self { ephemerides })
+    }
+}
+
+#[cfg(test)]
+mod ut_yuma {
+    use super::super::ut_gnss::check_velocity;
+    use super::super::GnssEphemerides;
+    use hifitime::{TimeScale, Unit};
+
+    const YUMA: &str = "******** Week 254 almanac for PRN-01 ********
+ID:                         01
+Health:                     000
+Eccentricity:               0.1044559479E-001
+Time of Applicability(s):  405504.0000
+Orbital Inclination(rad):   0.9864141206
+Rate of Right Ascen(r/s):  -0.7760323248E-008
+SQRT(A)  (m 1/2):           5153.709961
+Right Ascen at Week(rad):  -0.1738343835E+001
+Argument of Perigee(rad):   0.854331780
+Mean Anom(rad):             0.2166914060E+001
+Af0(s):                     0.4653930664E-003
+Af1(s/s):                   0.3637978807E-011
+week:                        254
+
+******** Week 254 almanac for PRN-02 ********
+ID:                         02
+Health:                     063
+Eccentricity:               0.1736640930E-001
+Time of Applicability(s):  405504.0000
+Orbital Inclination(rad):   0.9424173902
+Rate of Right Ascen(r/s):  -0.7771752131E-008
+SQRT(A)  (m 1/2):           5153.615723
+Right Ascen at Week(rad):  -0.2794585229E+001
+Argument of Perigee(rad):  -1.263046987
+Mean Anom(rad):            -0.3044049598E+001
+Af0(s):                    -0.6389617920E-004
+Af1(s/s):                   0.0000000000E+000
+week:                        254
+";
+
+    const SEM: &str = "1 CURRENT.ALM
+ 254 405504
+
+1
+63
+0
+  1.04455947900000E-02  1.39853664582700E-02 -2.47018760982030E-09
+  5.15370996100000E+03 -5.53332028267144E-01  2.71942251654995E-01
+  6.89750167808656E-01  4.65393066400000E-04  3.63797880700000E-12
+0
+11
+";
+
+    #[test]
+    fn yuma_and_sem() {
+        let yuma = GnssEphemerides::from_yuma(YUMA).unwrap();
+        assert_eq!(yuma.prns(), vec![1, 2]);
+        let prn1 = yuma.ephemerides[0];
+        assert_eq!(prn1.toe.to_time_of_week(), (2302, 405_504_000_000_000));
+        assert_eq!(prn1.toe.time_scale, TimeScale::GPST);
+        assert!(prn1.healthy);
+        // PRN 2 is unhealthy, so it has no state
+        assert!(!yuma.ephemerides[1].healthy);
+        assert!(yuma.state(2, prn1.toe).is_err());
+        assert_eq!(yuma.states(prn1.toe).len(), 1);
+
+        let state = yuma.state(1, prn1.toe + Unit::Hour * 2).unwrap();
+        assert!((state.rmag_km() - 26_560.0).abs() < 400.0);
+        check_velocity(&prn1, prn1.toe + Unit::Hour * 2);
+
+        // The SEM almanac of PRN 1 is the same satellite, with angles in semicircles
+        let sem = GnssEphemerides::from_sem(SEM).unwrap();
+        let sem1 = sem.ephemerides[0];
+        assert_eq!(sem1.prn, 1);
+        assert_eq!(sem1.toe, prn1.toe);
+        assert!((sem1.inc_rad - prn1.inc_rad).abs() < 1e-9);
+        assert!((sem1.raan_rad - prn1.raan_rad).abs() < 1e-9);
+        assert!((sem1.ma_rad - prn1.ma_rad).abs() < 1e-9);
+        assert!(
+            (sem.state(1, prn1.toe).unwrap().radius_km
+                - yuma.state(1, prn1.toe).unwrap().radius_km)
+                .norm()
+                < 1e-3
+        );
+
+        assert!(GnssEphemerides::from_yuma("ID: 01\nHealth: abc").is_err());
+        assert!(GnssEphemerides::from_sem("2 CURRENT.ALM\n254 405504\n1 63 0").is_err());
+    }
+}
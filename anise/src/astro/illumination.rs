@@ -0,0 +1,134 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::Epoch;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Stores the illumination angles of a point on the surface of a body, as computed by SPICE's `ilumin`.
+///
+/// - The phase angle is the angle between the directions from the surface point to the Sun and to the observer.
+/// - The incidence angle is the angle between the surface normal and the direction from the surface point to the Sun.
+/// - The emission angle is the angle between the surface normal and the direction from the surface point to the observer.
+///
+/// :type epoch: Epoch
+/// :type phase_deg: float
+/// :type incidence_deg: float
+/// :type emission_deg: float
+/// :rtype: IlluminationAngles
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise.astro"))]
+pub struct IlluminationAngles {
+    pub epoch: Epoch,
+    pub phase_deg: f64,
+    pub incidence_deg: f64,
+    pub emission_deg: f64,
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl IlluminationAngles {
+    /// Returns true if the Sun is above the local horizon of the surface point, i.e. the incidence angle is less than 90 degrees.
+    ///
+    /// :rtype: bool
+    pub fn is_lit(&self) -> bool {
+        self.incidence_deg < 90.0
+    }
+
+    /// Returns true if the observer is above the local horizon of the surface point, i.e. the emission angle is less than 90 degrees.
+    ///
+    /// :rtype: bool
+    pub fn is_visible(&self) -> bool {
+        self.emission_deg < 90.0
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+#[cfg(feature = "python")]
+impl IlluminationAngles {
+    #[new]
+    fn py_new(epoch: Epoch, phase_deg: f64, incidence_deg: f64, emission_deg: f64) -> Self {
+        Self {
+            epoch,
+            phase_deg,
+            incidence_deg,
+            emission_deg,
+        }
+    }
+
+    /// :rtype: Epoch
+    #[getter]
+    fn get_epoch(&self) -> PyResult<Epoch> {
+        Ok(self.epoch)
+    }
+    /// :type epoch: Epoch
+    #[setter]
+    fn set_epoch(&mut self, epoch: Epoch) -> PyResult<()> {
+        self.epoch = epoch;
+        Ok(())
+    }
+
+    /// :rtype: float
+    #[getter]
+    fn get_phase_deg(&self) -> PyResult<f64> {
+        Ok(self.phase_deg)
+    }
+    /// :type phase_deg: float
+    #[setter]
+    fn set_phase_deg(&mut self, phase_deg: f64) -> PyResult<()> {
+        self.phase_deg = phase_deg;
+        Ok(())
+    }
+
+    /// :rtype: float
+    #[getter]
+    fn get_incidence_deg(&self) -> PyResult<f64> {
+        Ok(self.incidence_deg)
+    }
+    /// :type incidence_deg: float
+    #[setter]
+    fn set_incidence_deg(&mut self, incidence_deg: f64) -> PyResult<()> {
+        self.incidence_deg = incidence_deg;
+        Ok(())
+    }
+
+    /// :rtype: float
+    #[getter]
+    fn get_emission_deg(&self) -> PyResult<f64> {
+        Ok(self.emission_deg)
+    }
+    /// :type emission_deg: float
+    #[setter]
+    fn set_emission_deg(&mut self, emission_deg: f64) -> PyResult<()> {
+        self.emission_deg = emission_deg;
+        Ok(())
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+impl fmt::Display for IlluminationAngles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: phase: {:.6} deg    incidence: {:.6} deg    emission: {:.6} deg",
+            self.epoch, self.phase_deg, self.incidence_deg, self.emission_deg
+        )
+    }
+}
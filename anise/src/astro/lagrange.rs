@@ -0,0 +1,108 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Maximum number of Newton-Raphson iterations to locate the collinear libration points.
+const MAX_ITERATIONS: usize = 50;
+
+/// Libration (Lagrange) points of a system of two bodies.
+///
+/// L1 is between the primary and the secondary, L2 is beyond the secondary, L3 is beyond the primary,
+/// and L4 (resp. L5) leads (resp. trails) the secondary by 60 degrees on its orbit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "python", pyclass(eq, eq_int))]
+#[cfg_attr(feature = "python", pyo3(module = "anise.astro"))]
+pub enum LagrangePoint {
+    L1,
+    L2,
+    L3,
+    L4,
+    L5,
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl LagrangePoint {
+    /// Returns the position of this libration point in the synodic frame of the circular restricted three body problem, normalized by the distance
+    /// between the primary and the secondary, and centered on the barycenter: the primary is at (-mass_ratio, 0) and the secondary at (1 - mass_ratio, 0).
+    ///
+    /// The mass ratio is the gravitational parameter of the secondary divided by the sum of the gravitational parameters of both bodies.
+    ///
+    /// :type mass_ratio: float
+    /// :rtype: typing.Tuple
+    pub fn synodic_position(&self, mass_ratio: f64) -> (f64, f64) {
+        let mu = mass_ratio;
+        match self {
+            Self::L4 => (0.5 - mu, 3.0_f64.sqrt() / 2.0),
+            Self::L5 => (0.5 - mu, -(3.0_f64.sqrt()) / 2.0),
+            _ => {
+                // Initial guess from the Hill radius
+                let hill = (mu / 3.0).cbrt();
+                let mut x = match self {
+                    Self::L1 => 1.0 - mu - hill,
+                    Self::L2 => 1.0 - mu + hill,
+                    _ => -1.0 - 5.0 * mu / 12.0,
+                };
+
+                // Newton-Raphson on the equilibrium of the gravity and centrifugal accelerations along the X axis
+                for _ in 0..MAX_ITERATIONS {
+                    let r1 = x + mu;
+                    let r2 = x - 1.0 + mu;
+                    let f = x - (1.0 - mu) * r1 / r1.abs().powi(3) - mu * r2 / r2.abs().powi(3);
+                    let df =
+                        1.0 + 2.0 * (1.0 - mu) / r1.abs().powi(3) + 2.0 * mu / r2.abs().powi(3);
+                    let dx = f / df;
+                    x -= dx;
+                    if dx.abs() < 1e-15 {
+                        break;
+                    }
+                }
+
+                (x, 0.0)
+            }
+        }
+    }
+}
+
+impl fmt::Display for LagrangePoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[cfg(test)]
+mod ut_lagrange {
+    use super::LagrangePoint;
+
+    #[test]
+    fn earth_moon_points() {
+        // Earth-Moon mass ratio, and the positions of the collinear points from Szebehely, Theory of Orbits.
+        let mu = 0.012_150_58;
+
+        let (l1, _) = LagrangePoint::L1.synodic_position(mu);
+        let (l2, _) = LagrangePoint::L2.synodic_position(mu);
+        let (l3, _) = LagrangePoint::L3.synodic_position(mu);
+        assert!((l1 - 0.836_915).abs() < 1e-5, "{l1}");
+        assert!((l2 - 1.155_682).abs() < 1e-5, "{l2}");
+        assert!((l3 + 1.005_063).abs() < 1e-5, "{l3}");
+
+        // The triangular points are equidistant from both bodies.
+        for point in [LagrangePoint::L4, LagrangePoint::L5] {
+            let (x, y) = point.synodic_position(mu);
+            let d1 = ((x + mu).powi(2) + y.powi(2)).sqrt();
+            let d2 = ((x - 1.0 + mu).powi(2) + y.powi(2)).sqrt();
+            assert!((d1 - 1.0).abs() < 1e-15 && (d2 - 1.0).abs() < 1e-15);
+        }
+        assert!(LagrangePoint::L4.synodic_position(mu).1 > 0.0);
+    }
+}
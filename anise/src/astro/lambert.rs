@@ -0,0 +1,273 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::f64::consts::PI;
+
+use hifitime::Duration;
+
+use super::utils::stumpff_c2_c3;
+use super::PhysicsResult;
+use crate::errors::{MathError, PhysicsError};
+use crate::math::Vector3;
+
+/// Maximum number of bisection iterations of the universal variable Lambert solver.
+pub const LAMBERT_MAX_ITER: usize = 1000;
+/// Convergence tolerance on the time of flight, relative to the requested time of flight.
+pub const LAMBERT_TOF_REL_TOL: f64 = 1e-10;
+
+/// Direction of motion of the Lambert transfer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum TransferKind {
+    /// Pick the short way if the transfer is prograde (angular momentum along +Z), the long way otherwise.
+    #[default]
+    Auto,
+    /// Transfer angle lower than 180 degrees
+    ShortWay,
+    /// Transfer angle greater than 180 degrees
+    LongWay,
+}
+
+/// Solution of the Lambert problem.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LambertSolution {
+    /// Velocity at the initial position, in km/s
+    pub v_init_km_s: Vector3,
+    /// Velocity at the final position, in km/s
+    pub v_final_km_s: Vector3,
+    /// Transfer angle, in radians
+    pub phi_rad: f64,
+}
+
+/// Solves the zero-revolution Lambert problem using the universal variable formulation and a bisection on psi.
+///
+/// # Arguments
+/// + `r_init_km`: initial position vector
+/// + `r_final_km`: final position vector
+/// + `tof`: time of flight between both positions, must be strictly positive
+/// + `mu_km3_s2`: gravitational parameter of the central body
+/// + `kind`: direction of motion
+///
+/// # Errors
+/// + Transfers of exactly 180 degrees are singular in this formulation and return a domain error.
+/// + If the bisection does not converge, a max iterations error is returned.
+///
+/// Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., Algorithm 58.
+pub fn lambert(
+    r_init_km: Vector3,
+    r_final_km: Vector3,
+    tof: Duration,
+    mu_km3_s2: f64,
+    kind: TransferKind,
+) -> PhysicsResult<LambertSolution> {
+    let tof_s = tof.to_seconds();
+    if tof_s <= 0.0 {
+        return Err(PhysicsError::AppliedMath {
+            source: MathError::DomainError {
+                value: tof_s,
+                msg: "Lambert time of flight must be strictly positive",
+            },
+        });
+    }
+
+    let r_init = r_init_km.norm();
+    let r_final = r_final_km.norm();
+    if r_init < f64::EPSILON || r_final < f64::EPSILON {
+        return Err(PhysicsError::RadiusError {
+            action: "Lambert problem requires non-zero position vectors",
+        });
+    }
+
+    let cos_dnu = (r_init_km.dot(&r_final_km) / (r_init * r_final)).clamp(-1.0, 1.0);
+
+    let dm = match kind {
+        TransferKind::ShortWay => 1.0,
+        TransferKind::LongWay => -1.0,
+        TransferKind::Auto => {
+            if r_init_km.cross(&r_final_km).z >= 0.0 {
+                1.0
+            } else {
+                -1.0
+            }
+        }
+    };
+
+    let phi_rad = if dm > 0.0 {
+        cos_dnu.acos()
+    } else {
+        2.0 * PI - cos_dnu.acos()
+    };
+
+    let a = dm * (r_init * r_final * (1.0 + cos_dnu)).sqrt();
+    if a.abs() < f64::EPSILON {
+        return Err(PhysicsError::AppliedMath {
+            source: MathError::DomainError {
+                value: phi_rad.to_degrees(),
+                msg: "Lambert transfer angle of 180 degrees is singular",
+            },
+        });
+    }
+
+    let sqrt_mu = mu_km3_s2.sqrt();
+
+    let mut psi = 0.0;
+    let mut psi_up = 4.0 * PI.powi(2);
+    let mut psi_low = -4.0 * PI.powi(2);
+    let (mut c2, mut c3) = stumpff_c2_c3(psi);
+
+    let mut iter = 0;
+    let y = loop {
+        let mut y = r_init + r_final + a * (psi * c3 - 1.0) / c2.sqrt();
+
+        if a > 0.0 && y < 0.0 {
+            // Readjust the lower bound until y is positive
+            while y < 0.0 {
+                iter += 1;
+                if iter > LAMBERT_MAX_ITER {
+                    return Err(PhysicsError::AppliedMath {
+                        source: MathError::MaxIterationsReached {
+                            iter,
+                            action: "readjusting psi in the Lambert solver",
+                        },
+                    });
+                }
+                psi += 0.1;
+                (c2, c3) = stumpff_c2_c3(psi);
+                y = r_init + r_final + a * (psi * c3 - 1.0) / c2.sqrt();
+            }
+            psi_low = psi;
+        }
+
+        let chi = (y / c2).sqrt();
+        let dt_s = (chi.powi(3) * c3 + a * y.sqrt()) / sqrt_mu;
+
+        if (dt_s - tof_s).abs() < LAMBERT_TOF_REL_TOL * tof_s {
+            break y;
+        }
+
+        iter += 1;
+        if iter > LAMBERT_MAX_ITER {
+            return Err(PhysicsError::AppliedMath {
+                source: MathError::MaxIterationsReached {
+                    iter,
+                    action: "solving the Lambert problem",
+                },
+            });
+        }
+
+        if dt_s <= tof_s {
+            psi_low = psi;
+        } else {
+            psi_up = psi;
+        }
+        psi = 0.5 * (psi_up + psi_low);
+        (c2, c3) = stumpff_c2_c3(psi);
+    };
+
+    let f = 1.0 - y / r_init;
+    let g_dot = 1.0 - y / r_final;
+    let g = a * (y / mu_km3_s2).sqrt();
+
+    Ok(LambertSolution {
+        v_init_km_s: (r_final_km - f * r_init_km) / g,
+        v_final_km_s: (g_dot * r_final_km - r_init_km) / g,
+        phi_rad,
+    })
+}
+
+#[cfg(test)]
+mod ut_lambert {
+    use super::*;
+    use hifitime::TimeUnits;
+
+    #[test]
+    fn vallado_example_7_5() {
+        let r_init_km = Vector3::new(15945.34, 0.0, 0.0);
+        let r_final_km = Vector3::new(12214.83899, 10249.46731, 0.0);
+
+        let sol = lambert(
+            r_init_km,
+            r_final_km,
+            76.0.minutes(),
+            398_600.441_8,
+            TransferKind::ShortWay,
+        )
+        .unwrap();
+
+        let exp_v_init = Vector3::new(2.058913, 2.915965, 0.0);
+        let exp_v_final = Vector3::new(-3.451565, 0.910315, 0.0);
+
+        assert!((sol.v_init_km_s - exp_v_init).norm() < 1e-5);
+        assert!((sol.v_final_km_s - exp_v_final).norm() < 1e-5);
+
+        // Auto selects the short way for this prograde transfer
+        let auto = lambert(
+            r_init_km,
+            r_final_km,
+            76.0.minutes(),
+            398_600.441_8,
+            TransferKind::Auto,
+        )
+        .unwrap();
+        assert_eq!(sol, auto);
+    }
+
+    #[test]
+    fn lambert_multi_year_transfer() {
+        // Heliocentric transfer along a known ellipse, from periapsis to a true anomaly of 160 degrees (about 2.35 years)
+        let mu_km3_s2 = 132_712_440_041.939_4;
+        let sma_km = 4.0 * 149_597_870.7;
+        let ecc: f64 = 0.7;
+        let nu_final = 160.0_f64.to_radians();
+
+        let p_km = sma_km * (1.0 - ecc.powi(2));
+        let pos = |nu: f64| p_km / (1.0 + ecc * nu.cos()) * Vector3::new(nu.cos(), nu.sin(), 0.0);
+        let vel =
+            |nu: f64| (mu_km3_s2 / p_km).sqrt() * Vector3::new(-nu.sin(), ecc + nu.cos(), 0.0);
+
+        let ecc_anomaly =
+            2.0 * (((1.0 - ecc) / (1.0 + ecc)).sqrt() * (nu_final / 2.0).tan()).atan();
+        let tof_s = (ecc_anomaly - ecc * ecc_anomaly.sin()) * (sma_km.powi(3) / mu_km3_s2).sqrt();
+        assert!(tof_s > 2.0 * 365.25 * 86_400.0);
+
+        let sol = lambert(
+            pos(0.0),
+            pos(nu_final),
+            tof_s.seconds(),
+            mu_km3_s2,
+            TransferKind::ShortWay,
+        )
+        .unwrap();
+
+        assert!((sol.v_init_km_s - vel(0.0)).norm() < 1e-6);
+        assert!((sol.v_final_km_s - vel(nu_final)).norm() < 1e-6);
+        assert!((sol.phi_rad - nu_final).abs() < 1e-12);
+    }
+
+    #[test]
+    fn lambert_invalid_inputs() {
+        let r_init_km = Vector3::new(7000.0, 0.0, 0.0);
+        assert!(lambert(
+            r_init_km,
+            -r_init_km,
+            1.hours(),
+            398_600.441_8,
+            TransferKind::ShortWay
+        )
+        .is_err());
+        assert!(lambert(
+            r_init_km,
+            Vector3::new(0.0, 7000.0, 0.0),
+            -1.hours(),
+            398_600.441_8,
+            TransferKind::ShortWay
+        )
+        .is_err());
+    }
+}
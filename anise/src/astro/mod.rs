@@ -25,13 +25,32 @@ use pyo3::pyclass::CompareOp;
 pub mod utils;
 
 pub(crate) mod aberration;
-pub use aberration::Aberration;
+pub use aberration::{Aberration, AberrationDiagnostics};
 
 pub(crate) mod occultation;
-pub use occultation::Occultation;
+pub use occultation::{Occultation, OccultationKind};
 
+pub(crate) mod illumination;
+pub use illumination::IlluminationAngles;
+
+pub(crate) mod lagrange;
+pub use lagrange::LagrangePoint;
+
+pub(crate) mod range_doppler;
+pub use range_doppler::TwoWayRangeDoppler;
+
+pub mod bplane;
+pub use bplane::BPlane;
+
+pub mod gnss;
+pub mod lambert;
 pub mod orbit;
+pub mod orbit_brouwer;
+pub mod orbit_describe;
+pub mod orbit_equinoctial;
 pub mod orbit_geodetic;
+pub mod orbit_typed;
+pub mod signal_delay;
 
 pub type PhysicsResult<T> = Result<T, PhysicsError>;
 
@@ -17,6 +17,36 @@ use hifitime::Epoch;
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+/// Classification of an occultation of a back object by a front object, as seen from an observer.
+///
+/// The integer values match the (positive) codes returned by SPICE's `occult` routine.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "python", pyclass(eq, eq_int))]
+#[cfg_attr(feature = "python", pyo3(module = "anise.astro"))]
+#[repr(u8)]
+pub enum OccultationKind {
+    /// The back object is fully visible from the observer.
+    #[default]
+    Visible = 0,
+    /// The limbs of both objects overlap: the back object is partially hidden (i.e. penumbra for solar eclipsing).
+    Partial = 1,
+    /// The front object is apparently smaller than the back object and lies entirely within its disk (e.g. an annular eclipse or a transit).
+    Annular = 2,
+    /// The back object is fully hidden by the front object (i.e. umbra for solar eclipsing).
+    Total = 3,
+}
+
+impl fmt::Display for OccultationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Visible => write!(f, "visible"),
+            Self::Partial => write!(f, "partial"),
+            Self::Annular => write!(f, "annular"),
+            Self::Total => write!(f, "total"),
+        }
+    }
+}
+
 /// Stores the result of an occultation computation with the occulation percentage
 /// Refer to the [MathSpec](https://nyxspace.com/nyxspace/MathSpec/celestial/eclipse/) for modeling details.
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -27,6 +57,7 @@ pub struct Occultation {
     pub percentage: f64,
     pub back_frame: Frame,
     pub front_frame: Frame,
+    pub kind: OccultationKind,
 }
 
 #[cfg_attr(feature = "python", pymethods)]
@@ -65,6 +96,13 @@ impl Occultation {
     pub fn is_partial(&self) -> bool {
         !self.is_visible() && !self.is_obstructed()
     }
+
+    /// Returns true if the front object lies entirely within the apparent disk of the back object (e.g. annular eclipse or transit)
+    ///
+    /// :rtype: bool
+    pub fn is_annular(&self) -> bool {
+        self.kind == OccultationKind::Annular
+    }
 }
 
 #[cfg_attr(feature = "python", pymethods)]
@@ -118,6 +156,18 @@ impl Occultation {
         Ok(())
     }
 
+    /// :rtype: OccultationKind
+    #[getter]
+    fn get_kind(&self) -> PyResult<OccultationKind> {
+        Ok(self.kind)
+    }
+    /// :type kind: OccultationKind
+    #[setter]
+    fn set_kind(&mut self, kind: OccultationKind) -> PyResult<()> {
+        self.kind = kind;
+        Ok(())
+    }
+
     fn __str__(&self) -> String {
         format!("{self}")
     }
@@ -139,6 +189,12 @@ impl fmt::Display for Occultation {
                 )
             } else if self.is_obstructed() {
                 write!(f, "{}: umbra due to {:e}", self.epoch, self.front_frame)
+            } else if self.is_annular() {
+                write!(
+                    f,
+                    "{}: annular eclipse of {:.3}% due to {:e}",
+                    self.epoch, self.percentage, self.front_frame
+                )
             } else {
                 write!(
                     f,
@@ -149,8 +205,8 @@ impl fmt::Display for Occultation {
         } else {
             write!(
                 f,
-                "{}: {:.3}% occultation of {:e} due to {:e}",
-                self.epoch, self.percentage, self.front_frame, self.back_frame
+                "{}: {:.3}% {} occultation of {:e} due to {:e}",
+                self.epoch, self.percentage, self.kind, self.back_frame, self.front_frame
             )
         }
     }
@@ -8,12 +8,12 @@
  * Documentation: https://nyxspace.com/
  */
 
-use super::utils::compute_mean_to_true_anomaly_rad;
+use super::utils::{compute_mean_to_true_anomaly_rad, stumpff_c2_c3};
 use super::PhysicsResult;
 
 use crate::{
     errors::{
-        HyperbolicTrueAnomalySnafu, InfiniteValueSnafu, ParabolicEccentricitySnafu,
+        HyperbolicTrueAnomalySnafu, InfiniteValueSnafu, MathError, ParabolicEccentricitySnafu,
         ParabolicSemiParamSnafu, PhysicsError, RadiusSnafu, VelocitySnafu,
     },
     math::{
@@ -40,6 +40,13 @@ use pyo3::types::PyType;
 /// If an orbit has an eccentricity below the following value, it is considered circular (only affects warning messages)
 pub const ECC_EPSILON: f64 = 1e-11;
 
+/// If the inverse of the semi major axis (in 1/km) is within this value of zero, the orbit is propagated as parabolic.
+const UNIVERSAL_ALPHA_EPSILON: f64 = 1e-6;
+/// Relative convergence tolerance of the universal variable.
+const UNIVERSAL_CHI_TOL: f64 = 1e-9;
+/// Maximum number of Newton iterations when solving for the universal variable.
+const UNIVERSAL_MAX_ITER: usize = 100;
+
 /// A helper type alias, but no assumptions are made on the underlying validity of the frame.
 pub type Orbit = CartesianState;
 
@@ -1223,6 +1230,20 @@ impl Orbit {
         between_pm_180((self.radius_km.z / self.rmag_km()).asin().to_degrees())
     }
 
+    /// Returns the right ascension (between 0 and 360 degrees), declination (between -90 and 90 degrees), and range (in km) of the position of this state in its frame.
+    ///
+    /// # SPICE Compatibility
+    /// This is the SPICE equivalent of recrad, with the range returned last.
+    ///
+    /// :rtype: typing.Tuple
+    pub fn radec(&self) -> (f64, f64, f64) {
+        (
+            self.right_ascension_deg(),
+            self.declination_deg(),
+            self.rmag_km(),
+        )
+    }
+
     /// Returns the semi minor axis in km, includes code for a hyperbolic orbit
     ///
     /// :rtype: float
@@ -1306,30 +1327,120 @@ impl Orbit {
         }
     }
 
-    /// Adjusts the true anomaly of this orbit using the mean anomaly.
+    /// Propagates this orbit to the provided epoch using two-body (Keplerian) dynamics and the gravitational parameter of its frame.
+    ///
+    /// This uses the universal variable formulation, which is valid for elliptical, parabolic, and hyperbolic orbits,
+    /// and for both forward and backward propagation.
     ///
     /// # Astrodynamics note
     /// This is not a true propagation of the orbit. This is akin to a two body propagation ONLY without any other force models applied.
     /// Use Nyx for high fidelity propagation.
     ///
+    /// Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., Algorithm 8.
+    ///
     /// :type new_epoch: Epoch
     /// :rtype: Orbit
     pub fn at_epoch(&self, new_epoch: Epoch) -> PhysicsResult<Self> {
-        let m0_rad = self.ma_deg()?.to_radians();
-        let mt_rad = m0_rad
-            + (self.frame.mu_km3_s2()? / self.sma_km()?.powi(3)).sqrt()
-                * (new_epoch - self.epoch).to_seconds();
+        let mu_km3_s2 = self.frame.mu_km3_s2()?;
+        let dt_s = (new_epoch - self.epoch).to_seconds();
 
-        Self::try_keplerian_mean_anomaly(
-            self.sma_km()?,
-            self.ecc()?,
-            self.inc_deg()?,
-            self.raan_deg()?,
-            self.aop_deg()?,
-            mt_rad.to_degrees(),
-            new_epoch,
-            self.frame,
-        )
+        ensure!(
+            self.rmag_km() > f64::EPSILON,
+            RadiusSnafu {
+                action: "cannot propagate an orbit with zero radius"
+            }
+        );
+
+        if dt_s.abs() < f64::EPSILON {
+            let mut me = *self;
+            me.epoch = new_epoch;
+            return Ok(me);
+        }
+
+        let sqrt_mu = mu_km3_s2.sqrt();
+        let r0_km = self.rmag_km();
+        let r0_dot_v0 = self.radius_km.dot(&self.velocity_km_s);
+        // Inverse of the semi major axis
+        let alpha = -self.vmag_km_s().powi(2) / mu_km3_s2 + 2.0 / r0_km;
+
+        // Initial guess of the universal variable
+        let mut chi = if alpha > UNIVERSAL_ALPHA_EPSILON {
+            // Elliptical
+            sqrt_mu * dt_s * alpha
+        } else if alpha < -UNIVERSAL_ALPHA_EPSILON {
+            // Hyperbolic
+            let sma_km = 1.0 / alpha;
+            let sign_dt = dt_s.signum();
+            sign_dt
+                * (-sma_km).sqrt()
+                * ((-2.0 * mu_km3_s2 * alpha * dt_s)
+                    / (r0_dot_v0 + sign_dt * (-mu_km3_s2 * sma_km).sqrt() * (1.0 - r0_km * alpha)))
+                    .ln()
+        } else {
+            // Parabolic, using Barker's equation
+            let p_km = self.hmag()?.powi(2) / mu_km3_s2;
+            let s = 0.5 * (1.0 / (3.0 * (mu_km3_s2 / p_km.powi(3)).sqrt() * dt_s)).atan();
+            let w = s.tan().cbrt().atan();
+            p_km.sqrt() * 2.0 / (2.0 * w).tan()
+        };
+
+        ensure!(
+            chi.is_finite(),
+            InfiniteValueSnafu {
+                action: "computing the initial universal variable guess"
+            }
+        );
+
+        let mut iter = 0;
+        let (psi, c2, c3, r_km) = loop {
+            let psi = chi.powi(2) * alpha;
+            let (c2, c3) = stumpff_c2_c3(psi);
+            let r_km = chi.powi(2) * c2
+                + r0_dot_v0 / sqrt_mu * chi * (1.0 - psi * c3)
+                + r0_km * (1.0 - psi * c2);
+
+            let next_chi = chi
+                + (sqrt_mu * dt_s
+                    - chi.powi(3) * c3
+                    - r0_dot_v0 / sqrt_mu * chi.powi(2) * c2
+                    - r0_km * chi * (1.0 - psi * c3))
+                    / r_km;
+
+            let converged = (next_chi - chi).abs() < UNIVERSAL_CHI_TOL * chi.abs().max(1.0);
+            chi = next_chi;
+
+            if converged {
+                let psi = chi.powi(2) * alpha;
+                let (c2, c3) = stumpff_c2_c3(psi);
+                let r_km = chi.powi(2) * c2
+                    + r0_dot_v0 / sqrt_mu * chi * (1.0 - psi * c3)
+                    + r0_km * (1.0 - psi * c2);
+                break (psi, c2, c3, r_km);
+            }
+
+            iter += 1;
+            if iter > UNIVERSAL_MAX_ITER || !chi.is_finite() {
+                return Err(PhysicsError::AppliedMath {
+                    source: MathError::MaxIterationsReached {
+                        iter,
+                        action: "solving Kepler's equation with universal variables",
+                    },
+                });
+            }
+        };
+
+        // Lagrange coefficients
+        let f = 1.0 - chi.powi(2) / r0_km * c2;
+        let g = dt_s - chi.powi(3) / sqrt_mu * c3;
+        let f_dot = sqrt_mu / (r_km * r0_km) * chi * (psi * c3 - 1.0);
+        let g_dot = 1.0 - chi.powi(2) / r_km * c2;
+
+        let mut me = *self;
+        me.radius_km = f * self.radius_km + g * self.velocity_km_s;
+        me.velocity_km_s = f_dot * self.radius_km + g_dot * self.velocity_km_s;
+        me.epoch = new_epoch;
+
+        Ok(me)
     }
 
     /// Returns a Cartesian state representing the RIC difference between self and other, in position and velocity (with transport theorem).
@@ -0,0 +1,584 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::{utils::compute_mean_to_true_anomaly_rad, PhysicsResult};
+use crate::{
+    errors::{MathError, PhysicsError},
+    math::{
+        angles::{between_0_360, between_pm_180},
+        cartesian::CartesianState,
+    },
+    prelude::Frame,
+};
+use core::f64::consts::TAU;
+use hifitime::{Epoch, Unit};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyType;
+
+/// Maximum number of fixed point iterations when converting osculating elements into mean elements.
+const MEAN_ELEMENTS_MAX_ITER: usize = 25;
+/// Convergence tolerance on the semi-major axis, in km, of the osculating to mean iterations.
+const MEAN_ELEMENTS_TOL_KM: f64 = 1e-9;
+/// Convergence tolerance on the eccentricity and angles (in radians) of the osculating to mean iterations.
+const MEAN_ELEMENTS_TOL: f64 = 1e-12;
+
+/// Keplerian elements [a (km), e, i, Ω, ω, M] where all angles are in radians.
+type Elements = [f64; 6];
+
+/// First order Brouwer-Lyddane mapping between mean and osculating Keplerian elements, accounting for the J2 short and long
+/// period terms. The mapping from mean to osculating is computed when `sign` is +1.0, and the approximate inverse when it is -1.0.
+///
+/// Source: Schaub and Junkins, "Analytical Mechanics of Space Systems", 4th Ed., Appendix G.
+fn brouwer_lyddane_map(
+    eq_radius_km: f64,
+    j2: f64,
+    elements: Elements,
+    sign: f64,
+) -> PhysicsResult<Elements> {
+    let [sma_km, ecc, inc_rad, raan_rad, aop_rad, ma_rad] = elements;
+
+    if !(0.0..1.0).contains(&ecc) {
+        return Err(PhysicsError::AppliedMath {
+            source: MathError::DomainError {
+                value: ecc,
+                msg: "Brouwer-Lyddane mean elements are only defined for elliptical orbits",
+            },
+        });
+    }
+
+    let (sin_i, cos_i) = inc_rad.sin_cos();
+    let cos2_i = cos_i.powi(2);
+    // The J2 long period terms are singular at the critical inclination (63.4 deg and 116.6 deg).
+    let crit = 1.0 - 5.0 * cos2_i;
+    if crit.abs() < 1e-6 || sin_i.abs() < 1e-6 {
+        return Err(PhysicsError::AppliedMath {
+            source: MathError::DomainError {
+                value: inc_rad.to_degrees(),
+                msg: "Brouwer-Lyddane mean elements are singular for equatorial orbits and at the critical inclination",
+            },
+        });
+    }
+
+    let gamma2 = sign * j2 / 2.0 * (eq_radius_km / sma_km).powi(2);
+    let eta = (1.0 - ecc.powi(2)).sqrt();
+    let gamma2p = gamma2 / eta.powi(4);
+
+    let ta_rad = compute_mean_to_true_anomaly_rad(ma_rad, ecc)?;
+    let (sin_f, cos_f) = ta_rad.sin_cos();
+    let a_r = (1.0 + ecc * cos_f) / eta.powi(2);
+
+    let two_aop = 2.0 * aop_rad;
+    let (sin_2w, cos_2w) = two_aop.sin_cos();
+    let long_period = 1.0 - 11.0 * cos2_i - 40.0 * cos2_i.powi(2) / crit;
+    // Difference between the true and mean anomalies, plus the equation of the center term
+    let eq_center = ta_rad - ma_rad + ecc * sin_f;
+    let sin_sum = 3.0 * (two_aop + 2.0 * ta_rad).sin()
+        + 3.0 * ecc * (two_aop + ta_rad).sin()
+        + ecc * (two_aop + 3.0 * ta_rad).sin();
+    let cos_f_poly = 3.0 * cos_f + 3.0 * ecc * cos_f.powi(2) + ecc.powi(2) * cos_f.powi(3);
+
+    let new_sma_km = sma_km
+        + sma_km
+            * gamma2
+            * ((3.0 * cos2_i - 1.0) * (a_r.powi(3) - 1.0 / eta.powi(3))
+                + 3.0 * (1.0 - cos2_i) * a_r.powi(3) * (two_aop + 2.0 * ta_rad).cos());
+
+    let d_ecc1 = gamma2p / 8.0 * ecc * eta.powi(2) * long_period * cos_2w;
+    let d_ecc = d_ecc1
+        + eta.powi(2) / 2.0
+            * (gamma2
+                * ((3.0 * cos2_i - 1.0) / eta.powi(6)
+                    * (ecc * eta + ecc / (1.0 + eta) + cos_f_poly)
+                    + 3.0 * (1.0 - cos2_i) / eta.powi(6)
+                        * (ecc + cos_f_poly)
+                        * (two_aop + 2.0 * ta_rad).cos())
+                - gamma2p
+                    * (1.0 - cos2_i)
+                    * (3.0 * (two_aop + ta_rad).cos() + (two_aop + 3.0 * ta_rad).cos()));
+
+    let d_inc = -ecc * d_ecc1 / eta.powi(2) / inc_rad.tan()
+        + gamma2p / 2.0
+            * cos_i
+            * sin_i.abs()
+            * (3.0 * (two_aop + 2.0 * ta_rad).cos()
+                + 3.0 * ecc * (two_aop + ta_rad).cos()
+                + ecc * (two_aop + 3.0 * ta_rad).cos());
+
+    let raan_term = gamma2p / 8.0
+        * ecc.powi(2)
+        * cos_i
+        * (11.0 + 80.0 * cos2_i / crit + 200.0 * cos2_i.powi(2) / crit.powi(2))
+        * sin_2w;
+    let node_term = gamma2p / 2.0 * cos_i * (6.0 * eq_center - sin_sum);
+
+    // Sum of the mean anomaly, argument of periapsis, and right ascension of the ascending node.
+    let new_m_w_raan =
+        ma_rad + aop_rad + raan_rad + gamma2p / 8.0 * eta.powi(3) * long_period * sin_2w
+            - gamma2p / 16.0
+                * (2.0 + ecc.powi(2)
+                    - 11.0 * (2.0 + 3.0 * ecc.powi(2)) * cos2_i
+                    - 40.0 * (2.0 + 5.0 * ecc.powi(2)) * cos2_i.powi(2) / crit
+                    - 400.0 * ecc.powi(2) * cos2_i.powi(3) / crit.powi(2))
+                * sin_2w
+            + gamma2p / 4.0 * (-6.0 * crit * eq_center + (3.0 - 5.0 * cos2_i) * sin_sum)
+            - raan_term
+            - node_term;
+
+    let ar_eta2 = (a_r * eta).powi(2);
+    let ecc_d_ma = gamma2p / 8.0 * ecc * eta.powi(3) * long_period * sin_2w
+        - gamma2p / 4.0
+            * eta.powi(3)
+            * (2.0 * (3.0 * cos2_i - 1.0) * (ar_eta2 + a_r + 1.0) * sin_f
+                + 3.0
+                    * (1.0 - cos2_i)
+                    * ((-ar_eta2 - a_r + 1.0) * (two_aop + ta_rad).sin()
+                        + (ar_eta2 + a_r + 1.0 / 3.0) * (two_aop + 3.0 * ta_rad).sin()));
+
+    let d_raan = -raan_term - node_term;
+
+    // Recombine the perturbations without dividing by the eccentricity or the sine of the inclination.
+    let (sin_m, cos_m) = ma_rad.sin_cos();
+    let d1 = (ecc + d_ecc) * sin_m + ecc_d_ma * cos_m;
+    let d2 = (ecc + d_ecc) * cos_m - ecc_d_ma * sin_m;
+    let new_ma_rad = d1.atan2(d2);
+    let new_ecc = (d1.powi(2) + d2.powi(2)).sqrt();
+
+    let (sin_half_i, cos_half_i) = (inc_rad / 2.0).sin_cos();
+    let (sin_raan, cos_raan) = raan_rad.sin_cos();
+    let d3 = (sin_half_i + cos_half_i * d_inc / 2.0) * sin_raan + sin_half_i * d_raan * cos_raan;
+    let d4 = (sin_half_i + cos_half_i * d_inc / 2.0) * cos_raan - sin_half_i * d_raan * sin_raan;
+    let new_raan_rad = d3.atan2(d4);
+    let new_inc_rad = 2.0 * (d3.powi(2) + d4.powi(2)).sqrt().asin();
+    let new_aop_rad = new_m_w_raan - new_ma_rad - new_raan_rad;
+
+    Ok([
+        new_sma_km,
+        new_ecc,
+        new_inc_rad,
+        new_raan_rad.rem_euclid(TAU),
+        new_aop_rad.rem_euclid(TAU),
+        new_ma_rad.rem_euclid(TAU),
+    ])
+}
+
+impl CartesianState {
+    /// Attempts to create a new Orbit from the Keplerian orbital elements where the semi-major axis is computed from
+    /// the mean motion, in revolutions per day, as published in general perturbations (GP) catalogs.
+    ///
+    /// NOTE: the mean motion is converted with the gravitational parameter of the frame only, so this does not account for
+    /// the SGP4 specific "Kozai" to "Brouwer" mean motion conversion.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_keplerian_mean_motion(
+        mean_motion_rev_day: f64,
+        ecc: f64,
+        inc_deg: f64,
+        raan_deg: f64,
+        aop_deg: f64,
+        ma_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> PhysicsResult<Self> {
+        if mean_motion_rev_day <= 0.0 {
+            return Err(PhysicsError::AppliedMath {
+                source: MathError::DomainError {
+                    value: mean_motion_rev_day,
+                    msg: "mean motion must be strictly positive",
+                },
+            });
+        }
+        let mean_motion_rad_s = mean_motion_rev_day * TAU / Unit::Day.in_seconds();
+        let sma_km = (frame.mu_km3_s2()? / mean_motion_rad_s.powi(2)).cbrt();
+
+        Self::try_keplerian_mean_anomaly(
+            sma_km, ecc, inc_deg, raan_deg, aop_deg, ma_deg, epoch, frame,
+        )
+    }
+
+    /// Attempts to create a new osculating Orbit from the Brouwer-Lyddane mean Keplerian elements, accounting for the
+    /// first order short and long period effects of the provided J2 zonal harmonic.
+    ///
+    /// The equatorial radius is fetched from the shape of the frame, which must therefore be fully defined.
+    /// These mean elements are singular for equatorial orbits, and at the critical inclination.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_brouwer_mean(
+        sma_km: f64,
+        ecc: f64,
+        inc_deg: f64,
+        raan_deg: f64,
+        aop_deg: f64,
+        ma_deg: f64,
+        j2: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> PhysicsResult<Self> {
+        let mean = [
+            sma_km,
+            ecc,
+            inc_deg.to_radians(),
+            raan_deg.to_radians(),
+            aop_deg.to_radians(),
+            ma_deg.to_radians(),
+        ];
+
+        let [osc_sma_km, osc_ecc, osc_inc_rad, osc_raan_rad, osc_aop_rad, osc_ma_rad] =
+            brouwer_lyddane_map(frame.mean_equatorial_radius_km()?, j2, mean, 1.0)?;
+
+        Self::try_keplerian_mean_anomaly(
+            osc_sma_km,
+            osc_ecc,
+            osc_inc_rad.to_degrees(),
+            osc_raan_rad.to_degrees(),
+            osc_aop_rad.to_degrees(),
+            osc_ma_rad.to_degrees(),
+            epoch,
+            frame,
+        )
+    }
+
+    /// Returns the Brouwer-Lyddane mean Keplerian elements (sma km, ecc, inc deg, raan deg, aop deg, ma deg) of this
+    /// osculating orbit, accounting for the first order short and long period effects of the provided J2 zonal harmonic.
+    ///
+    /// The first order inverse mapping is refined with fixed point iterations such that converting the returned mean
+    /// elements back with `try_brouwer_mean` yields this orbit. An error is returned if these iterations do not converge.
+    pub fn to_brouwer_mean(&self, j2: f64) -> PhysicsResult<(f64, f64, f64, f64, f64, f64)> {
+        let eq_radius_km = self.frame.mean_equatorial_radius_km()?;
+        let osc = [
+            self.sma_km()?,
+            self.ecc()?,
+            self.inc_deg()?.to_radians(),
+            self.raan_deg()?.to_radians(),
+            self.aop_deg()?.to_radians(),
+            self.ma_deg()?.to_radians(),
+        ];
+
+        let mut mean = brouwer_lyddane_map(eq_radius_km, j2, osc, -1.0)?;
+        let mut converged = false;
+        for _ in 0..MEAN_ELEMENTS_MAX_ITER {
+            let estimate = brouwer_lyddane_map(eq_radius_km, j2, mean, 1.0)?;
+            converged = true;
+            for (i, (mean_i, (osc_i, est_i))) in mean
+                .iter_mut()
+                .zip(osc.iter().zip(estimate.iter()))
+                .enumerate()
+            {
+                let delta = if i < 3 {
+                    osc_i - est_i
+                } else {
+                    between_pm_180((osc_i - est_i).to_degrees()).to_radians()
+                };
+                *mean_i += delta;
+                let tol = if i == 0 {
+                    MEAN_ELEMENTS_TOL_KM
+                } else {
+                    MEAN_ELEMENTS_TOL
+                };
+                converged &= delta.abs() < tol;
+            }
+            if converged {
+                break;
+            }
+        }
+
+        if !converged {
+            return Err(PhysicsError::AppliedMath {
+                source: MathError::MaxIterationsReached {
+                    iter: MEAN_ELEMENTS_MAX_ITER,
+                    action: "converting osculating elements into Brouwer-Lyddane mean elements",
+                },
+            });
+        }
+
+        let [sma_km, ecc, inc_rad, raan_rad, aop_rad, ma_rad] = mean;
+
+        Ok((
+            sma_km,
+            ecc,
+            inc_rad.to_degrees(),
+            between_0_360(raan_rad.to_degrees()),
+            between_0_360(aop_rad.to_degrees()),
+            between_0_360(ma_rad.to_degrees()),
+        ))
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl CartesianState {
+    /// Creates a new Orbit from the Keplerian orbital elements where the semi-major axis is computed from
+    /// the mean motion, in revolutions per day, as published in general perturbations (GP) catalogs.
+    ///
+    /// :type mean_motion_rev_day: float
+    /// :type ecc: float
+    /// :type inc_deg: float
+    /// :type raan_deg: float
+    /// :type aop_deg: float
+    /// :type ma_deg: float
+    /// :type epoch: Epoch
+    /// :type frame: Frame
+    /// :rtype: Orbit
+    #[cfg(feature = "python")]
+    #[classmethod]
+    pub fn from_keplerian_mean_motion(
+        _cls: &Bound<'_, PyType>,
+        mean_motion_rev_day: f64,
+        ecc: f64,
+        inc_deg: f64,
+        raan_deg: f64,
+        aop_deg: f64,
+        ma_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> PhysicsResult<Self> {
+        Self::try_keplerian_mean_motion(
+            mean_motion_rev_day,
+            ecc,
+            inc_deg,
+            raan_deg,
+            aop_deg,
+            ma_deg,
+            epoch,
+            frame,
+        )
+    }
+
+    /// Creates a new osculating Orbit from the Brouwer-Lyddane mean Keplerian elements, accounting for the first order short and
+    /// long period effects of the provided J2 zonal harmonic of the central body of the frame.
+    ///
+    /// :type sma_km: float
+    /// :type ecc: float
+    /// :type inc_deg: float
+    /// :type raan_deg: float
+    /// :type aop_deg: float
+    /// :type ma_deg: float
+    /// :type epoch: Epoch
+    /// :type frame: Frame
+    /// :type j2: float
+    /// :rtype: Orbit
+    #[cfg(feature = "python")]
+    #[classmethod]
+    pub fn from_brouwer_mean(
+        _cls: &Bound<'_, PyType>,
+        sma_km: f64,
+        ecc: f64,
+        inc_deg: f64,
+        raan_deg: f64,
+        aop_deg: f64,
+        ma_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+        j2: f64,
+    ) -> PhysicsResult<Self> {
+        Self::try_brouwer_mean(
+            sma_km, ecc, inc_deg, raan_deg, aop_deg, ma_deg, j2, epoch, frame,
+        )
+    }
+
+    /// Returns the Brouwer-Lyddane mean Keplerian elements (sma km, ecc, inc deg, raan deg, aop deg, ma deg) of this
+    /// osculating orbit, accounting for the first order short and long period effects of the provided J2 zonal harmonic
+    /// of the central body of the frame.
+    ///
+    /// :type j2: float
+    /// :rtype: typing.Tuple
+    #[cfg(feature = "python")]
+    #[pyo3(name = "to_brouwer_mean")]
+    pub fn py_to_brouwer_mean(&self, j2: f64) -> PhysicsResult<(f64, f64, f64, f64, f64, f64)> {
+        self.to_brouwer_mean(j2)
+    }
+
+    /// Returns the Keplerian mean motion in revolutions per day
+    ///
+    /// :rtype: float
+    pub fn mean_motion_rev_day(&self) -> PhysicsResult<f64> {
+        let mean_motion_rad_s = (self.frame.mu_km3_s2()? / self.sma_km()?.powi(3)).sqrt();
+        Ok(mean_motion_rad_s * Unit::Day.in_seconds() / TAU)
+    }
+}
+
+#[cfg(test)]
+mod ut_brouwer {
+    use crate::constants::frames::EARTH_J2000;
+    use crate::constants::usual_planetary_constants::EARTH_J2;
+    use crate::math::angles::between_pm_180;
+    use crate::math::Vector6;
+    use crate::prelude::{Frame, Orbit};
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+    use core::f64::consts::TAU;
+    use hifitime::Epoch;
+
+    fn eme2k() -> Frame {
+        EARTH_J2000
+            .with_mu_km3_s2(398_600.435_436_096)
+            .with_ellipsoid(Ellipsoid::from_spheroid(6378.1363, 6356.7519))
+    }
+
+    #[test]
+    fn mean_motion_round_trip() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        // Typical ISS mean motion from a GP catalog
+        let orbit = Orbit::try_keplerian_mean_motion(
+            15.5,
+            0.0005,
+            51.64,
+            35.0,
+            65.0,
+            150.0,
+            epoch,
+            eme2k(),
+        )
+        .unwrap();
+
+        assert!((orbit.mean_motion_rev_day().unwrap() - 15.5).abs() < 1e-10);
+        assert!((orbit.sma_km().unwrap() - 6_796.0).abs() < 5.0);
+        assert!((orbit.ma_deg().unwrap() - 150.0).abs() < 1e-8);
+
+        assert!(
+            Orbit::try_keplerian_mean_motion(-1.0, 0.0, 51.6, 0.0, 0.0, 0.0, epoch, eme2k())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn brouwer_mean_round_trip() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let mean = (7_000.0, 0.01, 51.6, 35.0, 65.0, 150.0);
+
+        let osc = Orbit::try_brouwer_mean(
+            mean.0,
+            mean.1,
+            mean.2,
+            mean.3,
+            mean.4,
+            mean.5,
+            EARTH_J2,
+            epoch,
+            eme2k(),
+        )
+        .unwrap();
+
+        // The J2 short period variations of the SMA are of the order of a few kilometers in LEO.
+        let osc_sma_km = osc.sma_km().unwrap();
+        assert!((osc_sma_km - mean.0).abs() > 0.1);
+        assert!((osc_sma_km - mean.0).abs() < 20.0);
+
+        let (sma_km, ecc, inc_deg, raan_deg, aop_deg, ma_deg) =
+            osc.to_brouwer_mean(EARTH_J2).unwrap();
+        assert!((sma_km - mean.0).abs() < 1e-6, "sma: {sma_km}");
+        assert!((ecc - mean.1).abs() < 1e-9, "ecc: {ecc}");
+        assert!((inc_deg - mean.2).abs() < 1e-7, "inc: {inc_deg}");
+        assert!(
+            between_pm_180(raan_deg - mean.3).abs() < 1e-7,
+            "raan: {raan_deg}"
+        );
+        assert!(
+            between_pm_180(aop_deg - mean.4).abs() < 1e-5,
+            "aop: {aop_deg}"
+        );
+        assert!(between_pm_180(ma_deg - mean.5).abs() < 1e-5, "ma: {ma_deg}");
+
+        // Without J2, the mean and osculating elements are identical.
+        let kep = osc.to_brouwer_mean(0.0).unwrap();
+        assert!((kep.0 - osc_sma_km).abs() < 1e-9);
+    }
+
+    /// Two body and J2 dynamics, in km/s and km/s^2.
+    fn j2_dynamics(state: &Vector6, mu_km3_s2: f64, eq_radius_km: f64) -> Vector6 {
+        let rmag = state.fixed_rows::<3>(0).norm();
+        let z2_r2 = (state[2] / rmag).powi(2);
+        let j2_factor = -1.5 * EARTH_J2 * mu_km3_s2 * eq_radius_km.powi(2) / rmag.powi(5);
+        let two_body = -mu_km3_s2 / rmag.powi(3);
+        Vector6::new(
+            state[3],
+            state[4],
+            state[5],
+            two_body * state[0] + j2_factor * state[0] * (1.0 - 5.0 * z2_r2),
+            two_body * state[1] + j2_factor * state[1] * (1.0 - 5.0 * z2_r2),
+            two_body * state[2] + j2_factor * state[2] * (3.0 - 5.0 * z2_r2),
+        )
+    }
+
+    #[test]
+    fn brouwer_mean_sma_orbit_average() {
+        // J2 only has short period effects on the SMA, so the mean SMA is the average of the osculating SMA over one orbit.
+        // This average is computed from an independent numerical propagation of the two body and J2 dynamics.
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let frame = eme2k();
+        let mu_km3_s2 = frame.mu_km3_s2().unwrap();
+        let eq_radius_km = frame.mean_equatorial_radius_km().unwrap();
+
+        // Periapsis at the ascending node maximizes the short period variations of the SMA at this epoch.
+        let osc =
+            Orbit::try_keplerian_mean_anomaly(7_000.0, 0.01, 51.6, 35.0, 0.0, 0.0, epoch, frame)
+                .unwrap();
+        let (mean_sma_km, ..) = osc.to_brouwer_mean(EARTH_J2).unwrap();
+        assert!((osc.sma_km().unwrap() - mean_sma_km).abs() > 1.0);
+
+        let num_steps = 2_000;
+        let step_s = TAU * (mean_sma_km.powi(3) / mu_km3_s2).sqrt() / num_steps as f64;
+        let mut state = osc.to_cartesian_pos_vel();
+        let mut sum_sma_km = 0.0;
+        for i in 0..=num_steps {
+            let sma_km = Orbit::from_cartesian_pos_vel(state, epoch, frame)
+                .sma_km()
+                .unwrap();
+            // Trapezoidal rule
+            sum_sma_km += if i == 0 || i == num_steps {
+                sma_km / 2.0
+            } else {
+                sma_km
+            };
+
+            // Fourth order Runge-Kutta
+            let k1 = j2_dynamics(&state, mu_km3_s2, eq_radius_km);
+            let k2 = j2_dynamics(&(state + k1 * (step_s / 2.0)), mu_km3_s2, eq_radius_km);
+            let k3 = j2_dynamics(&(state + k2 * (step_s / 2.0)), mu_km3_s2, eq_radius_km);
+            let k4 = j2_dynamics(&(state + k3 * step_s), mu_km3_s2, eq_radius_km);
+            state += (k1 + k2 * 2.0 + k3 * 2.0 + k4) * (step_s / 6.0);
+        }
+        let avg_sma_km = sum_sma_km / num_steps as f64;
+
+        assert!(
+            (avg_sma_km - mean_sma_km).abs() < 0.05,
+            "average SMA {avg_sma_km} km != mean SMA {mean_sma_km} km"
+        );
+    }
+
+    #[test]
+    fn brouwer_mean_singularities() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        // Critical inclination
+        assert!(Orbit::try_brouwer_mean(
+            7_000.0,
+            0.01,
+            63.434_948_822_922,
+            35.0,
+            65.0,
+            150.0,
+            EARTH_J2,
+            epoch,
+            eme2k()
+        )
+        .is_err());
+        // Hyperbolic
+        assert!(Orbit::try_brouwer_mean(
+            -7_000.0,
+            1.5,
+            28.5,
+            35.0,
+            65.0,
+            10.0,
+            EARTH_J2,
+            epoch,
+            eme2k()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,235 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::Epoch;
+
+use super::PhysicsResult;
+use crate::math::cartesian::CartesianState;
+use crate::math::Vector3;
+use crate::prelude::Frame;
+
+/// Classical Keplerian orbital elements.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KeplerianElements {
+    pub sma_km: f64,
+    pub ecc: f64,
+    pub inc_deg: f64,
+    pub raan_deg: f64,
+    pub aop_deg: f64,
+    pub ta_deg: f64,
+}
+
+/// Equinoctial elements (a, h, k, p, q, λ), cf. `to_equinoctial`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EquinoctialElements {
+    pub sma_km: f64,
+    pub h: f64,
+    pub k: f64,
+    pub p: f64,
+    pub q: f64,
+    pub mean_long_deg: f64,
+}
+
+/// Geodetic coordinates with respect to the shape of the frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GeodeticCoordinates {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub height_km: f64,
+}
+
+/// Difference between a state and a reference state, expressed in the RIC frame of the reference, cf. `ric_difference`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RICDifference {
+    pub radius_km: Vector3,
+    pub velocity_km_s: Vector3,
+}
+
+/// Summary of a state in all of the representations which apply to it, e.g. for debugging or report tables.
+///
+/// Each representation is None if it cannot be computed for this state, e.g. the Keplerian elements if the frame has no
+/// gravitational parameter, or the equinoctial elements of a hyperbolic orbit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OrbitDescription {
+    pub epoch: Epoch,
+    pub frame: Frame,
+    pub radius_km: Vector3,
+    pub velocity_km_s: Vector3,
+    pub keplerian: Option<KeplerianElements>,
+    pub equinoctial: Option<EquinoctialElements>,
+    pub geodetic: Option<GeodeticCoordinates>,
+    pub ric: Option<RICDifference>,
+}
+
+impl CartesianState {
+    /// Returns the summary of this state in its Cartesian, Keplerian, equinoctial, and geodetic representations, and, if a
+    /// reference state is provided, this state minus the reference expressed in the RIC frame of the reference.
+    ///
+    /// # Frame warning
+    /// The geodetic coordinates are computed if the frame has a shape, but they are only valid if this state is in a body
+    /// fixed frame: use `Almanac::describe_all` to compute them in the body fixed frame of the center from any frame.
+    pub fn describe_all(&self, reference: Option<&Self>) -> OrbitDescription {
+        let keplerian = || -> PhysicsResult<KeplerianElements> {
+            Ok(KeplerianElements {
+                sma_km: self.sma_km()?,
+                ecc: self.ecc()?,
+                inc_deg: self.inc_deg()?,
+                raan_deg: self.raan_deg()?,
+                aop_deg: self.aop_deg()?,
+                ta_deg: self.ta_deg()?,
+            })
+        };
+
+        let equinoctial = || -> PhysicsResult<EquinoctialElements> {
+            let (sma_km, h, k, p, q, mean_long_deg) = self.to_equinoctial()?;
+            Ok(EquinoctialElements {
+                sma_km,
+                h,
+                k,
+                p,
+                q,
+                mean_long_deg,
+            })
+        };
+
+        let geodetic = || -> PhysicsResult<GeodeticCoordinates> {
+            let (latitude_deg, longitude_deg, height_km) = self.latlongalt()?;
+            Ok(GeodeticCoordinates {
+                latitude_deg,
+                longitude_deg,
+                height_km,
+            })
+        };
+
+        let ric = |reference: &Self| -> PhysicsResult<RICDifference> {
+            let diff = reference.ric_difference(self)?;
+            Ok(RICDifference {
+                radius_km: -diff.radius_km,
+                velocity_km_s: -diff.velocity_km_s,
+            })
+        };
+
+        OrbitDescription {
+            epoch: self.epoch,
+            frame: self.frame,
+            radius_km: self.radius_km,
+            velocity_km_s: self.velocity_km_s,
+            keplerian: keplerian().ok(),
+            equinoctial: equinoctial().ok(),
+            geodetic: if self.frame.shape.is_some() {
+                geodetic().ok()
+            } else {
+                None
+            },
+            ric: reference.and_then(|reference| ric(reference).ok()),
+        }
+    }
+}
+
+impl fmt::Display for OrbitDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[{:x}] {}", self.frame, self.epoch)?;
+        writeln!(
+            f,
+            "  Cartesian:   r = [{:.6}, {:.6}, {:.6}] km ({:.6} km)\tv = [{:.9}, {:.9}, {:.9}] km/s ({:.9} km/s)",
+            self.radius_km.x,
+            self.radius_km.y,
+            self.radius_km.z,
+            self.radius_km.norm(),
+            self.velocity_km_s.x,
+            self.velocity_km_s.y,
+            self.velocity_km_s.z,
+            self.velocity_km_s.norm()
+        )?;
+        if let Some(kep) = self.keplerian {
+            writeln!(
+                f,
+                "  Keplerian:   sma = {:.6} km\tecc = {:.6}\tinc = {:.6} deg\traan = {:.6} deg\taop = {:.6} deg\tta = {:.6} deg",
+                kep.sma_km, kep.ecc, kep.inc_deg, kep.raan_deg, kep.aop_deg, kep.ta_deg
+            )?;
+        }
+        if let Some(eq) = self.equinoctial {
+            writeln!(
+                f,
+                "  Equinoctial: a = {:.6} km\th = {:.9}\tk = {:.9}\tp = {:.9}\tq = {:.9}\tλ = {:.6} deg",
+                eq.sma_km, eq.h, eq.k, eq.p, eq.q, eq.mean_long_deg
+            )?;
+        }
+        if let Some(geo) = self.geodetic {
+            writeln!(
+                f,
+                "  Geodetic:    latitude = {:.6} deg\tlongitude = {:.6} deg\theight = {:.3} km",
+                geo.latitude_deg, geo.longitude_deg, geo.height_km
+            )?;
+        }
+        if let Some(ric) = self.ric {
+            writeln!(
+                f,
+                "  RIC:         r = [{:.6}, {:.6}, {:.6}] km\tv = [{:.9}, {:.9}, {:.9}] km/s",
+                ric.radius_km.x,
+                ric.radius_km.y,
+                ric.radius_km.z,
+                ric.velocity_km_s.x,
+                ric.velocity_km_s.y,
+                ric.velocity_km_s.z
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ut_orbit_describe {
+    use crate::constants::frames::EARTH_J2000;
+    use crate::prelude::Orbit;
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+    use hifitime::Epoch;
+
+    #[test]
+    fn describe_all() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let eme2k = EARTH_J2000.with_mu_km3_s2(398_600.435_436_096);
+        let orbit = Orbit::keplerian(8_000.0, 0.1, 28.5, 35.0, 65.0, 150.0, epoch, eme2k);
+
+        let summary = orbit.describe_all(None);
+        println!("{summary}");
+        let kep = summary.keplerian.unwrap();
+        assert!((kep.sma_km - 8_000.0).abs() < 1e-8);
+        assert!((kep.ta_deg - 150.0).abs() < 1e-9);
+        let eq = summary.equinoctial.unwrap();
+        assert!((eq.sma_km - 8_000.0).abs() < 1e-8);
+        assert!((eq.mean_long_deg - orbit.mean_long_deg().unwrap()).abs() < 1e-12);
+        // No shape, no geodetic coordinates, and no reference
+        assert!(summary.geodetic.is_none() && summary.ric.is_none());
+
+        // Without a gravitational parameter, only the Cartesian representation applies
+        let summary = Orbit {
+            frame: EARTH_J2000,
+            ..orbit
+        }
+        .describe_all(None);
+        assert!(summary.keplerian.is_none() && summary.equinoctial.is_none());
+        assert_eq!(summary.radius_km, orbit.radius_km);
+
+        // Radial offset from the reference
+        let reference = orbit;
+        let mut offset = orbit;
+        offset.radius_km += orbit.radius_km.normalize() * 0.5;
+        offset.frame.shape = Some(Ellipsoid::from_spheroid(6378.1366, 6356.7519));
+        let summary = offset.describe_all(Some(&reference));
+        let ric = summary.ric.unwrap();
+        assert!((ric.radius_km.x - 0.5).abs() < 1e-9);
+        assert!(ric.radius_km.y.abs() < 1e-9 && ric.radius_km.z.abs() < 1e-9);
+        assert!(summary.geodetic.is_some());
+        println!("{summary}");
+    }
+}
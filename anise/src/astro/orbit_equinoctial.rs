@@ -0,0 +1,376 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::PhysicsResult;
+use crate::{
+    errors::{InfiniteValueSnafu, MathError, ParabolicSemiParamSnafu, PhysicsError, RadiusSnafu},
+    math::{angles::between_0_360, cartesian::CartesianState, Vector3},
+    prelude::Frame,
+};
+use hifitime::Epoch;
+use snafu::ensure;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyType;
+
+/// Maximum number of Newton iterations when solving the equinoctial form of Kepler's equation.
+const KEPLER_MAX_ITER: usize = 50;
+/// Convergence tolerance on the eccentric longitude, in radians.
+const KEPLER_TOL_RAD: f64 = 1e-14;
+
+/// In-plane unit vectors of the equinoctial frame (f_hat, g_hat) from the modified equinoctial elements h and k.
+fn equinoctial_frame(h: f64, k: f64) -> (Vector3, Vector3) {
+    let s2 = 1.0 + h.powi(2) + k.powi(2);
+    let f_hat = Vector3::new(1.0 + h.powi(2) - k.powi(2), 2.0 * h * k, -2.0 * k) / s2;
+    let g_hat = Vector3::new(2.0 * h * k, 1.0 - h.powi(2) + k.powi(2), 2.0 * h) / s2;
+    (f_hat, g_hat)
+}
+
+fn elliptical_only(ecc: f64) -> PhysicsError {
+    PhysicsError::AppliedMath {
+        source: MathError::DomainError {
+            value: ecc,
+            msg: "equinoctial elements with a mean longitude require an elliptical orbit",
+        },
+    }
+}
+
+impl CartesianState {
+    /// Attempts to create a new Orbit from the modified equinoctial elements (p, f, g, h, k, L), as defined in GMAT and Nyx:
+    ///
+    /// + `p_km`: semi-parameter, i.e. a (1 - e^2)
+    /// + `f`: e cos(ω + Ω)
+    /// + `g`: e sin(ω + Ω)
+    /// + `h`: tan(i/2) cos(Ω)
+    /// + `k`: tan(i/2) sin(Ω)
+    /// + `true_long_deg`: true longitude L = Ω + ω + ν
+    ///
+    /// These elements are not singular for circular or equatorial orbits, and are defined for hyperbolic orbits.
+    /// They are singular for retrograde equatorial orbits (i = 180 deg).
+    ///
+    /// Source: Walker, Ireland, and Owens, "A set of modified equinoctial orbit elements", Celestial Mechanics 36 (1985).
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_modified_equinoctial(
+        p_km: f64,
+        f: f64,
+        g: f64,
+        h: f64,
+        k: f64,
+        true_long_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> PhysicsResult<Self> {
+        ensure!(p_km > f64::EPSILON, ParabolicSemiParamSnafu { p_km });
+        let mu_km3_s2 = frame.mu_km3_s2()?;
+
+        let (sin_l, cos_l) = true_long_deg.to_radians().sin_cos();
+        let w = 1.0 + f * cos_l + g * sin_l;
+        ensure!(
+            w > f64::EPSILON,
+            InfiniteValueSnafu {
+                action: "computing the radius from modified equinoctial elements"
+            }
+        );
+        let r_km = p_km / w;
+
+        let (f_hat, g_hat) = equinoctial_frame(h, k);
+        let sqrt_mu_p = (mu_km3_s2 / p_km).sqrt();
+
+        let radius_km = r_km * (cos_l * f_hat + sin_l * g_hat);
+        let velocity_km_s = sqrt_mu_p * (-(g + sin_l) * f_hat + (f + cos_l) * g_hat);
+
+        Ok(Self {
+            radius_km,
+            velocity_km_s,
+            epoch,
+            frame,
+        })
+    }
+
+    /// Attempts to create a new Orbit from the equinoctial elements (a, h, k, p, q, λ), as defined in GMAT:
+    ///
+    /// + `sma_km`: semi-major axis
+    /// + `h`: e sin(ω + Ω)
+    /// + `k`: e cos(ω + Ω)
+    /// + `p`: tan(i/2) sin(Ω)
+    /// + `q`: tan(i/2) cos(Ω)
+    /// + `mean_long_deg`: mean longitude λ = Ω + ω + M
+    ///
+    /// These elements are only defined for elliptical orbits. The mean longitude is converted into the true longitude
+    /// by solving the equinoctial form of Kepler's equation with a Newton method.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_equinoctial(
+        sma_km: f64,
+        h: f64,
+        k: f64,
+        p: f64,
+        q: f64,
+        mean_long_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> PhysicsResult<Self> {
+        let ecc = (h.powi(2) + k.powi(2)).sqrt();
+        if ecc >= 1.0 {
+            return Err(elliptical_only(ecc));
+        }
+
+        // Solve λ = F - k sin(F) + h cos(F) for the eccentric longitude F
+        let mean_long_rad = mean_long_deg.to_radians();
+        let mut ecc_long_rad = mean_long_rad;
+        for _ in 0..KEPLER_MAX_ITER {
+            let (sin_f, cos_f) = ecc_long_rad.sin_cos();
+            let delta = (ecc_long_rad - k * sin_f + h * cos_f - mean_long_rad)
+                / (1.0 - k * cos_f - h * sin_f);
+            ecc_long_rad -= delta;
+            if delta.abs() < KEPLER_TOL_RAD {
+                break;
+            }
+        }
+
+        // Convert the eccentric anomaly into the true anomaly, and add the longitude of periapsis
+        let lon_peri_rad = h.atan2(k);
+        let ea_rad = ecc_long_rad - lon_peri_rad;
+        let ta_rad = 2.0
+            * ((1.0 + ecc).sqrt() * (ea_rad / 2.0).sin())
+                .atan2((1.0 - ecc).sqrt() * (ea_rad / 2.0).cos());
+
+        Self::try_modified_equinoctial(
+            sma_km * (1.0 - ecc.powi(2)),
+            k,
+            h,
+            q,
+            p,
+            (ta_rad + lon_peri_rad).to_degrees(),
+            epoch,
+            frame,
+        )
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl CartesianState {
+    /// Creates a new Orbit from the modified equinoctial elements (p, f, g, h, k, L), where p is the semi-parameter in km
+    /// and L is the true longitude in degrees, as defined in GMAT and Nyx.
+    ///
+    /// :type p_km: float
+    /// :type f: float
+    /// :type g: float
+    /// :type h: float
+    /// :type k: float
+    /// :type true_long_deg: float
+    /// :type epoch: Epoch
+    /// :type frame: Frame
+    /// :rtype: Orbit
+    #[cfg(feature = "python")]
+    #[classmethod]
+    pub fn from_modified_equinoctial(
+        _cls: &Bound<'_, PyType>,
+        p_km: f64,
+        f: f64,
+        g: f64,
+        h: f64,
+        k: f64,
+        true_long_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> PhysicsResult<Self> {
+        Self::try_modified_equinoctial(p_km, f, g, h, k, true_long_deg, epoch, frame)
+    }
+
+    /// Creates a new Orbit from the equinoctial elements (a, h, k, p, q, λ), where a is the semi-major axis in km
+    /// and λ is the mean longitude in degrees, as defined in GMAT.
+    ///
+    /// :type sma_km: float
+    /// :type h: float
+    /// :type k: float
+    /// :type p: float
+    /// :type q: float
+    /// :type mean_long_deg: float
+    /// :type epoch: Epoch
+    /// :type frame: Frame
+    /// :rtype: Orbit
+    #[cfg(feature = "python")]
+    #[classmethod]
+    pub fn from_equinoctial(
+        _cls: &Bound<'_, PyType>,
+        sma_km: f64,
+        h: f64,
+        k: f64,
+        p: f64,
+        q: f64,
+        mean_long_deg: f64,
+        epoch: Epoch,
+        frame: Frame,
+    ) -> PhysicsResult<Self> {
+        Self::try_equinoctial(sma_km, h, k, p, q, mean_long_deg, epoch, frame)
+    }
+
+    /// Returns the modified equinoctial elements (p, f, g, h, k, L), where p is the semi-parameter in km and L is the true longitude in degrees.
+    ///
+    /// The conversion is computed from the Cartesian state directly, and is not singular for circular or equatorial orbits.
+    /// It is singular for retrograde equatorial orbits (i = 180 deg).
+    ///
+    /// :rtype: typing.Tuple
+    pub fn to_modified_equinoctial(&self) -> PhysicsResult<(f64, f64, f64, f64, f64, f64)> {
+        ensure!(
+            self.rmag_km() > f64::EPSILON,
+            RadiusSnafu {
+                action: "cannot compute modified equinoctial elements with zero radial state"
+            }
+        );
+        let mu_km3_s2 = self.frame.mu_km3_s2()?;
+        let hvec = self.hvec()?;
+        let hmag = hvec.norm();
+        let h_hat = hvec / hmag;
+
+        if 1.0 + h_hat.z < f64::EPSILON {
+            return Err(PhysicsError::AppliedMath {
+                source: MathError::DomainError {
+                    value: h_hat.z,
+                    msg: "modified equinoctial elements are singular for retrograde equatorial orbits",
+                },
+            });
+        }
+
+        let p_km = hmag.powi(2) / mu_km3_s2;
+        let h = -h_hat.y / (1.0 + h_hat.z);
+        let k = h_hat.x / (1.0 + h_hat.z);
+
+        let (f_hat, g_hat) = equinoctial_frame(h, k);
+        let evec = self.evec()?;
+        let f = evec.dot(&f_hat);
+        let g = evec.dot(&g_hat);
+
+        let r_hat = self.r_hat();
+        let true_long_deg = between_0_360(r_hat.dot(&g_hat).atan2(r_hat.dot(&f_hat)).to_degrees());
+
+        Ok((p_km, f, g, h, k, true_long_deg))
+    }
+
+    /// Returns the equinoctial elements (a, h, k, p, q, λ), where a is the semi-major axis in km and λ is the mean longitude in degrees.
+    ///
+    /// These elements are only defined for elliptical orbits.
+    ///
+    /// :rtype: typing.Tuple
+    pub fn to_equinoctial(&self) -> PhysicsResult<(f64, f64, f64, f64, f64, f64)> {
+        let (p_km, f, g, h, k, true_long_deg) = self.to_modified_equinoctial()?;
+        let ecc = (f.powi(2) + g.powi(2)).sqrt();
+        if ecc >= 1.0 {
+            return Err(elliptical_only(ecc));
+        }
+        let sma_km = p_km / (1.0 - ecc.powi(2));
+
+        // Convert the true anomaly into the eccentric anomaly, which gives the eccentric longitude
+        let lon_peri_rad = g.atan2(f);
+        let ta_rad = true_long_deg.to_radians() - lon_peri_rad;
+        let ea_rad = 2.0
+            * ((1.0 - ecc).sqrt() * (ta_rad / 2.0).sin())
+                .atan2((1.0 + ecc).sqrt() * (ta_rad / 2.0).cos());
+        let ecc_long_rad = ea_rad + lon_peri_rad;
+
+        let (sin_f, cos_f) = ecc_long_rad.sin_cos();
+        let mean_long_deg = between_0_360((ecc_long_rad - f * sin_f + g * cos_f).to_degrees());
+
+        Ok((sma_km, g, f, k, h, mean_long_deg))
+    }
+
+    /// Returns the mean longitude in degrees, i.e. Ω + ω + M, which is well defined for circular and equatorial orbits.
+    ///
+    /// :rtype: float
+    pub fn mean_long_deg(&self) -> PhysicsResult<f64> {
+        Ok(self.to_equinoctial()?.5)
+    }
+}
+
+#[cfg(test)]
+mod ut_equinoctial {
+    use crate::constants::frames::EARTH_J2000;
+    use crate::prelude::{Frame, Orbit};
+    use hifitime::Epoch;
+
+    fn eme2k() -> Frame {
+        EARTH_J2000.with_mu_km3_s2(398_600.435_436_096)
+    }
+
+    #[test]
+    fn modified_equinoctial_round_trip() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let (ecc, inc_deg, raan_deg, aop_deg, ta_deg): (f64, f64, f64, f64, f64) =
+            (0.1, 28.5, 35.0, 65.0, 150.0);
+        let orbit = Orbit::keplerian(
+            8_000.0,
+            ecc,
+            inc_deg,
+            raan_deg,
+            aop_deg,
+            ta_deg,
+            epoch,
+            eme2k(),
+        );
+
+        let (p_km, f, g, h, k, true_long_deg) = orbit.to_modified_equinoctial().unwrap();
+        let lon_peri = (raan_deg + aop_deg).to_radians();
+        let tan_half_inc = (inc_deg / 2.0).to_radians().tan();
+
+        assert!((p_km - orbit.semi_parameter_km().unwrap()).abs() < 1e-8);
+        assert!((f - ecc * lon_peri.cos()).abs() < 1e-12);
+        assert!((g - ecc * lon_peri.sin()).abs() < 1e-12);
+        assert!((h - tan_half_inc * raan_deg.to_radians().cos()).abs() < 1e-12);
+        assert!((k - tan_half_inc * raan_deg.to_radians().sin()).abs() < 1e-12);
+        assert!((true_long_deg - (raan_deg + aop_deg + ta_deg)).abs() < 1e-9);
+
+        let rebuilt =
+            Orbit::try_modified_equinoctial(p_km, f, g, h, k, true_long_deg, epoch, eme2k())
+                .unwrap();
+        assert!((rebuilt.radius_km - orbit.radius_km).norm() < 1e-8);
+        assert!((rebuilt.velocity_km_s - orbit.velocity_km_s).norm() < 1e-11);
+    }
+
+    #[test]
+    fn circular_equatorial() {
+        // Keplerian elements are singular here, but the modified equinoctial elements are not.
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let orbit =
+            Orbit::try_modified_equinoctial(7_000.0, 0.0, 0.0, 0.0, 0.0, 42.0, epoch, eme2k())
+                .unwrap();
+        assert!((orbit.rmag_km() - 7_000.0).abs() < 1e-9);
+        assert!((orbit.vmag_km_s() - (398_600.435_436_096_f64 / 7_000.0).sqrt()).abs() < 1e-12);
+
+        let (p_km, f, g, h, k, true_long_deg) = orbit.to_modified_equinoctial().unwrap();
+        assert!((p_km - 7_000.0).abs() < 1e-8);
+        assert!(f.abs() < 1e-12 && g.abs() < 1e-12);
+        assert!(h.abs() < 1e-12 && k.abs() < 1e-12);
+        assert!((true_long_deg - 42.0).abs() < 1e-9);
+
+        // The mean longitude equals the true longitude for circular orbits
+        assert!((orbit.mean_long_deg().unwrap() - 42.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn equinoctial_round_trip() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let orbit = Orbit::keplerian(26_600.0, 0.7, 63.4, 120.0, 270.0, 10.0, epoch, eme2k());
+
+        let (sma_km, h, k, p, q, mean_long_deg) = orbit.to_equinoctial().unwrap();
+        assert!((sma_km - 26_600.0).abs() < 1e-6);
+        let expected_mean_long = (120.0 + 270.0 + orbit.ma_deg().unwrap()) % 360.0;
+        assert!((mean_long_deg - expected_mean_long).abs() < 1e-8);
+
+        let rebuilt =
+            Orbit::try_equinoctial(sma_km, h, k, p, q, mean_long_deg, epoch, eme2k()).unwrap();
+        assert!((rebuilt.radius_km - orbit.radius_km).norm() < 1e-6);
+        assert!((rebuilt.velocity_km_s - orbit.velocity_km_s).norm() < 1e-9);
+
+        // Equinoctial elements are only defined for elliptical orbits
+        assert!(Orbit::try_equinoctial(sma_km, 0.8, 0.8, p, q, 0.0, epoch, eme2k()).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::PhysicsResult;
+use crate::math::cartesian::CartesianState;
+use crate::math::units::{Deg, Km, KmPerSec};
+
+/// Typed accessors of the orbital state, where the unit is carried by the return type instead of the function name,
+/// such that mixing up units (e.g. adding an angle to a distance) is caught at compile time.
+///
+/// These are thin wrappers around the raw f64 accessors (e.g. `sma()` wraps `sma_km()`), which remain available for performance-critical code.
+impl CartesianState {
+    /// Returns the magnitude of the radius vector
+    pub fn rmag(&self) -> Km {
+        Km(self.rmag_km())
+    }
+
+    /// Returns the magnitude of the velocity vector
+    pub fn vmag(&self) -> KmPerSec {
+        KmPerSec(self.vmag_km_s())
+    }
+
+    /// Returns the distance between this state and another state
+    pub fn distance_to(&self, other: &Self) -> PhysicsResult<Km> {
+        self.distance_to_km(other).map(Km)
+    }
+
+    /// Returns the semi-major axis
+    pub fn sma(&self) -> PhysicsResult<Km> {
+        self.sma_km().map(Km)
+    }
+
+    /// Returns the semi-major axis altitude above the mean equatorial radius of the frame
+    pub fn sma_altitude(&self) -> PhysicsResult<Km> {
+        self.sma_altitude_km().map(Km)
+    }
+
+    /// Returns the radius of periapsis
+    pub fn periapsis(&self) -> PhysicsResult<Km> {
+        self.periapsis_km().map(Km)
+    }
+
+    /// Returns the radius of apoapsis
+    pub fn apoapsis(&self) -> PhysicsResult<Km> {
+        self.apoapsis_km().map(Km)
+    }
+
+    /// Returns the inclination
+    pub fn inc(&self) -> PhysicsResult<Deg> {
+        self.inc_deg().map(Deg)
+    }
+
+    /// Returns the right ascension of the ascending node
+    pub fn raan(&self) -> PhysicsResult<Deg> {
+        self.raan_deg().map(Deg)
+    }
+
+    /// Returns the argument of periapsis
+    pub fn aop(&self) -> PhysicsResult<Deg> {
+        self.aop_deg().map(Deg)
+    }
+
+    /// Returns the true anomaly
+    pub fn ta(&self) -> PhysicsResult<Deg> {
+        self.ta_deg().map(Deg)
+    }
+}
+
+#[cfg(test)]
+mod ut_orbit_typed {
+    use crate::constants::frames::EARTH_J2000;
+    use crate::math::units::{Deg, Km, Rad};
+    use crate::prelude::Orbit;
+    use hifitime::Epoch;
+
+    #[test]
+    fn typed_accessors() {
+        let eme2k = EARTH_J2000.with_mu_km3_s2(398_600.435_436_096);
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let orbit = Orbit::keplerian(7_000.0, 0.01, 51.6, 30.0, 60.0, 90.0, epoch, eme2k);
+
+        assert!((orbit.sma().unwrap() - Km(7_000.0)).abs() < Km(1e-9));
+        assert!((orbit.inc().unwrap() - Deg(51.6)).abs() < Deg(1e-9));
+        assert_eq!(orbit.rmag(), Km(orbit.rmag_km()));
+        let apsides_diff = orbit.apoapsis().unwrap() - orbit.periapsis().unwrap();
+        assert!((apsides_diff - 2.0 * 0.01 * orbit.sma().unwrap()).abs() < Km(1e-9));
+        let raan: Rad = orbit.raan().unwrap().into();
+        assert!((raan.to_deg() - Deg(30.0)).abs() < Deg(1e-9));
+    }
+}
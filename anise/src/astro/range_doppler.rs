@@ -0,0 +1,174 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use hifitime::{Duration, Epoch};
+
+use crate::constants::SPEED_OF_LIGHT_KM_S;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Stores a two-way range and range rate observable, computed with the light time iteration of both the uplink and the downlink.
+///
+/// The epoch is the reception epoch of the signal at the location. The signal was transmitted by the location at
+/// `epoch - uplink_light_time - downlink_light_time`, and it was turned around by the target at `epoch - downlink_light_time`.
+///
+/// :type epoch: Epoch
+/// :type range_km: float
+/// :type range_rate_km_s: float
+/// :type uplink_light_time: Duration
+/// :type downlink_light_time: Duration
+/// :rtype: TwoWayRangeDoppler
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise.astro"))]
+pub struct TwoWayRangeDoppler {
+    pub epoch: Epoch,
+    /// Half of the round trip light time, in kilometers
+    pub range_km: f64,
+    /// Time derivative of the two-way range with respect to the reception epoch, in kilometers per second
+    pub range_rate_km_s: f64,
+    pub uplink_light_time: Duration,
+    pub downlink_light_time: Duration,
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl TwoWayRangeDoppler {
+    /// Returns the epoch at which the signal was transmitted by the location.
+    ///
+    /// :rtype: Epoch
+    pub fn transmit_epoch(&self) -> Epoch {
+        self.epoch - self.uplink_light_time - self.downlink_light_time
+    }
+
+    /// Returns the epoch at which the signal was turned around by the target.
+    ///
+    /// :rtype: Epoch
+    pub fn turnaround_epoch(&self) -> Epoch {
+        self.epoch - self.downlink_light_time
+    }
+
+    /// Returns the two-way Doppler shift in Hertz of a signal transmitted at the uplink frequency (in Hertz) and
+    /// coherently turned around by the target with the provided turnaround ratio (e.g. 880/749 for X-band).
+    ///
+    /// The shift is negative when the range increases.
+    ///
+    /// :type uplink_frequency_hz: float
+    /// :type turnaround_ratio: float
+    /// :rtype: float
+    pub fn doppler_hz(&self, uplink_frequency_hz: f64, turnaround_ratio: f64) -> f64 {
+        -2.0 * turnaround_ratio * uplink_frequency_hz * self.range_rate_km_s / SPEED_OF_LIGHT_KM_S
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+#[cfg(feature = "python")]
+impl TwoWayRangeDoppler {
+    #[new]
+    fn py_new(
+        epoch: Epoch,
+        range_km: f64,
+        range_rate_km_s: f64,
+        uplink_light_time: Duration,
+        downlink_light_time: Duration,
+    ) -> Self {
+        Self {
+            epoch,
+            range_km,
+            range_rate_km_s,
+            uplink_light_time,
+            downlink_light_time,
+        }
+    }
+
+    /// :rtype: Epoch
+    #[getter]
+    fn get_epoch(&self) -> PyResult<Epoch> {
+        Ok(self.epoch)
+    }
+    /// :type epoch: Epoch
+    #[setter]
+    fn set_epoch(&mut self, epoch: Epoch) -> PyResult<()> {
+        self.epoch = epoch;
+        Ok(())
+    }
+
+    /// :rtype: float
+    #[getter]
+    fn get_range_km(&self) -> PyResult<f64> {
+        Ok(self.range_km)
+    }
+    /// :type range_km: float
+    #[setter]
+    fn set_range_km(&mut self, range_km: f64) -> PyResult<()> {
+        self.range_km = range_km;
+        Ok(())
+    }
+
+    /// :rtype: float
+    #[getter]
+    fn get_range_rate_km_s(&self) -> PyResult<f64> {
+        Ok(self.range_rate_km_s)
+    }
+    /// :type range_rate_km_s: float
+    #[setter]
+    fn set_range_rate_km_s(&mut self, range_rate_km_s: f64) -> PyResult<()> {
+        self.range_rate_km_s = range_rate_km_s;
+        Ok(())
+    }
+
+    /// :rtype: Duration
+    #[getter]
+    fn get_uplink_light_time(&self) -> PyResult<Duration> {
+        Ok(self.uplink_light_time)
+    }
+    /// :type uplink_light_time: Duration
+    #[setter]
+    fn set_uplink_light_time(&mut self, uplink_light_time: Duration) -> PyResult<()> {
+        self.uplink_light_time = uplink_light_time;
+        Ok(())
+    }
+
+    /// :rtype: Duration
+    #[getter]
+    fn get_downlink_light_time(&self) -> PyResult<Duration> {
+        Ok(self.downlink_light_time)
+    }
+    /// :type downlink_light_time: Duration
+    #[setter]
+    fn set_downlink_light_time(&mut self, downlink_light_time: Duration) -> PyResult<()> {
+        self.downlink_light_time = downlink_light_time;
+        Ok(())
+    }
+
+    fn __str__(&self) -> String {
+        format!("{self}")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self} (@{self:p})")
+    }
+}
+
+impl fmt::Display for TwoWayRangeDoppler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: range: {:.6} km    range-rate: {:.6} km/s    (uplink: {}, downlink: {})",
+            self.epoch,
+            self.range_km,
+            self.range_rate_km_s,
+            self.uplink_light_time,
+            self.downlink_light_time
+        )
+    }
+}
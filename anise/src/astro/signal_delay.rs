@@ -0,0 +1,198 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::f64::consts::PI;
+
+use hifitime::Epoch;
+
+use crate::constants::SPEED_OF_LIGHT_KM_S;
+
+/// Frequency of the GPS L1 signal in Hertz, for which the Klobuchar coefficients are broadcast.
+pub const GPS_L1_FREQUENCY_HZ: f64 = 1_575.42e6;
+
+/// Saastamoinen model of the tropospheric delay, computed from the surface meteorological conditions at the location.
+///
+/// The delay is the sum of the hydrostatic and wet delays, mapped to the elevation with the cosecant of the elevation,
+/// and does not depend on the frequency of the signal.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Saastamoinen {
+    /// Total surface pressure in hectopascals (millibars)
+    pub pressure_hpa: f64,
+    /// Surface temperature in Kelvin
+    pub temperature_k: f64,
+    /// Relative humidity, between 0 and 1
+    pub relative_humidity: f64,
+}
+
+impl Default for Saastamoinen {
+    /// Standard atmosphere at sea level, with 50% relative humidity
+    fn default() -> Self {
+        Self {
+            pressure_hpa: 1013.25,
+            temperature_k: 288.15,
+            relative_humidity: 0.5,
+        }
+    }
+}
+
+impl Saastamoinen {
+    /// Returns the standard atmosphere at the provided height above the geoid in kilometers, with 50% relative humidity.
+    pub fn standard_at_height(height_km: f64) -> Self {
+        let height_m = height_km.max(0.0) * 1e3;
+        Self {
+            pressure_hpa: 1013.25 * (1.0 - 2.2557e-5 * height_m).powf(5.2568),
+            temperature_k: 288.15 - 6.5e-3 * height_m,
+            relative_humidity: 0.5,
+        }
+    }
+
+    /// Returns the slant tropospheric delay in kilometers for a signal at the provided elevation (in degrees) seen from
+    /// the provided geodetic latitude (in degrees) and height (in kilometers). The delay is zero for negative elevations.
+    pub fn slant_delay_km(&self, elevation_deg: f64, latitude_deg: f64, height_km: f64) -> f64 {
+        if elevation_deg <= 0.0 {
+            return 0.0;
+        }
+
+        let sin_el = elevation_deg.to_radians().sin();
+
+        // Partial pressure of the water vapor in hectopascals
+        let water_vapor_hpa = 6.108
+            * self.relative_humidity
+            * ((17.15 * self.temperature_k - 4684.0) / (self.temperature_k - 38.45)).exp();
+
+        let hydrostatic_m = 0.0022768 * self.pressure_hpa
+            / (1.0 - 0.00266 * (2.0 * latitude_deg.to_radians()).cos() - 0.00028 * height_km);
+        let wet_m = 0.002277 * (1255.0 / self.temperature_k + 0.05) * water_vapor_hpa;
+
+        (hydrostatic_m + wet_m) / sin_el * 1e-3
+    }
+}
+
+/// Klobuchar model of the ionospheric delay, as broadcast in the GPS navigation message (IS-GPS-200).
+///
+/// The model provides the delay on the GPS L1 frequency, which is scaled to the frequency of the signal
+/// since the ionospheric delay is inversely proportional to the square of the frequency.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Klobuchar {
+    /// Coefficients of the amplitude of the vertical delay, in seconds, seconds per semicircle, etc.
+    pub alpha: [f64; 4],
+    /// Coefficients of the period of the model, in seconds, seconds per semicircle, etc.
+    pub beta: [f64; 4],
+    /// Frequency of the signal in Hertz
+    pub frequency_hz: f64,
+}
+
+impl Klobuchar {
+    /// Returns the slant ionospheric delay in kilometers of a signal at the provided epoch, seen from the provided geodetic
+    /// latitude and longitude (in degrees) at the provided azimuth and elevation (in degrees). The delay is zero for negative elevations.
+    pub fn slant_delay_km(
+        &self,
+        epoch: Epoch,
+        latitude_deg: f64,
+        longitude_deg: f64,
+        azimuth_deg: f64,
+        elevation_deg: f64,
+    ) -> f64 {
+        if elevation_deg <= 0.0 {
+            return 0.0;
+        }
+
+        // The model works in semicircles
+        let elevation_sc = elevation_deg / 180.0;
+        let azimuth_rad = azimuth_deg.to_radians();
+
+        // Earth centered angle between the location and the ionospheric pierce point
+        let psi = 0.0137 / (elevation_sc + 0.11) - 0.022;
+
+        // Latitude and longitude of the ionospheric pierce point
+        let phi_i = (latitude_deg / 180.0 + psi * azimuth_rad.cos()).clamp(-0.416, 0.416);
+        let lambda_i = longitude_deg / 180.0 + psi * azimuth_rad.sin() / (phi_i * PI).cos();
+
+        // Geomagnetic latitude of the pierce point
+        let phi_m = phi_i + 0.064 * ((lambda_i - 1.617) * PI).cos();
+
+        // Local time at the pierce point, in seconds
+        let local_time_s = (43200.0 * lambda_i + epoch.to_gpst_seconds()).rem_euclid(86400.0);
+
+        // Slant factor
+        let slant_factor = 1.0 + 16.0 * (0.53 - elevation_sc).powi(3);
+
+        let polynomial = |coeffs: &[f64; 4]| {
+            coeffs[0] + phi_m * (coeffs[1] + phi_m * (coeffs[2] + phi_m * coeffs[3]))
+        };
+
+        let amplitude_s = polynomial(&self.alpha).max(0.0);
+        let period_s = polynomial(&self.beta).max(72_000.0);
+
+        let x = 2.0 * PI * (local_time_s - 50_400.0) / period_s;
+
+        let delay_l1_s = if x.abs() < 1.57 {
+            slant_factor * (5e-9 + amplitude_s * (1.0 - x.powi(2) / 2.0 + x.powi(4) / 24.0))
+        } else {
+            slant_factor * 5e-9
+        };
+
+        delay_l1_s * SPEED_OF_LIGHT_KM_S * (GPS_L1_FREQUENCY_HZ / self.frequency_hz).powi(2)
+    }
+}
+
+#[cfg(test)]
+mod ut_signal_delay {
+    use super::*;
+
+    #[test]
+    fn saastamoinen() {
+        let tropo = Saastamoinen::default();
+
+        // The zenith delay at sea level is about 2.4 meters.
+        let zenith_km = tropo.slant_delay_km(90.0, 45.0, 0.0);
+        assert!(zenith_km > 2.2e-3 && zenith_km < 2.6e-3, "{zenith_km}");
+
+        // The delay increases towards the horizon, roughly with the cosecant of the elevation.
+        let low_km = tropo.slant_delay_km(10.0, 45.0, 0.0);
+        assert!((low_km / zenith_km - 1.0 / 10.0_f64.to_radians().sin()).abs() < 1e-9);
+
+        // Less atmosphere above a high location
+        let high = Saastamoinen::standard_at_height(2.0);
+        assert!(high.slant_delay_km(90.0, 45.0, 2.0) < zenith_km);
+
+        assert_eq!(tropo.slant_delay_km(-1.0, 45.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn klobuchar() {
+        let mut iono = Klobuchar {
+            alpha: [0.1118e-07, -0.7451e-08, -0.5961e-07, 0.1192e-06],
+            beta: [0.1167e+06, -0.2294e+06, -0.1311e+06, 0.1049e+07],
+            frequency_hz: GPS_L1_FREQUENCY_HZ,
+        };
+
+        let epoch = Epoch::from_gregorian_utc_hms(2024, 3, 20, 12, 0, 0);
+
+        // The night time delay at zenith is 5 ns, and the model is always at least that.
+        let zenith_km = iono.slant_delay_km(epoch, 40.0, 4.0, 0.0, 90.0);
+        assert!(
+            zenith_km >= 5e-9 * SPEED_OF_LIGHT_KM_S * 0.99,
+            "{zenith_km}"
+        );
+        assert!(zenith_km < 0.05, "{zenith_km}");
+
+        // The slant delay is larger near the horizon.
+        let low_km = iono.slant_delay_km(epoch, 40.0, 4.0, 0.0, 10.0);
+        assert!(low_km > zenith_km);
+
+        // At X band, the delay is much smaller.
+        iono.frequency_hz = 8.4e9;
+        let x_band_km = iono.slant_delay_km(epoch, 40.0, 4.0, 0.0, 90.0);
+        assert!((x_band_km - zenith_km * (GPS_L1_FREQUENCY_HZ / 8.4e9).powi(2)).abs() < 1e-15);
+
+        assert_eq!(iono.slant_delay_km(epoch, 40.0, 4.0, 0.0, -5.0), 0.0);
+    }
+}
@@ -194,3 +194,28 @@ pub fn compute_mean_to_true_anomaly_rad(ma_radians: f64, ecc: f64) -> PhysicsRes
         Ok(ta)
     }
 }
+
+/// Computes the Stumpff functions c2(psi) and c3(psi) used in the universal variable formulation.
+///
+/// Source: Vallado, Fundamentals of Astrodynamics and Applications, 4th ed., Algorithm 1.
+pub fn stumpff_c2_c3(psi: f64) -> (f64, f64) {
+    if psi > 1e-6 {
+        let sqrt_psi = psi.sqrt();
+        (
+            (1.0 - sqrt_psi.cos()) / psi,
+            (sqrt_psi - sqrt_psi.sin()) / psi.powf(1.5),
+        )
+    } else if psi < -1e-6 {
+        let sqrt_mpsi = (-psi).sqrt();
+        (
+            (1.0 - sqrt_mpsi.cosh()) / psi,
+            (sqrt_mpsi.sinh() - sqrt_mpsi) / (-psi).powf(1.5),
+        )
+    } else {
+        // Series expansion near zero to avoid the cancellation error
+        (
+            0.5 - psi / 24.0 + psi.powi(2) / 720.0,
+            1.0 / 6.0 - psi / 120.0 + psi.powi(2) / 5040.0,
+        )
+    }
+}
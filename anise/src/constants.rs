@@ -11,6 +11,12 @@
 /// Speed of light in kilometers per second (km/s)
 pub const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458;
 
+/// Astronomical unit, in km (IAU 2012 Resolution B2).
+pub const AU_KM: f64 = 149_597_870.7;
+
+/// Total solar irradiance at one astronomical unit, in W/m^2 (IAU 2015 Resolution B3).
+pub const SOLAR_FLUX_1AU_W_M2: f64 = 1361.0;
+
 pub mod celestial_objects {
     use crate::{ephemerides::EphemerisError, NaifId};
 
@@ -240,6 +246,9 @@ pub mod orientations {
     pub const IAU_NEPTUNE: NaifId = 799;
     pub const IAU_URANUS: NaifId = 899;
 
+    /// Offset of the orientation IDs of the synodic frames, whose ID is `SYNODIC_OFFSET + 1000 * primary + secondary` (cf. `Frame::synodic`)
+    pub const SYNODIC_OFFSET: NaifId = 2_000_000_000;
+
     /// Angle between J2000 to solar system ecliptic J2000 ([ECLIPJ2000]), in radians (about 23.43929 degrees). Apply this rotation about the X axis (R1)
     pub const J2000_TO_ECLIPJ2000_ANGLE_RAD: f64 = 0.40909280422232897;
 
@@ -362,6 +371,9 @@ pub mod usual_planetary_constants {
     /// ```
     /// Source: <https://www.britannica.com/science/month#ref225844> via <https://en.wikipedia.org/w/index.php?title=Lunar_day&oldid=1180701337>
     pub const MEAN_MOON_ANGULAR_VELOCITY_DEG_S: f64 = 2.661_698_975_163_682e-6;
+    /// Unnormalized J2 zonal harmonic of the Earth, e.g. for the Brouwer-Lyddane mean element conversions of Earth orbits.
+    /// Source: GMAT's Brouwer Mean Short conversion (JGM-2 value).
+    pub const EARTH_J2: f64 = 1.082_626_925_638_815e-3;
 }
 
 #[cfg(test)]
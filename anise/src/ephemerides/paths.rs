@@ -58,44 +58,59 @@ impl Almanac {
         source: Frame,
         epoch: Epoch,
     ) -> Result<(usize, [Option<NaifId>; MAX_TREE_DEPTH]), EphemerisError> {
-        let common_center = self.try_find_ephemeris_root()?;
+        let (of_path_len, of_path, result) = self.ephemeris_path_walk(source, epoch);
+        result.map(|_| (of_path_len, of_path))
+    }
+
+    /// Walks the path from the source frame to the root ephemeris of this context, returning the nodes reached so far
+    /// and, if the walk stopped before the root, the error of the hop which failed.
+    pub(crate) fn ephemeris_path_walk(
+        &self,
+        source: Frame,
+        epoch: Epoch,
+    ) -> (
+        usize,
+        [Option<NaifId>; MAX_TREE_DEPTH],
+        Result<(), EphemerisError>,
+    ) {
         // Build a tree, set a fixed depth to avoid allocations
         let mut of_path = [None; MAX_TREE_DEPTH];
         let mut of_path_len = 0;
 
+        let common_center = match self.try_find_ephemeris_root() {
+            Ok(common_center) => common_center,
+            Err(e) => return (of_path_len, of_path, Err(e)),
+        };
+
         if common_center == source.ephemeris_id {
             // We're querying the source, no need to check that this summary even exists.
-            return Ok((of_path_len, of_path));
+            return (of_path_len, of_path, Ok(()));
         }
 
-        // Grab the summary data, which we use to find the paths
-        let summary = self.spk_summary_at_epoch(source.ephemeris_id, epoch)?.0;
-
-        let mut center_id = summary.center_id;
-
-        of_path[of_path_len] = Some(summary.center_id);
-        of_path_len += 1;
-
-        if summary.center_id == common_center {
-            // Well that was quick!
-            return Ok((of_path_len, of_path));
-        }
+        let mut center_id = source.ephemeris_id;
 
-        for _ in 0..MAX_TREE_DEPTH {
-            let summary = self.spk_summary_at_epoch(center_id, epoch)?.0;
-            center_id = summary.center_id;
+        while of_path_len < MAX_TREE_DEPTH {
+            // Grab the summary data, which we use to find the paths
+            center_id = match self.spk_summary_at_epoch(center_id, epoch) {
+                Ok((summary, _, _)) => summary.center_id,
+                Err(e) => return (of_path_len, of_path, Err(e)),
+            };
             of_path[of_path_len] = Some(center_id);
             of_path_len += 1;
             if center_id == common_center {
                 // We're found the path!
-                return Ok((of_path_len, of_path));
+                return (of_path_len, of_path, Ok(()));
             }
         }
 
-        Err(EphemerisError::SPK {
-            action: "computing path to common node",
-            source: DAFError::MaxRecursionDepth,
-        })
+        (
+            of_path_len,
+            of_path,
+            Err(EphemerisError::SPK {
+                action: "computing path to common node",
+                source: DAFError::MaxRecursionDepth,
+            }),
+        )
     }
 
     /// Returns the ephemeris path between two frames and the common node. This may return a `DisjointRoots` error if the frames do not share a common root, which is considered a file integrity error.
@@ -82,8 +82,12 @@ impl Almanac {
                     .context(SPKSnafu {
                         action: "fetching data for interpolation",
                     })?;
-                data.evaluate(epoch, summary)
-                    .context(EphemInterpolationSnafu)?
+                let hint = spk_data.record_cache.get(idx_in_spk);
+                let (state, next_hint) = data
+                    .evaluate_with_hint(epoch, summary, hint)
+                    .context(EphemInterpolationSnafu)?;
+                spk_data.record_cache.set(idx_in_spk, next_hint);
+                state
             }
             DafDataType::Type13HermiteUnequalStep => {
                 let data = spk_data
@@ -91,8 +95,12 @@ impl Almanac {
                     .context(SPKSnafu {
                         action: "fetching data for interpolation",
                     })?;
-                data.evaluate(epoch, summary)
-                    .context(EphemInterpolationSnafu)?
+                let hint = spk_data.record_cache.get(idx_in_spk);
+                let (state, next_hint) = data
+                    .evaluate_with_window(epoch, hint, self.hermite_window)
+                    .context(EphemInterpolationSnafu)?;
+                spk_data.record_cache.set(idx_in_spk, next_hint);
+                state
             }
             dtype => {
                 return Err(EphemerisError::SPK {
@@ -14,10 +14,10 @@ use super::EphemerisError;
 use super::EphemerisPhysicsSnafu;
 use crate::almanac::Almanac;
 use crate::astro::aberration::stellar_aberration;
-use crate::astro::Aberration;
+use crate::astro::{Aberration, AberrationDiagnostics};
 use crate::constants::frames::SSB_J2000;
 use crate::constants::SPEED_OF_LIGHT_KM_S;
-use crate::hifitime::Epoch;
+use crate::hifitime::{Epoch, TimeUnits};
 use crate::math::cartesian::CartesianState;
 use crate::math::units::*;
 use crate::math::Vector3;
@@ -55,13 +55,40 @@ impl Almanac {
     pub fn translate(
         &self,
         target_frame: Frame,
-        mut observer_frame: Frame,
+        observer_frame: Frame,
         epoch: Epoch,
         ab_corr: Option<Aberration>,
     ) -> Result<CartesianState, EphemerisError> {
+        self.translate_with_diagnostics(target_frame, observer_frame, epoch, ab_corr)
+            .map(|(state, _)| state)
+    }
+
+    /// Returns the Cartesian state of the target frame as seen from the observer frame at the provided epoch, and optionally given the aberration correction,
+    /// along with the diagnostics of that aberration correction (light time, number of iterations, remaining light time change, and stellar correction angle).
+    ///
+    /// The returned state is exactly that of `translate`.
+    ///
+    /// :type target_frame: Orbit
+    /// :type observer_frame: Frame
+    /// :type epoch: Epoch
+    /// :type ab_corr: Aberration, optional
+    /// :rtype: typing.Tuple
+    pub fn translate_with_diagnostics(
+        &self,
+        target_frame: Frame,
+        mut observer_frame: Frame,
+        epoch: Epoch,
+        ab_corr: Option<Aberration>,
+    ) -> Result<(CartesianState, AberrationDiagnostics), EphemerisError> {
         if observer_frame == target_frame {
             // Both frames match, return this frame's hash (i.e. no need to go higher up).
-            return Ok(CartesianState::zero(observer_frame));
+            return Ok((
+                CartesianState::zero(observer_frame),
+                AberrationDiagnostics {
+                    ab_corr,
+                    ..Default::default()
+                },
+            ));
         }
 
         // If there is no frame info, the user hasn't loaded this frame, but might still want to compute a translation.
@@ -111,12 +138,20 @@ impl Almanac {
                     }
                 }
 
-                Ok(CartesianState {
-                    radius_km: pos_bwrd - pos_fwrd,
-                    velocity_km_s: vel_bwrd - vel_fwrd,
-                    epoch,
-                    frame: observer_frame.with_orient(target_frame.orientation_id),
-                })
+                let radius_km = pos_bwrd - pos_fwrd;
+
+                Ok((
+                    CartesianState {
+                        radius_km,
+                        velocity_km_s: vel_bwrd - vel_fwrd,
+                        epoch,
+                        frame: observer_frame.with_orient(target_frame.orientation_id),
+                    },
+                    AberrationDiagnostics {
+                        light_time: (radius_km.norm() / SPEED_OF_LIGHT_KM_S).seconds(),
+                        ..Default::default()
+                    },
+                ))
             }
             Some(ab_corr) => {
                 // This is a rewrite of NAIF SPICE's `spkapo`
@@ -145,6 +180,8 @@ impl Almanac {
                 let num_it = if ab_corr.converged { 3 } else { 1 };
                 let lt_sign = if ab_corr.transmit_mode { 1.0 } else { -1.0 };
 
+                let mut residual_lt_s = 0.0;
+
                 for _ in 0..num_it {
                     let epoch_lt = epoch + lt_sign * one_way_lt_s * TimeUnit::Second;
                     let tgt_ssb = self.translate(target_frame, SSB_J2000, epoch_lt, None)?;
@@ -153,24 +190,41 @@ impl Almanac {
 
                     rel_pos_km = tgt_ssb_pos_km - obs_ssb_pos_km;
                     rel_vel_km_s = tgt_ssb_vel_km_s - obs_ssb_vel_km_s;
+
+                    let prev_lt_s = one_way_lt_s;
                     one_way_lt_s = rel_pos_km.norm() / SPEED_OF_LIGHT_KM_S;
+                    residual_lt_s = (one_way_lt_s - prev_lt_s).abs();
                 }
 
+                let mut stellar_correction_deg = 0.0;
+
                 // If stellar aberration correction is requested, perform it now.
                 if ab_corr.stellar {
                     // Modifications based on transmission versus reception case is done in the function directly.
-                    rel_pos_km = stellar_aberration(rel_pos_km, obs_ssb_vel_km_s, ab_corr)
+                    let app_pos_km = stellar_aberration(rel_pos_km, obs_ssb_vel_km_s, ab_corr)
                         .context(EphemerisPhysicsSnafu {
                             action: "computing stellar aberration",
                         })?;
+
+                    stellar_correction_deg = rel_pos_km.angle(&app_pos_km).to_degrees();
+                    rel_pos_km = app_pos_km;
                 }
 
-                Ok(CartesianState {
-                    radius_km: rel_pos_km,
-                    velocity_km_s: rel_vel_km_s,
-                    epoch,
-                    frame: observer_frame.with_orient(target_frame.orientation_id),
-                })
+                Ok((
+                    CartesianState {
+                        radius_km: rel_pos_km,
+                        velocity_km_s: rel_vel_km_s,
+                        epoch,
+                        frame: observer_frame.with_orient(target_frame.orientation_id),
+                    },
+                    AberrationDiagnostics {
+                        ab_corr: Some(ab_corr),
+                        light_time: one_way_lt_s.seconds(),
+                        iterations: num_it,
+                        light_time_residual: residual_lt_s.seconds(),
+                        stellar_correction_deg,
+                    },
+                ))
             }
         }
     }
@@ -20,7 +20,9 @@ use crate::astro::PhysicsResult;
 use crate::constants::celestial_objects::{
     celestial_name_from_id, id_to_celestial_name, SOLAR_SYSTEM_BARYCENTER,
 };
-use crate::constants::orientations::{id_to_orientation_name, orientation_name_from_id, J2000};
+use crate::constants::orientations::{
+    id_to_orientation_name, orientation_name_from_id, J2000, SYNODIC_OFFSET,
+};
 use crate::errors::{AlmanacError, EphemerisSnafu, OrientationSnafu, PhysicsError};
 use crate::prelude::FrameUid;
 use crate::structure::planetocentric::ellipsoid::Ellipsoid;
@@ -91,6 +93,33 @@ impl Frame {
         self.shape = Some(shape);
         self
     }
+
+    /// Returns the synodic (rotating) frame of the primary and secondary bodies, whose X axis points from the primary to the secondary
+    /// and whose Z axis is along the angular momentum of the secondary with respect to the primary, e.g. the Earth-Moon rotating frame
+    /// of the circular restricted three body problem.
+    ///
+    /// The frame is centered on the barycenter of the system if both bodies belong to the same planetary system (e.g. the Earth-Moon barycenter
+    /// for the Earth and the Moon), and on the primary otherwise; use `with_ephem` to change its center. Its orientation is computed by the Almanac
+    /// from the instantaneous ephemeris of both bodies, including its angular velocity.
+    ///
+    /// Returns None if either ID is not between 0 and 999, i.e. not a barycenter, the Sun, a planet, or a natural satellite.
+    pub const fn synodic(primary_id: NaifId, secondary_id: NaifId) -> Option<Self> {
+        if primary_id < 0 || primary_id > 999 || secondary_id < 0 || secondary_id > 999 {
+            return None;
+        }
+
+        let ephemeris_id =
+            if primary_id >= 100 && secondary_id >= 100 && primary_id / 100 == secondary_id / 100 {
+                primary_id / 100
+            } else {
+                primary_id
+            };
+
+        Some(Self::new(
+            ephemeris_id,
+            SYNODIC_OFFSET + 1000 * primary_id + secondary_id,
+        ))
+    }
 }
 
 #[cfg(feature = "python")]
@@ -113,6 +142,18 @@ impl Frame {
         }
     }
 
+    /// Returns the synodic (rotating) frame of the primary and secondary bodies, whose X axis points from the primary to the secondary
+    /// and whose Z axis is along the angular momentum of the secondary with respect to the primary, or None if either ID is not between 0 and 999.
+    ///
+    /// :type primary_id: int
+    /// :type secondary_id: int
+    /// :rtype: Frame
+    #[staticmethod]
+    #[pyo3(name = "synodic")]
+    fn py_synodic(primary_id: NaifId, secondary_id: NaifId) -> Option<Self> {
+        Self::synodic(primary_id, secondary_id)
+    }
+
     fn __str__(&self) -> String {
         format!("{self}")
     }
@@ -225,6 +266,18 @@ impl Frame {
         self.mu_km3_s2.is_some() && self.shape.is_some()
     }
 
+    /// Returns the IDs of the primary and secondary bodies if this is a synodic frame (cf. `Frame.synodic`)
+    ///
+    /// :rtype: typing.Tuple
+    pub const fn synodic_bodies(&self) -> Option<(NaifId, NaifId)> {
+        let offset_id = self.orientation_id as i64 - SYNODIC_OFFSET as i64;
+        if offset_id >= 0 && offset_id < 1_000_000 {
+            Some(((offset_id / 1000) as NaifId, (offset_id % 1000) as NaifId))
+        } else {
+            None
+        }
+    }
+
     /// Returns true if the ephemeris origin is equal to the provided ID
     ///
     /// :type other_id: int
@@ -350,7 +403,10 @@ impl fmt::Display for Frame {
 
         let orientation_name = match orientation_name_from_id(self.orientation_id) {
             Some(name) => name.to_string(),
-            None => format!("orientation {}", self.orientation_id),
+            None => match self.synodic_bodies() {
+                Some((primary_id, secondary_id)) => format!("{primary_id}-{secondary_id} synodic"),
+                None => format!("orientation {}", self.orientation_id),
+            },
         };
 
         write!(f, "{body_name} {orientation_name}")?;
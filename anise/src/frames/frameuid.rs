@@ -10,6 +10,9 @@
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "metaload")]
+use serde_dhall::StaticType;
+
 use crate::{
     constants::{
         celestial_objects::celestial_name_from_id, orientations::orientation_name_from_id,
@@ -22,7 +25,8 @@ pub use super::Frame;
 
 /// A unique frame reference that only contains enough information to build the actual Frame object.
 /// It cannot be used for any computations, is it be used in any structure apart from error structures.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "metaload", derive(StaticType))]
 pub struct FrameUid {
     pub ephemeris_id: NaifId,
     pub orientation_id: NaifId,
@@ -0,0 +1,220 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use snafu::ResultExt;
+
+use super::Instrument;
+use crate::{
+    almanac::Almanac,
+    ephemerides::EphemerisPhysicsSnafu,
+    errors::{AlmanacError, AlmanacResult, EphemerisSnafu, OrientationSnafu, PhysicsError},
+    frames::Frame,
+    math::rotation::DCM,
+    prelude::Orbit,
+};
+
+#[cfg(feature = "python")]
+use ndarray::Array2;
+#[cfg(feature = "python")]
+use numpy::PyArray2;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyTypeError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+impl Almanac {
+    /// Computes the footprint of the instrument on the target body, given the spacecraft state and attitude (the rotation from the frame
+    /// of the spacecraft state to the body frame), by intersecting rays regularly spaced on the edge of the field of view with the shape of the target.
+    ///
+    /// Returns the geodetic latitude and longitude (in degrees) of each intersection, or None if that ray does not intersect the target.
+    /// The target frame must be body fixed, and its shape is fetched from the loaded planetary data if it is not set.
+    pub fn compute_footprint(
+        &self,
+        instrument: &Instrument,
+        sc_state: Orbit,
+        sc_attitude: DCM,
+        mut target_frame: Frame,
+        num_points: usize,
+    ) -> AlmanacResult<Vec<Option<(f64, f64)>>> {
+        if target_frame.shape.is_none() {
+            target_frame =
+                self.frame_from_uid(target_frame)
+                    .map_err(|e| AlmanacError::GenericError {
+                        err: format!("{e} when fetching {target_frame:e} frame data"),
+                    })?;
+        }
+
+        let shape = target_frame
+            .shape
+            .ok_or(PhysicsError::MissingFrameData {
+                action: "computing footprint",
+                data: "shape",
+                frame: target_frame.into(),
+            })
+            .context(EphemerisPhysicsSnafu {
+                action: "fetching shape of target",
+            })
+            .context(EphemerisSnafu {
+                action: "computing footprint",
+            })?;
+
+        let to_instrument = instrument
+            .dcm_to_instrument(&sc_state, &sc_attitude)
+            .context(EphemerisPhysicsSnafu {
+                action: "rotating into instrument frame",
+            })
+            .context(EphemerisSnafu {
+                action: "computing footprint",
+            })?;
+
+        let origin_km = self.transform_to(sc_state, target_frame, None)?.radius_km;
+        let to_target = self
+            .rotate(sc_state.frame, target_frame, sc_state.epoch)
+            .context(OrientationSnafu {
+                action: "computing footprint",
+            })?;
+        let target_from_instrument = to_target.rot_mat * to_instrument.rot_mat.transpose();
+
+        instrument
+            .fov
+            .edge_directions(num_points)
+            .iter()
+            .map(|direction| {
+                match shape.ray_intersection(&origin_km, &(target_from_instrument * direction)) {
+                    Some(point_km) => {
+                        let (lat_deg, long_deg, _) = Orbit::from_position(
+                            point_km.x,
+                            point_km.y,
+                            point_km.z,
+                            sc_state.epoch,
+                            target_frame,
+                        )
+                        .latlongalt()
+                        .context(EphemerisPhysicsSnafu {
+                            action: "computing latitude and longitude of intersection",
+                        })
+                        .context(EphemerisSnafu {
+                            action: "computing footprint",
+                        })?;
+                        Ok(Some((lat_deg, long_deg)))
+                    }
+                    None => Ok(None),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Almanac {
+    /// Computes the footprint of the instrument on the target body, given the spacecraft state and attitude (the rotation from the frame
+    /// of the spacecraft state to the body frame), by intersecting rays regularly spaced on the edge of the field of view with the shape of the target.
+    ///
+    /// Returns an array of shape (N, 2) of the geodetic latitude and longitude (in degrees) of each intersection, which are NaN if that ray does not intersect the target.
+    /// The target frame must be body fixed, and its shape is fetched from the loaded planetary data if it is not set.
+    ///
+    /// :type instrument: Instrument
+    /// :type sc_state: Orbit
+    /// :type sc_attitude: DCM
+    /// :type target_frame: Frame
+    /// :type num_points: int
+    /// :rtype: numpy.array
+    #[pyo3(name = "compute_footprint")]
+    fn py_compute_footprint<'py>(
+        &self,
+        py: Python<'py>,
+        instrument: Instrument,
+        sc_state: Orbit,
+        sc_attitude: DCM,
+        target_frame: Frame,
+        num_points: usize,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        let footprint =
+            self.compute_footprint(&instrument, sc_state, sc_attitude, target_frame, num_points)?;
+
+        let data = footprint
+            .iter()
+            .flat_map(|point| {
+                let (lat_deg, long_deg) = point.unwrap_or((f64::NAN, f64::NAN));
+                [lat_deg, long_deg]
+            })
+            .collect::<Vec<_>>();
+
+        let footprint = Array2::from_shape_vec((footprint.len(), 2), data)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(e.to_string()))?;
+        Ok(PyArray2::<f64>::from_owned_array(py, footprint))
+    }
+}
+
+#[cfg(test)]
+mod ut_footprint {
+    use crate::constants::frames::{EARTH_J2000, IAU_EARTH_FRAME};
+    use crate::constants::orientations::J2000;
+    use crate::instrument::{FovShape, Instrument};
+    use crate::math::rotation::DCM;
+    use crate::prelude::{Almanac, Orbit};
+    use core::f64::consts::FRAC_PI_2;
+    use hifitime::Epoch;
+
+    #[test]
+    fn nadir_footprint() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/pck08.pca")
+            .unwrap();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+
+        // Above the equator, looking at the center of the Earth with a boresight along the body -X axis
+        let sc = Orbit::from_position(7000.0, 0.0, 0.0, epoch, EARTH_J2000);
+        let attitude = DCM::identity(J2000, -100);
+        let instrument = Instrument {
+            mounting: DCM::r2(-FRAC_PI_2, -100, -100_001),
+            fov: FovShape::Circular {
+                half_angle_deg: 5.0,
+            },
+        };
+
+        let footprint = almanac
+            .compute_footprint(&instrument, sc, attitude, IAU_EARTH_FRAME, 36)
+            .unwrap();
+        assert_eq!(footprint.len(), 36);
+        let sub_sc = almanac
+            .transform_to(sc, IAU_EARTH_FRAME, None)
+            .unwrap()
+            .latlongalt()
+            .unwrap();
+
+        for point in &footprint {
+            let (lat_deg, long_deg) = point.unwrap();
+            // Within a few degrees of the sub-spacecraft point
+            let dlat = lat_deg - sub_sc.0;
+            let dlong = (long_deg - sub_sc.1 + 540.0) % 360.0 - 180.0;
+            let dist = (dlat.powi(2) + dlong.powi(2)).sqrt();
+            assert!(dist > 0.1 && dist < 10.0, "{point:?} vs {sub_sc:?}");
+        }
+
+        // A wide field of view looking away from the Earth does not see it
+        let instrument = Instrument {
+            mounting: DCM::r2(FRAC_PI_2, -100, -100_001),
+            ..instrument
+        };
+        assert!(almanac
+            .compute_footprint(&instrument, sc, attitude, IAU_EARTH_FRAME, 8)
+            .unwrap()
+            .iter()
+            .all(|point| point.is_none()));
+
+        // An attitude of the wrong frame is rejected
+        assert!(almanac
+            .compute_footprint(&instrument, sc, DCM::identity(-5, -100), IAU_EARTH_FRAME, 8)
+            .is_err());
+    }
+}
@@ -0,0 +1,343 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::f64::consts::TAU;
+use core::fmt;
+
+use snafu::ensure;
+
+use crate::astro::PhysicsResult;
+use crate::errors::{EpochMismatchSnafu, FrameMismatchSnafu, InvalidRotationSnafu};
+use crate::math::rotation::DCM;
+use crate::math::Vector3;
+use crate::prelude::Orbit;
+
+#[cfg(feature = "python")]
+use ndarray::{Array1, Array2};
+#[cfg(feature = "python")]
+use numpy::{PyArray1, PyArray2};
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyTypeError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+pub mod footprint;
+
+/// Shape of the field of view of an instrument, defined by its half angles about the boresight, which is the +Z axis of the instrument frame.
+///
+/// :type half_angle_deg: float
+/// :type x_half_angle_deg: float
+/// :type y_half_angle_deg: float
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise.instrument"))]
+pub enum FovShape {
+    /// Cone of the provided half angle about the boresight
+    Circular { half_angle_deg: f64 },
+    /// Rectangle of the provided half angles about the X and Y axes of the instrument frame
+    Rectangular {
+        x_half_angle_deg: f64,
+        y_half_angle_deg: f64,
+    },
+}
+
+impl FovShape {
+    /// Returns whether the provided direction, in the instrument frame, is within this field of view (edges included).
+    pub fn contains(&self, direction: &Vector3) -> bool {
+        match *self {
+            Self::Circular { half_angle_deg } => {
+                let norm = direction.norm();
+                norm > 0.0 && direction.z / norm >= half_angle_deg.to_radians().cos()
+            }
+            Self::Rectangular {
+                x_half_angle_deg,
+                y_half_angle_deg,
+            } => {
+                direction.z > 0.0
+                    && (direction.x / direction.z).abs() <= x_half_angle_deg.to_radians().tan()
+                    && (direction.y / direction.z).abs() <= y_half_angle_deg.to_radians().tan()
+            }
+        }
+    }
+
+    /// Returns unit vectors regularly spaced on the edge of this field of view, in the instrument frame.
+    /// Rectangular fields of view always include their four corners.
+    pub fn edge_directions(&self, num_points: usize) -> Vec<Vector3> {
+        match *self {
+            Self::Circular { half_angle_deg } => {
+                let (sin_half, cos_half) = half_angle_deg.to_radians().sin_cos();
+                (0..num_points)
+                    .map(|i| {
+                        let (sin_phi, cos_phi) = (TAU * i as f64 / num_points as f64).sin_cos();
+                        Vector3::new(sin_half * cos_phi, sin_half * sin_phi, cos_half)
+                    })
+                    .collect()
+            }
+            Self::Rectangular {
+                x_half_angle_deg,
+                y_half_angle_deg,
+            } => {
+                let tan_x = x_half_angle_deg.to_radians().tan();
+                let tan_y = y_half_angle_deg.to_radians().tan();
+                // Walk the edge counterclockwise from one corner to the next
+                let corners = [(1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0), (1.0, -1.0)];
+                let per_side = num_points.div_ceil(4).max(1);
+                let mut directions = Vec::with_capacity(4 * per_side);
+                for (side, (x0, y0)) in corners.iter().enumerate() {
+                    let (x1, y1) = corners[(side + 1) % 4];
+                    for i in 0..per_side {
+                        let t = i as f64 / per_side as f64;
+                        let x = x0 + (x1 - x0) * t;
+                        let y = y0 + (y1 - y0) * t;
+                        directions.push(Vector3::new(x * tan_x, y * tan_y, 1.0).normalize());
+                    }
+                }
+                directions
+            }
+        }
+    }
+}
+
+impl fmt::Display for FovShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Circular { half_angle_deg } => write!(f, "circular ({half_angle_deg} deg)"),
+            Self::Rectangular {
+                x_half_angle_deg,
+                y_half_angle_deg,
+            } => write!(
+                f,
+                "rectangular ({x_half_angle_deg} deg x {y_half_angle_deg} deg)"
+            ),
+        }
+    }
+}
+
+/// An instrument mounted on a spacecraft, defined by the rotation from the spacecraft body frame to the instrument frame,
+/// whose +Z axis is the boresight, and the shape of its field of view.
+///
+/// :type mounting: DCM
+/// :type fov: FovShape
+/// :rtype: Instrument
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "python", pyo3(module = "anise.instrument"))]
+pub struct Instrument {
+    /// Rotation from the spacecraft body frame to the instrument frame
+    pub mounting: DCM,
+    pub fov: FovShape,
+}
+
+impl Instrument {
+    /// Returns the boresight of this instrument in the spacecraft body frame.
+    pub fn boresight_body(&self) -> Vector3 {
+        self.mounting.rot_mat.transpose() * Vector3::z()
+    }
+
+    /// Returns whether the provided direction, in the spacecraft body frame, is within the field of view of this instrument.
+    pub fn contains_body_direction(&self, direction_body: &Vector3) -> bool {
+        self.fov.contains(&(self.mounting.rot_mat * direction_body))
+    }
+
+    /// Returns unit vectors regularly spaced on the edge of the field of view of this instrument, in the spacecraft body frame.
+    pub fn edge_directions_body(&self, num_points: usize) -> Vec<Vector3> {
+        let body_from_instrument = self.mounting.rot_mat.transpose();
+        self.fov
+            .edge_directions(num_points)
+            .iter()
+            .map(|direction| body_from_instrument * direction)
+            .collect()
+    }
+
+    /// Returns the rotation from the frame of the spacecraft state to the instrument frame, given the spacecraft attitude,
+    /// i.e. the rotation from the frame of the spacecraft state to the body frame.
+    pub fn dcm_to_instrument(&self, sc_state: &Orbit, sc_attitude: &DCM) -> PhysicsResult<DCM> {
+        ensure!(
+            sc_attitude.from == sc_state.frame.orientation_id,
+            InvalidRotationSnafu {
+                action: "using spacecraft attitude",
+                from1: sc_state.frame.orientation_id,
+                to1: sc_state.frame.orientation_id,
+                from2: sc_attitude.from,
+                to2: sc_attitude.to
+            }
+        );
+        self.mounting * *sc_attitude
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Instrument {
+    #[new]
+    pub fn py_new(mounting: DCM, fov: FovShape) -> Self {
+        Self { mounting, fov }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?} (@{self:p})")
+    }
+
+    /// :rtype: DCM
+    #[getter]
+    fn get_mounting(&self) -> PyResult<DCM> {
+        Ok(self.mounting)
+    }
+    /// :type mounting: DCM
+    #[setter]
+    fn set_mounting(&mut self, mounting: DCM) -> PyResult<()> {
+        self.mounting = mounting;
+        Ok(())
+    }
+    /// :rtype: FovShape
+    #[getter]
+    fn get_fov(&self) -> PyResult<FovShape> {
+        Ok(self.fov)
+    }
+    /// :type fov: FovShape
+    #[setter]
+    fn set_fov(&mut self, fov: FovShape) -> PyResult<()> {
+        self.fov = fov;
+        Ok(())
+    }
+
+    /// Returns the boresight of this instrument in the spacecraft body frame, as a vector of size 3.
+    ///
+    /// :rtype: numpy.array
+    fn boresight<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        let boresight = Array1::from_iter(self.boresight_body().iter().copied());
+        PyArray1::<f64>::from_owned_array(py, boresight)
+    }
+
+    /// Returns unit vectors regularly spaced on the edge of the field of view of this instrument, in the spacecraft body frame,
+    /// as an array of shape (N, 3).
+    ///
+    /// :type num_points: int
+    /// :rtype: numpy.array
+    fn edge_directions<'py>(
+        &self,
+        py: Python<'py>,
+        num_points: usize,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        let directions = self.edge_directions_body(num_points);
+        let data = directions
+            .iter()
+            .flat_map(|direction| direction.iter().copied())
+            .collect::<Vec<_>>();
+        let directions = Array2::from_shape_vec((directions.len(), 3), data)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(e.to_string()))?;
+        Ok(PyArray2::<f64>::from_owned_array(py, directions))
+    }
+}
+
+#[cfg_attr(feature = "python", pymethods)]
+impl Instrument {
+    /// Returns whether the target is within the field of view of this instrument, given the spacecraft state and attitude
+    /// (the rotation from the frame of the spacecraft state to the body frame). Both states must be in the same frame and at the same epoch.
+    ///
+    /// :type sc_state: Orbit
+    /// :type sc_attitude: DCM
+    /// :type target_state: Orbit
+    /// :rtype: bool
+    pub fn is_in_fov(
+        &self,
+        sc_state: Orbit,
+        sc_attitude: DCM,
+        target_state: Orbit,
+    ) -> PhysicsResult<bool> {
+        ensure!(
+            sc_state.epoch == target_state.epoch,
+            EpochMismatchSnafu {
+                action: "checking field of view",
+                epoch1: sc_state.epoch,
+                epoch2: target_state.epoch
+            }
+        );
+        ensure!(
+            sc_state.frame == target_state.frame,
+            FrameMismatchSnafu {
+                action: "checking field of view",
+                frame1: sc_state.frame,
+                frame2: target_state.frame
+            }
+        );
+
+        let dcm = self.dcm_to_instrument(&sc_state, &sc_attitude)?;
+        Ok(self
+            .fov
+            .contains(&(dcm.rot_mat * (target_state.radius_km - sc_state.radius_km))))
+    }
+}
+
+#[cfg(test)]
+mod ut_instrument {
+    use super::*;
+    use crate::constants::frames::EARTH_J2000;
+    use crate::constants::orientations::J2000;
+    use hifitime::Epoch;
+
+    #[test]
+    fn fov_shapes() {
+        let circular = FovShape::Circular {
+            half_angle_deg: 10.0,
+        };
+        assert!(circular.contains(&Vector3::z()));
+        assert!(circular.contains(&Vector3::new(0.17, 0.0, 1.0)));
+        assert!(!circular.contains(&Vector3::new(0.18, 0.0, 1.0)));
+        assert!(!circular.contains(&-Vector3::z()));
+        for direction in circular.edge_directions(12) {
+            assert!((direction.norm() - 1.0).abs() < 1e-12);
+            assert!((direction.z.acos().to_degrees() - 10.0).abs() < 1e-9);
+        }
+
+        let rectangular = FovShape::Rectangular {
+            x_half_angle_deg: 20.0,
+            y_half_angle_deg: 5.0,
+        };
+        assert!(rectangular.contains(&Vector3::new(0.35, 0.0, 1.0)));
+        assert!(!rectangular.contains(&Vector3::new(0.0, 0.1, 1.0)));
+        let edges = rectangular.edge_directions(8);
+        assert_eq!(edges.len(), 8);
+        // Starts at a corner
+        assert!((edges[0].x / edges[0].z - 20.0_f64.to_radians().tan()).abs() < 1e-12);
+        assert!((edges[0].y / edges[0].z - 5.0_f64.to_radians().tan()).abs() < 1e-12);
+        assert!(edges
+            .iter()
+            .all(|direction| rectangular.contains(&(direction * 0.999 + Vector3::z() * 1e-3))));
+    }
+
+    #[test]
+    fn instrument_pointing() {
+        // Instrument boresight along the body -X axis
+        let instrument = Instrument {
+            mounting: DCM::r2(-core::f64::consts::FRAC_PI_2, -100, -100_001),
+            fov: FovShape::Circular {
+                half_angle_deg: 5.0,
+            },
+        };
+        assert!((instrument.boresight_body() + Vector3::x()).norm() < 1e-12);
+        assert!(instrument.contains_body_direction(&-Vector3::x()));
+        assert!(!instrument.contains_body_direction(&Vector3::x()));
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let sc = Orbit::from_position(7000.0, 0.0, 0.0, epoch, EARTH_J2000);
+        let nadir = Orbit::from_position(0.0, 0.0, 0.0, epoch, EARTH_J2000);
+        // Body aligned with the inertial frame: the instrument points to the Earth
+        let attitude = DCM::identity(J2000, -100);
+        assert!(instrument.is_in_fov(sc, attitude, nadir).unwrap());
+        // Flipped about Z: the instrument points away from the Earth
+        let attitude = DCM::r3(core::f64::consts::PI, J2000, -100);
+        assert!(!instrument.is_in_fov(sc, attitude, nadir).unwrap());
+        // Wrong attitude frame
+        assert!(instrument
+            .is_in_fov(sc, DCM::identity(-5, -100), nadir)
+            .is_err());
+    }
+}
@@ -15,14 +15,17 @@ extern crate hifitime;
 extern crate log;
 
 pub mod almanac;
+pub mod analysis;
 pub mod astro;
 pub mod constants;
 pub mod ephemerides;
 pub mod errors;
 pub mod frames;
+pub mod instrument;
 pub mod math;
 pub mod naif;
 pub mod orientations;
+pub mod progress;
 pub mod structure;
 
 /// Re-export of hifitime
@@ -67,6 +70,7 @@ pub(crate) const DBL_SIZE: usize = 8;
 pub(crate) type NaifId = i32;
 
 /// Memory maps a file and **copies** the data on the heap prior to returning a pointer to this heap data.
+#[cfg(not(target_arch = "wasm32"))]
 #[macro_export]
 macro_rules! file2heap {
     ($filename:tt) => {
@@ -87,6 +91,51 @@ macro_rules! file2heap {
     };
 }
 
+/// Memory maps a file and returns its bytes **without** copying them on the heap: the memory map is kept alive as long as the bytes are
+/// referenced, and only the pages of the file which are accessed are read from disk.
+///
+/// The file must not be modified nor truncated while the bytes are referenced, so this is only used by `unsafe` functions
+/// which forward that requirement to their callers, e.g. `DAF::load_lazy`.
+#[cfg(not(target_arch = "wasm32"))]
+#[macro_export]
+macro_rules! file2mmap {
+    ($filename:tt) => {
+        match std::fs::File::open($filename) {
+            Err(e) => Err($crate::errors::InputOutputError::IOError { kind: e.kind() }),
+            Ok(file) => unsafe {
+                use bytes::Bytes;
+                use memmap2::MmapOptions;
+                match MmapOptions::new().map(&file) {
+                    Err(_) => Err($crate::errors::InputOutputError::IOUnknownError),
+                    Ok(mmap) => Ok(Bytes::from_owner(mmap)),
+                }
+            },
+        }
+    };
+}
+
+/// Reads a file onto the heap: memory maps are not available on WASM targets, where the file system is provided by the runtime, if any.
+/// In the browser, load the bytes with `Almanac::load_from_bytes` instead.
+#[cfg(target_arch = "wasm32")]
+#[macro_export]
+macro_rules! file2heap {
+    ($filename:tt) => {
+        match std::fs::read($filename) {
+            Err(e) => Err($crate::errors::InputOutputError::IOError { kind: e.kind() }),
+            Ok(data) => Ok(bytes::Bytes::from(data)),
+        }
+    };
+}
+
+/// Reads a file onto the heap: without memory maps on WASM targets, lazy loading still reads the whole file.
+#[cfg(target_arch = "wasm32")]
+#[macro_export]
+macro_rules! file2mmap {
+    ($filename:tt) => {
+        $crate::file2heap!($filename)
+    };
+}
+
 /// Memory maps a file and **copies** the data on the heap prior to returning a pointer to this heap data.
 #[macro_export]
 macro_rules! file_mmap {
@@ -0,0 +1,363 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+
+use super::angles::between_pm_180;
+use super::{Matrix6, Vector3, Vector6};
+use crate::astro::orbit::Orbit;
+use crate::astro::PhysicsResult;
+use crate::errors::{MathError, PhysicsError};
+use hifitime::Epoch;
+
+/// Relative step used in the central finite differencing of the element set Jacobians.
+const JACOBIAN_FD_REL_STEP: f64 = 1e-7;
+
+/// Set of elements in which a state (and therefore its covariance) is expressed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ElementSet {
+    /// Position and velocity: [km, km, km, km/s, km/s, km/s]
+    #[default]
+    Cartesian,
+    /// Keplerian elements: [SMA (km), ECC, INC (deg), RAAN (deg), AOP (deg), TA (deg)]
+    Keplerian,
+    /// Classical (direct) equinoctial elements: [SMA (km), h, k, p, q, mean longitude (deg)]
+    /// where h = e sin(ω+Ω), k = e cos(ω+Ω), p = tan(i/2) sin(Ω), q = tan(i/2) cos(Ω).
+    /// These are non-singular for circular and equatorial orbits, but only defined for elliptical orbits.
+    Equinoctial,
+}
+
+impl ElementSet {
+    /// Returns whether the element at the provided index is an angle in degrees.
+    const fn is_angle(&self, idx: usize) -> bool {
+        match self {
+            Self::Cartesian => false,
+            Self::Keplerian => idx >= 2,
+            Self::Equinoctial => idx == 5,
+        }
+    }
+
+    /// Returns the elements of the provided orbit in this element set.
+    pub fn elements(&self, orbit: &Orbit) -> PhysicsResult<Vector6> {
+        match self {
+            Self::Cartesian => Ok(orbit.to_cartesian_pos_vel()),
+            Self::Keplerian => orbit.to_keplerian_vec(),
+            Self::Equinoctial => equinoctial_vec(orbit),
+        }
+    }
+}
+
+impl fmt::Display for ElementSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cartesian => write!(f, "Cartesian"),
+            Self::Keplerian => write!(f, "Keplerian"),
+            Self::Equinoctial => write!(f, "Equinoctial"),
+        }
+    }
+}
+
+/// Computes the classical equinoctial elements of an elliptical orbit, cf. [Orbit::to_equinoctial].
+fn equinoctial_vec(orbit: &Orbit) -> PhysicsResult<Vector6> {
+    let (sma_km, h, k, p, q, mean_long_deg) = orbit.to_equinoctial()?;
+    Ok(Vector6::new(sma_km, h, k, p, q, mean_long_deg))
+}
+
+/// Computes the Jacobian of the provided element set with respect to the Cartesian state of the orbit, i.e. d(elements)/d(Cartesian),
+/// using central finite differencing. Angle differences are wrapped to avoid discontinuities at 0/360 degrees.
+pub fn jacobian_cartesian_to(element_set: ElementSet, orbit: &Orbit) -> PhysicsResult<Matrix6> {
+    if element_set == ElementSet::Cartesian {
+        return Ok(Matrix6::identity());
+    }
+
+    let state = orbit.to_cartesian_pos_vel();
+    let mut jacobian = Matrix6::zeros();
+
+    for j in 0..6 {
+        let step = JACOBIAN_FD_REL_STEP * state[j].abs().max(1.0);
+
+        let mut plus = *orbit;
+        let mut minus = *orbit;
+        if j < 3 {
+            plus.radius_km[j] += step;
+            minus.radius_km[j] -= step;
+        } else {
+            plus.velocity_km_s[j - 3] += step;
+            minus.velocity_km_s[j - 3] -= step;
+        }
+
+        let elem_plus = element_set.elements(&plus)?;
+        let elem_minus = element_set.elements(&minus)?;
+
+        for i in 0..6 {
+            let delta = if element_set.is_angle(i) {
+                between_pm_180(elem_plus[i] - elem_minus[i])
+            } else {
+                elem_plus[i] - elem_minus[i]
+            };
+            jacobian[(i, j)] = delta / (2.0 * step);
+        }
+    }
+
+    Ok(jacobian)
+}
+
+/// Computes the Jacobian of the Cartesian state with respect to the provided element set, i.e. d(Cartesian)/d(elements),
+/// as the inverse of the Jacobian d(elements)/d(Cartesian).
+pub fn jacobian_to_cartesian(element_set: ElementSet, orbit: &Orbit) -> PhysicsResult<Matrix6> {
+    jacobian_cartesian_to(element_set, orbit)?
+        .try_inverse()
+        .ok_or(PhysicsError::AppliedMath {
+            source: MathError::DomainError {
+                value: 0.0,
+                msg: "element set Jacobian is singular, determinant is",
+            },
+        })
+}
+
+/// Computes the two-body state transition matrix of the provided orbit from its epoch to the new epoch,
+/// using central finite differencing of the universal variable propagation of `Orbit::at_epoch`.
+pub fn two_body_stm(orbit: &Orbit, new_epoch: Epoch) -> PhysicsResult<Matrix6> {
+    let state = orbit.to_cartesian_pos_vel();
+    let mut stm = Matrix6::zeros();
+
+    for j in 0..6 {
+        let step = JACOBIAN_FD_REL_STEP * state[j].abs().max(1.0);
+
+        let mut plus = *orbit;
+        let mut minus = *orbit;
+        if j < 3 {
+            plus.radius_km[j] += step;
+            minus.radius_km[j] -= step;
+        } else {
+            plus.velocity_km_s[j - 3] += step;
+            minus.velocity_km_s[j - 3] -= step;
+        }
+
+        let delta = plus.at_epoch(new_epoch)?.to_cartesian_pos_vel()
+            - minus.at_epoch(new_epoch)?.to_cartesian_pos_vel();
+
+        stm.set_column(j, &(delta / (2.0 * step)));
+    }
+
+    Ok(stm)
+}
+
+/// A 6x6 state covariance expressed in a given element set.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Covariance {
+    /// Covariance matrix, in the squared units of the element set
+    pub matrix: Matrix6,
+    /// Element set in which this covariance is expressed
+    pub element_set: ElementSet,
+}
+
+impl Covariance {
+    /// Initializes a new covariance in the provided element set.
+    pub const fn new(matrix: Matrix6, element_set: ElementSet) -> Self {
+        Self {
+            matrix,
+            element_set,
+        }
+    }
+
+    /// Converts this covariance into the requested element set, linearized about the provided orbit.
+    ///
+    /// # Algorithm
+    /// 1. Map the covariance into Cartesian space using the inverse of the Jacobian of the current element set
+    /// 2. Map the Cartesian covariance into the requested element set: P' = J P J^T
+    ///
+    /// The orbit must be the (reference) state at which this covariance is defined, and its frame must have a gravitational parameter.
+    pub fn convert(&self, to_element_set: ElementSet, orbit: &Orbit) -> PhysicsResult<Self> {
+        if to_element_set == self.element_set {
+            return Ok(*self);
+        }
+
+        let cartesian = if self.element_set == ElementSet::Cartesian {
+            self.matrix
+        } else {
+            let jac = jacobian_to_cartesian(self.element_set, orbit)?;
+            jac * self.matrix * jac.transpose()
+        };
+
+        let matrix = if to_element_set == ElementSet::Cartesian {
+            cartesian
+        } else {
+            let jac = jacobian_cartesian_to(to_element_set, orbit)?;
+            jac * cartesian * jac.transpose()
+        };
+
+        Ok(Self {
+            matrix,
+            element_set: to_element_set,
+        })
+    }
+
+    /// Propagates this covariance from the epoch of the provided orbit to the new epoch using the two-body state transition matrix,
+    /// optionally adding state noise compensation (SNC) process noise.
+    ///
+    /// The returned covariance is expressed in the same element set as this covariance.
+    ///
+    /// # State noise compensation
+    /// If provided, `snc_km_s2` is the 1-sigma of a white acceleration noise on each Cartesian axis, and the following process noise is added
+    /// to the Cartesian covariance (where dt is the propagation duration):
+    /// ```text
+    /// Q = [ dt^3/3 σ²  dt^2/2 σ² ]
+    ///     [ dt^2/2 σ²  dt σ²     ]
+    /// ```
+    ///
+    /// This is a dynamically consistent alternative to interpolating the covariance between two known epochs.
+    pub fn propagate_two_body(
+        &self,
+        orbit: &Orbit,
+        new_epoch: Epoch,
+        snc_km_s2: Option<Vector3>,
+    ) -> PhysicsResult<Self> {
+        let cartesian = self.convert(ElementSet::Cartesian, orbit)?;
+        let stm = two_body_stm(orbit, new_epoch)?;
+
+        let mut matrix = stm * cartesian.matrix * stm.transpose();
+
+        if let Some(sigmas) = snc_km_s2 {
+            let dt_s = (new_epoch - orbit.epoch).to_seconds().abs();
+            for i in 0..3 {
+                let var = sigmas[i].powi(2);
+                matrix[(i, i)] += dt_s.powi(3) / 3.0 * var;
+                matrix[(i, i + 3)] += dt_s.powi(2) / 2.0 * var;
+                matrix[(i + 3, i)] += dt_s.powi(2) / 2.0 * var;
+                matrix[(i + 3, i + 3)] += dt_s * var;
+            }
+        }
+
+        let propagated = Self {
+            matrix,
+            element_set: ElementSet::Cartesian,
+        };
+
+        propagated.convert(self.element_set, &orbit.at_epoch(new_epoch)?)
+    }
+
+    /// Returns the 1-sigma standard deviations (square root of the diagonal) of this covariance
+    pub fn sigmas(&self) -> Vector6 {
+        self.matrix.diagonal().map(|var| var.abs().sqrt())
+    }
+}
+
+impl fmt::Display for Covariance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} covariance {:.6e}", self.element_set, self.matrix)
+    }
+}
+
+#[cfg(test)]
+mod ut_covariance {
+    use super::*;
+    use crate::constants::frames::EARTH_J2000;
+    use crate::math::angles::between_0_360;
+    use crate::prelude::Epoch;
+
+    fn orbit() -> Orbit {
+        let eme2k = EARTH_J2000.with_mu_km3_s2(398_600.435_436);
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 10);
+        Orbit::keplerian(
+            8_191.93, 0.024, 12.85, 306.614, 314.19, 99.887_7, epoch, eme2k,
+        )
+    }
+
+    #[test]
+    fn covariance_round_trip() {
+        let orbit = orbit();
+        let cart = Covariance::new(
+            Matrix6::from_diagonal(&Vector6::new(1.0, 1.0, 1.0, 1e-6, 1e-6, 1e-6)),
+            ElementSet::Cartesian,
+        );
+
+        for element_set in [ElementSet::Keplerian, ElementSet::Equinoctial] {
+            let converted = cart.convert(element_set, &orbit).unwrap();
+            assert_eq!(converted.element_set, element_set);
+            // Symmetric
+            assert!((converted.matrix - converted.matrix.transpose()).norm() < 1e-9);
+
+            let back = converted.convert(ElementSet::Cartesian, &orbit).unwrap();
+            assert!(
+                (back.matrix - cart.matrix).norm() < 1e-5,
+                "{element_set} round trip failed: {back}"
+            );
+        }
+
+        // Keplerian to equinoctial directly
+        let kep = cart.convert(ElementSet::Keplerian, &orbit).unwrap();
+        let equi = kep.convert(ElementSet::Equinoctial, &orbit).unwrap();
+        let equi_direct = cart.convert(ElementSet::Equinoctial, &orbit).unwrap();
+        assert!((equi.matrix - equi_direct.matrix).norm() < 1e-5);
+    }
+
+    #[test]
+    fn equinoctial_elements() {
+        let orbit = orbit();
+        let equi = ElementSet::Equinoctial.elements(&orbit).unwrap();
+        let ecc = orbit.ecc().unwrap();
+        let lon_peri = (orbit.aop_deg().unwrap() + orbit.raan_deg().unwrap()).to_radians();
+        let tan_half_inc = (orbit.inc_deg().unwrap().to_radians() / 2.0).tan();
+        let raan = orbit.raan_deg().unwrap().to_radians();
+
+        assert!((equi[0] - orbit.sma_km().unwrap()).abs() < 1e-9);
+        assert!((equi[1] - ecc * lon_peri.sin()).abs() < 1e-12);
+        assert!((equi[2] - ecc * lon_peri.cos()).abs() < 1e-12);
+        assert!((equi[3] - tan_half_inc * raan.sin()).abs() < 1e-12);
+        assert!((equi[4] - tan_half_inc * raan.cos()).abs() < 1e-12);
+        let mean_lon = between_0_360(
+            orbit.aop_deg().unwrap() + orbit.raan_deg().unwrap() + orbit.ma_deg().unwrap(),
+        );
+        assert!(between_pm_180(equi[5] - mean_lon).abs() < 1e-9);
+    }
+
+    #[test]
+    fn covariance_propagation() {
+        use hifitime::TimeUnits;
+
+        let orbit = orbit();
+        let cart = Covariance::new(
+            Matrix6::from_diagonal(&Vector6::new(1.0, 1.0, 1.0, 1e-6, 1e-6, 1e-6)),
+            ElementSet::Cartesian,
+        );
+
+        let new_epoch = orbit.epoch + 1.hours();
+
+        // The STM over a zero duration is the identity
+        let stm0 = two_body_stm(&orbit, orbit.epoch).unwrap();
+        assert!((stm0 - Matrix6::identity()).norm() < 1e-6);
+
+        // The two-body STM is symplectic, so its determinant is one
+        let stm = two_body_stm(&orbit, new_epoch).unwrap();
+        assert!((stm.determinant() - 1.0).abs() < 1e-5);
+
+        let propagated = cart.propagate_two_body(&orbit, new_epoch, None).unwrap();
+        assert_eq!(propagated.element_set, ElementSet::Cartesian);
+        // Without process noise, the position uncertainty grows along track.
+        assert!(
+            propagated.sigmas().fixed_rows::<3>(0).norm() > cart.sigmas().fixed_rows::<3>(0).norm()
+        );
+
+        let with_snc = cart
+            .propagate_two_body(&orbit, new_epoch, Some(Vector3::new(1e-9, 1e-9, 1e-9)))
+            .unwrap();
+        for i in 0..6 {
+            assert!(with_snc.matrix[(i, i)] > propagated.matrix[(i, i)]);
+        }
+
+        // Propagating a Keplerian covariance returns a Keplerian covariance
+        let kep = cart.convert(ElementSet::Keplerian, &orbit).unwrap();
+        let kep_prop = kep.propagate_two_body(&orbit, new_epoch, None).unwrap();
+        assert_eq!(kep_prop.element_set, ElementSet::Keplerian);
+        // In two-body dynamics, the SMA uncertainty does not change
+        assert!((kep_prop.sigmas()[0] - kep.sigmas()[0]).abs() / kep.sigmas()[0] < 1e-4);
+    }
+}
@@ -91,3 +91,62 @@ pub fn chebyshev_eval_poly(
 
     Ok(val)
 }
+
+/// Returns the Chebyshev nodes of the first kind in the normalized domain [-1; 1], i.e. the roots of the Chebyshev polynomial of degree `degree + 1`.
+///
+/// Sampling a function at these nodes and calling `chebyshev_fit` yields the interpolating polynomial of the requested degree,
+/// which is nearly minimax and does not suffer from Runge's phenomenon.
+pub fn chebyshev_nodes(degree: usize) -> Vec<f64> {
+    let n = degree + 1;
+    (0..n)
+        .map(|k| (core::f64::consts::PI * (k as f64 + 0.5) / n as f64).cos())
+        .collect()
+}
+
+/// Computes the Chebyshev coefficients of the polynomial interpolating the provided values, sampled at the `chebyshev_nodes` of the same degree.
+///
+/// The coefficients are returned in the format expected by `chebyshev_eval` (i.e. the first coefficient is _not_ halved).
+pub fn chebyshev_fit(values_at_nodes: &[f64]) -> Vec<f64> {
+    let n = values_at_nodes.len();
+    (0..n)
+        .map(|j| {
+            let scale = if j == 0 { 1.0 } else { 2.0 } / n as f64;
+            scale
+                * values_at_nodes
+                    .iter()
+                    .enumerate()
+                    .map(|(k, val)| {
+                        val * (core::f64::consts::PI * j as f64 * (k as f64 + 0.5) / n as f64).cos()
+                    })
+                    .sum::<f64>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod ut_chebyshev {
+    use super::*;
+
+    #[test]
+    fn fit_then_eval() {
+        let degree = 12;
+        let radius_s = 10.0;
+        let epoch = Epoch::from_et_seconds(0.0);
+
+        let func = |x: f64| (1.3 * x).sin() + 0.5 * x.powi(2);
+        let func_dt = |x: f64| (1.3 * (1.3 * x).cos() + x) / radius_s;
+
+        let values = chebyshev_nodes(degree)
+            .iter()
+            .map(|x| func(*x))
+            .collect::<Vec<_>>();
+        let coeffs = chebyshev_fit(&values);
+        assert_eq!(coeffs.len(), degree + 1);
+
+        for x in [-1.0, -0.3, 0.0, 0.42, 1.0] {
+            let (val, deriv) = chebyshev_eval(x, &coeffs, radius_s, epoch, degree).unwrap();
+            assert!((val - func(x)).abs() < 1e-11, "{x}: {val} != {}", func(x));
+            assert!((deriv - func_dt(x)).abs() < 1e-9);
+        }
+    }
+}
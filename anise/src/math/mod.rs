@@ -19,6 +19,7 @@ pub mod angles;
 pub mod cartesian;
 #[cfg(feature = "python")]
 mod cartesian_py;
+pub mod covariance;
 pub mod interpolation;
 pub mod rotation;
 pub mod units;
@@ -9,13 +9,15 @@
  */
 use crate::{
     astro::PhysicsResult,
-    errors::{InvalidRotationSnafu, InvalidStateRotationSnafu, PhysicsError},
+    errors::{
+        DCMMissingDerivativeSnafu, InvalidRotationSnafu, InvalidStateRotationSnafu, PhysicsError,
+    },
     math::{cartesian::CartesianState, Matrix3, Matrix6, Vector3, Vector6},
     prelude::Frame,
     NaifId,
 };
 use nalgebra::Vector4;
-use snafu::ensure;
+use snafu::{ensure, OptionExt};
 
 use super::{r1, r2, r3, Quaternion, Rotation};
 use core::fmt;
@@ -116,6 +118,27 @@ impl DCM {
         full_dcm
     }
 
+    /// Returns the angular velocity vector in rad/s of the `to` frame with respect to the `from` frame, expressed in the `to` frame.
+    ///
+    /// It is extracted from the skew-symmetric matrix [ω×] = -Ċ Cᵀ, where C is this rotation matrix and Ċ its time derivative.
+    ///
+    /// # Errors
+    /// + DCMMissingDerivative if the time derivative of this DCM is not set.
+    pub fn angular_velocity_rad_s(&self) -> PhysicsResult<Vector3> {
+        let rot_mat_dt = self.rot_mat_dt.context(DCMMissingDerivativeSnafu {
+            action: "computing the angular velocity",
+        })?;
+
+        let omega_tilde = -rot_mat_dt * self.rot_mat.transpose();
+
+        // Average the off-diagonal terms to remove any numerical asymmetry
+        Ok(Vector3::new(
+            0.5 * (omega_tilde[(2, 1)] - omega_tilde[(1, 2)]),
+            0.5 * (omega_tilde[(0, 2)] - omega_tilde[(2, 0)]),
+            0.5 * (omega_tilde[(1, 0)] - omega_tilde[(0, 1)]),
+        ))
+    }
+
     /// Builds an identity rotation
     pub fn identity(from: i32, to: i32) -> Self {
         let rot_mat = Matrix3::identity();
@@ -497,4 +520,23 @@ mod ut_dcm {
                 < f64::EPSILON
         );
     }
+
+    #[test]
+    fn test_angular_velocity() {
+        let rate_rad_s = 1e-3;
+        let angle_rad = 0.5;
+        let mut dcm = DCM::r3(angle_rad, 0, 1);
+        assert!(dcm.angular_velocity_rad_s().is_err());
+
+        // Time derivative of R3(θ) with θ' = rate
+        let (s, c) = angle_rad.sin_cos();
+        dcm.rot_mat_dt = Some(Matrix3::new(-s, c, 0.0, -c, -s, 0.0, 0.0, 0.0, 0.0) * rate_rad_s);
+
+        let omega = dcm.angular_velocity_rad_s().unwrap();
+        assert!((omega - Vector3::new(0.0, 0.0, rate_rad_s)).norm() < f64::EPSILON);
+
+        // The reverse rotation spins the other way, expressed in the `from` frame
+        let omega_rev = dcm.transpose().angular_velocity_rad_s().unwrap();
+        assert!((omega_rev + dcm.transpose() * omega).norm() < f64::EPSILON);
+    }
 }
@@ -8,13 +8,14 @@
  * Documentation: https://nyxspace.com/
  */
 
+use crate::errors::PhysicsError;
 use crate::NaifId;
 
 use super::DCM;
 
 use nalgebra::Matrix3;
-use ndarray::Array2;
-use numpy::{PyArray2, PyReadonlyArray2, PyUntypedArrayMethods};
+use ndarray::{Array1, Array2};
+use numpy::{PyArray1, PyArray2, PyReadonlyArray2, PyUntypedArrayMethods};
 use pyo3::basic::CompareOp;
 use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
@@ -185,6 +186,22 @@ impl DCM {
         Ok(pt_state_dcm)
     }
 
+    /// Returns the angular velocity vector in rad/s of the `to` frame with respect to the `from` frame, expressed in the `to` frame.
+    /// Raises an exception if the time derivative of this DCM is not set.
+    /// Warning: you MUST manually install numpy to call this function.
+    /// :rtype: numpy.array
+    #[pyo3(name = "angular_velocity_rad_s")]
+    fn py_angular_velocity_rad_s<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> Result<Bound<'py, PyArray1<f64>>, PhysicsError> {
+        let omega = self.angular_velocity_rad_s()?;
+        Ok(PyArray1::<f64>::from_owned_array(
+            py,
+            Array1::from_iter(omega.iter().copied()),
+        ))
+    }
+
     fn __str__(&self) -> String {
         format!("{self}")
     }
@@ -7,7 +7,10 @@
  *
  * Documentation: https://nyxspace.com/
  */
-use core::fmt::Display;
+use core::fmt::{self, Display};
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use hifitime::Duration;
 
 /// Re-export hifitime's units as DurationUnit.
 pub use hifitime::Unit as TimeUnit;
@@ -51,7 +54,7 @@ impl LengthUnit {
 }
 
 impl Display for LengthUnit {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Micrometer => write!(f, "um"),
             Self::Millimeter => write!(f, "mm"),
@@ -67,3 +70,208 @@ impl Default for LengthUnit {
         Self::Kilometer
     }
 }
+
+/// Defines a newtype wrapper of an f64 in the provided unit, with the arithmetic that preserves that unit.
+macro_rules! typed_quantity {
+    ($(#[$doc:meta])* $name:ident, $unit:literal) => {
+        $(#[$doc])*
+        #[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+        #[repr(transparent)]
+        pub struct $name(pub f64);
+
+        impl $name {
+            /// Returns the raw value of this quantity, in the unit of its type.
+            #[must_use]
+            pub const fn value(self) -> f64 {
+                self.0
+            }
+
+            /// Returns the absolute value of this quantity.
+            #[must_use]
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(quantity: $name) -> f64 {
+                quantity.0
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Mul<$name> for f64 {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                $name(self * rhs.0)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        /// The ratio of two quantities of the same unit is unitless.
+        impl Div for $name {
+            type Output = f64;
+            fn div(self, rhs: Self) -> f64 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Display::fmt(&self.0, f)?;
+                write!(f, " {}", $unit)
+            }
+        }
+    };
+}
+
+typed_quantity!(
+    /// A distance in kilometers.
+    Km,
+    "km"
+);
+typed_quantity!(
+    /// A speed in kilometers per second.
+    KmPerSec,
+    "km/s"
+);
+typed_quantity!(
+    /// An angle in radians.
+    Rad,
+    "rad"
+);
+typed_quantity!(
+    /// An angle in degrees.
+    Deg,
+    "deg"
+);
+
+impl From<Deg> for Rad {
+    fn from(angle: Deg) -> Self {
+        Self(angle.0.to_radians())
+    }
+}
+
+impl From<Rad> for Deg {
+    fn from(angle: Rad) -> Self {
+        Self(angle.0.to_degrees())
+    }
+}
+
+impl Rad {
+    /// Returns the sine of this angle
+    #[must_use]
+    pub fn sin(self) -> f64 {
+        self.0.sin()
+    }
+
+    /// Returns the cosine of this angle
+    #[must_use]
+    pub fn cos(self) -> f64 {
+        self.0.cos()
+    }
+
+    /// Returns this angle in degrees
+    #[must_use]
+    pub fn to_deg(self) -> Deg {
+        self.into()
+    }
+}
+
+impl Deg {
+    /// Returns this angle in radians
+    #[must_use]
+    pub fn to_rad(self) -> Rad {
+        self.into()
+    }
+}
+
+/// Distance traveled at this speed during the provided duration.
+impl Mul<Duration> for KmPerSec {
+    type Output = Km;
+    fn mul(self, rhs: Duration) -> Km {
+        Km(self.0 * rhs.to_seconds())
+    }
+}
+
+/// Average speed to cover this distance in the provided duration.
+impl Div<Duration> for Km {
+    type Output = KmPerSec;
+    fn div(self, rhs: Duration) -> KmPerSec {
+        KmPerSec(self.0 / rhs.to_seconds())
+    }
+}
+
+#[cfg(test)]
+mod ut_units {
+    use super::*;
+    use hifitime::TimeUnits;
+
+    #[test]
+    fn typed_quantities() {
+        let a = Km(7000.0);
+        let b = Km(500.0);
+        assert_eq!(a + b, Km(7500.0));
+        assert_eq!(a - b, Km(6500.0));
+        assert_eq!(-b, Km(-500.0));
+        assert_eq!(2.0 * b, Km(1000.0));
+        assert_eq!(a / b, 14.0);
+        assert_eq!(f64::from(a), 7000.0);
+        assert_eq!(format!("{b}"), "500 km");
+        assert_eq!(format!("{b:.1}"), "500.0 km");
+
+        let v = KmPerSec(7.5);
+        assert_eq!(v * 2.minutes(), Km(900.0));
+        assert_eq!(Km(900.0) / 2.minutes(), v);
+
+        let angle = Deg(180.0);
+        assert_eq!(Rad::from(angle), Rad(core::f64::consts::PI));
+        assert_eq!(angle.to_rad().to_deg(), angle);
+        assert!(Rad(core::f64::consts::FRAC_PI_2).cos().abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,330 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use crate::{naif::kpl::pool::KernelPool, structure::dataset::DataSetError, NaifId};
+
+/// Built-in mapping between NAIF IDs and body names, following the default SPICE body list (cf. the NAIF IDs required reading).
+///
+/// An ID may have several names: the first one listed is the preferred name, returned by [body_name_from_id].
+/// DSN stations are listed as `DSS-XX` with the ID `399000 + XX`, and the DSN complexes with the IDs 399001 to 399003.
+pub const NAIF_BODIES: &[(NaifId, &str)] = &[
+    // Barycenters
+    (0, "SOLAR SYSTEM BARYCENTER"),
+    (0, "SSB"),
+    (0, "SOLAR_SYSTEM_BARYCENTER"),
+    (1, "MERCURY BARYCENTER"),
+    (2, "VENUS BARYCENTER"),
+    (3, "EARTH BARYCENTER"),
+    (3, "EARTH-MOON BARYCENTER"),
+    (3, "EARTH MOON BARYCENTER"),
+    (3, "EMB"),
+    (4, "MARS BARYCENTER"),
+    (5, "JUPITER BARYCENTER"),
+    (6, "SATURN BARYCENTER"),
+    (7, "URANUS BARYCENTER"),
+    (8, "NEPTUNE BARYCENTER"),
+    (9, "PLUTO BARYCENTER"),
+    // Sun and planets
+    (10, "SUN"),
+    (199, "MERCURY"),
+    (299, "VENUS"),
+    (399, "EARTH"),
+    (499, "MARS"),
+    (599, "JUPITER"),
+    (699, "SATURN"),
+    (799, "URANUS"),
+    (899, "NEPTUNE"),
+    (999, "PLUTO"),
+    // Main satellites
+    (301, "MOON"),
+    (401, "PHOBOS"),
+    (402, "DEIMOS"),
+    (501, "IO"),
+    (502, "EUROPA"),
+    (503, "GANYMEDE"),
+    (504, "CALLISTO"),
+    (505, "AMALTHEA"),
+    (601, "MIMAS"),
+    (602, "ENCELADUS"),
+    (603, "TETHYS"),
+    (604, "DIONE"),
+    (605, "RHEA"),
+    (606, "TITAN"),
+    (607, "HYPERION"),
+    (608, "IAPETUS"),
+    (609, "PHOEBE"),
+    (701, "ARIEL"),
+    (702, "UMBRIEL"),
+    (703, "TITANIA"),
+    (704, "OBERON"),
+    (705, "MIRANDA"),
+    (801, "TRITON"),
+    (802, "NEREID"),
+    (901, "CHARON"),
+    (902, "NIX"),
+    (903, "HYDRA"),
+    // Comets and asteroids
+    (1000012, "67P/CHURYUMOV-GERASIMENKO (1969 R1)"),
+    (1000012, "CHURYUMOV-GERASIMENKO"),
+    (2000001, "CERES"),
+    (2000002, "PALLAS"),
+    (2000004, "VESTA"),
+    (2000016, "PSYCHE"),
+    (2000021, "LUTETIA"),
+    (2000433, "EROS"),
+    (2002867, "STEINS"),
+    (2025143, "ITOKAWA"),
+    (2101955, "BENNU"),
+    (2162173, "RYUGU"),
+    // Spacecraft
+    (-3, "MARS ORBITER MISSION"),
+    (-3, "MOM"),
+    (-5, "AKATSUKI"),
+    (-5, "PLANET-C"),
+    (-21, "SOHO"),
+    (-28, "JUPITER ICY MOONS EXPLORER"),
+    (-28, "JUICE"),
+    (-31, "VOYAGER 1"),
+    (-31, "VG1"),
+    (-32, "VOYAGER 2"),
+    (-32, "VG2"),
+    (-37, "HAYABUSA2"),
+    (-40, "CLEMENTINE"),
+    (-41, "MARS EXPRESS"),
+    (-41, "MEX"),
+    (-48, "HUBBLE SPACE TELESCOPE"),
+    (-48, "HST"),
+    (-49, "LUCY"),
+    (-53, "MARS ODYSSEY"),
+    (-53, "MARS SURVEYOR 01 ORBITER"),
+    (-61, "JUNO"),
+    (-62, "EMIRATES MARS MISSION"),
+    (-62, "EMM"),
+    (-62, "HOPE"),
+    (-64, "OSIRIS-REX"),
+    (-64, "ORX"),
+    (-74, "MARS RECONNAISSANCE ORBITER"),
+    (-74, "MARS RECON ORBITER"),
+    (-74, "MRO"),
+    (-76, "MARS SCIENCE LABORATORY"),
+    (-76, "MSL"),
+    (-76, "CURIOSITY"),
+    (-77, "GALILEO ORBITER"),
+    (-77, "GLL"),
+    (-79, "SPITZER SPACE TELESCOPE"),
+    (-79, "SPITZER"),
+    (-82, "CASSINI"),
+    (-82, "CAS"),
+    (-84, "PHOENIX"),
+    (-85, "LUNAR RECONNAISSANCE ORBITER"),
+    (-85, "LRO"),
+    (-86, "CHANDRAYAAN-1"),
+    (-86, "CH1"),
+    (-94, "MARS GLOBAL SURVEYOR"),
+    (-94, "MGS"),
+    (-95, "TESS"),
+    (-96, "PARKER SOLAR PROBE"),
+    (-96, "SOLAR PROBE PLUS"),
+    (-96, "SPP"),
+    (-98, "NEW HORIZONS"),
+    (-121, "BEPICOLOMBO MPO"),
+    (-121, "MPO"),
+    (-130, "HAYABUSA"),
+    (-135, "DART"),
+    (-140, "DEEP IMPACT FLYBY SPACECRAFT"),
+    (-143, "TRACE GAS ORBITER"),
+    (-143, "TGO"),
+    (-144, "SOLAR ORBITER"),
+    (-144, "SOLO"),
+    (-150, "HUYGENS PROBE"),
+    (-150, "HUYGENS"),
+    (-151, "CHANDRA"),
+    (-151, "AXAF"),
+    (-152, "CHANDRAYAAN-2"),
+    (-152, "CH2"),
+    (-155, "KOREA PATHFINDER LUNAR ORBITER"),
+    (-155, "KPLO"),
+    (-159, "EUROPA CLIPPER"),
+    (-168, "MARS 2020"),
+    (-168, "PERSEVERANCE"),
+    (-170, "JAMES WEBB SPACE TELESCOPE"),
+    (-170, "JWST"),
+    (-177, "GRAIL-A"),
+    (-181, "GRAIL-B"),
+    (-189, "INSIGHT"),
+    (-189, "NSYT"),
+    (-202, "MAVEN"),
+    (-203, "DAWN"),
+    (-226, "ROSETTA"),
+    (-227, "KEPLER"),
+    (-234, "STEREO AHEAD"),
+    (-235, "STEREO BEHIND"),
+    (-236, "MESSENGER"),
+    // DSN complexes and stations
+    (399001, "GOLDSTONE"),
+    (399002, "CANBERRA"),
+    (399003, "MADRID"),
+    (399004, "USUDA"),
+    (399012, "DSS-12"),
+    (399013, "DSS-13"),
+    (399014, "DSS-14"),
+    (399015, "DSS-15"),
+    (399016, "DSS-16"),
+    (399017, "DSS-17"),
+    (399023, "DSS-23"),
+    (399024, "DSS-24"),
+    (399025, "DSS-25"),
+    (399026, "DSS-26"),
+    (399027, "DSS-27"),
+    (399028, "DSS-28"),
+    (399033, "DSS-33"),
+    (399034, "DSS-34"),
+    (399035, "DSS-35"),
+    (399036, "DSS-36"),
+    (399042, "DSS-42"),
+    (399043, "DSS-43"),
+    (399045, "DSS-45"),
+    (399046, "DSS-46"),
+    (399049, "DSS-49"),
+    (399053, "DSS-53"),
+    (399054, "DSS-54"),
+    (399055, "DSS-55"),
+    (399056, "DSS-56"),
+    (399061, "DSS-61"),
+    (399063, "DSS-63"),
+    (399064, "DSS-64"),
+    (399065, "DSS-65"),
+    (399066, "DSS-66"),
+];
+
+/// Returns the NAIF ID of the provided body name from the built-in list, ignoring case and repeated whitespaces (like SPICE's `bodn2c`).
+pub fn body_id_from_name(name: &str) -> Option<NaifId> {
+    let name = normalize_body_name(name);
+    NAIF_BODIES
+        .iter()
+        .find(|(_, body_name)| *body_name == name)
+        .map(|(id, _)| *id)
+}
+
+/// Returns the preferred name of the provided NAIF ID from the built-in list (like SPICE's `bodc2n`).
+pub fn body_name_from_id(id: NaifId) -> Option<&'static str> {
+    NAIF_BODIES
+        .iter()
+        .find(|(body_id, _)| *body_id == id)
+        .map(|(_, name)| *name)
+}
+
+/// Upper cases the body name and collapses its whitespaces, as SPICE does before comparing body names.
+pub fn normalize_body_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+/// Returns the body names and IDs defined by the `NAIF_BODY_NAME` and `NAIF_BODY_CODE` variables of the provided text kernel
+/// (e.g. a frame kernel), in the order of their definition.
+///
+/// Both direct (`=`) and incremental (`+=`) assignments are supported, and both variables must have the same number of values.
+pub fn body_names_from_kernel(kernel: &str) -> Result<Vec<(String, NaifId)>, DataSetError> {
+    body_names_from_pool(&KernelPool::from_kernel(kernel)?)
+}
+
+/// Returns the body names and IDs defined by the `NAIF_BODY_NAME` and `NAIF_BODY_CODE` variables of the provided kernel pool,
+/// in the order of their definition.
+pub fn body_names_from_pool(pool: &KernelPool) -> Result<Vec<(String, NaifId)>, DataSetError> {
+    let names = pool.get_strings("NAIF_BODY_NAME").unwrap_or_default();
+    let codes = pool.get_i32s("NAIF_BODY_CODE").unwrap_or_default();
+
+    if names.len() != codes.len() {
+        return Err(DataSetError::Conversion {
+            action: format!(
+                "found {} NAIF_BODY_NAME but {} NAIF_BODY_CODE values",
+                names.len(),
+                codes.len()
+            ),
+        });
+    }
+
+    Ok(names
+        .iter()
+        .zip(codes)
+        .map(|(name, id)| (normalize_body_name(name), id))
+        .collect())
+}
+
+#[cfg(test)]
+mod ut_bodies {
+    use super::*;
+
+    #[test]
+    fn builtin_bodies() {
+        assert_eq!(body_id_from_name("Mars Reconnaissance Orbiter"), Some(-74));
+        assert_eq!(body_id_from_name("  mro "), Some(-74));
+        assert_eq!(body_id_from_name("earth moon  barycenter"), Some(3));
+        assert_eq!(body_id_from_name("DSS-14"), Some(399014));
+        assert_eq!(body_id_from_name("Vulcan"), None);
+
+        assert_eq!(body_name_from_id(-74), Some("MARS RECONNAISSANCE ORBITER"));
+        assert_eq!(body_name_from_id(399), Some("EARTH"));
+        assert_eq!(body_name_from_id(399065), Some("DSS-65"));
+        assert_eq!(body_name_from_id(-999_999), None);
+
+        // Names are unique in the built-in list.
+        for (idx, (_, name)) in NAIF_BODIES.iter().enumerate() {
+            assert!(
+                NAIF_BODIES[idx + 1..]
+                    .iter()
+                    .all(|(_, other)| other != name),
+                "{name} is listed twice"
+            );
+            assert_eq!(normalize_body_name(name), *name);
+        }
+    }
+
+    #[test]
+    fn kernel_body_names() {
+        let kernel = r"
+KPL/FK
+
+   NAIF_BODY_NAME = ( 'IGNORED' ) is in a comment block
+
+\begindata
+
+   NAIF_BODY_NAME += ( 'MY  SPACECRAFT', 'MY ''LANDER''' )
+   NAIF_BODY_CODE += ( -1001
+                       -1002 )
+
+   FRAME_MY_SC = -1001000
+
+   NAIF_BODY_NAME += 'MY ROVER'
+   NAIF_BODY_CODE += -1003
+
+\begintext
+";
+
+        let names = body_names_from_kernel(kernel).unwrap();
+        assert_eq!(
+            names,
+            vec![
+                ("MY SPACECRAFT".to_string(), -1001),
+                ("MY 'LANDER'".to_string(), -1002),
+                ("MY ROVER".to_string(), -1003),
+            ]
+        );
+
+        let kernel = r"
+\begindata
+   NAIF_BODY_NAME = ( 'A', 'B' )
+   NAIF_BODY_CODE = ( -1 )
+";
+        assert!(body_names_from_kernel(kernel).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use bytes::BytesMut;
+use snafu::ensure;
+use zerocopy::IntoBytes;
+
+use super::{
+    DAFError, FileRecord, NAIFRecord, NAIFSummaryRecord, NameRecord, SummaryRecord,
+    TooManySegmentsSnafu, DAF, RCRD_LEN,
+};
+use crate::naif::Endian;
+use crate::DBL_SIZE;
+
+/// FTP validation string that SPICE uses to detect files corrupted by an ASCII mode transfer.
+const FTP_STR: &[u8; 28] = b"FTPSTR:\r:\n:\r\n:\r\x00:\x81:\x10\xce:ENDFTP";
+
+/// Number of characters of comments stored per comment record, as per the SPICE convention.
+const COMMENT_CHARS_PER_RCRD: usize = 1000;
+
+/// End of transmission character, marking the end of the comments area.
+const EOT: u8 = 0x04;
+
+/// Assembles a new DAF file (SPK or BPC) from segments of raw double precision data.
+///
+/// The file is written with the native endianness, a single summary record and a single name record,
+/// and is readable by both ANISE and CSPICE.
+///
+/// # Limitations
+/// The summary and name records are not chained because ANISE only reads the first summary record of a DAF (cf. [DAF::data_summaries]),
+/// so the number of segments is limited to [Self::max_segments] (25 segments for SPK and BPC files). Adding more segments is an error.
+#[derive(Clone, Debug, Default)]
+pub struct DAFBuilder<R: NAIFSummaryRecord> {
+    internal_filename: String,
+    comments: Option<String>,
+    segments: Vec<(R, String, Vec<f64>)>,
+}
+
+impl<R: NAIFSummaryRecord> DAFBuilder<R> {
+    /// Initializes a new DAF builder with the provided internal file name (truncated to 60 characters).
+    pub fn new(internal_filename: &str) -> Self {
+        Self {
+            internal_filename: internal_filename.to_string(),
+            comments: None,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Sets the comments of this DAF file
+    pub fn with_comments(mut self, comments: &str) -> Self {
+        self.comments = Some(comments.to_string());
+        self
+    }
+
+    /// Maximum number of segments that fit in a single summary record.
+    pub const fn max_segments() -> usize {
+        (RCRD_LEN - SummaryRecord::SIZE) / R::SIZE
+    }
+
+    /// Returns the number of segments added so far
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Returns true if no segment has been added yet
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Adds a segment with its summary, name (truncated to the summary length), and data.
+    ///
+    /// The data indexes of the summary are set when building the file, so they need not be initialized.
+    ///
+    /// # Errors
+    /// Returns a `TooManySegments` error if this builder already has [Self::max_segments] segments.
+    pub fn add_segment(&mut self, summary: R, name: &str, data: Vec<f64>) -> Result<(), DAFError> {
+        ensure!(
+            self.segments.len() < Self::max_segments(),
+            TooManySegmentsSnafu {
+                kind: R::NAME,
+                count: self.segments.len() + 1,
+                max: Self::max_segments()
+            }
+        );
+        self.segments.push((summary, name.to_string(), data));
+        Ok(())
+    }
+
+    /// Builds the DAF file from the segments.
+    ///
+    /// # Layout
+    /// 1. The file record;
+    /// 2. The comment records, if any;
+    /// 3. The summary record, immediately followed by the name record;
+    /// 4. The segment data, padded to a full record.
+    pub fn build(&self) -> Result<DAF<R>, DAFError> {
+        let summary_size = (R::ND + R::NI.div_ceil(2)) as usize;
+
+        let comment_rcrds = self.comment_records();
+        let num_comment_rcrds = comment_rcrds.len() / RCRD_LEN;
+
+        // DAF record numbers start at one, and the first record is the file record.
+        let fwrd_rcrd = num_comment_rcrds + 2;
+        // Addresses are in double precision words, starting at one. Data starts after the name record.
+        let mut next_addr = (fwrd_rcrd + 1) * RCRD_LEN / DBL_SIZE + 1;
+
+        let mut summaries = Vec::with_capacity(self.segments.len());
+        let mut name_rcrd = NameRecord::default();
+        let mut data = Vec::new();
+
+        for (sno, (summary, name, segment_data)) in self.segments.iter().enumerate() {
+            let mut summary = *summary;
+            summary.update_indexes(next_addr, next_addr + segment_data.len() - 1);
+            summaries.push(summary);
+            name_rcrd.set_nth_name(sno, summary_size, name);
+            data.extend_from_slice(segment_data);
+            next_addr += segment_data.len();
+        }
+
+        let mut file_rcrd = FileRecord {
+            nd: R::ND,
+            ni: R::NI,
+            forward: fwrd_rcrd as u32,
+            backward: fwrd_rcrd as u32,
+            free_addr: next_addr as u32,
+            ..Default::default()
+        };
+        copy_padded(&mut file_rcrd.id_str, R::DAF_IDWORD.as_bytes());
+        copy_padded(
+            &mut file_rcrd.internal_filename,
+            self.internal_filename.as_bytes(),
+        );
+        copy_padded(
+            &mut file_rcrd.endian_str,
+            match Endian::f64_native() {
+                Endian::Little => b"LTL-IEEE",
+                Endian::Big => b"BIG-IEEE",
+            },
+        );
+        file_rcrd.ftp_str.copy_from_slice(FTP_STR);
+
+        let mut bytes = BytesMut::with_capacity(
+            (fwrd_rcrd + 1) * RCRD_LEN + (data.len() * DBL_SIZE).next_multiple_of(RCRD_LEN),
+        );
+        bytes.extend_from_slice(file_rcrd.as_bytes());
+        bytes.extend_from_slice(&comment_rcrds);
+
+        // Summary record: next, previous, and number of summaries, followed by the summaries.
+        let daf_summary = [0.0, 0.0, summaries.len() as f64];
+        bytes.extend_from_slice(daf_summary.as_bytes());
+        bytes.extend_from_slice(summaries.as_bytes());
+        bytes.resize(fwrd_rcrd * RCRD_LEN, 0x0);
+
+        bytes.extend_from_slice(name_rcrd.as_bytes());
+
+        bytes.extend_from_slice(data.as_bytes());
+        let padded_len = bytes.len().next_multiple_of(RCRD_LEN);
+        bytes.resize(padded_len, 0x0);
+
+        DAF::parse(bytes.freeze())
+    }
+
+    /// Returns the comment records as bytes, with lines separated by NULL and terminated by EOT.
+    fn comment_records(&self) -> Vec<u8> {
+        let comments = match &self.comments {
+            Some(comments) if !comments.is_empty() => comments,
+            _ => return Vec::new(),
+        };
+
+        let mut chars: Vec<u8> = comments
+            .lines()
+            .flat_map(|line| line.bytes().chain(core::iter::once(0x0)))
+            .collect();
+        chars.push(EOT);
+
+        let mut rcrds = Vec::new();
+        for chunk in chars.chunks(COMMENT_CHARS_PER_RCRD) {
+            let mut rcrd = [0x0; RCRD_LEN];
+            rcrd[..chunk.len()].copy_from_slice(chunk);
+            rcrds.extend_from_slice(&rcrd);
+        }
+        rcrds
+    }
+}
+
+/// Copies the source into the destination, truncating it if needed, and pads the destination with spaces.
+fn copy_padded(dest: &mut [u8], src: &[u8]) {
+    let len = src.len().min(dest.len());
+    dest[..len].copy_from_slice(&src[..len]);
+    for byte in dest.iter_mut().skip(len) {
+        *byte = b' ';
+    }
+}
+
+#[cfg(test)]
+mod ut_daf_builder {
+    use hifitime::Epoch;
+
+    use super::*;
+    use crate::naif::daf::{datatypes::Type2ChebyshevSet, NAIFDataSet};
+    use crate::naif::pck::BPCSummaryRecord;
+
+    #[test]
+    fn build_and_parse() {
+        let start = Epoch::from_et_seconds(0.0);
+        let end = Epoch::from_et_seconds(100.0);
+
+        // A single record of constant angles, degree 1.
+        let data = vec![
+            50.0, 50.0, 1.0, 0.0, 2.0, 0.0, 3.0, 0.0, 0.0, 100.0, 8.0, 1.0,
+        ];
+
+        let mut summary = BPCSummaryRecord {
+            frame_id: 3000,
+            inertial_frame_id: 1,
+            data_type_i: 2,
+            ..Default::default()
+        };
+        summary.update_epochs(start, end);
+
+        let mut builder = DAFBuilder::new("ANISE TEST").with_comments("line one\nline two");
+        builder.add_segment(summary, "TEST SEGMENT", data).unwrap();
+
+        let bpc = builder.build().unwrap();
+
+        assert_eq!(bpc.file_record().unwrap().identification(), Ok("PCK"));
+        assert_eq!(
+            bpc.file_record().unwrap().internal_filename(),
+            Ok("ANISE TEST")
+        );
+        assert!(bpc.comments().unwrap().unwrap().contains("line two"));
+
+        let summaries = bpc.data_summaries().unwrap();
+        assert_eq!(summaries[0].frame_id, 3000);
+        assert!(summaries[1].is_empty());
+
+        let (summary, idx) = bpc.summary_from_name("TEST SEGMENT").unwrap();
+        assert_eq!(idx, 0);
+
+        let set = bpc.nth_data::<Type2ChebyshevSet>(0).unwrap();
+        assert_eq!(set.degree(), 1);
+        let (angles, rates) = set.evaluate(Epoch::from_et_seconds(25.0), summary).unwrap();
+        assert_eq!(angles, crate::math::Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(rates, crate::math::Vector3::zeros());
+    }
+
+    #[test]
+    fn too_many_segments() {
+        let mut builder = DAFBuilder::<BPCSummaryRecord>::new("ANISE TEST");
+        for _ in 0..DAFBuilder::<BPCSummaryRecord>::max_segments() {
+            builder
+                .add_segment(BPCSummaryRecord::default(), "SEG", vec![0.0])
+                .unwrap();
+        }
+        assert!(builder
+            .add_segment(BPCSummaryRecord::default(), "SEG", vec![0.0])
+            .is_err());
+    }
+}
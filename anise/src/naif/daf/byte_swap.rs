@@ -0,0 +1,150 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use log::info;
+use zerocopy::{FromBytes, IntoBytes};
+
+use super::{DAFError, FileRecord, NAIFRecord, SummaryRecord, RCRD_LEN};
+use crate::errors::DecodingError;
+use crate::naif::Endian;
+use crate::DBL_SIZE;
+
+const INT_SIZE: usize = 4;
+
+/// Returns a copy of the provided DAF bytes swapped to the endianness of this platform, or None if the DAF is already
+/// in the native endianness (or if its file record cannot be read, in which case parsing will report the error).
+///
+/// The integers of the file record, the summary records, and the data arrays are swapped, while the comment and
+/// name records are left untouched since they only contain characters. The endian flag of the file record is updated
+/// so the converted bytes are a valid DAF for this platform.
+pub(crate) fn to_native_endian(
+    bytes: &[u8],
+    kind: &'static str,
+) -> Result<Option<Vec<u8>>, DAFError> {
+    let Some(Ok(mut file_record)) = bytes
+        .get(..FileRecord::SIZE)
+        .map(FileRecord::read_from_bytes)
+    else {
+        return Ok(None);
+    };
+
+    let native = Endian::f64_native();
+    match file_record.file_endianness() {
+        Ok(file_endian) if file_endian != native => {
+            info!("DAF/{kind}: swapping {file_endian:?} endian file to {native:?} endian")
+        }
+        _ => return Ok(None),
+    };
+
+    let mut swapped = bytes.to_vec();
+
+    // File record
+    file_record.nd = file_record.nd.swap_bytes();
+    file_record.ni = file_record.ni.swap_bytes();
+    file_record.forward = file_record.forward.swap_bytes();
+    file_record.backward = file_record.backward.swap_bytes();
+    file_record.free_addr = file_record.free_addr.swap_bytes();
+    file_record.endian_str = match native {
+        Endian::Little => *b"LTL-IEEE",
+        Endian::Big => *b"BIG-IEEE",
+    };
+    swapped[..FileRecord::SIZE].copy_from_slice(file_record.as_bytes());
+
+    let (nd, ni) = (file_record.nd(), file_record.ni());
+    let summary_size = file_record.summary_size() * DBL_SIZE;
+    if summary_size == 0 || ni < 2 {
+        return Ok(Some(swapped));
+    }
+    let max_summaries = (RCRD_LEN - SummaryRecord::SIZE) / summary_size;
+
+    // Summary records, which are a linked list starting from the forward record
+    let mut arrays = Vec::new();
+    let mut rcrd = file_record.fwrd_idx();
+    // Guard against loops in corrupted files
+    let mut remaining = bytes.len() / RCRD_LEN;
+    while rcrd > 0 && remaining > 0 {
+        remaining -= 1;
+
+        let start = (rcrd - 1) * RCRD_LEN;
+        let record = swapped
+            .get_mut(start..start + RCRD_LEN)
+            .ok_or(DecodingError::InaccessibleBytes {
+                start,
+                end: start + RCRD_LEN,
+                size: bytes.len(),
+            })
+            .map_err(|source| DAFError::DecodingSummary { kind, source })?;
+
+        swap_words(&mut record[..SummaryRecord::SIZE], DBL_SIZE);
+        let summary_rcrd = SummaryRecord::read_from_bytes(&record[..SummaryRecord::SIZE]).unwrap();
+
+        for sno in 0..summary_rcrd.num_summaries().min(max_summaries) {
+            let summary = &mut record[SummaryRecord::SIZE + sno * summary_size..][..summary_size];
+            let (doubles, ints) = summary.split_at_mut(nd * DBL_SIZE);
+            swap_words(doubles, DBL_SIZE);
+            swap_words(&mut ints[..ni * INT_SIZE], INT_SIZE);
+
+            // The last two integers are the initial and final addresses of the array
+            let address = |n: usize| {
+                i32::from_ne_bytes(ints[n * INT_SIZE..(n + 1) * INT_SIZE].try_into().unwrap())
+            };
+            arrays.push((address(ni - 2), address(ni - 1)));
+        }
+
+        rcrd = summary_rcrd.next_record();
+    }
+
+    // Data arrays, which only contain doubles
+    for (idx, (start_addr, end_addr)) in arrays.into_iter().enumerate() {
+        let start = (start_addr.max(1) as usize - 1) * DBL_SIZE;
+        let end = end_addr.max(0) as usize * DBL_SIZE;
+        let data = swapped
+            .get_mut(start..end)
+            .ok_or(DecodingError::InaccessibleBytes {
+                start,
+                end,
+                size: bytes.len(),
+            })
+            .map_err(|source| DAFError::DecodingData { kind, idx, source })?;
+        swap_words(data, DBL_SIZE);
+    }
+
+    Ok(Some(swapped))
+}
+
+/// Reverses the bytes of each word of the provided size.
+fn swap_words(bytes: &mut [u8], word_size: usize) {
+    for word in bytes.chunks_exact_mut(word_size) {
+        word.reverse();
+    }
+}
+
+#[cfg(test)]
+mod byte_swap_ut {
+    use super::to_native_endian;
+    use crate::file2heap;
+
+    #[test]
+    fn native_is_unchanged() {
+        let bytes = file2heap!("../data/gmat-hermite.bsp").unwrap();
+        assert!(to_native_endian(&bytes, "SPK").unwrap().is_none());
+    }
+
+    #[test]
+    fn big_endian_to_native() {
+        let little = file2heap!("../data/gmat-hermite.bsp").unwrap();
+        let big = file2heap!("../data/gmat-hermite-big-endian.bsp").unwrap();
+
+        let swapped = to_native_endian(&big, "SPK").unwrap().unwrap();
+        // The swapped file is now in the native endianness
+        assert_eq!(to_native_endian(&swapped, "SPK").unwrap(), None);
+        assert_eq!(swapped.len(), little.len());
+    }
+}
@@ -8,22 +8,23 @@
  * Documentation: https://nyxspace.com/
  */
 
+use super::byte_swap::to_native_endian;
 use super::file_record::FileRecordError;
 use super::{
     DAFError, DecodingNameSnafu, DecodingSummarySnafu, FileRecordSnafu, IOSnafu, NAIFDataSet,
-    NAIFRecord, NAIFSummaryRecord,
+    NAIFRecord, NAIFSummaryRecord, RecordIndexCache,
 };
 pub use super::{FileRecord, NameRecord, SummaryRecord};
 use crate::errors::DecodingError;
-use crate::file2heap;
 use crate::naif::daf::DecodingDataSnafu;
 use crate::{errors::IntegrityError, DBL_SIZE};
+use crate::{file2heap, file2mmap};
 use bytes::{Bytes, BytesMut};
 use core::fmt::Debug;
 use core::hash::Hash;
 use core::marker::PhantomData;
 use core::ops::Deref;
-use hifitime::{Epoch, Unit};
+use hifitime::Epoch;
 use log::{debug, error, trace};
 use snafu::ResultExt;
 
@@ -46,6 +47,8 @@ pub(crate) const RCRD_LEN: usize = 1024;
 pub struct GenericDAF<R: NAIFSummaryRecord, W: MutKind> {
     pub bytes: W,
     pub crc32_checksum: u32,
+    /// Index of the record last used in each segment, which speeds up sequential queries
+    pub(crate) record_cache: RecordIndexCache,
     pub _daf_type: PhantomData<R>,
 }
 
@@ -112,7 +115,7 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
     }
 
     pub fn daf_summary(&self) -> Result<SummaryRecord, DAFError> {
-        let rcrd_idx = (self.file_record()?.fwrd_idx() - 1) * RCRD_LEN;
+        let rcrd_idx = self.file_record()?.fwrd_idx().saturating_sub(1) * RCRD_LEN;
         let rcrd_bytes = self
             .bytes
             .get(rcrd_idx..rcrd_idx + RCRD_LEN)
@@ -138,7 +141,7 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
         }
 
         // Move onto the next record, DAF indexes start at 1 ... =(
-        let rcrd_idx = (self.file_record()?.fwrd_idx() - 1) * RCRD_LEN;
+        let rcrd_idx = self.file_record()?.fwrd_idx().saturating_sub(1) * RCRD_LEN;
         let rcrd_bytes = match self
             .bytes
             .get(rcrd_idx..rcrd_idx + RCRD_LEN)
@@ -185,9 +188,7 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
     ) -> Result<(&R, usize), DAFError> {
         let (summary, idx) = self.summary_from_name(name)?;
 
-        if epoch >= summary.start_epoch() - Unit::Nanosecond * 100
-            && epoch <= summary.end_epoch() + Unit::Nanosecond * 100
-        {
+        if summary.covers_epoch(epoch) {
             Ok((summary, idx))
         } else {
             error!("No summary {name} valid at epoch {epoch}");
@@ -216,9 +217,7 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
         // so we can't just call `summary_from_id`.
         for (idx, summary) in self.data_summaries()?.iter().enumerate() {
             if summary.id() == id {
-                if epoch >= summary.start_epoch() - Unit::Nanosecond * 100
-                    && epoch <= summary.end_epoch() + Unit::Nanosecond * 100
-                {
+                if summary.covers_epoch(epoch) {
                     trace!("Found {id} in position {idx}: {summary:?}");
                     return Ok((summary, idx));
                 } else {
@@ -259,14 +258,20 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
 
     /// Provided a name that is in the summary, return its full data, if name is available.
     pub fn nth_data<'a, S: NAIFDataSet<'a>>(&'a self, idx: usize) -> Result<S, DAFError> {
+        let data = self.nth_data_raw(idx)?;
+        // Convert it
+        S::from_f64_slice(data).context(DecodingDataSnafu { kind: R::NAME, idx })
+    }
+
+    /// Returns the raw data of the n-th segment as a slice of doubles, without decoding it.
+    ///
+    /// The doubles are in the endianness of this platform: files of the other endianness are swapped when loaded.
+    pub fn nth_data_raw(&self, idx: usize) -> Result<&[f64], DAFError> {
         let this_summary = self
             .data_summaries()?
             .get(idx)
-            .ok_or(DAFError::InvalidIndex {
-                idx,
-                kind: S::DATASET_NAME,
-            })?;
-        // Grab the data in native endianness (TODO: How to support both big and little endian?)
+            .ok_or(DAFError::InvalidIndex { idx, kind: R::NAME })?;
+        // The bytes were checked or swapped to the native endianness on load, so they can be read in place
         trace!("{idx} -> {this_summary:?}");
         if self.file_record()?.is_empty() {
             return Err(DAFError::FileRecord {
@@ -275,9 +280,12 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
             });
         }
 
-        let start = (this_summary.start_index() - 1) * DBL_SIZE;
-        let end = this_summary.end_index() * DBL_SIZE;
-        let data: &[f64] = Ref::into_ref(
+        let start = this_summary
+            .start_index()
+            .saturating_sub(1)
+            .saturating_mul(DBL_SIZE);
+        let end = this_summary.end_index().saturating_mul(DBL_SIZE);
+        Ok(Ref::into_ref(
             Ref::<&[u8], [f64]>::from_bytes(
                 match self
                     .bytes
@@ -298,10 +306,13 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
                 },
             )
             .unwrap(),
-        );
+        ))
+    }
 
-        // Convert it
-        S::from_f64_slice(data).context(DecodingDataSnafu { kind: R::NAME, idx })
+    /// Returns the name of the n-th segment
+    pub fn nth_name(&self, idx: usize) -> Result<String, DAFError> {
+        let summary_size = self.file_record()?.summary_size();
+        Ok(self.name_record()?.nth_name(idx, summary_size).to_string())
     }
 
     pub fn comments(&self) -> Result<Option<String>, DAFError> {
@@ -351,13 +362,9 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
     pub fn persist<P: AsRef<Path>>(&self, path: P) -> IoResult<()> {
         let mut fs = File::create(path)?;
 
-        let mut file_rcrd = Vec::from(self.file_record().unwrap().as_bytes());
-        file_rcrd.extend(vec![
-            0x0;
-            (self.file_record().unwrap().fwrd_idx() - 1) * RCRD_LEN
-                - file_rcrd.len()
-        ]);
-        fs.write_all(&file_rcrd)?;
+        // Write the file record and the comment records as is.
+        let fwrd_idx = self.file_record().unwrap().fwrd_idx();
+        fs.write_all(&self.bytes[..(fwrd_idx - 1) * RCRD_LEN])?;
 
         let mut daf_summary = Vec::from(self.daf_summary().unwrap().as_bytes());
         // Extend with the data summaries
@@ -372,7 +379,8 @@ impl<R: NAIFSummaryRecord, W: MutKind> GenericDAF<R, W> {
         name_rcrd.extend(vec![0x0; RCRD_LEN - name_rcrd.len()]);
         fs.write_all(&name_rcrd)?;
 
-        fs.write_all(&self.bytes[self.file_record().unwrap().fwrd_idx() * (2 * RCRD_LEN)..])
+        // The data starts right after the name record.
+        fs.write_all(&self.bytes[(fwrd_idx + 1) * RCRD_LEN..])
     }
 }
 
@@ -385,11 +393,46 @@ impl<R: NAIFSummaryRecord, W: MutKind> Hash for GenericDAF<R, W> {
 
 impl<R: NAIFSummaryRecord> DAF<R> {
     /// Parse the provided bytes as a SPICE Double Array File
+    ///
+    /// Files written in the other endianness (e.g. legacy big endian kernels) are swapped to the endianness of this platform.
     pub fn parse<B: Deref<Target = [u8]>>(bytes: B) -> Result<Self, DAFError> {
+        let bytes = match to_native_endian(&bytes, R::NAME)? {
+            Some(swapped) => Bytes::from(swapped),
+            None => Bytes::copy_from_slice(&bytes),
+        };
         let crc32_checksum = crc32fast::hash(&bytes);
         let me = Self {
-            bytes: Bytes::copy_from_slice(&bytes),
+            bytes,
             crc32_checksum,
+            record_cache: RecordIndexCache::default(),
+            _daf_type: PhantomData,
+        };
+        // Check that these calls will succeed.
+        me.file_record()?;
+        me.name_record()?;
+        Ok(me)
+    }
+
+    /// Parse the provided bytes as a SPICE Double Array File without copying them nor computing their checksum.
+    ///
+    /// When the bytes are memory mapped (e.g. with `load_lazy`), the summaries and the records are only read from disk when queried,
+    /// so the resident memory is bounded by the data actually used instead of the size of the file.
+    ///
+    /// # Integrity
+    /// Computing the CRC32 would read the whole file, so the checksum is set to zero: `scrub` will fail unless
+    /// `crc32_checksum` is first set to the value of `crc32()`.
+    ///
+    /// # Endianness
+    /// Files written in the other endianness cannot be read in place, so they are swapped into a copy on the heap.
+    pub fn parse_lazy(bytes: Bytes) -> Result<Self, DAFError> {
+        let bytes = match to_native_endian(&bytes, R::NAME)? {
+            Some(swapped) => Bytes::from(swapped),
+            None => bytes,
+        };
+        let me = Self {
+            bytes,
+            crc32_checksum: 0,
+            record_cache: RecordIndexCache::default(),
             _daf_type: PhantomData,
         };
         // Check that these calls will succeed.
@@ -421,6 +464,19 @@ impl<R: NAIFSummaryRecord> DAF<R> {
         Self::parse(bytes)
     }
 
+    /// Memory maps the provided path and parses it lazily, cf. `parse_lazy`.
+    ///
+    /// # Safety
+    /// The file must not be modified nor truncated while this DAF or any of its clones is in use, as its data is read from disk
+    /// on each query: a truncated file raises SIGBUS on access, and a rewritten file changes the data under this DAF.
+    pub unsafe fn load_lazy(path: &str) -> Result<Self, DAFError> {
+        let bytes = file2mmap!(path).context(IOSnafu {
+            action: format!("memory mapping {path:?}"),
+        })?;
+
+        Self::parse_lazy(bytes)
+    }
+
     /// Parse the provided static byte array as a SPICE Double Array File
     pub fn from_static<B: Deref<Target = [u8]>>(bytes: &'static B) -> Result<Self, DAFError> {
         Self::parse(Bytes::from_static(bytes))
@@ -431,6 +487,7 @@ impl<R: NAIFSummaryRecord> DAF<R> {
         MutDAF {
             bytes: BytesMut::from_iter(&self.bytes),
             crc32_checksum: self.crc32_checksum,
+            record_cache: RecordIndexCache::default(),
             _daf_type: PhantomData,
         }
     }
@@ -438,18 +495,64 @@ impl<R: NAIFSummaryRecord> DAF<R> {
 
 #[cfg(test)]
 mod daf_ut {
-    use hifitime::Epoch;
+    use hifitime::{Duration, Epoch};
 
     use crate::{
         errors::IntegrityError,
         file2heap,
         naif::{
-            daf::{datatypes::HermiteSetType13, file_record::FileRecordError, DAFError},
-            BPC,
+            daf::{
+                datatypes::HermiteSetType13, file_record::FileRecordError, DAFError, NAIFDataSet,
+                NAIFSummaryRecord,
+            },
+            MutSPK,
         },
         prelude::SPK,
     };
 
+    #[test]
+    fn lazy_load() {
+        let traj = SPK::load("../data/gmat-hermite.bsp").unwrap();
+        let mut lazy_traj = unsafe { SPK::load_lazy("../data/gmat-hermite.bsp") }.unwrap();
+
+        assert_eq!(traj.bytes, lazy_traj.bytes);
+        assert_eq!(
+            traj.data_summaries().unwrap(),
+            lazy_traj.data_summaries().unwrap()
+        );
+        assert!(
+            traj.nth_data::<HermiteSetType13>(0).unwrap()
+                == lazy_traj.nth_data::<HermiteSetType13>(0).unwrap()
+        );
+
+        // The checksum is not computed when lazily loading
+        assert!(lazy_traj.scrub().is_err());
+        lazy_traj.crc32_checksum = lazy_traj.crc32();
+        assert!(lazy_traj.scrub().is_ok());
+        assert_eq!(lazy_traj.crc32_checksum, traj.crc32_checksum);
+    }
+
+    #[test]
+    fn record_index_cache() {
+        let traj = SPK::load("../data/gmat-hermite.bsp").unwrap();
+        let summary = traj.data_summaries().unwrap()[0];
+        let data = traj.nth_data::<HermiteSetType13>(0).unwrap();
+
+        let (start, end) = (summary.start_epoch(), summary.end_epoch());
+        let step = Duration::from_seconds((end - start).to_seconds() / 1000.0);
+
+        let mut epoch = start;
+        while epoch <= end {
+            let hint = traj.record_cache.get(0);
+            let (state, next_hint) = data.evaluate_with_hint(epoch, &summary, hint).unwrap();
+            traj.record_cache.set(0, next_hint);
+
+            assert_eq!(state, data.evaluate(epoch, &summary).unwrap());
+            epoch += step;
+        }
+        assert!(traj.record_cache.get(0).is_some());
+    }
+
     #[test]
     fn crc32_errors() {
         let mut traj = SPK::load("../data/gmat-hermite.bsp").unwrap();
@@ -514,21 +617,39 @@ mod daf_ut {
 
     #[test]
     fn load_big_endian() {
-        // Ensure this fails
+        // Big endian files are swapped to the native endianness on load
+        let little = SPK::load("../data/gmat-hermite.bsp").unwrap();
+        let big = SPK::load("../data/gmat-hermite-big-endian.bsp").unwrap();
+
+        assert!(big.file_record().unwrap().endianness().is_ok());
+        assert_eq!(big.nth_name(0).unwrap(), little.nth_name(0).unwrap());
         assert_eq!(
-            SPK::load("../data/gmat-hermite-big-endian.bsp"),
-            Err(DAFError::FileRecord {
-                kind: "SPKSummaryRecord",
-                source: FileRecordError::WrongEndian
-            })
+            big.data_summaries().unwrap(),
+            little.data_summaries().unwrap()
         );
+        assert!(
+            big.nth_data::<HermiteSetType13>(0).unwrap()
+                == little.nth_data::<HermiteSetType13>(0).unwrap()
+        );
+        assert!(big.scrub().is_ok());
 
-        // Now ensure the error is correctly printed
-        if let Err(e) = BPC::load("../data/gmat-hermite-big-endian.bsp") {
-            assert_eq!(
-                format!("{e}"),
-                "DAF/BPCSummaryRecord: file record issue: endian of file does not match the endian order of the machine".to_string()
-            );
-        }
+        // Also when lazily loaded
+        let lazy_big = unsafe { SPK::load_lazy("../data/gmat-hermite-big-endian.bsp") }.unwrap();
+        assert_eq!(lazy_big.bytes, big.bytes);
+
+        // And the mutable DAF
+        let mut_big = MutSPK::load("../data/gmat-hermite-big-endian.bsp").unwrap();
+        assert_eq!(&mut_big.bytes[..], &big.bytes[..]);
+
+        // Invalid endian flags are still reported
+        let mut invalid = little.bytes.to_vec();
+        invalid[88..96].copy_from_slice(b"MID-IEEE");
+        assert!(matches!(
+            SPK::parse(invalid.as_slice()),
+            Err(DAFError::FileRecord {
+                source: FileRecordError::InvalidEndian { .. },
+                ..
+            })
+        ));
     }
 }
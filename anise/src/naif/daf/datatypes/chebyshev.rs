@@ -13,14 +13,16 @@ use hifitime::{Duration, Epoch, TimeUnits};
 use snafu::{ensure, ResultExt};
 
 use crate::{
-    errors::{DecodingError, IntegrityError, TooFewDoublesSnafu},
+    errors::{DecodingError, IntegrityError, MathError, TooFewDoublesSnafu},
     math::{
         interpolation::{chebyshev_eval, InterpDecodingSnafu, InterpolationError},
         Vector3,
     },
-    naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFSummaryRecord},
+    naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFSummaryRecord, RecordDomain},
 };
 
+use super::refit::{refit_chebyshev, ChebyshevRefit};
+
 #[derive(PartialEq)]
 pub struct Type2ChebyshevSet<'a> {
     pub init_epoch: Epoch,
@@ -53,10 +55,54 @@ impl Type2ChebyshevSet<'_> {
 
         let window_duration_s = self.interval_length.to_seconds();
 
-        let ephem_start_delta_s = epoch.to_et_seconds() - summary.start_epoch_et_s();
+        // The records start at the initial epoch of the data set, which precedes the start of the summary if truncated.
+        let ephem_start_delta_s = epoch.to_et_seconds() - self.init_epoch.to_et_seconds();
 
         Ok(((ephem_start_delta_s / window_duration_s) as usize + 1).min(self.num_records))
     }
+
+    /// Re-fits this data set with records `interval_multiple` times longer, using the lowest degree whose maximum error with
+    /// respect to this data set is within `max_error` (e.g. in km for SPKs), to shrink it while bounding the loss of accuracy.
+    ///
+    /// The last record may extend beyond the end of this data set, where its polynomials are extrapolated.
+    pub fn reduce(
+        &self,
+        interval_multiple: usize,
+        max_error: f64,
+    ) -> Result<ChebyshevRefit, InterpolationError> {
+        let init_et_s = self.init_epoch.to_et_seconds();
+        let interval_s = self.interval_length.to_seconds();
+
+        refit_chebyshev(
+            self.init_epoch,
+            self.interval_length,
+            self.num_records,
+            self.degree(),
+            interval_multiple,
+            max_error,
+            false,
+            |et_s| {
+                let n = (((et_s - init_et_s) / interval_s).max(0.0) as usize)
+                    .min(self.num_records.saturating_sub(1));
+                let record = self.nth_record(n).context(InterpDecodingSnafu)?;
+                let radius_s = record.radius.to_seconds();
+                let normalized_time = (et_s - record.midpoint_et_s) / radius_s;
+                let epoch = Epoch::from_et_seconds(et_s);
+
+                let mut val = Vector3::zeros();
+                let mut rate = Vector3::zeros();
+                for (cno, coeffs) in [record.x_coeffs, record.y_coeffs, record.z_coeffs]
+                    .iter()
+                    .enumerate()
+                {
+                    (val[cno], rate[cno]) =
+                        chebyshev_eval(normalized_time, coeffs, radius_s, epoch, self.degree())?;
+                }
+
+                Ok((val, rate))
+            },
+        )
+    }
 }
 
 impl fmt::Display for Type2ChebyshevSet<'_> {
@@ -133,12 +179,25 @@ impl<'a> NAIFDataSet<'a> for Type2ChebyshevSet<'a> {
     }
 
     fn nth_record(&self, n: usize) -> Result<Self::RecordKind, DecodingError> {
+        // A record holds at least the midpoint, the radius, and one coefficient per component.
+        if self.rsize < 5 {
+            return Err(DecodingError::Integrity {
+                source: IntegrityError::InvalidValue {
+                    dataset: Self::DATASET_NAME,
+                    variable: "record size",
+                    value: self.rsize as f64,
+                    reason: "must be at least 5",
+                },
+            });
+        }
+        let start = n.saturating_mul(self.rsize);
+        let end = start.saturating_add(self.rsize);
         Ok(Self::RecordKind::from_slice_f64(
             self.record_data
-                .get(n * self.rsize..(n + 1) * self.rsize)
+                .get(start..end)
                 .ok_or(DecodingError::InaccessibleBytes {
-                    start: n * self.rsize,
-                    end: (n + 1) * self.rsize,
+                    start,
+                    end,
                     size: self.record_data.len(),
                 })?,
         ))
@@ -156,7 +215,7 @@ impl<'a> NAIFDataSet<'a> for Type2ChebyshevSet<'a> {
 
         // Now, build the X, Y, Z data from the record data.
         let record = self
-            .nth_record(spline_idx - 1)
+            .nth_record(spline_idx.saturating_sub(1))
             .context(InterpDecodingSnafu)?;
 
         let normalized_time = (epoch.to_et_seconds() - record.midpoint_et_s) / radius_s;
@@ -177,6 +236,24 @@ impl<'a> NAIFDataSet<'a> for Type2ChebyshevSet<'a> {
         Ok((state, rate))
     }
 
+    fn record_domain<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        summary: &S,
+    ) -> Result<RecordDomain, InterpolationError> {
+        let spline_idx = self.spline_idx(epoch, summary)?;
+
+        let record = self
+            .nth_record(spline_idx.saturating_sub(1))
+            .context(InterpDecodingSnafu)?;
+
+        Ok(RecordDomain {
+            index: spline_idx.saturating_sub(1),
+            midpoint: record.midpoint_epoch(),
+            radius: record.radius,
+        })
+    }
+
     fn check_integrity(&self) -> Result<(), IntegrityError> {
         // Verify that none of the data is invalid once when we load it.
         for val in self.record_data {
@@ -204,14 +281,23 @@ impl<'a> NAIFDataSet<'a> for Type2ChebyshevSet<'a> {
         };
 
         let end_idx = if let Some(end) = new_end {
-            self.spline_idx(end, summary)?
+            self.spline_idx(end, summary)? - 1
         } else {
             self.num_records - 1
         };
 
+        if start_idx > end_idx {
+            return Err(InterpolationError::InterpMath {
+                source: MathError::DomainError {
+                    value: (end_idx as f64) - (start_idx as f64),
+                    msg: "truncation would remove all of the records",
+                },
+            });
+        }
+
         self.record_data = &self.record_data[start_idx * self.rsize..(end_idx + 1) * self.rsize];
-        self.num_records = (self.record_data.len() / self.rsize) - 1;
-        self.init_epoch = self.nth_record(0).unwrap().midpoint_epoch() - 0.5 * self.interval_length;
+        self.num_records = end_idx - start_idx + 1;
+        self.init_epoch += self.interval_length * (start_idx as i64);
 
         Ok(self)
     }
@@ -293,6 +379,31 @@ mod chebyshev_ut {
         }
     }
 
+    #[test]
+    fn invalid_record_size() {
+        let dataset = Type2ChebyshevSet::from_f64_slice(&[0.0, 0.0, 1.0, 2.0, 1.0]).unwrap();
+        match dataset.nth_record(0) {
+            Ok(_) => panic!("test failed on invalid record size"),
+            Err(e) => {
+                assert_eq!(
+                    e,
+                    DecodingError::Integrity {
+                        source: IntegrityError::InvalidValue {
+                            dataset: "Chebyshev Type 2",
+                            variable: "record size",
+                            value: 2.0,
+                            reason: "must be at least 5",
+                        },
+                    }
+                );
+            }
+        }
+
+        // Huge record sizes must not overflow
+        let dataset = Type2ChebyshevSet::from_f64_slice(&[0.0, 0.0, 1.0, 1e300, 1.0]).unwrap();
+        assert!(dataset.nth_record(1).is_err());
+    }
+
     #[test]
     fn subnormal() {
         match Type2ChebyshevSet::from_f64_slice(&[0.0, f64::INFINITY, 0.0, 0.0, 0.0]) {
@@ -13,14 +13,19 @@ use hifitime::{Duration, Epoch, TimeUnits};
 use snafu::{ensure, ResultExt};
 
 use crate::{
-    errors::{DecodingError, IntegrityError, TooFewDoublesSnafu},
+    errors::{DecodingError, IntegrityError, MathError, TooFewDoublesSnafu},
     math::{
-        interpolation::{chebyshev_eval_poly, InterpDecodingSnafu, InterpolationError},
+        interpolation::{
+            chebyshev_eval_poly, chebyshev_fit, chebyshev_nodes, InterpDecodingSnafu,
+            InterpolationError,
+        },
         Vector3,
     },
-    naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFSummaryRecord},
+    naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFSummaryRecord, RecordDomain},
 };
 
+use super::refit::{refit_chebyshev, ChebyshevRefit};
+
 #[derive(PartialEq)]
 pub struct Type3ChebyshevSet<'a> {
     pub init_epoch: Epoch,
@@ -53,10 +58,133 @@ impl Type3ChebyshevSet<'_> {
 
         let window_duration_s = self.interval_length.to_seconds();
 
-        let ephem_start_delta_s = epoch.to_et_seconds() - summary.start_epoch_et_s();
+        // The records start at the initial epoch of the data set, which precedes the start of the summary if truncated.
+        let ephem_start_delta_s = epoch.to_et_seconds() - self.init_epoch.to_et_seconds();
 
         Ok(((ephem_start_delta_s / window_duration_s) as usize + 1).min(self.num_records))
     }
+
+    /// Returns the DAF array of this data set where each record is split into `splits` records of equal length, with the
+    /// same degree, e.g. to truncate a segment closer to the requested epochs.
+    ///
+    /// The polynomials of each record are exactly representable on its subintervals, so this is lossless to within round-off.
+    pub fn split_records(&self, splits: usize) -> Result<Vec<f64>, InterpolationError> {
+        self.resample(splits, self.degree())
+    }
+
+    /// Returns the DAF array of this data set where the polynomials of each record are re-fitted with the provided degree.
+    ///
+    /// Increasing the degree is lossless, but decreasing it truncates the polynomials and loses accuracy.
+    pub fn refit(&self, degree: usize) -> Result<Vec<f64>, InterpolationError> {
+        self.resample(1, degree)
+    }
+
+    /// Samples each record at the Chebyshev nodes of each of its `splits` subintervals and fits the provided degree.
+    fn resample(&self, splits: usize, degree: usize) -> Result<Vec<f64>, InterpolationError> {
+        if splits == 0 || degree == 0 {
+            return Err(InterpolationError::InterpMath {
+                source: MathError::DomainError {
+                    value: splits.min(degree) as f64,
+                    msg: "Chebyshev resampling requires at least one split and a degree of at least one",
+                },
+            });
+        }
+
+        let nodes = chebyshev_nodes(degree);
+        let rsize = 2 + 6 * (degree + 1);
+        let mut data = Vec::with_capacity(self.num_records * splits * rsize + 4);
+
+        for n in 0..self.num_records {
+            let record = self.nth_record(n).context(InterpDecodingSnafu)?;
+            let radius_s = record.radius.to_seconds();
+            let sub_radius_s = radius_s / splits as f64;
+
+            for split in 0..splits {
+                let sub_midpoint_et_s =
+                    record.midpoint_et_s - radius_s + (2 * split + 1) as f64 * sub_radius_s;
+                data.push(sub_midpoint_et_s);
+                data.push(sub_radius_s);
+
+                for coeffs in [
+                    record.x_coeffs,
+                    record.y_coeffs,
+                    record.z_coeffs,
+                    record.vx_coeffs,
+                    record.vy_coeffs,
+                    record.vz_coeffs,
+                ] {
+                    let values = nodes
+                        .iter()
+                        .map(|node| {
+                            let et_s = sub_midpoint_et_s + node * sub_radius_s;
+                            chebyshev_eval_poly(
+                                (et_s - record.midpoint_et_s) / radius_s,
+                                coeffs,
+                                Epoch::from_et_seconds(et_s),
+                                self.degree(),
+                            )
+                        })
+                        .collect::<Result<Vec<f64>, InterpolationError>>()?;
+                    data.extend(chebyshev_fit(&values));
+                }
+            }
+        }
+
+        data.push(self.init_epoch.to_et_seconds());
+        data.push(self.interval_length.to_seconds() / splits as f64);
+        data.push(rsize as f64);
+        data.push((self.num_records * splits) as f64);
+
+        Ok(data)
+    }
+
+    /// Re-fits this data set with records `interval_multiple` times longer, using the lowest degree whose maximum error with
+    /// respect to this data set is within `max_error` (e.g. in km for SPKs), to shrink it while bounding the loss of accuracy.
+    ///
+    /// The last record may extend beyond the end of this data set, where its polynomials are extrapolated.
+    pub fn reduce(
+        &self,
+        interval_multiple: usize,
+        max_error: f64,
+    ) -> Result<ChebyshevRefit, InterpolationError> {
+        let init_et_s = self.init_epoch.to_et_seconds();
+        let interval_s = self.interval_length.to_seconds();
+
+        refit_chebyshev(
+            self.init_epoch,
+            self.interval_length,
+            self.num_records,
+            self.degree(),
+            interval_multiple,
+            max_error,
+            true,
+            |et_s| {
+                let n = (((et_s - init_et_s) / interval_s).max(0.0) as usize)
+                    .min(self.num_records.saturating_sub(1));
+                let record = self.nth_record(n).context(InterpDecodingSnafu)?;
+                let radius_s = record.radius.to_seconds();
+                let normalized_time = (et_s - record.midpoint_et_s) / radius_s;
+                let epoch = Epoch::from_et_seconds(et_s);
+
+                let mut val = Vector3::zeros();
+                let mut rate = Vector3::zeros();
+                for (cno, coeffs) in [record.x_coeffs, record.y_coeffs, record.z_coeffs]
+                    .iter()
+                    .enumerate()
+                {
+                    val[cno] = chebyshev_eval_poly(normalized_time, coeffs, epoch, self.degree())?;
+                }
+                for (cno, coeffs) in [record.vx_coeffs, record.vy_coeffs, record.vz_coeffs]
+                    .iter()
+                    .enumerate()
+                {
+                    rate[cno] = chebyshev_eval_poly(normalized_time, coeffs, epoch, self.degree())?;
+                }
+
+                Ok((val, rate))
+            },
+        )
+    }
 }
 
 impl fmt::Display for Type3ChebyshevSet<'_> {
@@ -182,6 +310,24 @@ impl<'a> NAIFDataSet<'a> for Type3ChebyshevSet<'a> {
         Ok((state, rate))
     }
 
+    fn record_domain<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        summary: &S,
+    ) -> Result<RecordDomain, InterpolationError> {
+        let spline_idx = self.spline_idx(epoch, summary)?;
+
+        let record = self
+            .nth_record(spline_idx - 1)
+            .context(InterpDecodingSnafu)?;
+
+        Ok(RecordDomain {
+            index: spline_idx - 1,
+            midpoint: record.midpoint_epoch(),
+            radius: record.radius,
+        })
+    }
+
     fn check_integrity(&self) -> Result<(), IntegrityError> {
         // Verify that none of the data is invalid once when we load it.
         for val in self.record_data {
@@ -209,14 +355,23 @@ impl<'a> NAIFDataSet<'a> for Type3ChebyshevSet<'a> {
         };
 
         let end_idx = if let Some(end) = new_end {
-            self.spline_idx(end, summary)?
+            self.spline_idx(end, summary)? - 1
         } else {
             self.num_records - 1
         };
 
+        if start_idx > end_idx {
+            return Err(InterpolationError::InterpMath {
+                source: MathError::DomainError {
+                    value: (end_idx as f64) - (start_idx as f64),
+                    msg: "truncation would remove all of the records",
+                },
+            });
+        }
+
         self.record_data = &self.record_data[start_idx * self.rsize..(end_idx + 1) * self.rsize];
-        self.num_records = (self.record_data.len() / self.rsize) - 1;
-        self.init_epoch = self.nth_record(0).unwrap().midpoint_epoch() - 0.5 * self.interval_length;
+        self.num_records = end_idx - start_idx + 1;
+        self.init_epoch += self.interval_length * (start_idx as i64);
 
         Ok(self)
     }
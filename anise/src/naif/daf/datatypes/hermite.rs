@@ -19,11 +19,30 @@ use crate::math::interpolation::{
 use crate::naif::daf::NAIFSummaryRecord;
 use crate::{
     math::{cartesian::CartesianState, Vector3},
-    naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFRecord},
+    naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFRecord, RecordDomain},
     DBL_SIZE,
 };
 
-use super::posvel::PositionVelocityRecord;
+use super::{posvel::PositionVelocityRecord, search_epoch};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Selection of the window of samples used to interpolate a Hermite Type 13 segment between two of its records.
+///
+/// The window is always shifted to remain within the segment, so the policy only matters away from its boundaries.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "python", pyclass(eq, eq_int))]
+#[cfg_attr(feature = "python", pyo3(module = "anise"))]
+pub enum HermiteWindow {
+    /// Centers the window on the requested epoch (SPICE behavior)
+    #[default]
+    Centered,
+    /// Starts the window at the record preceding the requested epoch
+    Forward,
+    /// Ends the window at the record following the requested epoch
+    Backward,
+}
 
 #[derive(PartialEq)]
 pub struct HermiteSetType12<'a> {
@@ -153,6 +172,150 @@ impl HermiteSetType13<'_> {
     pub fn degree(&self) -> usize {
         2 * self.samples - 1
     }
+
+    /// Returns the index of the first record of the interpolation window for an epoch between the records `idx - 1` and `idx`.
+    ///
+    /// The window is shifted to remain within the records, and uses all of them if there are fewer records than samples.
+    pub fn window_start(&self, idx: usize, window: HermiteWindow) -> usize {
+        let samples = self.samples.min(self.num_records);
+        let first_idx = match window {
+            HermiteWindow::Centered => idx.saturating_sub(samples / 2),
+            HermiteWindow::Forward => idx.saturating_sub(1),
+            HermiteWindow::Backward => (idx + 1).saturating_sub(samples),
+        };
+        first_idx.min(self.num_records - samples)
+    }
+
+    /// Returns the domain of the interpolation window used to evaluate this segment at the provided epoch with the provided
+    /// window selection policy, or a domain of zero radius if the epoch matches one of the records.
+    pub fn record_domain_with_window(
+        &self,
+        epoch: Epoch,
+        window: HermiteWindow,
+    ) -> Result<RecordDomain, InterpolationError> {
+        self.check_coverage(epoch)?;
+        match search_epoch(self.epoch_data, epoch.to_et_seconds(), None) {
+            Ok(idx) => Ok(RecordDomain {
+                index: idx,
+                midpoint: Epoch::from_et_seconds(self.epoch_data[idx]),
+                radius: Duration::ZERO,
+            }),
+            Err(idx) => {
+                let first_idx = self.window_start(idx, window);
+                let last_idx = first_idx + self.samples.min(self.num_records) - 1;
+                let start_et_s = self.epoch_data[first_idx];
+                let end_et_s = self.epoch_data[last_idx];
+                Ok(RecordDomain {
+                    index: first_idx,
+                    midpoint: Epoch::from_et_seconds(0.5 * (start_et_s + end_et_s)),
+                    radius: (0.5 * (end_et_s - start_et_s)).seconds(),
+                })
+            }
+        }
+    }
+
+    /// Evaluates this segment at the provided epoch, starting the search of the records from the hint, and interpolating
+    /// with the provided window selection policy. Returns the state and the hint for the next query.
+    pub fn evaluate_with_window(
+        &self,
+        epoch: Epoch,
+        hint: Option<usize>,
+        window: HermiteWindow,
+    ) -> Result<((Vector3, Vector3), Option<usize>), InterpolationError> {
+        // Start by doing a binary search on the epoch registry to limit the search space in the total number of epochs.
+        // TODO: use the epoch registry to reduce the search space
+        // Check that we even have interpolation data for that time
+        self.check_coverage(epoch)?;
+        // Now, search the epochs themselves, starting from the hint if any.
+        match search_epoch(self.epoch_data, epoch.to_et_seconds(), hint) {
+            Ok(idx) => {
+                // Oh wow, this state actually exists, no interpolation needed!
+                Ok((
+                    self.nth_record(idx)
+                        .context(InterpDecodingSnafu)?
+                        .to_pos_vel(),
+                    Some(idx),
+                ))
+            }
+            Err(idx) => {
+                // We didn't find it, so let's build an interpolation here.
+                let first_idx = self.window_start(idx, window);
+                let samples = self.samples.min(self.num_records);
+                if samples > MAX_SAMPLES {
+                    return Err(InterpolationError::CorruptedData {
+                        what: "number of interpolation samples exceeds MAX_SAMPLES (32)",
+                    });
+                }
+                let last_idx = first_idx + samples;
+
+                // Statically allocated arrays of the maximum number of samples
+                let mut epochs = [0.0; MAX_SAMPLES];
+                let mut xs = [0.0; MAX_SAMPLES];
+                let mut ys = [0.0; MAX_SAMPLES];
+                let mut zs = [0.0; MAX_SAMPLES];
+                let mut vxs = [0.0; MAX_SAMPLES];
+                let mut vys = [0.0; MAX_SAMPLES];
+                let mut vzs = [0.0; MAX_SAMPLES];
+                for (cno, idx) in (first_idx..last_idx).enumerate() {
+                    let record = self.nth_record(idx).context(InterpDecodingSnafu)?;
+                    xs[cno] = record.x_km;
+                    ys[cno] = record.y_km;
+                    zs[cno] = record.z_km;
+                    vxs[cno] = record.vx_km_s;
+                    vys[cno] = record.vy_km_s;
+                    vzs[cno] = record.vz_km_s;
+                    epochs[cno] = self.epoch_data[idx];
+                }
+
+                // TODO: Build a container that uses the underlying data and provides an index into it.
+
+                // Build the interpolation polynomials making sure to limit the slices to exactly the number of items we actually used
+                // The other ones are zeros, which would cause the interpolation function to fail.
+                let (x_km, vx_km_s) = hermite_eval(
+                    &epochs[..samples],
+                    &xs[..samples],
+                    &vxs[..samples],
+                    epoch.to_et_seconds(),
+                )?;
+
+                let (y_km, vy_km_s) = hermite_eval(
+                    &epochs[..samples],
+                    &ys[..samples],
+                    &vys[..samples],
+                    epoch.to_et_seconds(),
+                )?;
+
+                let (z_km, vz_km_s) = hermite_eval(
+                    &epochs[..samples],
+                    &zs[..samples],
+                    &vzs[..samples],
+                    epoch.to_et_seconds(),
+                )?;
+
+                // And build the result
+                let pos_km = Vector3::new(x_km, y_km, z_km);
+                let vel_km_s = Vector3::new(vx_km_s, vy_km_s, vz_km_s);
+
+                Ok(((pos_km, vel_km_s), Some(idx)))
+            }
+        }
+    }
+
+    fn check_coverage(&self, epoch: Epoch) -> Result<(), InterpolationError> {
+        let (Some(&first_et_s), Some(&last_et_s)) =
+            (self.epoch_data.first(), self.epoch_data.last())
+        else {
+            return Err(InterpolationError::MissingInterpolationData { epoch });
+        };
+        if epoch.to_et_seconds() < first_et_s - 1e-7 || epoch.to_et_seconds() > last_et_s + 1e-7 {
+            return Err(InterpolationError::NoInterpolationData {
+                req: epoch,
+                start: Epoch::from_et_seconds(first_et_s),
+                end: Epoch::from_et_seconds(last_et_s),
+            });
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for HermiteSetType13<'_> {
@@ -211,9 +374,10 @@ impl<'a> NAIFDataSet<'a> for HermiteSetType13<'a> {
             });
         }
 
-        let samples = num_samples_f64 as usize + 1;
+        let samples = (num_samples_f64 as usize).saturating_add(1);
         // NOTE: The ::SIZE returns the C representation memory size of this, but we only want the number of doubles.
-        let state_data_end_idx = PositionVelocityRecord::SIZE / DBL_SIZE * num_records;
+        let state_data_end_idx =
+            (PositionVelocityRecord::SIZE / DBL_SIZE).saturating_mul(num_records);
         let state_data =
             slice
                 .get(0..state_data_end_idx)
@@ -222,7 +386,7 @@ impl<'a> NAIFDataSet<'a> for HermiteSetType13<'a> {
                     end: state_data_end_idx,
                     size: slice.len(),
                 })?;
-        let epoch_data_end_idx = state_data_end_idx + num_records;
+        let epoch_data_end_idx = state_data_end_idx.saturating_add(num_records);
         let epoch_data = slice.get(state_data_end_idx..epoch_data_end_idx).ok_or(
             DecodingError::InaccessibleBytes {
                 start: state_data_end_idx,
@@ -249,13 +413,15 @@ impl<'a> NAIFDataSet<'a> for HermiteSetType13<'a> {
     }
 
     fn nth_record(&self, n: usize) -> Result<Self::RecordKind, DecodingError> {
-        let rcrd_len = self.state_data.len() / self.num_records;
+        let rcrd_len = PositionVelocityRecord::SIZE / DBL_SIZE;
+        let start = n.saturating_mul(rcrd_len);
+        let end = start.saturating_add(rcrd_len);
         Ok(Self::RecordKind::from_slice_f64(
             self.state_data
-                .get(n * rcrd_len..(n + 1) * rcrd_len)
+                .get(start..end)
                 .ok_or(DecodingError::InaccessibleBytes {
-                    start: n * rcrd_len,
-                    end: (n + 1) * rcrd_len,
+                    start,
+                    end,
                     size: self.state_data.len(),
                 })?,
         ))
@@ -264,97 +430,27 @@ impl<'a> NAIFDataSet<'a> for HermiteSetType13<'a> {
     fn evaluate<S: NAIFSummaryRecord>(
         &self,
         epoch: Epoch,
-        _: &S,
+        summary: &S,
     ) -> Result<Self::StateKind, InterpolationError> {
-        // Start by doing a binary search on the epoch registry to limit the search space in the total number of epochs.
-        // TODO: use the epoch registry to reduce the search space
-        // Check that we even have interpolation data for that time
-        if epoch.to_et_seconds() < self.epoch_data[0] - 1e-7
-            || epoch.to_et_seconds() > *self.epoch_data.last().unwrap() + 1e-7
-        {
-            return Err(InterpolationError::NoInterpolationData {
-                req: epoch,
-                start: Epoch::from_et_seconds(self.epoch_data[0]),
-                end: Epoch::from_et_seconds(*self.epoch_data.last().unwrap()),
-            });
-        }
-        // Now, perform a binary search on the epochs themselves.
-        match self.epoch_data.binary_search_by(|epoch_et| {
-            epoch_et
-                .partial_cmp(&epoch.to_et_seconds())
-                .expect("epochs in Hermite data is now NaN or infinite but was not before")
-        }) {
-            Ok(idx) => {
-                // Oh wow, this state actually exists, no interpolation needed!
-                Ok(self
-                    .nth_record(idx)
-                    .context(InterpDecodingSnafu)?
-                    .to_pos_vel())
-            }
-            Err(idx) => {
-                // We didn't find it, so let's build an interpolation here.
-                let num_left = self.samples / 2;
-
-                // Ensure that we aren't fetching out of the window
-                let mut first_idx = idx.saturating_sub(num_left);
-                let last_idx = self.num_records.min(first_idx + self.samples);
-
-                // Check that we have enough samples
-                if last_idx == self.num_records {
-                    first_idx = last_idx - 2 * num_left;
-                }
-
-                // Statically allocated arrays of the maximum number of samples
-                let mut epochs = [0.0; MAX_SAMPLES];
-                let mut xs = [0.0; MAX_SAMPLES];
-                let mut ys = [0.0; MAX_SAMPLES];
-                let mut zs = [0.0; MAX_SAMPLES];
-                let mut vxs = [0.0; MAX_SAMPLES];
-                let mut vys = [0.0; MAX_SAMPLES];
-                let mut vzs = [0.0; MAX_SAMPLES];
-                for (cno, idx) in (first_idx..last_idx).enumerate() {
-                    let record = self.nth_record(idx).context(InterpDecodingSnafu)?;
-                    xs[cno] = record.x_km;
-                    ys[cno] = record.y_km;
-                    zs[cno] = record.z_km;
-                    vxs[cno] = record.vx_km_s;
-                    vys[cno] = record.vy_km_s;
-                    vzs[cno] = record.vz_km_s;
-                    epochs[cno] = self.epoch_data[idx];
-                }
-
-                // TODO: Build a container that uses the underlying data and provides an index into it.
-
-                // Build the interpolation polynomials making sure to limit the slices to exactly the number of items we actually used
-                // The other ones are zeros, which would cause the interpolation function to fail.
-                let (x_km, vx_km_s) = hermite_eval(
-                    &epochs[..self.samples],
-                    &xs[..self.samples],
-                    &vxs[..self.samples],
-                    epoch.to_et_seconds(),
-                )?;
-
-                let (y_km, vy_km_s) = hermite_eval(
-                    &epochs[..self.samples],
-                    &ys[..self.samples],
-                    &vys[..self.samples],
-                    epoch.to_et_seconds(),
-                )?;
-
-                let (z_km, vz_km_s) = hermite_eval(
-                    &epochs[..self.samples],
-                    &zs[..self.samples],
-                    &vzs[..self.samples],
-                    epoch.to_et_seconds(),
-                )?;
+        self.evaluate_with_hint(epoch, summary, None)
+            .map(|(state, _)| state)
+    }
 
-                // And build the result
-                let pos_km = Vector3::new(x_km, y_km, z_km);
-                let vel_km_s = Vector3::new(vx_km_s, vy_km_s, vz_km_s);
+    fn evaluate_with_hint<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        _: &S,
+        hint: Option<usize>,
+    ) -> Result<(Self::StateKind, Option<usize>), InterpolationError> {
+        self.evaluate_with_window(epoch, hint, HermiteWindow::default())
+    }
 
-                Ok((pos_km, vel_km_s))
-            }
-        }
+    fn record_domain<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        _: &S,
+    ) -> Result<RecordDomain, InterpolationError> {
+        self.record_domain_with_window(epoch, HermiteWindow::default())
     }
 
     fn check_integrity(&self) -> Result<(), IntegrityError> {
@@ -397,7 +493,104 @@ mod hermite_ut {
         naif::daf::NAIFDataSet,
     };
 
-    use super::HermiteSetType13;
+    use super::{HermiteSetType13, HermiteWindow};
+    use hifitime::Epoch;
+
+    /// Builds the data of a Type 13 segment of x = t^2, y = t, z = 3 sampled at the provided epochs
+    fn quadratic_data(epochs: &[f64], samples: usize) -> Vec<f64> {
+        let mut data = Vec::new();
+        for t in epochs {
+            data.extend_from_slice(&[t * t, *t, 3.0, 2.0 * t, 1.0, 0.0]);
+        }
+        data.extend_from_slice(epochs);
+        data.push((samples - 1) as f64);
+        data.push(epochs.len() as f64);
+        data
+    }
+
+    #[test]
+    fn window_selection() {
+        let epochs = [0.0, 10.0, 25.0, 30.0, 50.0, 55.0, 70.0, 80.0, 85.0, 100.0];
+        let data = quadratic_data(&epochs, 4);
+        let dataset = HermiteSetType13::from_f64_slice(&data).unwrap();
+
+        // Between records 4 and 5
+        assert_eq!(dataset.window_start(5, HermiteWindow::Centered), 3);
+        assert_eq!(dataset.window_start(5, HermiteWindow::Forward), 4);
+        assert_eq!(dataset.window_start(5, HermiteWindow::Backward), 2);
+        // Windows are shifted to remain within the records
+        for window in [
+            HermiteWindow::Centered,
+            HermiteWindow::Forward,
+            HermiteWindow::Backward,
+        ] {
+            assert_eq!(dataset.window_start(0, window), 0);
+            assert_eq!(dataset.window_start(1, window), 0);
+            assert_eq!(dataset.window_start(9, window), 6);
+            assert_eq!(dataset.window_start(10, window), 6);
+
+            // Hermite interpolation of degree 7 is exact for this quadratic, including at and just beyond the boundaries
+            for t in [-5e-8, 0.0, 3.0, 27.5, 52.0, 97.0, 100.0, 100.0 + 5e-8] {
+                let ((pos, vel), _) = dataset
+                    .evaluate_with_window(Epoch::from_et_seconds(t), None, window)
+                    .unwrap();
+                assert!((pos.x - t * t).abs() < 1e-5, "{window:?} @ {t}: {pos}");
+                assert!((pos.y - t).abs() < 1e-9, "{window:?} @ {t}: {pos}");
+                assert!((vel.x - 2.0 * t).abs() < 1e-6, "{window:?} @ {t}: {vel}");
+            }
+
+            assert!(dataset
+                .evaluate_with_window(Epoch::from_et_seconds(100.1), None, window)
+                .is_err());
+        }
+
+        // Fewer records than samples uses all of the records
+        let data = quadratic_data(&epochs[..3], 8);
+        let dataset = HermiteSetType13::from_f64_slice(&data).unwrap();
+        assert_eq!(dataset.window_start(2, HermiteWindow::Backward), 0);
+        let ((pos, _), _) = dataset
+            .evaluate_with_window(Epoch::from_et_seconds(12.0), None, HermiteWindow::Forward)
+            .unwrap();
+        assert!((pos.x - 144.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn record_domain() {
+        let epochs = [0.0, 10.0, 25.0, 30.0, 50.0, 55.0, 70.0, 80.0, 85.0, 100.0];
+        let data = quadratic_data(&epochs, 4);
+        let dataset = HermiteSetType13::from_f64_slice(&data).unwrap();
+
+        // Centered window of records 3 to 6 between records 4 and 5
+        let epoch = Epoch::from_et_seconds(52.0);
+        let domain = dataset
+            .record_domain_with_window(epoch, HermiteWindow::Centered)
+            .unwrap();
+        assert_eq!(domain.index, 3);
+        assert_eq!(domain.start(), Epoch::from_et_seconds(30.0));
+        assert_eq!(domain.end(), Epoch::from_et_seconds(70.0));
+        assert!(domain.contains(epoch));
+        assert!((domain.normalized_time(epoch) - 0.1).abs() < 1e-12);
+
+        let domain = dataset
+            .record_domain_with_window(epoch, HermiteWindow::Forward)
+            .unwrap();
+        assert_eq!(
+            (domain.start(), domain.end()),
+            (Epoch::from_et_seconds(50.0), Epoch::from_et_seconds(80.0))
+        );
+
+        // No interpolation on a record
+        let domain = dataset
+            .record_domain_with_window(Epoch::from_et_seconds(25.0), HermiteWindow::Centered)
+            .unwrap();
+        assert_eq!(domain.index, 2);
+        assert_eq!(domain.radius, hifitime::Duration::ZERO);
+        assert_eq!(domain.normalized_time(Epoch::from_et_seconds(25.0)), 0.0);
+
+        assert!(dataset
+            .record_domain_with_window(Epoch::from_et_seconds(100.1), HermiteWindow::Centered)
+            .is_err());
+    }
 
     #[test]
     fn too_small() {
@@ -412,6 +605,19 @@ mod hermite_ut {
         }
     }
 
+    #[test]
+    fn corrupted_data() {
+        // No records at all: decoding succeeds but evaluation must not panic
+        let dataset = HermiteSetType13::from_f64_slice(&[0.0, 0.0, 0.0]).unwrap();
+        assert!(dataset.nth_record(0).is_err());
+        assert!(dataset
+            .evaluate_with_window(Epoch::from_et_seconds(0.0), None, HermiteWindow::Centered)
+            .is_err());
+
+        // Huge number of records and samples must not overflow
+        assert!(HermiteSetType13::from_f64_slice(&[0.0, 1e300, 1e300]).is_err());
+    }
+
     #[test]
     fn invalid_data() {
         // Two metadata, one state, one epoch
@@ -19,11 +19,11 @@ use crate::{
         interpolation::{lagrange_eval, InterpDecodingSnafu, InterpolationError, MAX_SAMPLES},
         Vector3,
     },
-    naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFRecord, NAIFSummaryRecord},
+    naif::daf::{NAIFDataRecord, NAIFDataSet, NAIFRecord, NAIFSummaryRecord, RecordDomain},
     DBL_SIZE,
 };
 
-use super::posvel::PositionVelocityRecord;
+use super::{posvel::PositionVelocityRecord, search_epoch};
 
 #[derive(PartialEq)]
 pub struct LagrangeSetType8<'a> {
@@ -159,6 +159,42 @@ impl fmt::Display for LagrangeSetType9<'_> {
     }
 }
 
+impl LagrangeSetType9<'_> {
+    /// Returns the first index and the end index (exclusive) of the records of the interpolation window for an epoch between
+    /// the records `idx - 1` and `idx`.
+    fn window_bounds(&self, idx: usize) -> (usize, usize) {
+        let group_size = self.degree.saturating_add(1);
+        let num_left = group_size / 2;
+
+        // Ensure that we aren't fetching out of the window
+        let mut first_idx = idx.saturating_sub(num_left);
+        let last_idx = self.num_records.min(first_idx + group_size);
+
+        // Check that we have enough samples
+        if last_idx == self.num_records {
+            first_idx = last_idx.saturating_sub(2 * num_left);
+        }
+
+        (first_idx, last_idx)
+    }
+
+    fn check_coverage(&self, epoch: Epoch) -> Result<(), InterpolationError> {
+        let (Some(&first_et_s), Some(&last_et_s)) =
+            (self.epoch_data.first(), self.epoch_data.last())
+        else {
+            return Err(InterpolationError::MissingInterpolationData { epoch });
+        };
+        if epoch.to_et_seconds() < first_et_s - 1e-7 || epoch.to_et_seconds() > last_et_s + 1e-7 {
+            return Err(InterpolationError::NoInterpolationData {
+                req: epoch,
+                start: Epoch::from_et_seconds(first_et_s),
+                end: Epoch::from_et_seconds(last_et_s),
+            });
+        }
+        Ok(())
+    }
+}
+
 impl<'a> NAIFDataSet<'a> for LagrangeSetType9<'a> {
     type StateKind = (Vector3, Vector3);
     type RecordKind = PositionVelocityRecord;
@@ -178,12 +214,32 @@ impl<'a> NAIFDataSet<'a> for LagrangeSetType9<'a> {
         let num_records = slice[slice.len() - 1] as usize;
         let degree = slice[slice.len() - 2] as usize;
         // NOTE: The ::SIZE returns the C representation memory size of this, but we only want the number of doubles.
-        let state_data_end_idx = PositionVelocityRecord::SIZE / DBL_SIZE * num_records;
-        let state_data = slice.get(0..state_data_end_idx).unwrap();
-        let epoch_data_end_idx = state_data_end_idx + num_records;
-        let epoch_data = slice.get(state_data_end_idx..epoch_data_end_idx).unwrap();
+        let state_data_end_idx =
+            (PositionVelocityRecord::SIZE / DBL_SIZE).saturating_mul(num_records);
+        let state_data =
+            slice
+                .get(0..state_data_end_idx)
+                .ok_or(DecodingError::InaccessibleBytes {
+                    start: 0,
+                    end: state_data_end_idx,
+                    size: slice.len(),
+                })?;
+        let epoch_data_end_idx = state_data_end_idx.saturating_add(num_records);
+        let epoch_data = slice.get(state_data_end_idx..epoch_data_end_idx).ok_or(
+            DecodingError::InaccessibleBytes {
+                start: state_data_end_idx,
+                end: epoch_data_end_idx,
+                size: slice.len(),
+            },
+        )?;
         // And the epoch directory is whatever remains minus the metadata
-        let epoch_registry = slice.get(epoch_data_end_idx..slice.len() - 2).unwrap();
+        let epoch_registry = slice.get(epoch_data_end_idx..slice.len() - 2).ok_or(
+            DecodingError::InaccessibleBytes {
+                start: epoch_data_end_idx,
+                end: slice.len() - 2,
+                size: slice.len(),
+            },
+        )?;
 
         Ok(Self {
             degree,
@@ -195,13 +251,15 @@ impl<'a> NAIFDataSet<'a> for LagrangeSetType9<'a> {
     }
 
     fn nth_record(&self, n: usize) -> Result<Self::RecordKind, DecodingError> {
-        let rcrd_len = self.state_data.len() / self.num_records;
+        let rcrd_len = PositionVelocityRecord::SIZE / DBL_SIZE;
+        let start = n.saturating_mul(rcrd_len);
+        let end = start.saturating_add(rcrd_len);
         Ok(Self::RecordKind::from_slice_f64(
             self.state_data
-                .get(n * rcrd_len..(n + 1) * rcrd_len)
+                .get(start..end)
                 .ok_or(DecodingError::InaccessibleBytes {
-                    start: n * rcrd_len,
-                    end: (n + 1) * rcrd_len,
+                    start,
+                    end,
                     size: self.state_data.len(),
                 })?,
         ))
@@ -210,46 +268,42 @@ impl<'a> NAIFDataSet<'a> for LagrangeSetType9<'a> {
     fn evaluate<S: NAIFSummaryRecord>(
         &self,
         epoch: Epoch,
-        _: &S,
+        summary: &S,
     ) -> Result<Self::StateKind, InterpolationError> {
+        self.evaluate_with_hint(epoch, summary, None)
+            .map(|(state, _)| state)
+    }
+
+    fn evaluate_with_hint<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        _: &S,
+        hint: Option<usize>,
+    ) -> Result<(Self::StateKind, Option<usize>), InterpolationError> {
         // Start by doing a binary search on the epoch registry to limit the search space in the total number of epochs.
         // TODO: use the epoch registry to reduce the search space
         // Check that we even have interpolation data for that time
-        if epoch.to_et_seconds() < self.epoch_data[0] - 1e-7
-            || epoch.to_et_seconds() > *self.epoch_data.last().unwrap() + 1e-7
-        {
-            return Err(InterpolationError::NoInterpolationData {
-                req: epoch,
-                start: Epoch::from_et_seconds(self.epoch_data[0]),
-                end: Epoch::from_et_seconds(*self.epoch_data.last().unwrap()),
-            });
-        }
-        // Now, perform a binary search on the epochs themselves.
-        match self.epoch_data.binary_search_by(|epoch_et| {
-            epoch_et
-                .partial_cmp(&epoch.to_et_seconds())
-                .expect("epochs in Hermite data is now NaN or infinite but was not before")
-        }) {
+        self.check_coverage(epoch)?;
+        // Now, search the epochs themselves, starting from the hint if any.
+        match search_epoch(self.epoch_data, epoch.to_et_seconds(), hint) {
             Ok(idx) => {
                 // Oh wow, this state actually exists, no interpolation needed!
-                Ok(self
-                    .nth_record(idx)
-                    .context(InterpDecodingSnafu)?
-                    .to_pos_vel())
+                Ok((
+                    self.nth_record(idx)
+                        .context(InterpDecodingSnafu)?
+                        .to_pos_vel(),
+                    Some(idx),
+                ))
             }
             Err(idx) => {
                 // We didn't find it, so let's build an interpolation here.
-                let group_size = self.degree + 1;
-                let num_left = group_size / 2;
-
-                // Ensure that we aren't fetching out of the window
-                let mut first_idx = idx.saturating_sub(num_left);
-                let last_idx = self.num_records.min(first_idx + group_size);
-
-                // Check that we have enough samples
-                if last_idx == self.num_records {
-                    first_idx = last_idx - 2 * num_left;
+                let group_size = self.degree.saturating_add(1);
+                if group_size > MAX_SAMPLES {
+                    return Err(InterpolationError::CorruptedData {
+                        what: "degree of the interpolation exceeds MAX_SAMPLES (32)",
+                    });
                 }
+                let (first_idx, last_idx) = self.window_bounds(idx);
 
                 // Statically allocated arrays of the maximum number of samples
                 let mut epochs = [0.0; MAX_SAMPLES];
@@ -315,7 +369,32 @@ impl<'a> NAIFDataSet<'a> for LagrangeSetType9<'a> {
                 let pos_km = Vector3::new(x_km, y_km, z_km);
                 let vel_km_s = Vector3::new(vx_km_s, vy_km_s, vz_km_s);
 
-                Ok((pos_km, vel_km_s))
+                Ok(((pos_km, vel_km_s), Some(idx)))
+            }
+        }
+    }
+
+    fn record_domain<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        _: &S,
+    ) -> Result<RecordDomain, InterpolationError> {
+        self.check_coverage(epoch)?;
+        match search_epoch(self.epoch_data, epoch.to_et_seconds(), None) {
+            Ok(idx) => Ok(RecordDomain {
+                index: idx,
+                midpoint: Epoch::from_et_seconds(self.epoch_data[idx]),
+                radius: Duration::ZERO,
+            }),
+            Err(idx) => {
+                let (first_idx, last_idx) = self.window_bounds(idx);
+                let start_et_s = self.epoch_data[first_idx];
+                let end_et_s = self.epoch_data[last_idx - 1];
+                Ok(RecordDomain {
+                    index: first_idx,
+                    midpoint: Epoch::from_et_seconds(0.5 * (start_et_s + end_et_s)),
+                    radius: (0.5 * (end_et_s - start_et_s)).seconds(),
+                })
             }
         }
     }
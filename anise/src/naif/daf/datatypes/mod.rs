@@ -13,8 +13,59 @@ pub mod chebyshev3;
 pub mod hermite;
 pub mod lagrange;
 pub mod posvel;
+pub mod refit;
 
 pub use chebyshev::*;
 pub use chebyshev3::*;
 pub use hermite::*;
 pub use lagrange::*;
+pub use refit::ChebyshevRefit;
+
+/// Searches the sorted epochs (in ET seconds) for the provided epoch, starting with the provided hint, e.g. the index returned by the previous search.
+///
+/// Like `binary_search`, returns `Ok(idx)` if the epoch is exactly the idx-th epoch, or `Err(idx)` where the epoch would be inserted.
+/// If the hint or the index following it match, the result is found in constant time, otherwise this falls back to a binary search.
+pub(crate) fn search_epoch(
+    epoch_data: &[f64],
+    epoch_et: f64,
+    hint: Option<usize>,
+) -> Result<usize, usize> {
+    if let Some(hint) = hint {
+        // Sequential queries typically hit the same interval or the next one.
+        for idx in [hint, hint + 1] {
+            match epoch_data.get(idx) {
+                Some(&epoch) if epoch == epoch_et => return Ok(idx),
+                Some(&epoch) if epoch > epoch_et && idx > 0 && epoch_data[idx - 1] < epoch_et => {
+                    return Err(idx)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    epoch_data.binary_search_by(|epoch| {
+        epoch
+            .partial_cmp(&epoch_et)
+            .expect("epochs in interpolation data is now NaN or infinite but was not before")
+    })
+}
+
+#[cfg(test)]
+mod ut_search_epoch {
+    use super::search_epoch;
+
+    #[test]
+    fn with_hint() {
+        let epochs = [0.0, 10.0, 20.0, 30.0, 40.0];
+        for epoch_et in [-1.0, 0.0, 5.0, 10.0, 15.0, 39.0, 40.0, 41.0] {
+            let expected = search_epoch(&epochs, epoch_et, None);
+            for hint in [None, Some(0), Some(1), Some(2), Some(4), Some(5), Some(99)] {
+                assert_eq!(
+                    search_epoch(&epochs, epoch_et, hint),
+                    expected,
+                    "{epoch_et} with {hint:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use hifitime::{Duration, Epoch};
+
+use crate::errors::MathError;
+use crate::math::interpolation::{
+    chebyshev_eval, chebyshev_eval_poly, chebyshev_fit, chebyshev_nodes, InterpolationError,
+};
+use crate::math::Vector3;
+
+/// Number of samples per coefficient used to check the error of each re-fitted record.
+const CHECK_SAMPLES_PER_COEFF: usize = 8;
+
+/// DAF array of a Chebyshev data set re-fitted with longer records or a lower degree, and its maximum errors with respect to
+/// the original data set, in the units of the data set (e.g. km and km/s for SPKs, radians and radians per second for BPCs).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChebyshevRefit {
+    /// DAF array of the re-fitted data set, to be decoded with the same data type as the original one
+    pub data: Vec<f64>,
+    pub degree: usize,
+    pub interval_length: Duration,
+    pub num_records: usize,
+    /// Maximum error of the re-fitted values, sampled several times per coefficient in each record
+    pub max_error: f64,
+    /// Maximum error of the re-fitted rates, sampled like the values
+    pub max_rate_error: f64,
+}
+
+impl fmt::Display for ChebyshevRefit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} records of {} with degree {}: max error {:.3e}, max rate error {:.3e}",
+            self.num_records,
+            self.interval_length,
+            self.degree,
+            self.max_error,
+            self.max_rate_error
+        )
+    }
+}
+
+/// Re-fits the Chebyshev data set starting at `init_epoch` with `num_records` records of `interval_length` with records
+/// `interval_multiple` times longer, using the lowest degree whose maximum error is within `max_error`.
+///
+/// The `eval` function returns the value and rate of the original data set at the provided ET seconds, extrapolating the
+/// polynomials of its first and last records if needed. If `fit_rates` is set, the rates are fitted with their own
+/// polynomials (Type 3), otherwise they are the derivatives of the fitted values (Type 2). The degree is bounded such that
+/// the re-fitted data set is never larger than the original one.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn refit_chebyshev<F>(
+    init_epoch: Epoch,
+    interval_length: Duration,
+    num_records: usize,
+    degree: usize,
+    interval_multiple: usize,
+    max_error: f64,
+    fit_rates: bool,
+    mut eval: F,
+) -> Result<ChebyshevRefit, InterpolationError>
+where
+    F: FnMut(f64) -> Result<(Vector3, Vector3), InterpolationError>,
+{
+    if interval_multiple == 0 || max_error.is_nan() || max_error <= 0.0 {
+        return Err(InterpolationError::InterpMath {
+            source: MathError::DomainError {
+                value: max_error,
+                msg: "Chebyshev re-fitting requires a positive interval multiple and maximum error",
+            },
+        });
+    }
+
+    let init_et_s = init_epoch.to_et_seconds();
+    let end_et_s = init_et_s + interval_length.to_seconds() * num_records as f64;
+    let new_interval_s = interval_length.to_seconds() * interval_multiple as f64;
+    let radius_s = new_interval_s / 2.0;
+    let new_num_records = num_records.div_ceil(interval_multiple);
+    let num_comps = if fit_rates { 6 } else { 3 };
+
+    let mut lowest_error = f64::INFINITY;
+    for new_degree in 1..interval_multiple * (degree + 1) {
+        let nodes = chebyshev_nodes(new_degree);
+        let rsize = 2 + num_comps * (new_degree + 1);
+        let num_checks = CHECK_SAMPLES_PER_COEFF * (new_degree + 1);
+
+        let mut data = Vec::with_capacity(new_num_records * rsize + 4);
+        let mut max_err = 0.0_f64;
+        let mut max_rate_err = 0.0_f64;
+
+        for n in 0..new_num_records {
+            let midpoint_et_s = init_et_s + (n as f64 + 0.5) * new_interval_s;
+
+            let samples = nodes
+                .iter()
+                .map(|node| eval(midpoint_et_s + node * radius_s))
+                .collect::<Result<Vec<(Vector3, Vector3)>, InterpolationError>>()?;
+
+            let mut coeffs = Vec::with_capacity(num_comps);
+            for comp in 0..num_comps {
+                let values = samples
+                    .iter()
+                    .map(|(val, rate)| if comp < 3 { val[comp] } else { rate[comp - 3] })
+                    .collect::<Vec<f64>>();
+                coeffs.push(chebyshev_fit(&values));
+            }
+
+            // Check the record against the original data set where the latter is defined
+            let start_et_s = midpoint_et_s - radius_s;
+            let stop_et_s = (midpoint_et_s + radius_s).min(end_et_s);
+            for k in 0..=num_checks {
+                let et_s = start_et_s + (stop_et_s - start_et_s) * k as f64 / num_checks as f64;
+                let epoch = Epoch::from_et_seconds(et_s);
+                let normalized_time = (et_s - midpoint_et_s) / radius_s;
+                let (val, rate) = eval(et_s)?;
+
+                let mut fit_val = Vector3::zeros();
+                let mut fit_rate = Vector3::zeros();
+                for (comp, comp_coeffs) in coeffs.iter().take(3).enumerate() {
+                    let (v, deriv) =
+                        chebyshev_eval(normalized_time, comp_coeffs, radius_s, epoch, new_degree)?;
+                    fit_val[comp] = v;
+                    fit_rate[comp] = if fit_rates {
+                        chebyshev_eval_poly(normalized_time, &coeffs[comp + 3], epoch, new_degree)?
+                    } else {
+                        deriv
+                    };
+                }
+
+                max_err = max_err.max((fit_val - val).norm());
+                max_rate_err = max_rate_err.max((fit_rate - rate).norm());
+            }
+
+            if max_err > max_error {
+                break;
+            }
+
+            data.push(midpoint_et_s);
+            data.push(radius_s);
+            for comp_coeffs in coeffs {
+                data.extend(comp_coeffs);
+            }
+        }
+
+        if max_err <= max_error {
+            data.push(init_et_s);
+            data.push(new_interval_s);
+            data.push(rsize as f64);
+            data.push(new_num_records as f64);
+
+            return Ok(ChebyshevRefit {
+                data,
+                degree: new_degree,
+                interval_length: Duration::from_seconds(new_interval_s),
+                num_records: new_num_records,
+                max_error: max_err,
+                max_rate_error: max_rate_err,
+            });
+        }
+
+        lowest_error = lowest_error.min(max_err);
+    }
+
+    Err(InterpolationError::InterpMath {
+        source: MathError::DomainError {
+            value: lowest_error,
+            msg: "no degree meets the maximum error without enlarging the data set, the lowest error reached is",
+        },
+    })
+}
+
+#[cfg(test)]
+mod ut_refit {
+    use super::{refit_chebyshev, InterpolationError, Vector3};
+    use crate::naif::daf::datatypes::Type2ChebyshevSet;
+    use crate::naif::daf::NAIFDataSet;
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn refit_within_max_error() {
+        let eval = |et_s: f64| -> Result<(Vector3, Vector3), InterpolationError> {
+            let (sin, cos) = (et_s / 200.0).sin_cos();
+            Ok((
+                Vector3::new(sin, cos, et_s / 1000.0),
+                Vector3::new(cos / 200.0, -sin / 200.0, 1e-3),
+            ))
+        };
+        let init_epoch = Epoch::from_et_seconds(0.0);
+
+        let refit =
+            refit_chebyshev(init_epoch, Unit::Second * 100, 9, 10, 2, 1e-9, false, eval).unwrap();
+        println!("{refit}");
+        assert!(refit.max_error <= 1e-9);
+        assert_eq!(refit.num_records, 5);
+        assert_eq!(refit.interval_length, Unit::Second * 200);
+        // A looser maximum error allows a lower degree
+        let looser =
+            refit_chebyshev(init_epoch, Unit::Second * 100, 9, 10, 2, 1e-4, false, eval).unwrap();
+        assert!(looser.degree < refit.degree && looser.max_error <= 1e-4);
+
+        let dataset = Type2ChebyshevSet::from_f64_slice(&refit.data).unwrap();
+        assert_eq!(dataset.degree(), refit.degree);
+        assert_eq!(dataset.num_records, 5);
+        let record = dataset.nth_record(4).unwrap();
+        assert_eq!(record.midpoint_epoch(), Epoch::from_et_seconds(900.0));
+
+        // Fitting the rates with their own polynomials
+        let refit =
+            refit_chebyshev(init_epoch, Unit::Second * 100, 9, 10, 1, 1e-9, true, eval).unwrap();
+        assert!(refit.max_rate_error <= 1e-9);
+        assert_eq!(refit.data.len(), 9 * (2 + 6 * (refit.degree + 1)) + 4);
+
+        // Unreachable and invalid maximum errors
+        assert!(
+            refit_chebyshev(init_epoch, Unit::Second * 100, 9, 2, 4, 1e-15, false, eval).is_err()
+        );
+        assert!(
+            refit_chebyshev(init_epoch, Unit::Second * 100, 9, 10, 0, 1e-9, false, eval).is_err()
+        );
+        assert!(
+            refit_chebyshev(init_epoch, Unit::Second * 100, 9, 10, 1, -1.0, false, eval).is_err()
+        );
+    }
+}
@@ -92,7 +92,7 @@ impl FileRecord {
     }
 
     pub fn summary_size(&self) -> usize {
-        (self.nd + (self.ni + 1) / 2) as usize
+        self.nd() + (self.ni() + 1) / 2
     }
 
     pub fn identification(&self) -> Result<&str, FileRecordError> {
@@ -116,18 +116,24 @@ impl FileRecord {
         }
     }
 
-    pub fn endianness(&self) -> Result<Endian, FileRecordError> {
+    /// Returns the endianness in which the file was written, regardless of the endianness of this platform.
+    pub fn file_endianness(&self) -> Result<Endian, FileRecordError> {
         let str_endianness = core::str::from_utf8(&self.endian_str).context(ParsingSnafu)?;
 
-        let file_endian = if str_endianness == "LTL-IEEE" {
-            Endian::Little
+        if str_endianness == "LTL-IEEE" {
+            Ok(Endian::Little)
         } else if str_endianness == "BIG-IEEE" {
-            Endian::Big
+            Ok(Endian::Big)
         } else {
-            return Err(FileRecordError::InvalidEndian {
+            Err(FileRecordError::InvalidEndian {
                 read: str_endianness.to_string(),
-            });
-        };
+            })
+        }
+    }
+
+    /// Returns the endianness of the file, or an error if it does not match the endianness of this platform.
+    pub fn endianness(&self) -> Result<Endian, FileRecordError> {
+        let file_endian = self.file_endianness()?;
         if file_endian != Endian::f64_native() || file_endian != Endian::u64_native() {
             Err(FileRecordError::WrongEndian)
         } else {
@@ -0,0 +1,226 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use core::ops::Deref;
+
+use bytes::BytesMut;
+use log::warn;
+use zerocopy::IntoBytes;
+
+use super::datatypes::{HermiteSetType13, LagrangeSetType9, Type2ChebyshevSet, Type3ChebyshevSet};
+use super::{
+    DAFError, DafDataType, NAIFDataSet, NAIFRecord, NAIFSummaryRecord, SummaryRecord, DAF, RCRD_LEN,
+};
+use crate::DBL_SIZE;
+
+/// Integrity of a segment of a DAF file, as reported by `DAF::parse_lenient`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SegmentIntegrity {
+    /// The data of this segment is available and decodes without error
+    Intact,
+    /// The data of this segment ends at byte `end` but the file only contains `size` bytes (e.g. partially downloaded)
+    Truncated { end: usize, size: usize },
+    /// The data of this segment is available but could not be decoded or contains invalid values
+    Corrupt { reason: String },
+}
+
+/// Report on the integrity of a segment of a DAF file parsed with `DAF::parse_lenient`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentReport<R: NAIFSummaryRecord> {
+    /// Index of this segment in the original summary record
+    pub idx: usize,
+    pub name: String,
+    /// Summary of this segment as read from the file
+    pub summary: R,
+    pub integrity: SegmentIntegrity,
+}
+
+impl<R: NAIFSummaryRecord> SegmentReport<R> {
+    /// Returns whether the data of this segment was salvaged
+    pub fn is_intact(&self) -> bool {
+        self.integrity == SegmentIntegrity::Intact
+    }
+}
+
+impl fmt::Display for SegmentIntegrity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Intact => write!(f, "intact"),
+            Self::Truncated { end, size } => {
+                write!(f, "truncated (data ends at byte {end} but file has {size})")
+            }
+            Self::Corrupt { reason } => write!(f, "corrupt ({reason})"),
+        }
+    }
+}
+
+impl<R: NAIFSummaryRecord> DAF<R> {
+    /// Parse the provided bytes as a SPICE Double Array File, salvaging the segments which can be read from a corrupted
+    /// or truncated file (e.g. a partially downloaded kernel), and reporting on the integrity of each segment.
+    ///
+    /// The file record, name record, and summary record must be readable. The summaries of the segments whose data is
+    /// truncated or corrupted are cleared, so queries fall back onto other segments or other loaded files instead of failing.
+    ///
+    /// # Integrity
+    /// No checksum is verified: the CRC32 of the returned DAF is that of the salvaged bytes, so `scrub` succeeds on it.
+    pub fn parse_lenient<B: Deref<Target = [u8]>>(
+        bytes: B,
+    ) -> Result<(Self, Vec<SegmentReport<R>>), DAFError> {
+        let daf = Self::parse(bytes)?;
+
+        let summaries = daf.data_summaries()?;
+        let mut salvaged = summaries.to_vec();
+        let mut reports = Vec::new();
+
+        for (idx, summary) in summaries.iter().enumerate() {
+            if summary.is_empty() {
+                continue;
+            }
+
+            let integrity = daf.segment_integrity(idx, summary);
+            let name = daf.nth_name(idx)?;
+            if integrity != SegmentIntegrity::Intact {
+                warn!("DAF/{}: segment #{idx} `{name}` is {integrity}", R::NAME);
+                salvaged[idx] = R::default();
+            }
+
+            reports.push(SegmentReport {
+                idx,
+                name,
+                summary: *summary,
+                integrity,
+            });
+        }
+
+        if reports.iter().all(|report| report.is_intact()) {
+            return Ok((daf, reports));
+        }
+
+        // Overwrite the summaries with the salvaged ones, which are stored right after the DAF summary record.
+        let summaries_start =
+            daf.file_record()?.fwrd_idx().saturating_sub(1) * RCRD_LEN + SummaryRecord::SIZE;
+        let salvaged_bytes = salvaged.as_bytes();
+
+        let mut bytes = BytesMut::from(&daf.bytes[..]);
+        bytes[summaries_start..summaries_start + salvaged_bytes.len()]
+            .copy_from_slice(salvaged_bytes);
+
+        let bytes = bytes.freeze();
+        let repaired = Self {
+            crc32_checksum: crc32fast::hash(&bytes),
+            bytes,
+            ..Default::default()
+        };
+
+        Ok((repaired, reports))
+    }
+
+    /// Checks that the data of the n-th segment is available, and that it decodes if it is of a type supported by ANISE.
+    fn segment_integrity(&self, idx: usize, summary: &R) -> SegmentIntegrity {
+        let end = summary.end_index().saturating_mul(DBL_SIZE);
+        if end > self.bytes.len() {
+            return SegmentIntegrity::Truncated {
+                end,
+                size: self.bytes.len(),
+            };
+        }
+
+        let decoded = match summary.data_type() {
+            Ok(DafDataType::Type2ChebyshevTriplet) => self.check_nth_data::<Type2ChebyshevSet>(idx),
+            Ok(DafDataType::Type3ChebyshevSextuplet) => {
+                self.check_nth_data::<Type3ChebyshevSet>(idx)
+            }
+            Ok(DafDataType::Type9LagrangeUnequalStep) => {
+                self.check_nth_data::<LagrangeSetType9>(idx)
+            }
+            Ok(DafDataType::Type13HermiteUnequalStep) => {
+                self.check_nth_data::<HermiteSetType13>(idx)
+            }
+            Ok(_) => self
+                .nth_data_raw(idx)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match decoded {
+            Ok(()) => SegmentIntegrity::Intact,
+            Err(reason) => SegmentIntegrity::Corrupt { reason },
+        }
+    }
+
+    fn check_nth_data<'a, S: NAIFDataSet<'a>>(&'a self, idx: usize) -> Result<(), String> {
+        self.nth_data::<S>(idx)
+            .map_err(|e| e.to_string())?
+            .check_integrity()
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod lenient_ut {
+    use super::SegmentIntegrity;
+    use crate::file2heap;
+    use crate::naif::daf::datatypes::Type2ChebyshevSet;
+    use crate::naif::daf::NAIFSummaryRecord;
+    use crate::naif::SPK;
+    use crate::DBL_SIZE;
+
+    #[test]
+    fn salvage_truncated() {
+        let bytes = file2heap!("../data/de440s.bsp").unwrap();
+
+        // Nominal file is fully intact
+        let (spk, reports) = SPK::parse_lenient(bytes.clone()).unwrap();
+        assert!(!reports.is_empty());
+        assert!(reports.iter().all(|report| report.is_intact()));
+        assert!(spk.scrub().is_ok());
+
+        // Truncate the file in the middle of its last segment
+        let summaries = spk.data_summaries().unwrap();
+        let (last_idx, last) = summaries
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, summary)| summary.end_index())
+            .unwrap();
+        let truncated = &bytes[..(last.end_index() - 100) * DBL_SIZE];
+
+        // Strict parsing succeeds but the data cannot be read
+        let strict = SPK::parse(truncated).unwrap();
+        assert!(strict.nth_data::<Type2ChebyshevSet>(last_idx).is_err());
+
+        let (salvaged, reports) = SPK::parse_lenient(truncated).unwrap();
+        for report in &reports {
+            println!("{} - {}", report.name, report.integrity);
+            if report.idx == last_idx {
+                assert_eq!(
+                    report.integrity,
+                    SegmentIntegrity::Truncated {
+                        end: last.end_index() * DBL_SIZE,
+                        size: truncated.len()
+                    }
+                );
+            } else {
+                assert!(report.is_intact());
+            }
+        }
+
+        // The truncated segment is no longer listed, but all others are
+        let salvaged_summaries = salvaged.data_summaries().unwrap();
+        assert!(salvaged_summaries[last_idx].is_empty());
+        for (idx, summary) in summaries.iter().enumerate() {
+            if idx != last_idx {
+                assert_eq!(&salvaged_summaries[idx], summary);
+            }
+        }
+        assert!(salvaged.scrub().is_ok());
+    }
+}
@@ -13,19 +13,28 @@ use crate::{
     NaifId,
 };
 use core::fmt::Display;
-use hifitime::Epoch;
+use hifitime::{Epoch, Unit};
 use snafu::prelude::*;
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
 pub(crate) const RCRD_LEN: usize = 1024;
+/// Tolerance on the bounds of a summary when searching for the summary which covers an epoch, in nanoseconds.
+pub const SUMMARY_EPOCH_TOLERANCE_NS: i64 = 100;
+pub mod builder;
+mod byte_swap;
 #[allow(clippy::module_inception)]
 pub mod daf;
 mod data_types;
 pub mod mut_daf;
+pub use builder::DAFBuilder;
 pub use data_types::DataType as DafDataType;
 pub mod file_record;
+pub mod lenient;
 pub mod name_record;
+pub(crate) mod record_cache;
+pub mod record_domain;
 pub mod summary_record;
+pub mod transfer;
 // Defines the supported data types
 pub mod datatypes;
 
@@ -34,8 +43,12 @@ pub use daf::DAF;
 use crate::errors::DecodingError;
 use core::fmt::Debug;
 pub use file_record::FileRecord;
+pub use lenient::{SegmentIntegrity, SegmentReport};
 pub use name_record::NameRecord;
+pub(crate) use record_cache::RecordIndexCache;
+pub use record_domain::RecordDomain;
 pub use summary_record::SummaryRecord;
+pub use transfer::{decode_transfer_number, transfer_file_kind, TRANSFER_HEADER};
 
 use self::file_record::FileRecordError;
 
@@ -61,15 +74,28 @@ pub trait NAIFSummaryRecord: NAIFRecord + Copy + Immutable + KnownLayout {
     fn end_epoch_et_s(&self) -> f64;
     /// Returns whatever is the ID of this summary record.
     fn id(&self) -> i32;
+    /// Returns the ID this summary record is relative to, i.e. the center of an SPK segment or the inertial frame of a BPC segment.
+    fn center_id(&self) -> i32;
     fn is_empty(&self) -> bool {
         self.start_index() == self.end_index()
     }
+    /// Returns whether this summary covers the provided epoch, within [SUMMARY_EPOCH_TOLERANCE_NS] of its bounds.
+    fn covers_epoch(&self, epoch: Epoch) -> bool {
+        let tolerance = Unit::Nanosecond * SUMMARY_EPOCH_TOLERANCE_NS;
+        epoch >= self.start_epoch() - tolerance && epoch <= self.end_epoch() + tolerance
+    }
     /// Updates the indexes of this summary (used when modifying a DAF).
     fn update_indexes(&mut self, start: usize, end: usize);
     /// Updates the epochs of this summary (used when modifying a DAF).
     fn update_epochs(&mut self, start_epoch: Epoch, end_epoch: Epoch);
     /// Name of this NAIF type
     const NAME: &'static str;
+    /// DAF identification word written in the file record, e.g. `DAF/SPK`
+    const DAF_IDWORD: &'static str;
+    /// Number of double precision components of this summary
+    const ND: u32 = 2;
+    /// Number of integer components of this summary
+    const NI: u32;
 }
 
 pub trait NAIFDataSet<'a>: Sized + Display + PartialEq {
@@ -101,6 +127,31 @@ pub trait NAIFDataSet<'a>: Sized + Display + PartialEq {
         summary: &S,
     ) -> Result<Self::StateKind, InterpolationError>;
 
+    /// Evaluates this data set like `evaluate`, starting the search for the record from the provided hint, typically the index
+    /// returned by the previous query of this data set. Returns the state and the record index to use as the hint of the next query.
+    ///
+    /// Data sets which compute the record index directly (e.g. Chebyshev) ignore the hint and return None.
+    fn evaluate_with_hint<S: NAIFSummaryRecord>(
+        &self,
+        epoch: Epoch,
+        summary: &S,
+        _hint: Option<usize>,
+    ) -> Result<(Self::StateKind, Option<usize>), InterpolationError> {
+        Ok((self.evaluate(epoch, summary)?, None))
+    }
+
+    /// Returns the domain of the record used to evaluate this data set at the provided epoch.
+    fn record_domain<S: NAIFSummaryRecord>(
+        &self,
+        _epoch: Epoch,
+        _summary: &S,
+    ) -> Result<RecordDomain, InterpolationError> {
+        Err(InterpolationError::UnsupportedOperation {
+            kind: Self::DATASET_NAME,
+            op: "record domain",
+        })
+    }
+
     /// Checks the integrity of this data set, returns an error if the data has issues.
     fn check_integrity(&self) -> Result<(), IntegrityError>;
 
@@ -228,6 +279,20 @@ pub enum DAFError {
     InvalidIndex { kind: &'static str, idx: usize },
     #[snafu(display("could not build data vector of type DAF/{kind}"))]
     DataBuildError { kind: &'static str },
+    #[snafu(display(
+        "DAF/{kind}: cannot build a file with {count} segments (maximum is {max}, the capacity of a single summary record)"
+    ))]
+    TooManySegments {
+        kind: &'static str,
+        count: usize,
+        max: usize,
+    },
+    #[snafu(display("DAF/{kind}: transfer file line {line}: {details}"))]
+    TransferFormat {
+        kind: &'static str,
+        line: usize,
+        details: String,
+    },
 }
 
 // Manual implementation of PartialEq because IOError does not derive it, sadly.
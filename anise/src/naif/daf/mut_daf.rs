@@ -11,8 +11,8 @@
 use core::{marker::PhantomData, ops::Deref};
 
 use super::{
-    daf::MutDAF, DAFError, DecodingNameSnafu, IOSnafu, NAIFDataSet, NAIFSummaryRecord, NameRecord,
-    RCRD_LEN,
+    byte_swap::to_native_endian, daf::MutDAF, DAFError, DecodingNameSnafu, IOSnafu, NAIFDataSet,
+    NAIFSummaryRecord, NameRecord, RecordIndexCache, RCRD_LEN,
 };
 use crate::{
     errors::DecodingError,
@@ -27,13 +27,19 @@ use zerocopy::IntoBytes;
 
 impl<R: NAIFSummaryRecord> MutDAF<R> {
     /// Parse the provided bytes as a SPICE Double Array File
+    ///
+    /// Files written in the other endianness (e.g. legacy big endian kernels) are swapped to the endianness of this platform.
     pub fn parse<B: Deref<Target = [u8]>>(bytes: B) -> Result<Self, DAFError> {
-        let crc32_checksum = crc32fast::hash(&bytes);
         let mut buf = BytesMut::with_capacity(0);
-        buf.extend(bytes.iter());
+        match to_native_endian(&bytes, R::NAME)? {
+            Some(swapped) => buf.extend(swapped),
+            None => buf.extend(bytes.iter()),
+        };
+        let crc32_checksum = crc32fast::hash(&buf);
         let me = Self {
             bytes: buf,
             crc32_checksum,
+            record_cache: RecordIndexCache::default(),
             _daf_type: PhantomData,
         };
         // Check that these calls will succeed.
@@ -87,7 +93,7 @@ impl<R: NAIFSummaryRecord> MutDAF<R> {
             });
         }
 
-        let orig_index_start = this_summary.start_index() - 1;
+        let orig_index_start = this_summary.start_index().saturating_sub(1);
         let orig_index_end = this_summary.end_index();
         let orig_data_start = orig_index_start * DBL_SIZE;
         let orig_data_end = orig_index_end * DBL_SIZE;
@@ -134,7 +140,7 @@ impl<R: NAIFSummaryRecord> MutDAF<R> {
 
         let summary_bytes: Vec<u8> = new_summaries.as_bytes().to_vec();
 
-        let rcrd_idx = (self.file_record()?.fwrd_idx() - 1) * RCRD_LEN;
+        let rcrd_idx = self.file_record()?.fwrd_idx().saturating_sub(1) * RCRD_LEN;
         // Note: we use copy_from_slice here because we have the guarantee that the summary bytes are the same length as the original version.
         let orig_summary_bytes =
             &mut new_bytes[rcrd_idx..rcrd_idx + RCRD_LEN][SummaryRecord::SIZE..];
@@ -159,7 +165,7 @@ impl<R: NAIFSummaryRecord> MutDAF<R> {
             });
         }
 
-        let orig_index_start = this_summary.start_index() - 1;
+        let orig_index_start = this_summary.start_index().saturating_sub(1);
         let orig_index_end = this_summary.end_index();
         let orig_data_start = orig_index_start * DBL_SIZE;
         let orig_data_end = orig_index_end * DBL_SIZE;
@@ -202,7 +208,7 @@ impl<R: NAIFSummaryRecord> MutDAF<R> {
         // We need to pad with zeros all of the summaries we've removed.
         summary_bytes.extend(vec![0x0; 1000 - summary_bytes.len()]);
 
-        let rcrd_idx = (self.file_record()?.fwrd_idx() - 1) * RCRD_LEN;
+        let rcrd_idx = self.file_record()?.fwrd_idx().saturating_sub(1) * RCRD_LEN;
         // Note: we use copy_from_slice here because we have the guarantee that the summary bytes are the same length as the original version.
         let orig_summary_bytes =
             &mut new_bytes[rcrd_idx..rcrd_idx + RCRD_LEN][SummaryRecord::SIZE..];
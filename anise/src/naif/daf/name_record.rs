@@ -36,12 +36,20 @@ impl NameRecord {
     ///
     /// Note that we don't actually use `&self` here, but it's just easier to call.
     pub const fn num_entries(&self, summary_size: usize) -> usize {
-        RCRD_LEN / (summary_size * DBL_SIZE)
+        if summary_size == 0 {
+            0
+        } else {
+            RCRD_LEN / (summary_size * DBL_SIZE)
+        }
     }
 
     pub fn nth_name(&self, n: usize, summary_size: usize) -> &str {
-        let this_name =
-            &self.raw_names[n * summary_size * DBL_SIZE..(n + 1) * summary_size * DBL_SIZE];
+        let start = n.saturating_mul(summary_size * DBL_SIZE);
+        let end = start.saturating_add(summary_size * DBL_SIZE);
+        let Some(this_name) = self.raw_names.get(start..end) else {
+            warn!("name record has no entry {n}! Using `UNNAMED OBJECT` instead");
+            return "UNNAMED OBJECT";
+        };
         match core::str::from_utf8(this_name) {
             Ok(name) => name.trim(),
             Err(e) => {
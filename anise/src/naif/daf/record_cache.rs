@@ -0,0 +1,110 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Number of slots of the cache, segments are mapped to a slot by their index modulo this number.
+const NUM_SLOTS: usize = 32;
+
+/// Marks an empty slot.
+const EMPTY: u64 = u64::MAX;
+
+/// Lock-free cache of the index of the record last used in each segment of a DAF, used as the starting point of the
+/// search for the record of the next query. Sequential-epoch queries then find their record in constant time
+/// instead of a binary search over all of the epochs of the segment.
+///
+/// Each slot packs the segment index (upper 32 bits) with the record index (lower 32 bits) in a single atomic,
+/// so concurrent queries of different segments sharing a slot simply evict each other's hint.
+/// Hints are always validated before use, so a stale hint only costs the regular search.
+///
+/// Clones of a DAF share the same cache, and the cache is ignored when comparing DAFs.
+#[derive(Clone)]
+pub struct RecordIndexCache {
+    slots: Arc<[AtomicU64; NUM_SLOTS]>,
+}
+
+impl RecordIndexCache {
+    /// Returns the index of the record last used in the provided segment, if cached.
+    pub fn get(&self, segment: usize) -> Option<usize> {
+        let packed = self.slots[segment % NUM_SLOTS].load(Ordering::Relaxed);
+        if packed != EMPTY && (packed >> 32) as usize == segment {
+            Some((packed & u64::from(u32::MAX)) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Stores the index of the record last used in the provided segment, or clears it if None.
+    pub fn set(&self, segment: usize, record: Option<usize>) {
+        let packed = match record {
+            Some(record) if segment < u32::MAX as usize && record < u32::MAX as usize => {
+                ((segment as u64) << 32) | record as u64
+            }
+            _ => EMPTY,
+        };
+        self.slots[segment % NUM_SLOTS].store(packed, Ordering::Relaxed);
+    }
+}
+
+impl Default for RecordIndexCache {
+    fn default() -> Self {
+        Self {
+            slots: Arc::new(core::array::from_fn(|_| AtomicU64::new(EMPTY))),
+        }
+    }
+}
+
+impl fmt::Debug for RecordIndexCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RecordIndexCache")
+    }
+}
+
+impl PartialEq for RecordIndexCache {
+    fn eq(&self, _other: &Self) -> bool {
+        // The cache is only a performance hint and does not change the data.
+        true
+    }
+}
+
+#[cfg(test)]
+mod ut_record_cache {
+    use super::*;
+
+    #[test]
+    fn get_set() {
+        let cache = RecordIndexCache::default();
+        assert_eq!(cache.get(3), None);
+
+        cache.set(3, Some(1234));
+        assert_eq!(cache.get(3), Some(1234));
+
+        // Segments sharing a slot evict each other
+        cache.set(3 + NUM_SLOTS, Some(7));
+        assert_eq!(cache.get(3), None);
+        assert_eq!(cache.get(3 + NUM_SLOTS), Some(7));
+
+        // Clones share the cache
+        let clone = cache.clone();
+        clone.set(0, Some(42));
+        assert_eq!(cache.get(0), Some(42));
+
+        cache.set(0, None);
+        assert_eq!(clone.get(0), None);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RecordIndexCache>();
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use hifitime::{Duration, Epoch};
+
+/// Interval of the record of a NAIF data set which is used to interpolate at a given epoch, e.g. to diagnose interpolation
+/// artifacts near record boundaries or to align a sampling grid with the structure of the records.
+///
+/// For Chebyshev data sets, this is the interval covered by the Chebyshev record. For data sets of unequally spaced states
+/// (Hermite and Lagrange), this is the span of the window of states used for the interpolation: its radius is zero if the
+/// epoch exactly matches a state, since that state is returned without interpolation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RecordDomain {
+    /// Index of the record, or of the first state of the interpolation window
+    pub index: usize,
+    pub midpoint: Epoch,
+    pub radius: Duration,
+}
+
+impl RecordDomain {
+    /// Returns the start epoch of this record.
+    pub fn start(&self) -> Epoch {
+        self.midpoint - self.radius
+    }
+
+    /// Returns the end epoch of this record.
+    pub fn end(&self) -> Epoch {
+        self.midpoint + self.radius
+    }
+
+    /// Returns whether this record covers the provided epoch.
+    pub fn contains(&self, epoch: Epoch) -> bool {
+        epoch >= self.start() && epoch <= self.end()
+    }
+
+    /// Returns the normalized time of the epoch in this record, from -1 at its start to +1 at its end (as used in the evaluation
+    /// of Chebyshev polynomials), or zero if this record has no duration.
+    pub fn normalized_time(&self, epoch: Epoch) -> f64 {
+        if self.radius == Duration::ZERO {
+            0.0
+        } else {
+            (epoch - self.midpoint).to_seconds() / self.radius.to_seconds()
+        }
+    }
+}
+
+impl fmt::Display for RecordDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "record #{}: {} ± {} ({} to {})",
+            self.index,
+            self.midpoint,
+            self.radius,
+            self.start(),
+            self.end()
+        )
+    }
+}
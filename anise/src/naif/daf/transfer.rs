@@ -0,0 +1,501 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use snafu::ResultExt;
+use zerocopy::FromBytes;
+
+use super::{DAFBuilder, DAFError, IOSnafu, NAIFRecord, NAIFSummaryRecord, DAF};
+use crate::file2heap;
+use crate::DBL_SIZE;
+
+/// First line of a SPICE DAF transfer file (`.xsp`, `.xpc`, `.xfr`)
+pub const TRANSFER_HEADER: &str = "DAFETF NAIF DAF ENCODED TRANSFER FILE";
+
+const BEGIN_COMMENTS: &str = "~NAIF/SPC BEGIN COMMENTS~";
+const END_COMMENTS: &str = "~NAIF/SPC END COMMENTS~";
+
+/// Returns the DAF kind of the provided transfer file (e.g. `SPK` or `PCK`), if these bytes are a DAF transfer file.
+pub fn transfer_file_kind(bytes: &[u8]) -> Option<&str> {
+    let text = core::str::from_utf8(bytes.get(..256.min(bytes.len()))?).ok()?;
+    let mut lines = text.lines();
+    if !lines.next()?.starts_with("DAFETF") {
+        return None;
+    }
+    lines
+        .next()?
+        .trim()
+        .trim_matches('\'')
+        .trim()
+        .strip_prefix("DAF/")
+}
+
+/// Decodes a number of the SPICE transfer format, where `[-]MANTISSA^[-]EXPONENT` is the hexadecimal representation
+/// of `±0.MANTISSA × 16^EXPONENT`.
+pub fn decode_transfer_number(encoded: &str) -> Option<f64> {
+    let (mantissa, exponent) = encoded.trim().split_once('^')?;
+    let (negative, mantissa) = match mantissa.strip_prefix('-') {
+        Some(mantissa) => (true, mantissa),
+        None => (false, mantissa),
+    };
+    let exponent = match exponent.strip_prefix('-') {
+        Some(exponent) => -i32::from_str_radix(exponent, 16).ok()?,
+        None => i32::from_str_radix(exponent, 16).ok()?,
+    };
+    // A double has 53 bits of mantissa, i.e. at most 14 hexadecimal digits, so the conversion of the mantissa is exact.
+    if mantissa.is_empty() || mantissa.len() > 14 {
+        return None;
+    }
+    let mut value = u64::from_str_radix(mantissa, 16).ok()? as f64;
+    // Scaling by a power of 16 is exact, but is done in steps so the intermediate power does not overflow.
+    let mut shift = exponent - mantissa.len() as i32;
+    while shift != 0 {
+        let step = shift.clamp(-64, 64);
+        value *= 16_f64.powi(step);
+        shift -= step;
+    }
+
+    Some(if negative { -value } else { value })
+}
+
+/// Token of a transfer file with the line it is on
+enum Token<'a> {
+    Quoted(String, usize),
+    Bare(&'a str, usize),
+}
+
+impl Token<'_> {
+    fn line(&self) -> usize {
+        match self {
+            Self::Quoted(_, line) | Self::Bare(_, line) => *line,
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Self::Quoted(text, _) => text,
+            Self::Bare(text, _) => text,
+        }
+    }
+}
+
+/// Splits the transfer data in bare words and quoted strings, where two consecutive quotes are an escaped quote.
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for (lno, line) in text.lines().enumerate() {
+        let mut rest = line;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            if let Some(quoted) = rest.strip_prefix('\'') {
+                let mut value = String::new();
+                let mut chars = quoted.char_indices().peekable();
+                let mut end = quoted.len();
+                while let Some((idx, c)) = chars.next() {
+                    if c == '\'' {
+                        if matches!(chars.peek(), Some((_, '\''))) {
+                            chars.next();
+                            value.push('\'');
+                        } else {
+                            end = idx + 1;
+                            break;
+                        }
+                    } else {
+                        value.push(c);
+                    }
+                }
+                tokens.push(Token::Quoted(value, lno + 1));
+                rest = &quoted[end..];
+            } else {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                tokens.push(Token::Bare(&rest[..end], lno + 1));
+                rest = &rest[end..];
+            }
+        }
+    }
+    tokens
+}
+
+impl<R: NAIFSummaryRecord> DAF<R> {
+    /// Converts the provided SPICE DAF transfer file (as produced by `toxfr` or `spacit`) into a binary DAF in memory,
+    /// which avoids having to convert it with the SPICE toolkit utilities (e.g. `tobin`).
+    ///
+    /// The comments of the transfer file are kept. Like with the `DAFBuilder`, the number of segments is limited to what
+    /// fits in a single summary record: transfer files with more arrays than [DAFBuilder::max_segments] are rejected.
+    pub fn from_transfer(text: &str) -> Result<Self, DAFError> {
+        let err = |line: usize, details: String| DAFError::TransferFormat {
+            kind: R::NAME,
+            line,
+            details,
+        };
+
+        if !text.starts_with("DAFETF") {
+            return Err(err(1, format!("expected `{TRANSFER_HEADER}`")));
+        }
+
+        // The comments, if any, are at the end of the file
+        let (data, comments) = match text.split_once(BEGIN_COMMENTS) {
+            Some((data, comments)) => (
+                data,
+                comments
+                    .split_once(END_COMMENTS)
+                    .map_or(comments, |(comments, _)| comments)
+                    .trim_matches(['\r', '\n']),
+            ),
+            None => (text, ""),
+        };
+
+        let tokens = tokenize(data);
+        let num_tokens = tokens.len();
+        // Skip the header line
+        let mut tokens = tokens.iter().skip_while(|token| token.line() == 1);
+        let last_line = data.lines().count();
+
+        let mut next = |what: &str| {
+            tokens
+                .next()
+                .ok_or_else(|| err(last_line, format!("missing {what}")))
+        };
+
+        let idword = next("identification word")?;
+        if idword.text().trim() != R::DAF_IDWORD {
+            return Err(err(
+                idword.line(),
+                format!("expected {} but found `{}`", R::DAF_IDWORD, idword.text()),
+            ));
+        }
+
+        for (what, expected) in [("ND", R::ND), ("NI", R::NI)] {
+            let token = next(what)?;
+            let value = token
+                .text()
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .or_else(|| decode_transfer_number(token.text()).map(|v| v as u32));
+            if value != Some(expected) {
+                return Err(err(
+                    token.line(),
+                    format!("expected {what} = {expected} but found `{}`", token.text()),
+                ));
+            }
+        }
+
+        let internal_filename = next("internal file name")?.text().trim().to_string();
+
+        let mut builder = DAFBuilder::<R>::new(&internal_filename);
+        if !comments.is_empty() {
+            builder = builder.with_comments(comments);
+        }
+
+        let nd = R::ND as usize;
+        let ni = R::NI as usize;
+
+        loop {
+            let token = next("`BEGIN_ARRAY` or `TOTAL_ARRAYS`")?;
+            match token {
+                Token::Bare("TOTAL_ARRAYS", line) => {
+                    let total = next("total number of arrays")?;
+                    if total.text().parse::<usize>().ok() != Some(builder.len()) {
+                        return Err(err(
+                            *line,
+                            format!(
+                                "{} arrays declared but {} read",
+                                total.text(),
+                                builder.len()
+                            ),
+                        ));
+                    }
+                    break;
+                }
+                Token::Bare("BEGIN_ARRAY", line) => {
+                    let _array_no = next("array number")?;
+                    let len_token = next("array length")?;
+                    let len = len_token.text().parse::<usize>().map_err(|_| {
+                        err(
+                            *line,
+                            format!("invalid array length `{}`", len_token.text()),
+                        )
+                    })?;
+                    let name = next("array name")?.text().trim_end().to_string();
+
+                    if builder.len() == DAFBuilder::<R>::max_segments() {
+                        return Err(err(
+                            *line,
+                            format!(
+                                "more than {} arrays, which is the maximum number of segments of a single summary record",
+                                DAFBuilder::<R>::max_segments()
+                            ),
+                        ));
+                    }
+
+                    // The values are the summary (without the addresses) followed by the data, in blocks prefixed by their count.
+                    // Each value is a token, so the length is checked against the input before allocating.
+                    let num_values = (nd + ni - 2)
+                        .checked_add(len)
+                        .filter(|num_values| *num_values <= num_tokens)
+                        .ok_or_else(|| {
+                            err(
+                                *line,
+                                format!("array length {len} exceeds the size of the transfer file"),
+                            )
+                        })?;
+                    let mut values = Vec::with_capacity(num_values);
+                    loop {
+                        match next("`END_ARRAY`")? {
+                            Token::Bare("END_ARRAY", _) => {
+                                next("array number")?;
+                                next("array length")?;
+                                break;
+                            }
+                            Token::Bare(_, _) => continue,
+                            Token::Quoted(encoded, line) => {
+                                values.push(decode_transfer_number(encoded).ok_or_else(|| {
+                                    err(*line, format!("invalid encoded number `{encoded}`"))
+                                })?);
+                            }
+                        }
+                    }
+
+                    if values.len() != num_values {
+                        return Err(err(
+                            *line,
+                            format!(
+                                "array `{name}` should have {num_values} values but has {}",
+                                values.len()
+                            ),
+                        ));
+                    }
+
+                    // Assemble the summary, whose addresses are set by the builder
+                    let mut summary_bytes = vec![0_u8; R::SIZE];
+                    let (doubles, ints) = summary_bytes.split_at_mut(nd * DBL_SIZE);
+                    for (dno, value) in values[..nd].iter().enumerate() {
+                        doubles[dno * DBL_SIZE..(dno + 1) * DBL_SIZE]
+                            .copy_from_slice(&value.to_ne_bytes());
+                    }
+                    for (ino, value) in values[nd..nd + ni - 2].iter().enumerate() {
+                        ints[ino * 4..(ino + 1) * 4]
+                            .copy_from_slice(&(*value as i32).to_ne_bytes());
+                    }
+                    let summary = R::read_from_bytes(&summary_bytes).unwrap();
+
+                    builder.add_segment(summary, &name, values[nd + ni - 2..].to_vec())?;
+                }
+                token => {
+                    return Err(err(
+                        token.line(),
+                        format!(
+                            "expected `BEGIN_ARRAY` or `TOTAL_ARRAYS` but found `{}`",
+                            token.text()
+                        ),
+                    ))
+                }
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Loads the provided SPICE DAF transfer file and converts it into a binary DAF, cf. `from_transfer`.
+    pub fn load_transfer(path: &str) -> Result<Self, DAFError> {
+        let bytes = file2heap!(path).context(IOSnafu {
+            action: format!("loading {path:?}"),
+        })?;
+
+        let text = core::str::from_utf8(&bytes).map_err(|e| DAFError::TransferFormat {
+            kind: R::NAME,
+            line: 0,
+            details: format!("not a text file: {e}"),
+        })?;
+
+        Self::from_transfer(text)
+    }
+}
+
+#[cfg(test)]
+mod ut_transfer {
+    use super::{decode_transfer_number, transfer_file_kind, TRANSFER_HEADER};
+    use crate::almanac::Almanac;
+    use crate::naif::daf::datatypes::HermiteSetType13;
+    use crate::naif::daf::{DAFBuilder, DAFError, NAIFDataSet, NAIFSummaryRecord};
+    use crate::naif::spk::summary::SPKSummaryRecord;
+    use crate::naif::{BPC, SPK};
+    use bytes::Bytes;
+
+    /// Encodes a number in the SPICE transfer format, the inverse of `decode_transfer_number`.
+    fn encode(value: f64) -> String {
+        if value == 0.0 {
+            return "0^0".to_string();
+        }
+        let mut exponent = 0;
+        let mut mantissa = value.abs();
+        while mantissa >= 1.0 {
+            mantissa /= 16.0;
+            exponent += 1;
+        }
+        while mantissa < 1.0 / 16.0 {
+            mantissa *= 16.0;
+            exponent -= 1;
+        }
+        let mut digits = String::new();
+        while mantissa > 0.0 {
+            mantissa *= 16.0;
+            let digit = mantissa.trunc();
+            digits.push(
+                char::from_digit(digit as u32, 16)
+                    .unwrap()
+                    .to_ascii_uppercase(),
+            );
+            mantissa -= digit;
+        }
+        let sign = if value < 0.0 { "-" } else { "" };
+        if exponent < 0 {
+            format!("{sign}{digits}^-{:X}", -exponent)
+        } else {
+            format!("{sign}{digits}^{exponent:X}")
+        }
+    }
+
+    #[test]
+    fn decode_numbers() {
+        assert_eq!(decode_transfer_number("0^0"), Some(0.0));
+        assert_eq!(decode_transfer_number("1^1"), Some(1.0));
+        assert_eq!(decode_transfer_number("A^1"), Some(10.0));
+        assert_eq!(decode_transfer_number("8^0"), Some(0.5));
+        assert_eq!(decode_transfer_number("-1^-1"), Some(-1.0 / 256.0));
+        assert_eq!(decode_transfer_number("-B11E4A^8"), Some(-2_971_552_256.0));
+        assert_eq!(decode_transfer_number("12"), None);
+        assert_eq!(decode_transfer_number("G^1"), None);
+
+        for value in [1.0, -3.5, 1e-300, 6.02214076e23, core::f64::consts::PI] {
+            assert_eq!(decode_transfer_number(&encode(value)), Some(value));
+        }
+    }
+
+    #[test]
+    fn spk_from_transfer() {
+        let binary = SPK::load("../data/gmat-hermite.bsp").unwrap();
+        let summary = binary.data_summaries().unwrap()[0];
+        let data = binary.nth_data_raw(0).unwrap();
+
+        // Encode the binary file as a transfer file, with data blocks of 1024 values
+        let mut xfr = format!("{TRANSFER_HEADER}\n'DAF/SPK '\n'2'\n'6'\n'GMAT TRANSFER'\n");
+        xfr += &format!(
+            "BEGIN_ARRAY 1 {}\n'{}'\n",
+            data.len(),
+            binary.nth_name(0).unwrap()
+        );
+        for value in [summary.start_epoch_et_s, summary.end_epoch_et_s] {
+            xfr += &format!("'{}'\n", encode(value));
+        }
+        for value in [
+            summary.target_id,
+            summary.center_id,
+            summary.frame_id,
+            summary.data_type_i,
+        ] {
+            xfr += &format!("'{}'\n", encode(value as f64));
+        }
+        for block in data.chunks(1024) {
+            xfr += &format!("{}\n", block.len());
+            for value in block {
+                xfr += &format!("'{}'\n", encode(*value));
+            }
+        }
+        xfr += &format!("END_ARRAY 1 {}\nTOTAL_ARRAYS 1\n", data.len());
+        xfr += " ~NAIF/SPC BEGIN COMMENTS~\nConverted from GMAT\n ~NAIF/SPC END COMMENTS~\n";
+
+        assert_eq!(transfer_file_kind(xfr.as_bytes()), Some("SPK"));
+
+        let converted = SPK::from_transfer(&xfr).unwrap();
+        assert_eq!(converted.nth_name(0).unwrap(), binary.nth_name(0).unwrap());
+        assert_eq!(
+            converted
+                .file_record()
+                .unwrap()
+                .internal_filename()
+                .unwrap(),
+            "GMAT TRANSFER"
+        );
+        assert!(converted
+            .comments()
+            .unwrap()
+            .unwrap()
+            .contains("Converted from GMAT"));
+
+        let mut expected = summary;
+        expected.update_indexes(
+            converted.data_summaries().unwrap()[0].start_idx as usize,
+            converted.data_summaries().unwrap()[0].end_idx as usize,
+        );
+        assert_eq!(converted.data_summaries().unwrap()[0], expected);
+        assert_eq!(converted.nth_data_raw(0).unwrap(), data);
+        assert!(
+            converted.nth_data::<HermiteSetType13>(0).unwrap()
+                == binary.nth_data::<HermiteSetType13>(0).unwrap()
+        );
+
+        // The Almanac converts transfer files transparently
+        let almanac = Almanac::default()
+            .load_from_bytes(Bytes::from(xfr.clone()))
+            .unwrap();
+        assert_eq!(almanac.num_loaded_spk(), 1);
+
+        // Wrong kind of DAF
+        assert!(matches!(
+            BPC::from_transfer(&xfr),
+            Err(DAFError::TransferFormat { line: 2, .. })
+        ));
+
+        // Truncated file
+        let truncated = &xfr[..xfr.len() / 2];
+        assert!(matches!(
+            SPK::from_transfer(truncated),
+            Err(DAFError::TransferFormat { .. })
+        ));
+
+        // Array lengths larger than the file are rejected before allocating
+        for len in [usize::MAX.to_string(), "1000000000000".to_string()] {
+            let oversized = xfr.replacen(
+                &format!("BEGIN_ARRAY 1 {}", data.len()),
+                &format!("BEGIN_ARRAY 1 {len}"),
+                1,
+            );
+            assert!(matches!(
+                SPK::from_transfer(&oversized),
+                Err(DAFError::TransferFormat { line: 6, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn transfer_too_many_arrays() {
+        let max = DAFBuilder::<SPKSummaryRecord>::max_segments();
+
+        let mut xfr = format!("{TRANSFER_HEADER}\n'DAF/SPK '\n'2'\n'6'\n'MANY ARRAYS'\n");
+        for no in 1..=max + 1 {
+            xfr += &format!("BEGIN_ARRAY {no} 1\n'SEGMENT {no}'\n");
+            for _ in 0..6 {
+                xfr += "'0^0'\n";
+            }
+            xfr += &format!("1\n'1^1'\nEND_ARRAY {no} 1\n");
+        }
+        xfr += &format!("TOTAL_ARRAYS {}\n", max + 1);
+
+        match SPK::from_transfer(&xfr) {
+            Err(DAFError::TransferFormat { details, .. }) => {
+                assert!(details.contains("summary record"), "{details}")
+            }
+            other => panic!("expected a transfer format error, got {other:?}"),
+        }
+    }
+}
@@ -253,4 +253,127 @@ mod fk_ut {
             )
             .unwrap();
     }
+
+    #[test]
+    fn test_convert_tkframe_specs() {
+        use crate::math::rotation::{r1, r2, r3, DCM};
+        use crate::math::Matrix3;
+
+        // A rotation of -90 degrees about the Z axis, as in the TKFRAME examples of the frames required reading.
+        let expected = Matrix3::new(0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+        // Generic rotation to check the ordering of the angles and of the matrix elements.
+        let generic =
+            r3(30.0_f64.to_radians()) * r1(20.0_f64.to_radians()) * r2(10.0_f64.to_radians());
+        let generic_elements = generic
+            .as_slice()
+            .iter()
+            .map(|x| format!("{x:.16E}").replace('E', "D"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let kernel = format!(
+            r"
+\begindata
+
+   FRAME_TEST_ANGLES           = -1000001
+   FRAME_-1000001_NAME         = 'TEST_ANGLES'
+   FRAME_-1000001_CLASS        = 4
+   FRAME_-1000001_CLASS_ID     = -1000001
+   FRAME_-1000001_CENTER       = -1000
+   TKFRAME_-1000001_SPEC       = 'ANGLES'
+   TKFRAME_-1000001_RELATIVE   = 'J2000'
+   TKFRAME_-1000001_ANGLES     = ( 1.5707963267948966, 0.0, 0.0 )
+   TKFRAME_-1000001_AXES       = ( 3, 1, 2 )
+   TKFRAME_-1000001_UNITS      = 'RADIANS'
+
+   FRAME_TEST_MATRIX           = -1000002
+   FRAME_-1000002_NAME         = 'TEST_MATRIX'
+   FRAME_-1000002_CLASS        = 4
+   FRAME_-1000002_CLASS_ID     = -1000002
+   FRAME_-1000002_CENTER       = -1000
+   TKFRAME_-1000002_SPEC       = 'MATRIX'
+   TKFRAME_-1000002_RELATIVE   = 'J2000'
+   TKFRAME_-1000002_MATRIX     = ( 0 -1 0
+                                   1  0 0
+                                   0  0 1 )
+
+   FRAME_TEST_QUATERNION       = -1000003
+   FRAME_-1000003_NAME         = 'TEST_QUATERNION'
+   FRAME_-1000003_CLASS        = 4
+   FRAME_-1000003_CLASS_ID     = -1000003
+   FRAME_-1000003_CENTER       = -1000
+   TKFRAME_-1000003_SPEC       = 'QUATERNION'
+   TKFRAME_-1000003_RELATIVE   = 'J2000'
+   TKFRAME_-1000003_Q          = ( 0.7071067811865476 0.0 0.0 -0.7071067811865476 )
+
+   FRAME_TEST_GENERIC_ANGLES   = -1000004
+   FRAME_-1000004_NAME         = 'TEST_GENERIC_ANGLES'
+   FRAME_-1000004_CLASS        = 4
+   FRAME_-1000004_CLASS_ID     = -1000004
+   FRAME_-1000004_CENTER       = -1000
+   TKFRAME_-1000004_SPEC       = 'ANGLES'
+   TKFRAME_-1000004_RELATIVE   = 'TEST_ANGLES'
+   TKFRAME_-1000004_ANGLES     = ( 30.0, 20.0, 10.0 )
+   TKFRAME_-1000004_AXES       = ( 3, 1, 2 )
+   TKFRAME_-1000004_UNITS      = 'DEGREES'
+
+   FRAME_TEST_GENERIC_MATRIX   = -1000005
+   FRAME_-1000005_NAME         = 'TEST_GENERIC_MATRIX'
+   FRAME_-1000005_CLASS        = 4
+   FRAME_-1000005_CLASS_ID     = -1000005
+   FRAME_-1000005_CENTER       = -1000
+   TKFRAME_-1000005_SPEC       = 'MATRIX'
+   TKFRAME_-1000005_RELATIVE   = 'TEST_GENERIC_ANGLES'
+   TKFRAME_-1000005_MATRIX     = ( {generic_elements} )
+
+\begintext
+"
+        );
+
+        let path = std::env::temp_dir().join("anise-test-tkframe-specs.tf");
+        std::fs::write(&path, kernel).unwrap();
+        let dataset = convert_fk(&path, false).unwrap();
+        assert_eq!(dataset.len(), 5);
+
+        for name in ["TEST_ANGLES", "TEST_MATRIX", "TEST_QUATERNION"] {
+            let dcm = DCM::from(dataset.get_by_name(name).unwrap());
+            assert!(
+                (dcm.rot_mat - expected).norm() < 1e-12,
+                "{name}: {}",
+                dcm.rot_mat
+            );
+            assert_eq!(dcm.to, 1, "{name} is relative to J2000");
+        }
+
+        for name in ["TEST_GENERIC_ANGLES", "TEST_GENERIC_MATRIX"] {
+            let dcm = DCM::from(dataset.get_by_name(name).unwrap());
+            assert!(
+                (dcm.rot_mat - generic).norm() < 1e-12,
+                "{name}: {}",
+                dcm.rot_mat
+            );
+        }
+
+        // Chains of fixed frames are stored one hop at a time.
+        assert_eq!(dataset.get_by_id(-1000004).unwrap().to, -1000001);
+        assert_eq!(dataset.get_by_id(-1000005).unwrap().to, -1000004);
+
+        // Invalid specifications are rejected.
+        std::fs::write(
+            &path,
+            r"
+\begindata
+   FRAME_TEST_BAD              = -1000006
+   FRAME_-1000006_CLASS        = 4
+   FRAME_-1000006_CENTER       = -1000
+   TKFRAME_-1000006_RELATIVE   = 'J2000'
+   TKFRAME_-1000006_MATRIX     = ( 1 0 0 0 1 0 0 0 2 )
+\begintext
+",
+        )
+        .unwrap();
+        assert!(convert_fk(&path, false).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
 }
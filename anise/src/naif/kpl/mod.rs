@@ -19,6 +19,7 @@ use self::parser::Assignment;
 pub mod fk;
 
 pub mod parser;
+pub mod pool;
 pub mod tpc;
 
 pub trait KPLItem: Debug + Default {
@@ -113,6 +114,7 @@ pub enum Parameter {
     Matrix,
     Units,
     Axes,
+    Quaternion,
 }
 
 impl FromStr for Parameter {
@@ -140,6 +142,7 @@ impl FromStr for Parameter {
             "MATRIX" => Ok(Self::Matrix),
             "UNITS" => Ok(Self::Units),
             "AXES" => Ok(Self::Axes),
+            "Q" => Ok(Self::Quaternion),
             "MAX_PHASE_DEGREE" => Ok(Self::MaxPhaseDegree),
             "GMLIST" | "NAME" | "SPEC" => {
                 whatever!("unsupported parameter `{s}`")
@@ -18,14 +18,17 @@ use std::path::Path;
 
 use log::{error, info, warn};
 
-use crate::constants::orientations::J2000;
+use crate::constants::celestial_objects::EARTH;
+use crate::constants::orientations::{id_to_orientation_name, J2000};
 use crate::math::rotation::{r1, r2, r3, Quaternion, DCM};
 use crate::math::Matrix3;
 use crate::naif::kpl::fk::FKItem;
 use crate::naif::kpl::tpc::TPCItem;
 use crate::naif::kpl::Parameter;
 use crate::structure::dataset::{DataSetError, DataSetType};
+use crate::structure::lookuptable::KEY_NAME_LEN;
 use crate::structure::metadata::Metadata;
+use crate::structure::planetocentric::dipole::GeomagneticDipole;
 use crate::structure::planetocentric::ellipsoid::Ellipsoid;
 use crate::structure::planetocentric::phaseangle::PhaseAngle;
 use crate::structure::planetocentric::{PlanetaryData, MAX_NUT_PREC_ANGLES};
@@ -60,7 +63,8 @@ impl Assignment {
         if vec.len() > 1 {
             KPLValue::Matrix(
                 vec.iter()
-                    .map(|s| s.parse::<f64>().unwrap_or(0.0))
+                    // SPICE allows the Fortran exponent notation
+                    .map(|s| s.replace(['D', 'd'], "E").parse::<f64>().unwrap_or(0.0))
                     .collect(),
             )
         } else if vec.is_empty() {
@@ -138,6 +142,34 @@ pub fn parse_file<P: AsRef<Path> + fmt::Debug, I: KPLItem>(
     Ok(map)
 }
 
+/// Returns the metadata of a data set converted from these KPL files, recording their names and checksums as its sources.
+fn conversion_metadata<P: AsRef<Path> + fmt::Debug>(
+    dataset_type: DataSetType,
+    files: &[P],
+) -> Metadata {
+    let mut metadata = Metadata {
+        dataset_type,
+        ..Default::default()
+    }
+    .with_tool_version(format!("anise {}", env!("CARGO_PKG_VERSION")));
+
+    for file in files {
+        let path = file.as_ref();
+        match std::fs::read(path) {
+            Ok(contents) => {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string());
+                metadata.add_source(name, &contents);
+            }
+            Err(e) => warn!("cannot record source {file:?} in metadata: {e}"),
+        }
+    }
+
+    metadata
+}
+
 /// Converts two KPL/TPC files, one defining the planetary constants as text, and the other defining the gravity parameters, into the PlanetaryDataSet equivalent ANISE file.
 /// KPL/TPC files must be converted into "PCA" (Planetary Constant ANISE) files before being loaded into ANISE.
 pub fn convert_tpc<P: AsRef<Path> + fmt::Debug>(
@@ -146,8 +178,8 @@ pub fn convert_tpc<P: AsRef<Path> + fmt::Debug>(
 ) -> Result<PlanetaryDataSet, DataSetError> {
     let mut dataset = PlanetaryDataSet::default();
 
-    let gravity_data = parse_file::<_, TPCItem>(gm, false)?;
-    let mut planetary_data = parse_file::<_, TPCItem>(pck, false)?;
+    let gravity_data = parse_file::<_, TPCItem>(&gm, false)?;
+    let mut planetary_data = parse_file::<_, TPCItem>(&pck, false)?;
 
     for (key, value) in gravity_data {
         if let Some(planet_data) = planetary_data.get_mut(&key) {
@@ -286,6 +318,24 @@ pub fn convert_tpc<P: AsRef<Path> + fmt::Debug>(
                             constant.nut_prec_angles = coeffs;
                         };
 
+                        // Add the geomagnetic dipole, whose strength is not in the PCKs: only that of the Earth is known
+                        let scalar = |param| match planetary_data.data.get(&param) {
+                            Some(KPLValue::Float(data)) => Some(*data),
+                            Some(KPLValue::Matrix(data)) => data.first().copied(),
+                            _ => None,
+                        };
+                        if let (Some(lat_deg), Some(lon_deg)) = (
+                            scalar(Parameter::GeoMagNorthPoleCenterDipoleLatitude),
+                            scalar(Parameter::GeoMagNorthPoleCenterDipoleLongitude),
+                        ) {
+                            if object_id == EARTH {
+                                constant.geomagnetic_dipole =
+                                    Some(GeomagneticDipole::earth(lat_deg, lon_deg));
+                            } else {
+                                warn!("Skipping geomagnetic dipole of {object_id}: unknown dipole strength");
+                            }
+                        }
+
                         // Skip the DER serialization in full.
                         dataset.push(constant, Some(object_id), None)?;
                         info!("Added {object_id}");
@@ -304,138 +354,107 @@ pub fn convert_tpc<P: AsRef<Path> + fmt::Debug>(
     println!("Added {} items", dataset.lut.by_id.len());
 
     dataset.set_crc32();
-    dataset.metadata = Metadata::default();
-    dataset.metadata.dataset_type = DataSetType::PlanetaryData;
+    dataset.metadata = conversion_metadata(DataSetType::PlanetaryData, &[pck, gm]);
 
     Ok(dataset)
 }
 
 /// Converts a KPL/FK file, that defines frame constants like fixed rotations, and frame name to ID mappings into the EulerParameterDataSet equivalent ANISE file.
 /// KPL/FK files must be converted into "PCA" (Planetary Constant ANISE) files before being loaded into ANISE.
+///
+/// Fixed (TK) frames may be specified by Euler angles, by a matrix, or by a quaternion. Frames defined relative to another TK frame of the same file
+/// are stored relative to that frame, so chains of fixed frames are traversed when computing the orientation path.
 pub fn convert_fk<P: AsRef<Path> + fmt::Debug>(
     fk_file_path: P,
     show_comments: bool,
 ) -> Result<EulerParameterDataSet, DataSetError> {
     let mut dataset = EulerParameterDataSet::default();
 
-    let assignments = parse_file::<_, FKItem>(fk_file_path, show_comments)?;
+    let assignments = parse_file::<_, FKItem>(&fk_file_path, show_comments)?;
 
     let mut ids_to_update = Vec::new();
 
     // Add all of the data into the data set
     for (id, item) in assignments {
-        if !item.data.contains_key(&Parameter::Angles)
-            && !item.data.contains_key(&Parameter::Matrix)
-        {
-            let mut warn = false;
-            if let Some(class) = item.data.get(&Parameter::Class) {
-                if class.to_i32().unwrap() == 2 {
-                    // BPC based frame, insert as-is.
-                    // Class 2 need a BPC for the full rotation.
-                    dataset.push(Quaternion::identity(id, id), Some(id), item.name.as_deref())?;
-                } else {
-                    warn = true;
+        let rot_mat = match tkframe_rotation(id, &item)? {
+            Some(rot_mat) => rot_mat,
+            None => {
+                if let Some(class) = item.data.get(&Parameter::Class) {
+                    if class.to_i32().unwrap() == 2 {
+                        // BPC based frame, insert as-is.
+                        // Class 2 need a BPC for the full rotation.
+                        dataset.push(
+                            Quaternion::identity(id, id),
+                            Some(id),
+                            item.name.as_deref(),
+                        )?;
+                        continue;
+                    }
                 }
-            } else {
-                warn = true;
-            }
-            if warn {
-                warn!("{id} contains neither angles nor matrix, cannot convert to Euler Parameter");
+                warn!("{id} contains neither angles, matrix, nor quaternion, cannot convert to Euler Parameter");
                 continue;
             }
-        } else if let Some(angles) = item.data.get(&Parameter::Angles) {
-            let unit = item
-                .data
-                .get(&Parameter::Units)
-                .ok_or(DataSetError::Conversion {
-                    action: format!("no unit data for FK ID {id}"),
-                })?;
-            let mut angle_data = angles.to_vec_f64().unwrap();
-            if unit == &KPLValue::String("ARCSECONDS".to_string()) {
-                // Convert the angles data into degrees
-                for item in &mut angle_data {
-                    *item /= 3600.0;
-                }
-            }
-            // Build the quaternion from the Euler matrices
-            let from = id;
-            let to = item.data[&Parameter::Center].to_i32().unwrap();
-            if let Some(class) = item.data.get(&Parameter::Class) {
-                if class.to_i32().unwrap() == 4 {
-                    // This is a relative frame.
-                    let relative_to = item.data.get(&Parameter::Relative).ok_or(DataSetError::Conversion {
-                        action: format!("frame {id} is class 4 relative to, but the RELATIVE_TO token was not found"),
-                    })?.to_string().unwrap();
-
-                    // Always mark as something to update later.
-                    ids_to_update.push((id, relative_to.clone()));
-                }
-            }
+        };
 
-            let mut dcm = Matrix3::identity();
-
-            for (i, rot) in item.data[&Parameter::Axes]
-                .to_vec_f64()
-                .unwrap()
-                .iter()
-                .enumerate()
-            {
-                let this_dcm = if rot == &1.0 {
-                    r1(angle_data[i].to_radians())
-                } else if rot == &2.0 {
-                    r2(angle_data[i].to_radians())
-                } else {
-                    r3(angle_data[i].to_radians())
-                };
-                dcm *= this_dcm;
-            }
-            // Convert to quaternion
-            let q = DCM {
-                rot_mat: dcm,
-                to,
-                from,
-                rot_mat_dt: None,
+        let to = item
+            .data
+            .get(&Parameter::Center)
+            .and_then(|center| center.to_i32().ok())
+            .ok_or(DataSetError::Conversion {
+                action: format!("no center data for FK ID {id}"),
+            })?;
+
+        if let Some(class) = item.data.get(&Parameter::Class) {
+            if class.to_i32().unwrap() == 4 {
+                // This is a relative frame.
+                let relative_to = item.data.get(&Parameter::Relative).ok_or(DataSetError::Conversion {
+                    action: format!("frame {id} is class 4 relative to, but the RELATIVE_TO token was not found"),
+                })?.to_string().unwrap();
+
+                // Always mark as something to update later.
+                ids_to_update.push((id, relative_to.clone()));
             }
-            .into();
-
-            dataset.push(q, Some(id), item.name.as_deref())?;
-        } else if let Some(matrix) = item.data.get(&Parameter::Matrix) {
-            let mat_data = matrix.to_vec_f64().unwrap();
-            let rot_mat = Matrix3::new(
-                mat_data[0],
-                mat_data[1],
-                mat_data[2],
-                mat_data[3],
-                mat_data[4],
-                mat_data[5],
-                mat_data[6],
-                mat_data[7],
-                mat_data[8],
-            );
-            let dcm = DCM {
-                from: id,
-                to: item.data[&Parameter::Center].to_i32().unwrap(),
-                rot_mat,
-                rot_mat_dt: None,
-            };
+        }
 
-            dataset.push(dcm.into(), Some(id), item.name.as_deref())?;
+        // Convert to quaternion
+        let q = DCM {
+            rot_mat,
+            to,
+            from: id,
+            rot_mat_dt: None,
         }
+        .into();
+
+        dataset.push(q, Some(id), item.name.as_deref())?;
     }
 
-    // Finally, let's update the frames of the IDs defined as relative.
+    // Finally, let's update the frames of the IDs defined as relative, such that chains of fixed frames
+    // are resolved one hop at a time when computing the orientation path.
     for (id, relative_to) in ids_to_update {
-        let parent_idx = dataset
+        let parent_id = match dataset
             .lut
             .by_name
-            .get(&(relative_to.as_str().try_into().unwrap()))
-            .ok_or(DataSetError::Conversion {
-                action: format!(
-                    "frame {id} is class 4 relative to `{relative_to}`, but that frame is not found"
-                ),
-            })?;
+            .get(&heapless::String::<KEY_NAME_LEN>::try_from(relative_to.as_str()).unwrap())
+        {
+            Some(parent_idx) => {
+                // The ID of a frame loaded as-is from a BPC is its own ID.
+                let parent = dataset.data[(*parent_idx) as usize];
+                parent.from
+            }
+            None => id_to_orientation_name(&relative_to).map_err(|_| {
+                DataSetError::Conversion {
+                    action: format!(
+                        "frame {id} is class 4 relative to `{relative_to}`, but that frame is not found"
+                    ),
+                }
+            })?,
+        };
 
-        let parent_id = dataset.data[(*parent_idx) as usize].to;
+        if parent_id == id {
+            return Err(DataSetError::Conversion {
+                action: format!("frame {id} is defined relative to itself"),
+            });
+        }
 
         // Modify this EP.
         let index = dataset.lut.by_id.get(&id).unwrap();
@@ -444,9 +463,123 @@ pub fn convert_fk<P: AsRef<Path> + fmt::Debug>(
         this_q.to = parent_id;
     }
 
+    // Ensure that the chains of relative frames end.
+    for q in &dataset.data {
+        let mut to = q.to;
+        for _ in 0..dataset.data.len() {
+            match dataset.lut.by_id.get(&to) {
+                Some(idx) if dataset.data[*idx as usize].to != to => {
+                    to = dataset.data[*idx as usize].to
+                }
+                _ => break,
+            }
+            if to == q.from {
+                return Err(DataSetError::Conversion {
+                    action: format!("frame {} is part of a cycle of relative frames", q.from),
+                });
+            }
+        }
+    }
+
     dataset.set_crc32();
-    dataset.metadata = Metadata::default();
-    dataset.metadata.dataset_type = DataSetType::EulerParameterData;
+    dataset.metadata = conversion_metadata(DataSetType::EulerParameterData, &[fk_file_path]);
 
     Ok(dataset)
 }
+
+/// Returns the rotation matrix of the TK frame from its `ANGLES`, `MATRIX`, or `Q` specification, or None if it has none of these.
+///
+/// # SPICE Compatibility
+/// This follows the TKFRAME conventions of the SPICE frames required reading: the `MATRIX` is listed in column-major order, the `Q`
+/// is a SPICE quaternion (scalar first), and the `ANGLES` may be in degrees, radians, arcminutes, or arcseconds. All three specifications
+/// of the same frame return the same rotation matrix.
+fn tkframe_rotation(id: i32, item: &FKItem) -> Result<Option<Matrix3>, DataSetError> {
+    let rot_mat = if let Some(angles) = item.data.get(&Parameter::Angles) {
+        let unit = item
+            .data
+            .get(&Parameter::Units)
+            .ok_or(DataSetError::Conversion {
+                action: format!("no unit data for FK ID {id}"),
+            })?
+            .to_string()
+            .unwrap_or_default();
+
+        // Convert the angles data into degrees
+        let deg_per_unit = match unit.trim() {
+            "DEGREES" => 1.0,
+            "RADIANS" => 1.0_f64.to_degrees(),
+            "ARCMINUTES" => 1.0 / 60.0,
+            "ARCSECONDS" => 1.0 / 3600.0,
+            _ => {
+                return Err(DataSetError::Conversion {
+                    action: format!("unsupported unit `{unit}` for FK ID {id}"),
+                })
+            }
+        };
+
+        let angle_data = angles.to_vec_f64().unwrap_or_default();
+        let axes = item
+            .data
+            .get(&Parameter::Axes)
+            .and_then(|axes| axes.to_vec_f64().ok())
+            .unwrap_or_default();
+
+        if angle_data.len() != 3 || axes.len() != 3 {
+            return Err(DataSetError::Conversion {
+                action: format!("FK ID {id} must have three angles and three axes"),
+            });
+        }
+
+        // Build the DCM from the Euler matrices
+        let mut dcm = Matrix3::identity();
+        for (angle, rot) in angle_data.iter().zip(axes.iter()) {
+            let angle_rad = (angle * deg_per_unit).to_radians();
+            let this_dcm = if rot == &1.0 {
+                r1(angle_rad)
+            } else if rot == &2.0 {
+                r2(angle_rad)
+            } else if rot == &3.0 {
+                r3(angle_rad)
+            } else {
+                return Err(DataSetError::Conversion {
+                    action: format!("invalid axis {rot} for FK ID {id}"),
+                });
+            };
+            dcm *= this_dcm;
+        }
+        dcm
+    } else if let Some(matrix) = item.data.get(&Parameter::Matrix) {
+        let mat_data = matrix.to_vec_f64().unwrap_or_default();
+        if mat_data.len() != 9 {
+            return Err(DataSetError::Conversion {
+                action: format!("FK ID {id} must have nine matrix elements"),
+            });
+        }
+        Matrix3::from_column_slice(&mat_data)
+    } else if let Some(q) = item.data.get(&Parameter::Quaternion) {
+        let q_data = q.to_vec_f64().unwrap_or_default();
+        if q_data.len() != 4 {
+            return Err(DataSetError::Conversion {
+                action: format!("FK ID {id} must have four quaternion elements"),
+            });
+        }
+        // The ANISE quaternion is the conjugate of the SPICE quaternion of the same rotation.
+        DCM::from(Quaternion::new(
+            q_data[0], -q_data[1], -q_data[2], -q_data[3], id, id,
+        ))
+        .rot_mat
+    } else {
+        return Ok(None);
+    };
+
+    // Validate that this is a rotation matrix
+    if (rot_mat.transpose() * rot_mat - Matrix3::identity()).norm() > 1e-6
+        || rot_mat.determinant() < 0.0
+    {
+        return Err(DataSetError::Conversion {
+            action: format!("FK ID {id} does not define a rotation matrix: {rot_mat}"),
+        });
+    }
+
+    Ok(Some(rot_mat))
+}
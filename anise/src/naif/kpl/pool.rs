@@ -0,0 +1,435 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::structure::dataset::DataSetError;
+
+/// Emulation of the SPICE kernel pool: stores all of the variables assigned in the data blocks of text kernels (TPC, FK, IK, SCLK, etc.),
+/// including those that ANISE does not model, e.g. `FRAME_-85_NAME` or `INS-98300_FOV_SHAPE`.
+///
+/// Numeric variables are stored as doubles, and character variables (quoted in the kernel) as strings. Dates (e.g. `@1972-JAN-1`)
+/// are stored as strings without the `@` prefix, so a variable mixing numbers and dates, like `DELTET/DELTA_AT` of the leap seconds
+/// kernel, has both numeric and character values.
+///
+/// # SPICE Compatibility
+/// Direct assignments (`=`) replace the variable, and incremental assignments (`+=`) append to it, as in `furnsh`.
+/// The typed getters are the equivalents of `gdpool`, `gipool`, and `gcpool`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KernelPool {
+    /// Numeric variables, by their name
+    pub numeric: BTreeMap<String, Vec<f64>>,
+    /// Character variables, by their name
+    pub strings: BTreeMap<String, Vec<String>>,
+}
+
+/// A token of the data block of a text kernel
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// A variable name, a number, or a date
+    Bare(String),
+    /// A quoted string, unquoted
+    Quoted(String),
+    Assign,
+    Append,
+    Open,
+    Close,
+}
+
+impl KernelPool {
+    /// Builds a new kernel pool from the content of the provided text kernel.
+    pub fn from_kernel(kernel: &str) -> Result<Self, DataSetError> {
+        let mut me = Self::default();
+        me.load_kernel(kernel)?;
+        Ok(me)
+    }
+
+    /// Builds a new kernel pool from the text kernel at the provided path.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, DataSetError> {
+        let kernel = std::fs::read_to_string(path).map_err(|source| DataSetError::IO {
+            action: "reading text kernel",
+            source,
+        })?;
+        Self::from_kernel(&kernel)
+    }
+
+    /// Adds all of the variables assigned in the data blocks of the provided text kernel to this pool.
+    pub fn load_kernel(&mut self, kernel: &str) -> Result<(), DataSetError> {
+        // Only keep the data blocks, whose markers must be alone on their line
+        let mut data = String::new();
+        let mut in_data = false;
+        for line in kernel.lines() {
+            let tline = line.trim();
+            if tline == "\\begindata" {
+                in_data = true;
+            } else if tline == "\\begintext" {
+                in_data = false;
+            } else if in_data {
+                data.push_str(line);
+                data.push('\n');
+            }
+        }
+
+        let tokens = tokenize(&data);
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let name = match &tokens[i] {
+                Token::Bare(name) => name.clone(),
+                token => {
+                    return Err(DataSetError::Conversion {
+                        action: format!("expected a variable name but found {token:?}"),
+                    })
+                }
+            };
+
+            let append = match tokens.get(i + 1) {
+                Some(Token::Assign) => false,
+                Some(Token::Append) => true,
+                _ => {
+                    return Err(DataSetError::Conversion {
+                        action: format!("expected `=` or `+=` after `{name}`"),
+                    })
+                }
+            };
+            i += 2;
+
+            // Collect the values, either a single one or a list in parentheses
+            let mut values = Vec::new();
+            if tokens.get(i) == Some(&Token::Open) {
+                i += 1;
+                while i < tokens.len() && tokens[i] != Token::Close {
+                    values.push(tokens[i].clone());
+                    i += 1;
+                }
+                if i == tokens.len() {
+                    return Err(DataSetError::Conversion {
+                        action: format!("missing closing parenthesis for `{name}`"),
+                    });
+                }
+                i += 1;
+            } else if let Some(value) = tokens.get(i) {
+                values.push(value.clone());
+                i += 1;
+            }
+
+            self.assign(name, append, values)?;
+        }
+
+        Ok(())
+    }
+
+    /// Assigns or appends the values to the variable, ensuring that all of them have the same type.
+    fn assign(
+        &mut self,
+        name: String,
+        append: bool,
+        values: Vec<Token>,
+    ) -> Result<(), DataSetError> {
+        let quoted = matches!(values.first(), Some(Token::Quoted(_)));
+
+        if !append {
+            self.numeric.remove(&name);
+            self.strings.remove(&name);
+        } else if (quoted && self.numeric.contains_key(&name))
+            || (!quoted && !values.is_empty() && self.strings.contains_key(&name))
+        {
+            return Err(DataSetError::Conversion {
+                action: format!("cannot append values of a different type to `{name}`"),
+            });
+        }
+
+        if quoted {
+            let mut strings = Vec::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Token::Quoted(value) => strings.push(value),
+                    token => {
+                        return Err(DataSetError::Conversion {
+                            action: format!("`{name}` mixes strings and {token:?}"),
+                        })
+                    }
+                }
+            }
+            self.strings.entry(name).or_default().extend(strings);
+        } else {
+            let mut numbers = Vec::with_capacity(values.len());
+            let mut dates = Vec::new();
+            for value in values {
+                match value {
+                    Token::Bare(value) if value.starts_with('@') => {
+                        dates.push(value[1..].to_string())
+                    }
+                    // SPICE allows the Fortran exponent notation
+                    Token::Bare(value) => match value.replace(['D', 'd'], "E").parse::<f64>() {
+                        Ok(number) => numbers.push(number),
+                        Err(_) => {
+                            return Err(DataSetError::Conversion {
+                                action: format!("invalid number `{value}` in `{name}`"),
+                            })
+                        }
+                    },
+                    token => {
+                        return Err(DataSetError::Conversion {
+                            action: format!("unexpected {token:?} in `{name}`"),
+                        })
+                    }
+                }
+            }
+            if !dates.is_empty() {
+                self.strings.entry(name.clone()).or_default().extend(dates);
+            }
+            if !numbers.is_empty() || self.strings.get(&name).is_none() {
+                self.numeric.entry(name).or_default().extend(numbers);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the content of a text kernel which defines all of the variables of this pool, such that loading it into an empty pool
+    /// returns an identical pool. Numbers are written with their shortest exact representation.
+    pub fn to_kernel(&self) -> String {
+        let mut kernel = String::from("KPL\n\n\\begindata\n\n");
+
+        for (name, numbers) in &self.numeric {
+            let mut values = numbers
+                .iter()
+                .map(|value| format!("{value:?}"))
+                .collect::<Vec<_>>();
+            // Variables mixing numbers and dates
+            if let Some(dates) = self.strings.get(name) {
+                values.extend(dates.iter().map(|date| format!("@{date}")));
+            }
+            kernel.push_str(&format!("{name} = ( {} )\n", values.join(" ")));
+        }
+
+        for (name, strings) in &self.strings {
+            if self.numeric.contains_key(name) {
+                continue;
+            }
+            let values = strings
+                .iter()
+                .map(|value| format!("'{}'", value.replace('\'', "''")))
+                .collect::<Vec<_>>();
+            kernel.push_str(&format!("{name} = ( {} )\n", values.join(" ")));
+        }
+
+        kernel.push_str("\n\\begintext\n");
+        kernel
+    }
+
+    /// Returns all of the values of the numeric variable, if defined (SPICE `gdpool`).
+    pub fn get_f64s(&self, name: &str) -> Option<&[f64]> {
+        self.numeric.get(name).map(|values| values.as_slice())
+    }
+
+    /// Returns the first value of the numeric variable, if defined.
+    pub fn get_f64(&self, name: &str) -> Option<f64> {
+        self.get_f64s(name)?.first().copied()
+    }
+
+    /// Returns all of the values of the numeric variable rounded to the nearest integer, if defined (SPICE `gipool`).
+    pub fn get_i32s(&self, name: &str) -> Option<Vec<i32>> {
+        self.get_f64s(name)
+            .map(|values| values.iter().map(|value| value.round() as i32).collect())
+    }
+
+    /// Returns the first value of the numeric variable rounded to the nearest integer, if defined.
+    pub fn get_i32(&self, name: &str) -> Option<i32> {
+        self.get_f64(name).map(|value| value.round() as i32)
+    }
+
+    /// Returns all of the values of the character variable, if defined (SPICE `gcpool`).
+    pub fn get_strings(&self, name: &str) -> Option<&[String]> {
+        self.strings.get(name).map(|values| values.as_slice())
+    }
+
+    /// Returns the first value of the character variable, if defined.
+    pub fn get_string(&self, name: &str) -> Option<&str> {
+        self.get_strings(name)?.first().map(|value| value.as_str())
+    }
+
+    /// Returns whether the variable is defined in this pool, whatever its type.
+    pub fn contains(&self, name: &str) -> bool {
+        self.numeric.contains_key(name) || self.strings.contains_key(name)
+    }
+
+    /// Returns the names of all of the variables of this pool that start with the provided prefix, in alphabetical order (SPICE `gnpool`).
+    pub fn names_starting_with(&self, prefix: &str) -> Vec<&str> {
+        let mut names = self
+            .numeric
+            .keys()
+            .chain(self.strings.keys())
+            .filter(|name| name.starts_with(prefix))
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Returns the number of variables in this pool.
+    pub fn len(&self) -> usize {
+        self.names_starting_with("").len()
+    }
+
+    /// Returns whether this pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.numeric.is_empty() && self.strings.is_empty()
+    }
+}
+
+/// Splits the data blocks of a text kernel into names, values, operators, and parentheses.
+fn tokenize(data: &str) -> Vec<Token> {
+    let chars = data.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == ',' {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::Open);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::Close);
+            i += 1;
+        } else if c == '=' {
+            tokens.push(Token::Assign);
+            i += 1;
+        } else if c == '+' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::Append);
+            i += 2;
+        } else if c == '\'' {
+            // Quoted string, where two consecutive quotes are an escaped quote
+            let mut value = String::new();
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\'' {
+                    if chars.get(i + 1) == Some(&'\'') {
+                        value.push('\'');
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                value.push(chars[i]);
+                i += 1;
+            }
+            tokens.push(Token::Quoted(value));
+            i += 1;
+        } else {
+            let start = i;
+            while i < chars.len()
+                && !chars[i].is_whitespace()
+                && !matches!(chars[i], '(' | ')' | ',' | '=')
+                && !(chars[i] == '+' && chars.get(i + 1) == Some(&'='))
+            {
+                i += 1;
+            }
+            tokens.push(Token::Bare(chars[start..i].iter().collect()));
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod ut_pool {
+    use super::KernelPool;
+
+    #[test]
+    fn kernel_pool() {
+        let kernel = r"
+KPL/IK
+
+   INS-98300_FOV_SHAPE = 'CIRCLE' is in a comment block
+
+\begindata
+
+   INS-98300_FOV_FRAME       = 'NH_LORRI'
+   INS-98300_FOV_SHAPE       = 'CIRCLE'
+   INS-98300_BORESIGHT       = ( 0.0, 0.0, 1.0 )
+   INS-98300_FOV_REF_ANGLE   = 1.45D-1
+   FRAME_-98_NAME            = 'NH_SPACECRAFT'
+   FRAME_-98_CLASS           = 3
+
+   SCLK_PARTITION_START_98 = ( 0.0000000000000E+00
+                               2.5000000000000E+09 )
+
+   NAIF_BODY_NAME += ( 'NEW HORIZONS', 'NH' )
+   NAIF_BODY_CODE += ( -98, -98 )
+
+\begintext
+
+   Trailing comments.
+
+\begindata
+
+   NAIF_BODY_NAME += 'NH''S PROBE'
+   FRAME_-98_CLASS = 4
+   DELTET/DELTA_AT = ( 10, @1972-JAN-1 )
+
+\begintext
+";
+
+        let pool = KernelPool::from_kernel(kernel).unwrap();
+
+        assert_eq!(pool.get_string("INS-98300_FOV_SHAPE"), Some("CIRCLE"));
+        assert_eq!(
+            pool.get_f64s("INS-98300_BORESIGHT"),
+            Some([0.0, 0.0, 1.0].as_slice())
+        );
+        assert_eq!(pool.get_f64("INS-98300_FOV_REF_ANGLE"), Some(0.145));
+        assert_eq!(pool.get_string("FRAME_-98_NAME"), Some("NH_SPACECRAFT"));
+        // Reassigned in the second data block
+        assert_eq!(pool.get_i32("FRAME_-98_CLASS"), Some(4));
+        assert_eq!(
+            pool.get_f64s("SCLK_PARTITION_START_98"),
+            Some([0.0, 2.5e9].as_slice())
+        );
+        assert_eq!(
+            pool.get_strings("NAIF_BODY_NAME").unwrap(),
+            ["NEW HORIZONS", "NH", "NH'S PROBE"]
+        );
+        assert_eq!(pool.get_i32s("NAIF_BODY_CODE"), Some(vec![-98, -98]));
+        assert_eq!(pool.get_f64("DELTET/DELTA_AT"), Some(10.0));
+        assert_eq!(pool.get_string("DELTET/DELTA_AT"), Some("1972-JAN-1"));
+
+        // Typed getters do not convert between types.
+        assert_eq!(pool.get_f64("FRAME_-98_NAME"), None);
+        assert_eq!(pool.get_string("FRAME_-98_CLASS"), None);
+        assert!(!pool.contains("INS-98300_FOV_CROSS_ANGLE"));
+
+        assert_eq!(
+            pool.names_starting_with("INS-98300_"),
+            [
+                "INS-98300_BORESIGHT",
+                "INS-98300_FOV_FRAME",
+                "INS-98300_FOV_REF_ANGLE",
+                "INS-98300_FOV_SHAPE"
+            ]
+        );
+        assert_eq!(pool.len(), 10);
+
+        // Round trip through a text kernel
+        assert_eq!(KernelPool::from_kernel(&pool.to_kernel()).unwrap(), pool);
+
+        // Invalid kernels
+        assert!(KernelPool::from_kernel("\\begindata\nA = ( 1, 2").is_err());
+        assert!(KernelPool::from_kernel("\\begindata\nA = ( 1, 'B' )").is_err());
+        assert!(KernelPool::from_kernel("\\begindata\nA = 1\nA += 'B'").is_err());
+        assert!(KernelPool::from_kernel("\\begindata\nA = 1.0X").is_err());
+        assert!(KernelPool::from_kernel("\\begindata\nA 1").is_err());
+    }
+}
@@ -154,6 +154,16 @@ fn test_anise_conversion() {
     assert!(!dataset.is_empty(), "should not be empty");
     assert_eq!(dataset.lut.by_id.len(), 49);
 
+    // Both files are recorded as the sources of the data set
+    let sources = dataset
+        .metadata
+        .sources
+        .iter()
+        .map(|source| source.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(sources, ["pck00008.tpc", "gm_de431.tpc"]);
+    assert!(dataset.metadata.tool_version.is_some());
+
     let path = "../target/gm_pck_08.anise";
 
     // Test saving
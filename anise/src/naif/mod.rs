@@ -8,6 +8,7 @@
  * Documentation: https://nyxspace.com/
  */
 
+pub mod bodies;
 pub mod daf;
 
 pub mod kpl;
@@ -0,0 +1,247 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::f64::consts::{PI, TAU};
+
+use hifitime::{Duration, Epoch};
+use snafu::ResultExt;
+
+use super::BPCSummaryRecord;
+use crate::errors::{MathError, PhysicsError};
+use crate::math::interpolation::{chebyshev_fit, chebyshev_nodes, InterpolationError};
+use crate::math::rotation::DCM;
+use crate::math::{Matrix3, Vector3};
+use crate::naif::daf::{DAFBuilder, DafDataType, NAIFSummaryRecord, DAF};
+use crate::naif::BPC;
+use crate::orientations::{BPCSnafu, OrientationError};
+use crate::NaifId;
+
+/// Builds a binary PCK (DAF/PCK) file from rotations sampled at arbitrary epochs.
+///
+/// Each segment is stored as a Type 2 Chebyshev triplet of the 3-1-3 Euler angles of the rotation
+/// from the inertial frame to the body fixed frame, which is the representation expected by ANISE and CSPICE.
+///
+/// # Example
+/// ```ignore
+/// let mut builder = BPC::builder("ITRF93 EXPORT");
+/// builder.add_type2_segment(
+///     "ITRF93", ITRF93, J2000, start, end, 1.days(), 15,
+///     |epoch| almanac.rotate(EME2000, EARTH_ITRF93, epoch),
+/// )?;
+/// builder.build()?.persist("itrf93.bpc")?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BPCBuilder {
+    daf: DAFBuilder<BPCSummaryRecord>,
+}
+
+impl DAF<BPCSummaryRecord> {
+    /// Initializes a new builder of a binary PCK with the provided internal file name.
+    pub fn builder(internal_filename: &str) -> BPCBuilder {
+        BPCBuilder {
+            daf: DAFBuilder::new(internal_filename),
+        }
+    }
+}
+
+impl BPCBuilder {
+    /// Sets the comments of this BPC file
+    pub fn with_comments(mut self, comments: &str) -> Self {
+        self.daf = self.daf.with_comments(comments);
+        self
+    }
+
+    /// Samples the provided rotation function and adds a Type 2 Chebyshev segment covering `start` to `end`.
+    ///
+    /// # Arguments
+    /// + `name`: name of the segment, truncated to 40 characters
+    /// + `frame_id`: orientation ID of the body fixed frame, i.e. the `to` frame of the rotations
+    /// + `inertial_frame_id`: orientation ID of the inertial frame, i.e. the `from` frame of the rotations
+    /// + `interval_length`: duration covered by each Chebyshev record, the last record may extend past the end epoch
+    /// + `degree`: degree of the Chebyshev polynomials, the rotation is sampled `degree + 1` times per record
+    /// + `rotation`: function returning the DCM from the inertial frame to the body fixed frame at the requested epoch
+    ///
+    /// # Limitations
+    /// The interval length must be short enough that the Euler angles change by less than 180 degrees between two samples,
+    /// and the body frame Z axis should not be aligned with the inertial Z axis (the 3-1-3 sequence is singular in that case).
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_type2_segment<F>(
+        &mut self,
+        name: &str,
+        frame_id: NaifId,
+        inertial_frame_id: NaifId,
+        start: Epoch,
+        end: Epoch,
+        interval_length: Duration,
+        degree: usize,
+        mut rotation: F,
+    ) -> Result<(), OrientationError>
+    where
+        F: FnMut(Epoch) -> Result<DCM, OrientationError>,
+    {
+        let interval_s = interval_length.to_seconds();
+        if interval_s <= 0.0 || end <= start {
+            return Err(OrientationError::OrientationInterpolation {
+                source: InterpolationError::InterpMath {
+                    source: MathError::DomainError {
+                        value: interval_s,
+                        msg: "BPC segment requires a positive interval length and an end after its start",
+                    },
+                },
+            });
+        }
+
+        let radius_s = interval_s / 2.0;
+        let num_records = ((end - start).to_seconds() / interval_s).ceil() as usize;
+        let nodes = chebyshev_nodes(degree);
+        let rsize = 2 + 3 * (degree + 1);
+
+        let mut data = Vec::with_capacity(num_records * rsize + 4);
+
+        for rno in 0..num_records {
+            let midpoint_et_s = start.to_et_seconds() + radius_s * (2 * rno + 1) as f64;
+
+            let mut angles: Vec<Vector3> = Vec::with_capacity(nodes.len());
+            for node in &nodes {
+                let epoch = Epoch::from_et_seconds(midpoint_et_s + node * radius_s);
+                let dcm = rotation(epoch)?;
+                if dcm.from != inertial_frame_id || dcm.to != frame_id {
+                    return Err(OrientationError::OrientationPhysics {
+                        source: PhysicsError::InvalidRotation {
+                            action: "building BPC segment",
+                            from1: inertial_frame_id,
+                            to1: frame_id,
+                            from2: dcm.from,
+                            to2: dcm.to,
+                        },
+                    });
+                }
+
+                let mut these_angles = euler_313_angles(&dcm.rot_mat);
+                // Unwrap the first and third angles to keep them continuous for the fit.
+                if let Some(prev) = angles.last() {
+                    for i in [0, 2] {
+                        these_angles[i] += TAU * ((prev[i] - these_angles[i]) / TAU).round();
+                    }
+                }
+                angles.push(these_angles);
+            }
+
+            data.push(midpoint_et_s);
+            data.push(radius_s);
+            for i in 0..3 {
+                let values = angles.iter().map(|a| a[i]).collect::<Vec<f64>>();
+                data.extend(chebyshev_fit(&values));
+            }
+        }
+
+        data.push(start.to_et_seconds());
+        data.push(interval_s);
+        data.push(rsize as f64);
+        data.push(num_records as f64);
+
+        let mut summary = BPCSummaryRecord {
+            frame_id,
+            inertial_frame_id,
+            data_type_i: DafDataType::Type2ChebyshevTriplet as i32,
+            ..Default::default()
+        };
+        summary.update_epochs(start, end);
+
+        self.daf.add_segment(summary, name, data).context(BPCSnafu {
+            action: "adding Type 2 segment",
+        })
+    }
+
+    /// Builds the BPC from all of the segments, which may then be loaded in an Almanac or persisted to disk.
+    pub fn build(&self) -> Result<BPC, OrientationError> {
+        self.daf.build().context(BPCSnafu {
+            action: "building BPC",
+        })
+    }
+}
+
+/// Returns the angles [a0, a1, a2] such that the rotation matrix is R3(a2) * R1(a1) * R3(a0).
+fn euler_313_angles(rot_mat: &Matrix3) -> Vector3 {
+    let a1 = rot_mat[(2, 2)].clamp(-1.0, 1.0).acos();
+    if a1.abs() < f64::EPSILON || (a1 - PI).abs() < f64::EPSILON {
+        // Gimbal lock: only the sum (or difference) of the first and third angles is observable.
+        Vector3::new(0.0, a1, rot_mat[(0, 1)].atan2(rot_mat[(0, 0)]))
+    } else {
+        Vector3::new(
+            rot_mat[(2, 0)].atan2(-rot_mat[(2, 1)]),
+            a1,
+            rot_mat[(0, 2)].atan2(rot_mat[(1, 2)]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod ut_bpc_builder {
+    use super::*;
+    use crate::math::rotation::{r1, r3};
+    use crate::naif::daf::{datatypes::Type2ChebyshevSet, NAIFDataSet};
+    use hifitime::TimeUnits;
+
+    #[test]
+    fn euler_angles() {
+        let rot_mat = r3(-1.2) * r1(0.4) * r3(2.5);
+        let angles = euler_313_angles(&rot_mat);
+        assert!((angles - Vector3::new(2.5, 0.4, -1.2)).norm() < 1e-14);
+    }
+
+    #[test]
+    fn constant_rate_segment() {
+        let start = Epoch::from_et_seconds(0.0);
+        let end = start + 2.days();
+        // Roughly the rotation rate of the Earth
+        let rate_rad_s = 7.292_115e-5;
+
+        let rotation = |epoch: Epoch| -> Result<DCM, OrientationError> {
+            let twist = rate_rad_s * epoch.to_et_seconds();
+            Ok(DCM {
+                rot_mat: r3(twist) * r1(0.3) * r3(1.1),
+                rot_mat_dt: None,
+                from: 1,
+                to: 3000,
+            })
+        };
+
+        let mut builder = BPC::builder("TEST").with_comments("Constant rate test");
+        builder
+            .add_type2_segment("TEST", 3000, 1, start, end, 4.hours(), 10, rotation)
+            .unwrap();
+        let bpc = builder.build().unwrap();
+
+        let summary = bpc.data_summaries().unwrap()[0];
+        assert_eq!(summary.frame_id, 3000);
+        assert_eq!(summary.inertial_frame_id, 1);
+        assert_eq!(summary.start_epoch(), start);
+        assert_eq!(summary.end_epoch(), end);
+
+        let set = bpc.nth_data::<Type2ChebyshevSet>(0).unwrap();
+        assert_eq!(set.degree(), 10);
+        assert_eq!(set.num_records, 12);
+
+        for hours in [0.0, 3.3, 17.9, 47.99] {
+            let epoch = start + hours.hours();
+            let (angles, rates) = set.evaluate(epoch, &summary).unwrap();
+            let expected = rotation(epoch).unwrap();
+            let rot_mat = r3(angles[2]) * r1(angles[1]) * r3(angles[0]);
+            assert!((rot_mat - expected.rot_mat).norm() < 1e-12);
+            assert!((rates - Vector3::new(0.0, 0.0, rate_rad_s)).norm() < 1e-14);
+        }
+
+        // Check the frame validation
+        assert!(builder
+            .add_type2_segment("TEST", 3001, 1, start, end, 4.hours(), 10, rotation)
+            .is_err());
+    }
+}
@@ -20,6 +20,9 @@ use pyo3::prelude::*;
 
 use super::daf::DafDataType;
 
+pub mod builder;
+pub use builder::BPCBuilder;
+
 #[cfg_attr(feature = "python", pyclass)]
 #[cfg_attr(feature = "python", pyo3(module = "anise.internals"))]
 #[derive(Clone, Copy, Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq)]
@@ -55,6 +58,8 @@ impl NAIFRecord for BPCSummaryRecord {}
 
 impl NAIFSummaryRecord for BPCSummaryRecord {
     const NAME: &'static str = "BPCSummaryRecord";
+    const DAF_IDWORD: &'static str = "DAF/PCK";
+    const NI: u32 = 5;
 
     type Error = OrientationError;
 
@@ -85,6 +90,10 @@ impl NAIFSummaryRecord for BPCSummaryRecord {
         self.frame_id
     }
 
+    fn center_id(&self) -> i32 {
+        self.inertial_frame_id
+    }
+
     fn start_epoch_et_s(&self) -> f64 {
         self.start_epoch_et_s
     }
@@ -0,0 +1,484 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Duration, Epoch};
+use snafu::ResultExt;
+
+use super::summary::SPKSummaryRecord;
+use crate::ephemerides::{EphemerisError, SPKSnafu};
+use crate::errors::MathError;
+use crate::math::cartesian::CartesianState;
+use crate::math::interpolation::{chebyshev_fit, chebyshev_nodes, InterpolationError};
+use crate::math::Vector3;
+use crate::naif::daf::datatypes::refit::refit_chebyshev;
+use crate::naif::daf::datatypes::ChebyshevRefit;
+use crate::naif::daf::{DAFBuilder, DafDataType, NAIFSummaryRecord, DAF};
+use crate::naif::SPK;
+use crate::NaifId;
+
+/// Number of epochs between two entries of the epoch directory of Type 13 segments, as per the SPICE convention.
+const TYPE13_DIRECTORY_STEP: usize = 100;
+
+/// Maximum degree of the Type 2 segments fitted within a maximum error.
+const TYPE2_MAX_DEGREE: usize = 27;
+
+/// Maximum number of records of the Type 2 segments fitted within a maximum error.
+const TYPE2_MAX_RECORDS: usize = 1 << 20;
+
+/// Maximum window size of a Type 13 segment (SPICE's MAXDEG is 27, i.e. at most 14 states).
+const TYPE13_MAX_WINDOW: usize = 14;
+
+/// Builds an SPK (DAF/SPK) file from states sampled at arbitrary epochs.
+///
+/// Segments are either stored as Type 2 Chebyshev position polynomials (compact, for smooth trajectories)
+/// or Type 13 Hermite states (exact at the sampled epochs, for unequally spaced data).
+///
+/// # Example
+/// ```ignore
+/// let mut builder = SPK::builder("MOON EXPORT");
+/// builder.add_type2_segment(
+///     "MOON", MOON, EARTH, J2000, start, end, 4.days(), 15,
+///     |epoch| almanac.translate_geometric(MOON_J2000, EARTH_J2000, epoch),
+/// )?;
+/// builder.build()?.persist("moon.bsp")?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SPKBuilder {
+    daf: DAFBuilder<SPKSummaryRecord>,
+}
+
+impl DAF<SPKSummaryRecord> {
+    /// Initializes a new builder of an SPK with the provided internal file name.
+    pub fn builder(internal_filename: &str) -> SPKBuilder {
+        SPKBuilder {
+            daf: DAFBuilder::new(internal_filename),
+        }
+    }
+}
+
+impl SPKBuilder {
+    /// Sets the comments of this SPK file
+    pub fn with_comments(mut self, comments: &str) -> Self {
+        self.daf = self.daf.with_comments(comments);
+        self
+    }
+
+    /// Samples the provided state function and adds a Type 2 Chebyshev segment covering `start` to `end`.
+    ///
+    /// # Arguments
+    /// + `name`: name of the segment, truncated to 40 characters
+    /// + `target_id`: ephemeris ID of the target
+    /// + `center_id`: ephemeris ID of the center (observer) of the states
+    /// + `frame_id`: orientation ID in which the states are expressed
+    /// + `interval_length`: duration covered by each Chebyshev record, the last record may extend past the end epoch
+    /// + `degree`: degree of the Chebyshev polynomials, the state is sampled `degree + 1` times per record
+    /// + `state`: function returning the state of the target with respect to the center at the requested epoch
+    ///
+    /// # Note
+    /// Only the position is fitted: the velocity is recovered from the derivative of the polynomials, as is done in SPICE.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_type2_segment<F>(
+        &mut self,
+        name: &str,
+        target_id: NaifId,
+        center_id: NaifId,
+        frame_id: NaifId,
+        start: Epoch,
+        end: Epoch,
+        interval_length: Duration,
+        degree: usize,
+        mut state: F,
+    ) -> Result<(), EphemerisError>
+    where
+        F: FnMut(Epoch) -> Result<CartesianState, EphemerisError>,
+    {
+        let interval_s = interval_length.to_seconds();
+        if interval_s <= 0.0 || end <= start {
+            return Err(EphemerisError::EphemInterpolation {
+                source: InterpolationError::InterpMath {
+                    source: MathError::DomainError {
+                        value: interval_s,
+                        msg: "SPK segment requires a positive interval length and an end after its start",
+                    },
+                },
+            });
+        }
+
+        let radius_s = interval_s / 2.0;
+        let num_records = ((end - start).to_seconds() / interval_s).ceil() as usize;
+        let nodes = chebyshev_nodes(degree);
+        let rsize = 2 + 3 * (degree + 1);
+
+        let mut data = Vec::with_capacity(num_records * rsize + 4);
+
+        for rno in 0..num_records {
+            let midpoint_et_s = start.to_et_seconds() + radius_s * (2 * rno + 1) as f64;
+
+            let mut positions: Vec<Vector3> = Vec::with_capacity(nodes.len());
+            for node in &nodes {
+                let epoch = Epoch::from_et_seconds(midpoint_et_s + node * radius_s);
+                positions.push(state(epoch)?.radius_km);
+            }
+
+            data.push(midpoint_et_s);
+            data.push(radius_s);
+            for i in 0..3 {
+                let values = positions.iter().map(|p| p[i]).collect::<Vec<f64>>();
+                data.extend(chebyshev_fit(&values));
+            }
+        }
+
+        data.push(start.to_et_seconds());
+        data.push(interval_s);
+        data.push(rsize as f64);
+        data.push(num_records as f64);
+
+        let mut summary = SPKSummaryRecord {
+            target_id,
+            center_id,
+            frame_id,
+            data_type_i: DafDataType::Type2ChebyshevTriplet as i32,
+            ..Default::default()
+        };
+        summary.update_epochs(start, end);
+
+        self.daf.add_segment(summary, name, data).context(SPKSnafu {
+            action: "adding Type 2 segment",
+        })
+    }
+
+    /// Samples the provided state function and adds the smallest Type 2 Chebyshev segment covering `start` to `end` whose
+    /// maximum position error is within `max_error_km`, returning the fit (records, degree, and errors) of that segment.
+    ///
+    /// The number of records is doubled from a single record covering the whole span, and the lowest degree meeting the
+    /// maximum error is used for each number of records, until the size of the segment stops decreasing.
+    ///
+    /// # Note
+    /// The maximum error is sampled several times per coefficient in each record, so it may be slightly exceeded between samples.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_type2_segment_within<F>(
+        &mut self,
+        name: &str,
+        target_id: NaifId,
+        center_id: NaifId,
+        frame_id: NaifId,
+        start: Epoch,
+        end: Epoch,
+        max_error_km: f64,
+        mut state: F,
+    ) -> Result<ChebyshevRefit, EphemerisError>
+    where
+        F: FnMut(Epoch) -> Result<CartesianState, EphemerisError>,
+    {
+        let span = end - start;
+        if span <= Duration::ZERO {
+            return Err(EphemerisError::EphemInterpolation {
+                source: InterpolationError::InterpMath {
+                    source: MathError::DomainError {
+                        value: span.to_seconds(),
+                        msg: "SPK segment requires an end after its start",
+                    },
+                },
+            });
+        }
+
+        // The fit requires an interpolation error, so keep track of the first error from the state function.
+        let mut state_err = None;
+        let mut eval = |et_s: f64| {
+            let epoch = Epoch::from_et_seconds(et_s);
+            match state(epoch) {
+                Ok(state) => Ok((state.radius_km, state.velocity_km_s)),
+                Err(e) => {
+                    state_err.get_or_insert(e);
+                    Err(InterpolationError::MissingInterpolationData { epoch })
+                }
+            }
+        };
+
+        let mut best: Option<ChebyshevRefit> = None;
+        let mut num_records = 1;
+        while num_records <= TYPE2_MAX_RECORDS {
+            match refit_chebyshev(
+                start,
+                span / (num_records as i64),
+                num_records,
+                TYPE2_MAX_DEGREE,
+                1,
+                max_error_km,
+                false,
+                &mut eval,
+            ) {
+                Ok(refit) => {
+                    if best
+                        .as_ref()
+                        .is_some_and(|best| best.data.len() <= refit.data.len())
+                    {
+                        break;
+                    }
+                    best = Some(refit);
+                }
+                Err(InterpolationError::MissingInterpolationData { .. }) => break,
+                Err(InterpolationError::InterpMath { .. }) if best.is_some() => break,
+                Err(InterpolationError::InterpMath { .. }) => {}
+                Err(source) => return Err(EphemerisError::EphemInterpolation { source }),
+            }
+            num_records *= 2;
+        }
+
+        if let Some(e) = state_err {
+            return Err(e);
+        }
+
+        let refit = best.ok_or(EphemerisError::EphemInterpolation {
+            source: InterpolationError::InterpMath {
+                source: MathError::DomainError {
+                    value: max_error_km,
+                    msg: "no Type 2 segment meets the maximum error",
+                },
+            },
+        })?;
+
+        let mut summary = SPKSummaryRecord {
+            target_id,
+            center_id,
+            frame_id,
+            data_type_i: DafDataType::Type2ChebyshevTriplet as i32,
+            ..Default::default()
+        };
+        summary.update_epochs(start, end);
+
+        self.daf
+            .add_segment(summary, name, refit.data.clone())
+            .context(SPKSnafu {
+                action: "adding Type 2 segment",
+            })?;
+
+        Ok(refit)
+    }
+
+    /// Adds a Type 13 Hermite segment storing the provided states, which must be sorted by strictly increasing epoch.
+    ///
+    /// # Arguments
+    /// + `name`: name of the segment, truncated to 40 characters
+    /// + `target_id`: ephemeris ID of the target
+    /// + `center_id`: ephemeris ID of the center (observer) of the states
+    /// + `frame_id`: orientation ID in which the states are expressed
+    /// + `states`: the states to store, the frames of the states are not checked
+    /// + `degree`: odd degree of the Hermite polynomials, each interpolation uses `(degree + 1) / 2` states
+    pub fn add_type13_segment(
+        &mut self,
+        name: &str,
+        target_id: NaifId,
+        center_id: NaifId,
+        frame_id: NaifId,
+        states: &[CartesianState],
+        degree: usize,
+    ) -> Result<(), EphemerisError> {
+        let window_size = (degree + 1) / 2;
+        if degree % 2 == 0 || window_size > TYPE13_MAX_WINDOW {
+            return Err(EphemerisError::EphemInterpolation {
+                source: InterpolationError::InterpMath {
+                    source: MathError::DomainError {
+                        value: degree as f64,
+                        msg: "Type 13 degree must be odd and at most 27",
+                    },
+                },
+            });
+        }
+
+        if states.len() < window_size {
+            return Err(EphemerisError::EphemInterpolation {
+                source: InterpolationError::CorruptedData {
+                    what: "Type 13 segment requires at least as many states as the window size",
+                },
+            });
+        }
+
+        if states.windows(2).any(|pair| pair[1].epoch <= pair[0].epoch) {
+            return Err(EphemerisError::EphemInterpolation {
+                source: InterpolationError::CorruptedData {
+                    what: "Type 13 states must be sorted by strictly increasing epoch",
+                },
+            });
+        }
+
+        let num_states = states.len();
+        let mut data = Vec::with_capacity(7 * num_states + num_states / TYPE13_DIRECTORY_STEP + 2);
+
+        for state in states {
+            data.extend(state.radius_km.iter());
+            data.extend(state.velocity_km_s.iter());
+        }
+
+        let epochs_et_s = states
+            .iter()
+            .map(|state| state.epoch.to_et_seconds())
+            .collect::<Vec<f64>>();
+        data.extend(&epochs_et_s);
+
+        // The epoch directory contains every 100th epoch, excluding the last one.
+        data.extend(
+            epochs_et_s
+                .iter()
+                .skip(TYPE13_DIRECTORY_STEP - 1)
+                .step_by(TYPE13_DIRECTORY_STEP)
+                .take((num_states - 1) / TYPE13_DIRECTORY_STEP),
+        );
+
+        // NOTE: Type 13 stores the window size minus one.
+        data.push((window_size - 1) as f64);
+        data.push(num_states as f64);
+
+        let mut summary = SPKSummaryRecord {
+            target_id,
+            center_id,
+            frame_id,
+            data_type_i: DafDataType::Type13HermiteUnequalStep as i32,
+            ..Default::default()
+        };
+        summary.update_epochs(states[0].epoch, states[num_states - 1].epoch);
+
+        self.daf.add_segment(summary, name, data).context(SPKSnafu {
+            action: "adding Type 13 segment",
+        })
+    }
+
+    /// Builds the SPK from all of the segments, which may then be loaded in an Almanac or persisted to disk.
+    pub fn build(&self) -> Result<SPK, EphemerisError> {
+        self.daf.build().context(SPKSnafu {
+            action: "building SPK",
+        })
+    }
+}
+
+#[cfg(test)]
+mod ut_spk_builder {
+    use super::*;
+    use crate::naif::daf::{
+        datatypes::{HermiteSetType13, Type2ChebyshevSet},
+        NAIFDataSet,
+    };
+    use crate::prelude::Frame;
+    use hifitime::TimeUnits;
+
+    /// Circular orbit of radius 7000 km with a period of about 1.6 hours, in the XY plane.
+    fn circular(epoch: Epoch) -> CartesianState {
+        let rate_rad_s = 1.1e-3;
+        let (s, c) = (rate_rad_s * epoch.to_et_seconds()).sin_cos();
+        CartesianState {
+            radius_km: Vector3::new(7000.0 * c, 7000.0 * s, 0.0),
+            velocity_km_s: Vector3::new(-7000.0 * rate_rad_s * s, 7000.0 * rate_rad_s * c, 0.0),
+            epoch,
+            frame: Frame::from_ephem_j2000(399),
+        }
+    }
+
+    #[test]
+    fn type2_circular() {
+        let start = Epoch::from_et_seconds(0.0);
+        let end = start + 6.hours();
+
+        let mut builder = SPK::builder("TEST").with_comments("Circular orbit test");
+        builder
+            .add_type2_segment("TEST", -10, 399, 1, start, end, 10.minutes(), 12, |epoch| {
+                Ok(circular(epoch))
+            })
+            .unwrap();
+        let spk = builder.build().unwrap();
+
+        let summary = spk.data_summaries().unwrap()[0];
+        assert_eq!(summary.target_id, -10);
+        assert_eq!(summary.center_id, 399);
+        assert_eq!(summary.start_epoch_et_s, 0.0);
+
+        let set = spk.nth_data::<Type2ChebyshevSet>(0).unwrap();
+        assert_eq!(set.degree(), 12);
+        assert_eq!(set.num_records, 36);
+
+        for minutes in [0.0, 7.3, 181.9, 359.99] {
+            let epoch = start + minutes.minutes();
+            let (pos_km, vel_km_s) = set.evaluate(epoch, &summary).unwrap();
+            let expected = circular(epoch);
+            assert!((pos_km - expected.radius_km).norm() < 1e-7);
+            assert!((vel_km_s - expected.velocity_km_s).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn type2_within_max_error() {
+        let start = Epoch::from_et_seconds(0.0);
+        let end = start + 6.hours();
+
+        let mut builder = SPK::builder("TEST");
+        let refit = builder
+            .add_type2_segment_within("TEST", -10, 399, 1, start, end, 1e-6, |epoch| {
+                Ok(circular(epoch))
+            })
+            .unwrap();
+        assert!(refit.max_error <= 1e-6);
+        let spk = builder.build().unwrap();
+
+        let summary = spk.data_summaries().unwrap()[0];
+        let set = spk.nth_data::<Type2ChebyshevSet>(0).unwrap();
+        assert_eq!(set.degree(), refit.degree);
+        assert_eq!(set.num_records, refit.num_records);
+        // Fewer records than the same orbit fitted with the fixed degree of 12 every ten minutes
+        assert!(set.num_records < 36);
+
+        for minutes in [0.0, 7.3, 181.9, 359.99] {
+            let epoch = start + minutes.minutes();
+            let (pos_km, _) = set.evaluate(epoch, &summary).unwrap();
+            assert!((pos_km - circular(epoch).radius_km).norm() < 2e-6);
+        }
+
+        // State errors are returned as is
+        assert_eq!(
+            builder.add_type2_segment_within("TEST", -10, 399, 1, start, end, 1e-6, |_| {
+                Err(EphemerisError::Unreachable)
+            }),
+            Err(EphemerisError::Unreachable)
+        );
+    }
+
+    #[test]
+    fn type13_circular() {
+        let start = Epoch::from_et_seconds(0.0);
+        // Unequally spaced states, and more than 100 to exercise the epoch directory.
+        let states = (0..250)
+            .map(|i| circular(start + (30.0 * f64::from(i) + 5.0 * f64::from(i % 3)).seconds()))
+            .collect::<Vec<_>>();
+
+        let mut builder = SPK::builder("TEST");
+        assert!(builder
+            .add_type13_segment("TEST", -10, 399, 1, &states, 8)
+            .is_err());
+
+        builder
+            .add_type13_segment("TEST", -10, 399, 1, &states, 7)
+            .unwrap();
+        let spk = builder.build().unwrap();
+
+        let summary = spk.data_summaries().unwrap()[0];
+        let set = spk.nth_data::<HermiteSetType13>(0).unwrap();
+        assert_eq!(set.samples, 4);
+        assert_eq!(set.num_records, 250);
+        assert_eq!(set.epoch_registry.len(), 2);
+
+        // Stored states are returned exactly
+        let (pos_km, _) = set.evaluate(states[42].epoch, &summary).unwrap();
+        assert_eq!(pos_km, states[42].radius_km);
+
+        let epoch = start + 1234.5.seconds();
+        let (pos_km, vel_km_s) = set.evaluate(epoch, &summary).unwrap();
+        let expected = circular(epoch);
+        assert!((pos_km - expected.radius_km).norm() < 1e-6);
+        assert!((vel_km_s - expected.velocity_km_s).norm() < 1e-8);
+    }
+}
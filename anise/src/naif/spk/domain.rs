@@ -0,0 +1,111 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::Epoch;
+use snafu::ResultExt;
+
+use super::summary::SPKSummaryRecord;
+use crate::ephemerides::{EphemInterpolationSnafu, EphemerisError, SPKSnafu};
+use crate::naif::daf::datatypes::{
+    HermiteSetType13, LagrangeSetType9, Type2ChebyshevSet, Type3ChebyshevSet,
+};
+use crate::naif::daf::{DAFError, DafDataType, NAIFDataSet, NAIFSummaryRecord, RecordDomain, DAF};
+use crate::NaifId;
+
+impl DAF<SPKSummaryRecord> {
+    /// Returns the domain of the record used to interpolate the ephemeris of the provided ID at the provided epoch, e.g. to
+    /// diagnose interpolation artifacts near record boundaries, or to align a sampling grid with the records of a segment.
+    ///
+    /// Hermite segments use the default window selection policy, cf. `RecordDomain` for the domains of unequal step segments.
+    pub fn record_domain(&self, id: NaifId, epoch: Epoch) -> Result<RecordDomain, EphemerisError> {
+        let (summary, idx) = self.summary_from_id_at_epoch(id, epoch).context(SPKSnafu {
+            action: "searching for the segment of the record domain",
+        })?;
+
+        let action = "fetching data for the record domain";
+
+        match summary.data_type()? {
+            DafDataType::Type2ChebyshevTriplet => self
+                .nth_data::<Type2ChebyshevSet>(idx)
+                .context(SPKSnafu { action })?
+                .record_domain(epoch, summary),
+            DafDataType::Type3ChebyshevSextuplet => self
+                .nth_data::<Type3ChebyshevSet>(idx)
+                .context(SPKSnafu { action })?
+                .record_domain(epoch, summary),
+            DafDataType::Type9LagrangeUnequalStep => self
+                .nth_data::<LagrangeSetType9>(idx)
+                .context(SPKSnafu { action })?
+                .record_domain(epoch, summary),
+            DafDataType::Type13HermiteUnequalStep => self
+                .nth_data::<HermiteSetType13>(idx)
+                .context(SPKSnafu { action })?
+                .record_domain(epoch, summary),
+            dtype => {
+                return Err(EphemerisError::SPK {
+                    action: "record domain",
+                    source: DAFError::UnsupportedDatatype {
+                        dtype,
+                        kind: "SPK computations",
+                    },
+                })
+            }
+        }
+        .context(EphemInterpolationSnafu)
+    }
+}
+
+#[cfg(test)]
+mod ut_domain {
+    use crate::constants::celestial_objects::MOON;
+    use crate::naif::daf::datatypes::Type2ChebyshevSet;
+    use crate::naif::SPK;
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn chebyshev_record_domain() {
+        let spk = SPK::load("../data/de440s.bsp").unwrap();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1) + Unit::Hour * 7;
+
+        let domain = spk.record_domain(MOON, epoch).unwrap();
+        println!("{domain}");
+        assert!(domain.contains(epoch));
+        assert!(domain.normalized_time(epoch).abs() <= 1.0);
+
+        let (_, idx) = spk.summary_from_id_at_epoch(MOON, epoch).unwrap();
+        let data = spk.nth_data::<Type2ChebyshevSet>(idx).unwrap();
+        assert_eq!(domain.radius * 2, data.interval_length);
+        let start = data.init_epoch + data.interval_length * (domain.index as i64);
+        assert!((domain.start() - start).abs() < Unit::Microsecond * 1);
+
+        // The next record starts where this one ends
+        let next = spk
+            .record_domain(MOON, domain.end() + Unit::Second * 1)
+            .unwrap();
+        assert_eq!(next.index, domain.index + 1);
+        assert!((next.start() - domain.end()).abs() < Unit::Microsecond * 1);
+
+        assert!(spk
+            .record_domain(MOON, Epoch::from_gregorian_utc_at_midnight(1800, 1, 1))
+            .is_err());
+    }
+
+    #[test]
+    fn hermite_record_domain() {
+        let spk = SPK::load("../data/gmat-hermite.bsp").unwrap();
+        let (summary, _) = spk.summary_from_id(-10000001).unwrap();
+        let epoch = summary.start_epoch() + (summary.end_epoch() - summary.start_epoch()) / 3;
+
+        let domain = spk.record_domain(-10000001, epoch).unwrap();
+        println!("{domain}");
+        assert!(domain.contains(epoch));
+        assert!(domain.start() >= summary.start_epoch() && domain.end() <= summary.end_epoch());
+    }
+}
@@ -9,4 +9,8 @@
  */
 
 // Defines how to read an SPK
+pub mod builder;
+pub mod domain;
 pub mod summary;
+
+pub use builder::SPKBuilder;
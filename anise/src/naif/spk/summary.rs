@@ -153,6 +153,8 @@ impl NAIFRecord for SPKSummaryRecord {}
 
 impl NAIFSummaryRecord for SPKSummaryRecord {
     const NAME: &'static str = "SPKSummaryRecord";
+    const DAF_IDWORD: &'static str = "DAF/SPK";
+    const NI: u32 = 6;
 
     type Error = EphemerisError;
 
@@ -183,6 +185,10 @@ impl NAIFSummaryRecord for SPKSummaryRecord {
         self.target_id
     }
 
+    fn center_id(&self) -> i32 {
+        self.center_id
+    }
+
     fn start_epoch_et_s(&self) -> f64 {
         self.start_epoch_et_s
     }
@@ -0,0 +1,320 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Epoch, Unit};
+use snafu::ensure;
+
+use crate::math::interpolation::{
+    CorruptedDataSnafu, InterpolationError, NoInterpolationDataSnafu,
+};
+use crate::math::rotation::{Quaternion, DCM};
+use crate::math::{Vector3, Vector4};
+use crate::NaifId;
+
+/// Interpolation scheme used to query an attitude series between its nodes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AttitudeInterpolation {
+    /// Spherical linear interpolation: constant angular velocity between nodes (discontinuous at the nodes).
+    Slerp,
+    /// Spherical quadrangle interpolation: smooth (C1) interpolation through the nodes.
+    #[default]
+    Squad,
+}
+
+/// A time series of attitude (quaternion) data from one frame to another, which may be queried at arbitrary epochs.
+///
+/// This is typically used for sparse spacecraft attitude data, e.g. from an ADCS simulator or a CCSDS AEM.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttitudeSeries {
+    /// Source frame of all of the quaternions
+    pub from: NaifId,
+    /// Destination frame of all of the quaternions
+    pub to: NaifId,
+    /// Interpolation scheme
+    pub interpolation: AttitudeInterpolation,
+    epochs: Vec<Epoch>,
+    quaternions: Vec<Vector4>,
+}
+
+impl AttitudeSeries {
+    /// Builds a new attitude series from the provided time-tagged quaternions.
+    ///
+    /// The nodes are sorted by epoch, normalized, and their signs are made continuous (q and -q represent the same rotation)
+    /// to prevent the interpolation from taking the long way around.
+    ///
+    /// # Errors
+    /// + If fewer than two nodes are provided, if two nodes share an epoch, or if the frames of a quaternion do not match the series.
+    pub fn new(
+        from: NaifId,
+        to: NaifId,
+        mut nodes: Vec<(Epoch, Quaternion)>,
+        interpolation: AttitudeInterpolation,
+    ) -> Result<Self, InterpolationError> {
+        ensure!(
+            nodes.len() >= 2,
+            CorruptedDataSnafu {
+                what: "attitude series requires at least two nodes"
+            }
+        );
+
+        nodes.sort_by(|(e1, _), (e2, _)| e1.cmp(e2));
+
+        let mut epochs = Vec::with_capacity(nodes.len());
+        let mut quaternions: Vec<Vector4> = Vec::with_capacity(nodes.len());
+
+        for (epoch, q) in nodes {
+            ensure!(
+                q.from == from && q.to == to,
+                CorruptedDataSnafu {
+                    what: "quaternion frames differ from the attitude series frames"
+                }
+            );
+            ensure!(
+                epochs.last() != Some(&epoch),
+                CorruptedDataSnafu {
+                    what: "attitude series contains duplicate epochs"
+                }
+            );
+
+            let mut q = q.normalize().as_vector();
+            if let Some(prev) = quaternions.last() {
+                if prev.dot(&q) < 0.0 {
+                    q = -q;
+                }
+            }
+            epochs.push(epoch);
+            quaternions.push(q);
+        }
+
+        Ok(Self {
+            from,
+            to,
+            interpolation,
+            epochs,
+            quaternions,
+        })
+    }
+
+    /// Returns the start and end epochs of this series
+    pub fn domain(&self) -> (Epoch, Epoch) {
+        (self.epochs[0], *self.epochs.last().unwrap())
+    }
+
+    /// Returns the number of nodes in this series
+    pub fn len(&self) -> usize {
+        self.epochs.len()
+    }
+
+    /// Returns whether this series is empty (it never is once built)
+    pub fn is_empty(&self) -> bool {
+        self.epochs.is_empty()
+    }
+
+    /// Returns the interpolated quaternion at the provided epoch.
+    pub fn at(&self, epoch: Epoch) -> Result<Quaternion, InterpolationError> {
+        let (start, end) = self.domain();
+        ensure!(
+            epoch >= start && epoch <= end,
+            NoInterpolationDataSnafu {
+                req: epoch,
+                start,
+                end
+            }
+        );
+
+        // Find the interval [i, i+1] containing the epoch
+        let i = match self.epochs.binary_search(&epoch) {
+            Ok(idx) => {
+                let q = self.quaternions[idx];
+                return Ok(Quaternion::new(q[0], q[1], q[2], q[3], self.from, self.to));
+            }
+            Err(idx) => idx - 1,
+        };
+
+        let h = (epoch - self.epochs[i]).to_seconds()
+            / (self.epochs[i + 1] - self.epochs[i]).to_seconds();
+
+        let q0 = self.quaternions[i];
+        let q1 = self.quaternions[i + 1];
+
+        let q = match self.interpolation {
+            AttitudeInterpolation::Slerp => slerp(&q0, &q1, h),
+            AttitudeInterpolation::Squad => {
+                let s0 = self.squad_control(i);
+                let s1 = self.squad_control(i + 1);
+                slerp(
+                    &slerp(&q0, &q1, h),
+                    &slerp(&s0, &s1, h),
+                    2.0 * h * (1.0 - h),
+                )
+            }
+        };
+
+        Ok(Quaternion::new(q[0], q[1], q[2], q[3], self.from, self.to))
+    }
+
+    /// Returns the DCM at the provided epoch, including its time derivative estimated by central differencing of the interpolation.
+    pub fn dcm_at(&self, epoch: Epoch) -> Result<DCM, InterpolationError> {
+        let mut dcm: DCM = self.at(epoch)?.into();
+
+        let (start, end) = self.domain();
+        let step = Unit::Millisecond * 1;
+        let pre = if epoch - step < start {
+            epoch
+        } else {
+            epoch - step
+        };
+        let post = if epoch + step > end {
+            epoch
+        } else {
+            epoch + step
+        };
+
+        let dcm_pre: DCM = self.at(pre)?.into();
+        let dcm_post: DCM = self.at(post)?.into();
+
+        dcm.rot_mat_dt = Some((dcm_post.rot_mat - dcm_pre.rot_mat) / (post - pre).to_seconds());
+
+        Ok(dcm)
+    }
+
+    /// Returns the angular velocity in rad/s of the `to` frame with respect to the `from` frame, expressed in the `to` frame,
+    /// computed from the interpolated attitude. With SQUAD interpolation, this is continuous across the nodes.
+    pub fn angular_velocity_rad_s(&self, epoch: Epoch) -> Result<Vector3, InterpolationError> {
+        let dcm = self.dcm_at(epoch)?;
+        Ok(dcm
+            .angular_velocity_rad_s()
+            .expect("derivative is always set by dcm_at"))
+    }
+
+    /// Computes the SQUAD intermediate control quaternion of node i.
+    fn squad_control(&self, i: usize) -> Vector4 {
+        let qi = self.quaternions[i];
+        if i == 0 || i == self.quaternions.len() - 1 {
+            return qi;
+        }
+        let qi_inv = conjugate(&qi);
+        let log_next = quat_log(&hamilton(&qi_inv, &self.quaternions[i + 1]));
+        let log_prev = quat_log(&hamilton(&qi_inv, &self.quaternions[i - 1]));
+        hamilton(&qi, &quat_exp(&(-(log_next + log_prev) / 4.0)))
+    }
+}
+
+/// Hamilton product of two quaternions stored as [w, x, y, z]
+fn hamilton(p: &Vector4, q: &Vector4) -> Vector4 {
+    Vector4::new(
+        p[0] * q[0] - p[1] * q[1] - p[2] * q[2] - p[3] * q[3],
+        p[0] * q[1] + p[1] * q[0] + p[2] * q[3] - p[3] * q[2],
+        p[0] * q[2] - p[1] * q[3] + p[2] * q[0] + p[3] * q[1],
+        p[0] * q[3] + p[1] * q[2] - p[2] * q[1] + p[3] * q[0],
+    )
+}
+
+fn conjugate(q: &Vector4) -> Vector4 {
+    Vector4::new(q[0], -q[1], -q[2], -q[3])
+}
+
+/// Logarithm of a unit quaternion, returned as a pure quaternion
+fn quat_log(q: &Vector4) -> Vector4 {
+    let v = Vector3::new(q[1], q[2], q[3]);
+    let v_norm = v.norm();
+    if v_norm < f64::EPSILON {
+        return Vector4::zeros();
+    }
+    let angle = v_norm.atan2(q[0]);
+    let axis = v * angle / v_norm;
+    Vector4::new(0.0, axis.x, axis.y, axis.z)
+}
+
+/// Exponential of a pure quaternion
+fn quat_exp(q: &Vector4) -> Vector4 {
+    let v = Vector3::new(q[1], q[2], q[3]);
+    let angle = v.norm();
+    if angle < f64::EPSILON {
+        return Vector4::new(1.0, 0.0, 0.0, 0.0);
+    }
+    let (s, c) = angle.sin_cos();
+    let axis = v * s / angle;
+    Vector4::new(c, axis.x, axis.y, axis.z)
+}
+
+/// Spherical linear interpolation between two unit quaternions for h in [0, 1]
+fn slerp(q0: &Vector4, q1: &Vector4, h: f64) -> Vector4 {
+    let cos_theta = q0.dot(q1).clamp(-1.0, 1.0);
+    if cos_theta.abs() > 1.0 - 1e-12 {
+        // Nearly identical: linear interpolation avoids the division by zero
+        return (q0 * (1.0 - h) + q1 * h).normalize();
+    }
+    let theta = cos_theta.acos();
+    let sin_theta = theta.sin();
+    (q0 * ((1.0 - h) * theta).sin() + q1 * (h * theta).sin()) / sin_theta
+}
+
+#[cfg(test)]
+mod ut_attitude_series {
+    use super::*;
+    use hifitime::TimeUnits;
+
+    #[test]
+    fn constant_rate_spin() {
+        let rate_rad_s = 0.01_f64;
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+
+        let nodes = (0..10)
+            .map(|i| {
+                let epoch = start + (i * 60).seconds();
+                let angle = rate_rad_s * f64::from(i * 60);
+                (epoch, Quaternion::about_z(angle, 1, -1000))
+            })
+            .collect::<Vec<_>>();
+
+        for interpolation in [AttitudeInterpolation::Slerp, AttitudeInterpolation::Squad] {
+            let series = AttitudeSeries::new(1, -1000, nodes.clone(), interpolation).unwrap();
+            assert_eq!(series.len(), 10);
+
+            let epoch = start + 95.seconds();
+            let q = series.at(epoch).unwrap();
+            let expected = Quaternion::about_z(rate_rad_s * 95.0, 1, -1000);
+            assert!(
+                (q.as_vector() - expected.as_vector()).norm() < 1e-9,
+                "{interpolation:?}: got {q} expected {expected}"
+            );
+
+            let omega = series.angular_velocity_rad_s(epoch).unwrap();
+            assert!((omega.norm() - rate_rad_s).abs() < 1e-6, "{omega}");
+
+            // Nodes are returned exactly
+            let q_node = series.at(start + 120.seconds()).unwrap();
+            let expected = Quaternion::about_z(rate_rad_s * 120.0, 1, -1000);
+            assert!((q_node.as_vector() - expected.as_vector()).norm() < 1e-12);
+
+            // Outside of the domain
+            assert!(series.at(start - 1.seconds()).is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_series() {
+        let start = Epoch::from_gregorian_utc_at_midnight(2025, 1, 1);
+        let q = Quaternion::about_x(0.1, 1, -1000);
+        assert!(AttitudeSeries::new(1, -1000, vec![(start, q)], Default::default()).is_err());
+        assert!(
+            AttitudeSeries::new(1, -1000, vec![(start, q), (start, q)], Default::default())
+                .is_err()
+        );
+        assert!(AttitudeSeries::new(
+            1,
+            -2000,
+            vec![(start, q), (start + 1.seconds(), q)],
+            Default::default()
+        )
+        .is_err());
+    }
+}
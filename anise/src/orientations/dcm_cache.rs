@@ -0,0 +1,241 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use hifitime::{Duration, Epoch};
+
+use crate::math::rotation::DCM;
+use crate::NaifId;
+
+/// Orientation IDs of the rotation and index of the epoch quantum.
+type DcmKey = (NaifId, NaifId, i128);
+
+#[derive(Default)]
+struct DcmLru {
+    /// DCM and last use of each key
+    entries: HashMap<DcmKey, (DCM, u64)>,
+    /// Key of each last use, oldest first
+    recency: BTreeMap<u64, DcmKey>,
+    /// Incremented on each use
+    clock: u64,
+}
+
+/// Least recently used cache of rotations, keyed by the pair of orientations and the epoch quantized to a fixed granularity,
+/// e.g. to avoid recomputing the ITRF93 to J2000 rotation for each ground station of an AER or access report at the same epoch.
+///
+/// On a miss, the rotation is computed at the center of the quantum of the requested epoch, so the result does not depend on
+/// the order of the queries. The rotation is then off by up to half the quantum (e.g. about 0.23 m on the surface of the
+/// Earth with a quantum of 1 ms), so the quantum must be chosen according to the required accuracy.
+///
+/// Clones of an Almanac start with an empty cache of the same settings, since they may load different data.
+pub struct DcmCache {
+    capacity: usize,
+    quantum: Duration,
+    lru: Mutex<DcmLru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl DcmCache {
+    /// Initializes a cache of up to `capacity` rotations (at least one), quantized to `quantum` (at least one nanosecond).
+    pub fn new(capacity: usize, quantum: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            quantum: quantum.max(Duration::from_total_nanoseconds(1)),
+            lru: Mutex::new(DcmLru::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn quantum(&self) -> Duration {
+        self.quantum
+    }
+
+    /// Returns the number of queries served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of queries which computed the rotation.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of cached rotations.
+    pub fn len(&self) -> usize {
+        self.lru.lock().map_or(0, |lru| lru.entries.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clears all of the cached rotations and the statistics.
+    pub fn clear(&self) {
+        if let Ok(mut lru) = self.lru.lock() {
+            *lru = DcmLru::default();
+        }
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns the index of the quantum of the provided epoch, i.e. the epoch rounded to the nearest multiple of the quantum.
+    fn quantum_index(&self, epoch: Epoch) -> i128 {
+        let quantum_ns = self.quantum.total_nanoseconds();
+        (epoch.to_tdb_duration().total_nanoseconds() + quantum_ns / 2).div_euclid(quantum_ns)
+    }
+
+    /// Returns the cached rotation from `from` to `to` in the quantum of the epoch, or computes it at the center of the quantum
+    /// with the provided function and caches it, evicting the least recently used rotation if the cache is full.
+    ///
+    /// Errors are returned as is and not cached.
+    pub fn get_or_insert_with<E, F>(
+        &self,
+        from: NaifId,
+        to: NaifId,
+        epoch: Epoch,
+        compute: F,
+    ) -> Result<DCM, E>
+    where
+        F: FnOnce(Epoch) -> Result<DCM, E>,
+    {
+        let index = self.quantum_index(epoch);
+        let key = (from, to, index);
+
+        if let Ok(mut lru) = self.lru.lock() {
+            lru.clock += 1;
+            let now = lru.clock;
+            if let Some((dcm, last_use)) = lru.entries.get_mut(&key) {
+                let dcm = *dcm;
+                let prev_use = core::mem::replace(last_use, now);
+                lru.recency.remove(&prev_use);
+                lru.recency.insert(now, key);
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(dcm);
+            }
+        }
+
+        // Compute without holding the lock, since the computation may be slow.
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let quantized_epoch = Epoch::from_tdb_duration(Duration::from_total_nanoseconds(
+            index * self.quantum.total_nanoseconds(),
+        ));
+        let dcm = compute(quantized_epoch)?;
+
+        if let Ok(mut lru) = self.lru.lock() {
+            lru.clock += 1;
+            let now = lru.clock;
+            if let Some((_, prev_use)) = lru.entries.insert(key, (dcm, now)) {
+                // Another thread computed the same rotation in the meantime
+                lru.recency.remove(&prev_use);
+            }
+            lru.recency.insert(now, key);
+            while lru.entries.len() > self.capacity {
+                match lru.recency.pop_first() {
+                    Some((_, oldest)) => {
+                        lru.entries.remove(&oldest);
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        Ok(dcm)
+    }
+}
+
+impl Clone for DcmCache {
+    fn clone(&self) -> Self {
+        Self::new(self.capacity, self.quantum)
+    }
+}
+
+impl fmt::Debug for DcmCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DcmCache {{ capacity: {}, quantum: {}, hits: {}, misses: {} }}",
+            self.capacity,
+            self.quantum,
+            self.hits(),
+            self.misses()
+        )
+    }
+}
+
+#[cfg(test)]
+mod ut_dcm_cache {
+    use super::{DcmCache, DCM};
+    use hifitime::{Epoch, Unit};
+
+    #[test]
+    fn lru_quantized() {
+        let cache = DcmCache::new(2, Unit::Millisecond * 1);
+        let epoch = Epoch::from_tdb_seconds(100.0);
+        let compute = |epoch: Epoch| -> Result<DCM, ()> {
+            let mut dcm = DCM::identity(1, 2);
+            dcm.rot_mat[(0, 1)] = epoch.to_tdb_seconds();
+            Ok(dcm)
+        };
+
+        // Computed at the center of the quantum
+        let dcm = cache
+            .get_or_insert_with(1, 2, epoch + Unit::Microsecond * 400, compute)
+            .unwrap();
+        assert!((dcm.rot_mat[(0, 1)] - 100.0).abs() < 1e-9);
+        let dcm = cache
+            .get_or_insert_with(1, 2, epoch - Unit::Microsecond * 499, compute)
+            .unwrap();
+        assert!((dcm.rot_mat[(0, 1)] - 100.0).abs() < 1e-9);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // Other quantum and other pair of frames
+        let dcm = cache
+            .get_or_insert_with(1, 2, epoch + Unit::Microsecond * 600, compute)
+            .unwrap();
+        assert!((dcm.rot_mat[(0, 1)] - 100.001).abs() < 1e-9);
+        cache
+            .get_or_insert_with(2, 1, epoch, |_| Ok::<DCM, ()>(DCM::identity(2, 1)))
+            .unwrap();
+        assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 3, 2));
+
+        // The first rotation was evicted, but not the second one
+        cache.get_or_insert_with(1, 2, epoch, compute).unwrap();
+        assert_eq!(cache.misses(), 4);
+        cache.get_or_insert_with(2, 1, epoch, compute).unwrap();
+        assert_eq!(cache.hits(), 2);
+
+        // Errors are not cached
+        assert!(cache
+            .get_or_insert_with(3, 4, epoch, |_| Err::<DCM, ()>(()))
+            .is_err());
+        assert_eq!(cache.len(), 2);
+
+        // Clones start empty
+        let clone = cache.clone();
+        assert!(clone.is_empty() && clone.hits() == 0);
+        assert_eq!(
+            (clone.capacity(), clone.quantum()),
+            (2, Unit::Millisecond * 1)
+        );
+
+        cache.clear();
+        assert!(cache.is_empty() && cache.misses() == 0);
+    }
+}
@@ -12,13 +12,19 @@ use hifitime::Epoch;
 use snafu::prelude::*;
 
 use crate::{
-    errors::PhysicsError, math::interpolation::InterpolationError, naif::daf::DAFError,
-    prelude::FrameUid, structure::dataset::DataSetError,
+    ephemerides::EphemerisError, errors::PhysicsError, math::interpolation::InterpolationError,
+    naif::daf::DAFError, prelude::FrameUid, structure::dataset::DataSetError, NaifId,
 };
 
+pub mod attitude_series;
+pub mod dcm_cache;
 mod paths;
+pub mod profiles;
 mod rotate_to_parent;
 mod rotations;
+pub mod two_vector;
+
+pub use dcm_cache::DcmCache;
 
 #[derive(Debug, Snafu, PartialEq)]
 #[snafu(visibility(pub(crate)))]
@@ -64,4 +70,16 @@ pub enum OrientationError {
     },
     #[snafu(display("unknown orientation ID associated with `{name}`"))]
     OrientationNameToId { name: String },
+    #[snafu(display("invalid two-vector frame {orientation_id}: {reason}"))]
+    TwoVectorFrame {
+        orientation_id: NaifId,
+        reason: String,
+    },
+    #[snafu(display("when {action} of dynamic frame {orientation_id} {source}"))]
+    DynamicFrameEphemeris {
+        action: &'static str,
+        orientation_id: NaifId,
+        #[snafu(backtrace)]
+        source: EphemerisError,
+    },
 }
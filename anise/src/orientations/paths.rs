@@ -29,8 +29,12 @@ impl Almanac {
     /// 1. For each loaded BPC, iterated in reverse order (to mimic SPICE behavior)
     /// 2. For each summary record in each BPC, follow the orientation branch all the way up until the end of this BPC or until the J2000.
     pub fn try_find_orientation_root(&self) -> Result<NaifId, OrientationError> {
+        // Synodic frames only require ephemeris data.
         ensure!(
-            self.num_loaded_bpc() > 0 || !self.planetary_data.is_empty(),
+            self.num_loaded_bpc() > 0
+                || !self.planetary_data.is_empty()
+                || !self.dynamic_frames.is_empty()
+                || self.num_loaded_spk() > 0,
             NoOrientationsLoadedSnafu
         );
 
@@ -69,94 +73,107 @@ impl Almanac {
             }
         }
 
-        if common_center == ECLIPJ2000 {
-            // Rotation from ecliptic J2000 to J2000 is embedded.
+        if common_center == ECLIPJ2000 || common_center == i32::MAX {
+            // Rotation from ecliptic J2000 to J2000 is embedded, and dynamic frames are ultimately defined relative to J2000.
             common_center = J2000;
         }
 
         Ok(common_center)
     }
 
+    /// Returns the ID of the parent of the provided orientation, searching the dynamic frames, the loaded BPCs,
+    /// the planetary data, and finally the Euler parameters.
+    fn orientation_parent_id(&self, id: NaifId, epoch: Epoch) -> Result<NaifId, OrientationError> {
+        if let Some(frame) = self.dynamic_frame(id) {
+            return Ok(frame.relative_orientation_id);
+        }
+
+        match self.bpc_summary_at_epoch(id, epoch) {
+            Ok((summary, _, _)) => Ok(summary.inertial_frame_id),
+            Err(_) => {
+                // Not available as a BPC, so let's see if there's planetary data for it.
+                match self.planetary_data.get_by_id(id) {
+                    Ok(planetary_data) => Ok(planetary_data.parent_id),
+                    Err(_) => {
+                        // Finally, let's see if it's in the loaded Euler Parameters.
+                        Ok(self
+                            .euler_param_data
+                            .get_by_id(id)
+                            .context(OrientationDataSetSnafu)?
+                            .to)
+                    }
+                }
+            }
+        }
+    }
+
     /// Try to construct the path from the source frame all the way to the root orientation of this context.
     pub fn orientation_path_to_root(
         &self,
         source: Frame,
         epoch: Epoch,
     ) -> Result<(usize, [Option<NaifId>; MAX_TREE_DEPTH]), OrientationError> {
-        let common_center = self.try_find_orientation_root()?;
+        let (of_path_len, of_path, result) = self.orientation_path_walk(source, epoch);
+        result.map(|_| (of_path_len, of_path))
+    }
+
+    /// Walks the path from the source frame to the root orientation of this context, returning the nodes reached so far
+    /// and, if the walk stopped before the root, the error of the hop which failed.
+    pub(crate) fn orientation_path_walk(
+        &self,
+        source: Frame,
+        epoch: Epoch,
+    ) -> (
+        usize,
+        [Option<NaifId>; MAX_TREE_DEPTH],
+        Result<(), OrientationError>,
+    ) {
         // Build a tree, set a fixed depth to avoid allocations
         let mut of_path = [None; MAX_TREE_DEPTH];
         let mut of_path_len = 0;
 
+        let common_center = match self.try_find_orientation_root() {
+            Ok(common_center) => common_center,
+            Err(e) => return (of_path_len, of_path, Err(e)),
+        };
+
         if common_center == source.orientation_id {
             // We're querying the source, no need to check that this summary even exists.
-            return Ok((of_path_len, of_path));
+            return (of_path_len, of_path, Ok(()));
         }
 
-        // Grab the summary data, which we use to find the paths
-        // Let's see if this orientation is defined in the loaded BPC files
-        let mut inertial_frame_id = match self.bpc_summary_at_epoch(source.orientation_id, epoch) {
-            Ok((summary, _, _)) => summary.inertial_frame_id,
-            Err(_) => {
-                // Not available as a BPC, so let's see if there's planetary data for it.
-                match self.planetary_data.get_by_id(source.orientation_id) {
-                    Ok(planetary_data) => planetary_data.parent_id,
-                    Err(_) => {
-                        // Finally, let's see if it's in the loaded Euler Parameters.
-                        self.euler_param_data
-                            .get_by_id(source.orientation_id)
-                            .context(OrientationDataSetSnafu)?
-                            .to
-                    }
-                }
-            }
-        };
+        let mut inertial_frame_id = source.orientation_id;
 
-        of_path[of_path_len] = Some(inertial_frame_id);
-        of_path_len += 1;
-
-        if inertial_frame_id == ECLIPJ2000 {
-            // Add the hop to J2000
-            inertial_frame_id = J2000;
+        while of_path_len < MAX_TREE_DEPTH {
+            // Let's see if this orientation is defined in the loaded BPC files, or any other orientation data
+            inertial_frame_id = match self.orientation_parent_id(inertial_frame_id, epoch) {
+                Ok(parent_id) => parent_id,
+                Err(e) => return (of_path_len, of_path, Err(e)),
+            };
             of_path[of_path_len] = Some(inertial_frame_id);
             of_path_len += 1;
-        }
-
-        if inertial_frame_id == common_center {
-            // Well that was quick!
-            return Ok((of_path_len, of_path));
-        }
 
-        for _ in 0..MAX_TREE_DEPTH - 1 {
-            inertial_frame_id = match self.bpc_summary_at_epoch(inertial_frame_id, epoch) {
-                Ok((summary, _, _)) => summary.inertial_frame_id,
-                Err(_) => {
-                    // Not available as a BPC, so let's see if there's planetary data for it.
-                    match self.planetary_data.get_by_id(inertial_frame_id) {
-                        Ok(planetary_data) => planetary_data.parent_id,
-                        Err(_) => {
-                            // Finally, let's see if it's in the loaded Euler Parameters.
-                            self.euler_param_data
-                                .get_by_id(inertial_frame_id)
-                                .context(OrientationDataSetSnafu)?
-                                .to
-                        }
-                    }
-                }
-            };
+            if of_path_len == 1 && inertial_frame_id == ECLIPJ2000 {
+                // Add the hop to J2000
+                inertial_frame_id = J2000;
+                of_path[of_path_len] = Some(inertial_frame_id);
+                of_path_len += 1;
+            }
 
-            of_path[of_path_len] = Some(inertial_frame_id);
-            of_path_len += 1;
             if inertial_frame_id == common_center {
                 // We're found the path!
-                return Ok((of_path_len, of_path));
+                return (of_path_len, of_path, Ok(()));
             }
         }
 
-        Err(OrientationError::BPC {
-            action: "computing path to common node",
-            source: DAFError::MaxRecursionDepth,
-        })
+        (
+            of_path_len,
+            of_path,
+            Err(OrientationError::BPC {
+                action: "computing path to common node",
+                source: DAFError::MaxRecursionDepth,
+            }),
+        )
     }
 
     /// Returns the orientation path between two frames and the common node. This may return a `DisjointRoots` error if the frames do not share a common root, which is considered a file integrity error.
@@ -0,0 +1,299 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use hifitime::{Duration, Epoch, TimeSeries};
+use snafu::ResultExt;
+
+use super::attitude_series::{AttitudeInterpolation, AttitudeSeries};
+use super::two_vector::{FrameAxis, FrameVector, TwoVectorFrame};
+use super::{OrientationError, OrientationInterpolationSnafu};
+use crate::almanac::Almanac;
+use crate::constants::celestial_objects::SUN;
+use crate::constants::orientations::J2000;
+use crate::math::rotation::{Quaternion, DCM};
+use crate::math::Matrix3;
+use crate::NaifId;
+
+/// Standard pointing law of a spacecraft, defining its body frame with respect to J2000.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PointingLaw {
+    /// Body +Z axis toward the center of the central body, and body +X axis toward the velocity (the body +Y axis is along the negative orbit normal)
+    Nadir { center_id: NaifId },
+    /// Body +Z axis toward the center of the central body, yawed about that axis such that the Sun is in the body XZ plane on the +X side:
+    /// solar arrays along the body Y axis may track the Sun by rotating about that axis
+    NadirYawSteering { center_id: NaifId },
+    /// Body +Z axis toward the Sun, and body +X axis toward the center of the central body
+    SunPointing { center_id: NaifId },
+    /// Body +Z axis toward the target, and body +X axis toward the velocity with respect to the central body
+    TargetTracking {
+        target_id: NaifId,
+        center_id: NaifId,
+    },
+    /// Constant rotation from J2000 to the body frame
+    InertialHold(Quaternion),
+}
+
+/// Attitude profile of a spacecraft whose trajectory is in the loaded ephemerides, following a standard pointing law.
+///
+/// The attitude is the rotation from J2000 to the body frame, so it may directly be used as the spacecraft attitude of an
+/// `Instrument` whose mounting starts from the body frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AttitudeProfile {
+    /// Ephemeris ID of the spacecraft
+    pub spacecraft_id: NaifId,
+    /// Orientation ID of the body frame of the spacecraft
+    pub body_id: NaifId,
+    pub law: PointingLaw,
+}
+
+impl AttitudeProfile {
+    /// Returns the two-vector frame implementing this pointing law, or None for an inertial hold.
+    ///
+    /// This frame may be added to an Almanac with `with_two_vector_frame` so that the body frame may be used in any rotation.
+    pub fn two_vector_frame(&self) -> Option<TwoVectorFrame> {
+        let position = |observer_id, target_id| FrameVector::ObserverTargetPosition {
+            observer_id,
+            target_id,
+            ab_corr: None,
+        };
+        let velocity = |center_id| FrameVector::ObserverTargetVelocity {
+            observer_id: center_id,
+            target_id: self.spacecraft_id,
+            orientation_id: J2000,
+            ab_corr: None,
+        };
+
+        let (primary, secondary) = match self.law {
+            PointingLaw::Nadir { center_id } => {
+                (position(self.spacecraft_id, center_id), velocity(center_id))
+            }
+            PointingLaw::NadirYawSteering { center_id } => (
+                position(self.spacecraft_id, center_id),
+                position(self.spacecraft_id, SUN),
+            ),
+            PointingLaw::SunPointing { center_id } => (
+                position(self.spacecraft_id, SUN),
+                position(self.spacecraft_id, center_id),
+            ),
+            PointingLaw::TargetTracking {
+                target_id,
+                center_id,
+            } => (position(self.spacecraft_id, target_id), velocity(center_id)),
+            PointingLaw::InertialHold(_) => return None,
+        };
+
+        Some(TwoVectorFrame {
+            orientation_id: self.body_id,
+            relative_orientation_id: J2000,
+            primary_axis: FrameAxis::Z,
+            primary,
+            secondary_axis: FrameAxis::X,
+            secondary,
+        })
+    }
+}
+
+impl Almanac {
+    /// Returns the attitude of the spacecraft at the provided epoch, i.e. the DCM from J2000 to its body frame, including its
+    /// time derivative (zero for an inertial hold).
+    ///
+    /// # Errors
+    /// + If the ephemerides needed by the pointing law are not loaded, or if its two directions are colinear.
+    pub fn attitude_dcm(
+        &self,
+        profile: &AttitudeProfile,
+        epoch: Epoch,
+    ) -> Result<DCM, OrientationError> {
+        match profile.law {
+            PointingLaw::InertialHold(q) => {
+                let mut dcm: DCM = Quaternion {
+                    from: J2000,
+                    to: profile.body_id,
+                    ..q
+                }
+                .into();
+                dcm.rot_mat_dt = Some(Matrix3::zeros());
+                Ok(dcm)
+            }
+            _ => {
+                let frame = profile
+                    .two_vector_frame()
+                    .ok_or(OrientationError::Unreachable)?;
+                self.two_vector_rotation(&frame, epoch)
+            }
+        }
+    }
+
+    /// Returns the Euler parameters (quaternions) of the attitude of the spacecraft, from J2000 to its body frame, sampled
+    /// every `step` from `start` to `end` inclusively.
+    pub fn attitude_quaternions(
+        &self,
+        profile: &AttitudeProfile,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+    ) -> Result<Vec<(Epoch, Quaternion)>, OrientationError> {
+        TimeSeries::inclusive(start, end, step)
+            .map(|epoch| {
+                self.attitude_dcm(profile, epoch)
+                    .map(|dcm| (epoch, Quaternion::from(dcm)))
+            })
+            .collect()
+    }
+
+    /// Returns the attitude of the spacecraft sampled every `step` from `start` to `end` as an attitude series, which may then
+    /// be queried at any epoch in that domain without the ephemerides.
+    pub fn attitude_series(
+        &self,
+        profile: &AttitudeProfile,
+        start: Epoch,
+        end: Epoch,
+        step: Duration,
+        interpolation: AttitudeInterpolation,
+    ) -> Result<AttitudeSeries, OrientationError> {
+        let nodes = self.attitude_quaternions(profile, start, end, step)?;
+        AttitudeSeries::new(J2000, profile.body_id, nodes, interpolation)
+            .context(OrientationInterpolationSnafu)
+    }
+}
+
+#[cfg(test)]
+mod ut_profiles {
+    use super::{AttitudeProfile, PointingLaw};
+    use crate::constants::celestial_objects::{EARTH, MOON, SUN};
+    use crate::constants::frames::EARTH_J2000;
+    use crate::constants::orientations::J2000;
+    use crate::instrument::{FovShape, Instrument};
+    use crate::math::rotation::{Quaternion, DCM};
+    use crate::math::Vector3;
+    use crate::orientations::attitude_series::AttitudeInterpolation;
+    use crate::prelude::{Almanac, Frame};
+    use hifitime::{Epoch, TimeScale, Unit};
+
+    #[test]
+    fn pointing_laws() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .load("../data/gmat-hermite.bsp")
+            .unwrap();
+
+        let sc_id = -10000001;
+        let body_id = -100;
+        let epoch = Epoch::from_gregorian_hms(2000, 1, 1, 14, 0, 0, TimeScale::UTC);
+
+        let sc = almanac
+            .translate(Frame::from_ephem_j2000(sc_id), EARTH_J2000, epoch, None)
+            .unwrap();
+        let direction_of = |id| {
+            let state = almanac
+                .translate(Frame::from_ephem_j2000(id), EARTH_J2000, epoch, None)
+                .unwrap();
+            (state.radius_km - sc.radius_km).normalize()
+        };
+        let nadir = -sc.radius_km.normalize();
+
+        let profile = |law| AttitudeProfile {
+            spacecraft_id: sc_id,
+            body_id,
+            law,
+        };
+
+        // Nadir: the boresight of a nadir imager points to the center of the Earth
+        let dcm = almanac
+            .attitude_dcm(&profile(PointingLaw::Nadir { center_id: EARTH }), epoch)
+            .unwrap();
+        assert_eq!((dcm.from, dcm.to), (J2000, body_id));
+        assert!((dcm.rot_mat * nadir - Vector3::z()).norm() < 1e-12);
+        assert!((dcm.rot_mat * sc.velocity_km_s).x > 0.0);
+        assert!((dcm.rot_mat * sc.radius_km.cross(&sc.velocity_km_s)).y < 0.0);
+
+        let imager = Instrument {
+            mounting: DCM::identity(body_id, -100_001),
+            fov: FovShape::Circular {
+                half_angle_deg: 1.0,
+            },
+        };
+        let earth = almanac
+            .translate(Frame::from_ephem_j2000(EARTH), EARTH_J2000, epoch, None)
+            .unwrap();
+        assert!(imager.is_in_fov(sc, dcm, earth).unwrap());
+
+        // Yaw steering: still nadir pointing, with the Sun in the body XZ plane
+        let dcm = almanac
+            .attitude_dcm(
+                &profile(PointingLaw::NadirYawSteering { center_id: EARTH }),
+                epoch,
+            )
+            .unwrap();
+        let sun_body = dcm.rot_mat * direction_of(SUN);
+        assert!((dcm.rot_mat * nadir - Vector3::z()).norm() < 1e-12);
+        assert!(sun_body.y.abs() < 1e-12 && sun_body.x > 0.0);
+
+        // Sun pointing and target tracking
+        let dcm = almanac
+            .attitude_dcm(
+                &profile(PointingLaw::SunPointing { center_id: EARTH }),
+                epoch,
+            )
+            .unwrap();
+        assert!((dcm.rot_mat * direction_of(SUN) - Vector3::z()).norm() < 1e-12);
+        assert!((dcm.rot_mat * nadir).y.abs() < 1e-12);
+
+        let tracking = profile(PointingLaw::TargetTracking {
+            target_id: MOON,
+            center_id: EARTH,
+        });
+        let dcm = almanac.attitude_dcm(&tracking, epoch).unwrap();
+        assert!((dcm.rot_mat * direction_of(MOON) - Vector3::z()).norm() < 1e-12);
+
+        // Inertial hold has no angular velocity
+        let hold = profile(PointingLaw::InertialHold(Quaternion::about_z(
+            0.5, J2000, body_id,
+        )));
+        let dcm = almanac.attitude_dcm(&hold, epoch).unwrap();
+        assert!(dcm.angular_velocity_rad_s().unwrap().norm() < f64::EPSILON);
+        assert!(hold.two_vector_frame().is_none());
+
+        // The nadir attitude rotates at the orbital rate
+        let nadir_profile = profile(PointingLaw::Nadir { center_id: EARTH });
+        let dcm = almanac.attitude_dcm(&nadir_profile, epoch).unwrap();
+        let orbit_rate = sc.radius_km.cross(&sc.velocity_km_s).norm() / sc.radius_km.norm_squared();
+        let rate = dcm.angular_velocity_rad_s().unwrap().norm();
+        assert!(
+            (rate - orbit_rate).abs() / orbit_rate < 1e-3,
+            "{rate} vs {orbit_rate}"
+        );
+
+        // The Euler parameters sampled every minute are interpolated as an attitude series
+        let series = almanac
+            .attitude_series(
+                &nadir_profile,
+                epoch,
+                epoch + Unit::Minute * 30,
+                Unit::Minute * 1,
+                AttitudeInterpolation::Squad,
+            )
+            .unwrap();
+        assert_eq!(series.len(), 31);
+        let mid = epoch + Unit::Second * 930;
+        let interpolated: DCM = series.at(mid).unwrap().into();
+        let exact = almanac.attitude_dcm(&nadir_profile, mid).unwrap();
+        assert!((interpolated.rot_mat - exact.rot_mat).norm() < 1e-5);
+
+        // Missing ephemerides
+        let unknown = AttitudeProfile {
+            spacecraft_id: -999,
+            ..nadir_profile
+        };
+        assert!(almanac
+            .attitude_dcm(&unknown, Epoch::from_gregorian_utc_at_midnight(2024, 1, 1))
+            .is_err());
+    }
+}
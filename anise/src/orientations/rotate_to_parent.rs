@@ -45,6 +45,11 @@ impl Almanac {
                 to: ECLIPJ2000,
             });
         }
+        // Dynamic frames are computed from the ephemerides and take precedence over the loaded BPC files
+        if let Some(frame) = self.dynamic_frame(source.orientation_id) {
+            trace!("query {source} wrt to its parent @ {epoch:E} using two-vector frame");
+            return self.two_vector_rotation(&frame, epoch);
+        }
         // Let's see if this orientation is defined in the loaded BPC files
         match self.bpc_summary_at_epoch(source.orientation_id, epoch) {
             Ok((summary, bpc_no, idx_in_bpc)) => {
@@ -10,20 +10,28 @@
 
 use snafu::ResultExt;
 
+use super::DcmCache;
 use super::OrientationError;
 use super::OrientationPhysicsSnafu;
 use crate::almanac::Almanac;
 use crate::constants::orientations::J2000;
-use crate::hifitime::Epoch;
+use crate::hifitime::{Duration, Epoch, Unit};
 use crate::math::cartesian::CartesianState;
 use crate::math::rotation::DCM;
 use crate::math::units::*;
 use crate::math::Vector3;
 use crate::prelude::Frame;
 
+#[cfg(feature = "python")]
+use ndarray::Array1;
+#[cfg(feature = "python")]
+use numpy::PyArray1;
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+/// Half step, in seconds, of the central differencing of rotations which do not provide their time derivative.
+const ANGULAR_VELOCITY_FD_STEP_S: f64 = 0.1;
+
 #[cfg_attr(feature = "python", pymethods)]
 impl Almanac {
     /// Returns the 6x6 DCM needed to rotation the `from_frame` to the `to_frame`.
@@ -33,11 +41,71 @@ impl Almanac {
     ///
     /// # Note
     /// This function performs a recursion of no more than twice the MAX_TREE_DEPTH.
+    ///
+    /// # Cache
+    /// If this Almanac has a DCM cache (cf. `with_dcm_cache`), the rotation is computed at the center of the quantum of the epoch.
     pub fn rotate(
         &self,
         from_frame: Frame,
         to_frame: Frame,
         epoch: Epoch,
+    ) -> Result<DCM, OrientationError> {
+        match &self.dcm_cache {
+            Some(cache) if from_frame.orientation_id != to_frame.orientation_id => cache
+                .get_or_insert_with(
+                    from_frame.orientation_id,
+                    to_frame.orientation_id,
+                    epoch,
+                    |epoch| self.rotate_uncached(from_frame, to_frame, epoch),
+                ),
+            _ => self.rotate_uncached(from_frame, to_frame, epoch),
+        }
+    }
+
+    /// Returns a copy of this Almanac which caches up to `capacity` rotations, computed at the center of each `quantum` of time,
+    /// e.g. to compute the ITRF93 to J2000 rotation once per epoch in AER and access reports of many ground stations.
+    ///
+    /// The cached rotations lag or lead by up to half of the quantum: with 1 ms, that is about 0.23 m on the surface of the Earth.
+    ///
+    /// :type capacity: int
+    /// :type quantum: Duration
+    /// :rtype: Almanac
+    pub fn with_dcm_cache(&self, capacity: usize, quantum: Duration) -> Self {
+        let mut me = self.clone();
+        me.dcm_cache = Some(DcmCache::new(capacity, quantum));
+        me
+    }
+
+    /// Returns a copy of this Almanac without a DCM cache.
+    ///
+    /// :rtype: Almanac
+    pub fn without_dcm_cache(&self) -> Self {
+        let mut me = self.clone();
+        me.dcm_cache = None;
+        me
+    }
+}
+
+impl Almanac {
+    /// Rotates the provided Cartesian state into the requested observer frame
+    ///
+    /// **WARNING:** This function only performs the translation and no rotation _whatsoever_. Use the `transform_to` function instead to include rotations.
+    pub fn rotate_to(
+        &self,
+        state: CartesianState,
+        observer_frame: Frame,
+    ) -> Result<CartesianState, OrientationError> {
+        let dcm = self.rotate(state.frame, observer_frame, state.epoch)?;
+
+        (dcm * state).context(OrientationPhysicsSnafu {})
+    }
+
+    /// Returns the DCM from the `from_frame` to the `to_frame` without using the DCM cache, cf. `rotate`.
+    pub fn rotate_uncached(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
     ) -> Result<DCM, OrientationError> {
         let mut to_frame: Frame = to_frame;
 
@@ -114,20 +182,6 @@ impl Almanac {
             (dcm_bwrd * dcm_fwrd).context(OrientationPhysicsSnafu)
         }
     }
-
-    /// Rotates the provided Cartesian state into the requested observer frame
-    ///
-    /// **WARNING:** This function only performs the translation and no rotation _whatsoever_. Use the `transform_to` function instead to include rotations.
-    #[allow(clippy::too_many_arguments)]
-    pub fn rotate_to(
-        &self,
-        state: CartesianState,
-        observer_frame: Frame,
-    ) -> Result<CartesianState, OrientationError> {
-        let dcm = self.rotate(state.frame, observer_frame, state.epoch)?;
-
-        (dcm * state).context(OrientationPhysicsSnafu {})
-    }
 }
 
 impl Almanac {
@@ -161,3 +215,58 @@ impl Almanac {
         (dcm * input_state).context(OrientationPhysicsSnafu {})
     }
 }
+
+impl Almanac {
+    /// Returns the angular velocity vector in rad/s of the `to_frame` with respect to the `from_frame`, expressed in the `to_frame`.
+    ///
+    /// If the rotation between these frames does not provide its time derivative, it is computed by central differencing
+    /// of the rotation matrices 0.1 seconds before and after the requested epoch.
+    pub fn angular_velocity(
+        &self,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+    ) -> Result<Vector3, OrientationError> {
+        let mut dcm = self.rotate(from_frame, to_frame, epoch)?;
+
+        if dcm.rot_mat_dt.is_none() {
+            let step = Unit::Second * ANGULAR_VELOCITY_FD_STEP_S;
+            let dcm_pre = self.rotate(from_frame, to_frame, epoch - step)?;
+            let dcm_post = self.rotate(from_frame, to_frame, epoch + step)?;
+            dcm.rot_mat_dt =
+                Some((dcm_post.rot_mat - dcm_pre.rot_mat) / (2.0 * ANGULAR_VELOCITY_FD_STEP_S));
+        }
+
+        dcm.angular_velocity_rad_s()
+            .context(OrientationPhysicsSnafu {})
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Almanac {
+    /// Returns the angular velocity vector in rad/s of the `to_frame` with respect to the `from_frame`, expressed in the `to_frame`.
+    ///
+    /// If the rotation between these frames does not provide its time derivative, it is computed by central differencing
+    /// of the rotation matrices 0.1 seconds before and after the requested epoch.
+    /// Warning: you MUST manually install numpy to call this function.
+    ///
+    /// :type from_frame: Frame
+    /// :type to_frame: Frame
+    /// :type epoch: Epoch
+    /// :rtype: numpy.array
+    #[pyo3(name = "angular_velocity")]
+    fn py_angular_velocity<'py>(
+        &self,
+        py: Python<'py>,
+        from_frame: Frame,
+        to_frame: Frame,
+        epoch: Epoch,
+    ) -> Result<Bound<'py, PyArray1<f64>>, OrientationError> {
+        let omega = self.angular_velocity(from_frame, to_frame, epoch)?;
+        Ok(PyArray1::<f64>::from_owned_array(
+            py,
+            Array1::from_iter(omega.iter().copied()),
+        ))
+    }
+}
@@ -0,0 +1,736 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::str::FromStr;
+
+use hifitime::{Epoch, Unit};
+use log::warn;
+use snafu::ResultExt;
+
+use super::{DynamicFrameEphemerisSnafu, OrientationError, OrientationPhysicsSnafu};
+use crate::almanac::Almanac;
+use crate::astro::Aberration;
+use crate::constants::orientations::{id_to_orientation_name, J2000};
+use crate::math::rotation::DCM;
+use crate::math::{Matrix3, Vector3};
+use crate::naif::bodies::body_id_from_name;
+use crate::naif::kpl::pool::KernelPool;
+use crate::prelude::Frame;
+use crate::structure::dataset::DataSetError;
+use crate::NaifId;
+
+/// Half step, in seconds, of the central differencing of the rotation of two-vector frames to compute its time derivative.
+const TWO_VECTOR_FD_STEP_S: f64 = 0.1;
+
+/// Minimum angle, in radians, between the primary and the secondary vectors of a two-vector frame.
+const MIN_SEPARATION_RAD: f64 = 1e-10;
+
+/// Axis of a two-vector frame along which a defining vector is aligned.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameAxis {
+    X,
+    Y,
+    Z,
+    NegX,
+    NegY,
+    NegZ,
+}
+
+impl FrameAxis {
+    /// Index of this axis, from 0 (X) to 2 (Z)
+    fn index(self) -> usize {
+        match self {
+            Self::X | Self::NegX => 0,
+            Self::Y | Self::NegY => 1,
+            Self::Z | Self::NegZ => 2,
+        }
+    }
+
+    fn sign(self) -> f64 {
+        match self {
+            Self::X | Self::Y | Self::Z => 1.0,
+            Self::NegX | Self::NegY | Self::NegZ => -1.0,
+        }
+    }
+}
+
+impl FromStr for FrameAxis {
+    type Err = DataSetError;
+
+    /// Parses the axis as written in a SPICE frame kernel, e.g. `X`, `+Y`, or `-Z`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "X" | "+X" => Ok(Self::X),
+            "Y" | "+Y" => Ok(Self::Y),
+            "Z" | "+Z" => Ok(Self::Z),
+            "-X" => Ok(Self::NegX),
+            "-Y" => Ok(Self::NegY),
+            "-Z" => Ok(Self::NegZ),
+            _ => Err(DataSetError::Conversion {
+                action: format!("unknown frame axis `{s}`"),
+            }),
+        }
+    }
+}
+
+/// Definition of one of the vectors of a two-vector frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FrameVector {
+    /// Position of the target as seen from the observer
+    ObserverTargetPosition {
+        observer_id: NaifId,
+        target_id: NaifId,
+        ab_corr: Option<Aberration>,
+    },
+    /// Velocity of the target with respect to the observer, computed in the provided orientation frame
+    ObserverTargetVelocity {
+        observer_id: NaifId,
+        target_id: NaifId,
+        orientation_id: NaifId,
+        ab_corr: Option<Aberration>,
+    },
+    /// Constant vector expressed in the provided orientation frame
+    Constant {
+        vector: Vector3,
+        orientation_id: NaifId,
+    },
+}
+
+/// A dynamic frame defined by two vectors, e.g. the Sun-Earth rotating frame whose X axis points from the Sun to the Earth
+/// and whose XY plane contains the velocity of the Earth.
+///
+/// The primary vector sets the direction of the primary axis. The secondary axis is the component of the secondary vector
+/// orthogonal to the primary vector, and the third axis completes the right handed frame.
+///
+/// # SPICE Compatibility
+/// This is the equivalent of the parameterized dynamic frames of the "TWO-VECTOR" family of SPICE frame kernels, with the
+/// `OBSERVER_TARGET_POSITION`, `OBSERVER_TARGET_VELOCITY`, and `CONSTANT` (rectangular) vector definitions.
+/// The time derivative of the rotation is computed by central differencing.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TwoVectorFrame {
+    /// Orientation ID of this frame
+    pub orientation_id: NaifId,
+    /// Orientation ID of the frame in which the vectors are expressed, typically J2000
+    pub relative_orientation_id: NaifId,
+    pub primary_axis: FrameAxis,
+    pub primary: FrameVector,
+    pub secondary_axis: FrameAxis,
+    pub secondary: FrameVector,
+}
+
+impl TwoVectorFrame {
+    /// Initializes a new two-vector frame, ensuring that the primary and secondary axes differ.
+    pub fn new(
+        orientation_id: NaifId,
+        relative_orientation_id: NaifId,
+        primary_axis: FrameAxis,
+        primary: FrameVector,
+        secondary_axis: FrameAxis,
+        secondary: FrameVector,
+    ) -> Result<Self, OrientationError> {
+        if primary_axis.index() == secondary_axis.index() {
+            return Err(OrientationError::TwoVectorFrame {
+                orientation_id,
+                reason: "the primary and secondary axes must differ".to_string(),
+            });
+        }
+        if orientation_id == relative_orientation_id {
+            return Err(OrientationError::TwoVectorFrame {
+                orientation_id,
+                reason: "the frame cannot be relative to itself".to_string(),
+            });
+        }
+
+        Ok(Self {
+            orientation_id,
+            relative_orientation_id,
+            primary_axis,
+            primary,
+            secondary_axis,
+            secondary,
+        })
+    }
+
+    /// Returns the rotation matrix from the relative frame to this frame given the primary and the secondary vectors
+    /// expressed in the relative frame, i.e. the rows of the matrix are the axes of this frame.
+    pub fn rotation_matrix(
+        &self,
+        primary: Vector3,
+        secondary: Vector3,
+    ) -> Result<Matrix3, OrientationError> {
+        let normal = primary.cross(&secondary);
+        if primary.norm() < f64::EPSILON
+            || secondary.norm() < f64::EPSILON
+            || normal.norm() < MIN_SEPARATION_RAD * primary.norm() * secondary.norm()
+        {
+            return Err(OrientationError::TwoVectorFrame {
+                orientation_id: self.orientation_id,
+                reason: "the primary and secondary vectors are zero or colinear".to_string(),
+            });
+        }
+
+        let primary_hat = primary.normalize();
+        // Component of the secondary vector orthogonal to the primary vector
+        let secondary_hat = normal.normalize().cross(&primary_hat);
+
+        let i = self.primary_axis.index();
+        let j = self.secondary_axis.index();
+        let k = 3 - i - j;
+
+        let mut axes = [Vector3::zeros(); 3];
+        axes[i] = self.primary_axis.sign() * primary_hat;
+        axes[j] = self.secondary_axis.sign() * secondary_hat;
+        axes[k] = if (j + 3 - i) % 3 == 1 {
+            axes[i].cross(&axes[j])
+        } else {
+            axes[j].cross(&axes[i])
+        };
+
+        Ok(Matrix3::from_rows(&[
+            axes[0].transpose(),
+            axes[1].transpose(),
+            axes[2].transpose(),
+        ]))
+    }
+
+    /// Returns the synodic frame of the primary and secondary bodies (cf. `Frame::synodic`), relative to J2000: its X axis points from the primary to
+    /// the secondary, and its XY plane contains the velocity of the secondary with respect to the primary.
+    ///
+    /// Returns None if the orientation ID is not that of a synodic frame.
+    pub fn synodic(orientation_id: NaifId) -> Option<Self> {
+        let (primary_id, secondary_id) = Frame::from_orient_ssb(orientation_id).synodic_bodies()?;
+
+        Some(Self {
+            orientation_id,
+            relative_orientation_id: J2000,
+            primary_axis: FrameAxis::X,
+            primary: FrameVector::ObserverTargetPosition {
+                observer_id: primary_id,
+                target_id: secondary_id,
+                ab_corr: None,
+            },
+            secondary_axis: FrameAxis::Y,
+            secondary: FrameVector::ObserverTargetVelocity {
+                observer_id: primary_id,
+                target_id: secondary_id,
+                orientation_id: J2000,
+                ab_corr: None,
+            },
+        })
+    }
+
+    /// Builds the two-vector frame of the provided ID from the variables of a frame kernel loaded in the kernel pool.
+    ///
+    /// Bodies may be referred to by their name (as known to `body_id_from_name`) or their NAIF ID, and frames by their name
+    /// (built-in, or defined in the kernel pool) or their ID.
+    pub fn from_kernel_pool(
+        pool: &KernelPool,
+        orientation_id: NaifId,
+    ) -> Result<Self, DataSetError> {
+        let prefix = format!("FRAME_{orientation_id}_");
+
+        let family = pool_string(pool, &format!("{prefix}FAMILY"))?;
+        if family != "TWO-VECTOR" {
+            return Err(DataSetError::Conversion {
+                action: format!("frame {orientation_id} is of the unsupported `{family}` family"),
+            });
+        }
+
+        let relative_orientation_id =
+            pool_frame_id(pool, &pool_string(pool, &format!("{prefix}RELATIVE"))?)?;
+
+        let primary = pool_frame_vector(pool, &format!("{prefix}PRI_"))?;
+        let secondary = pool_frame_vector(pool, &format!("{prefix}SEC_"))?;
+
+        Self::new(
+            orientation_id,
+            relative_orientation_id,
+            FrameAxis::from_str(&pool_string(pool, &format!("{prefix}PRI_AXIS"))?)?,
+            primary,
+            FrameAxis::from_str(&pool_string(pool, &format!("{prefix}SEC_AXIS"))?)?,
+            secondary,
+        )
+        .map_err(|e| DataSetError::Conversion {
+            action: e.to_string(),
+        })
+    }
+}
+
+/// Returns the name, center, and definition of all of the two-vector frames defined in the kernel pool.
+/// Dynamic frames of other families are skipped.
+pub fn two_vector_frames_from_pool(
+    pool: &KernelPool,
+) -> Result<Vec<(String, NaifId, TwoVectorFrame)>, DataSetError> {
+    let mut frames = Vec::new();
+
+    for name in pool.names_starting_with("FRAME_") {
+        let id_str = match name
+            .strip_prefix("FRAME_")
+            .and_then(|rest| rest.strip_suffix("_FAMILY"))
+        {
+            Some(id_str) => id_str,
+            None => continue,
+        };
+
+        let orientation_id = match id_str.parse::<NaifId>() {
+            Ok(orientation_id) => orientation_id,
+            Err(_) => continue,
+        };
+
+        let prefix = format!("FRAME_{orientation_id}_");
+        let frame_name = pool_string(pool, &format!("{prefix}NAME"))?;
+
+        let family = pool_string(pool, name)?;
+        if family != "TWO-VECTOR" {
+            warn!("skipping dynamic frame {frame_name} of the unsupported `{family}` family");
+            continue;
+        }
+
+        let center = pool_body_id(pool, &format!("{prefix}CENTER"))?;
+
+        frames.push((
+            frame_name,
+            center,
+            TwoVectorFrame::from_kernel_pool(pool, orientation_id)?,
+        ));
+    }
+
+    Ok(frames)
+}
+
+fn pool_string(pool: &KernelPool, name: &str) -> Result<String, DataSetError> {
+    pool.get_string(name)
+        .map(|value| value.trim().to_uppercase())
+        .ok_or(DataSetError::Conversion {
+            action: format!("missing character variable `{name}`"),
+        })
+}
+
+/// Returns the body ID of the variable, which may be an integer or a body name.
+fn pool_body_id(pool: &KernelPool, name: &str) -> Result<NaifId, DataSetError> {
+    if let Some(id) = pool.get_i32(name) {
+        return Ok(id);
+    }
+    let body = pool_string(pool, name)?;
+    body_id_from_name(&body)
+        .or_else(|| body.parse::<NaifId>().ok())
+        .ok_or(DataSetError::Conversion {
+            action: format!("unknown body `{body}` in `{name}`"),
+        })
+}
+
+/// Returns the orientation ID of the frame name, from the built-in names or the `FRAME_<name>` variable of the pool.
+fn pool_frame_id(pool: &KernelPool, frame_name: &str) -> Result<NaifId, DataSetError> {
+    if let Ok(id) = id_to_orientation_name(frame_name) {
+        Ok(id)
+    } else if let Some(id) = pool.get_i32(&format!("FRAME_{frame_name}")) {
+        Ok(id)
+    } else {
+        frame_name
+            .parse::<NaifId>()
+            .map_err(|_| DataSetError::Conversion {
+                action: format!("unknown frame `{frame_name}`"),
+            })
+    }
+}
+
+fn pool_ab_corr(pool: &KernelPool, name: &str) -> Result<Option<Aberration>, DataSetError> {
+    match pool.get_string(name) {
+        None => Ok(None),
+        Some(ab_corr) => Aberration::new(ab_corr).map_err(|e| DataSetError::Conversion {
+            action: format!("invalid aberration correction in `{name}`: {e}"),
+        }),
+    }
+}
+
+fn pool_frame_vector(pool: &KernelPool, prefix: &str) -> Result<FrameVector, DataSetError> {
+    match pool_string(pool, &format!("{prefix}VECTOR_DEF"))?.as_str() {
+        "OBSERVER_TARGET_POSITION" => Ok(FrameVector::ObserverTargetPosition {
+            observer_id: pool_body_id(pool, &format!("{prefix}OBSERVER"))?,
+            target_id: pool_body_id(pool, &format!("{prefix}TARGET"))?,
+            ab_corr: pool_ab_corr(pool, &format!("{prefix}ABCORR"))?,
+        }),
+        "OBSERVER_TARGET_VELOCITY" => Ok(FrameVector::ObserverTargetVelocity {
+            observer_id: pool_body_id(pool, &format!("{prefix}OBSERVER"))?,
+            target_id: pool_body_id(pool, &format!("{prefix}TARGET"))?,
+            orientation_id: pool_frame_id(pool, &pool_string(pool, &format!("{prefix}FRAME"))?)?,
+            ab_corr: pool_ab_corr(pool, &format!("{prefix}ABCORR"))?,
+        }),
+        "CONSTANT" => {
+            let spec = pool_string(pool, &format!("{prefix}SPEC"))?;
+            if spec != "RECTANGULAR" {
+                return Err(DataSetError::Conversion {
+                    action: format!("unsupported `{spec}` specification of `{prefix}VECTOR`"),
+                });
+            }
+            let vector = pool
+                .get_f64s(&format!("{prefix}VECTOR"))
+                .filter(|vector| vector.len() == 3)
+                .ok_or(DataSetError::Conversion {
+                    action: format!("`{prefix}VECTOR` must have three components"),
+                })?;
+            Ok(FrameVector::Constant {
+                vector: Vector3::from_column_slice(vector),
+                orientation_id: pool_frame_id(
+                    pool,
+                    &pool_string(pool, &format!("{prefix}FRAME"))?,
+                )?,
+            })
+        }
+        def => Err(DataSetError::Conversion {
+            action: format!("unsupported vector definition `{def}` in `{prefix}VECTOR_DEF`"),
+        }),
+    }
+}
+
+impl Almanac {
+    /// Adds the provided two-vector frame to a clone of this Almanac, and registers it under the provided name with the provided center,
+    /// such that it can be used in any rotation or transformation, and fetched with `frame_from_name`.
+    pub fn with_two_vector_frame(&self, name: &str, center: NaifId, frame: TwoVectorFrame) -> Self {
+        let mut me = self.register_frame(name, center, frame.orientation_id, None, None);
+        me.dynamic_frames.insert(frame.orientation_id, frame);
+        me
+    }
+
+    /// Returns the dynamic frame of the provided orientation ID, either added to this Almanac or a synodic frame.
+    pub(crate) fn dynamic_frame(&self, orientation_id: NaifId) -> Option<TwoVectorFrame> {
+        self.dynamic_frames
+            .get(&orientation_id)
+            .copied()
+            .or_else(|| TwoVectorFrame::synodic(orientation_id))
+    }
+
+    /// Returns the DCM from the relative frame of the provided two-vector frame to that frame, including its time derivative.
+    pub(crate) fn two_vector_rotation(
+        &self,
+        frame: &TwoVectorFrame,
+        epoch: Epoch,
+    ) -> Result<DCM, OrientationError> {
+        let rot_mat = self.two_vector_rot_mat(frame, epoch)?;
+
+        let step = Unit::Second * TWO_VECTOR_FD_STEP_S;
+        let rot_mat_pre = self.two_vector_rot_mat(frame, epoch - step)?;
+        let rot_mat_post = self.two_vector_rot_mat(frame, epoch + step)?;
+
+        Ok(DCM {
+            rot_mat,
+            rot_mat_dt: Some((rot_mat_post - rot_mat_pre) / (2.0 * TWO_VECTOR_FD_STEP_S)),
+            from: frame.relative_orientation_id,
+            to: frame.orientation_id,
+        })
+    }
+
+    fn two_vector_rot_mat(
+        &self,
+        frame: &TwoVectorFrame,
+        epoch: Epoch,
+    ) -> Result<Matrix3, OrientationError> {
+        let primary = self.two_vector_frame_vector(frame, &frame.primary, epoch)?;
+        let secondary = self.two_vector_frame_vector(frame, &frame.secondary, epoch)?;
+        frame.rotation_matrix(primary, secondary)
+    }
+
+    /// Returns the vector expressed in the relative frame of the two-vector frame.
+    fn two_vector_frame_vector(
+        &self,
+        frame: &TwoVectorFrame,
+        vector: &FrameVector,
+        epoch: Epoch,
+    ) -> Result<Vector3, OrientationError> {
+        let relative = Frame::from_orient_ssb(frame.relative_orientation_id);
+
+        match *vector {
+            FrameVector::ObserverTargetPosition {
+                observer_id,
+                target_id,
+                ab_corr,
+            } => {
+                let state = self
+                    .translate(
+                        Frame::from_ephem_j2000(target_id),
+                        Frame::from_ephem_j2000(observer_id),
+                        epoch,
+                        ab_corr,
+                    )
+                    .context(DynamicFrameEphemerisSnafu {
+                        action: "computing the position vector",
+                        orientation_id: frame.orientation_id,
+                    })?;
+
+                Ok(self.rotate(state.frame, relative, epoch)?.rot_mat * state.radius_km)
+            }
+            FrameVector::ObserverTargetVelocity {
+                observer_id,
+                target_id,
+                orientation_id,
+                ab_corr,
+            } => {
+                let mut state = self
+                    .translate(
+                        Frame::from_ephem_j2000(target_id),
+                        Frame::from_ephem_j2000(observer_id),
+                        epoch,
+                        ab_corr,
+                    )
+                    .context(DynamicFrameEphemerisSnafu {
+                        action: "computing the velocity vector",
+                        orientation_id: frame.orientation_id,
+                    })?;
+
+                if orientation_id != J2000 {
+                    // The velocity is computed in the rotating frame, if any.
+                    let dcm =
+                        self.rotate(state.frame, Frame::from_orient_ssb(orientation_id), epoch)?;
+                    state = (dcm * state).context(OrientationPhysicsSnafu)?;
+                }
+
+                Ok(self.rotate(state.frame, relative, epoch)?.rot_mat * state.velocity_km_s)
+            }
+            FrameVector::Constant {
+                vector,
+                orientation_id,
+            } => Ok(self
+                .rotate(Frame::from_orient_ssb(orientation_id), relative, epoch)?
+                .rot_mat
+                * vector),
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_two_vector {
+    use super::*;
+    use crate::constants::frames::{EARTH_J2000, MOON_J2000, SUN_J2000};
+
+    const SUN_EARTH_ROT: NaifId = 1_400_010;
+
+    fn sun_earth_rotating() -> TwoVectorFrame {
+        TwoVectorFrame::new(
+            SUN_EARTH_ROT,
+            J2000,
+            FrameAxis::X,
+            FrameVector::ObserverTargetPosition {
+                observer_id: 10,
+                target_id: 399,
+                ab_corr: None,
+            },
+            FrameAxis::Y,
+            FrameVector::ObserverTargetVelocity {
+                observer_id: 10,
+                target_id: 399,
+                orientation_id: J2000,
+                ab_corr: None,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn two_vector_axes() {
+        let frame = sun_earth_rotating();
+
+        let rot_mat = frame
+            .rotation_matrix(Vector3::new(2.0, 0.0, 0.0), Vector3::new(1.0, 3.0, 0.0))
+            .unwrap();
+        assert!((rot_mat - Matrix3::identity()).norm() < 1e-15);
+
+        // Primary along -Z, secondary along X: the Y axis is Z cross X.
+        let mut frame = frame;
+        frame.primary_axis = FrameAxis::NegZ;
+        frame.secondary_axis = FrameAxis::X;
+        let rot_mat = frame
+            .rotation_matrix(Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.0, 1.0, 0.0))
+            .unwrap();
+        assert_eq!(rot_mat * Vector3::new(0.0, 0.0, 1.0), -Vector3::z());
+        assert_eq!(rot_mat * Vector3::new(0.0, 1.0, 0.0), Vector3::x());
+        assert!((rot_mat.determinant() - 1.0).abs() < 1e-15);
+
+        assert!(frame
+            .rotation_matrix(Vector3::x(), 2.0 * Vector3::x())
+            .is_err());
+
+        assert!(TwoVectorFrame::new(
+            SUN_EARTH_ROT,
+            J2000,
+            FrameAxis::X,
+            frame.primary,
+            FrameAxis::NegX,
+            frame.secondary
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn sun_earth_rotating_frame() {
+        let almanac = Almanac::new("../data/de440s.bsp")
+            .unwrap()
+            .with_two_vector_frame("Sun-Earth Rotating", 10, sun_earth_rotating());
+
+        let frame = almanac.frame_from_name("Sun-Earth Rotating").unwrap();
+        assert_eq!(frame.orientation_id, SUN_EARTH_ROT);
+        assert_eq!(frame.ephemeris_id, 10);
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+
+        // In this frame, the Earth is along the X axis, and its velocity has no Z component.
+        let earth = almanac
+            .transform(
+                EARTH_J2000,
+                SUN_J2000.with_orient(SUN_EARTH_ROT),
+                epoch,
+                None,
+            )
+            .unwrap();
+
+        assert!(earth.radius_km.y.abs() < 1e-6, "{earth}");
+        assert!(earth.radius_km.z.abs() < 1e-6, "{earth}");
+        assert!(earth.radius_km.x > 1.4e8, "{earth}");
+        // The Earth is almost fixed in the rotating frame, it only moves radially.
+        assert!(earth.velocity_km_s.z.abs() < 1e-6, "{earth}");
+        assert!(earth.velocity_km_s.y.abs() < 1e-4, "{earth}");
+        assert!(earth.velocity_km_s.x.abs() < 1.0, "{earth}");
+
+        // The frame rotates at about one revolution per year about its Z axis.
+        let omega = almanac
+            .angular_velocity(
+                Frame::from_orient_ssb(J2000),
+                Frame::from_orient_ssb(SUN_EARTH_ROT),
+                epoch,
+            )
+            .unwrap();
+        let year_s = 365.25 * 86_400.0;
+        assert!(
+            (omega.norm() * year_s / core::f64::consts::TAU - 1.0).abs() < 0.05,
+            "{omega}"
+        );
+
+        // Round trip
+        let back = almanac.transform_to(earth, EARTH_J2000, None).unwrap();
+        assert!(back.rmag_km() < 1e-6, "{back}");
+        assert!(back.vmag_km_s() < 1e-9, "{back}");
+    }
+
+    #[test]
+    fn earth_moon_synodic_frame() {
+        let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+
+        let earth_moon = Frame::synodic(399, 301).unwrap();
+        assert_eq!(earth_moon.ephemeris_id, 3);
+        assert_eq!(earth_moon.synodic_bodies(), Some((399, 301)));
+        assert_eq!(Frame::synodic(10, 3).unwrap().ephemeris_id, 10);
+        assert!(Frame::synodic(-10000001, 301).is_none());
+        assert!(EARTH_J2000.synodic_bodies().is_none());
+
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 3, 20);
+
+        // The Earth and the Moon are on the X axis, on either side of the barycenter.
+        let moon = almanac
+            .transform(MOON_J2000, earth_moon, epoch, None)
+            .unwrap();
+        let earth = almanac
+            .transform(EARTH_J2000, earth_moon, epoch, None)
+            .unwrap();
+
+        assert!(moon.radius_km.x > 3.5e5, "{moon}");
+        assert!(earth.radius_km.x < 0.0, "{earth}");
+        for state in [moon, earth] {
+            assert!(state.radius_km.y.abs() < 1e-3, "{state}");
+            assert!(state.radius_km.z.abs() < 1e-3, "{state}");
+            // Both bodies only move radially in the rotating frame.
+            assert!(state.velocity_km_s.y.abs() < 1e-6, "{state}");
+            assert!(state.velocity_km_s.z.abs() < 1e-6, "{state}");
+        }
+
+        // The distances to the barycenter are in the inverse ratio of the masses.
+        assert!((moon.radius_km.x / -earth.radius_km.x - 81.3).abs() < 0.01);
+
+        // Round trip
+        let back = almanac.transform_to(moon, MOON_J2000, None).unwrap();
+        assert!(back.rmag_km() < 1e-6, "{back}");
+        assert!(back.vmag_km_s() < 1e-9, "{back}");
+    }
+
+    #[test]
+    fn two_vector_frame_kernel() {
+        let kernel = r"
+\begindata
+
+   FRAME_SUN_EARTH_ROT           = 1400010
+   FRAME_1400010_NAME            = 'SUN_EARTH_ROT'
+   FRAME_1400010_CLASS           = 5
+   FRAME_1400010_CLASS_ID        = 1400010
+   FRAME_1400010_CENTER          = 'SUN'
+   FRAME_1400010_RELATIVE        = 'J2000'
+   FRAME_1400010_DEF_STYLE       = 'PARAMETERIZED'
+   FRAME_1400010_FAMILY          = 'TWO-VECTOR'
+   FRAME_1400010_PRI_AXIS        = 'X'
+   FRAME_1400010_PRI_VECTOR_DEF  = 'OBSERVER_TARGET_POSITION'
+   FRAME_1400010_PRI_OBSERVER    = 'SUN'
+   FRAME_1400010_PRI_TARGET      = 'EARTH'
+   FRAME_1400010_PRI_ABCORR      = 'NONE'
+   FRAME_1400010_SEC_AXIS        = 'Y'
+   FRAME_1400010_SEC_VECTOR_DEF  = 'OBSERVER_TARGET_VELOCITY'
+   FRAME_1400010_SEC_OBSERVER    = 'SUN'
+   FRAME_1400010_SEC_TARGET      = 399
+   FRAME_1400010_SEC_ABCORR      = 'NONE'
+   FRAME_1400010_SEC_FRAME       = 'J2000'
+
+   FRAME_EARTH_POLE_SUN          = 1400020
+   FRAME_1400020_NAME            = 'EARTH_POLE_SUN'
+   FRAME_1400020_CLASS           = 5
+   FRAME_1400020_CLASS_ID        = 1400020
+   FRAME_1400020_CENTER          = 399
+   FRAME_1400020_RELATIVE        = 'SUN_EARTH_ROT'
+   FRAME_1400020_DEF_STYLE       = 'PARAMETERIZED'
+   FRAME_1400020_FAMILY          = 'TWO-VECTOR'
+   FRAME_1400020_PRI_AXIS        = 'Z'
+   FRAME_1400020_PRI_VECTOR_DEF  = 'CONSTANT'
+   FRAME_1400020_PRI_FRAME       = 'J2000'
+   FRAME_1400020_PRI_SPEC        = 'RECTANGULAR'
+   FRAME_1400020_PRI_VECTOR      = ( 0, 0, 1 )
+   FRAME_1400020_SEC_AXIS        = '-X'
+   FRAME_1400020_SEC_VECTOR_DEF  = 'OBSERVER_TARGET_POSITION'
+   FRAME_1400020_SEC_OBSERVER    = 'EARTH'
+   FRAME_1400020_SEC_TARGET      = 'SUN'
+   FRAME_1400020_SEC_ABCORR      = 'LT+S'
+
+\begintext
+";
+        let pool = KernelPool::from_kernel(kernel).unwrap();
+        let frames = two_vector_frames_from_pool(&pool).unwrap();
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(
+            frames[0],
+            ("SUN_EARTH_ROT".to_string(), 10, sun_earth_rotating())
+        );
+
+        let (name, center, frame) = &frames[1];
+        assert_eq!(name, "EARTH_POLE_SUN");
+        assert_eq!(*center, 399);
+        assert_eq!(frame.relative_orientation_id, SUN_EARTH_ROT);
+        assert_eq!(frame.secondary_axis, FrameAxis::NegX);
+        assert_eq!(
+            frame.primary,
+            FrameVector::Constant {
+                vector: Vector3::z(),
+                orientation_id: J2000
+            }
+        );
+        assert_eq!(
+            frame.secondary,
+            FrameVector::ObserverTargetPosition {
+                observer_id: 399,
+                target_id: 10,
+                ab_corr: Aberration::new("LT+S").unwrap()
+            }
+        );
+    }
+}
@@ -0,0 +1,25 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+/// Receives the progress of long-running operations, such as the download of remote files by the MetaAlmanac or coverage analyses,
+/// so that applications can display it (e.g. as a progress bar in a terminal or in a GUI).
+///
+/// Operations which accept a progress sink call `start` once per task, then `update` as the task progresses, and `finish` when the task completes
+/// successfully. Calls may come from several threads when the operation is parallelized (e.g. with the `rayon` feature).
+pub trait ProgressSink: Send + Sync {
+    /// Called when a task starts, with the total amount of work if it is known (e.g. the number of bytes of a download).
+    fn start(&self, task: &str, total: Option<u64>);
+
+    /// Called as the task progresses, with the amount of work done so far, in the same unit as the total.
+    fn update(&self, done: u64);
+
+    /// Called when the task completes successfully.
+    fn finish(&self);
+}
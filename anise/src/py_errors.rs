@@ -10,6 +10,7 @@
 
 use crate::almanac::metaload::MetaAlmanacError;
 use crate::almanac::planetary::PlanetaryDataError;
+use crate::analysis::AnalysisError;
 use crate::ephemerides::EphemerisError;
 use crate::errors::{AlmanacError, DecodingError, InputOutputError, IntegrityError, PhysicsError};
 use crate::orientations::OrientationError;
@@ -71,3 +72,8 @@ impl From<DataSetError> for PyErr {
         PyException::new_err(err.to_string())
     }
 }
+impl From<AnalysisError> for PyErr {
+    fn from(err: AnalysisError) -> PyErr {
+        PyException::new_err(err.to_string())
+    }
+}
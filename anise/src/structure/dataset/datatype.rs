@@ -18,6 +18,7 @@ pub enum DataSetType {
     SpacecraftData,
     PlanetaryData,
     EulerParameterData,
+    LocationData,
 }
 
 impl From<u8> for DataSetType {
@@ -27,6 +28,7 @@ impl From<u8> for DataSetType {
             1 => DataSetType::SpacecraftData,
             2 => DataSetType::PlanetaryData,
             3 => DataSetType::EulerParameterData,
+            4 => DataSetType::LocationData,
             _ => panic!("Invalid value for DataSetType {val}"),
         }
     }
@@ -1,5 +1,6 @@
 use snafu::prelude::*;
 
+use super::merge::EntryKey;
 use crate::{
     errors::{DecodingError, IntegrityError},
     structure::lookuptable::LutError,
@@ -31,6 +32,8 @@ pub enum DataSetError {
     },
     #[snafu(display("data set conversion error: {action}"))]
     Conversion { action: String },
+    #[snafu(display("cannot merge data sets: {key} is in both data sets"))]
+    MergeConflict { key: EntryKey },
 }
 
 impl PartialEq for DataSetError {
@@ -76,6 +79,9 @@ impl PartialEq for DataSetError {
                     source: _r_source,
                 },
             ) => l_action == r_action,
+            (Self::MergeConflict { key: l_key }, Self::MergeConflict { key: r_key }) => {
+                l_key == r_key
+            }
             _ => false,
         }
     }
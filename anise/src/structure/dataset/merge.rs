@@ -0,0 +1,339 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use std::collections::HashMap;
+
+use log::info;
+use snafu::ResultExt;
+
+use super::error::DataSetLutSnafu;
+use super::{DataSet, DataSetError, DataSetT};
+use crate::structure::lookuptable::{LutError, KEY_NAME_LEN};
+use crate::NaifId;
+
+type NameKey = heapless::String<KEY_NAME_LEN>;
+
+/// How to resolve an entry of the other data set whose ID or name is already in this data set when merging.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the data of this data set
+    #[default]
+    KeepSelf,
+    /// Replace the data of this data set with that of the other data set
+    KeepOther,
+    /// Return an error without changing this data set
+    Error,
+}
+
+/// The ID and name of an entry of a data set.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EntryKey {
+    pub id: Option<NaifId>,
+    pub name: Option<String>,
+}
+
+/// Structural differences between two data sets, as returned by `DataSet::diff`.
+///
+/// Entries are matched by ID if they have one, and by name otherwise.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DataSetDiff {
+    /// Entries only in the other data set
+    pub added: Vec<EntryKey>,
+    /// Entries only in this data set
+    pub removed: Vec<EntryKey>,
+    /// Entries in both data sets, but whose data or key differ
+    pub changed: Vec<EntryKey>,
+}
+
+impl DataSetDiff {
+    /// Returns true if both data sets have the same entries with the same data
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
+    /// Merges the entries of the other data set into this one, resolving the entries whose ID or name is already in this
+    /// data set per the provided conflict policy. All of the IDs and names of the merged entries are added to the look up table.
+    ///
+    /// The merge is recorded in the edit log of the metadata and the checksum is updated.
+    ///
+    /// # Errors
+    /// + If the policy is `ConflictPolicy::Error` and an entry is in both data sets, in which case this data set is unchanged.
+    pub fn merge(&mut self, other: &Self, policy: ConflictPolicy) -> Result<(), DataSetError> {
+        let (ids, names) = other.keys_by_index();
+
+        // Find the conflicting entries before changing anything
+        let mut merges = Vec::with_capacity(other.data.len());
+        for (index, data) in other.data.iter().enumerate() {
+            let entry_ids = ids.get(&(index as u32)).cloned().unwrap_or_default();
+            let entry_names = names.get(&(index as u32)).cloned().unwrap_or_default();
+            if entry_ids.is_empty() && entry_names.is_empty() {
+                // Removed entry
+                continue;
+            }
+
+            let existing = entry_ids
+                .iter()
+                .find_map(|id| self.lut.by_id.get(id))
+                .or_else(|| {
+                    entry_names
+                        .iter()
+                        .find_map(|name| self.lut.by_name.get(name))
+                })
+                .copied();
+
+            if existing.is_some() && policy == ConflictPolicy::Error {
+                return Err(DataSetError::MergeConflict {
+                    key: EntryKey {
+                        id: entry_ids.first().copied(),
+                        name: entry_names.first().map(|name| name.to_string()),
+                    },
+                });
+            }
+
+            merges.push((existing, entry_ids, entry_names, data));
+        }
+
+        let (mut added, mut replaced) = (0, 0);
+        for (existing, entry_ids, entry_names, data) in merges {
+            let index = match existing {
+                Some(_) if policy == ConflictPolicy::KeepSelf => continue,
+                Some(index) => {
+                    *self
+                        .data
+                        .get_mut(index as usize)
+                        .ok_or(LutError::InvalidIndex { index })
+                        .context(DataSetLutSnafu {
+                            action: "merging data sets",
+                        })? = data.clone();
+                    replaced += 1;
+                    index
+                }
+                None => {
+                    self.data.push(data.clone());
+                    added += 1;
+                    (self.data.len() - 1) as u32
+                }
+            };
+
+            for id in entry_ids {
+                if !self.lut.by_id.contains_key(&id) {
+                    self.lut.by_id.insert(id, index);
+                }
+            }
+            for name in entry_names {
+                if !self.lut.by_name.contains_key(&name) {
+                    self.lut
+                        .append_name(&name, index)
+                        .context(DataSetLutSnafu {
+                            action: "merging data sets",
+                        })?;
+                }
+            }
+        }
+
+        let description = format!(
+            "merged {} {} entries: {added} added, {replaced} replaced",
+            other.data.len(),
+            T::NAME
+        );
+        info!("{description}");
+        self.metadata.record_edit(description);
+        self.set_crc32();
+
+        Ok(())
+    }
+
+    /// Returns the IDs and the names of each index of the data.
+    fn keys_by_index(&self) -> (HashMap<u32, Vec<NaifId>>, HashMap<u32, Vec<NameKey>>) {
+        let mut ids: HashMap<u32, Vec<NaifId>> = HashMap::new();
+        for (id, index) in &self.lut.by_id {
+            ids.entry(*index).or_default().push(*id);
+        }
+
+        let mut names: HashMap<u32, Vec<NameKey>> = HashMap::new();
+        for (name, index) in &self.lut.by_name {
+            names.entry(*index).or_default().push(name.clone());
+        }
+
+        (ids, names)
+    }
+
+    /// Returns the key of each entry, using the first ID and first name of each index.
+    fn entry_keys(&self) -> Vec<(EntryKey, u32)> {
+        let (ids, names) = self.keys_by_index();
+        let mut indexes = ids.keys().chain(names.keys()).copied().collect::<Vec<_>>();
+        indexes.sort_unstable();
+        indexes.dedup();
+
+        indexes
+            .into_iter()
+            .map(|index| {
+                let key = EntryKey {
+                    id: ids.get(&index).and_then(|ids| ids.first().copied()),
+                    name: names
+                        .get(&index)
+                        .and_then(|names| names.first().map(|name| name.to_string())),
+                };
+                (key, index)
+            })
+            .collect()
+    }
+}
+
+impl<T: DataSetT + PartialEq, const ENTRIES: usize> DataSet<T, ENTRIES> {
+    /// Returns the entries added, removed, or changed in the other data set compared to this one, e.g. to review an update
+    /// of a shared location or spacecraft catalog.
+    pub fn diff(&self, other: &Self) -> DataSetDiff {
+        let mine = self.entry_keys();
+        let theirs = other.entry_keys();
+
+        let find = |keys: &[(EntryKey, u32)], key: &EntryKey| {
+            keys.iter()
+                .find(|(other_key, _)| match key.id {
+                    Some(id) => other_key.id == Some(id),
+                    None => other_key.id.is_none() && other_key.name == key.name,
+                })
+                .cloned()
+        };
+
+        let mut diff = DataSetDiff::default();
+
+        for (key, index) in &mine {
+            match find(theirs.as_slice(), key) {
+                None => diff.removed.push(key.clone()),
+                Some((other_key, other_index)) => {
+                    if other_key != *key
+                        || self.data.get(*index as usize) != other.data.get(other_index as usize)
+                    {
+                        diff.changed.push(other_key);
+                    }
+                }
+            }
+        }
+
+        for (key, _) in &theirs {
+            if find(mine.as_slice(), key).is_none() {
+                diff.added.push(key.clone());
+            }
+        }
+
+        diff
+    }
+}
+
+impl fmt::Display for EntryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.id, &self.name) {
+            (Some(id), Some(name)) => write!(f, "{name} (#{id})"),
+            (Some(id), None) => write!(f, "#{id}"),
+            (None, Some(name)) => write!(f, "{name}"),
+            (None, None) => write!(f, "(unnamed)"),
+        }
+    }
+}
+
+impl fmt::Display for DataSetDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no differences");
+        }
+        for (sign, keys) in [
+            ("+", &self.added),
+            ("-", &self.removed),
+            ("~", &self.changed),
+        ] {
+            for key in keys {
+                writeln!(f, "{sign} {key}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod merge_ut {
+    use super::{ConflictPolicy, DataSetDiff, EntryKey};
+    use crate::structure::dataset::DataSetError;
+    use crate::structure::spacecraft::{Mass, SpacecraftData};
+    use crate::structure::SpacecraftDataSet;
+
+    fn sc(dry_mass_kg: f64) -> SpacecraftData {
+        SpacecraftData {
+            mass: Some(Mass::from_dry_mass(dry_mass_kg)),
+            ..Default::default()
+        }
+    }
+
+    fn key(id: i32, name: &str) -> EntryKey {
+        EntryKey {
+            id: Some(id),
+            name: Some(name.to_string()),
+        }
+    }
+
+    #[test]
+    fn merge_and_diff() {
+        let mut base = SpacecraftDataSet::default();
+        base.push(sc(100.0), Some(-10), Some("Alpha")).unwrap();
+        base.push(sc(200.0), Some(-20), Some("Bravo")).unwrap();
+        base.push(sc(300.0), None, Some("Charlie")).unwrap();
+
+        let mut update = SpacecraftDataSet::default();
+        update.push(sc(250.0), Some(-20), Some("Bravo")).unwrap();
+        update.push(sc(300.0), None, Some("Charlie")).unwrap();
+        update.push(sc(400.0), Some(-40), Some("Delta")).unwrap();
+
+        let diff = base.diff(&update);
+        println!("{diff}");
+        assert_eq!(
+            diff,
+            DataSetDiff {
+                added: vec![key(-40, "Delta")],
+                removed: vec![key(-10, "Alpha")],
+                changed: vec![key(-20, "Bravo")],
+            }
+        );
+        assert!(base.diff(&base).is_empty());
+
+        // Conflicts with the error policy leave the data set unchanged
+        let mut merged = base.clone();
+        assert_eq!(
+            merged.merge(&update, ConflictPolicy::Error),
+            Err(DataSetError::MergeConflict {
+                key: key(-20, "Bravo")
+            })
+        );
+        assert_eq!(merged, base);
+
+        let mut merged = base.clone();
+        merged.merge(&update, ConflictPolicy::KeepSelf).unwrap();
+        assert_eq!(merged.get_by_name("Bravo").unwrap(), sc(200.0));
+        assert_eq!(merged.get_by_id(-40).unwrap(), sc(400.0));
+        assert_eq!(merged.get_by_name("Delta").unwrap(), sc(400.0));
+        assert_eq!(merged.get_by_id(-10).unwrap(), sc(100.0));
+        assert_eq!(merged.data.len(), 4);
+        assert!(merged.check_integrity().is_ok());
+        assert_eq!(merged.metadata.edit_log.len(), 1);
+
+        let mut merged = base.clone();
+        merged.merge(&update, ConflictPolicy::KeepOther).unwrap();
+        assert_eq!(merged.get_by_id(-20).unwrap(), sc(250.0));
+        assert_eq!(merged.get_by_name("Charlie").unwrap(), sc(300.0));
+        assert_eq!(merged.data.len(), 4);
+
+        // The only remaining difference is the entry removed from the update
+        let diff = merged.diff(&update);
+        assert!(diff.added.is_empty() && diff.changed.is_empty());
+        assert_eq!(diff.removed, vec![key(-10, "Alpha")]);
+    }
+}
@@ -9,7 +9,7 @@
  */
 use self::error::{DataDecodingSnafu, DataSetLutSnafu};
 use super::{
-    lookuptable::{LookUpTable, LutError},
+    lookuptable::{LookUpTable, LutError, KEY_NAME_LEN},
     metadata::Metadata,
     semver::Semver,
     ANISE_VERSION,
@@ -21,10 +21,7 @@ use crate::{
 };
 use core::fmt;
 use core::ops::Deref;
-use der::{
-    asn1::{OctetString, SequenceOf},
-    Decode, Encode, Reader, Writer,
-};
+use der::{asn1::OctetString, Decode, Encode, Reader, Writer};
 use log::{error, trace};
 use snafu::prelude::*;
 
@@ -41,10 +38,18 @@ io_imports!();
 
 mod datatype;
 mod error;
+mod merge;
+mod planetary_edit;
 mod pretty_print;
+mod quaternion_csv;
+#[cfg(feature = "toml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+mod toml_set;
+mod tpc_export;
 
 pub use datatype::DataSetType;
 pub use error::DataSetError;
+pub use merge::{ConflictPolicy, DataSetDiff, EntryKey};
 
 /// The kind of data that can be encoded in a dataset
 pub trait DataSetT: Clone + Default + Encode + for<'a> Decode<'a> {
@@ -271,7 +276,7 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
     /// This will return an error if the ID is not in the lookup table.
     /// Note that this function requires a new heap allocation to change the underlying dataset
     pub fn rm_by_id(&mut self, id: NaifId) -> Result<(), DataSetError> {
-        if let Some(index) = self.lut.by_id.remove(&id) {
+        if let Some(index) = self.lut.by_id.swap_remove(&id) {
             *self
                 .data
                 .get_mut(index as usize)
@@ -301,7 +306,7 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
 
     /// Get a copy of the data with that name, if that name is in the lookup table
     pub fn get_by_name(&self, name: &str) -> Result<T, DataSetError> {
-        if let Some(index) = self.lut.by_name.get(&name.try_into().unwrap()) {
+        if let Some(index) = self.lut.by_name.get(&name_key(name)) {
             self.data
                 .get(*index as usize)
                 .cloned()
@@ -323,7 +328,7 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
     /// This will return an error if the name is not in the lookup table.
     /// Note that this function requires a new heap allocation to change the underlying dataset
     pub fn set_by_name(&mut self, name: &str, new_value: T) -> Result<(), DataSetError> {
-        if let Some(index) = self.lut.by_name.get(&name.try_into().unwrap()) {
+        if let Some(index) = self.lut.by_name.get(&name_key(name)) {
             *self
                 .data
                 .get_mut(*index as usize)
@@ -347,7 +352,7 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
     /// This will return an error if the name is not in the lookup table.
     /// Note that this function requires a new heap allocation to change the underlying dataset
     pub fn rm_by_name(&mut self, name: &str) -> Result<(), DataSetError> {
-        if let Some(index) = self.lut.by_name.remove(&name.try_into().unwrap()) {
+        if let Some(index) = self.lut.by_name.swap_remove(&name_key(name)) {
             *self
                 .data
                 .get_mut(index as usize)
@@ -449,6 +454,11 @@ impl<T: DataSetT, const ENTRIES: usize> DataSet<T, ENTRIES> {
     }
 }
 
+/// Returns the look up table key of this name.
+fn name_key(name: &str) -> heapless::String<KEY_NAME_LEN> {
+    name.try_into().unwrap()
+}
+
 impl<T: DataSetT, const ENTRIES: usize> Encode for DataSet<T, ENTRIES> {
     fn encoded_len(&self) -> der::Result<der::Length> {
         let (bytes_meta, bytes) = self.build_data_seq();
@@ -477,14 +487,14 @@ impl<'a, T: DataSetT, const ENTRIES: usize> Decode<'a> for DataSet<T, ENTRIES> {
         // Metadata of the bytes to decode.
         // The first integer contains the number of usable items in the data.
         // The other integers are the encoded lengths of each of the data.
-        let bytes_meta: SequenceOf<u32, ENTRIES> = decoder.decode()?;
+        let bytes_meta: Vec<u32> = decoder.decode()?;
         let der_octets: OctetString = decoder.decode()?;
         let bytes = der_octets.as_bytes();
 
         let mut data = vec![];
 
         let mut idx = 0;
-        for meta_idx in 0..*bytes_meta.get(0).unwrap() as usize {
+        for meta_idx in 0..*bytes_meta.first().unwrap() as usize {
             let next_len = *bytes_meta.get(meta_idx + 1).unwrap() as usize;
             let this_data = T::from_der(&bytes[idx..idx + next_len]).unwrap();
             data.push(this_data);
@@ -537,8 +547,11 @@ mod dataset_ut {
         assert_eq!(repr, repr_dec);
 
         dbg!(repr);
-        assert_eq!(core::mem::size_of::<DataSet<SpacecraftData, 2>>(), 256);
-        assert_eq!(core::mem::size_of::<DataSet<SpacecraftData, 128>>(), 8824);
+        // The look up table grows as needed, so the size of a data set does not depend on its number of entries
+        assert_eq!(
+            core::mem::size_of::<DataSet<SpacecraftData, 2>>(),
+            core::mem::size_of::<DataSet<SpacecraftData, 128>>()
+        );
     }
 
     #[test]
@@ -560,6 +573,7 @@ mod dataset_ut {
             }),
             mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.6)),
             drag_data: Some(DragData::default()),
+            ..Default::default()
         };
         let srp_sc = SpacecraftData {
             srp_data: Some(SRPData::default()),
@@ -703,6 +717,7 @@ mod dataset_ut {
             }),
             mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.6)),
             drag_data: Some(DragData::default()),
+            ..Default::default()
         };
         let srp_sc = SpacecraftData {
             srp_data: Some(SRPData::default()),
@@ -0,0 +1,207 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use log::info;
+
+use super::DataSetError;
+use crate::constants::orientations::J2000;
+use crate::structure::planetocentric::dipole::GeomagneticDipole;
+use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+use crate::structure::planetocentric::phaseangle::PhaseAngle;
+use crate::structure::planetocentric::{PlanetaryData, MAX_NUT_PREC_ANGLES};
+use crate::structure::PlanetaryDataSet;
+use crate::NaifId;
+
+/// Builder-style edits of planetary data sets, e.g. to use a project specific gravitational parameter of the Moon.
+///
+/// Each edit is recorded in the edit log of the metadata of the data set, and the checksum of the data is updated.
+/// If the body is not yet in the data set, it is added with a J2000 parent orientation and without other data.
+impl PlanetaryDataSet {
+    /// Adds this body to the data set, or replaces all of its data if it is already in the data set.
+    pub fn with_body(mut self, data: PlanetaryData) -> Result<Self, DataSetError> {
+        let id = data.object_id;
+        let description = if self.get_by_id(id).is_ok() {
+            self.set_by_id(id, data)?;
+            format!("replaced the data of {id}")
+        } else {
+            self.push(data, Some(id), None)?;
+            format!("added {id}")
+        };
+
+        self.record(description);
+        Ok(self)
+    }
+
+    /// Sets the gravitational parameter of this body, in km^3/s^2.
+    pub fn with_mu(self, id: NaifId, mu_km3_s2: f64) -> Result<Self, DataSetError> {
+        self.edit(id, |data| {
+            let description = format!(
+                "set mu of {id} from {} to {mu_km3_s2} km^3/s^2",
+                data.mu_km3_s2
+            );
+            data.mu_km3_s2 = mu_km3_s2;
+            description
+        })
+    }
+
+    /// Sets the tri-axial ellipsoid shape of this body.
+    pub fn with_radii(self, id: NaifId, shape: Ellipsoid) -> Result<Self, DataSetError> {
+        self.edit(id, |data| {
+            let description = match data.shape {
+                Some(prev) => format!("set radii of {id} from {prev} to {shape}"),
+                None => format!("set radii of {id} to {shape}"),
+            };
+            data.shape = Some(shape);
+            description
+        })
+    }
+
+    /// Sets the pole right ascension, pole declination, and prime meridian model of this body.
+    ///
+    /// The nutation and precession coefficients of these phase angles use the nutation and precession angles of the system.
+    pub fn with_pole_model(
+        self,
+        id: NaifId,
+        pole_right_ascension: PhaseAngle<MAX_NUT_PREC_ANGLES>,
+        pole_declination: PhaseAngle<MAX_NUT_PREC_ANGLES>,
+        prime_meridian: PhaseAngle<MAX_NUT_PREC_ANGLES>,
+    ) -> Result<Self, DataSetError> {
+        self.edit(id, |data| {
+            data.pole_right_ascension = Some(pole_right_ascension);
+            data.pole_declination = Some(pole_declination);
+            data.prime_meridian = Some(prime_meridian);
+            format!(
+                "set pole model of {id} to RA = {pole_right_ascension}, Dec = {pole_declination}, PM = {prime_meridian}"
+            )
+        })
+    }
+
+    /// Sets the centered tilted dipole model of the magnetic field of this body, e.g. for a body other than the Earth.
+    pub fn with_geomagnetic_dipole(
+        self,
+        id: NaifId,
+        dipole: GeomagneticDipole,
+    ) -> Result<Self, DataSetError> {
+        self.edit(id, |data| {
+            data.geomagnetic_dipole = Some(dipole);
+            format!("set geomagnetic {dipole} of {id}")
+        })
+    }
+
+    /// Applies the provided edit to the data of this body, adding the body if needed.
+    fn edit<F: FnOnce(&mut PlanetaryData) -> String>(
+        mut self,
+        id: NaifId,
+        edit: F,
+    ) -> Result<Self, DataSetError> {
+        let (mut data, exists) = match self.get_by_id(id) {
+            Ok(data) => (data, true),
+            Err(_) => (
+                PlanetaryData {
+                    object_id: id,
+                    parent_id: J2000,
+                    ..Default::default()
+                },
+                false,
+            ),
+        };
+
+        let description = edit(&mut data);
+
+        if exists {
+            self.set_by_id(id, data)?;
+            self.record(description);
+        } else {
+            self.push(data, Some(id), None)?;
+            self.record(format!("added {id} and {description}"));
+        }
+
+        Ok(self)
+    }
+
+    fn record(&mut self, description: String) {
+        info!("[planetary data] {description}");
+        self.metadata.record_edit(description);
+        self.set_crc32();
+    }
+}
+
+#[cfg(test)]
+mod planetary_edit_ut {
+    use crate::prelude::Almanac;
+    use crate::structure::planetocentric::dipole::GeomagneticDipole;
+    use crate::structure::planetocentric::ellipsoid::Ellipsoid;
+    use crate::structure::planetocentric::phaseangle::PhaseAngle;
+    use crate::structure::PlanetaryDataSet;
+
+    #[test]
+    fn edit_with_provenance() {
+        let orig = Almanac::new("../data/pck08.pca").unwrap().planetary_data;
+        let moon = orig.get_by_id(301).unwrap();
+
+        let edited = orig
+            .clone()
+            .with_mu(301, 4902.800066)
+            .unwrap()
+            .with_radii(301, Ellipsoid::from_sphere(1737.4))
+            .unwrap();
+
+        let edited_moon = edited.get_by_id(301).unwrap();
+        assert_eq!(edited_moon.mu_km3_s2, 4902.800066);
+        assert_eq!(edited_moon.shape, Some(Ellipsoid::from_sphere(1737.4)));
+        // The orientation is unchanged
+        assert_eq!(edited_moon.prime_meridian, moon.prime_meridian);
+        assert_eq!(edited.len(), orig.len());
+        assert!(edited.check_integrity().is_ok());
+
+        assert_eq!(edited.metadata.edit_log.len(), 2);
+        assert!(edited.metadata.edit_log[0].contains("set mu of 301"));
+        assert!(edited.metadata.edit_log[1].contains("set radii of 301"));
+
+        // The edit log is persisted
+        let mut buf = vec![];
+        der::Encode::encode_to_vec(&edited, &mut buf).unwrap();
+        let reloaded = PlanetaryDataSet::try_from_bytes(buf).unwrap();
+        assert_eq!(reloaded.metadata.edit_log, edited.metadata.edit_log);
+        assert_eq!(reloaded.get_by_id(301).unwrap(), edited_moon);
+
+        // Add a new body with a pole model
+        let pole = PhaseAngle {
+            offset_deg: 10.0,
+            rate_deg: 0.1,
+            ..Default::default()
+        };
+        let edited = edited
+            .with_mu(2000433, 4.463e-4)
+            .unwrap()
+            .with_pole_model(2000433, pole, pole, pole)
+            .unwrap();
+        assert_eq!(edited.len(), orig.len() + 1);
+        let eros = edited.get_by_id(2000433).unwrap();
+        assert_eq!(eros.mu_km3_s2, 4.463e-4);
+        assert_eq!(eros.pole_declination, Some(pole));
+        assert!(edited.metadata.edit_log[2].contains("added 2000433"));
+        assert!(edited.check_integrity().is_ok());
+
+        // Jupiter's dipole moment points toward its north pole, unlike the Earth's
+        let dipole = GeomagneticDipole {
+            north_pole_lat_deg: 79.5,
+            north_pole_lon_deg: 159.2,
+            reference_radius_km: 71_492.0,
+            equatorial_field_nt: -417_400.0,
+        };
+        let edited = edited.with_geomagnetic_dipole(599, dipole).unwrap();
+        assert_eq!(
+            edited.get_by_id(599).unwrap().geomagnetic_dipole,
+            Some(dipole)
+        );
+        assert!(edited.metadata.edit_log[4].contains("set geomagnetic dipole"));
+    }
+}
@@ -0,0 +1,282 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::str::FromStr;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use hifitime::{Duration, Epoch, TimeSeries, Unit};
+use log::info;
+
+use super::{DataSetError, DataSetType};
+use crate::math::rotation::Quaternion;
+use crate::orientations::attitude_series::{AttitudeInterpolation, AttitudeSeries};
+use crate::structure::metadata::Metadata;
+use crate::structure::EulerParameterDataSet;
+use crate::NaifId;
+
+impl EulerParameterDataSet {
+    /// Converts a CSV file of time-tagged quaternions (e.g. from an ADCS simulator) into an Euler Parameter data set.
+    ///
+    /// Refer to [Self::from_quaternion_csv_str] for the expected format. The CSV file is recorded as a source in the metadata.
+    pub fn from_quaternion_csv<P: AsRef<Path>>(
+        path: P,
+        from: NaifId,
+        to: NaifId,
+        step: Option<Duration>,
+        norm_tolerance: f64,
+    ) -> Result<Self, DataSetError> {
+        let contents = read_to_string(&path).map_err(|source| DataSetError::IO {
+            source,
+            action: "reading quaternion CSV",
+        })?;
+
+        let mut dataset = Self::from_quaternion_csv_str(&contents, from, to, step, norm_tolerance)?;
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        dataset.metadata.add_source(name, contents.as_bytes());
+
+        Ok(dataset)
+    }
+
+    /// Converts CSV data of time-tagged quaternions into an Euler Parameter data set, where each entry is the rotation
+    /// from the `from` frame to the `to` frame, named after its epoch in UTC at millisecond precision (e.g. `2024-01-01T00:00:00.000 UTC`).
+    ///
+    /// Each line must be `epoch,w,x,y,z`, where the quaternion uses the Hamiltonian convention with the scalar first.
+    /// Empty lines, lines starting with `#`, and a header line are skipped.
+    ///
+    /// + Each quaternion is normalized, but its norm must be within `norm_tolerance` of one, otherwise the data is likely corrupted.
+    /// + If a `step` is provided, the quaternions are resampled every step from the first to the last epoch using spherical
+    ///   linear interpolation, otherwise the data is stored at the epochs of the CSV.
+    ///
+    /// # Errors
+    /// + If a line cannot be parsed, if its norm is out of tolerance, or if the file contains fewer than two quaternions or duplicate epochs.
+    /// + If the step is shorter than one millisecond, or if there are more samples than the data set can hold.
+    pub fn from_quaternion_csv_str(
+        contents: &str,
+        from: NaifId,
+        to: NaifId,
+        step: Option<Duration>,
+        norm_tolerance: f64,
+    ) -> Result<Self, DataSetError> {
+        let mut nodes = Vec::new();
+
+        for (lno, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+            if fields.len() != 5 {
+                return Err(DataSetError::Conversion {
+                    action: format!(
+                        "line {}: expected `epoch,w,x,y,z` but found {} fields",
+                        lno + 1,
+                        fields.len()
+                    ),
+                });
+            }
+
+            let epoch = match Epoch::from_str(fields[0]) {
+                Ok(epoch) => epoch,
+                Err(e) => {
+                    if nodes.is_empty() && fields[1].parse::<f64>().is_err() {
+                        // This is the header line
+                        continue;
+                    }
+                    return Err(DataSetError::Conversion {
+                        action: format!("line {}: {e}", lno + 1),
+                    });
+                }
+            };
+
+            let mut wxyz = [0.0; 4];
+            for (i, field) in fields[1..].iter().enumerate() {
+                wxyz[i] = field.parse().map_err(|e| DataSetError::Conversion {
+                    action: format!("line {}: {e} when parsing `{field}`", lno + 1),
+                })?;
+            }
+
+            let q = Quaternion {
+                w: wxyz[0],
+                x: wxyz[1],
+                y: wxyz[2],
+                z: wxyz[3],
+                from,
+                to,
+            };
+
+            let norm = q.scalar_norm();
+            if norm.is_nan() || (norm - 1.0).abs() > norm_tolerance {
+                return Err(DataSetError::Conversion {
+                    action: format!(
+                        "line {}: quaternion norm is {norm} which is not within {norm_tolerance} of one",
+                        lno + 1
+                    ),
+                });
+            }
+
+            nodes.push((epoch, q));
+        }
+
+        let mut epochs = nodes.iter().map(|(epoch, _)| *epoch).collect::<Vec<_>>();
+        epochs.sort();
+
+        // The series normalizes the nodes, ensures their signs are continuous, and checks for duplicates.
+        let series =
+            AttitudeSeries::new(from, to, nodes, AttitudeInterpolation::Slerp).map_err(|e| {
+                DataSetError::Conversion {
+                    action: format!("{e}"),
+                }
+            })?;
+
+        if let Some(step) = step {
+            if step < Unit::Millisecond * 1 {
+                return Err(DataSetError::Conversion {
+                    action: format!("resampling step of {step} is shorter than 1 ms"),
+                });
+            }
+            let (start, end) = series.domain();
+            epochs = TimeSeries::inclusive(start, end, step).collect();
+        }
+
+        let mut dataset = EulerParameterDataSet::default();
+        let mut prev_name = String::new();
+
+        for epoch in epochs {
+            let q = series.at(epoch).map_err(|e| DataSetError::Conversion {
+                action: format!("{e}"),
+            })?;
+
+            let (y, mo, d, h, mi, s, ns) = epoch.to_gregorian_utc();
+            let name = format!(
+                "{y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:02}.{:03} UTC",
+                ns / 1_000_000
+            );
+            if name == prev_name {
+                return Err(DataSetError::Conversion {
+                    action: format!("several quaternions within the same millisecond of {name}"),
+                });
+            }
+
+            dataset.push(q, None, Some(&name))?;
+            prev_name = name;
+        }
+
+        info!("Added {} quaternions", dataset.lut.by_name.len());
+
+        dataset.metadata = Metadata {
+            dataset_type: DataSetType::EulerParameterData,
+            ..Default::default()
+        }
+        .with_tool_version(format!("anise {}", env!("CARGO_PKG_VERSION")));
+        dataset.set_crc32();
+
+        Ok(dataset)
+    }
+}
+
+#[cfg(test)]
+mod quaternion_csv_ut {
+    use crate::math::rotation::Quaternion;
+    use crate::structure::{dataset::DataSetType, EulerParameterDataSet};
+    use hifitime::Unit;
+
+    const CSV: &str = "epoch,q_w,q_x,q_y,q_z
+# Spinning about Z at 0.005 rad/s
+2025-01-01T00:00:00 UTC,1.0,0.0,0.0,0.0
+2025-01-01T00:01:00 UTC,0.9887710779360422,0.0,0.0,0.14943813247359922
+2025-01-01T00:02:00 UTC,0.955336489125606,0.0,0.0,0.29552020666133955
+";
+
+    #[test]
+    fn quaternion_csv_raw() {
+        let dataset =
+            EulerParameterDataSet::from_quaternion_csv_str(CSV, 1, -1000, None, 1e-9).unwrap();
+        assert_eq!(
+            dataset.metadata.dataset_type,
+            DataSetType::EulerParameterData
+        );
+        assert_eq!(dataset.data.len(), 3);
+
+        let q = dataset.get_by_name("2025-01-01T00:02:00.000 UTC").unwrap();
+        let expected = Quaternion::about_z(0.6, 1, -1000);
+        assert!((q.as_vector() - expected.as_vector()).norm() < 1e-12);
+        assert_eq!((q.from, q.to), (1, -1000));
+    }
+
+    #[test]
+    fn quaternion_csv_resampled() {
+        let dataset = EulerParameterDataSet::from_quaternion_csv_str(
+            CSV,
+            1,
+            -1000,
+            Some(Unit::Second * 30),
+            1e-9,
+        )
+        .unwrap();
+        assert_eq!(dataset.data.len(), 5);
+
+        let q = dataset.get_by_name("2025-01-01T00:01:30.000 UTC").unwrap();
+        let expected = Quaternion::about_z(0.45, 1, -1000);
+        assert!((q.as_vector() - expected.as_vector()).norm() < 1e-12);
+
+        // Sub-millisecond resampling is not supported
+        assert!(EulerParameterDataSet::from_quaternion_csv_str(
+            CSV,
+            1,
+            -1000,
+            Some(Unit::Microsecond * 1),
+            1e-9
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn quaternion_csv_invalid() {
+        // Not normalized
+        let csv = "2025-01-01T00:00:00 UTC,1.0,0.1,0.0,0.0
+2025-01-01T00:01:00 UTC,1.0,0.0,0.0,0.0";
+        assert!(EulerParameterDataSet::from_quaternion_csv_str(csv, 1, -1000, None, 1e-6).is_err());
+        // But fine with a looser tolerance
+        assert!(EulerParameterDataSet::from_quaternion_csv_str(csv, 1, -1000, None, 1e-2).is_ok());
+        // Missing a component
+        assert!(EulerParameterDataSet::from_quaternion_csv_str(
+            "2025-01-01T00:00:00 UTC,1.0,0.0,0.0",
+            1,
+            -1000,
+            None,
+            1e-6
+        )
+        .is_err());
+        // Single quaternion
+        assert!(EulerParameterDataSet::from_quaternion_csv_str(
+            "2025-01-01T00:00:00 UTC,1.0,0.0,0.0,0.0",
+            1,
+            -1000,
+            None,
+            1e-6
+        )
+        .is_err());
+        // Invalid epoch after the header
+        assert!(EulerParameterDataSet::from_quaternion_csv_str(
+            "2025-01-01T00:00:00 UTC,1.0,0.0,0.0,0.0\nyesterday,1.0,0.0,0.0,0.0",
+            1,
+            -1000,
+            None,
+            1e-6
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,203 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{DataSet, DataSetError, DataSetT, DataSetType};
+use crate::{
+    structure::{LocationDataSet, SpacecraftDataSet},
+    NaifId,
+};
+
+/// A TOML entry of a data set, at least one of the ID or alias must be set.
+#[derive(Serialize, Deserialize)]
+struct TomlEntry<T> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<NaifId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    alias: Option<String>,
+    value: T,
+}
+
+/// TOML does not support top level arrays, so all entries are stored in the `data` array of tables.
+#[derive(Serialize, Deserialize)]
+struct TomlSet<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<TomlEntry<T>>,
+}
+
+fn from_toml<T, const ENTRIES: usize>(
+    repr: &str,
+    dataset_type: DataSetType,
+) -> Result<DataSet<T, ENTRIES>, DataSetError>
+where
+    T: DataSetT + DeserializeOwned,
+{
+    let set: TomlSet<T> = ::toml::from_str(repr).map_err(|e| DataSetError::Conversion {
+        action: format!("parsing TOML {}: {e}", T::NAME),
+    })?;
+
+    let mut dataset = DataSet::<T, ENTRIES>::default();
+    for entry in set.data {
+        dataset.push(entry.value, entry.id, entry.alias.as_deref())?;
+    }
+
+    dataset.metadata.dataset_type = dataset_type;
+    dataset.set_crc32();
+
+    Ok(dataset)
+}
+
+fn to_toml<T, const ENTRIES: usize>(dataset: &DataSet<T, ENTRIES>) -> Result<String, DataSetError>
+where
+    T: DataSetT + Serialize,
+{
+    let mut set = TomlSet { data: Vec::new() };
+
+    for (index, (id, alias)) in dataset.lut.entries().iter() {
+        let value =
+            dataset
+                .data
+                .get(*index as usize)
+                .cloned()
+                .ok_or_else(|| DataSetError::Conversion {
+                    action: format!("{} has no entry #{index}", T::NAME),
+                })?;
+
+        set.data.push(TomlEntry {
+            id: *id,
+            alias: alias.as_ref().map(|alias| alias.to_string()),
+            value,
+        });
+    }
+
+    ::toml::to_string_pretty(&set).map_err(|e| DataSetError::Conversion {
+        action: format!("serializing {} as TOML: {e}", T::NAME),
+    })
+}
+
+impl SpacecraftDataSet {
+    /// Builds a spacecraft data set from its TOML representation, where each `[[data]]` table has an optional `id`,
+    /// an optional `alias`, and the spacecraft data as its `value` table.
+    pub fn from_toml(repr: &str) -> Result<Self, DataSetError> {
+        from_toml(repr, DataSetType::SpacecraftData)
+    }
+
+    /// Returns the TOML representation of this spacecraft data set.
+    pub fn to_toml(&self) -> Result<String, DataSetError> {
+        to_toml(self)
+    }
+}
+
+impl LocationDataSet {
+    /// Builds a location data set from its TOML representation, where each `[[data]]` table has an optional `id`,
+    /// an optional `alias`, and the location as its `value` table.
+    pub fn from_toml(repr: &str) -> Result<Self, DataSetError> {
+        from_toml(repr, DataSetType::LocationData)
+    }
+
+    /// Returns the TOML representation of this location data set.
+    pub fn to_toml(&self) -> Result<String, DataSetError> {
+        to_toml(self)
+    }
+}
+
+#[cfg(test)]
+mod toml_set_ut {
+    use crate::constants::frames::EARTH_ITRF93;
+    use crate::structure::{
+        dataset::DataSetType,
+        location::{FrequencyBand, Location, RefractionModel, TerrainMask},
+        spacecraft::{Mass, SRPData, SpacecraftData},
+        LocationDataSet, SpacecraftDataSet,
+    };
+
+    #[test]
+    fn spacecraft_toml() {
+        let repr = r#"
+[[data]]
+id = -20
+alias = "Lunar Reconnaissance Orbiter"
+
+[data.value.mass]
+dry_mass_kg = 1018.0
+prop_mass_kg = 898.0
+extra_mass_kg = 0.0
+
+[data.value.srp_data]
+area_m2 = 14.0
+coeff_reflectivity = 1.3
+
+[[data]]
+alias = "Cubesat"
+
+[data.value.mass]
+dry_mass_kg = 4.0
+prop_mass_kg = 0.0
+extra_mass_kg = 0.0
+"#;
+
+        let dataset = SpacecraftDataSet::from_toml(repr).unwrap();
+        assert_eq!(dataset.metadata.dataset_type, DataSetType::SpacecraftData);
+        assert_eq!(dataset.data.len(), 2);
+
+        let lro = dataset.get_by_id(-20).unwrap();
+        assert_eq!(
+            lro,
+            SpacecraftData {
+                mass: Some(Mass::from_dry_and_prop_masses(1018.0, 898.0)),
+                srp_data: Some(SRPData {
+                    area_m2: 14.0,
+                    coeff_reflectivity: 1.3
+                }),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            dataset.get_by_name("Lunar Reconnaissance Orbiter").unwrap(),
+            lro
+        );
+
+        // Dump and reload
+        let dumped = dataset.to_toml().unwrap();
+        let reloaded = SpacecraftDataSet::from_toml(&dumped).unwrap();
+        assert_eq!(reloaded.lut, dataset.lut);
+        assert_eq!(reloaded.data, dataset.data);
+    }
+
+    #[test]
+    fn location_toml() {
+        let mut dataset = LocationDataSet::default();
+        let dss65 = Location {
+            latitude_deg: 40.427_222,
+            longitude_deg: 4.250_556,
+            height_km: 0.834_939,
+            frame: EARTH_ITRF93.into(),
+            terrain_mask: TerrainMask::from_flat_terrain(5.0),
+            min_elevation_deg: Some(6.0),
+            tx_bands: vec![FrequencyBand::S],
+            rx_bands: vec![FrequencyBand::S, FrequencyBand::X],
+            refraction: Some(RefractionModel::ItuRP834),
+            ..Default::default()
+        };
+        dataset
+            .push(dss65.clone(), Some(65), Some("DSS-65"))
+            .unwrap();
+
+        let dumped = dataset.to_toml().unwrap();
+        assert!(dumped.contains("DSS-65"));
+
+        let reloaded = LocationDataSet::from_toml(&dumped).unwrap();
+        assert_eq!(reloaded.metadata.dataset_type, DataSetType::LocationData);
+        assert_eq!(reloaded.get_by_name("DSS-65").unwrap(), dss65);
+
+        assert!(LocationDataSet::from_toml("data = 1").is_err());
+    }
+}
@@ -0,0 +1,212 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt::Write;
+
+use log::warn;
+
+use crate::structure::planetocentric::phaseangle::PhaseAngle;
+use crate::structure::planetocentric::{PlanetaryData, MAX_NUT_PREC_ANGLES};
+use crate::structure::PlanetaryDataSet;
+use crate::NaifId;
+
+/// Column at which the values of the assignments start, matching the layout of the NAIF PCKs.
+const VALUE_COLUMN: usize = 30;
+
+impl PlanetaryDataSet {
+    /// Exports this planetary data set as a KPL text PCK (TPC), the reverse operation of `convert_tpc`, so that edited data
+    /// may be loaded by SPICE-based tools.
+    ///
+    /// For each entry with an ID, the GM, radii, pole right ascension, pole declination, prime meridian (with their
+    /// nutation and precession coefficients), long axis, north geomagnetic pole, and the nutation and precession angles are
+    /// written in the `\begindata` block. Floats are written with the shortest representation which parses back to the exact same value.
+    ///
+    /// # Limitations
+    /// + Entries only referenced by name are skipped since TPC keywords are built from the NAIF ID.
+    /// + The parent ID and the names of the entries are not stored in TPC files: `convert_tpc` infers the former from the NAIF ID.
+    /// + The rotation data of an entry is only exported if its pole right ascension, pole declination, and prime meridian are all set.
+    /// + The strength of the geomagnetic dipole is not stored in TPC files: `convert_tpc` only sets that of the Earth.
+    pub fn to_tpc_string(&self) -> String {
+        let mut ids = self
+            .lut
+            .entries()
+            .values()
+            .filter_map(|(opt_id, opt_name)| {
+                if opt_id.is_none() {
+                    warn!("[to_tpc_string] skipping {opt_name:?}: entry has no ID");
+                }
+                *opt_id
+            })
+            .collect::<Vec<NaifId>>();
+        ids.sort_unstable();
+
+        let mut tpc = String::new();
+        writeln!(tpc, "KPL/PCK").unwrap();
+        writeln!(tpc).unwrap();
+        writeln!(
+            tpc,
+            "Planetary constants exported by ANISE {} from a planetary data set with {} entries.",
+            env!("CARGO_PKG_VERSION"),
+            ids.len()
+        )
+        .unwrap();
+        writeln!(tpc).unwrap();
+        writeln!(tpc, "\\begindata").unwrap();
+
+        for id in ids {
+            match self.get_by_id(id) {
+                Ok(data) => write_body(&mut tpc, id, &data),
+                Err(e) => warn!("[to_tpc_string] skipping {id}: {e}"),
+            }
+        }
+
+        writeln!(tpc).unwrap();
+        writeln!(tpc, "\\begintext").unwrap();
+        tpc
+    }
+}
+
+/// Writes all of the assignments of this body.
+fn write_body(tpc: &mut String, id: NaifId, data: &PlanetaryData) {
+    writeln!(tpc).unwrap();
+    write_assignment(tpc, id, "GM", &[data.mu_km3_s2], 1);
+
+    if let Some(shape) = data.shape {
+        write_assignment(
+            tpc,
+            id,
+            "RADII",
+            &[
+                shape.semi_major_equatorial_radius_km,
+                shape.semi_minor_equatorial_radius_km,
+                shape.polar_radius_km,
+            ],
+            3,
+        );
+    }
+
+    match (
+        data.pole_right_ascension,
+        data.pole_declination,
+        data.prime_meridian,
+    ) {
+        (Some(pole_ra), Some(pole_dec), Some(pm)) => {
+            for (keyword, nut_prec_keyword, angle) in [
+                ("POLE_RA", "NUT_PREC_RA", pole_ra),
+                ("POLE_DEC", "NUT_PREC_DEC", pole_dec),
+                ("PM", "NUT_PREC_PM", pm),
+            ] {
+                write_phase_angle(tpc, id, keyword, nut_prec_keyword, &angle);
+            }
+        }
+        (None, None, None) => {}
+        _ => warn!("[to_tpc_string] {id} has incomplete rotation data -- skipping it"),
+    }
+
+    if let Some(long_axis) = data.long_axis {
+        write_assignment(tpc, id, "LONG_AXIS", &[long_axis], 1);
+    }
+
+    if let Some(dipole) = data.geomagnetic_dipole {
+        write_assignment(
+            tpc,
+            id,
+            "N_GEOMAG_CTR_DIPOLE_LON",
+            &[dipole.north_pole_lon_deg],
+            1,
+        );
+        write_assignment(
+            tpc,
+            id,
+            "N_GEOMAG_CTR_DIPOLE_LAT",
+            &[dipole.north_pole_lat_deg],
+            1,
+        );
+    }
+
+    let num_angles = usize::from(data.num_nut_prec_angles).min(MAX_NUT_PREC_ANGLES);
+    if num_angles > 0 {
+        let angles = data.nut_prec_angles[..num_angles]
+            .iter()
+            .flat_map(|angle| [angle.offset_deg, angle.rate_deg])
+            .collect::<Vec<f64>>();
+        write_assignment(tpc, id, "NUT_PREC_ANGLES", &angles, 2);
+    }
+}
+
+/// Writes the offset, rate, and acceleration of this phase angle, and its nutation and precession coefficients, if any.
+fn write_phase_angle(
+    tpc: &mut String,
+    id: NaifId,
+    keyword: &str,
+    nut_prec_keyword: &str,
+    angle: &PhaseAngle<MAX_NUT_PREC_ANGLES>,
+) {
+    write_assignment(
+        tpc,
+        id,
+        keyword,
+        &[angle.offset_deg, angle.rate_deg, angle.accel_deg],
+        3,
+    );
+
+    let coeffs_count = usize::from(angle.coeffs_count).min(MAX_NUT_PREC_ANGLES);
+    if coeffs_count > 0 {
+        write_assignment(tpc, id, nut_prec_keyword, &angle.coeffs[..coeffs_count], 3);
+    }
+}
+
+/// Writes `BODY{id}_{keyword} = ( values )`, with `per_line` values on each line.
+fn write_assignment(tpc: &mut String, id: NaifId, keyword: &str, values: &[f64], per_line: usize) {
+    let lhs = format!("BODY{id}_{keyword}");
+    for (lno, chunk) in values.chunks(per_line).enumerate() {
+        if lno == 0 {
+            write!(tpc, "    {lhs:<width$} = (", width = VALUE_COLUMN - 4).unwrap();
+        } else {
+            writeln!(tpc).unwrap();
+            write!(tpc, "{:width$}", "", width = VALUE_COLUMN + 3).unwrap();
+        }
+        for value in chunk {
+            // The debug representation always includes a decimal point or an exponent, so the value is never read as an integer.
+            write!(tpc, " {value:?}").unwrap();
+        }
+    }
+    writeln!(tpc, " )").unwrap();
+}
+
+#[cfg(test)]
+mod tpc_export_ut {
+    use crate::naif::kpl::parser::convert_tpc;
+
+    #[test]
+    fn tpc_round_trip() {
+        let dataset = convert_tpc("../data/pck00008.tpc", "../data/gm_de431.tpc").unwrap();
+
+        let tpc = dataset.to_tpc_string();
+        println!("{tpc}");
+        assert!(tpc.contains("BODY399_POLE_RA"));
+        assert!(tpc.contains("BODY3_NUT_PREC_ANGLES"));
+        assert!(tpc.contains("BODY399_N_GEOMAG_CTR_DIPOLE_LAT"));
+
+        // The GM and the constants are in the same file now.
+        let path = std::env::temp_dir().join("anise-tpc-round-trip.tpc");
+        std::fs::write(&path, &tpc).unwrap();
+        let reloaded = convert_tpc(&path, &path).unwrap();
+
+        assert_eq!(reloaded.len(), dataset.len());
+        for (id, _) in &dataset.lut.by_id {
+            assert_eq!(
+                reloaded.get_by_id(*id).unwrap(),
+                dataset.get_by_id(*id).unwrap(),
+                "{id} differs"
+            );
+        }
+    }
+}
@@ -0,0 +1,632 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+use der::{Decode, Encode, Reader, Writer};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "metaload")]
+use serde_dhall::StaticType;
+
+use super::dataset::DataSetT;
+#[cfg(feature = "metaload")]
+use super::{
+    dataset::{DataSetError, DataSetType},
+    LocationDataSet,
+};
+#[cfg(feature = "metaload")]
+use crate::NaifId;
+use crate::{
+    astro::{
+        signal_delay::{Klobuchar, Saastamoinen},
+        AzElRange,
+    },
+    frames::FrameUid,
+    math::angles::{between_0_360, between_pm_180},
+};
+
+/// Frequency bands of the ground antennas, following the IEEE Std 521 nomenclature.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "metaload", derive(StaticType))]
+#[repr(u8)]
+pub enum FrequencyBand {
+    UHF = 0,
+    L = 1,
+    S = 2,
+    C = 3,
+    X = 4,
+    Ku = 5,
+    K = 6,
+    Ka = 7,
+}
+
+impl TryFrom<u8> for FrequencyBand {
+    type Error = der::Error;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        match val {
+            0 => Ok(Self::UHF),
+            1 => Ok(Self::L),
+            2 => Ok(Self::S),
+            3 => Ok(Self::C),
+            4 => Ok(Self::X),
+            5 => Ok(Self::Ku),
+            6 => Ok(Self::K),
+            7 => Ok(Self::Ka),
+            _ => Err(der::ErrorKind::Value {
+                tag: der::Tag::Integer,
+            }
+            .into()),
+        }
+    }
+}
+
+impl Encode for FrequencyBand {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        (*self as u8).encoded_len()
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        (*self as u8).encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for FrequencyBand {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let asu8: u8 = decoder.decode()?;
+        Self::try_from(asu8)
+    }
+}
+
+/// Atmospheric refraction models used to correct the geometric elevation of an object seen from a location.
+///
+/// Refraction bends the line of sight towards the ground, so the apparent elevation of an object is higher than its geometric
+/// elevation. The correction is only significant near the horizon, and is not applied below one degree under the horizon.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "metaload", derive(StaticType))]
+#[repr(u8)]
+pub enum RefractionModel {
+    /// Optical refraction of Bennett (1982), in the form of Saemundsson (1986) for geometric elevations, for a standard atmosphere
+    /// (pressure of 1010 mbar and temperature of 10 degrees Celsius).
+    Bennett = 0,
+    /// Radio refraction of ITU-R P.834 for a standard atmosphere, accounting for the height of the location.
+    ItuRP834 = 1,
+}
+
+impl RefractionModel {
+    /// Returns the refraction correction in degrees to add to the geometric elevation (in degrees) of an object seen from a height (in km).
+    pub fn correction_deg(&self, elevation_deg: f64, height_km: f64) -> f64 {
+        if elevation_deg < -1.0 {
+            return 0.0;
+        }
+        match self {
+            Self::Bennett => {
+                // Saemundsson's formula returns the refraction in arcminutes, and the constant term ensures that it is zero at zenith (Meeus, 1998).
+                (1.02
+                    / (elevation_deg + 10.3 / (elevation_deg + 5.11))
+                        .to_radians()
+                        .tan()
+                    + 0.0019279)
+                    / 60.0
+            }
+            Self::ItuRP834 => {
+                let h_km = height_km.max(0.0);
+                1.0 / (1.314
+                    + 0.6437 * elevation_deg
+                    + 0.02869 * elevation_deg.powi(2)
+                    + h_km * (0.2305 + 0.09428 * elevation_deg + 0.01096 * elevation_deg.powi(2))
+                    + 0.008583 * h_km.powi(2))
+            }
+        }
+    }
+}
+
+impl TryFrom<u8> for RefractionModel {
+    type Error = der::Error;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        match val {
+            0 => Ok(Self::Bennett),
+            1 => Ok(Self::ItuRP834),
+            _ => Err(der::ErrorKind::Value {
+                tag: der::Tag::Integer,
+            }
+            .into()),
+        }
+    }
+}
+
+impl Encode for RefractionModel {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        (*self as u8).encoded_len()
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        (*self as u8).encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for RefractionModel {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let asu8: u8 = decoder.decode()?;
+        Self::try_from(asu8)
+    }
+}
+
+/// A terrain mask entry: from this azimuth (in degrees) onward and until the next entry, the terrain blocks
+/// the line of sight of the location up to the provided elevation (in degrees).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "metaload", derive(StaticType))]
+pub struct TerrainMask {
+    pub azimuth_deg: f64,
+    pub elevation_mask_deg: f64,
+}
+
+impl TerrainMask {
+    /// Returns a terrain mask with the same elevation for every azimuth.
+    pub fn from_flat_terrain(elevation_mask_deg: f64) -> Vec<Self> {
+        vec![Self {
+            azimuth_deg: 0.0,
+            elevation_mask_deg,
+        }]
+    }
+}
+
+impl Encode for TerrainMask {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        self.azimuth_deg.encoded_len()? + self.elevation_mask_deg.encoded_len()?
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        self.azimuth_deg.encode(encoder)?;
+        self.elevation_mask_deg.encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for TerrainMask {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        Ok(Self {
+            azimuth_deg: decoder.decode()?,
+            elevation_mask_deg: decoder.decode()?,
+        })
+    }
+}
+
+/// Location is defined by its latitude, longitude, and height above the geoid of the body fixed frame, along with
+/// the constraints of its antenna.
+///
+/// The optional antenna constraints (minimum elevation, transmit and receive bands, slew rate limit) are consulted
+/// by the Almanac when computing the visibility of an object from this location.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "metaload", derive(StaticType))]
+pub struct Location {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub height_km: f64,
+    /// Frame on which this location rests, must be a body fixed frame
+    pub frame: FrameUid,
+    /// Terrain mask, sorted by increasing azimuth
+    #[serde(default)]
+    pub terrain_mask: Vec<TerrainMask>,
+    /// If set to true, the terrain mask is not used to compute the visibility
+    #[serde(default)]
+    pub terrain_mask_ignored: bool,
+    /// Minimum elevation of the antenna in degrees, applied in addition to the terrain mask
+    pub min_elevation_deg: Option<f64>,
+    /// Bands on which this location can transmit
+    #[serde(default)]
+    pub tx_bands: Vec<FrequencyBand>,
+    /// Bands on which this location can receive
+    #[serde(default)]
+    pub rx_bands: Vec<FrequencyBand>,
+    /// Maximum slew rate of the antenna in degrees per second
+    pub max_slew_rate_deg_s: Option<f64>,
+    /// Atmospheric refraction model applied to the elevations computed from this location
+    #[serde(default)]
+    pub refraction: Option<RefractionModel>,
+}
+
+impl DataSetT for Location {
+    const NAME: &'static str = "location data";
+}
+
+impl Location {
+    /// Specifies what optional data is available in this structure.
+    ///
+    /// Returns:
+    /// + Bit 0 is set if `min_elevation_deg` is available
+    /// + Bit 1 is set if `max_slew_rate_deg_s` is available
+    /// + Bit 2 is set if `refraction` is available
+    fn available_data(&self) -> u8 {
+        let mut bits: u8 = 0;
+
+        if self.min_elevation_deg.is_some() {
+            bits |= 1 << 0;
+        }
+        if self.max_slew_rate_deg_s.is_some() {
+            bits |= 1 << 1;
+        }
+        if self.refraction.is_some() {
+            bits |= 1 << 2;
+        }
+
+        bits
+    }
+
+    /// Returns the elevation mask in degrees at the provided azimuth, accounting for both the terrain mask (unless ignored)
+    /// and the minimum elevation of the antenna. If neither is set, the mask is the local horizon (zero degrees).
+    pub fn elevation_mask_at_azimuth_deg(&self, azimuth_deg: f64) -> f64 {
+        let mut mask_deg = self.min_elevation_deg.unwrap_or(0.0);
+
+        if !self.terrain_mask_ignored && !self.terrain_mask.is_empty() {
+            let azimuth_deg = between_0_360(azimuth_deg);
+            // The terrain mask wraps around, so azimuths before the first entry use the last entry.
+            let terrain = self
+                .terrain_mask
+                .iter()
+                .rev()
+                .find(|mask| mask.azimuth_deg <= azimuth_deg)
+                .or(self.terrain_mask.last())
+                .unwrap();
+
+            mask_deg = mask_deg.max(terrain.elevation_mask_deg);
+        }
+
+        mask_deg
+    }
+
+    /// Returns whether the provided azimuth, elevation, and range is visible from this location, i.e. it is above the
+    /// elevation mask and not obstructed.
+    pub fn is_visible(&self, aer: &AzElRange) -> bool {
+        aer.is_valid()
+            && !aer.is_obstructed()
+            && aer.elevation_deg >= self.elevation_mask_at_azimuth_deg(aer.azimuth_deg)
+    }
+
+    /// Returns whether the antenna can slew between both azimuth and elevation pointings without exceeding its
+    /// maximum slew rate. This is always true if the location does not specify a maximum slew rate.
+    pub fn can_slew(&self, from: &AzElRange, to: &AzElRange) -> bool {
+        match self.max_slew_rate_deg_s {
+            None => true,
+            Some(max_rate_deg_s) => {
+                let dt_s = (to.epoch - from.epoch).abs().to_seconds();
+                let azimuth_delta_deg = between_pm_180(to.azimuth_deg - from.azimuth_deg).abs();
+                let elevation_delta_deg = (to.elevation_deg - from.elevation_deg).abs();
+                // Az-El mounts move each axis independently, so the slowest axis drives the slew duration.
+                let delta_deg = azimuth_delta_deg.max(elevation_delta_deg);
+                if dt_s > 0.0 {
+                    delta_deg / dt_s <= max_rate_deg_s
+                } else {
+                    delta_deg < f64::EPSILON
+                }
+            }
+        }
+    }
+
+    /// Returns the one-way signal delay in kilometers between this location and the object at the provided azimuth,
+    /// elevation, and range, due to the optional troposphere and ionosphere models.
+    pub fn signal_delay_km(
+        &self,
+        aer: &AzElRange,
+        troposphere: Option<&Saastamoinen>,
+        ionosphere: Option<&Klobuchar>,
+    ) -> f64 {
+        let tropo_km = troposphere.map_or(0.0, |tropo| {
+            tropo.slant_delay_km(aer.elevation_deg, self.latitude_deg, self.height_km)
+        });
+        let iono_km = ionosphere.map_or(0.0, |iono| {
+            iono.slant_delay_km(
+                aer.epoch,
+                self.latitude_deg,
+                self.longitude_deg,
+                aer.azimuth_deg,
+                aer.elevation_deg,
+            )
+        });
+        tropo_km + iono_km
+    }
+
+    /// Returns whether this location can transmit on the provided band.
+    pub fn can_transmit(&self, band: FrequencyBand) -> bool {
+        self.tx_bands.contains(&band)
+    }
+
+    /// Returns whether this location can receive on the provided band.
+    pub fn can_receive(&self, band: FrequencyBand) -> bool {
+        self.rx_bands.contains(&band)
+    }
+}
+
+impl Encode for Location {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        let available_flags = self.available_data();
+        self.latitude_deg.encoded_len()?
+            + self.longitude_deg.encoded_len()?
+            + self.height_km.encoded_len()?
+            + self.frame.ephemeris_id.encoded_len()?
+            + self.frame.orientation_id.encoded_len()?
+            + self.terrain_mask.encoded_len()?
+            + self.terrain_mask_ignored.encoded_len()?
+            + available_flags.encoded_len()?
+            + self.min_elevation_deg.encoded_len()?
+            + self.tx_bands.encoded_len()?
+            + self.rx_bands.encoded_len()?
+            + self.max_slew_rate_deg_s.encoded_len()?
+            + self.refraction.encoded_len()?
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        self.latitude_deg.encode(encoder)?;
+        self.longitude_deg.encode(encoder)?;
+        self.height_km.encode(encoder)?;
+        self.frame.ephemeris_id.encode(encoder)?;
+        self.frame.orientation_id.encode(encoder)?;
+        self.terrain_mask.encode(encoder)?;
+        self.terrain_mask_ignored.encode(encoder)?;
+        self.available_data().encode(encoder)?;
+        self.min_elevation_deg.encode(encoder)?;
+        self.tx_bands.encode(encoder)?;
+        self.rx_bands.encode(encoder)?;
+        self.max_slew_rate_deg_s.encode(encoder)?;
+        self.refraction.encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for Location {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let latitude_deg = decoder.decode()?;
+        let longitude_deg = decoder.decode()?;
+        let height_km = decoder.decode()?;
+        let frame = FrameUid {
+            ephemeris_id: decoder.decode()?,
+            orientation_id: decoder.decode()?,
+        };
+        let terrain_mask = decoder.decode()?;
+        let terrain_mask_ignored = decoder.decode()?;
+
+        let data_flags: u8 = decoder.decode()?;
+
+        let min_elevation_deg = if data_flags & (1 << 0) != 0 {
+            Some(decoder.decode()?)
+        } else {
+            None
+        };
+
+        let tx_bands = decoder.decode()?;
+        let rx_bands = decoder.decode()?;
+
+        let max_slew_rate_deg_s = if data_flags & (1 << 1) != 0 {
+            Some(decoder.decode()?)
+        } else {
+            None
+        };
+
+        let refraction = if data_flags & (1 << 2) != 0 {
+            Some(decoder.decode()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            latitude_deg,
+            longitude_deg,
+            height_km,
+            frame,
+            terrain_mask,
+            terrain_mask_ignored,
+            min_elevation_deg,
+            tx_bands,
+            rx_bands,
+            max_slew_rate_deg_s,
+            refraction,
+        })
+    }
+}
+
+/// A Dhall representation of a location data set, to build an ANISE location data file from a human readable configuration.
+#[cfg(feature = "metaload")]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, StaticType)]
+pub struct LocationDhallSet {
+    pub data: Vec<LocationDhallSetEntry>,
+}
+
+/// An entry of a Location Dhall set, at least one of the ID or alias must be set.
+#[cfg(feature = "metaload")]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, StaticType)]
+pub struct LocationDhallSetEntry {
+    pub id: Option<NaifId>,
+    pub alias: Option<String>,
+    pub value: Location,
+}
+
+#[cfg(feature = "metaload")]
+impl LocationDhallSet {
+    /// Parses the provided Dhall string as a location Dhall set.
+    pub fn from_dhall(repr: &str) -> Result<Self, DataSetError> {
+        serde_dhall::from_str(repr)
+            .static_type_annotation()
+            .parse::<Self>()
+            .map_err(|e| DataSetError::Conversion {
+                action: format!("parsing location Dhall set: {e}"),
+            })
+    }
+
+    /// Serializes this location Dhall set as a Dhall string.
+    pub fn to_dhall(&self) -> Result<String, DataSetError> {
+        serde_dhall::serialize(&self)
+            .static_type_annotation()
+            .to_string()
+            .map_err(|e| DataSetError::Conversion {
+                action: format!("serializing location Dhall set: {e}"),
+            })
+    }
+
+    /// Converts this Dhall set into a location data set, which can then be saved as an ANISE file.
+    pub fn to_dataset(&self) -> Result<LocationDataSet, DataSetError> {
+        let mut dataset = LocationDataSet::default();
+
+        for entry in &self.data {
+            dataset.push(entry.value.clone(), entry.id, entry.alias.as_deref())?;
+        }
+
+        dataset.metadata.dataset_type = DataSetType::LocationData;
+        dataset.set_crc32();
+
+        Ok(dataset)
+    }
+
+    /// Builds a Dhall set from the provided location data set.
+    pub fn from_dataset(dataset: &LocationDataSet) -> Result<Self, DataSetError> {
+        let mut me = Self::default();
+
+        for (index, (id, alias)) in dataset.lut.entries().iter() {
+            let value = dataset.data.get(*index as usize).cloned().ok_or_else(|| {
+                DataSetError::Conversion {
+                    action: format!("location data set has no entry #{index}"),
+                }
+            })?;
+
+            me.data.push(LocationDhallSetEntry {
+                id: *id,
+                alias: alias.as_ref().map(|alias| alias.to_string()),
+                value,
+            });
+        }
+
+        Ok(me)
+    }
+}
+
+#[cfg(test)]
+mod location_ut {
+    use super::{Decode, Encode, FrequencyBand, Location, RefractionModel, TerrainMask};
+    use crate::astro::AzElRange;
+    use crate::constants::frames::EARTH_ITRF93;
+    use hifitime::{Epoch, TimeUnits};
+
+    fn madrid() -> Location {
+        Location {
+            latitude_deg: 40.427_222,
+            longitude_deg: 4.250_556,
+            height_km: 0.834_939,
+            frame: EARTH_ITRF93.into(),
+            terrain_mask: vec![
+                TerrainMask {
+                    azimuth_deg: 0.0,
+                    elevation_mask_deg: 5.0,
+                },
+                TerrainMask {
+                    azimuth_deg: 35.0,
+                    elevation_mask_deg: 10.0,
+                },
+                TerrainMask {
+                    azimuth_deg: 270.0,
+                    elevation_mask_deg: 3.0,
+                },
+            ],
+            terrain_mask_ignored: false,
+            min_elevation_deg: Some(6.0),
+            tx_bands: vec![FrequencyBand::S, FrequencyBand::X],
+            rx_bands: vec![FrequencyBand::S, FrequencyBand::X, FrequencyBand::Ka],
+            max_slew_rate_deg_s: Some(1.0),
+            refraction: Some(RefractionModel::ItuRP834),
+        }
+    }
+
+    fn aer(epoch: Epoch, azimuth_deg: f64, elevation_deg: f64) -> AzElRange {
+        AzElRange {
+            epoch,
+            azimuth_deg,
+            elevation_deg,
+            range_km: 40_000.0,
+            range_rate_km_s: 0.0,
+            obstructed_by: None,
+            light_time: 0.13.seconds(),
+        }
+    }
+
+    #[test]
+    fn location_min_repr() {
+        let repr = Location::default();
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = Location::from_der(&buf).unwrap();
+
+        assert_eq!(repr, repr_dec);
+    }
+
+    #[test]
+    fn location_with_constraints() {
+        let repr = madrid();
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = Location::from_der(&buf).unwrap();
+
+        assert_eq!(repr, repr_dec);
+    }
+
+    #[test]
+    fn location_visibility() {
+        let loc = madrid();
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+        // Terrain mask is 5 deg but the antenna minimum is 6 deg
+        assert_eq!(loc.elevation_mask_at_azimuth_deg(10.0), 6.0);
+        assert_eq!(loc.elevation_mask_at_azimuth_deg(100.0), 10.0);
+        assert_eq!(loc.elevation_mask_at_azimuth_deg(300.0), 6.0);
+        assert!(loc.is_visible(&aer(epoch, 10.0, 7.0)));
+        assert!(!loc.is_visible(&aer(epoch, 100.0, 7.0)));
+
+        let mut ignored = loc.clone();
+        ignored.terrain_mask_ignored = true;
+        assert!(ignored.is_visible(&aer(epoch, 100.0, 7.0)));
+        ignored.min_elevation_deg = None;
+        assert_eq!(ignored.elevation_mask_at_azimuth_deg(100.0), 0.0);
+
+        // Slewing 30 degrees in azimuth across the north in one minute is fine, but not in ten seconds.
+        let from = aer(epoch, 345.0, 20.0);
+        assert!(loc.can_slew(&from, &aer(epoch + 1.minutes(), 15.0, 25.0)));
+        assert!(!loc.can_slew(&from, &aer(epoch + 10.seconds(), 15.0, 25.0)));
+
+        assert!(loc.can_receive(FrequencyBand::Ka));
+        assert!(!loc.can_transmit(FrequencyBand::Ka));
+    }
+
+    #[cfg(feature = "metaload")]
+    #[test]
+    fn location_dhall_round_trip() {
+        use super::{LocationDhallSet, LocationDhallSetEntry};
+
+        let set = LocationDhallSet {
+            data: vec![
+                LocationDhallSetEntry {
+                    id: Some(1),
+                    alias: Some("DSS-65".to_string()),
+                    value: madrid(),
+                },
+                LocationDhallSetEntry {
+                    id: None,
+                    alias: Some("Default".to_string()),
+                    value: Location::default(),
+                },
+            ],
+        };
+
+        let repr = set.to_dhall().unwrap();
+        assert_eq!(LocationDhallSet::from_dhall(&repr).unwrap(), set);
+
+        let dataset = set.to_dataset().unwrap();
+        assert_eq!(dataset.get_by_name("DSS-65").unwrap(), madrid());
+        assert_eq!(dataset.get_by_id(1).unwrap(), madrid());
+        assert_eq!(LocationDhallSet::from_dataset(&dataset).unwrap(), set);
+    }
+}
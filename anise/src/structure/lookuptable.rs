@@ -7,11 +7,9 @@
  *
  * Documentation: https://nyxspace.com/
  */
-use der::{
-    asn1::{OctetStringRef, SequenceOf},
-    Decode, Encode, Reader, Writer,
-};
-use heapless::{FnvIndexMap, String};
+use der::{asn1::OctetStringRef, Decode, Encode, Reader, Writer};
+use heapless::String;
+use indexmap::IndexMap;
 use log::warn;
 use snafu::prelude::*;
 
@@ -23,14 +21,6 @@ pub const KEY_NAME_LEN: usize = 32;
 #[derive(Debug, Snafu, PartialEq)]
 #[snafu(visibility(pub(crate)))]
 pub enum LutError {
-    #[snafu(display(
-        "ID LUT is full with all {max_slots} taken (increase ENTRIES at build time)"
-    ))]
-    IdLutFull { max_slots: usize },
-    #[snafu(display(
-        "Names LUT is full with all {max_slots} taken (increase ENTRIES at build time)"
-    ))]
-    NameLutFull { max_slots: usize },
     #[snafu(display("must provide either an ID or a name for a loop up, but provided neither"))]
     NoKeyProvided,
     #[snafu(display("ID {id} not in look up table"))]
@@ -43,54 +33,61 @@ pub enum LutError {
 
 /// A LookUpTable allows finding the [u32] ("NaifId") associated with either an ID or a name.
 ///
+/// The look up table grows as needed: `ENTRIES` is only the number of entries for which memory is reserved upon creation.
+/// The DER encoding does not depend on `ENTRIES`, so a table can be decoded with a different `ENTRIES` than it was encoded with.
+///
+/// # Breaking change
+/// Up to ANISE 0.5.2, `by_id`, `by_name`, and the return of `entries` were fixed capacity `heapless::FnvIndexMap` and appending to a full
+/// table failed with `LutError::IdLutFull` or `LutError::NameLutFull`. They are now `indexmap::IndexMap` and these errors were removed.
+///
 /// # Note
 /// _Both_ the IDs and the name MUST be unique in the look up table.
-#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct LookUpTable<const ENTRIES: usize> {
     /// Unique IDs of each item in the LUT
-    pub by_id: FnvIndexMap<NaifId, u32, ENTRIES>,
+    pub by_id: IndexMap<NaifId, u32>,
     /// Corresponding index for each hash
-    pub by_name: FnvIndexMap<String<32>, u32, ENTRIES>,
+    pub by_name: IndexMap<String<KEY_NAME_LEN>, u32>,
+}
+
+impl<const ENTRIES: usize> Default for LookUpTable<ENTRIES> {
+    fn default() -> Self {
+        Self {
+            by_id: IndexMap::with_capacity(ENTRIES),
+            by_name: IndexMap::with_capacity(ENTRIES),
+        }
+    }
 }
 
 impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     pub fn append(&mut self, id: i32, name: &str, index: u32) -> Result<(), LutError> {
-        self.by_id
-            .insert(id, index)
-            .map_err(|_| LutError::IdLutFull { max_slots: ENTRIES })?;
-        self.by_name
-            .insert(name.try_into().unwrap(), index)
-            .map_err(|_| LutError::NameLutFull { max_slots: ENTRIES })?;
+        self.by_id.insert(id, index);
+        self.by_name.insert(name.try_into().unwrap(), index);
         Ok(())
     }
 
     pub fn append_id(&mut self, id: i32, index: u32) -> Result<(), LutError> {
-        self.by_id
-            .insert(id, index)
-            .map_err(|_| LutError::IdLutFull { max_slots: ENTRIES })?;
+        self.by_id.insert(id, index);
         Ok(())
     }
 
     pub fn append_name(&mut self, name: &str, index: u32) -> Result<(), LutError> {
-        self.by_name
-            .insert(name.try_into().unwrap(), index)
-            .map_err(|_| LutError::NameLutFull { max_slots: ENTRIES })?;
+        self.by_name.insert(name.try_into().unwrap(), index);
         Ok(())
     }
 
     /// Returns the list of entries of this LUT
-    pub fn entries(&self) -> FnvIndexMap<u32, (Option<NaifId>, Option<String<32>>), ENTRIES> {
-        let mut rtn = FnvIndexMap::default();
+    pub fn entries(&self) -> IndexMap<u32, (Option<NaifId>, Option<String<KEY_NAME_LEN>>)> {
+        let mut rtn = IndexMap::with_capacity(self.len());
 
         for (id, entry) in &self.by_id {
-            // IDs are unique, and this is the first iteration, so we can't be overwriting anything
-            rtn.insert(*entry, (Some(*id), None)).unwrap();
+            rtn.insert(*entry, (Some(*id), None));
         }
 
         // Now map to the names
         for (name, entry) in &self.by_name {
             if !rtn.contains_key(entry) {
-                rtn.insert(*entry, (None, Some(name.clone()))).unwrap();
+                rtn.insert(*entry, (None, Some(name.clone())));
             } else {
                 let val = rtn.get_mut(entry).unwrap();
                 val.1 = Some(name.clone());
@@ -105,8 +102,7 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     /// This will return an error if the current ID is not in the LUT, or if the new ID is already in the LUT.
     pub fn reid(&mut self, current_id: i32, new_id: i32) -> Result<(), LutError> {
         if let Some(entry) = self.by_id.swap_remove(&current_id) {
-            // We can unwrap the insertion because we just removed something.
-            self.by_id.insert(new_id, entry).unwrap();
+            self.by_id.insert(new_id, entry);
             Ok(())
         } else {
             Err(LutError::UnknownId { id: current_id })
@@ -117,7 +113,7 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     ///
     /// If this item was inserted with a name, it will rename accessible by the name.
     pub fn rmid(&mut self, id: i32) -> Result<(), LutError> {
-        if self.by_id.remove(&id).is_none() {
+        if self.by_id.swap_remove(&id).is_none() {
             Err(LutError::UnknownId { id })
         } else {
             Ok(())
@@ -128,11 +124,9 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     ///
     /// This will return an error if the current ID is not in the LUT, or if the new ID is already in the LUT.
     pub fn rename(&mut self, current_name: &str, new_name: &str) -> Result<(), LutError> {
-        if let Some(entry) = self.by_name.swap_remove(&current_name.try_into().unwrap()) {
-            // We can unwrap the insertion because we just removed something.
-            self.by_name
-                .insert(new_name.try_into().unwrap(), entry)
-                .unwrap();
+        let current_key: String<KEY_NAME_LEN> = current_name.try_into().unwrap();
+        if let Some(entry) = self.by_name.swap_remove(&current_key) {
+            self.by_name.insert(new_name.try_into().unwrap(), entry);
             Ok(())
         } else {
             Err(LutError::UnknownName {
@@ -145,7 +139,8 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     ///
     /// If this item was inserted with a name, it will rename accessible by the name.
     pub fn rmname(&mut self, name: &str) -> Result<(), LutError> {
-        if self.by_name.remove(&name.try_into().unwrap()).is_none() {
+        let key: String<KEY_NAME_LEN> = name.try_into().unwrap();
+        if self.by_name.swap_remove(&key).is_none() {
             Err(LutError::UnknownName {
                 name: name.try_into().unwrap(),
             })
@@ -188,32 +183,22 @@ impl<const ENTRIES: usize> LookUpTable<ENTRIES> {
     ///
     /// # Note
     /// The list of entries might be duplicated if all items have both a name and an ID.
-    fn der_encoding(
-        &self,
-    ) -> (
-        SequenceOf<i32, ENTRIES>,
-        SequenceOf<u32, ENTRIES>,
-        SequenceOf<OctetStringRef, ENTRIES>,
-        SequenceOf<u32, ENTRIES>,
-    ) {
+    fn der_encoding(&self) -> (Vec<i32>, Vec<u32>, Vec<OctetStringRef<'_>>, Vec<u32>) {
         // Build the list of entries
-        let mut id_entries = SequenceOf::<u32, ENTRIES>::new();
-        let mut name_entries = SequenceOf::<u32, ENTRIES>::new();
+        let mut id_entries = Vec::with_capacity(self.by_id.len());
+        let mut name_entries = Vec::with_capacity(self.by_name.len());
 
         // Build the list of keys
-        let mut ids = SequenceOf::<i32, ENTRIES>::new();
+        let mut ids = Vec::with_capacity(self.by_id.len());
         for (id, index) in &self.by_id {
-            ids.add(*id).unwrap();
-            id_entries.add(*index).unwrap();
+            ids.push(*id);
+            id_entries.push(*index);
         }
         // Build the list of names
-        let mut names = SequenceOf::<OctetStringRef, ENTRIES>::new();
+        let mut names = Vec::with_capacity(self.by_name.len());
         for (name, index) in &self.by_name {
-            names
-                .add(OctetStringRef::new(name.as_bytes()).unwrap())
-                .unwrap();
-
-            name_entries.add(*index).unwrap();
+            names.push(OctetStringRef::new(name.as_bytes()).unwrap());
+            name_entries.push(*index);
         }
 
         (ids, id_entries, names, name_entries)
@@ -242,23 +227,22 @@ impl<'a, const ENTRIES: usize> Decode<'a> for LookUpTable<ENTRIES> {
     fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
         // Decode as sequences and use that to build the look up table.
         let mut lut = Self::default();
-        let ids: SequenceOf<i32, ENTRIES> = decoder.decode()?;
-        let id_entries: SequenceOf<u32, ENTRIES> = decoder.decode()?;
-        let names: SequenceOf<OctetStringRef, ENTRIES> = decoder.decode()?;
-        let name_entries: SequenceOf<u32, ENTRIES> = decoder.decode()?;
+        // Files written when the table had a fixed capacity use the same encoding.
+        let ids: Vec<i32> = decoder.decode()?;
+        let id_entries: Vec<u32> = decoder.decode()?;
+        let names: Vec<OctetStringRef<'a>> = decoder.decode()?;
+        let name_entries: Vec<u32> = decoder.decode()?;
 
         for (id, index) in ids.iter().zip(id_entries.iter()) {
-            lut.by_id.insert(*id, *index).unwrap();
+            lut.by_id.insert(*id, *index);
         }
 
         for (name, entry) in names.iter().zip(name_entries.iter()) {
             let key = core::str::from_utf8(name.as_bytes()).unwrap();
-            lut.by_name
-                .insert(
-                    key[..KEY_NAME_LEN.min(key.len())].try_into().unwrap(),
-                    *entry,
-                )
-                .unwrap();
+            lut.by_name.insert(
+                key[..KEY_NAME_LEN.min(key.len())].try_into().unwrap(),
+                *entry,
+            );
         }
 
         if !lut.check_integrity() {
@@ -274,7 +258,7 @@ impl<'a, const ENTRIES: usize> Decode<'a> for LookUpTable<ENTRIES> {
 
 #[cfg(test)]
 mod lut_ut {
-    use super::{Decode, Encode, LookUpTable};
+    use super::{Decode, Encode, LookUpTable, String, KEY_NAME_LEN};
     #[test]
     fn zero_repr() {
         let repr = LookUpTable::<2>::default();
@@ -287,7 +271,39 @@ mod lut_ut {
         assert_eq!(repr, repr_dec);
 
         dbg!(repr);
-        assert_eq!(core::mem::size_of::<LookUpTable<64>>(), 4368);
+        // The size of the table no longer depends on the number of entries
+        assert_eq!(
+            core::mem::size_of::<LookUpTable<64>>(),
+            core::mem::size_of::<LookUpTable<2>>()
+        );
+    }
+
+    #[test]
+    fn repr_beyond_entries() {
+        // Large catalogs, e.g. thousands of ground stations, are not limited by the number of entries.
+        let mut names = Vec::new();
+        let mut repr = LookUpTable::<8>::default();
+        for i in 0..5000 {
+            names.push(format!("Station {i}"));
+        }
+        for (i, name) in names.iter().enumerate() {
+            repr.append(i as i32 + 1, name, i as u32).unwrap();
+        }
+        assert_eq!(repr.len(), 5000);
+        assert_eq!(repr.entries().len(), 5000);
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = LookUpTable::<8>::from_der(&buf).unwrap();
+        assert_eq!(repr, repr_dec);
+        let key = String::<KEY_NAME_LEN>::try_from("Station 4999").unwrap();
+        assert_eq!(repr_dec.by_name.get(&key), Some(&4999));
+
+        // The encoding does not depend on the number of entries
+        let repr_dec = LookUpTable::<128>::from_der(&buf).unwrap();
+        assert_eq!(repr.by_id, repr_dec.by_id);
+        assert_eq!(repr.by_name, repr_dec.by_name);
     }
 
     #[test]
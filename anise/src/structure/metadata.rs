@@ -11,13 +11,38 @@ use crate::errors::DecodingError;
 use bytes::Bytes;
 use core::fmt;
 use core::str::FromStr;
-use der::{asn1::Utf8StringRef, Decode, Encode, Reader, Writer};
+use der::{
+    asn1::{ContextSpecific, ContextSpecificRef, Utf8StringRef},
+    Decode, Encode, Reader, Sequence, TagMode, TagNumber, Writer,
+};
 use heapless::String;
 use hifitime::Epoch;
+use std::collections::BTreeMap;
 
 /// Default maximum length of the Metadata originator length string
 pub const MAX_ORIGINATOR_LEN: usize = 32;
 
+/// Context specific tag of the edit log, which is only encoded if the log isn't empty, so files without edits are unchanged.
+const EDIT_LOG_TAG: TagNumber = TagNumber::N0;
+/// Context specific tags of the optional provenance fields, which are also only encoded if set.
+const TOOL_VERSION_TAG: TagNumber = TagNumber::N1;
+const SOURCES_TAG: TagNumber = TagNumber::N2;
+const CUSTOM_TAG: TagNumber = TagNumber::N3;
+
+/// A file from which a data set was generated, e.g. the TPC used to build a PCA, identified by its name and the CRC32 of its contents.
+#[derive(Clone, Debug, PartialEq, Eq, Sequence)]
+pub struct SourceFile {
+    pub name: std::string::String,
+    pub crc32: u32,
+}
+
+/// DER representation of an entry of the custom key-value section of the metadata.
+#[derive(Clone, Debug, PartialEq, Eq, Sequence)]
+struct KeyValue {
+    key: std::string::String,
+    value: std::string::String,
+}
+
 use super::{dataset::DataSetType, semver::Semver, ANISE_VERSION};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -30,6 +55,14 @@ pub struct Metadata {
     pub creation_date: Epoch,
     /// Originator of the file, either an organization, a person, a tool, or a combination thereof
     pub originator: String<MAX_ORIGINATOR_LEN>,
+    /// Log of the edits made to the data after its creation, each prefixed with the date time of the edit.
+    pub edit_log: Vec<std::string::String>,
+    /// Name and version of the tool which generated the file, e.g. `anise 0.5.2`
+    pub tool_version: Option<std::string::String>,
+    /// Files from which the data was generated
+    pub sources: Vec<SourceFile>,
+    /// Small free-form key-value section, e.g. for the project or configuration item of this file
+    pub custom: BTreeMap<std::string::String, std::string::String>,
 }
 
 impl Metadata {
@@ -61,6 +94,79 @@ impl Metadata {
     pub fn from_bytes(buf: Bytes) -> Self {
         Self::from_der(&buf).unwrap()
     }
+
+    /// Appends the description of an edit to the edit log, prefixed with the current date time if available.
+    pub fn record_edit<D: fmt::Display>(&mut self, description: D) {
+        let entry = match Epoch::now() {
+            Ok(now) => format!("{now}: {description}"),
+            Err(_) => format!("{description}"),
+        };
+        self.edit_log.push(entry);
+    }
+
+    /// Sets the name and version of the tool which generated the file.
+    pub fn with_tool_version<S: Into<std::string::String>>(mut self, tool_version: S) -> Self {
+        self.tool_version = Some(tool_version.into());
+        self
+    }
+
+    /// Adds a source file, storing the CRC32 of its contents.
+    pub fn add_source<S: Into<std::string::String>>(&mut self, name: S, contents: &[u8]) {
+        self.sources.push(SourceFile {
+            name: name.into(),
+            crc32: crc32fast::hash(contents),
+        });
+    }
+
+    /// Sets the value of this key of the custom key-value section, returning the previous value if any.
+    pub fn set_custom<K: Into<std::string::String>, V: Into<std::string::String>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Option<std::string::String> {
+        self.custom.insert(key.into(), value.into())
+    }
+
+    fn edit_log_field(&self) -> Option<ContextSpecificRef<'_, Vec<std::string::String>>> {
+        (!self.edit_log.is_empty()).then_some(ContextSpecificRef {
+            tag_number: EDIT_LOG_TAG,
+            tag_mode: TagMode::Explicit,
+            value: &self.edit_log,
+        })
+    }
+
+    fn tool_version_field(&self) -> Option<ContextSpecificRef<'_, std::string::String>> {
+        self.tool_version
+            .as_ref()
+            .map(|tool_version| ContextSpecificRef {
+                tag_number: TOOL_VERSION_TAG,
+                tag_mode: TagMode::Explicit,
+                value: tool_version,
+            })
+    }
+
+    fn sources_field(&self) -> Option<ContextSpecificRef<'_, Vec<SourceFile>>> {
+        (!self.sources.is_empty()).then_some(ContextSpecificRef {
+            tag_number: SOURCES_TAG,
+            tag_mode: TagMode::Explicit,
+            value: &self.sources,
+        })
+    }
+
+    fn custom_field(&self) -> Option<ContextSpecific<Vec<KeyValue>>> {
+        (!self.custom.is_empty()).then(|| ContextSpecific {
+            tag_number: CUSTOM_TAG,
+            tag_mode: TagMode::Explicit,
+            value: self
+                .custom
+                .iter()
+                .map(|(key, value)| KeyValue {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+        })
+    }
 }
 
 impl Default for Metadata {
@@ -70,6 +176,10 @@ impl Default for Metadata {
             dataset_type: DataSetType::NotApplicable,
             creation_date: Epoch::now().unwrap(),
             originator: Default::default(),
+            edit_log: Vec::new(),
+            tool_version: None,
+            sources: Vec::new(),
+            custom: BTreeMap::new(),
         }
     }
 }
@@ -80,13 +190,21 @@ impl Encode for Metadata {
             + self.dataset_type.encoded_len()?
             + Utf8StringRef::new(&format!("{}", self.creation_date))?.encoded_len()?
             + Utf8StringRef::new(&self.originator)?.encoded_len()?
+            + self.edit_log_field().encoded_len()?
+            + self.tool_version_field().encoded_len()?
+            + self.sources_field().encoded_len()?
+            + self.custom_field().encoded_len()?
     }
 
     fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
         self.anise_version.encode(encoder)?;
         self.dataset_type.encode(encoder)?;
         Utf8StringRef::new(&format!("{}", self.creation_date))?.encode(encoder)?;
-        Utf8StringRef::new(&self.originator)?.encode(encoder)
+        Utf8StringRef::new(&self.originator)?.encode(encoder)?;
+        self.edit_log_field().encode(encoder)?;
+        self.tool_version_field().encode(encoder)?;
+        self.sources_field().encode(encoder)?;
+        self.custom_field().encode(encoder)
     }
 }
 
@@ -100,11 +218,35 @@ impl<'a> Decode<'a> for Metadata {
         let originator = orig_str[..MAX_ORIGINATOR_LEN.min(orig_str.len())]
             .try_into()
             .unwrap();
+        // Files written before the edit log was introduced do not include it.
+        let edit_log =
+            ContextSpecific::<Vec<std::string::String>>::decode_explicit(decoder, EDIT_LOG_TAG)?
+                .map(|field| field.value)
+                .unwrap_or_default();
+        let tool_version =
+            ContextSpecific::<std::string::String>::decode_explicit(decoder, TOOL_VERSION_TAG)?
+                .map(|field| field.value);
+        let sources = ContextSpecific::<Vec<SourceFile>>::decode_explicit(decoder, SOURCES_TAG)?
+            .map(|field| field.value)
+            .unwrap_or_default();
+        let custom = ContextSpecific::<Vec<KeyValue>>::decode_explicit(decoder, CUSTOM_TAG)?
+            .map(|field| {
+                field
+                    .value
+                    .into_iter()
+                    .map(|entry| (entry.key, entry.value))
+                    .collect()
+            })
+            .unwrap_or_default();
         Ok(Self {
             anise_version,
             dataset_type,
             creation_date,
             originator,
+            edit_log,
+            tool_version,
+            sources,
+            custom,
         })
     }
 }
@@ -121,7 +263,20 @@ impl fmt::Display for Metadata {
                 &self.originator
             }
         )?;
-        writeln!(f, "Creation date: {}", self.creation_date)
+        writeln!(f, "Creation date: {}", self.creation_date)?;
+        if let Some(tool_version) = &self.tool_version {
+            writeln!(f, "Tool: {tool_version}")?;
+        }
+        for source in &self.sources {
+            writeln!(f, "Source: {} (CRC32 {:08x})", source.name, source.crc32)?;
+        }
+        for (key, value) in &self.custom {
+            writeln!(f, "{key}: {value}")?;
+        }
+        for entry in &self.edit_log {
+            writeln!(f, "Edit: {entry}")?;
+        }
+        Ok(())
     }
 }
 
@@ -189,4 +344,66 @@ Creation date: {}
 
         assert_eq!(repr, repr_dec);
     }
+
+    #[test]
+    fn meta_with_edit_log() {
+        let mut repr = Metadata::default();
+
+        let mut unedited = vec![];
+        repr.encode_to_vec(&mut unedited).unwrap();
+
+        repr.record_edit("set the GM of 301");
+        repr.record_edit("set the radii of 301");
+        assert_eq!(repr.edit_log.len(), 2);
+        assert!(repr.edit_log[0].ends_with(": set the GM of 301"));
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        // The edit log is appended after the fields of an unedited metadata
+        assert_eq!(&buf[..unedited.len()], &unedited[..]);
+
+        let repr_dec = Metadata::from_der(&buf).unwrap();
+        assert_eq!(repr, repr_dec);
+        assert!(format!("{repr_dec}").contains("Edit: "));
+
+        // Metadata without an edit log still decodes
+        assert!(Metadata::from_der(&unedited).unwrap().edit_log.is_empty());
+    }
+
+    #[test]
+    fn meta_with_provenance() {
+        let mut repr = Metadata::default().with_tool_version("anise-cli 0.5.2");
+        repr.add_source("pck00008.tpc", b"KPL/PCK");
+        repr.add_source("gm_de431.tpc", b"KPL/PCK");
+        assert_eq!(repr.set_custom("project", "Lunar relay"), None);
+        assert_eq!(
+            repr.set_custom("project", "Lunar relay phase B"),
+            Some("Lunar relay".to_string())
+        );
+        repr.set_custom("config item", "CI-042");
+        repr.record_edit("set the GM of 301");
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = Metadata::from_der(&buf).unwrap();
+        assert_eq!(repr, repr_dec);
+        assert_eq!(repr_dec.sources[0].crc32, crc32fast::hash(b"KPL/PCK"));
+        assert_eq!(repr_dec.custom["project"], "Lunar relay phase B");
+
+        let repr_str = format!("{repr_dec}");
+        println!("{repr_str}");
+        assert!(repr_str.contains("Tool: anise-cli 0.5.2"));
+        assert!(repr_str.contains("Source: gm_de431.tpc"));
+        assert!(repr_str.contains("config item: CI-042"));
+
+        // Only some of the optional fields
+        let repr = Metadata {
+            sources: repr.sources.clone(),
+            ..Default::default()
+        };
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+        assert_eq!(Metadata::from_der(&buf).unwrap(), repr);
+    }
 }
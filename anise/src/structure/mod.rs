@@ -13,6 +13,7 @@
  * All other computations are at a higher level module.
  */
 pub mod dataset;
+pub mod location;
 pub mod lookuptable;
 pub mod metadata;
 pub mod planetocentric;
@@ -20,10 +21,11 @@ pub mod semver;
 pub mod spacecraft;
 
 use self::{
-    dataset::DataSet, planetocentric::PlanetaryData, semver::Semver, spacecraft::SpacecraftData,
+    dataset::DataSet, location::Location, planetocentric::PlanetaryData, semver::Semver,
+    spacecraft::SpacecraftData,
 };
 use crate::{
-    almanac::{MAX_PLANETARY_DATA, MAX_SPACECRAFT_DATA},
+    almanac::{MAX_LOCATION_DATA, MAX_PLANETARY_DATA, MAX_SPACECRAFT_DATA},
     math::rotation::Quaternion,
 };
 
@@ -40,3 +42,5 @@ pub type SpacecraftDataSet = DataSet<SpacecraftData, MAX_SPACECRAFT_DATA>;
 pub type PlanetaryDataSet = DataSet<PlanetaryData, MAX_PLANETARY_DATA>;
 /// Euler Parameter Data Set allow mapping an ID and/or name to a time invariant Quaternion
 pub type EulerParameterDataSet = DataSet<Quaternion, MAX_PLANETARY_DATA>;
+/// Location Data Set allow mapping an ID and/or name to a ground location, optionally including its terrain mask and antenna constraints
+pub type LocationDataSet = DataSet<Location, MAX_LOCATION_DATA>;
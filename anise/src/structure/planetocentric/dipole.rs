@@ -0,0 +1,155 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use core::fmt;
+use der::{Decode, Encode, Reader, Writer};
+
+use crate::math::Vector3;
+
+/// Centered tilted dipole model of the magnetic field of a planetary body, in its body fixed frame.
+///
+/// The NAIF PCKs only provide the location of the north geomagnetic pole (`N_GEOMAG_CTR_DIPOLE_LAT` and
+/// `N_GEOMAG_CTR_DIPOLE_LON`), so the strength of the dipole is stored alongside it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GeomagneticDipole {
+    /// Planetocentric latitude of the north geomagnetic pole, where the dipole axis crosses the northern hemisphere, in degrees
+    pub north_pole_lat_deg: f64,
+    /// Planetocentric longitude of the north geomagnetic pole, in degrees
+    pub north_pole_lon_deg: f64,
+    /// Reference radius of the field model, in km
+    pub reference_radius_km: f64,
+    /// Field strength on the geomagnetic equator at the reference radius, in nT, negative if the dipole moment points toward
+    /// the north geomagnetic pole (e.g. for Jupiter)
+    pub equatorial_field_nt: f64,
+}
+
+impl GeomagneticDipole {
+    /// Reference radius of the International Geomagnetic Reference Field, in km.
+    pub const IGRF_REFERENCE_RADIUS_KM: f64 = 6371.2;
+    /// Strength of the dipole terms of IGRF-13 at epoch 2020.0 on the geomagnetic equator at the reference radius, in nT.
+    pub const IGRF13_2020_EQUATORIAL_FIELD_NT: f64 = 29_806.0;
+
+    /// Returns the dipole of the Earth with the provided north geomagnetic pole, and the IGRF-13 strength at epoch 2020.0.
+    pub fn earth(north_pole_lat_deg: f64, north_pole_lon_deg: f64) -> Self {
+        Self {
+            north_pole_lat_deg,
+            north_pole_lon_deg,
+            reference_radius_km: Self::IGRF_REFERENCE_RADIUS_KM,
+            equatorial_field_nt: Self::IGRF13_2020_EQUATORIAL_FIELD_NT,
+        }
+    }
+
+    /// Returns the unit vector toward the north geomagnetic pole, in the body fixed frame.
+    pub fn north_pole_unit(&self) -> Vector3 {
+        let (sin_lat, cos_lat) = self.north_pole_lat_deg.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.north_pole_lon_deg.to_radians().sin_cos();
+        Vector3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat)
+    }
+
+    /// Returns the magnetic field at the provided position in the body fixed frame, in nT, or a zero vector at the center of the body.
+    ///
+    /// With a positive strength, the dipole moment points toward the south geomagnetic pole (as for the Earth), so the field
+    /// points north on the geomagnetic equator and downward at the north geomagnetic pole, where its strength is twice the equatorial one.
+    pub fn field_nt(&self, radius_km: &Vector3) -> Vector3 {
+        let r_km = radius_km.norm();
+        if r_km < f64::EPSILON {
+            return Vector3::zeros();
+        }
+        let r_hat = radius_km / r_km;
+        let moment_hat = -self.north_pole_unit();
+
+        self.equatorial_field_nt
+            * (self.reference_radius_km / r_km).powi(3)
+            * (3.0 * moment_hat.dot(&r_hat) * r_hat - moment_hat)
+    }
+}
+
+impl fmt::Display for GeomagneticDipole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dipole north pole = ({} deg, {} deg), B0 = {} nT at {} km",
+            self.north_pole_lat_deg,
+            self.north_pole_lon_deg,
+            self.equatorial_field_nt,
+            self.reference_radius_km
+        )
+    }
+}
+
+impl Encode for GeomagneticDipole {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        self.north_pole_lat_deg.encoded_len()?
+            + self.north_pole_lon_deg.encoded_len()?
+            + self.reference_radius_km.encoded_len()?
+            + self.equatorial_field_nt.encoded_len()?
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        self.north_pole_lat_deg.encode(encoder)?;
+        self.north_pole_lon_deg.encode(encoder)?;
+        self.reference_radius_km.encode(encoder)?;
+        self.equatorial_field_nt.encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for GeomagneticDipole {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        Ok(Self {
+            north_pole_lat_deg: decoder.decode()?,
+            north_pole_lon_deg: decoder.decode()?,
+            reference_radius_km: decoder.decode()?,
+            equatorial_field_nt: decoder.decode()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod ut_dipole {
+    use super::{GeomagneticDipole, Vector3};
+
+    #[test]
+    fn aligned_dipole() {
+        let dipole = GeomagneticDipole {
+            north_pole_lat_deg: 90.0,
+            north_pole_lon_deg: 0.0,
+            reference_radius_km: 6371.2,
+            equatorial_field_nt: 30_000.0,
+        };
+
+        // Northward on the equator, decreasing with the cube of the distance
+        let b = dipole.field_nt(&Vector3::new(6371.2, 0.0, 0.0));
+        assert!((b - Vector3::new(0.0, 0.0, 30_000.0)).norm() < 1e-9);
+        let b = dipole.field_nt(&Vector3::new(0.0, 2.0 * 6371.2, 0.0));
+        assert!((b - Vector3::new(0.0, 0.0, 3_750.0)).norm() < 1e-9);
+
+        // Downward and twice as strong at the north pole, upward at the south pole
+        let b = dipole.field_nt(&Vector3::new(0.0, 0.0, 6371.2));
+        assert!((b - Vector3::new(0.0, 0.0, -60_000.0)).norm() < 1e-9);
+        let b = dipole.field_nt(&Vector3::new(0.0, 0.0, -6371.2));
+        assert!((b - Vector3::new(0.0, 0.0, -60_000.0)).norm() < 1e-9);
+
+        assert_eq!(dipole.field_nt(&Vector3::zeros()), Vector3::zeros());
+    }
+
+    #[test]
+    fn tilted_dipole() {
+        let dipole = GeomagneticDipole::earth(79.54, 288.43);
+        let pole = dipole.north_pole_unit();
+        assert!((pole.norm() - 1.0).abs() < 1e-12);
+        assert!(pole.z > 0.97 && pole.x > 0.0 && pole.y < 0.0);
+
+        // Along the dipole axis, the field is antiparallel to the pole direction
+        let b = dipole.field_nt(&(pole * 7000.0));
+        assert!((b.normalize() + pole).norm() < 1e-12);
+        let expected = 2.0 * dipole.equatorial_field_nt * (6371.2_f64 / 7000.0).powi(3);
+        assert!((b.norm() - expected).abs() < 1e-9);
+    }
+}
@@ -12,6 +12,9 @@ use core::fmt;
 use der::{Decode, Encode, Reader, Writer};
 use serde_derive::{Deserialize, Serialize};
 
+use crate::errors::MathError;
+use crate::math::Vector3;
+
 #[cfg(feature = "metaload")]
 use serde_dhall::StaticType;
 
@@ -65,6 +68,322 @@ impl Ellipsoid {
             polar_radius_km,
         }
     }
+
+    /// Returns the outward unit normal of the ellipsoid at the provided point, expressed in the frame of the ellipsoid.
+    /// The point is assumed to be on the surface of the ellipsoid.
+    pub fn surface_normal(&self, point_km: &Vector3) -> Vector3 {
+        Vector3::new(
+            point_km.x / self.semi_major_equatorial_radius_km.powi(2),
+            point_km.y / self.semi_minor_equatorial_radius_km.powi(2),
+            point_km.z / self.polar_radius_km.powi(2),
+        )
+        .normalize()
+    }
+
+    /// Returns the first intersection of the ray from the origin along the direction with the ellipsoid, both in the frame of the ellipsoid,
+    /// or None if the ray does not intersect the ellipsoid or if the origin is inside of it.
+    pub fn ray_intersection(&self, origin_km: &Vector3, direction: &Vector3) -> Option<Vector3> {
+        let radii = Vector3::new(
+            self.semi_major_equatorial_radius_km,
+            self.semi_minor_equatorial_radius_km,
+            self.polar_radius_km,
+        );
+        // Scale the problem such that the ellipsoid is the unit sphere
+        let p = origin_km.component_div(&radii);
+        let d = direction.component_div(&radii);
+
+        let a = d.dot(&d);
+        let b = 2.0 * p.dot(&d);
+        let c = p.dot(&p) - 1.0;
+        let discriminant = b * b - 4.0 * a * c;
+        if c < 0.0 || a == 0.0 || discriminant < 0.0 {
+            return None;
+        }
+
+        let t = (-b - discriminant.sqrt()) / (2.0 * a);
+        if t < 0.0 {
+            None
+        } else {
+            Some(origin_km + direction * t)
+        }
+    }
+
+    /// Returns the point on the surface of the ellipsoid nearest to the provided point, both in the frame of the ellipsoid.
+    ///
+    /// The point may be inside or outside of the ellipsoid. This uses the robust bisection method of D. Eberly,
+    /// "Distance from a Point to an Ellipse, an Ellipsoid, or a Hyperellipsoid" (2013).
+    pub fn nearest_point(&self, point_km: &Vector3) -> Vector3 {
+        let radii = [
+            self.semi_major_equatorial_radius_km,
+            self.semi_minor_equatorial_radius_km,
+            self.polar_radius_km,
+        ];
+        // Solve in the first octant with the radii sorted in decreasing order, then restore the axes and signs
+        let mut axes = [0, 1, 2];
+        axes.sort_by(|i, j| radii[*j].total_cmp(&radii[*i]));
+        let e = axes.map(|i| radii[i]);
+        let y = axes.map(|i| point_km[i].abs());
+
+        let x = nearest_point_octant(e, y);
+
+        let mut nearest = Vector3::zeros();
+        for (k, i) in axes.iter().enumerate() {
+            nearest[*i] = x[k].copysign(point_km[*i]);
+        }
+        nearest
+    }
+
+    /// Returns the altitude of the provided point above the ellipsoid, i.e. its distance to the nearest point on the surface,
+    /// which is negative if the point is inside of the ellipsoid.
+    pub fn altitude_km(&self, point_km: &Vector3) -> f64 {
+        let distance_km = (point_km - self.nearest_point(point_km)).norm();
+        let scaled = point_km.component_div(&Vector3::new(
+            self.semi_major_equatorial_radius_km,
+            self.semi_minor_equatorial_radius_km,
+            self.polar_radius_km,
+        ));
+        if scaled.norm_squared() < 1.0 {
+            -distance_km
+        } else {
+            distance_km
+        }
+    }
+
+    /// Returns the tangent point of the ray from the origin along the direction, i.e. the point of the ray with the lowest
+    /// altitude above the ellipsoid, and that altitude in kilometers, e.g. for limb sounding or stellar occultations.
+    ///
+    /// Returns None if the ray intersects the ellipsoid (including if its origin is inside of it), or if its direction is zero. If the ray moves away from the ellipsoid,
+    /// the tangent point is the origin of the ray.
+    pub fn tangent_point(
+        &self,
+        origin_km: &Vector3,
+        direction: &Vector3,
+    ) -> Option<(Vector3, f64)> {
+        if direction.norm() < f64::EPSILON
+            || self.altitude_km(origin_km) <= 0.0
+            || self.ray_intersection(origin_km, direction).is_some()
+        {
+            return None;
+        }
+        let direction = direction.normalize();
+
+        // The altitude along the ray is convex, and larger than at the origin beyond this bound
+        let mut lower = 0.0;
+        let mut upper = 2.0 * origin_km.norm()
+            + self
+                .semi_major_equatorial_radius_km
+                .max(self.polar_radius_km);
+        let altitude_at = |t: f64| self.altitude_km(&(origin_km + direction * t));
+
+        // Golden section search of the minimum altitude
+        let ratio = (5f64.sqrt() - 1.0) / 2.0;
+        let mut left = upper - ratio * (upper - lower);
+        let mut right = lower + ratio * (upper - lower);
+        let (mut alt_left, mut alt_right) = (altitude_at(left), altitude_at(right));
+        while upper - lower > 1e-9 * (1.0 + upper) {
+            if alt_left < alt_right {
+                upper = right;
+                right = left;
+                alt_right = alt_left;
+                left = upper - ratio * (upper - lower);
+                alt_left = altitude_at(left);
+            } else {
+                lower = left;
+                left = right;
+                alt_left = alt_right;
+                right = lower + ratio * (upper - lower);
+                alt_right = altitude_at(right);
+            }
+        }
+
+        let t = (lower + upper) / 2.0;
+        let tangent_km = origin_km + direction * t;
+        Some((tangent_km, self.altitude_km(&tangent_km)))
+    }
+
+    /// Returns the geodesic distance in kilometers between two points on the surface, from their geodetic latitude and
+    /// longitude in degrees, using the inverse formula of T. Vincenty (1975).
+    ///
+    /// Tri-axial ellipsoids are approximated by the spheroid of their mean equatorial radius.
+    ///
+    /// # Errors
+    /// If the formula does not converge, which may happen for nearly antipodal points.
+    pub fn geodesic_distance_km(
+        &self,
+        lat1_deg: f64,
+        long1_deg: f64,
+        lat2_deg: f64,
+        long2_deg: f64,
+    ) -> Result<f64, MathError> {
+        let a = self.mean_equatorial_radius_km();
+        let b = self.polar_radius_km;
+        let f = self.flattening();
+
+        let long_diff = (long2_deg - long1_deg).to_radians();
+        let (sin_u1, cos_u1) = ((1.0 - f) * lat1_deg.to_radians().tan()).atan().sin_cos();
+        let (sin_u2, cos_u2) = ((1.0 - f) * lat2_deg.to_radians().tan()).atan().sin_cos();
+
+        let mut lambda = long_diff;
+        for _ in 0..VINCENTY_MAX_ITER {
+            let (sin_lambda, cos_lambda) = lambda.sin_cos();
+            let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+                + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+            .sqrt();
+            if sin_sigma == 0.0 {
+                // Coincident points
+                return Ok(0.0);
+            }
+            let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+            let sigma = sin_sigma.atan2(cos_sigma);
+            let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+            let cos2_alpha = 1.0 - sin_alpha.powi(2);
+            // Equatorial lines have no midpoint term
+            let cos_2sigma_m = if cos2_alpha.abs() > f64::EPSILON {
+                cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+            } else {
+                0.0
+            };
+            let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+            let prev_lambda = lambda;
+            lambda = long_diff
+                + (1.0 - c)
+                    * f
+                    * sin_alpha
+                    * (sigma
+                        + c * sin_sigma
+                            * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+
+            if (lambda - prev_lambda).abs() < 1e-12 {
+                let u2 = cos2_alpha * (a * a - b * b) / (b * b);
+                let big_a =
+                    1.0 + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
+                let big_b = u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
+                let delta_sigma = big_b
+                    * sin_sigma
+                    * (cos_2sigma_m
+                        + big_b / 4.0
+                            * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                                - big_b / 6.0
+                                    * cos_2sigma_m
+                                    * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                    * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+                return Ok(b * big_a * (sigma - delta_sigma));
+            }
+        }
+
+        Err(MathError::MaxIterationsReached {
+            iter: VINCENTY_MAX_ITER,
+            action: "computing geodesic distance",
+        })
+    }
+}
+
+/// Maximum number of iterations of the inverse formula of Vincenty.
+const VINCENTY_MAX_ITER: usize = 200;
+
+/// Maximum number of bisections when searching for the nearest point, enough to reach the floating point precision.
+const NEAREST_POINT_MAX_ITER: usize = 1100;
+
+/// Returns the nearest point on the ellipsoid of radii `e` sorted in decreasing order to the point `y` in the first octant.
+fn nearest_point_octant(e: [f64; 3], y: [f64; 3]) -> [f64; 3] {
+    if y[2] > 0.0 {
+        if y[1] > 0.0 {
+            if y[0] > 0.0 {
+                let z = [y[0] / e[0], y[1] / e[1], y[2] / e[2]];
+                let g = z[0].powi(2) + z[1].powi(2) + z[2].powi(2) - 1.0;
+                if g == 0.0 {
+                    return y;
+                }
+                let r = [(e[0] / e[2]).powi(2), (e[1] / e[2]).powi(2)];
+                let s = bisect_root(&[r[0] * z[0], r[1] * z[1], z[2]], &[r[0], r[1], 1.0], g);
+                [
+                    r[0] * y[0] / (s + r[0]),
+                    r[1] * y[1] / (s + r[1]),
+                    y[2] / (s + 1.0),
+                ]
+            } else {
+                let [x1, x2] = nearest_point_quadrant([e[1], e[2]], [y[1], y[2]]);
+                [0.0, x1, x2]
+            }
+        } else if y[0] > 0.0 {
+            let [x0, x2] = nearest_point_quadrant([e[0], e[2]], [y[0], y[2]]);
+            [x0, 0.0, x2]
+        } else {
+            [0.0, 0.0, e[2]]
+        }
+    } else {
+        let denom = [e[0].powi(2) - e[2].powi(2), e[1].powi(2) - e[2].powi(2)];
+        let numer = [e[0] * y[0], e[1] * y[1]];
+        if numer[0] < denom[0] && numer[1] < denom[1] {
+            let xde = [numer[0] / denom[0], numer[1] / denom[1]];
+            let discr = 1.0 - xde[0].powi(2) - xde[1].powi(2);
+            if discr > 0.0 {
+                return [e[0] * xde[0], e[1] * xde[1], e[2] * discr.sqrt()];
+            }
+        }
+        let [x0, x1] = nearest_point_quadrant([e[0], e[1]], [y[0], y[1]]);
+        [x0, x1, 0.0]
+    }
+}
+
+/// Returns the nearest point on the ellipse of radii `e` sorted in decreasing order to the point `y` in the first quadrant.
+fn nearest_point_quadrant(e: [f64; 2], y: [f64; 2]) -> [f64; 2] {
+    if y[1] > 0.0 {
+        if y[0] > 0.0 {
+            let z = [y[0] / e[0], y[1] / e[1]];
+            let g = z[0].powi(2) + z[1].powi(2) - 1.0;
+            if g == 0.0 {
+                return y;
+            }
+            let r = (e[0] / e[1]).powi(2);
+            let s = bisect_root(&[r * z[0], z[1]], &[r, 1.0], g);
+            [r * y[0] / (s + r), y[1] / (s + 1.0)]
+        } else {
+            [0.0, e[1]]
+        }
+    } else {
+        let numer = e[0] * y[0];
+        let denom = e[0].powi(2) - e[1].powi(2);
+        if numer < denom {
+            let xde = numer / denom;
+            [e[0] * xde, e[1] * (1.0 - xde.powi(2)).sqrt()]
+        } else {
+            [e[0], 0.0]
+        }
+    }
+}
+
+/// Bisects the root `s` of `sum((n_i / (s + r_i))^2) - 1`, where the last ratio `r_i` is one, given the sign `g` of that
+/// function at zero.
+fn bisect_root(n: &[f64], r: &[f64], g: f64) -> f64 {
+    let z_last = n[n.len() - 1];
+    let mut s0 = z_last - 1.0;
+    let mut s1 = if g < 0.0 {
+        0.0
+    } else {
+        n.iter().map(|n_i| n_i * n_i).sum::<f64>().sqrt() - 1.0
+    };
+    let mut s = s0;
+    for _ in 0..NEAREST_POINT_MAX_ITER {
+        s = (s0 + s1) / 2.0;
+        if s == s0 || s == s1 {
+            break;
+        }
+        let g = n
+            .iter()
+            .zip(r)
+            .map(|(n_i, r_i)| (n_i / (s + r_i)).powi(2))
+            .sum::<f64>()
+            - 1.0;
+        if g > 0.0 {
+            s0 = s;
+        } else if g < 0.0 {
+            s1 = s;
+        } else {
+            break;
+        }
+    }
+    s
 }
 
 #[cfg_attr(feature = "python", pymethods)]
@@ -244,3 +563,112 @@ impl<'a> Decode<'a> for Ellipsoid {
         })
     }
 }
+
+#[cfg(test)]
+mod ut_ellipsoid {
+    use super::{Ellipsoid, Vector3};
+
+    #[test]
+    fn ray_intersection() {
+        let earth = Ellipsoid::from_spheroid(6378.0, 6357.0);
+        let origin = Vector3::new(7000.0, 0.0, 0.0);
+        let hit = earth.ray_intersection(&origin, &-Vector3::x()).unwrap();
+        assert!((hit - Vector3::new(6378.0, 0.0, 0.0)).norm() < 1e-9);
+        let origin = Vector3::new(0.0, 0.0, 7000.0);
+        let hit = earth.ray_intersection(&origin, &-Vector3::z()).unwrap();
+        assert!((hit.z - 6357.0).abs() < 1e-9);
+        // Pointing away, and origin inside
+        assert!(earth.ray_intersection(&origin, &Vector3::z()).is_none());
+        assert!(earth
+            .ray_intersection(&Vector3::zeros(), &Vector3::x())
+            .is_none());
+    }
+
+    #[test]
+    fn surface_normal() {
+        let earth = Ellipsoid::from_spheroid(6378.0, 6357.0);
+        let normal = earth.surface_normal(&Vector3::new(6378.0, 0.0, 0.0));
+        assert!((normal - Vector3::x()).norm() < 1e-12);
+        let normal = earth.surface_normal(&Vector3::new(0.0, 0.0, -6357.0));
+        assert!((normal + Vector3::z()).norm() < 1e-12);
+    }
+
+    #[test]
+    fn nearest_point() {
+        let earth = Ellipsoid::from_spheroid(6378.0, 6357.0);
+        let nearest = earth.nearest_point(&Vector3::new(7000.0, 0.0, 0.0));
+        assert!((nearest - Vector3::new(6378.0, 0.0, 0.0)).norm() < 1e-9);
+        let nearest = earth.nearest_point(&Vector3::new(0.0, 0.0, -7000.0));
+        assert!((nearest - Vector3::new(0.0, 0.0, -6357.0)).norm() < 1e-9);
+        assert!((earth.altitude_km(&Vector3::new(0.0, 0.0, -7000.0)) - 643.0).abs() < 1e-9);
+        assert!((earth.altitude_km(&Vector3::new(6000.0, 0.0, 0.0)) + 378.0).abs() < 1e-9);
+
+        // The nearest point is on the surface, along its normal, from inside or outside of a tri-axial ellipsoid
+        let triaxial = Ellipsoid {
+            semi_major_equatorial_radius_km: 3.0,
+            semi_minor_equatorial_radius_km: 1.0,
+            polar_radius_km: 2.0,
+        };
+        for point in [
+            Vector3::new(4.0, -2.0, 3.0),
+            Vector3::new(-0.5, 0.2, 0.3),
+            Vector3::new(0.0, 1.5, -2.5),
+            Vector3::new(1.0, 0.0, 0.0),
+        ] {
+            let nearest = triaxial.nearest_point(&point);
+            let on_surface =
+                (nearest.x / 3.0).powi(2) + nearest.y.powi(2) + (nearest.z / 2.0).powi(2);
+            assert!((on_surface - 1.0).abs() < 1e-12, "{point}");
+            let offset = point - nearest;
+            assert!(
+                offset.cross(&triaxial.surface_normal(&nearest)).norm() < 1e-9,
+                "{point}"
+            );
+        }
+        // Inside on the major axis, the nearest point is along the smallest axis
+        let nearest = triaxial.nearest_point(&Vector3::zeros());
+        assert!((nearest.y.abs() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tangent_point() {
+        let earth = Ellipsoid::from_spheroid(6378.0, 6357.0);
+        // Horizontal ray above the pole
+        let origin = Vector3::new(0.0, -10_000.0, 6_500.0);
+        let (tangent, altitude_km) = earth.tangent_point(&origin, &Vector3::y()).unwrap();
+        assert!((tangent - Vector3::new(0.0, 0.0, 6_500.0)).norm() < 1e-4);
+        assert!((altitude_km - 143.0).abs() < 1e-6);
+        // Ray moving away from the ellipsoid, and rays hitting it
+        let (tangent, _) = earth.tangent_point(&origin, &-Vector3::y()).unwrap();
+        assert!((tangent - origin).norm() < 1e-4);
+        assert!(earth
+            .tangent_point(&origin, &Vector3::new(0.0, 1.0, -1.0))
+            .is_none());
+        assert!(earth
+            .tangent_point(&Vector3::zeros(), &Vector3::x())
+            .is_none());
+    }
+
+    #[test]
+    fn geodesic_distance() {
+        // Flinders Peak to Buninyong on the WGS84 ellipsoid (Vincenty, 1975)
+        let wgs84 = Ellipsoid::from_spheroid(6378.137, 6378.137 * (1.0 - 1.0 / 298.257223563));
+        let distance_km = wgs84
+            .geodesic_distance_km(
+                -(37.0 + 57.0 / 60.0 + 3.72030 / 3600.0),
+                144.0 + 25.0 / 60.0 + 29.52440 / 3600.0,
+                -(37.0 + 39.0 / 60.0 + 10.15610 / 3600.0),
+                143.0 + 55.0 / 60.0 + 35.38390 / 3600.0,
+            )
+            .unwrap();
+        assert!((distance_km - 54.972_271).abs() < 1e-5, "{distance_km}");
+
+        // Along the equator, and coincident points
+        let distance_km = wgs84.geodesic_distance_km(0.0, 0.0, 0.0, 90.0).unwrap();
+        assert!((distance_km - 6378.137 * core::f64::consts::FRAC_PI_2).abs() < 1e-6);
+        assert_eq!(
+            wgs84.geodesic_distance_km(10.0, 20.0, 10.0, 20.0).unwrap(),
+            0.0
+        );
+    }
+}
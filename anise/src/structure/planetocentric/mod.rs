@@ -20,9 +20,11 @@ use crate::{
 };
 use core::f64::consts::FRAC_PI_2;
 use core::fmt;
+pub mod dipole;
 pub mod ellipsoid;
 pub mod phaseangle;
 use der::{Decode, Encode, Reader, Writer};
+use dipole::GeomagneticDipole;
 use ellipsoid::Ellipsoid;
 use hifitime::{Epoch, TimeUnits, Unit};
 use phaseangle::PhaseAngle;
@@ -82,6 +84,8 @@ pub struct PlanetaryData {
     /// E.g. For `E1 = 125.045 -  0.052992 d`, this would be stored as a single entry `(125.045, -0.052992)`.
     pub num_nut_prec_angles: u8,
     pub nut_prec_angles: [PhaseAngle<0>; MAX_NUT_PREC_ANGLES],
+    /// Centered tilted dipole model of the magnetic field, in the body fixed frame of this object
+    pub geomagnetic_dipole: Option<GeomagneticDipole>,
 }
 
 impl DataSetT for PlanetaryData {
@@ -106,6 +110,7 @@ impl PlanetaryData {
     /// + Bit 2 is set if `pole_declination` is available
     /// + Bit 3 is set if `prime_meridian` is available
     /// + Bit 4 is set if `long_axis` is available
+    /// + Bit 5 is set if `geomagnetic_dipole` is available
     fn available_data(&self) -> u8 {
         let mut bits: u8 = 0;
 
@@ -124,6 +129,9 @@ impl PlanetaryData {
         if self.long_axis.is_some() {
             bits |= 1 << 4;
         }
+        if self.geomagnetic_dipole.is_some() {
+            bits |= 1 << 5;
+        }
 
         bits
     }
@@ -274,6 +282,7 @@ impl Encode for PlanetaryData {
             + self.long_axis.encoded_len()?
             + self.num_nut_prec_angles.encoded_len()?
             + self.nut_prec_angles.encoded_len()?
+            + self.geomagnetic_dipole.encoded_len()?
     }
 
     fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
@@ -287,7 +296,8 @@ impl Encode for PlanetaryData {
         self.prime_meridian.encode(encoder)?;
         self.long_axis.encode(encoder)?;
         self.num_nut_prec_angles.encode(encoder)?;
-        self.nut_prec_angles.encode(encoder)
+        self.nut_prec_angles.encode(encoder)?;
+        self.geomagnetic_dipole.encode(encoder)
     }
 }
 
@@ -329,6 +339,15 @@ impl<'a> Decode<'a> for PlanetaryData {
             None
         };
 
+        let num_nut_prec_angles = decoder.decode()?;
+        let nut_prec_angles = decoder.decode()?;
+
+        let geomagnetic_dipole = if data_flags & (1 << 5) != 0 {
+            Some(decoder.decode()?)
+        } else {
+            None
+        };
+
         Ok(Self {
             object_id,
             parent_id,
@@ -338,8 +357,9 @@ impl<'a> Decode<'a> for PlanetaryData {
             pole_declination,
             prime_meridian,
             long_axis,
-            num_nut_prec_angles: decoder.decode()?,
-            nut_prec_angles: decoder.decode()?,
+            num_nut_prec_angles,
+            nut_prec_angles,
+            geomagnetic_dipole,
         })
     }
 }
@@ -374,6 +394,9 @@ impl fmt::Display for PlanetaryData {
         if self.num_nut_prec_angles > 0 {
             write!(f, " + {} nut/prec angles", self.num_nut_prec_angles)?;
         }
+        if let Some(dipole) = self.geomagnetic_dipole {
+            write!(f, " {dipole}")?;
+        }
 
         Ok(())
     }
@@ -381,7 +404,7 @@ impl fmt::Display for PlanetaryData {
 
 #[cfg(test)]
 mod planetary_constants_ut {
-    use super::{Ellipsoid, PhaseAngle, PlanetaryData};
+    use super::{Ellipsoid, GeomagneticDipole, PhaseAngle, PlanetaryData};
     use der::{Decode, Encode};
 
     #[test]
@@ -533,7 +556,7 @@ mod planetary_constants_ut {
 
         assert_eq!(repr, min_repr_dec);
 
-        assert_eq!(core::mem::size_of::<PlanetaryData>(), 1984);
+        assert_eq!(core::mem::size_of::<PlanetaryData>(), 2024);
 
         assert_eq!(format!("{repr}"), "planetary data 1234 (μ = 12345.6789 km^3/s^2) Dec = 66.541 + 0.013 t PM = 38.317 + 13.1763582 t");
     }
@@ -555,6 +578,27 @@ mod planetary_constants_ut {
         assert_eq!(min_repr, min_repr_dec);
     }
 
+    #[test]
+    fn pc_encdec_with_dipole_only() {
+        let repr = PlanetaryData {
+            object_id: 399,
+            mu_km3_s2: 398600.435436,
+            geomagnetic_dipole: Some(GeomagneticDipole::earth(80.74, 287.34)),
+            ..Default::default()
+        };
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = PlanetaryData::from_der(&buf).unwrap();
+
+        assert_eq!(repr, repr_dec);
+        assert_eq!(
+            format!("{repr}"),
+            "IAU_EARTH (μ = 398600.435436 km^3/s^2) dipole north pole = (80.74 deg, 287.34 deg), B0 = 29806 nT at 6371.2 km"
+        );
+    }
+
     #[test]
     fn test_301() {
         // Build the Moon 301 representation from pck00008.tpc data
@@ -602,6 +646,7 @@ mod planetary_constants_ut {
             long_axis: None,
             num_nut_prec_angles: 0,
             nut_prec_angles: Default::default(),
+            geomagnetic_dipole: None,
         };
 
         // Encode
@@ -0,0 +1,248 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+use der::{Decode, Encode, Reader, Writer};
+use hifitime::{Duration, Epoch, TimeScale};
+use serde_derive::{Deserialize, Serialize};
+
+/// A wet mass of the spacecraft, in kg, valid from the provided epoch.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MassEntry {
+    pub epoch: Epoch,
+    pub wet_mass_kg: f64,
+}
+
+impl Encode for MassEntry {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        let (centuries, nanoseconds) = self.epoch.duration.to_parts();
+        u8::from(self.epoch.time_scale).encoded_len()?
+            + centuries.encoded_len()?
+            + nanoseconds.encoded_len()?
+            + self.wet_mass_kg.encoded_len()?
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        // Store the epoch in its own time scale to avoid any loss of precision.
+        let (centuries, nanoseconds) = self.epoch.duration.to_parts();
+        u8::from(self.epoch.time_scale).encode(encoder)?;
+        centuries.encode(encoder)?;
+        nanoseconds.encode(encoder)?;
+        self.wet_mass_kg.encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for MassEntry {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let time_scale: u8 = decoder.decode()?;
+        let centuries: i16 = decoder.decode()?;
+        let nanoseconds: u64 = decoder.decode()?;
+        Ok(Self {
+            epoch: Epoch::from_duration(
+                Duration::from_parts(centuries, nanoseconds),
+                TimeScale::from(time_scale),
+            ),
+            wet_mass_kg: decoder.decode()?,
+        })
+    }
+}
+
+/// Maximum number of entries in a mass history.
+pub const MAX_MASS_ENTRIES: usize = 32;
+
+/// Time-tagged history of the wet mass of a spacecraft, sorted by increasing epoch.
+///
+/// The mass is linearly interpolated between consecutive entries. An instantaneous mass change, e.g. from an impulsive
+/// maneuver, is represented by two entries with the same epoch: the mass before and then the mass after the change.
+///
+/// The history holds up to [MAX_MASS_ENTRIES] entries, without any heap allocation, so that the spacecraft data remains `Copy`.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(into = "Vec<MassEntry>", try_from = "Vec<MassEntry>")]
+pub struct MassHistory {
+    entries: [MassEntry; MAX_MASS_ENTRIES],
+    len: usize,
+}
+
+impl Default for MassHistory {
+    fn default() -> Self {
+        let unset = MassEntry {
+            epoch: Epoch::from_tai_duration(Duration::ZERO),
+            wet_mass_kg: 0.0,
+        };
+        Self {
+            entries: [unset; MAX_MASS_ENTRIES],
+            len: 0,
+        }
+    }
+}
+
+impl PartialEq for MassHistory {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries() == other.entries()
+    }
+}
+
+impl MassHistory {
+    /// Builds a new mass history from the provided entries, sorting them by epoch while preserving the order of
+    /// entries with the same epoch.
+    ///
+    /// Returns the first entry which does not fit if there are more than [MAX_MASS_ENTRIES] entries.
+    pub fn new(entries: impl IntoIterator<Item = MassEntry>) -> Result<Self, MassEntry> {
+        let mut me = Self::default();
+        for entry in entries {
+            me.push(entry.epoch, entry.wet_mass_kg)?;
+        }
+        Ok(me)
+    }
+
+    /// Returns the entries of this mass history, sorted by epoch.
+    pub fn entries(&self) -> &[MassEntry] {
+        &self.entries[..self.len]
+    }
+
+    /// Adds the provided wet mass at that epoch, after any existing entry at that same epoch.
+    ///
+    /// Returns the entry back if the history is full.
+    pub fn push(&mut self, epoch: Epoch, wet_mass_kg: f64) -> Result<(), MassEntry> {
+        let entry = MassEntry { epoch, wet_mass_kg };
+        if self.len == MAX_MASS_ENTRIES {
+            return Err(entry);
+        }
+        let index = self.entries().partition_point(|entry| entry.epoch <= epoch);
+        self.entries.copy_within(index..self.len, index + 1);
+        self.entries[index] = entry;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Returns the first and last epochs of this mass history, if it isn't empty.
+    pub fn domain(&self) -> Option<(Epoch, Epoch)> {
+        Some((self.entries().first()?.epoch, self.entries().last()?.epoch))
+    }
+
+    /// Returns the wet mass in kg at the provided epoch.
+    ///
+    /// The mass is interpolated linearly between consecutive entries and the last mass is held after the last entry.
+    /// At the epoch of an instantaneous mass change, the mass after the change is returned.
+    /// Returns None if the history is empty or if the epoch is before the first entry.
+    pub fn wet_mass_kg_at(&self, epoch: Epoch) -> Option<f64> {
+        let entries = self.entries();
+        // Index of the first entry strictly after the requested epoch.
+        let index = entries.partition_point(|entry| entry.epoch <= epoch);
+        if index == 0 {
+            return None;
+        }
+
+        let prev = entries[index - 1];
+        match entries.get(index) {
+            None => Some(prev.wet_mass_kg),
+            Some(next) => {
+                let frac =
+                    (epoch - prev.epoch).to_seconds() / (next.epoch - prev.epoch).to_seconds();
+                Some(prev.wet_mass_kg + frac * (next.wet_mass_kg - prev.wet_mass_kg))
+            }
+        }
+    }
+}
+
+impl From<MassHistory> for Vec<MassEntry> {
+    fn from(history: MassHistory) -> Self {
+        history.entries().to_vec()
+    }
+}
+
+impl TryFrom<Vec<MassEntry>> for MassHistory {
+    type Error = String;
+
+    fn try_from(entries: Vec<MassEntry>) -> Result<Self, Self::Error> {
+        Self::new(entries)
+            .map_err(|_| format!("a mass history holds at most {MAX_MASS_ENTRIES} entries"))
+    }
+}
+
+impl Encode for MassHistory {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        self.entries().to_vec().encoded_len()
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        self.entries().to_vec().encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for MassHistory {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        let entries: Vec<MassEntry> = decoder.decode()?;
+        Self::new(entries).map_err(|_| der::ErrorKind::Overlength.into())
+    }
+}
+
+#[cfg(test)]
+mod mass_history_ut {
+    use super::{Decode, Encode, MassEntry, MassHistory, MAX_MASS_ENTRIES};
+    use hifitime::{Epoch, TimeUnits};
+
+    #[test]
+    fn mass_history_repr() {
+        let epoch = Epoch::from_gregorian_utc(2024, 1, 1, 12, 34, 56, 789);
+        let repr = MassHistory::new([
+            MassEntry {
+                epoch: epoch + 1.days(),
+                wet_mass_kg: 480.0,
+            },
+            MassEntry {
+                epoch,
+                wet_mass_kg: 500.0,
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(repr.entries()[0].epoch, epoch);
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = MassHistory::from_der(&buf).unwrap();
+
+        assert_eq!(repr, repr_dec);
+    }
+
+    #[test]
+    fn mass_history_interpolation() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let mut history = MassHistory::default();
+        assert_eq!(history.wet_mass_kg_at(epoch), None);
+        assert_eq!(history.domain(), None);
+
+        // Finite burn over ten minutes, then an impulsive maneuver one day later.
+        history.push(epoch, 500.0).unwrap();
+        history.push(epoch + 10.minutes(), 490.0).unwrap();
+        history.push(epoch + 1.days(), 490.0).unwrap();
+        history.push(epoch + 1.days(), 485.0).unwrap();
+
+        assert_eq!(history.domain(), Some((epoch, epoch + 1.days())));
+        assert_eq!(history.wet_mass_kg_at(epoch - 1.seconds()), None);
+        assert_eq!(history.wet_mass_kg_at(epoch), Some(500.0));
+        assert_eq!(history.wet_mass_kg_at(epoch + 5.minutes()), Some(495.0));
+        assert_eq!(history.wet_mass_kg_at(epoch + 12.hours()), Some(490.0));
+        // Mass after the impulsive maneuver, held thereafter
+        assert_eq!(history.wet_mass_kg_at(epoch + 1.days()), Some(485.0));
+        assert_eq!(history.wet_mass_kg_at(epoch + 30.days()), Some(485.0));
+
+        // The history has a fixed capacity
+        let full = MassHistory::new((0..MAX_MASS_ENTRIES).map(|i| MassEntry {
+            epoch: epoch + (i as i64).hours(),
+            wet_mass_kg: 500.0 - i as f64,
+        }))
+        .unwrap();
+        assert_eq!(full.entries().len(), MAX_MASS_ENTRIES);
+        let mut overfull = full;
+        assert!(overfull.push(epoch, 500.0).is_err());
+        assert_eq!(overfull, full);
+    }
+}
@@ -8,17 +8,22 @@
  * Documentation: https://nyxspace.com/
  */
 use der::{Decode, Encode, Reader, Writer};
+use hifitime::Epoch;
 use serde::{Deserialize, Serialize};
 
 mod drag;
 mod inertia;
 mod mass;
+mod mass_history;
+mod propulsion;
 mod srp;
 
 use super::dataset::DataSetT;
 pub use drag::DragData;
 pub use inertia::Inertia;
 pub use mass::Mass;
+pub use mass_history::{MassEntry, MassHistory, MAX_MASS_ENTRIES};
+pub use propulsion::PropulsionData;
 pub use srp::SRPData;
 
 /// Spacecraft constants can store the some of the spacecraft constant data as the CCSDS Orbit Parameter Message (OPM) and CCSDS Attitude Parameter Messages (APM)
@@ -32,6 +37,10 @@ pub struct SpacecraftData {
     pub drag_data: Option<DragData>,
     // Inertia tensor
     pub inertia: Option<Inertia>,
+    /// Main propulsion system data
+    pub propulsion: Option<PropulsionData>,
+    /// Time-tagged history of the wet mass
+    pub mass_history: Option<MassHistory>,
 }
 
 impl DataSetT for SpacecraftData {
@@ -46,6 +55,8 @@ impl SpacecraftData {
     /// + Bit 1 is set if `srp_data` is available
     /// + Bit 2 is set if `drag_data` is available
     /// + Bit 3 is set if `inertia` is available
+    /// + Bit 4 is set if `propulsion` is available
+    /// + Bit 5 is set if `mass_history` is available
     fn available_data(&self) -> u8 {
         let mut bits: u8 = 0;
 
@@ -61,9 +72,24 @@ impl SpacecraftData {
         if self.inertia.is_some() {
             bits |= 1 << 3;
         }
+        if self.propulsion.is_some() {
+            bits |= 1 << 4;
+        }
+        if self.mass_history.is_some() {
+            bits |= 1 << 5;
+        }
 
         bits
     }
+
+    /// Returns the wet mass in kg at the provided epoch, interpolated from the mass history if it is set and covers that
+    /// epoch, or the total mass otherwise.
+    pub fn wet_mass_kg_at(&self, epoch: Epoch) -> Option<f64> {
+        self.mass_history
+            .as_ref()
+            .and_then(|history| history.wet_mass_kg_at(epoch))
+            .or_else(|| self.mass.map(|mass| mass.total_mass_kg()))
+    }
 }
 
 impl Encode for SpacecraftData {
@@ -74,6 +100,8 @@ impl Encode for SpacecraftData {
             + self.srp_data.encoded_len()?
             + self.drag_data.encoded_len()?
             + self.inertia.encoded_len()?
+            + self.propulsion.encoded_len()?
+            + self.mass_history.encoded_len()?
     }
 
     fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
@@ -81,7 +109,9 @@ impl Encode for SpacecraftData {
         self.mass.encode(encoder)?;
         self.srp_data.encode(encoder)?;
         self.drag_data.encode(encoder)?;
-        self.inertia.encode(encoder)
+        self.inertia.encode(encoder)?;
+        self.propulsion.encode(encoder)?;
+        self.mass_history.encode(encoder)
     }
 }
 
@@ -113,18 +143,36 @@ impl<'a> Decode<'a> for SpacecraftData {
             None
         };
 
+        let propulsion = if data_flags & (1 << 4) != 0 {
+            Some(decoder.decode()?)
+        } else {
+            None
+        };
+
+        let mass_history = if data_flags & (1 << 5) != 0 {
+            Some(decoder.decode()?)
+        } else {
+            None
+        };
+
         Ok(Self {
             mass: mass_kg,
             srp_data,
             drag_data,
             inertia,
+            propulsion,
+            mass_history,
         })
     }
 }
 
 #[cfg(test)]
 mod spacecraft_constants_ut {
-    use super::{Decode, DragData, Encode, Inertia, Mass, SRPData, SpacecraftData};
+    use super::{
+        Decode, DragData, Encode, Inertia, Mass, MassHistory, PropulsionData, SRPData,
+        SpacecraftData,
+    };
+    use hifitime::{Epoch, TimeUnits};
 
     #[test]
     fn sc_min_repr() {
@@ -198,6 +246,48 @@ mod spacecraft_constants_ut {
         assert_eq!(repr, repr_dec);
     }
 
+    #[test]
+    fn sc_with_propulsion_only() {
+        let repr = SpacecraftData {
+            propulsion: Some(PropulsionData::default()),
+            ..Default::default()
+        };
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = SpacecraftData::from_der(&buf).unwrap();
+
+        assert_eq!(repr, repr_dec);
+    }
+
+    #[test]
+    fn sc_with_mass_history() {
+        let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+        let mut history = MassHistory::default();
+        history.push(epoch, 200.6).unwrap();
+        history.push(epoch + 1.hours(), 190.6).unwrap();
+
+        let repr = SpacecraftData {
+            mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.6)),
+            mass_history: Some(history),
+            ..Default::default()
+        };
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = SpacecraftData::from_der(&buf).unwrap();
+
+        assert_eq!(repr, repr_dec);
+
+        // Before the history, the total mass is used.
+        assert_eq!(repr.wet_mass_kg_at(epoch - 1.days()), Some(200.6));
+        assert_eq!(repr.wet_mass_kg_at(epoch + 30.minutes()), Some(195.6));
+        assert_eq!(repr.wet_mass_kg_at(epoch + 1.days()), Some(190.6));
+        assert_eq!(SpacecraftData::default().wet_mass_kg_at(epoch), None);
+    }
+
     #[test]
     fn sc_with_srp_mass_inertia() {
         let repr = SpacecraftData {
@@ -244,6 +334,12 @@ mod spacecraft_constants_ut {
             }),
             mass: Some(Mass::from_dry_and_prop_masses(150.0, 50.6)),
             drag_data: Some(DragData::default()),
+            propulsion: Some(PropulsionData {
+                thrust_n: 22.0,
+                isp_s: 220.0,
+                min_impulse_bit_ns: 0.1,
+            }),
+            mass_history: Some(MassHistory::default()),
         };
 
         let mut buf = vec![];
@@ -0,0 +1,101 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+use der::{Decode, Encode, Reader, Writer};
+use serde_derive::{Deserialize, Serialize};
+
+/// Standard gravity in m/s^2, used to convert the specific impulse into an exhaust velocity.
+pub const STD_GRAVITY_M_S2: f64 = 9.80665;
+
+/// Defines the main propulsion system of a spacecraft, assumed to be a single equivalent thruster.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PropulsionData {
+    /// Thrust of the propulsion system, in Newtons
+    pub thrust_n: f64,
+    /// Specific impulse of the propulsion system, in seconds
+    pub isp_s: f64,
+    /// Minimum impulse bit of the propulsion system, in Newton seconds
+    pub min_impulse_bit_ns: f64,
+}
+
+impl PropulsionData {
+    /// Returns the effective exhaust velocity in m/s
+    pub fn exhaust_velocity_m_s(&self) -> f64 {
+        self.isp_s * STD_GRAVITY_M_S2
+    }
+
+    /// Returns the mass flow rate in kg/s when thrusting at full thrust, or zero if the specific impulse is zero.
+    pub fn mass_flow_rate_kg_s(&self) -> f64 {
+        let exhaust_velocity_m_s = self.exhaust_velocity_m_s();
+        if exhaust_velocity_m_s > 0.0 {
+            self.thrust_n / exhaust_velocity_m_s
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Encode for PropulsionData {
+    fn encoded_len(&self) -> der::Result<der::Length> {
+        self.thrust_n.encoded_len()?
+            + self.isp_s.encoded_len()?
+            + self.min_impulse_bit_ns.encoded_len()?
+    }
+
+    fn encode(&self, encoder: &mut impl Writer) -> der::Result<()> {
+        self.thrust_n.encode(encoder)?;
+        self.isp_s.encode(encoder)?;
+        self.min_impulse_bit_ns.encode(encoder)
+    }
+}
+
+impl<'a> Decode<'a> for PropulsionData {
+    fn decode<R: Reader<'a>>(decoder: &mut R) -> der::Result<Self> {
+        Ok(Self {
+            thrust_n: decoder.decode()?,
+            isp_s: decoder.decode()?,
+            min_impulse_bit_ns: decoder.decode()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod propulsion_ut {
+    use super::{Decode, Encode, PropulsionData};
+    #[test]
+    fn zero_repr() {
+        let repr = PropulsionData::default();
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = PropulsionData::from_der(&buf).unwrap();
+
+        assert_eq!(repr, repr_dec);
+        assert_eq!(repr.mass_flow_rate_kg_s(), 0.0);
+    }
+
+    #[test]
+    fn example_repr() {
+        // Typical hydrazine monopropellant thruster
+        let repr = PropulsionData {
+            thrust_n: 22.0,
+            isp_s: 220.0,
+            min_impulse_bit_ns: 0.1,
+        };
+
+        let mut buf = vec![];
+        repr.encode_to_vec(&mut buf).unwrap();
+
+        let repr_dec = PropulsionData::from_der(&buf).unwrap();
+
+        assert_eq!(repr, repr_dec);
+        assert!((repr.mass_flow_rate_kg_s() - 22.0 / (220.0 * 9.80665)).abs() < f64::EPSILON);
+    }
+}
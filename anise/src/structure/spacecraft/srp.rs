@@ -10,6 +10,9 @@
 use der::{Decode, Encode, Reader, Writer};
 use serde_derive::{Deserialize, Serialize};
 
+use crate::constants::{AU_KM, SOLAR_FLUX_1AU_W_M2, SPEED_OF_LIGHT_KM_S};
+use crate::math::Vector3;
+
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SRPData {
     /// Solar radiation pressure area in m^2 -- default 0.0
@@ -25,6 +28,20 @@ impl SRPData {
             ..Default::default()
         }
     }
+
+    /// Returns the cannonball solar radiation pressure acceleration, in km/s^2, given the position of the spacecraft with
+    /// respect to the Sun (in km), its mass, and the shadow function (the fraction of the solar flux it receives, cf.
+    /// `Almanac::solar_shadow_function`).
+    pub fn acceleration_km_s2(&self, sun_to_sc_km: &Vector3, mass_kg: f64, shadow: f64) -> Vector3 {
+        let distance_km = sun_to_sc_km.norm();
+        if distance_km < f64::EPSILON || mass_kg <= 0.0 {
+            return Vector3::zeros();
+        }
+        let pressure_n_m2 =
+            SOLAR_FLUX_1AU_W_M2 / (SPEED_OF_LIGHT_KM_S * 1e3) * (AU_KM / distance_km).powi(2);
+        let accel_m_s2 = shadow * pressure_n_m2 * self.coeff_reflectivity * self.area_m2 / mass_kg;
+        sun_to_sc_km / distance_km * accel_m_s2 * 1e-3
+    }
 }
 
 impl Default for SRPData {
@@ -58,7 +75,7 @@ impl<'a> Decode<'a> for SRPData {
 
 #[cfg(test)]
 mod srp_ut {
-    use super::{Decode, Encode, SRPData};
+    use super::{Decode, Encode, SRPData, Vector3, AU_KM};
     #[test]
     fn zero_repr() {
         let repr = SRPData {
@@ -85,4 +102,26 @@ mod srp_ut {
 
         assert_eq!(repr, repr_dec);
     }
+
+    #[test]
+    fn cannonball_acceleration() {
+        let srp = SRPData {
+            area_m2: 10.0,
+            coeff_reflectivity: 1.5,
+        };
+        let sun_to_sc_km = Vector3::new(0.0, AU_KM, 0.0);
+        let accel = srp.acceleration_km_s2(&sun_to_sc_km, 1000.0, 1.0);
+        // About 4.54e-6 N/m^2 at 1 AU, directed away from the Sun
+        let expected_m_s2 = 1361.0 / 299_792_458.0 * 1.5 * 10.0 / 1000.0;
+        assert!((accel.y * 1e3 - expected_m_s2).abs() < 1e-15);
+        assert!(accel.x.abs() < f64::EPSILON && accel.z.abs() < f64::EPSILON);
+        // Half the flux in penumbra, and none in umbra
+        assert!(
+            (srp.acceleration_km_s2(&sun_to_sc_km, 1000.0, 0.5).y - accel.y / 2.0).abs() < 1e-20
+        );
+        assert_eq!(
+            srp.acceleration_km_s2(&sun_to_sc_km, 1000.0, 0.0),
+            Vector3::zeros()
+        );
+    }
 }
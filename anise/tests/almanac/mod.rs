@@ -25,6 +25,34 @@ fn test_load_ctx() {
     dbg!(core::mem::size_of::<Almanac>());
 }
 
+#[test]
+fn test_load_lazy() {
+    let almanac = Almanac::default()
+        .load("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap();
+    // The kernels are not modified by any test
+    let lazy_almanac = unsafe {
+        Almanac::default()
+            .load_lazy("../data/de440s.bsp")
+            .unwrap()
+            .load_lazy("../data/earth_latest_high_prec.bpc")
+    }
+    .unwrap();
+
+    let epoch = Epoch::from_str("2021-10-29 12:34:56 TDB").unwrap();
+
+    assert_eq!(
+        almanac
+            .transform(SUN_J2000, EARTH_ITRF93, epoch, None)
+            .unwrap(),
+        lazy_almanac
+            .transform(SUN_J2000, EARTH_ITRF93, epoch, None)
+            .unwrap()
+    );
+}
+
 #[test]
 fn test_state_transformation() {
     // Load BSP and BPC
@@ -105,3 +133,599 @@ fn test_type3_state_transformation() {
 
     println!("{state:x}");
 }
+
+#[test]
+fn test_porkchop_earth_mars_2020() {
+    use anise::astro::lambert::TransferKind;
+    use anise::constants::frames::MARS_BARYCENTER_J2000;
+    use hifitime::{TimeSeries, TimeUnits};
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let departures = TimeSeries::inclusive(
+        Epoch::from_str("2020-07-01 00:00:00 TDB").unwrap(),
+        Epoch::from_str("2020-08-15 00:00:00 TDB").unwrap(),
+        5.days(),
+    )
+    .collect::<Vec<Epoch>>();
+
+    let arrivals = TimeSeries::inclusive(
+        Epoch::from_str("2021-01-15 00:00:00 TDB").unwrap(),
+        Epoch::from_str("2021-03-01 00:00:00 TDB").unwrap(),
+        5.days(),
+    )
+    .collect::<Vec<Epoch>>();
+
+    let plot = almanac
+        .porkchop(
+            EARTH_J2000,
+            MARS_BARYCENTER_J2000,
+            SUN_J2000,
+            &departures,
+            &arrivals,
+            TransferKind::Auto,
+        )
+        .unwrap();
+
+    assert_eq!(plot.c3_km2_s2.nrows(), departures.len());
+    assert_eq!(plot.c3_km2_s2.ncols(), arrivals.len());
+
+    let (dep, arr, c3) = plot.min_c3().unwrap();
+    println!("min C3 = {c3:.3} km^2/s^2 departing {dep} arriving {arr}");
+    // Mars 2020 launched with a C3 of about 14.5 km^2/s^2
+    assert!(c3 > 5.0 && c3 < 25.0);
+}
+
+#[test]
+fn test_coverage_nadir_imager() {
+    use anise::analysis::coverage::{CoverageAsset, CoverageAttitude};
+    use anise::constants::frames::IAU_EARTH_FRAME;
+    use anise::instrument::{FovShape, Instrument};
+    use anise::math::rotation::DCM;
+    use anise::progress::ProgressSink;
+    use hifitime::TimeSeries;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::Mutex;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/gmat-hermite.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let sc_id = -10000001;
+    let (start, end) = almanac.spk_domain(sc_id).unwrap();
+    let epochs = TimeSeries::inclusive(start, end, (end - start) / 200.0).collect::<Vec<Epoch>>();
+
+    // Nadir pointing imager
+    let asset = CoverageAsset {
+        spacecraft_id: sc_id,
+        instrument: Instrument {
+            mounting: DCM::identity(-100, -100_001),
+            fov: FovShape::Circular {
+                half_angle_deg: 30.0,
+            },
+        },
+        attitude: CoverageAttitude::Nadir,
+    };
+
+    let report = almanac
+        .coverage(IAU_EARTH_FRAME, &[asset], &epochs, 10.0, 10.0, 0.0)
+        .unwrap();
+    println!("{report}");
+
+    assert_eq!(report.cells.len(), 18 * 36);
+    assert_eq!(report.num_samples, epochs.len());
+    let covered = report.percent_covered();
+    assert!(covered > 0.0 && covered < 100.0, "{covered}");
+    for cell in report.cells.iter().filter(|cell| cell.is_covered()) {
+        assert!(cell.first_access.unwrap() <= cell.last_access.unwrap());
+        if cell.num_accesses > 1 {
+            assert!(cell.max_revisit.unwrap() >= cell.mean_revisit.unwrap());
+        }
+    }
+
+    // Looking away from the Earth, nothing is covered
+    let zenith = CoverageAsset {
+        instrument: Instrument {
+            mounting: DCM::r1(core::f64::consts::PI, -100, -100_001),
+            ..asset.instrument
+        },
+        ..asset
+    };
+    let report = almanac
+        .coverage(IAU_EARTH_FRAME, &[zenith], &epochs, 10.0, 10.0, 0.0)
+        .unwrap();
+    assert_eq!(report.percent_covered(), 0.0);
+    assert!(report.max_revisit().is_none());
+
+    assert!(almanac
+        .coverage(IAU_EARTH_FRAME, &[], &epochs, 10.0, 10.0, 0.0)
+        .is_err());
+
+    // The progress sink is told the total amount of work and sees all of it done.
+    #[derive(Default)]
+    struct Recorder {
+        total: Mutex<Option<u64>>,
+        done: AtomicU64,
+        finished: AtomicBool,
+    }
+
+    impl ProgressSink for Recorder {
+        fn start(&self, _task: &str, total: Option<u64>) {
+            *self.total.lock().unwrap() = total;
+        }
+
+        fn update(&self, done: u64) {
+            self.done.fetch_max(done, Ordering::Relaxed);
+        }
+
+        fn finish(&self) {
+            self.finished.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let recorder = Recorder::default();
+    let with_progress = almanac
+        .coverage_with_progress(
+            IAU_EARTH_FRAME,
+            &[zenith],
+            &epochs,
+            10.0,
+            10.0,
+            0.0,
+            Some(&recorder),
+        )
+        .unwrap();
+    assert_eq!(with_progress, report);
+    let total = (epochs.len() + 18 * 36) as u64;
+    assert_eq!(*recorder.total.lock().unwrap(), Some(total));
+    assert_eq!(recorder.done.load(Ordering::Relaxed), total);
+    assert!(recorder.finished.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_dop_gnss_walker() {
+    use anise::analysis::dop::DopTransmitters;
+    use anise::astro::gnss::{BroadcastEphemeris, GnssEphemerides};
+    use anise::structure::location::Location;
+    use hifitime::{TimeScale, TimeSeries, TimeUnits};
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap()
+        .load("../data/earth_latest_high_prec.bpc")
+        .unwrap();
+
+    // Walker 55 deg: 24/6/1 constellation, similar to the GPS nominal constellation
+    let toe = Epoch::from_gregorian(2020, 2, 7, 0, 0, 0, 0, TimeScale::GPST);
+    let sma_m: f64 = 26_560e3;
+    let mut gnss = GnssEphemerides::default();
+    for plane in 0..6 {
+        for slot in 0..4 {
+            gnss.ephemerides.push(BroadcastEphemeris {
+                prn: (plane * 4 + slot + 1) as u8,
+                toe,
+                sqrt_a_m: sma_m.sqrt(),
+                ecc: 0.0,
+                inc_rad: 55f64.to_radians(),
+                inc_rate_rad_s: 0.0,
+                raan_rad: (60.0 * plane as f64).to_radians(),
+                raan_rate_rad_s: 0.0,
+                aop_rad: 0.0,
+                ma_rad: (90.0 * slot as f64 + 15.0 * plane as f64).to_radians(),
+                delta_n_rad_s: 0.0,
+                cuc_rad: 0.0,
+                cus_rad: 0.0,
+                crc_m: 0.0,
+                crs_m: 0.0,
+                cic_rad: 0.0,
+                cis_rad: 0.0,
+                af0_s: 0.0,
+                af1_s_s: 0.0,
+                healthy: true,
+            });
+        }
+    }
+
+    let madrid = Location {
+        latitude_deg: 40.427_222,
+        longitude_deg: 4.250_556,
+        height_km: 0.834_939,
+        frame: EARTH_ITRF93.into(),
+        min_elevation_deg: Some(5.0),
+        ..Default::default()
+    };
+
+    let epochs = TimeSeries::inclusive(toe, toe + 12.hours(), 1.hours()).collect::<Vec<Epoch>>();
+    let dops = almanac
+        .dop(DopTransmitters::Gnss(&gnss), &madrid, &epochs)
+        .unwrap();
+    assert_eq!(dops.len(), epochs.len());
+    for dop in &dops {
+        println!("{dop}");
+        assert!(dop.is_valid() && dop.num_visible >= 4);
+        assert!(dop.hdop < dop.pdop && dop.vdop < dop.pdop && dop.pdop < dop.gdop);
+        assert!(dop.gdop < 10.0);
+    }
+
+    // A higher elevation mask hides some of the satellites and degrades the geometry
+    let masked = Location {
+        min_elevation_deg: Some(30.0),
+        ..madrid.clone()
+    };
+    let masked_dops = almanac
+        .dop(DopTransmitters::Gnss(&gnss), &masked, &epochs)
+        .unwrap();
+    for (dop, masked) in dops.iter().zip(masked_dops.iter()) {
+        assert!(masked.num_visible <= dop.num_visible);
+        assert!(!masked.is_valid() || masked.gdop >= dop.gdop - 1e-12);
+    }
+    let num_visible = |dops: &[anise::analysis::dop::Dop]| -> usize {
+        dops.iter().map(|dop| dop.num_visible).sum()
+    };
+    assert!(num_visible(&masked_dops) < num_visible(&dops));
+
+    assert!(almanac
+        .dop(DopTransmitters::Gnss(&gnss), &madrid, &[])
+        .is_err());
+}
+
+#[test]
+fn test_constellation_visibility() {
+    use anise::analysis::constellation::Constellation;
+    use anise::constants::frames::IAU_EARTH_FRAME;
+    use anise::prelude::Frame;
+    use anise::structure::location::Location;
+    use hifitime::TimeSeries;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/gmat-hermite.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let sc_id = -10000001;
+    let constellation = Constellation::from_ephemerides("Demo", &[sc_id, 301]);
+    assert_eq!(constellation.len(), 2);
+
+    let (start, end) = almanac.spk_domain(sc_id).unwrap();
+    let epochs = TimeSeries::inclusive(start, end, (end - start) / 100.0).collect::<Vec<Epoch>>();
+
+    // The states sharing the frame data of the members match the individual transformations
+    for epoch in epochs.iter().step_by(10) {
+        let states = almanac
+            .constellation_states(&constellation, IAU_EARTH_FRAME, *epoch)
+            .unwrap();
+        for (id, state) in states {
+            let expected = almanac
+                .transform(Frame::from_ephem_j2000(id), IAU_EARTH_FRAME, *epoch, None)
+                .unwrap();
+            assert!((state.radius_km - expected.radius_km).norm() < 1e-6);
+            assert!((state.velocity_km_s - expected.velocity_km_s).norm() < 1e-9);
+        }
+    }
+
+    let madrid = Location {
+        latitude_deg: 40.427_222,
+        longitude_deg: 4.250_556,
+        height_km: 0.834_939,
+        frame: IAU_EARTH_FRAME.into(),
+        min_elevation_deg: Some(5.0),
+        ..Default::default()
+    };
+
+    let report = almanac
+        .constellation_visibility(&constellation, &madrid, &epochs)
+        .unwrap();
+    println!("{report}");
+    assert_eq!(report.members.len(), 2);
+    assert_eq!(report.num_in_view.len(), epochs.len());
+
+    // Each member is consistent with the visibility of that member alone
+    for (idx, epoch) in epochs.iter().enumerate() {
+        let visible = [sc_id, 301]
+            .iter()
+            .filter(|id| {
+                let state = almanac
+                    .transform(Frame::from_ephem_j2000(**id), IAU_EARTH_FRAME, *epoch, None)
+                    .unwrap();
+                almanac
+                    .is_visible_from_location(state, &madrid, None, None)
+                    .unwrap()
+            })
+            .count();
+        assert_eq!(report.num_in_view[idx], visible);
+    }
+    let (_, max_in_view) = report.min_max_in_view();
+    assert!(max_in_view <= 2);
+    assert!(report.percent_any_in_view() <= 100.0);
+
+    assert!(almanac
+        .constellation_visibility(
+            &Constellation::from_ephemerides("Empty", &[]),
+            &madrid,
+            &epochs
+        )
+        .is_err());
+}
+
+#[test]
+fn test_opportunities() {
+    use anise::analysis::opportunities::{
+        ImagingConstraints, ImagingSensor, OpportunityKind, OpportunityRanking, OpportunityRequest,
+    };
+    use anise::constants::celestial_objects::EARTH;
+    use anise::constants::frames::IAU_EARTH_FRAME;
+    use anise::instrument::{FovShape, Instrument};
+    use anise::math::rotation::DCM;
+    use anise::orientations::profiles::{AttitudeProfile, PointingLaw};
+    use anise::prelude::Frame;
+    use anise::structure::location::Location;
+    use hifitime::TimeSeries;
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/gmat-hermite.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let sc_id = -10000001;
+    let (start, end) = almanac.spk_domain(sc_id).unwrap();
+    let epochs = TimeSeries::inclusive(start, end, (end - start) / 200.0).collect::<Vec<Epoch>>();
+
+    let madrid = Location {
+        latitude_deg: 40.427_222,
+        longitude_deg: 4.250_556,
+        height_km: 0.834_939,
+        frame: IAU_EARTH_FRAME.into(),
+        min_elevation_deg: Some(5.0),
+        ..Default::default()
+    };
+
+    let communication = OpportunityRequest::Communication {
+        name: "Madrid".to_string(),
+        station: madrid.clone(),
+    };
+    let unconstrained = OpportunityRequest::Imaging {
+        name: "Madrid (any)".to_string(),
+        target: madrid.clone(),
+        constraints: ImagingConstraints::default(),
+    };
+    let constraints = ImagingConstraints {
+        min_sun_elevation_deg: Some(10.0),
+        max_off_nadir_deg: Some(45.0),
+        sunlit_spacecraft: true,
+    };
+    let constrained = OpportunityRequest::Imaging {
+        name: "Madrid (daylight)".to_string(),
+        target: madrid.clone(),
+        constraints,
+    };
+
+    let list = almanac
+        .opportunities(
+            sc_id,
+            None,
+            &[communication, unconstrained, constrained],
+            &epochs,
+            OpportunityRanking::Duration,
+        )
+        .unwrap();
+    println!("{list}");
+    assert_eq!(list.num_samples, epochs.len());
+
+    // Ranked by decreasing duration
+    for (idx, pair) in list.opportunities.windows(2).enumerate() {
+        assert_eq!(pair[0].rank, idx + 1);
+        assert!(pair[0].duration() >= pair[1].duration());
+    }
+
+    // An imaging request without constraints is the contact with the station
+    let contacts = list.of_request("Madrid");
+    let imaging = list.of_request("Madrid (any)");
+    assert_eq!(contacts.len(), imaging.len());
+    for (contact, image) in contacts.iter().zip(&imaging) {
+        assert_eq!((contact.start, contact.end), (image.start, image.end));
+        assert_eq!(contact.kind, OpportunityKind::Communication);
+        assert_eq!(image.kind, OpportunityKind::Imaging);
+        assert!(contact.max_elevation_deg >= 5.0);
+    }
+
+    // The contacts match the visibility of the spacecraft from the station
+    let visible = epochs
+        .iter()
+        .filter(|epoch| {
+            let state = almanac
+                .transform(
+                    Frame::from_ephem_j2000(sc_id),
+                    IAU_EARTH_FRAME,
+                    **epoch,
+                    None,
+                )
+                .unwrap();
+            almanac
+                .is_visible_from_location(state, &madrid, None, None)
+                .unwrap()
+        })
+        .count();
+    let in_contacts = epochs
+        .iter()
+        .filter(|epoch| {
+            contacts
+                .iter()
+                .any(|contact| **epoch >= contact.start && **epoch <= contact.end)
+        })
+        .count();
+    assert_eq!(visible, in_contacts);
+
+    // The constrained opportunities are within the unconstrained ones and meet their constraints
+    for image in list.of_request("Madrid (daylight)") {
+        assert!(imaging
+            .iter()
+            .any(|any| image.start >= any.start && image.end <= any.end));
+        assert!(image.min_off_nadir_deg <= 45.0);
+        let sun = almanac
+            .transform(SUN_J2000, IAU_EARTH_FRAME, image.start, None)
+            .unwrap();
+        let sun_aer = almanac
+            .azimuth_elevation_range_sez_from_location(sun, &madrid, None, None)
+            .unwrap();
+        assert!(sun_aer.elevation_deg >= 10.0);
+    }
+
+    // A wide nadir imager sees the target only when it is close to nadir
+    let sensor = ImagingSensor {
+        instrument: Instrument {
+            mounting: DCM::identity(-100, -100_001),
+            fov: FovShape::Circular {
+                half_angle_deg: 30.0,
+            },
+        },
+        attitude: AttitudeProfile {
+            spacecraft_id: sc_id,
+            body_id: -100,
+            law: PointingLaw::Nadir { center_id: EARTH },
+        },
+    };
+    let in_fov = almanac
+        .opportunities(
+            sc_id,
+            Some(&sensor),
+            &[OpportunityRequest::Imaging {
+                name: "Madrid (nadir)".to_string(),
+                target: madrid.clone(),
+                constraints: ImagingConstraints::default(),
+            }],
+            &epochs,
+            OpportunityRanking::MinOffNadir,
+        )
+        .unwrap();
+    println!("{in_fov}");
+    for image in &in_fov.opportunities {
+        assert!(image.min_off_nadir_deg <= 30.0);
+    }
+
+    // Invalid setups
+    let other = ImagingSensor {
+        attitude: AttitudeProfile {
+            spacecraft_id: -200,
+            ..sensor.attitude
+        },
+        ..sensor
+    };
+    assert!(almanac
+        .opportunities(
+            sc_id,
+            Some(&other),
+            &[OpportunityRequest::Communication {
+                name: "Madrid".to_string(),
+                station: madrid
+            }],
+            &epochs,
+            OpportunityRanking::Chronological
+        )
+        .is_err());
+    assert!(almanac
+        .opportunities(sc_id, None, &[], &epochs, OpportunityRanking::Chronological)
+        .is_err());
+}
+
+#[test]
+fn test_beta_angle_seasons() {
+    use anise::analysis::beta_angle::BetaAngleOrbit;
+    use anise::constants::celestial_objects::EARTH;
+    use anise::constants::usual_planetary_constants::EARTH_J2;
+    use hifitime::{TimeSeries, TimeUnits};
+
+    let almanac = Almanac::new("../data/de440s.bsp")
+        .unwrap()
+        .load("../data/gmat-hermite.bsp")
+        .unwrap()
+        .load("../data/pck08.pca")
+        .unwrap();
+
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let start = Epoch::from_gregorian_utc_at_midnight(2000, 1, 1);
+
+    // A polar orbit does not precess, so its beta angle follows the Sun over the seasons
+    let polar = Orbit::try_keplerian(7_778.0, 0.0, 90.0, 0.0, 0.0, 0.0, start, eme2k).unwrap();
+    let orbit = BetaAngleOrbit::MeanJ2 {
+        orbit: polar,
+        j2: EARTH_J2,
+    };
+    let epochs = TimeSeries::inclusive(start, start + 200.days(), 1.days()).collect::<Vec<Epoch>>();
+
+    let report = almanac.beta_angle_seasons(&orbit, &epochs).unwrap();
+    println!("{report}");
+    assert_eq!(report.history.len(), epochs.len());
+
+    // The orbit normal is the -Y axis of J2000, so the beta angle is bounded by the complement of the obliquity of the ecliptic
+    let (min_beta_deg, max_beta_deg) = report.min_max_beta_deg();
+    assert!(min_beta_deg > -66.6 && max_beta_deg < 66.6);
+
+    // Around the June solstice, the Sun is far from the orbital plane
+    assert!(!report.eclipse_free_seasons.is_empty());
+    let critical_deg = report.history[0].2;
+    assert!((critical_deg - (6_378.14f64 / 7_778.0).asin().to_degrees()).abs() < 1e-3);
+    for (start, end) in &report.eclipse_free_seasons {
+        for boundary in [start, end] {
+            if *boundary != report.start_epoch && *boundary != report.end_epoch {
+                let beta_deg = almanac.beta_angle_deg(&orbit, *boundary).unwrap();
+                assert!((beta_deg.abs() - critical_deg).abs() < 1e-3);
+            }
+        }
+    }
+    for (epoch, beta_deg, critical_deg) in &report.history {
+        let in_season = report
+            .eclipse_free_seasons
+            .iter()
+            .any(|(start, end)| epoch >= start && epoch <= end);
+        assert_eq!(in_season, beta_deg.abs() > *critical_deg, "{epoch}");
+    }
+    let total_eclipse = report
+        .eclipse_seasons()
+        .iter()
+        .fold(0.days(), |acc, (start, end)| acc + (*end - *start));
+    assert_eq!(total_eclipse + report.total_eclipse_free(), 200.days());
+
+    // The mean orbit matches the trajectory of an ephemeris at its epoch
+    let sc_id = -10000001;
+    let epoch = almanac.spk_domain(sc_id).unwrap().0 + 1.hours();
+    let state = almanac
+        .transform(
+            anise::prelude::Frame::from_ephem_j2000(sc_id),
+            eme2k,
+            epoch,
+            None,
+        )
+        .unwrap();
+    let from_ephem = almanac
+        .beta_angle_deg(
+            &BetaAngleOrbit::Ephemeris {
+                spacecraft_id: sc_id,
+                center_id: EARTH,
+            },
+            epoch,
+        )
+        .unwrap();
+    let from_mean = almanac
+        .beta_angle_deg(
+            &BetaAngleOrbit::MeanJ2 {
+                orbit: state,
+                j2: EARTH_J2,
+            },
+            epoch,
+        )
+        .unwrap();
+    assert!((from_ephem - from_mean).abs() < 1e-6);
+
+    assert!(almanac.beta_angle_seasons(&orbit, &epochs[..1]).is_err());
+}
@@ -855,3 +855,92 @@ fn gh_regression_340(almanac: Almanac) {
         assert!(orbit.at_epoch(epoch).is_ok(), "error on {epoch}");
     }
 }
+
+#[rstest]
+fn verif_orbit_at_epoch_universal(almanac: Almanac) {
+    let eme2k = almanac.frame_from_uid(EARTH_J2000).unwrap();
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 10);
+
+    // Hyperbolic
+    let hyperbolic = Orbit::new(
+        546507.344255845,
+        -527978.380486028,
+        531109.066836708,
+        -4.9220589268733,
+        5.36316523097915,
+        -5.22166308425181,
+        epoch,
+        eme2k,
+    );
+
+    // Parabolic: escape velocity at periapsis
+    let rp_km = 7_000.0;
+    let parabolic = Orbit::new(
+        rp_km,
+        0.0,
+        0.0,
+        0.0,
+        (2.0 * eme2k.mu_km3_s2().unwrap() / rp_km).sqrt(),
+        0.0,
+        epoch,
+        eme2k,
+    );
+
+    for (ono, orbit) in [hyperbolic, parabolic].iter().enumerate() {
+        let future = orbit.at_epoch(epoch + Unit::Day * 1).unwrap();
+        assert_eq!(future.epoch, epoch + Unit::Day * 1);
+        // Energy and angular momentum are conserved
+        f64_eq_tol!(
+            orbit.energy_km2_s2().unwrap(),
+            future.energy_km2_s2().unwrap(),
+            1e-9,
+            format!("#{ono}: energy changed")
+        );
+        f64_eq_tol!(
+            orbit.hmag().unwrap(),
+            future.hmag().unwrap(),
+            1e-6,
+            format!("#{ono}: angular momentum changed")
+        );
+        // Propagating backward returns the initial state
+        let back = future.at_epoch(epoch).unwrap();
+        f64_eq_tol!(
+            (back.radius_km - orbit.radius_km).norm(),
+            0.0,
+            1e-6,
+            format!("#{ono}: position mismatch after round trip")
+        );
+        f64_eq_tol!(
+            (back.velocity_km_s - orbit.velocity_km_s).norm(),
+            0.0,
+            1e-9,
+            format!("#{ono}: velocity mismatch after round trip")
+        );
+    }
+
+    // The inbound hyperbolic orbit must have moved closer to periapsis
+    let future = hyperbolic.at_epoch(epoch + Unit::Hour * 12).unwrap();
+    assert!(future.rmag_km() < hyperbolic.rmag_km());
+
+    // Propagation requires the gravitational parameter
+    let mut no_mu = hyperbolic;
+    no_mu.frame.mu_km3_s2 = None;
+    assert!(no_mu.at_epoch(epoch + Unit::Hour * 1).is_err());
+}
+
+#[test]
+fn radec() {
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2024, 1, 1);
+
+    let orbit = Orbit::from_position(1.0, 1.0, 2.0_f64.sqrt(), epoch, EARTH_J2000);
+    let (ra_deg, dec_deg, range_km) = orbit.radec();
+    f64_eq_tol!(ra_deg, 45.0, 1e-12, "right ascension");
+    f64_eq_tol!(dec_deg, 45.0, 1e-12, "declination");
+    f64_eq_tol!(range_km, 2.0, 1e-12, "range");
+
+    // Right ascension is between 0 and 360 degrees
+    let orbit = Orbit::from_position(0.0, -1.0, -1.0, epoch, EARTH_J2000);
+    let (ra_deg, dec_deg, _) = orbit.radec();
+    f64_eq_tol!(ra_deg, 270.0, 1e-12, "right ascension");
+    f64_eq_tol!(dec_deg, -45.0, 1e-12, "declination");
+}
@@ -347,6 +347,54 @@ fn spk_hermite_type13_verif() {
     );
 }
 
+#[test]
+fn spk_hermite_type13_windows() {
+    use anise::naif::daf::datatypes::HermiteWindow;
+
+    let ctx = Almanac::default()
+        .load("../data/de440s.bsp")
+        .and_then(|ctx| ctx.load("../data/gmat-hermite.bsp"))
+        .unwrap();
+
+    let my_sc_j2k = Frame::from_ephem_j2000(-10000001);
+    let (start, end) = ctx.spk_domain(-10000001).unwrap();
+    let epoch = Epoch::from_gregorian_hms(2000, 1, 1, 14, 0, 0, TimeScale::UTC);
+
+    let centered = ctx
+        .translate_geometric(my_sc_j2k, EARTH_J2000, epoch)
+        .unwrap();
+
+    for window in [
+        HermiteWindow::Centered,
+        HermiteWindow::Forward,
+        HermiteWindow::Backward,
+    ] {
+        let ctx = ctx.with_hermite_window(window);
+        assert_eq!(ctx.hermite_window, window);
+
+        // Queries at and near the boundaries are valid with any window
+        for edge in [start, start + 17.seconds(), end - 17.seconds(), end] {
+            ctx.translate_geometric(my_sc_j2k, EARTH_J2000, edge)
+                .unwrap_or_else(|e| panic!("{window:?} @ {edge}: {e}"));
+        }
+        assert!(ctx
+            .translate_geometric(my_sc_j2k, EARTH_J2000, end + 1.seconds())
+            .is_err());
+
+        let state = ctx
+            .translate_geometric(my_sc_j2k, EARTH_J2000, epoch)
+            .unwrap();
+        if window == HermiteWindow::Centered {
+            assert_eq!(state, centered);
+        } else {
+            assert!(
+                (state.radius_km - centered.radius_km).norm() < 1e-1,
+                "{window:?}: {state}"
+            );
+        }
+    }
+}
+
 #[test]
 fn multithread_query() {
     use core::str::FromStr;
@@ -621,6 +669,75 @@ fn de440s_translation_verif_aberrations() {
     }
 }
 
+#[test]
+fn de440s_aberration_diagnostics() {
+    let ctx = Almanac::new("../data/de440s.bsp").unwrap();
+
+    let epoch = Epoch::from_gregorian_utc_at_midnight(2002, 2, 7);
+
+    let (state, diag) = ctx
+        .translate_with_diagnostics(MOON_J2000, EARTH_MOON_BARYCENTER_J2000, epoch, None)
+        .unwrap();
+    assert_eq!(diag.iterations, 0);
+    assert_eq!(diag.stellar_correction_deg, 0.0);
+    assert_eq!(diag.light_time, state.light_time());
+
+    let mut residuals = Vec::new();
+
+    for name in ["LT", "LT+S", "CN", "CN+S", "XLT", "XLT+S", "XCN", "XCN+S"] {
+        let ab_corr = Aberration::new(name).unwrap();
+        let (state, diag) = ctx
+            .translate_with_diagnostics(MOON_J2000, EARTH_MOON_BARYCENTER_J2000, epoch, ab_corr)
+            .unwrap();
+        println!("{diag}");
+
+        // The diagnostics do not change the state.
+        assert_eq!(
+            state,
+            ctx.translate(MOON_J2000, EARTH_MOON_BARYCENTER_J2000, epoch, ab_corr)
+                .unwrap()
+        );
+
+        let ab_corr = ab_corr.unwrap();
+        assert_eq!(diag.ab_corr, Some(ab_corr));
+        assert_eq!(diag.iterations, if ab_corr.converged { 3 } else { 1 });
+        // About 1.3 seconds between the Moon and the EMB.
+        assert!((diag.light_time.to_seconds() - 1.3).abs() < 0.1);
+
+        if ab_corr.stellar {
+            // The stellar aberration is about 20 arcseconds from the orbital velocity of the Earth.
+            assert!(
+                diag.stellar_correction_deg > 1e-3 && diag.stellar_correction_deg < 1e-2,
+                "{diag}"
+            );
+        } else {
+            assert_eq!(diag.stellar_correction_deg, 0.0);
+            // Without stellar aberration, the light time is the norm of the corrected position.
+            assert!(
+                (diag.light_time - state.light_time()).abs() < 2.0 * Unit::Nanosecond,
+                "{diag}"
+            );
+        }
+
+        residuals.push((ab_corr.converged, diag.light_time_residual));
+    }
+
+    // The converged corrections have a smaller residual than the unconverged ones.
+    let max_converged = residuals
+        .iter()
+        .filter(|(converged, _)| *converged)
+        .map(|(_, residual)| *residual)
+        .max()
+        .unwrap();
+    let min_unconverged = residuals
+        .iter()
+        .filter(|(converged, _)| !*converged)
+        .map(|(_, residual)| *residual)
+        .min()
+        .unwrap();
+    assert!(max_converged < min_unconverged);
+}
+
 #[cfg(feature = "metaload")]
 #[test]
 fn type9_lagrange_query() {
@@ -638,6 +755,7 @@ fn type9_lagrange_query() {
     let lagrange_meta = MetaFile {
         uri: "http://public-data.nyxspace.com/anise/ci/env:LAGRANGE_BSP".to_string(),
         crc32: None,
+        ..Default::default()
     };
 
     let almanac = Almanac::default()
@@ -713,3 +831,76 @@ fn type9_lagrange_query() {
         (state.velocity_km_s - expected_vel_km_s).norm()
     );
 }
+
+#[test]
+fn export_spk_moon() {
+    use anise::naif::daf::DafDataType;
+    use core::str::FromStr;
+
+    let almanac = Almanac::new("../data/de440s.bsp").unwrap();
+
+    let start = Epoch::from_str("2020-02-07T00:00:00 ET").unwrap();
+    let end = start + Unit::Day * 10;
+
+    for (datatype, step, degree) in [
+        (DafDataType::Type2ChebyshevTriplet, Unit::Day * 1, 12),
+        (DafDataType::Type13HermiteUnequalStep, Unit::Hour * 1, 15),
+    ] {
+        let output_path = format!("../target/moon-export-{datatype:?}.bsp");
+        let spk = almanac
+            .export_spk(
+                MOON_J2000,
+                EARTH_J2000,
+                TimeSeries::inclusive(start, end, step),
+                datatype,
+                degree,
+                &output_path,
+            )
+            .unwrap();
+
+        let summary = spk.data_summaries().unwrap()[0];
+        assert_eq!(summary.data_type().unwrap(), datatype);
+        assert_eq!(summary.target_id, MOON_J2000.ephemeris_id);
+        assert_eq!(summary.center_id, EARTH_J2000.ephemeris_id);
+
+        // The exported file is traceable to the data it was exported from.
+        let digest = format!("0x{:08x}", almanac.context_digest());
+        assert!(spk.comments().unwrap().unwrap().contains(&digest));
+
+        // Reload the file from disk to ensure that it was properly written.
+        let exported = Almanac::new(&output_path).unwrap();
+
+        for hours in [0.0, 7.5, 50.25, 133.1, 239.9] {
+            let epoch = start + Unit::Hour * hours;
+            let expected = almanac
+                .translate_geometric(MOON_J2000, EARTH_J2000, epoch)
+                .unwrap();
+            let got = exported
+                .translate_geometric(MOON_J2000, EARTH_J2000, epoch)
+                .unwrap();
+
+            let pos_err_km = (got.radius_km - expected.radius_km).norm();
+            let vel_err_km_s = (got.velocity_km_s - expected.velocity_km_s).norm();
+            assert!(
+                pos_err_km < 1e-6,
+                "{datatype:?} position error at {epoch}: {pos_err_km:e} km"
+            );
+            assert!(
+                vel_err_km_s < 1e-9,
+                "{datatype:?} velocity error at {epoch}: {vel_err_km_s:e} km/s"
+            );
+        }
+    }
+
+    // Type 9 export is not supported.
+    assert!(almanac
+        .export_spk(
+            MOON_J2000,
+            EARTH_J2000,
+            TimeSeries::inclusive(start, end, Unit::Day * 1),
+            DafDataType::Type9LagrangeUnequalStep,
+            7,
+            "../target/moon-export-invalid.bsp",
+        )
+        .is_err());
+}
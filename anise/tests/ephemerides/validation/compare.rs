@@ -71,6 +71,8 @@ pub struct CompareEphem {
     pub num_queries_per_pair: usize,
     pub dry_run: bool,
     pub aberration: Option<Aberration>,
+    /// If set, the epochs are drawn at random in the domain of each pair with this seed, instead of being evenly spaced
+    pub random_seed: Option<u64>,
     pub writer: ArrowWriter<File>,
     pub batch_src_frame: Vec<String>,
     pub batch_dst_frame: Vec<String>,
@@ -113,6 +115,7 @@ impl CompareEphem {
             aberration,
             writer,
             dry_run: false,
+            random_seed: None,
             batch_src_frame: Vec::new(),
             batch_dst_frame: Vec::new(),
             batch_component: Vec::new(),
@@ -123,6 +126,42 @@ impl CompareEphem {
         }
     }
 
+    /// Draws the epochs of each pair at random with the provided seed instead of evenly spacing them.
+    pub fn with_random_epochs(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// Returns the epochs of the queries of a pair, either evenly spaced or drawn at random in its domain.
+    fn epochs(&self, start_epoch: Epoch, end_epoch: Epoch, bound_offset: Duration) -> Vec<Epoch> {
+        let start_epoch = start_epoch + bound_offset;
+        let end_epoch = end_epoch - bound_offset;
+
+        match self.random_seed {
+            Some(seed) => {
+                // xorshift64*: deterministic and good enough to spread the queries over the domain
+                let mut state = seed.max(1);
+                (0..self.num_queries_per_pair)
+                    .map(|_| {
+                        state ^= state >> 12;
+                        state ^= state << 25;
+                        state ^= state >> 27;
+                        let draw = (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64
+                            / (1_u64 << 53) as f64;
+                        start_epoch + (end_epoch - start_epoch) * draw
+                    })
+                    .collect()
+            }
+            None => {
+                let time_step = ((end_epoch - start_epoch).to_seconds()
+                    / (self.num_queries_per_pair as f64))
+                    .seconds();
+
+                TimeSeries::exclusive(start_epoch, end_epoch - time_step, time_step).collect()
+            }
+        }
+    }
+
     /// Executes this ephemeris validation and return the number of querying errors
     #[must_use]
     pub fn run(mut self) -> usize {
@@ -224,23 +263,18 @@ impl CompareEphem {
         let mut i: usize = 0;
         let mut err_count: usize = 0;
         for (from_frame, to_frame, start_epoch, end_epoch) in pairs.values() {
-            let time_step = ((*end_epoch - *start_epoch).to_seconds()
-                / (self.num_queries_per_pair as f64))
-                .seconds();
-
-            let time_it = TimeSeries::exclusive(
-                *start_epoch + bound_offset,
-                *end_epoch - time_step - bound_offset,
-                time_step,
-            );
+            let epochs = self.epochs(*start_epoch, *end_epoch, bound_offset);
 
-            info!("{time_it} for {from_frame} -> {to_frame} ");
+            info!(
+                "{} queries from {start_epoch} to {end_epoch} for {from_frame} -> {to_frame} ",
+                epochs.len()
+            );
 
             if self.dry_run {
                 continue;
             }
 
-            for epoch in time_it {
+            for epoch in epochs {
                 let data = match ctx.translate(*from_frame, *to_frame, epoch, self.aberration) {
                     Ok(state) => {
                         // Find the SPICE names
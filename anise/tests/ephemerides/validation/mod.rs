@@ -13,5 +13,7 @@ mod type03_chebyshev_jpl_de;
 mod type09_lagrange;
 mod type13_hermite;
 
+mod random_sweep;
+
 mod compare;
 mod validate;
@@ -0,0 +1,73 @@
+/*
+ * ANISE Toolkit
+ * Copyright (C) 2021-onward Christopher Rabotin <christopher.rabotin@gmail.com> et al. (cf. AUTHORS.md)
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ *
+ * Documentation: https://nyxspace.com/
+ */
+
+use super::{compare::*, validate::Validation};
+
+// Tolerances of the random sweeps, in km and km/s: geometric queries of Chebyshev and Hermite data match SPICE to machine precision.
+const MAX_Q75_ERR: f64 = 1e-9;
+const MAX_Q99_ERR: f64 = 1e-8;
+const MAX_ABS_ERR: f64 = 1e-7;
+
+#[ignore = "Requires Rust SPICE -- must be executed serially"]
+#[test]
+fn validate_random_sweep_de440s() {
+    let file_name = "spk-random-sweep-de440s".to_string();
+    let comparator = CompareEphem::new(
+        vec!["../data/de440s.bsp".to_string()],
+        file_name.clone(),
+        1_000,
+        None,
+    )
+    .with_random_epochs(0x414E495345);
+
+    let err_count = comparator.run();
+
+    assert_eq!(err_count, 0, "None of the queries should fail!");
+
+    let validator = Validation {
+        file_name,
+        max_q75_err: MAX_Q75_ERR,
+        max_q99_err: MAX_Q99_ERR,
+        max_abs_err: MAX_ABS_ERR,
+    };
+
+    validator.report_html();
+    validator.validate();
+}
+
+#[ignore = "Requires Rust SPICE -- must be executed serially"]
+#[test]
+fn validate_random_sweep_hermite() {
+    let file_name = "spk-random-sweep-hermite".to_string();
+    let comparator = CompareEphem::new(
+        vec![
+            "../data/de440s.bsp".to_string(),
+            "../data/gmat-hermite.bsp".to_string(),
+        ],
+        file_name.clone(),
+        1_000,
+        None,
+    )
+    .with_random_epochs(2_024);
+
+    let err_count = comparator.run();
+
+    assert_eq!(err_count, 0, "None of the queries should fail!");
+
+    let validator = Validation {
+        file_name,
+        max_q75_err: MAX_Q75_ERR,
+        max_q99_err: MAX_Q99_ERR,
+        max_abs_err: MAX_ABS_ERR,
+    };
+
+    validator.report_html();
+    validator.validate();
+}
@@ -82,3 +82,55 @@ fn validate_jplde_de440s_aberration_lt() {
 
     validator.validate();
 }
+
+#[ignore = "Requires Rust SPICE -- must be executed serially"]
+#[test]
+fn validate_jplde_de440s_aberration_cn_s() {
+    let output_file_name = "spk-type2-validation-de440s-cn-s-aberration".to_string();
+    let comparator = CompareEphem::new(
+        vec!["../data/de440s.bsp".to_string()],
+        output_file_name.clone(),
+        1_000,
+        Aberration::CN_S,
+    );
+
+    let err_count = comparator.run();
+
+    assert!(err_count <= 10, "A few are expected to fail");
+
+    let validator = Validation {
+        file_name: output_file_name,
+        max_q75_err: 1e-3,
+        max_q99_err: 5e-3,
+        max_abs_err: 0.09,
+        ..Default::default()
+    };
+
+    validator.validate();
+}
+
+#[ignore = "Requires Rust SPICE -- must be executed serially"]
+#[test]
+fn validate_jplde_de440s_aberration_xcn_s() {
+    let output_file_name = "spk-type2-validation-de440s-xcn-s-aberration".to_string();
+    let comparator = CompareEphem::new(
+        vec!["../data/de440s.bsp".to_string()],
+        output_file_name.clone(),
+        1_000,
+        Aberration::XCN_S,
+    );
+
+    let err_count = comparator.run();
+
+    assert!(err_count <= 10, "A few are expected to fail");
+
+    let validator = Validation {
+        file_name: output_file_name,
+        max_q75_err: 1e-3,
+        max_q99_err: 5e-3,
+        max_abs_err: 0.09,
+        ..Default::default()
+    };
+
+    validator.validate();
+}
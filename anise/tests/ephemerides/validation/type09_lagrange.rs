@@ -23,6 +23,7 @@ fn validate_lagrange_type9_with_varying_segment_sizes() {
     let mut lagrange_meta = MetaFile {
         uri: "http://public-data.nyxspace.com/anise/ci/env:LAGRANGE_BSP".to_string(),
         crc32: None,
+        ..Default::default()
     };
     lagrange_meta.process(true).unwrap();
 
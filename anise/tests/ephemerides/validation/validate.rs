@@ -8,6 +8,9 @@
  * Documentation: https://nyxspace.com/
  */
 
+use std::fs::File;
+use std::io::Write;
+
 use polars::{lazy::dsl::Expr, prelude::*};
 
 #[derive(Debug, Default)]
@@ -97,4 +100,82 @@ impl Validation {
             self.max_abs_err
         );
     }
+
+    /// Writes an HTML report of the residuals next to the Parquet file, with the statistics of the absolute errors of
+    /// each pair of frames and component, flagging those which exceed the tolerances of this validation.
+    pub fn report_html(&self) {
+        let df = LazyFrame::scan_parquet(
+            format!("../target/{}.parquet", self.file_name),
+            Default::default(),
+        )
+        .unwrap();
+
+        let stats = df
+            .group_by([
+                col("source frame"),
+                col("destination frame"),
+                col("component"),
+            ])
+            .agg([
+                col("Absolute difference").count().alias("queries"),
+                col("Absolute difference").median().alias("median abs err"),
+                col("Absolute difference")
+                    .quantile(
+                        Expr::Literal(polars::prelude::LiteralValue::Float64(0.75)),
+                        QuantileInterpolOptions::Higher,
+                    )
+                    .alias("q75 abs err"),
+                col("Absolute difference")
+                    .quantile(
+                        Expr::Literal(polars::prelude::LiteralValue::Float64(0.99)),
+                        QuantileInterpolOptions::Higher,
+                    )
+                    .alias("q99 abs err"),
+                col("Absolute difference").max().alias("max abs err"),
+            ])
+            .sort(
+                ["source frame", "destination frame", "component"],
+                Default::default(),
+            )
+            .collect()
+            .unwrap();
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{0}</title>\n\
+            <style>table {{ border-collapse: collapse; }} td, th {{ border: 1px solid #ccc; padding: 4px 8px; }} \
+            .fail {{ background: #f8d7da; }}</style></head>\n<body>\n<h1>{0}</h1>\n\
+            <p>Tolerances: q75 &le; {1:e}, q99 &le; {2:e}, max &le; {3:e}</p>\n<table>\n<tr>",
+            self.file_name, self.max_q75_err, self.max_q99_err, self.max_abs_err
+        );
+        for name in stats.get_column_names() {
+            html.push_str(&format!("<th>{name}</th>"));
+        }
+        html.push_str("</tr>\n");
+
+        for row_no in 0..stats.height() {
+            let row = stats.get_row(row_no).unwrap().0;
+            let err = |idx: usize| match row[idx] {
+                AnyValue::Float64(val) => val,
+                _ => f64::NAN,
+            };
+            // Errored queries are infinite, so NaN and infinite errors are failures too
+            let fails = !(err(5) <= self.max_q75_err
+                && err(6) <= self.max_q99_err
+                && err(7) <= self.max_abs_err);
+
+            html.push_str(if fails { "<tr class=\"fail\">" } else { "<tr>" });
+            for value in &row {
+                match value {
+                    AnyValue::String(val) => html.push_str(&format!("<td>{val}</td>")),
+                    AnyValue::Float64(val) => html.push_str(&format!("<td>{val:.3e}</td>")),
+                    val => html.push_str(&format!("<td>{val}</td>")),
+                }
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+
+        let mut file = File::create(format!("../target/{}.html", self.file_name)).unwrap();
+        file.write_all(html.as_bytes()).unwrap();
+    }
 }
@@ -13,7 +13,10 @@ use std::mem::size_of_val;
 use anise::{
     file2heap,
     naif::{
-        daf::{datatypes::Type2ChebyshevSet, NAIFDataSet, DAF},
+        daf::{
+            datatypes::{Type2ChebyshevSet, Type3ChebyshevSet},
+            NAIFDataSet, DAF,
+        },
         pck::BPCSummaryRecord,
         spk::summary::SPKSummaryRecord,
         Endian,
@@ -202,6 +205,49 @@ fn test_spk_mut_summary_name() {
     );
 }
 
+#[test]
+fn test_daf_persist_roundtrip() {
+    let _ = pretty_env_logger::try_init();
+
+    // Persisting an unmodified DAF preserves its layout: file record, comments, summaries, names, and data.
+    for (path, output_path) in [
+        ("../data/de440s.bsp", "../target/persisted-de440s.bsp"),
+        (
+            "../data/gmat-hermite.bsp",
+            "../target/persisted-gmat-hermite.bsp",
+        ),
+    ] {
+        let spk = SPK::load(path).unwrap();
+        spk.to_mutable().persist(output_path).unwrap();
+        let reloaded = SPK::load(output_path).unwrap();
+
+        assert_eq!(reloaded.bytes.len(), spk.bytes.len(), "{path}");
+        assert_eq!(
+            reloaded.file_record().unwrap(),
+            spk.file_record().unwrap(),
+            "{path}"
+        );
+        assert_eq!(reloaded.comments().unwrap(), spk.comments().unwrap());
+        let summaries = spk.data_summaries().unwrap();
+        assert_eq!(reloaded.data_summaries().unwrap(), summaries, "{path}");
+        for (idx, summary) in summaries.iter().enumerate() {
+            if summary.is_empty() {
+                continue;
+            }
+            assert_eq!(
+                reloaded.nth_name(idx).unwrap(),
+                spk.nth_name(idx).unwrap(),
+                "{path}"
+            );
+            assert_eq!(
+                reloaded.nth_data_raw(idx).unwrap(),
+                spk.nth_data_raw(idx).unwrap(),
+                "{path} #{idx}"
+            );
+        }
+    }
+}
+
 #[test]
 fn test_spk_truncate_cheby() {
     let _ = pretty_env_logger::try_init();
@@ -262,3 +308,173 @@ fn test_spk_truncate_cheby() {
         "summary 301 not removed"
     );
 }
+
+#[test]
+fn test_spk_truncate_mid_record_cheby2() {
+    let _ = pretty_env_logger::try_init();
+
+    let my_spk = SPK::load("../data/de440s.bsp").unwrap();
+    let (summary, idx) = my_spk.summary_from_id(301).unwrap();
+    let summary = *summary;
+    let segment = my_spk.nth_data::<Type2ChebyshevSet>(idx).unwrap();
+
+    // Neither bound is on a record boundary
+    let new_start = summary.start_epoch() + Unit::Day * 16 + Unit::Hour * 5;
+    let new_end = new_start + Unit::Day * 30;
+
+    let truncated = segment
+        .truncate(&summary, Some(new_start), Some(new_end))
+        .unwrap();
+    assert!(truncated.init_epoch <= new_start);
+    assert!(new_start - truncated.init_epoch < truncated.interval_length);
+    assert_eq!(
+        truncated.record_data.len(),
+        truncated.num_records * truncated.rsize
+    );
+    let last_record_end =
+        truncated.init_epoch + truncated.interval_length * (truncated.num_records as i64);
+    assert!(last_record_end >= new_end);
+    assert!(last_record_end - new_end < truncated.interval_length);
+
+    let mut my_spk_trunc = my_spk.to_mutable();
+    my_spk_trunc
+        .set_nth_data(idx, truncated, new_start, new_end)
+        .unwrap();
+
+    let output_path = "../target/truncated-mid-record-de440s.bsp";
+    my_spk_trunc.persist(output_path).unwrap();
+
+    // The records of the truncated segment are found from its initial epoch, not from the start of its summary
+    let reloaded = SPK::load(output_path).unwrap();
+    let reloaded_summary = reloaded.data_summaries().unwrap()[idx];
+    assert_eq!(reloaded_summary.start_epoch(), new_start);
+    assert_eq!(reloaded_summary.end_epoch(), new_end);
+    let reloaded_segment = reloaded.nth_data::<Type2ChebyshevSet>(idx).unwrap();
+    for epoch in [
+        new_start,
+        new_start + Unit::Day * 3 + Unit::Hour * 22,
+        new_start + Unit::Day * 11 + Unit::Minute * 7,
+        new_end,
+    ] {
+        let (pos_km, vel_km_s) = segment.evaluate(epoch, &summary).unwrap();
+        let (other_pos_km, other_vel_km_s) =
+            reloaded_segment.evaluate(epoch, &reloaded_summary).unwrap();
+        assert_eq!(pos_km, other_pos_km, "{epoch}");
+        assert_eq!(vel_km_s, other_vel_km_s, "{epoch}");
+    }
+
+    // Truncating everything is an error rather than a panic
+    assert!(segment
+        .truncate(&summary, Some(new_end), Some(new_start))
+        .is_err());
+}
+
+#[test]
+fn test_spk_reduce_cheby() {
+    let _ = pretty_env_logger::try_init();
+
+    let my_spk = SPK::load("../data/de440s.bsp").unwrap();
+    let (summary, idx) = my_spk.summary_from_id(301).unwrap();
+    let summary = *summary;
+    let segment = my_spk.nth_data::<Type2ChebyshevSet>(idx).unwrap();
+
+    // Twice longer records within one meter
+    let refit = segment.reduce(2, 1e-3).unwrap();
+    println!("{refit}");
+    assert!(refit.max_error <= 1e-3);
+    assert_eq!(refit.interval_length, segment.interval_length * 2);
+    assert!(refit.data.len() < segment.record_data.len());
+
+    let reduced = Type2ChebyshevSet::from_f64_slice(&refit.data).unwrap();
+    assert_eq!(reduced.degree(), refit.degree);
+
+    let mut my_spk_reduced = my_spk.to_mutable();
+    my_spk_reduced
+        .set_nth_data(idx, reduced, summary.start_epoch(), summary.end_epoch())
+        .unwrap();
+
+    let output_path = "../target/reduced-de440s.bsp";
+    my_spk_reduced.persist(output_path).unwrap();
+
+    let reloaded = SPK::load(output_path).unwrap();
+    let reloaded_summary = reloaded.data_summaries().unwrap()[idx];
+    let reloaded_segment = reloaded.nth_data::<Type2ChebyshevSet>(idx).unwrap();
+    let step = (summary.end_epoch() - summary.start_epoch()) / 997;
+    for i in 0..=997_i64 {
+        let epoch = summary.start_epoch() + step * i;
+        let (pos_km, _) = segment.evaluate(epoch, &summary).unwrap();
+        let (reduced_pos_km, _) = reloaded_segment.evaluate(epoch, &reloaded_summary).unwrap();
+        // The maximum error of the re-fit is sampled, so allow for some margin between its samples
+        assert!((pos_km - reduced_pos_km).norm() <= 2e-3, "{epoch}");
+    }
+
+    // The degree cannot exceed that of a data set of the original size
+    assert!(segment.reduce(2, 1e-12).is_err());
+}
+
+#[test]
+fn test_spk_truncate_split_refit_cheby3() {
+    let _ = pretty_env_logger::try_init();
+
+    let my_spk = SPK::load("../data/de440_type3.bsp").unwrap();
+    let (summary, idx) = my_spk.summary_from_id(301).unwrap();
+    let summary = *summary;
+    let segment = my_spk.nth_data::<Type3ChebyshevSet>(idx).unwrap();
+
+    let new_start = summary.start_epoch() + Unit::Day * 16 + Unit::Hour * 5;
+    let new_end = new_start + Unit::Day * 30;
+    let epochs = [
+        new_start,
+        new_start + Unit::Day * 11 + Unit::Minute * 7,
+        new_end,
+    ];
+
+    let assert_same = |other: &Type3ChebyshevSet, other_summary: &SPKSummaryRecord| {
+        for epoch in epochs {
+            let (pos_km, vel_km_s) = segment.evaluate(epoch, &summary).unwrap();
+            let (other_pos_km, other_vel_km_s) = other.evaluate(epoch, other_summary).unwrap();
+            assert!((pos_km - other_pos_km).norm() < 1e-6, "{epoch}");
+            assert!((vel_km_s - other_vel_km_s).norm() < 1e-9, "{epoch}");
+        }
+    };
+
+    // Splitting the records and increasing their degree is lossless
+    let split_data = segment.split_records(3).unwrap();
+    let split = Type3ChebyshevSet::from_f64_slice(&split_data).unwrap();
+    assert_eq!(split.num_records, 3 * segment.num_records);
+    assert_eq!(split.degree(), segment.degree());
+    assert_same(&split, &summary);
+
+    let refit_data = segment.refit(segment.degree() + 2).unwrap();
+    let refit = Type3ChebyshevSet::from_f64_slice(&refit_data).unwrap();
+    assert_eq!(refit.degree(), segment.degree() + 2);
+    assert_same(&refit, &summary);
+
+    assert!(segment.split_records(0).is_err());
+
+    // Truncating the split records trims closer to the requested epochs
+    let truncated = split
+        .truncate(&summary, Some(new_start), Some(new_end))
+        .unwrap();
+    assert!(truncated.init_epoch <= new_start);
+    assert!(new_start - truncated.init_epoch < truncated.interval_length);
+    let last_record_end =
+        truncated.init_epoch + truncated.interval_length * (truncated.num_records as i64);
+    assert!(last_record_end >= new_end);
+    assert!(last_record_end - new_end < truncated.interval_length);
+
+    let mut my_spk_trunc = my_spk.to_mutable();
+    my_spk_trunc
+        .set_nth_data(idx, truncated, new_start, new_end)
+        .unwrap();
+
+    let output_path = "../target/truncated-de440-type3.bsp";
+    my_spk_trunc.persist(output_path).unwrap();
+
+    let reloaded = SPK::load(output_path).unwrap();
+    let reloaded_summary = reloaded.data_summaries().unwrap()[idx];
+    assert_eq!(reloaded_summary.start_epoch(), new_start);
+    assert_eq!(reloaded_summary.end_epoch(), new_end);
+    let reloaded_segment = reloaded.nth_data::<Type3ChebyshevSet>(idx).unwrap();
+    assert_same(&reloaded_segment, &reloaded_summary);
+}
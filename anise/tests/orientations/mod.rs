@@ -506,3 +506,120 @@ fn regression_test_issue_357_test_moon_me_j2k() {
     let (lat, long, alt) = orbit_moon_me.latlongalt().unwrap();
     dbg!(lat, long, alt);
 }
+
+#[test]
+fn test_itrf93_angular_velocity() {
+    use core::str::FromStr;
+    let almanac = Almanac::new("../data/earth_latest_high_prec.bpc").unwrap();
+
+    let epoch = Epoch::from_str("2019-03-01T04:02:51.0 ET").unwrap();
+
+    // The BPC provides the time derivative of the rotation.
+    let omega_rad_s = almanac
+        .angular_velocity(EME2000, EARTH_ITRF93, epoch)
+        .unwrap();
+
+    // Earth rotation rate, mostly about the ITRF93 Z axis.
+    let earth_rate_rad_s = 7.292_115e-5;
+    assert!((omega_rad_s.norm() - earth_rate_rad_s).abs() < 1e-9);
+    assert!(omega_rad_s.z / omega_rad_s.norm() > 1.0 - 1e-6);
+
+    // The reverse rotation spins the other way, expressed in the J2000 frame.
+    let dcm = almanac.rotate(EME2000, EARTH_ITRF93, epoch).unwrap();
+    let omega_rev_rad_s = almanac
+        .angular_velocity(EARTH_ITRF93, EME2000, epoch)
+        .unwrap();
+    assert!((omega_rev_rad_s + dcm.transpose() * omega_rad_s).norm() < 1e-15);
+}
+
+#[test]
+fn test_bpc_builder_itrf93() {
+    use core::str::FromStr;
+    let almanac = Almanac::new("../data/earth_latest_high_prec.bpc").unwrap();
+
+    let start = Epoch::from_str("2019-03-01T00:00:00 ET").unwrap();
+    let end = start + Unit::Day * 2;
+
+    let mut builder = BPC::builder("ITRF93 EXPORT").with_comments("Exported by ANISE");
+    builder
+        .add_type2_segment(
+            "EARTH ITRF93",
+            ITRF93,
+            J2000,
+            start,
+            end,
+            Unit::Hour * 6,
+            13,
+            |epoch| almanac.rotate(EME2000, EARTH_ITRF93, epoch),
+        )
+        .unwrap();
+
+    let output_path = "../target/itrf93-export.bpc";
+    builder.build().unwrap().persist(output_path).unwrap();
+
+    let exported = Almanac::new(output_path).unwrap();
+
+    for hours in [0.0, 5.5, 17.0, 31.2, 47.9] {
+        let epoch = start + Unit::Hour * hours;
+        let expected = almanac.rotate(EME2000, EARTH_ITRF93, epoch).unwrap();
+        let got = exported.rotate(EME2000, EARTH_ITRF93, epoch).unwrap();
+
+        assert!(
+            (got.rot_mat - expected.rot_mat).norm() < 1e-9,
+            "rotation error at {epoch}: {:e}",
+            (got.rot_mat - expected.rot_mat).norm()
+        );
+        assert!(
+            (got.rot_mat_dt.unwrap() - expected.rot_mat_dt.unwrap()).norm() < 1e-12,
+            "rotation rate error at {epoch}"
+        );
+    }
+}
+
+#[test]
+fn test_itrf93_dcm_cache() {
+    use core::str::FromStr;
+    let almanac = Almanac::new("../data/earth_latest_high_prec.bpc").unwrap();
+    let cached = almanac.with_dcm_cache(16, Unit::Millisecond * 1);
+
+    let start = Epoch::from_str("2019-03-01T04:02:51.0 ET").unwrap();
+    let num_epochs = 100_i64;
+    let num_stations = 10_i64;
+
+    for minutes in 0..num_epochs {
+        let epoch = start + Unit::Minute * minutes + Unit::Microsecond * 123;
+        let uncached_dcm = almanac.rotate(EARTH_ITRF93, EME2000, epoch).unwrap();
+        for station in 0..num_stations {
+            // Ground stations of an AER report all rotate from ITRF93 at the same epoch
+            let dcm = cached.rotate(EARTH_ITRF93, EME2000, epoch).unwrap();
+            assert_eq!((dcm.from, dcm.to), (ITRF93, J2000));
+            // Earth rotation over at most half of the quantum
+            assert!((dcm.rot_mat - uncached_dcm.rot_mat).norm() < 1e-7);
+
+            let longitude_rad = (36.0 * station as f64).to_radians();
+            let surface = Orbit::from_position(
+                6378.137 * longitude_rad.cos(),
+                6378.137 * longitude_rad.sin(),
+                0.0,
+                epoch,
+                EARTH_ITRF93,
+            );
+            let expected = almanac.rotate_to(surface, EME2000).unwrap();
+            let state = cached.rotate_to(surface, EME2000).unwrap();
+            assert!((state.radius_km - expected.radius_km).norm() < 1e-3);
+        }
+    }
+
+    let cache = cached.dcm_cache.as_ref().unwrap();
+    println!("{cache:?}");
+    // Twice per station: once for the DCM and once for the state
+    assert_eq!(cache.misses(), num_epochs as u64);
+    assert_eq!(cache.hits(), (num_epochs * (2 * num_stations - 1)) as u64);
+    assert_eq!(cache.len(), 16);
+
+    // Clones start with an empty cache, and identity rotations are not cached
+    let clone = cached.clone();
+    clone.rotate(EME2000, EME2000, start).unwrap();
+    assert!(clone.dcm_cache.as_ref().unwrap().is_empty());
+    assert!(cached.without_dcm_cache().dcm_cache.is_none());
+}